@@ -0,0 +1,27 @@
+//! Scanner benchmarks over synthetic scenes: `cargo bench --features noop --bench scanner`
+//! (`noop` drops the napi registration, which only links inside Node).
+//! The same cases are exposed at runtime through `self_benchmark()`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+use unity_file_tools::benchmark::{run_case, synthetic_scene, CASES};
+
+const SCENE_SIZES: &[u32] = &[1_000, 10_000, 100_000];
+
+fn scanner_benchmarks(c: &mut Criterion) {
+    let scenes: Vec<(u32, String)> = SCENE_SIZES.iter().map(|&n| (n, synthetic_scene(n))).collect();
+    for case in CASES {
+        let mut group = c.benchmark_group(*case);
+        group.sample_size(10);
+        for (objects, content) in &scenes {
+            group.throughput(Throughput::Bytes(content.len() as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(objects), content, |b, content| {
+                b.iter(|| run_case(case, black_box(content)))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, scanner_benchmarks);
+criterion_main!(benches);
@@ -3,6 +3,59 @@
 
 /* auto-generated by NAPI-RS */
 
+/**
+ * Time every case on synthetic scenes of the given sizes (default 1k and 10k objects,
+ * 5 iterations each) and report them with the build/environment details.
+ */
+export declare function selfBenchmark(objectCounts?: Array<number> | undefined | null, iterations?: number | undefined | null): BenchmarkReport
+/** Total packed size of one source asset across the build output. */
+export interface BuildAssetSize {
+  path: string
+  guid?: string
+  /** Packed bytes, summed over every output file containing the asset */
+  size: number
+  /** Output files (bundles, sharedassets) containing the asset */
+  files: number
+}
+/** A source asset packed into more than one output file. */
+export interface BuildDuplicateAsset {
+  path: string
+  files: Array<string>
+  size: number
+  /** Bytes beyond the largest single copy */
+  wasted: number
+}
+export interface BuildStepDuration {
+  name: string
+  /** Nesting depth; nested steps are part of their parent's duration */
+  depth: number
+  durationMs: number
+}
+export interface BuildReportAnalysis {
+  file: string
+  /** "binary" or "json" */
+  format: string
+  platform?: string
+  /** "Succeeded", "Failed", "Cancelled" or "Unknown" */
+  result?: string
+  totalSize?: number
+  totalTimeMs?: number
+  totalErrors?: number
+  totalWarnings?: number
+  /** Distinct source assets in the packed output */
+  assetCount: number
+  /** Largest assets first */
+  assets: Array<BuildAssetSize>
+  /** Most wasted bytes first */
+  duplicates: Array<BuildDuplicateAsset>
+  /** Steps in build order */
+  steps: Array<BuildStepDuration>
+}
+/**
+ * Summarize a Unity build report: a `.buildreport` file, a JSON export, or a project root
+ * (reads `Library/LastBuild.buildreport`). `limit` caps assets and duplicates (default 50).
+ */
+export declare function analyzeBuildReport(path: string, limit?: number | undefined | null): any
 /** Basic GameObject information */
 export interface GameObject {
   name: string
@@ -18,7 +71,19 @@ export interface Component {
   scriptPath?: string | undefined
   scriptGuid?: string | undefined
   scriptName?: string | undefined
+  /** Parsed `m_EditorClassIdentifier` ("Assembly::Namespace.Class") */
+  editorClassIdentifier?: EditorClassIdentifier | undefined
+  /** `m_Enabled` of Behaviours, Renderers and Colliders (None for classes without it) */
+  enabled?: boolean | undefined
   properties?: Record<string, any> | undefined
+  /** Field renames across Unity versions the property filters matched through */
+  aliasesApplied?: string[] | undefined
+}
+/** Script identity from `m_EditorClassIdentifier` */
+export interface EditorClassIdentifier {
+  assembly?: string | undefined
+  namespace?: string | undefined
+  className: string
 }
 /** GameObject with detailed component information */
 export interface GameObjectDetail {
@@ -30,7 +95,40 @@ export interface GameObjectDetail {
   depth?: number | undefined
   components: Array<Component>
   children?: string[] | undefined
+  /** Children in sibling order, resolved to their GameObjects (or nested prefab instances) */
+  orderedChildren?: ChildEntry[] | undefined
   parentTransformId?: string | undefined
+  /** Undefined tag / unnamed or out-of-range layer (when tag validation is enabled) */
+  warnings?: string[] | undefined
+  provenance?: ObjectProvenance | undefined
+}
+/** Where a GameObject comes from */
+export interface ObjectProvenance {
+  /**
+   * "scene" (defined directly in this scene or prefab), "prefab_instance" (part of a prefab
+   * instance) or "added_to_prefab_instance" (added in this file under a prefab instance)
+   */
+  kind: string
+  prefabInstanceId?: string | undefined
+  sourceGuid?: string | undefined
+  sourcePrefab?: string | undefined
+  /** Prefabs the source prefab is a variant of, nearest base first */
+  variantChain: Array<string>
+  /** "project", "package", "package_sample" or "unknown" (source prefab not resolved) */
+  origin: string
+  /** Package name (display name for imported samples) */
+  package?: string | undefined
+}
+/** A child of a GameObject, in m_Children (sibling) order */
+export interface ChildEntry {
+  /** Position among siblings (Transform.GetSiblingIndex) */
+  siblingIndex: number
+  transformId: string
+  /** Child GameObject name, or the nested prefab instance's name override (empty when unresolved) */
+  name: string
+  gameObjectId?: string | undefined
+  /** Set when the child Transform is the stripped root of a nested prefab instance */
+  prefabInstanceId?: string | undefined
 }
 /** PrefabInstance information */
 export interface PrefabInstanceInfo {
@@ -39,6 +137,42 @@ export interface PrefabInstanceInfo {
   sourceGuid: string
   sourcePrefab?: string | undefined
   modificationsCount: number
+  removedComponents?: RemovedPrefabObject[] | undefined
+  removedGameObjects?: RemovedPrefabObject[] | undefined
+  /** Components added to the instance's objects in this file */
+  addedComponents?: AddedPrefabComponent[] | undefined
+  /** Transform the instance is parented to (m_Modification.m_TransformParent); undefined at the root */
+  parentTransformId?: string | undefined
+  /** GameObject owning the parent Transform */
+  parentGameObjectId?: string | undefined
+  /** PrefabInstance owning the parent Transform, when it is a stripped object of another instance */
+  parentPrefabInstanceId?: string | undefined
+}
+/**
+ * A source-prefab object stripped by a PrefabInstance (m_RemovedComponents / m_RemovedGameObjects).
+ * `class_id`, `type_name` and `name` are filled in when the source prefab can be loaded;
+ * for components, `name` is the owning GameObject's name.
+ */
+export interface RemovedPrefabObject {
+  fileId: string
+  guid?: string | undefined
+  classId?: number | undefined
+  typeName?: string | undefined
+  name?: string | undefined
+}
+/**
+ * A component added in this file to a GameObject of a prefab instance. It hangs off the
+ * stripped placeholder of the source GameObject it extends; `source_object_name` is filled in
+ * when the source prefab can be loaded.
+ */
+export interface AddedPrefabComponent {
+  /** Stripped GameObject placeholder the component is attached to */
+  gameObjectId: string
+  /** The extended GameObject in the source prefab (m_CorrespondingSourceObject) */
+  sourceObjectId: string
+  sourceGuid?: string | undefined
+  sourceObjectName?: string | undefined
+  component: Component
 }
 /** A single property override in a PrefabInstance */
 export interface PrefabModification {
@@ -48,6 +182,43 @@ export interface PrefabModification {
   value: string
   objectReference?: string | undefined
 }
+/** A prefab whose nested-prefab chain exceeds the depth threshold */
+export interface PrefabNestingChain {
+  /** Outermost prefab of the chain */
+  prefab: string
+  /** Nesting levels below the prefab */
+  depth: number
+  /** The deepest chain, from the prefab down */
+  chain: Array<string>
+}
+/** Circular and over-deep prefab nesting in a project */
+export interface PrefabNestingReport {
+  projectRoot: string
+  prefabCount: number
+  /** Depth threshold applied */
+  maxDepth: number
+  /** Groups of prefabs that (transitively) instantiate each other, each sorted by path */
+  cycles: Array<Array<string>>
+  /** Deepest first */
+  deepChains: Array<PrefabNestingChain>
+}
+/**
+ * A node in a nested-prefab expanded hierarchy.
+ * `source_file` records which scene/prefab the object was loaded from.
+ */
+export interface NestedPrefabNode {
+  name: string
+  fileId: string
+  type: string
+  sourceFile: string
+  nestingLevel: number
+  active?: boolean | undefined
+  sourceGuid?: string | undefined
+  sourcePrefab?: string | undefined
+  /** Why a PrefabInstance was not expanded: "depth_limit", "cycle", or "missing_source" */
+  unexpandedReason?: string | undefined
+  children: Array<NestedPrefabNode>
+}
 /** Union result from find_by_name: either a GameObject or PrefabInstance */
 export interface FindResult {
   name: string
@@ -58,6 +229,10 @@ export interface FindResult {
   sourceGuid?: string | undefined
   sourcePrefab?: string | undefined
   modificationsCount?: number | undefined
+  /** Hierarchy path from the scene root, e.g. "Canvas/ShopPanel/BuyButton" */
+  path?: string | undefined
+  /** Name of the parent GameObject or PrefabInstance (None at the root) */
+  parentName?: string | undefined
 }
 /** Full scene inspection result */
 export interface SceneInspection {
@@ -69,6 +244,8 @@ export interface SceneInspection {
 /** Options for scanning */
 export interface ScanOptions {
   verbose?: boolean | undefined
+  /** Keep only these fields of each result (dotted paths, e.g. ["name", "components.type"]) */
+  fields?: string[] | undefined
 }
 /** Options for inspecting */
 export interface InspectOptions {
@@ -76,6 +253,32 @@ export interface InspectOptions {
   identifier?: string | undefined
   includeProperties?: boolean | undefined
   verbose?: boolean | undefined
+  /** Recursively load nested prefab sources when inspecting a PrefabInstance */
+  expandNested?: boolean | undefined
+  maxNestingDepth?: number | undefined
+  /** Summarize property values (arrays, long strings) whose serialized size exceeds this many bytes */
+  maxPropertyBytes?: number | undefined
+  /** Append pixel dimensions (and sprite name/size) to Texture2D/Sprite references */
+  resolveImages?: boolean | undefined
+  /** Keep only these fields of the result (dotted paths, e.g. ["name", "components.type"]) */
+  fields?: string[] | undefined
+}
+/** Options for Scanner::set_project_root */
+export interface ProjectRootOptions {
+  /**
+   * Prebuilt `{ guid: relative_path }` cache, or the path of a persisted cache JSON file
+   * (relative paths resolve against the project root)
+   */
+  guidCache?: Record<string, string> | string | undefined
+  /** Also resolve GUIDs of Packages/ and Library/PackageCache/ assets */
+  includePackages?: boolean | undefined
+  /**
+   * `{ old_guid: new_guid_or_path }` for assets moved or replaced, or the path of such a JSON
+   * file (see `set_guid_aliases`)
+   */
+  guidAliases?: Record<string, string> | string | undefined
+  /** An earlier `{ guid: relative_path }` cache, or the path of one (see `load_guid_history`) */
+  guidHistory?: Record<string, string> | string | undefined
 }
 /** Pagination options for inspect_all */
 export interface PaginationOptions {
@@ -83,9 +286,17 @@ export interface PaginationOptions {
   includeProperties?: boolean | undefined
   verbose?: boolean | undefined
   pageSize?: number | undefined
+  /** Index of the first GameObject to return; negative values are treated as 0 */
   cursor?: number | undefined
   maxDepth?: number | undefined
   filterComponent?: string | undefined
+  /** Summarize property values (arrays, long strings) whose serialized size exceeds this many bytes */
+  maxPropertyBytes?: number | undefined
+  /**
+   * GameObject order: "document" (file order, the default) or "hierarchy" (the editor's
+   * Hierarchy window order, from m_RootOrder / SceneRoots and m_Children)
+   */
+  sort?: string | undefined
 }
 /** Paginated inspection result */
 export interface PaginatedInspection {
@@ -100,6 +311,1236 @@ export interface PaginatedInspection {
   prefabInstances?: PrefabInstanceInfo[] | undefined
   error?: string | undefined
 }
+/** Aggregate statistics for a scene/prefab file */
+export interface SceneStats {
+  file: string
+  sizeBytes: number
+  gameobjectCount: number
+  componentCount: number
+  prefabInstanceCount: number
+  scriptCount: number
+  /** Component count per type name (e.g. { "Transform": 12, "MonoBehaviour": 3 }) */
+  componentTypes: Record<string, number>
+  /** Distinct MonoBehaviour script types (script name, or "guid:<guid>" when unresolved) */
+  scriptTypes: Array<string>
+}
+/** One prefab asset in the project catalog (see `catalog_prefabs`) */
+export interface PrefabCatalogEntry {
+  /** Project-relative path */
+  path: string
+  /** File name without extension */
+  name: string
+  guid?: string | undefined
+  /** Root GameObject name; for variants, the name given to the base prefab instance */
+  rootObject?: string | undefined
+  rootFileId?: string | undefined
+  /** Base prefab path of a variant ("guid:<guid>" when unresolved) */
+  variantOf?: string | undefined
+  gameobjectCount: number
+  componentCount: number
+  /** Component count per type name */
+  componentTypes: Record<string, number>
+  /** Distinct MonoBehaviour script types (script name, or "guid:<guid>" when unresolved) */
+  scriptTypes: Array<string>
+  /** Distinct prefabs instantiated inside this one, variant base included ("guid:<guid>" when unresolved) */
+  nestedPrefabs: Array<string>
+  /** Serialized size on disk */
+  sizeBytes: number
+}
+/** Every prefab of a project with its size and contents, sorted by path */
+export interface PrefabCatalog {
+  projectRoot: string
+  prefabCount: number
+  totalSizeBytes: number
+  prefabs: Array<PrefabCatalogEntry>
+}
+/** Number of blocks of one class in a file */
+export interface ClassCount {
+  classId: number
+  className: string
+  count: number
+}
+/** Block counts of a scene/prefab from its headers alone (see `count_objects`) */
+export interface ObjectCounts {
+  file: string
+  sizeBytes: number
+  totalBlocks: number
+  gameobjectCount: number
+  prefabInstanceCount: number
+  /** Blocks marked `stripped` (placeholders for objects of prefab instances) */
+  strippedCount: number
+  /** Per class, in ascending class ID order */
+  classes: Array<ClassCount>
+}
+/** Hierarchy and component totals for the subtree under one GameObject (see `subtree_metrics`) */
+export interface SubtreeMetrics {
+  file: string
+  gameObjectId: string
+  name: string
+  /** GameObjects below the node, at any depth (the node itself excluded) */
+  descendantCount: number
+  /** Levels below the node (0 for a leaf) */
+  maxDepth: number
+  /** Largest number of GameObjects on a single level */
+  maxBreadth: number
+  /** Largest number of direct children of any one GameObject */
+  maxChildren: number
+  /** GameObjects per level, starting with the node itself */
+  levelCounts: Array<number>
+  /** GameObjects in the subtree with m_IsActive: 0 */
+  inactiveCount: number
+  /** Components on the node and all descendants */
+  componentCount: number
+  /** Component count per type name */
+  componentTypes: Record<string, number>
+  /** MonoBehaviour count per script (script name, or "guid:<guid>" when unresolved) */
+  scriptTypes: Record<string, number>
+  /** PrefabInstances parented to a Transform in the subtree (their contents are not counted) */
+  prefabInstanceCount: number
+}
+/** A serialized Vector3 (`{x: 0, y: 1, z: 0}`) */
+export interface Vector3 {
+  x: number
+  y: number
+  z: number
+}
+/** A serialized rotation quaternion (`{x: 0, y: 0, z: 0, w: 1}`) */
+export interface Quaternion {
+  x: number
+  y: number
+  z: number
+  w: number
+}
+/** Position, rotation and scale in one space */
+export interface TransformTrs {
+  position: Vector3
+  rotation: Quaternion
+  /** Rotation in degrees as the inspector shows it (Unity's Z, X, Y order) */
+  eulerAngles: Vector3
+  scale: Vector3
+}
+/** Where a GameObject is, without reading its Transform properties (see `get_transform_summary`) */
+export interface TransformSummary {
+  file: string
+  gameObjectId: string
+  name: string
+  transformId: string
+  isRectTransform: boolean
+  local: TransformTrs
+  /**
+   * Composed through the parents; scale is the lossy scale (skew from rotated,
+   * non-uniformly scaled parents is dropped)
+   */
+  world: TransformTrs
+  /**
+   * False when an ancestor is part of a nested prefab instance, whose transform values are
+   * not in this file; `world` is then relative to that ancestor
+   */
+  worldExact: boolean
+  /** Names of the ancestors, root first */
+  parentChain: Array<string>
+  /** Position among the parent's children (or among the scene roots) */
+  siblingIndex?: number | undefined
+  childCount: number
+}
+/** Difference between two versions of the same scene/prefab */
+export interface SceneStatsDiff {
+  file: string
+  old: SceneStats
+  new: SceneStats
+  gameobjectsDelta: number
+  componentsDelta: number
+  prefabInstancesDelta: number
+  scriptsDelta: number
+  sizeDeltaBytes: number
+  addedScriptTypes: Array<string>
+  removedScriptTypes: Array<string>
+  /** Non-zero per-type component count changes */
+  componentTypeDeltas: Record<string, number>
+}
+/** Physics layer collision matrix parsed from DynamicsManager.asset / Physics2DSettings.asset */
+export interface CollisionMatrix {
+  file: string
+  /** One row per named layer (all 32 layers when no TagManager.asset is found) */
+  layers: Array<LayerCollisionRow>
+  /** Layer pairs (a <= b) whose collision is disabled */
+  disabledPairs: Array<LayerPair>
+}
+/** Collision settings of a single physics layer */
+export interface LayerCollisionRow {
+  index: number
+  name?: string | undefined
+  /** Indices of layers this layer collides with */
+  collidesWith: Array<number>
+}
+/** A pair of physics layers */
+export interface LayerPair {
+  layerA: number
+  layerB: number
+  nameA?: string | undefined
+  nameB?: string | undefined
+}
+/** A legacy Input Manager axis from InputManager.asset */
+export interface InputAxis {
+  name: string
+  descriptiveName: string
+  descriptiveNegativeName: string
+  negativeButton: string
+  positiveButton: string
+  altNegativeButton: string
+  altPositiveButton: string
+  gravity: number
+  dead: number
+  sensitivity: number
+  snap: boolean
+  invert: boolean
+  /** "key_or_mouse_button", "mouse_movement" or "joystick_axis" */
+  axisType: string
+  /** 0-based axis index (0 = X axis, 1 = Y axis, 2 = 3rd axis, ...) */
+  axis: number
+  /** 0 = all joysticks, otherwise the joystick number */
+  joyNum: number
+}
+/** Summary of a Camera component (class 20) */
+export interface CameraInfo {
+  fileId: string
+  gameObjectId: string
+  gameObjectName: string
+  enabled: boolean
+  /** "perspective" or "orthographic" */
+  projection: string
+  fieldOfView: number
+  orthographicSize: number
+  nearClip: number
+  farClip: number
+  depth: number
+  /** "skybox", "solid_color", "depth_only" or "nothing" */
+  clearFlags: string
+  cullingMask: number
+  /** Layer names in the culling mask ("Everything" when all layers are rendered) */
+  cullingLayers: Array<string>
+  hdr: boolean
+  targetDisplay: number
+}
+/** Summary of a Light component (class 108) */
+export interface LightInfo {
+  fileId: string
+  gameObjectId: string
+  gameObjectName: string
+  enabled: boolean
+  /** "spot", "directional", "point", "area" or "disc" */
+  lightType: string
+  intensity: number
+  range: number
+  spotAngle: number
+  /** "none", "hard" or "soft" */
+  shadows: string
+  /** "realtime", "mixed" or "baked" */
+  bakeMode: string
+  cullingMask: number
+  cullingLayers: Array<string>
+}
+/** A Canvas (class 223) found by the canvas audit */
+export interface CanvasInfo {
+  file: string
+  fileId: string
+  gameObjectId: string
+  gameObjectName: string
+  /** "screen_space_overlay", "screen_space_camera" or "world_space" */
+  renderMode: string
+  sortOrder: number
+  sortingLayerId: number
+  overrideSorting: boolean
+  targetDisplay: number
+  /** Number of ancestor Canvases (0 = root canvas) */
+  nestingDepth: number
+  hasCamera: boolean
+  /** e.g. "duplicate_sort_order", "world_space_without_camera" */
+  issues: Array<string>
+}
+/** Result of auditing the Canvases of a file or project */
+export interface CanvasAudit {
+  path: string
+  filesScanned: number
+  canvases: Array<CanvasInfo>
+  issueCount: number
+}
+/** A GameObject carrying a Rigidbody and/or Colliders, found by the physics audit */
+export interface PhysicsObjectInfo {
+  gameObjectId: string
+  gameObjectName: string
+  layer: number
+  layerName?: string | undefined
+  /** "Rigidbody" or "Rigidbody2D" */
+  rigidbody?: string | undefined
+  isKinematic: boolean
+  /** Collider type names on this GameObject (e.g. ["BoxCollider", "MeshCollider"]) */
+  colliders: Array<string>
+  /** e.g. "moving_collider_without_rigidbody", "non_convex_mesh_collider_on_rigidbody" */
+  issues: Array<string>
+}
+/** Result of auditing Rigidbody/Collider setup in a scene or prefab */
+export interface PhysicsAudit {
+  file: string
+  objects: Array<PhysicsObjectInfo>
+  issueCount: number
+}
+/** Custom script execution order entry */
+export interface ExecutionOrderEntry {
+  guid: string
+  scriptPath?: string | undefined
+  scriptName?: string | undefined
+  /** Execution order value (negative runs before default time, positive after) */
+  order: number
+  /** "meta" (MonoImporter executionOrder) or "mono_manager" (ProjectSettings/MonoManager.asset) */
+  source: string
+}
+/** A NavMeshAgent (class 195) summary */
+export interface NavMeshAgentInfo {
+  fileId: string
+  gameObjectId: string
+  gameObjectName: string
+  enabled: boolean
+  agentTypeId: number
+  radius: number
+  height: number
+  speed: number
+  angularSpeed: number
+  acceleration: number
+  stoppingDistance: number
+  baseOffset: number
+  avoidancePriority: number
+  issues: Array<string>
+}
+/** A NavMeshObstacle (class 208) summary */
+export interface NavMeshObstacleInfo {
+  fileId: string
+  gameObjectId: string
+  gameObjectName: string
+  enabled: boolean
+  /** "capsule" or "box" */
+  shape: string
+  carve: boolean
+  /** Raw extents vector, e.g. "{x: 0.5, y: 0.5, z: 0.5}" */
+  extents: string
+}
+/** An OffMeshLink (class 191) summary */
+export interface OffMeshLinkInfo {
+  fileId: string
+  gameObjectId: string
+  gameObjectName: string
+  enabled: boolean
+  /** Name of the GameObject whose Transform is the link start */
+  start?: string | undefined
+  end?: string | undefined
+  biDirectional: boolean
+  activated: boolean
+  costOverride: number
+  issues: Array<string>
+}
+/** Navigation overview of a scene: NavMeshSettings bake parameters and all nav components */
+export interface NavMeshReport {
+  file: string
+  hasBakedNavmesh: boolean
+  bakedAgentRadius?: number | undefined
+  bakedAgentHeight?: number | undefined
+  bakedAgentSlope?: number | undefined
+  bakedAgentClimb?: number | undefined
+  agents: Array<NavMeshAgentInfo>
+  obstacles: Array<NavMeshObstacleInfo>
+  links: Array<OffMeshLinkInfo>
+  /** Scene-level problems, e.g. "agents_without_baked_navmesh" */
+  issues: Array<string>
+}
+/** A local fileID reference that could not be carried across a GameObject move */
+export interface BrokenReference {
+  /**
+   * "outgoing" (a moved object referenced one left behind) or
+   * "incoming" (an object left behind referenced a moved one)
+   */
+  direction: string
+  /** fileID (in the source file) of the block holding the reference */
+  fromFileId: string
+  property: string
+  /** fileID (in the source file) the reference pointed at; it is reset to 0 */
+  targetFileId: string
+}
+/** Result of transplanting a GameObject subtree from one scene/prefab into another */
+export interface MoveGameObjectResult {
+  success: boolean
+  error?: string | undefined
+  srcFile: string
+  dstFile: string
+  /** True when no file was written */
+  dryRun: boolean
+  movedBlockCount: number
+  /** fileID of the moved root GameObject in the destination */
+  newFileId?: string | undefined
+  /** Source fileID -> destination fileID for every moved block */
+  fileIdMap: Record<string, string>
+  brokenReferences: Array<BrokenReference>
+  /** Unified diff of both files (destination first), returned on dry runs only */
+  diff?: string | undefined
+}
+/** Result of generating a new scene or prefab from a scaffold spec */
+export interface ScaffoldResult {
+  success: boolean
+  error?: string | undefined
+  file: string
+  /** True when no file was written */
+  dryRun: boolean
+  objectCount: number
+  blockCount: number
+  /** Object path ("Root/Child") -> GameObject fileID */
+  fileIds: Record<string, string>
+  /** Generated YAML, returned on dry runs only */
+  content?: string | undefined
+  /**
+   * Unified diff against the file being replaced (empty old side for a new file),
+   * returned on dry runs only
+   */
+  diff?: string | undefined
+}
+/** Which GameObjects a bulk edit applies to; every given criterion must match */
+export interface GameObjectSelector {
+  /** Exact name, or a case-insensitive glob with `*` and `?` */
+  name?: string | undefined
+  tag?: string | undefined
+  layer?: number | undefined
+  /** Component class (e.g. "Light") or script name (e.g. "PlayerController") */
+  componentType?: string | undefined
+  active?: boolean | undefined
+  /** Only descendants of the GameObject with this fileID */
+  under?: string | undefined
+}
+/** Values a bulk edit sets on every matched GameObject; omitted fields are left alone */
+export interface GameObjectChanges {
+  name?: string | undefined
+  tag?: string | undefined
+  layer?: number | undefined
+  active?: boolean | undefined
+}
+/** One rewritten GameObject property */
+export interface PropertyChange {
+  /** "m_Name", "m_TagString", "m_Layer" or "m_IsActive" */
+  property: string
+  oldValue: string
+  newValue: string
+  /** 1-based line number */
+  line: number
+}
+/** Changes made to one matched GameObject (empty when it already had the requested values) */
+export interface GameObjectChangeReport {
+  fileId: string
+  /** Name before the edit */
+  name: string
+  changes: Array<PropertyChange>
+}
+/** Result of a bulk tag/layer/active/name edit of one scene or prefab */
+export interface BulkUpdateResult {
+  success: boolean
+  error?: string | undefined
+  file: string
+  /** True when no file was written */
+  dryRun: boolean
+  matchedCount: number
+  changedCount: number
+  objects: Array<GameObjectChangeReport>
+  /** Tags or layers TagManager does not define (only with tag validation on) */
+  warnings: Array<string>
+  /** Unified diff of the would-be file change, returned on dry runs only */
+  diff?: string | undefined
+}
+/** A GameObject added, removed or modified since the last poll of a subscribed file */
+export interface SceneObjectChange {
+  fileId: string
+  name: string
+  /** Hierarchy path (names from the root, joined with '/') */
+  path: string
+  /**
+   * The GameObject block itself changed: name, tag, layer, active state, component list
+   * or parent
+   */
+  gameobjectChanged: boolean
+  /** Types of components added, removed or edited */
+  changedComponents: Array<string>
+}
+/** Changes to a subscribed scene or prefab since the previous poll (or the subscription) */
+export interface SceneChangesResult {
+  success: boolean
+  error?: string | undefined
+  file: string
+  /** True when any GameObject was added, removed or modified */
+  changed: boolean
+  added: Array<SceneObjectChange>
+  removed: Array<SceneObjectChange>
+  modified: Array<SceneObjectChange>
+}
+/** A component type a script expects on its own GameObject */
+export interface ComponentExpectation {
+  typeName: string
+  /** "RequireComponent", "GetComponent", "GetComponents" or "TryGetComponent" */
+  source: string
+  /** True for [RequireComponent]; runtime lookups are advisory */
+  required: boolean
+}
+/** Components a script expects (and adds itself) at runtime */
+export interface ScriptComponentUsage {
+  scriptPath: string
+  scriptGuid?: string | undefined
+  expects: Array<ComponentExpectation>
+  /** Types added via AddComponent<T>() on its own GameObject */
+  adds: Array<string>
+}
+/** A GameObject whose script expects a component it does not have */
+export interface MissingComponentIssue {
+  file: string
+  gameObjectId: string
+  gameObjectName: string
+  scriptPath: string
+  missingType: string
+  source: string
+  /** "error" for [RequireComponent], "warning" for runtime lookups */
+  severity: string
+}
+/** Result of cross-checking scripts' component expectations against scenes and prefabs */
+export interface ComponentUsageReport {
+  projectRoot: string
+  scripts: Array<ScriptComponentUsage>
+  filesChecked: number
+  issues: Array<MissingComponentIssue>
+  /** The scan stopped early at its resource budget */
+  budgetExhausted: boolean
+}
+/** How widely one MonoBehaviour script is instantiated (see `script_coverage`) */
+export interface ScriptCoverageEntry {
+  name: string
+  namespace?: string | undefined
+  scriptPath: string
+  guid: string
+  /** Scenes with at least one instance */
+  sceneCount: number
+  /** Prefabs with at least one instance */
+  prefabCount: number
+  /** Instances across all scanned files */
+  instanceCount: number
+  /** Scenes and prefabs instantiating the script, sorted */
+  usedIn: Array<string>
+  /** No scanned scene or prefab instantiates the script */
+  unused: boolean
+}
+/** MonoBehaviour scripts of a project with their scene/prefab usage, unused scripts first */
+export interface ScriptCoverageReport {
+  projectRoot: string
+  scriptCount: number
+  unusedCount: number
+  /** Scenes and prefabs read */
+  filesScanned: number
+  scripts: Array<ScriptCoverageEntry>
+  /** The scan stopped early at its resource budget */
+  budgetExhausted: boolean
+}
+/** One persistent listener of a serialized UnityEvent */
+export interface UnityEventListener {
+  method: string
+  /** "event_defined", "void", "object", "int", "float", "string" or "bool" */
+  mode: string
+  /** Argument for the listener's mode (object arguments are resolved like targets) */
+  argument?: string | undefined
+  /** "off", "editor_and_runtime" or "runtime_only" */
+  callState: string
+  targetFileId: string
+  targetGuid?: string | undefined
+  /** Asset path for cross-file targets */
+  targetPath?: string | undefined
+  /** Component type or script name of the target */
+  targetType?: string | undefined
+  /** Name of the target GameObject (or asset) */
+  targetName?: string | undefined
+  /** "missing_target", "missing_target_asset", "missing_method" or "method_not_found" */
+  issue?: string | undefined
+}
+/** A UnityEvent field on a component with its persistent listeners */
+export interface UnityEventInfo {
+  file: string
+  gameObjectId: string
+  gameObjectName: string
+  componentFileId: string
+  componentType: string
+  /** Dotted property path of the event (e.g. "m_OnClick") */
+  event: string
+  listeners: Array<UnityEventListener>
+}
+/** Project-wide UnityEvent audit */
+export interface UnityEventReport {
+  path: string
+  filesScanned: number
+  eventCount: number
+  listenerCount: number
+  /** Events with at least one broken listener (only the broken listeners are kept) */
+  broken: Array<UnityEventInfo>
+}
+/** The parts of a `GlobalObjectId_V1-{type}-{assetGUID}-{objectId}-{prefabId}` string */
+export interface GlobalObjectIdParts {
+  /** 0 null, 1 imported asset, 2 scene object, 3 source asset */
+  identifierType: number
+  assetGuid: string
+  /** Local fileID of the object (of the source object, for objects inside a prefab instance) */
+  targetObjectId: string
+  /** fileID of the PrefabInstance holding the object, "0" when it is not in one */
+  targetPrefabId: string
+}
+/**
+ * A reference into another scene serialized in a component: a GlobalObjectId string or a
+ * SceneAsset reference
+ */
+export interface CrossSceneReference {
+  file: string
+  gameObjectId: string
+  gameObjectName: string
+  componentFileId: string
+  componentType: string
+  /** Serialized field holding the reference */
+  property: string
+  /** "global_object_id" or "scene_asset" */
+  kind: string
+  /** The serialized GlobalObjectId string or reference */
+  value: string
+  assetGuid: string
+  globalObjectId?: GlobalObjectIdParts | undefined
+  /** Project-relative path of the referenced asset */
+  targetPath?: string | undefined
+  /** "missing_asset" (GUID not in the project) or "missing_object" (the target scene has no such object) */
+  issue?: string | undefined
+}
+/** A mismatch between a material's serialized properties and its shader's Properties block */
+export interface MaterialPropertyIssue {
+  /** "stale" (not declared by the shader), "type_mismatch" (wrong section) or "missing" (no serialized value) */
+  kind: string
+  property: string
+  /** m_SavedProperties section: m_TexEnvs, m_Ints, m_Floats or m_Colors */
+  section: string
+  message: string
+}
+/** Result of checking a material against its shader (see `check_material_shader`) */
+export interface MaterialShaderCheck {
+  material: string
+  /** Shader source path */
+  shader: string
+  shaderName?: string | undefined
+  declaredCount: number
+  serializedCount: number
+  issues: Array<MaterialPropertyIssue>
+}
+/** A legacy component, UnityScript/Boo script or deprecated C# API in use */
+export interface DeprecatedUsage {
+  /** "component", "script" or "api" */
+  category: string
+  file: string
+  /** 1-based source line (api) */
+  line?: number | undefined
+  /** Owning GameObject (component, script) */
+  gameObjectId?: string | undefined
+  gameObjectName?: string | undefined
+  /** Component type, script path or API name */
+  name: string
+  /** What to migrate to */
+  replacement: string
+}
+/** Modernization report of legacy/deprecated usage in a file or project */
+export interface DeprecationReport {
+  path: string
+  /** Scenes and prefabs scanned */
+  filesScanned: number
+  /** C# sources scanned */
+  scriptsScanned: number
+  /** Usage count per name (e.g. { "GUIText": 3, "WWW": 1 }) */
+  summary: Record<string, number>
+  usages: Array<DeprecatedUsage>
+}
+/** An animation curve binding that does not resolve against the animated hierarchy */
+export interface AnimationBindingIssue {
+  /** Clip asset path */
+  clip: string
+  /** Hierarchy path relative to the animated GameObject ("" for the object itself) */
+  path: string
+  attribute: string
+  classId: number
+  targetType: string
+  /** "missing_path", "missing_component" or "missing_property" */
+  issue: string
+  detail: string
+}
+/** Binding validation of an AnimatorController or clip against one animated GameObject */
+export interface AnimationBindingReport {
+  animationPath: string
+  file: string
+  gameObjectId: string
+  gameObjectName: string
+  clipsChecked: number
+  bindingsChecked: number
+  issues: Array<AnimationBindingIssue>
+}
+/** An asset referenced (directly or through other assets) by a scene, with its estimated cost */
+export interface AssetCost {
+  path: string
+  guid: string
+  /** "texture", "mesh", "audio", "video", "font", "material", "prefab", "animation", "shader", "script" or "other" */
+  category: string
+  /** Source file size on disk */
+  fileBytes: number
+  /** Heuristic runtime memory from file size and import settings */
+  estimatedMemoryBytes: number
+  /** Number of references across the scene and followed assets */
+  references: number
+  /** Referenced by the scene itself rather than only through another asset */
+  direct: boolean
+}
+/** Asset cost totals of one category */
+export interface AssetCostCategory {
+  category: string
+  count: number
+  fileBytes: number
+  estimatedMemoryBytes: number
+}
+/** Rough memory/build impact estimate of a scene or prefab */
+export interface SceneCostReport {
+  file: string
+  serializedBytes: number
+  gameobjectCount: number
+  componentCount: number
+  prefabInstanceCount: number
+  dependencyCount: number
+  /** Referenced GUIDs not found in the project (package or deleted assets) */
+  unresolvedReferences: number
+  /** Total source size of all dependencies (build impact) */
+  totalAssetBytes: number
+  /** Serialized scene size plus the estimated memory of all dependencies */
+  estimatedMemoryBytes: number
+  /** Per-category totals, largest estimated memory first */
+  categories: Array<AssetCostCategory>
+  /** Most expensive dependencies by estimated memory */
+  largestAssets: Array<AssetCost>
+}
+/** An external asset referenced by a scene or prefab */
+export interface GuidManifestEntry {
+  guid: string
+  /** Project path of the asset; unset when the GUID is not in the project */
+  path?: string
+  /** Asset category as in SceneCostReport ("other" when unresolved) */
+  category: string
+  /** Number of references in the file */
+  references: number
+  /** Where an unresolved GUID's asset went, from the GUID aliases or history */
+  resolution?: GuidResolution | undefined
+}
+/** What became of an asset GUID (see `resolve_guid`) */
+export interface GuidResolution {
+  guid: string
+  /**
+   * "current" (in the GUID cache), "aliased" (from the alias map), "regenerated" (its former
+   * path has a new GUID), "moved" (its file now lives elsewhere) or "deleted"
+   */
+  status: string
+  /** Current project path of the asset */
+  path?: string | undefined
+  /** The asset's GUID now, when it changed */
+  newGuid?: string | undefined
+  /** Path the GUID had in the GUID history */
+  formerPath?: string | undefined
+  /** e.g. "moved to Assets/Art/Rock.mat" */
+  message: string
+}
+/** Every external GUID a scene or prefab references, sorted by GUID */
+export interface GuidManifest {
+  file: string
+  guidCount: number
+  referenceCount: number
+  /** GUIDs not found in the project (package or deleted assets) */
+  unresolvedCount: number
+  entries: Array<GuidManifestEntry>
+}
+/** Options for compute_scene_bounds */
+export interface SceneBoundsOptions {
+  /** Also report bounds per layer or per tag */
+  groupBy?: 'layer' | 'tag' | undefined
+  /** Cells along the longer side of the occupancy grid (default: 32, max: 256; 0 skips the grid) */
+  gridResolution?: number | undefined
+  /** Plane the grid projects onto: "xz" (top-down, default) or "xy" (2D games) */
+  plane?: 'xz' | 'xy' | undefined
+  /** Skip inactive objects and their children (default: false) */
+  activeOnly?: boolean | undefined
+}
+/** Axis-aligned box around a set of world positions */
+export interface SceneAabb {
+  min: Vector3
+  max: Vector3
+  center: Vector3
+  size: Vector3
+  objectCount: number
+}
+/** Bounds of the objects sharing one layer or tag */
+export interface SceneBoundsGroup {
+  /** Layer index or tag */
+  key: string
+  bounds: SceneAabb
+}
+/** Object counts per cell of a 2D grid laid over the scene bounds */
+export interface OccupancyGrid {
+  plane: string
+  columns: number
+  rows: number
+  /** World units per (square) cell */
+  cellSize: number
+  /** Minimum corner on the plane's first axis (x) */
+  originU: number
+  /** Minimum corner on the plane's second axis (z or y) */
+  originV: number
+  /** Row-major counts, first row at the maximum of the second axis (north up) */
+  cells: Array<number>
+  /** One string per row: '.' empty, 1-9 objects, '#' ten or more */
+  rendered: Array<string>
+}
+/** Extent of a scene's positioned objects, for overviews and "how big is this level" */
+export interface SceneBoundsReport {
+  file: string
+  /** GameObjects and prefab instances placed (RectTransform UI is not) */
+  objectCount: number
+  /** Objects inside prefab instances, placed at their instance's root */
+  approximateCount: number
+  /** Box around object pivots (renderer extents are not included); undefined when nothing is placed */
+  bounds?: SceneAabb | undefined
+  groups: Array<SceneBoundsGroup>
+  grid?: OccupancyGrid | undefined
+}
+/** A sprite declared by a texture's import settings */
+export interface SpriteInfo {
+  name: string
+  /** fileID used by references to this sprite */
+  fileId: string
+  x: number
+  y: number
+  width: number
+  height: number
+}
+/** Pixel dimensions and sprites of a texture asset */
+export interface TextureInfo {
+  path: string
+  /** "png", "jpeg", "gif", "bmp", "psd" or "tga" (undefined when the header is not recognized) */
+  format?: string | undefined
+  width?: number | undefined
+  height?: number | undefined
+  /** TextureImporter spriteMode (0 none, 1 single, 2 multiple) */
+  spriteMode: number
+  sprites: Array<SpriteInfo>
+}
+/** Timing of one benchmark case on one synthetic scene size */
+export interface BenchmarkResult {
+  /** "block_index", "scan_scene_minimal", "inspect_all_paginated" or "extract_properties" */
+  case: string
+  objects: number
+  sceneBytes: number
+  iterations: number
+  meanMs: number
+  minMs: number
+  maxMs: number
+}
+/** Result of `self_benchmark`, with the environment it ran in */
+export interface BenchmarkReport {
+  version: string
+  os: string
+  arch: string
+  /** Rayon worker threads */
+  threads: number
+  /** Built without optimizations (numbers are not representative) */
+  debugBuild: boolean
+  results: Array<BenchmarkResult>
+}
+/** Inferred schema of one serialized property path */
+export interface PropertySchema {
+  /** Dotted serialized path; sequence items use `name[]` */
+  path: string
+  /** Observed value types, most frequent first ("int", "float", "string", "reference", "vector3", "array", ...) */
+  types: Array<string>
+  /** Number of samples containing the property */
+  occurrences: number
+  /** Missing from some samples (older serialized versions, conditional fields) */
+  optional: boolean
+  /** Observed empty or `{fileID: 0}` */
+  nullable: boolean
+  /** Numeric range (array length range for sequences) */
+  min?: number | undefined
+  max?: number | undefined
+  examples: Array<string>
+}
+/** Schema of a component type inferred from its serialized occurrences across a project */
+export interface ComponentSchema {
+  typeName: string
+  /** Script backing the type, for MonoBehaviours */
+  scriptPath?: string | undefined
+  samples: number
+  /** Files containing at least one sample */
+  files: Array<string>
+  properties: Array<PropertySchema>
+  /** The scan stopped early at its resource budget */
+  budgetExhausted: boolean
+}
+/** One bin of a property histogram: a distinct value, or a numeric range */
+export interface HistogramBin {
+  /** The value, or "min..max" for a numeric range */
+  label: string
+  /** Inclusive numeric bounds (equal for a single numeric value) */
+  min?: number | undefined
+  max?: number | undefined
+  count: number
+}
+/** Distribution of one component property across a project */
+export interface PropertyHistogram {
+  componentType: string
+  propertyPath: string
+  /** Components of the type found */
+  samples: number
+  /** Components of the type that do not serialize the property */
+  missing: number
+  /** All values are numbers; bins are then ordered by value, else by count */
+  numeric: boolean
+  min?: number | undefined
+  max?: number | undefined
+  mean?: number | undefined
+  bins: Array<HistogramBin>
+  /** Files containing at least one sample */
+  files: Array<string>
+  /** Field renames across Unity versions the lookup fell back to, e.g. an m_Drag read for m_LinearDamping */
+  aliasesApplied: Array<string>
+  /** The scan stopped early at its resource budget */
+  budgetExhausted: boolean
+}
+/** A GameObject or component matched across two versions of a scene */
+export interface ObjectCorrelation {
+  /** "GameObject" or "Component" */
+  kind: string
+  oldFileId: string
+  newFileId: string
+  /** GameObject name, or component type */
+  name: string
+  /** Hierarchy path of the (owning) GameObject in the new version */
+  path: string
+  /** "same_id", "path_and_components", "path" or "name_and_components" */
+  method: string
+  confidence: number
+}
+/** fileID mapping between two versions of a scene */
+export interface ObjectCorrelationResult {
+  mappings: Array<ObjectCorrelation>
+  /** Matched objects whose fileID changed */
+  remappedCount: number
+  /** Old GameObjects with no counterpart (removed or ambiguous) */
+  unmatchedOld: Array<string>
+  /** New GameObjects with no counterpart (added or ambiguous) */
+  unmatchedNew: Array<string>
+}
+/** One broken scene convention */
+export interface RuleViolation {
+  /** Rule `id`, or its kind and position ("naming[0]", "max_depth") */
+  rule: string
+  /** Rule severity, "error" unless the rule sets one */
+  severity: string
+  gameObjectId: string
+  gameObjectName: string
+  path: string
+  message: string
+}
+/** Result of checking a scene or prefab against a rule file */
+export interface RuleValidationReport {
+  file: string
+  rulesPath: string
+  rulesChecked: number
+  objectsChecked: number
+  violations: Array<RuleViolation>
+}
+/** Component as returned by scan/inspect (snake_case keys; verbose fields only in verbose mode) */
+export interface ComponentOutput {
+  type: string
+  /** Script path (non-verbose mode) */
+  script?: string | undefined
+  class_id?: number | undefined
+  file_id?: string | undefined
+  script_path?: string | undefined
+  script_guid?: string | undefined
+  script_name?: string | undefined
+  editor_class_identifier?: EditorClassIdentifier | undefined
+  /** `m_Enabled` of enableable components, in default and verbose output alike */
+  enabled?: boolean | undefined
+  properties?: Record<string, any> | undefined
+  /** Field renames across Unity versions the property filters matched through */
+  aliases_applied?: string[] | undefined
+}
+/** GameObject entry of scan_scene_with_components */
+export interface SceneObjectOutput {
+  name: string
+  active: boolean
+  file_id?: string | undefined
+  component_count?: number | undefined
+  components: Array<ComponentOutput>
+  tag: string
+  layer: number
+}
+/** One PrefabInstance override, grouped by target in PrefabInstanceOutput */
+export interface PrefabModificationOutput {
+  propertyPath: string
+  value: string
+}
+/** PrefabInstance entry of scan_scene_with_components, or the result of inspecting one */
+export interface PrefabInstanceOutput {
+  /** Always "PrefabInstance" */
+  type: 'PrefabInstance'
+  name: string
+  file_id?: string | undefined
+  source_guid: string
+  source_prefab?: string | undefined
+  modifications_count: number
+  removed_components?: RemovedPrefabObject[] | undefined
+  removed_game_objects?: RemovedPrefabObject[] | undefined
+  added_components?: AddedPrefabComponent[] | undefined
+  parent_transform_id?: string | undefined
+  parent_game_object_id?: string | undefined
+  parent_prefab_instance_id?: string | undefined
+  /** Overrides keyed by target fileID (with include_properties) */
+  modifications?: Record<string, PrefabModificationOutput[]> | undefined
+  /** Nested prefab tree (with expand_nested) */
+  expanded?: NestedPrefabNode[] | undefined
+  unexpanded_reason?: string | undefined
+}
+/** Result of inspecting one GameObject */
+export interface GameObjectInspection {
+  name: string
+  file_id: string
+  active: boolean
+  tag: string
+  layer: number
+  components: Array<ComponentOutput>
+  /** Child Transform fileIDs (verbose mode) */
+  children?: string[] | undefined
+  /** Children in sibling order with their resolved names */
+  ordered_children?: ChildEntry[] | undefined
+  parent_transform_id?: string | undefined
+  /** Notes attached with annotate_object */
+  annotations?: ObjectAnnotation[] | undefined
+  /** Undefined tag / unnamed or out-of-range layer (when tag validation is enabled) */
+  warnings?: string[] | undefined
+  /** Prefab instance / package origin (always for non-local objects, otherwise verbose mode) */
+  provenance?: ObjectProvenance | undefined
+}
+/** Root object of a .asset file as returned by read_asset */
+export interface AssetObjectOutput {
+  class_id: number
+  file_id: string
+  type_name: string
+  name: string
+  properties: Record<string, any>
+  script_guid?: string | undefined
+  script_path?: string | undefined
+  /** Field renames across Unity versions the property filters matched through */
+  aliases_applied?: string[] | undefined
+}
+/** Error result of APIs that report failures in-band */
+export interface ErrorOutput {
+  error: string
+  is_error: true
+}
+/** Persistent note/labels attached to a scene or prefab object */
+export interface ObjectAnnotation {
+  id: number
+  /** Scene/prefab path relative to the project root */
+  file: string
+  /** Current fileID of the annotated GameObject */
+  fileId: string
+  /** Object name and hierarchy path when last seen */
+  objectName: string
+  objectPath: string
+  note?: string | undefined
+  labels: Array<string>
+  createdMs: number
+  updatedMs: number
+  /** Previous fileID when the object was found again under a new one */
+  remappedFrom?: string | undefined
+  /** The object could not be found in the current file */
+  orphaned: boolean
+}
+/** Something that may rely on a GameObject's current name */
+export interface RenameRisk {
+  /** "animation_path", "find_call", "string_literal", "unity_event_argument" or "name_conflict" */
+  kind: string
+  /** "high" (breaks after the rename) or "medium" (may break) */
+  severity: string
+  file: string
+  /** 1-based line for C# sources */
+  line?: number | undefined
+  /** Curve path, source line or event that references the name */
+  reference: string
+  message: string
+}
+/** What might break when a GameObject is renamed */
+export interface RenameImpactReport {
+  file: string
+  gameObjectId: string
+  oldName: string
+  newName: string
+  /** Hierarchy path before the rename */
+  path: string
+  newPath: string
+  /** C# sources, scenes/prefabs and clips checked */
+  filesScanned: number
+  /** High-severity risks first */
+  risks: Array<RenameRisk>
+}
+/** Options for duplicate-structure detection */
+export interface DuplicateOptions {
+  /** Smallest cluster reported (default: 2) */
+  minCount?: number | undefined
+  /** Property values copies may differ in and still be clustered together (default: 1) */
+  maxDifferences?: number | undefined
+}
+/** Group of (near-)identical GameObject copies: a prefab extraction candidate */
+export interface DuplicateCluster {
+  /** Component types of each copy (scripts by name), sorted */
+  components: Array<string>
+  count: number
+  fileIds: Array<string>
+  /** Distinct names of the copies, without Unity's " (N)" suffix */
+  names: Array<string>
+  /** Hierarchy path of the first copy */
+  samplePath: string
+  /** Objects below each copy (identical across the cluster) */
+  descendantCount: number
+  /** Every copy has the same property values */
+  identical: boolean
+  /** "Component.m_Field" values that differ between copies */
+  varyingProperties: Array<string>
+  suggestion: string
+}
+/** Duplicate GameObject structures of a scene or prefab */
+export interface DuplicateStructureReport {
+  file: string
+  objectsChecked: number
+  /** Objects that belong to a cluster */
+  duplicateObjects: number
+  /** Largest first (copies × objects per copy) */
+  clusters: Array<DuplicateCluster>
+}
+/** A component property whose value is not what Unity writes for a fresh component */
+export interface PropertyDefaultDiff {
+  /** Dotted property path (`m_Shadows.m_Type`, `m_Materials`) */
+  path: string
+  value: string
+  /** Unity's default, unset when not known (scripts, types missing from the table) */
+  default?: string | undefined
+}
+/** Non-default properties of one component (see `diff_against_defaults`) */
+export interface ComponentDefaultsDiff {
+  file: string
+  componentId: string
+  componentType: string
+  classId: number
+  /** The built-in defaults table covers this type */
+  knownType: boolean
+  /** Properties compared (bookkeeping and hierarchy fields excluded) */
+  totalProperties: number
+  /** Properties left at their default */
+  defaultCount: number
+  differences: Array<PropertyDefaultDiff>
+}
+/** GameObject compared by `find_similar` */
+export interface SimilarObject {
+  fileId: string
+  name: string
+  path: string
+  /** 0..1: mean of the component overlap and the share of equal property values */
+  similarity: number
+  /** Component types (scripts by name), sorted */
+  components: Array<string>
+  /** Components of the target this object lacks */
+  missingComponents: Array<string>
+  /** Components this object has beyond the target's */
+  extraComponents: Array<string>
+  /** "Component.m_Field" values both objects have but that differ */
+  differingProperties: Array<string>
+}
+/** Objects of a scene or prefab configured like a target GameObject */
+export interface SimilarObjectsReport {
+  file: string
+  target: SimilarObject
+  threshold: number
+  objectsChecked: number
+  /** Most similar first */
+  matches: Array<SimilarObject>
+}
+/** One finding of a scene health report */
+export interface HealthIssue {
+  /** "error" or "warning" */
+  severity: string
+  message: string
+  gameObjectId?: string | undefined
+  gameObjectName?: string | undefined
+}
+/** Score and findings of one health category */
+export interface HealthCategory {
+  /** "missing_references", "events", "physics", "canvas", "deprecated" or "lint" */
+  name: string
+  /** 0..100; each error costs 20 points, each warning 5 */
+  score: number
+  /** Share of the category in the overall score */
+  weight: number
+  errorCount: number
+  warningCount: number
+  /** The first findings (the counts cover all of them) */
+  issues: Array<HealthIssue>
+}
+/** Composite quality score of a scene or prefab (see `health_report`) */
+export interface SceneHealthReport {
+  file: string
+  /** 0..100: weighted mean of the category scores */
+  score: number
+  /** "A" (90+) to "F" (below 60) */
+  grade: string
+  issueCount: number
+  categories: Array<HealthCategory>
+  /** Checks left out and why (e.g. GUID references without a project) */
+  skipped: Array<string>
+}
+/**
+ * Optional limits on one project-wide scan; a scan that hits one returns what it has so far
+ * with `budget_exhausted` set
+ */
+export interface ResourceBudget {
+  /** Files read at most */
+  maxFiles?: number | undefined
+  /** Bytes read at most (file sizes on disk) */
+  maxBytes?: number | undefined
+  /** Wall time in milliseconds after which no further files are read */
+  maxMs?: number | undefined
+}
+/** Guardrails of safety mode (see `set_safety_config`) */
+export interface SafetyConfig {
+  /** Directories file access is confined to */
+  projectRoots: Array<string>
+  /** Allow writes inside the project roots (default false) */
+  allowWrites?: boolean | undefined
+  /** Bytes one call may read before its reads fail */
+  maxBytesPerCall?: number | undefined
+}
+/** Phase timings of one napi call (see `set_call_timing`) */
+export interface CallTiming {
+  /** napi function name */
+  call: string
+  total_ms: number
+  /** Reading files from disk */
+  io_ms: number
+  /** Splitting YAML into blocks and GameObjects */
+  parse_ms: number
+  /** Regex-driven component/property extraction */
+  regex_ms: number
+}
 /** Chunk types for indexing */
 export const enum ChunkType {
   Prose = 'Prose',
@@ -110,10 +1551,32 @@ export const enum ChunkType {
 /** Chunk metadata */
 export interface ChunkMetadata {
   filePath: string
+  /** Heading trail to the chunk, outermost first ("Physics > Rigidbody > AddForce") */
   section?: string | undefined
+  /** Level (1-6) of the chunk's nearest heading */
+  headingLevel?: number | undefined
   language?: string | undefined
   unityClass?: string | undefined
   unityMethod?: string | undefined
+  /** 1-based page of the PDF manual the chunk was extracted from */
+  page?: number | undefined
+  /** Document title from markdown frontmatter */
+  title?: string | undefined
+  /** Document tags from markdown frontmatter */
+  tags?: string[] | undefined
+  /** Unity version the document applies to (frontmatter `unity_version` or `version`) */
+  version?: string | undefined
+}
+/** Restricts `search` to chunks whose document frontmatter and heading trail match every given field */
+export interface DocSearchFilter {
+  /** Case-insensitive substring of the document title */
+  title?: string | undefined
+  /** Tags the document must all carry (case-insensitive) */
+  tags?: string[] | undefined
+  /** Version prefix: "2022" matches "2022.3" */
+  version?: string | undefined
+  /** Case-insensitive substring of the heading trail, e.g. "Physics >" for a manual chapter */
+  section?: string | undefined
 }
 /** A chunk of indexed content */
 export interface Chunk {
@@ -129,6 +1592,20 @@ export interface IndexResult {
   totalTokens: number
   filesProcessed: number
   elapsedMs: number
+  /** Chunks not stored because the index already holds the same content for the same file */
+  duplicatesSkipped: number
+}
+/** Result of compacting the index storage */
+export interface CompactResult {
+  /** Chunks merged into an earlier chunk with the same content and source file */
+  duplicatesRemoved: number
+  /** Chunks dropped because their source file no longer exists */
+  orphansRemoved: number
+  chunksRemaining: number
+  /** Storage file size before and after rewriting, in bytes */
+  bytesBefore: number
+  bytesAfter: number
+  bytesSaved: number
 }
 /** Search result from index */
 export interface SearchResult {
@@ -137,20 +1614,292 @@ export interface SearchResult {
   score: number
   metadata: ChunkMetadata
 }
+/** A document section whose title or file path matches a `find_sections` query */
+export interface SectionMatch {
+  filePath: string
+  section?: string | undefined
+  /** Where the query matched: "section", "path" or "both" */
+  matchedIn: string
+  chunkCount: number
+  totalTokens: number
+  /** IDs of the section's chunks, for pulling content */
+  chunkIds: Array<string>
+}
+/**
+ * Encode a JS value as a Unity YAML scalar or flow mapping (see `encode_value`), e.g.
+ * `0.1` → `0.1`, `true` → `1`, `"#Tag"` → `'#Tag'`, `{x: 0, y: 1, z: 0}` → `{x: 0, y: 1, z: 0}`,
+ * `{fileID: 2100000, guid, type: 2}` → `{fileID: 2100000, guid: ..., type: 2}`.
+ * Returns the encoded text, or an error object for values without a single-line form.
+ */
+export declare function encodeYamlValue(value: any): string | ErrorOutput
+/** Dependencies of one script on other scripts of the project. */
+export interface ScriptDependency {
+  /** Script path relative to the project root */
+  path: string
+  /** Assembly the script compiles into (asmdef name or a predefined Assembly-CSharp* name) */
+  assembly: string
+  /** Namespaces declared in the script */
+  namespaces: Array<string>
+  usings: Array<string>
+  /** Type names declared in the script */
+  declaredTypes: Array<string>
+  /** Project types the script references, as "Namespace.Type" (or "Type" in the global namespace) */
+  referencedTypes: Array<string>
+  /** Scripts declaring the referenced types */
+  dependsOn: Array<string>
+}
+/** Usage-derived dependencies of one assembly. */
+export interface AssemblyDependency {
+  name: string
+  /** Defining .asmdef path (None for predefined assemblies) */
+  asmdefPath?: string
+  scriptCount: number
+  /** References declared in the asmdef (implicit ones for predefined assemblies) */
+  declaredReferences: Array<string>
+  /** Assemblies whose types this assembly's scripts actually reference */
+  usedReferences: Array<string>
+  /** Used but not declared: these references fail to compile in Unity */
+  undeclaredReferences: Array<string>
+}
+/** Script and assembly dependency graph of a project. */
+export interface ScriptDependencyReport {
+  projectRoot: string
+  scripts: Array<ScriptDependency>
+  assemblies: Array<AssemblyDependency>
+  /** Groups of assemblies that depend on each other through script usage, each sorted by name */
+  assemblyCycles: Array<Array<string>>
+}
+/**
+ * Map every script under Assets/ to the project scripts it depends on, assign scripts to
+ * assemblies, and report assembly-level cycles and asmdef references that are used but missing.
+ */
+export declare function analyzeScriptDependencies(projectRoot: string): ScriptDependencyReport
+/**
+ * Extract type names from a single .NET DLL.
+ *
+ * Returns public types with their name and namespace.
+ * GUID is always None for DLL types (they have no .meta files).
+ */
+export declare function extractDllTypes(path: string): Array<CSharpTypeRef>
+/**
+ * Extract type info with fields from a single .NET DLL.
+ *
+ * Returns extended type info including serializable fields, base class,
+ * and struct/enum distinction via the Extends column.
+ */
+export declare function extractDllFields(path: string): Array<CSharpTypeInfo>
+/** One syntax problem in a C# script. */
+export interface CSharpLintIssue {
+  /** 1-based line */
+  line: number
+  /** 1-based column (characters) */
+  column: number
+  /**
+   * "unbalanced_delimiter", "unclosed_delimiter", "unterminated_string", "unterminated_char",
+   * "unterminated_comment" or "malformed_attribute"
+   */
+  kind: string
+  message: string
+}
+/** Syntax sanity result of a C# script. */
+export interface CSharpLintReport {
+  path: string
+  /** No issues found */
+  ok: boolean
+  issues: Array<CSharpLintIssue>
+}
+/**
+ * Check a .cs file for unbalanced braces/brackets/parentheses, unterminated strings, chars
+ * and block comments, and malformed attributes. Returns None if the file cannot be read.
+ */
+export declare function lintCsharp(path: string): CSharpLintReport | null
+/** Metrics of one script. */
+export interface ScriptMetrics {
+  /** Script path relative to the project root */
+  path: string
+  assembly: string
+  lines: number
+  /** Lines with code (not blank, not comment-only) */
+  codeLines: number
+  commentLines: number
+  typeCount: number
+  methodCount: number
+  serializedFieldCount: number
+  /** Rough cyclomatic complexity of the whole script */
+  complexity: number
+  maxMethodComplexity: number
+}
+/** Script metrics summed over one assembly. */
+export interface AssemblyMetrics {
+  name: string
+  scriptCount: number
+  lines: number
+  codeLines: number
+  commentLines: number
+  typeCount: number
+  methodCount: number
+  serializedFieldCount: number
+  complexity: number
+  maxMethodComplexity: number
+}
+/** Complexity of one method. */
+export interface MethodComplexity {
+  path: string
+  method: string
+  /** 1-based line of the declaration */
+  line: number
+  complexity: number
+}
+/** Metrics of every script under Assets/. */
+export interface ScriptMetricsReport {
+  projectRoot: string
+  scripts: Array<ScriptMetrics>
+  /** Sorted by name */
+  assemblies: Array<AssemblyMetrics>
+  /** The most complex methods of the project, most complex first */
+  hotspots: Array<MethodComplexity>
+}
+/**
+ * Lines of code, type, method and serialized field counts, and rough cyclomatic complexity
+ * of every script under Assets/, summed per assembly (asmdef or predefined Assembly-CSharp*),
+ * with the project's most complex methods as hotspots.
+ */
+export declare function scriptMetrics(projectRoot: string): ScriptMetricsReport
+/** A method declared directly in a type body. */
+export interface CSharpMethodInfo {
+  name: string
+  returnType: string
+  /** Parameter list as written (e.g. "Vector3 target, float speed = 1f") */
+  parameters: string
+  /** Compact one-line signature (e.g. "public void MoveTo(Vector3 target)") */
+  signature: string
+  isPublic: boolean
+  isStatic: boolean
+}
+/** Per-type part of a script summary. */
+export interface CSharpTypeSummary {
+  name: string
+  /** "class", "struct", "enum", or "interface" */
+  kind: string
+  namespace?: string
+  baseClass?: string
+  /** Implemented interfaces (remaining entries of the base list) */
+  interfaces: Array<string>
+  serializedFields: Array<CSharpFieldRef>
+  publicMethods: Array<CSharpMethodInfo>
+  /** Unity callbacks implemented by this type (any access level) */
+  unityMessages: Array<string>
+}
+/** Usage of a type-parameterised Unity API (e.g. GetComponent<Rigidbody>). */
+export interface CSharpApiUsage {
+  /** API name, e.g. "GetComponent", "AddComponent", "RequireComponent" */
+  api: string
+  typeName: string
+  count: number
+}
+/** Compact structured summary of a C# script. */
+export interface CSharpScriptSummary {
+  path: string
+  lineCount: number
+  usings: Array<string>
+  types: Array<CSharpTypeSummary>
+  apiUsages: Array<CSharpApiUsage>
+  /** First argument of each Instantiate call (field, local or expression head), deduplicated */
+  instantiateTargets: Array<string>
+}
+/**
+ * Summarize a C# script: types, base classes, serialized fields, public methods,
+ * implemented Unity messages and Unity API usage. Returns None if the file cannot be read.
+ */
+export declare function summarizeScript(path: string): CSharpScriptSummary | null
+/** A type or member declaration. */
+export interface CSharpSymbol {
+  name: string
+  /**
+   * "class", "struct", "interface", "enum", "method", "constructor", "property", "field",
+   * "event" or "enum_member"
+   */
+  kind: string
+  /** Declaring type for members and nested types ("Outer.Inner" for nested declaring types) */
+  container?: string
+  namespace?: string
+  /** Script path relative to the project root */
+  filePath: string
+  /** 1-based line and column of the name */
+  line: number
+  column: number
+  /** Method signature, or the declared type of fields, properties and events */
+  signature?: string
+}
+/** One occurrence of an identifier. */
+export interface CSharpSymbolUsage {
+  filePath: string
+  line: number
+  column: number
+  /** The source line, trimmed */
+  text: string
+}
+/** Outcome of an incremental index refresh. */
+export interface SymbolIndexStats {
+  indexPath: string
+  fileCount: number
+  /** Scripts parsed in this refresh (new or changed) */
+  filesUpdated: number
+  filesRemoved: number
+  symbolCount: number
+}
+/**
+ * Build (or incrementally refresh) the project's symbol index. `include_packages` also indexes
+ * Library/PackageCache/ and Packages/ scripts; when omitted the previous setting is kept.
+ */
+export declare function updateSymbolIndex(projectRoot: string, includePackages?: boolean | undefined | null): any
+/**
+ * Go to definition: declarations named `name` (a simple name, or qualified like
+ * `Enemy.TakeDamage` / `Game.Enemy`). Partial types yield one entry per declaring file.
+ */
+export declare function findSymbolDefinitions(projectRoot: string, name: string): Array<CSharpSymbol>
+/**
+ * Members declared in the type `type_name` (simple or qualified name) across all of its
+ * partial declarations, in file and line order. Nested types are listed as members too.
+ */
+export declare function listTypeMembers(projectRoot: string, typeName: string): Array<CSharpSymbol>
 /**
- * Extract type names from a single .NET DLL.
- *
- * Returns public types with their name and namespace.
- * GUID is always None for DLL types (they have no .meta files).
+ * Find usages: occurrences of the symbol's identifier (the last segment of a qualified name)
+ * outside comments, strings and the symbol's own declarations.
  */
-export declare function extractDllTypes(path: string): Array<CSharpTypeRef>
+export declare function findSymbolUsages(projectRoot: string, name: string, limit?: number | undefined | null): Array<CSharpSymbolUsage>
+/** One obsolete API use. */
+export interface ObsoleteApiUsage {
+  /** Script path relative to the project root */
+  filePath: string
+  /** 1-based line and column (characters) */
+  line: number
+  column: number
+  ruleId: string
+  /** The obsolete source text */
+  api: string
+  replacement: string
+  note?: string
+  /** The source line, trimmed */
+  text: string
+  /** The source line with the replacement applied, trimmed */
+  suggested: string
+}
+/** Result of an obsolete API scan. */
+export interface ObsoleteApiReport {
+  projectRoot: string
+  filesScanned: number
+  ruleCount: number
+  usages: Array<ObsoleteApiUsage>
+  /** Config rules that were skipped because their pattern does not compile */
+  ruleErrors: Array<string>
+}
 /**
- * Extract type info with fields from a single .NET DLL.
- *
- * Returns extended type info including serializable fields, base class,
- * and struct/enum distinction via the Extends column.
+ * Scan the project's scripts under Assets/ for obsolete Unity APIs, with a suggested
+ * replacement for each use. The built-in mapping is extended (or overridden by id) with
+ * `obsolete_apis` rules from the global and project `.unity-agentic/config.json`.
  */
-export declare function extractDllFields(path: string): Array<CSharpTypeInfo>
+export declare function scanObsoleteApis(projectRoot: string): ObsoleteApiReport
 /** A serializable field extracted from a C# type. */
 export interface CSharpFieldRef {
   /** Field name (e.g., "health", "moveSpeed") */
@@ -199,6 +1948,14 @@ export interface CSharpTypeRef {
  * with their namespace context and the GUID from the adjacent .meta file.
  */
 export declare function extractCsharpTypes(path: string): Array<CSharpTypeRef>
+/** Type registry of a budgeted scan (see `build_type_registry_with_budget`). */
+export interface TypeRegistryResult {
+  types: Array<CSharpTypeRef>
+  /** Source files and DLLs read */
+  filesScanned: number
+  /** Files were left unread because the budget ran out */
+  budgetExhausted: boolean
+}
 /**
  * Build a type registry by scanning all .cs files in a Unity project.
  *
@@ -208,6 +1965,11 @@ export declare function extractCsharpTypes(path: string): Array<CSharpTypeRef>
  * When include_dlls is true, also extracts types from DLLs in Library/ScriptAssemblies/.
  */
 export declare function buildTypeRegistry(projectRoot: string, includePackages?: boolean | undefined | null, includeDlls?: boolean | undefined | null): Array<CSharpTypeRef>
+/**
+ * `build_type_registry` stopping at a resource budget (files, bytes, wall time), returning
+ * the types found so far with `budget_exhausted` set.
+ */
+export declare function buildTypeRegistryWithBudget(projectRoot: string, includePackages?: boolean | undefined | null, includeDlls?: boolean | undefined | null, budget?: ResourceBudget | undefined | null): TypeRegistryResult
 /**
  * Extract serialized field info from a single C# source file.
  *
@@ -215,19 +1977,232 @@ export declare function buildTypeRegistry(projectRoot: string, includePackages?:
  * This is called on-demand during component creation, not during registry builds.
  */
 export declare function extractSerializedFields(path: string): Array<CSharpTypeInfo>
+/** One frame of an exception stack trace. */
+export interface LogStackFrame {
+  /** Method as printed (e.g. "Player.Update", "Player:Update") */
+  method: string
+  /** Script path from the frame, or resolved from the declaring type through the project's scripts */
+  file?: string
+  line?: number
+}
+/** A compiler diagnostic, exception or import warning from a Unity log. */
+export interface LogEntry {
+  /** "compile_error", "compile_warning", "exception" or "import_warning" */
+  kind: string
+  /** 1-based line in the log where the entry first appears */
+  logLine: number
+  message: string
+  /** Script or asset the entry is about */
+  file?: string
+  line?: number
+  column?: number
+  /** Compiler code (CS0103) or exception type (NullReferenceException) */
+  code?: string
+  stack: Array<LogStackFrame>
+  /** Times the identical entry was logged (Unity repeats compile errors on every refresh) */
+  occurrences: number
+}
+/** Size of one category or asset in a build report. */
+export interface BuildReportItem {
+  name: string
+  /** Size as printed (e.g. "12.3 mb") */
+  size: string
+  percent: number
+}
+/** The "Build Report" section Unity logs after a player build. */
+export interface BuildReportSummary {
+  /** "Succeeded", "Failed", "Cancelled", ... */
+  result?: string
+  durationMs?: number
+  totalSize?: string
+  categories: Array<BuildReportItem>
+  /** Largest assets, as listed by Unity (at most 20) */
+  largestAssets: Array<BuildReportItem>
+}
+/** Structured contents of a Unity log. */
+export interface LogReport {
+  file: string
+  compileErrors: number
+  compileWarnings: number
+  exceptions: number
+  importWarnings: number
+  entries: Array<LogEntry>
+  buildReports: Array<BuildReportSummary>
+}
+/**
+ * Parse a Unity Editor.log or Player.log (default: this platform's Editor.log).
+ *
+ * With `project_root`, stack frames without a source location are resolved to the script
+ * declaring their type. Returns None when the log cannot be read.
+ */
+export declare function parseUnityLog(path?: string | undefined | null, projectRoot?: string | undefined | null): LogReport | null
+/** One `#include` directive. */
+export interface ShaderInclude {
+  /** Target as written */
+  target: string
+  line: number
+  /** Project-relative path of the included file */
+  resolved?: string
+  /**
+   * "resolved", "builtin" (Unity's own CGIncludes), "unverified" (a package that is not
+   * present locally) or "missing"
+   */
+  status: string
+}
+/** A shader source file and its include edges. */
+export interface ShaderSourceFile {
+  /** Path relative to the project root */
+  path: string
+  includes: Array<ShaderInclude>
+  /** Files that include this one */
+  includedBy: Array<string>
+}
+/** An include target that resolves to no file. */
+export interface MissingShaderInclude {
+  file: string
+  line: number
+  target: string
+}
+/** Include graph of a project's shader sources. */
+export interface ShaderIncludeReport {
+  projectRoot: string
+  files: Array<ShaderSourceFile>
+  missing: Array<MissingShaderInclude>
+  /** Groups of files that include each other (directly or transitively), each sorted by path */
+  cycles: Array<Array<string>>
+}
+/**
+ * Build the `#include` graph across the project's shader sources (Assets/ and embedded or local
+ * packages), flagging include targets that resolve to no file and include cycles.
+ *
+ * Targets starting with `Assets/` or `Packages/` resolve from the project root (packages also
+ * through Library/PackageCache), anything else relative to the including file.
+ */
+export declare function analyzeShaderIncludes(projectRoot: string): ShaderIncludeReport
+/** One asset or folder in a package. */
+export interface UnityPackageEntry {
+  guid: string
+  /** Project path the asset imports to (e.g. "Assets/Plugins/Tool/Tool.dll") */
+  pathname: string
+  /** Uncompressed size of the asset; unset for folders */
+  size?: number
+  isFolder: boolean
+  hasMeta: boolean
+  hasPreview: boolean
+  /**
+   * Against the project: "new", "update" (same GUID at the same path), "guid_conflict"
+   * (the GUID belongs to another project asset) or "path_conflict" (another asset exists at
+   * the path). Unset when no project root is given.
+   */
+  status?: string
+  /** Project path currently holding this GUID, when it differs from `pathname` */
+  existingPath?: string
+}
+export interface UnityPackageReport {
+  success: boolean
+  error?: string
+  path: string
+  /** Sorted by pathname */
+  entries: Array<UnityPackageEntry>
+  assetCount: number
+  folderCount: number
+  /** Uncompressed size of all assets */
+  totalBytes: number
+  /** Entries with a GUID or path conflict */
+  collisionCount: number
+}
+/**
+ * List the assets of a `.unitypackage` (paths, GUIDs, sizes) without extracting it. With
+ * `project_root`, each entry is checked against the project: assets whose GUID already
+ * belongs to a different project asset, or whose path is taken by another asset, are
+ * reported as collisions.
+ */
+export declare function readUnitypackage(path: string, projectRoot?: string | undefined | null): UnityPackageReport
+/** A changed path with its category. */
+export interface VcsChangedFile {
+  /** Project-relative path, forward slashes */
+  path: string
+  /** "scene", "prefab", "meta", "settings", "code", "asset" or "other" */
+  category: string
+  /** Present on disk (false for deletions) */
+  exists: boolean
+  /** Serialized as Unity YAML: merge with UnityYAMLMerge (Smart Merge), not line-based merging */
+  unityYaml: boolean
+}
+/** A risky combination of changes. */
+export interface VcsWarning {
+  /**
+   * "asset_without_meta", "meta_deleted_asset_present", "asset_deleted_meta_kept",
+   * "orphan_meta" or "prefab_and_instance_changed"
+   */
+  kind: string
+  /** "error" (the project breaks) or "warning" (review needed) */
+  severity: string
+  path: string
+  /** Other paths involved (the .meta, the changed instances of a prefab) */
+  related: Array<string>
+  message: string
+}
+export interface VcsChangeReport {
+  projectRoot: string
+  files: Array<VcsChangedFile>
+  /** Changed files per category */
+  categoryCounts: Record<string, number>
+  /** Changed files that need Unity's YAML merge handling */
+  unityYamlCount: number
+  warnings: Array<VcsWarning>
+}
+/**
+ * Classify `changed_paths` (project-relative or absolute, e.g. from `git diff --name-only`)
+ * and flag combinations that break the project or need a careful merge: an asset changed
+ * while its `.meta` is missing, a `.meta` deleted while its asset stays (its GUID is
+ * regenerated and every reference breaks), an asset deleted while its `.meta` stays, and a
+ * prefab changed together with scenes or prefabs that instantiate or derive from it.
+ */
+export declare function vcsClassifyChanges(projectRoot: string, changedPaths: Array<string>): VcsChangeReport
+export interface NapiFileTypeGroup {
+  name: string
+  extensions: Array<string>
+  /** Defined in the project's `.unity-agentic/config.json` */
+  custom: boolean
+}
+/**
+ * File-type groups grep accepts as `file_type`: the project's custom `file_types` (from
+ * `.unity-agentic/config.json`) and the built-in groups, so callers need no mirror of them.
+ */
+export declare function listFileTypeGroups(projectPath?: string | undefined | null): Array<NapiFileTypeGroup>
+/** Options controlling which roots the project walk covers. */
+export interface NapiWalkOptions {
+  /**
+   * Also walk embedded packages in `Packages/` and local `file:` packages
+   * referenced from `Packages/manifest.json` (default: false)
+   */
+  includePackages?: boolean
+  /** Follow symlinked files and directories; symlink loops are detected and skipped (default: false) */
+  followSymlinks?: boolean
+}
 /**
  * Walk a Unity project and collect files matching the given extensions.
  *
  * Walks `Assets/` (and `ProjectSettings/` when `.asset` is among extensions).
- * Skips standard Unity noise directories (Library, Temp, etc.).
+ * Skips standard Unity noise directories (Library, Temp, etc.) and the `skip_dirs` of the
+ * global and project configs (see `DirFilter`); `exclude_dirs` adds rules for this call.
+ * With `include_packages`, embedded and local `file:` packages are walked as
+ * additional asset roots.
  */
-export declare function walkProjectFiles(projectPath: string, extensions: Array<string>, excludeDirs?: Array<string> | undefined | null): Array<string>
+export declare function walkProjectFiles(projectPath: string, extensions: Array<string>, excludeDirs?: Array<string> | undefined | null, options?: NapiWalkOptions | undefined | null): Array<string>
 export interface NapiGrepOptions {
   projectPath: string
   pattern: string
   fileType?: string
   maxResults?: number
   contextLines?: number
+  /** Skip files larger than this many bytes (default: 10 MB) */
+  maxFileSize?: number
+  /** Search exactly these extensions instead of a `file_type` group */
+  extensions?: Array<string>
+  /** Limits on files, bytes and time searched */
+  budget?: ResourceBudget
 }
 export interface NapiGrepMatch {
   file: string
@@ -235,6 +2210,11 @@ export interface NapiGrepMatch {
   line: string
   contextBefore?: Array<string>
   contextAfter?: Array<string>
+  /**
+   * File-type group of the file: the requested `file_type`, else the narrowest group
+   * containing its extension
+   */
+  fileType?: string
 }
 export interface NapiGrepResult {
   success: boolean
@@ -245,9 +2225,53 @@ export interface NapiGrepResult {
   truncated: boolean
   matches: Array<NapiGrepMatch>
   error?: string
+  /** Files skipped because they exceed `max_file_size` */
+  skippedLargeFiles: number
+  /** Files skipped because their content looks binary (NUL bytes) */
+  skippedBinaryFiles: number
+  /** Files were left unsearched because the budget ran out */
+  budgetExhausted: boolean
 }
 /** Grep across Unity project files in parallel using Rayon. */
 export declare function grepProject(options: NapiGrepOptions): NapiGrepResult
+export interface NapiValueChange {
+  lineNumber: number
+  fileId: string
+  className: string
+  /** Dotted key path of the edited property (e.g. "m_Modification.m_Modifications.value") */
+  property: string
+  oldLine: string
+  newLine: string
+}
+export interface NapiFileReplacement {
+  file: string
+  changes: Array<NapiValueChange>
+  /** Unified diff of the file change */
+  diff: string
+}
+export interface NapiReplaceResult {
+  success: boolean
+  projectPath: string
+  selector: string
+  dryRun: boolean
+  totalFilesScanned: number
+  filesChanged: number
+  totalChanges: number
+  files: Array<NapiFileReplacement>
+  error?: string
+}
+/**
+ * Replace property values across every scene, prefab and YAML asset of a project.
+ *
+ * `selector` is `[ClassName.]property[.sub]` (e.g. `m_Layer`, `MeshRenderer.m_Materials`).
+ * A value matches when it equals `old_value`, or when it is an object reference whose guid is
+ * `old_value` (retargeting asset references). Only the matched value text is rewritten.
+ * `new_value` is written as a YAML value: numbers, JSON objects (`{"x": 1}` → `{x: 1}`) and
+ * JSON strings are encoded like `encode_yaml_value`, other text is quoted when YAML needs it.
+ * Binary files are skipped; a file that cannot be read fails the call before anything is
+ * written. With `dry_run` (default: true) nothing is written and the result carries the diff report.
+ */
+export declare function replacePropertyValues(projectPath: string, selector: string, oldValue: string, newValue: string, dryRun?: boolean | undefined | null): NapiReplaceResult
 /**
  * Build the GUID cache by scanning all .meta files under Assets/ in parallel.
  *
@@ -270,6 +2294,186 @@ export declare function buildPackageGuidCache(projectRoot: string): any
  * build_package_guid_cache (Library/PackageCache/).
  */
 export declare function buildLocalPackageGuidCache(projectRoot: string): any
+export interface NapiFileHash {
+  /** Path relative to the project root, with forward slashes */
+  path: string
+  hash: string
+}
+export interface NapiProjectHash {
+  projectRoot: string
+  /** Combined hash over the sorted (path, hash) pairs of all matched files */
+  hash: string
+  fileCount: number
+  files: Array<NapiFileHash>
+}
+/**
+ * Stable content hash of one file (see `hash_project` for normalization).
+ * Returns None when the file cannot be read.
+ */
+export declare function hashAsset(file: string): string | null
+/**
+ * Stable content hashes for the files under `Assets/`, `ProjectSettings/` and `Packages/`,
+ * optionally limited to paths matching any of `globs` (e.g. `Assets/**/*.prefab`, `*.unity`).
+ *
+ * Line endings are normalized and volatile fields (`timeCreated`, `licenseType`) are ignored,
+ * so the combined hash only changes when relevant content does. Library/Temp and other
+ * noise directories are skipped.
+ */
+export declare function hashProject(projectRoot: string, globs?: Array<string> | undefined | null): NapiProjectHash
+export interface NapiSnapshotCount {
+  name: string
+  count: number
+}
+export interface NapiSnapshotScene {
+  path: string
+  enabled: boolean
+}
+export interface NapiSnapshotPackage {
+  name: string
+  /** Version, or the `file:`/git reference for non-registry packages */
+  version: string
+}
+export interface NapiProjectSnapshot {
+  projectRoot: string
+  unityVersion?: string
+  /** Scenes in EditorBuildSettings, in build order */
+  buildScenes: Array<NapiSnapshotScene>
+  /** Top-level folders under Assets/ with their file counts (.meta excluded), largest first */
+  folders: Array<NapiSnapshotCount>
+  /** Asset files per extension, most common first */
+  assetCounts: Array<NapiSnapshotCount>
+  /** Packages/manifest.json dependencies */
+  packages: Array<NapiSnapshotPackage>
+  /** C# types declared under Assets/, per kind (class, struct, enum, interface) */
+  typeCounts: Array<NapiSnapshotCount>
+  /** Entries dropped to stay within the budget, per section (e.g. "folders: 14 more") */
+  omitted: Array<string>
+  /** Size of this snapshot as JSON, in bytes */
+  sizeBytes: number
+}
+/**
+ * One bounded overview of a Unity project for an agent's context at session start: Unity
+ * version, build scenes, top-level Assets/ layout, asset counts per extension, packages and
+ * C# type counts. `budget` caps the serialized size in bytes (default 8000); when exceeded,
+ * folders, then packages, then asset counts, then build scenes are cut from the end and the
+ * dropped counts are listed in `omitted`.
+ */
+export declare function projectSnapshot(projectRoot: string, budget?: number | undefined | null): NapiProjectSnapshot
+/**
+ * Set the stderr log level: "off", "error", "warn", "info" (one line per call with its
+ * duration), "debug" (plus parsing phases) or "trace". Returns false for an unknown level or
+ * when the host process already installed its own tracing subscriber.
+ */
+export declare function setLogLevel(level: string): boolean
+/**
+ * Enable or disable per-call phase timings. While enabled, every napi call that parses Unity
+ * files writes `{"timing": {...}}` as one JSON line to stderr.
+ */
+export declare function setCallTiming(enabled: boolean): void
+/** Phase timings of the most recent call (on this thread) made while timing was enabled. */
+export declare function getLastCallTiming(): CallTiming | null
+/**
+ * Turn on safety mode: file access confined to `project_roots`, writes denied unless
+ * `allow_writes`, and each call failing reads past `max_bytes_per_call`. Replaces any earlier
+ * config.
+ */
+export declare function setSafetyConfig(config: SafetyConfig): any
+/**
+ * Add a project root to the safety config, turning safety mode on (read-only, no read
+ * budget) if it was off. Returns false when the directory does not exist.
+ */
+export declare function registerProjectRoot(projectRoot: string): boolean
+/** The active safety config, or None when safety mode is off. */
+export declare function getSafetyConfig(): SafetyConfig | null
+/** Turn safety mode off. */
+export declare function clearSafetyConfig(): void
+/** A build of the project index. */
+export interface SearchIndexBuild {
+  success: boolean
+  error?: string
+  indexPath: string
+  /** Scenes, prefabs and YAML assets in the project */
+  filesTotal: number
+  /** Files (re)indexed by this build */
+  filesUpdated: number
+  /** Files dropped from the index because they no longer exist */
+  filesRemoved: number
+  /** Serialized objects in the index */
+  documents: number
+}
+/** One serialized object matching a query. */
+export interface PropertySearchHit {
+  /** Project-relative path, forward slashes */
+  path: string
+  fileId: string
+  classId: number
+  /** Unity type name (e.g. "Light", "MonoBehaviour") */
+  type: string
+  /** Owning GameObject (the object itself for GameObjects); unset for assets */
+  gameObjectId?: string
+  gameObjectName?: string
+  score: number
+  /**
+   * Field renames across Unity versions the object was also indexed under, e.g. its m_Drag
+   * searchable as m_LinearDamping
+   */
+  aliasesApplied: Array<string>
+}
+export interface PropertySearchResult {
+  success: boolean
+  error?: string
+  query: string
+  /** All matches; `hits` holds the best `limit` of them */
+  totalHits: number
+  hits: Array<PropertySearchHit>
+}
+/**
+ * Build or refresh the project's property search index under `.unity-agentic/search-index/`.
+ * Only scenes, prefabs and YAML assets changed since the last build are re-indexed, and
+ * deleted ones are dropped.
+ */
+export declare function buildSearchIndex(projectRoot: string): SearchIndexBuild
+/**
+ * Serialized objects matching `query` (best `limit` first, default 100). Terms are ANDed
+ * unless joined with `OR`; fields are `type`, `class_id`, `name` (owning GameObject),
+ * `path`, `file_id`, `game_object_id`, and any property path, e.g.
+ * `m_Intensity:[2 TO *] AND type:Light`, `m_Script.guid:<guid>`, `m_LocalPosition.y:[* TO 0}`.
+ * Builds the index on first use; call `build_search_index` to pick up later edits.
+ */
+export declare function searchProjectIndex(projectRoot: string, query: string, limit?: number | undefined | null): PropertySearchResult
+/** A GUID and the asset path it maps to. */
+export interface GuidMapping {
+  guid: string
+  /** Asset path relative to the project root */
+  path: string
+}
+/** A GUID whose asset moved or was renamed. */
+export interface GuidPathChange {
+  guid: string
+  previousPath: string
+  path: string
+}
+/** Changes to a project's GUID map since a sync token. */
+export interface GuidChanges {
+  /** Pass to the next call to receive only later changes */
+  token: string
+  /**
+   * True when `since_token` was missing, unknown or too old: `added` holds the whole map and
+   * the caller should replace its mirror
+   */
+  fullResync: boolean
+  added: Array<GuidMapping>
+  removed: Array<GuidMapping>
+  modified: Array<GuidPathChange>
+  error?: string | undefined
+}
+/**
+ * Added, removed and moved GUID → path mappings of a project's Assets/ since `since_token`
+ * (from a previous call). Without a token, or with one the database cannot answer (another
+ * epoch, or older than its journal), `full_resync` is set and `added` holds the whole map.
+ * The database is refreshed first, re-reading only changed `.meta` files.
+ */
+export declare function getGuidChanges(projectRoot: string, sinceToken?: string | undefined | null): GuidChanges
 /** Get the version of the native module */
 export declare function getVersion(): string
 /** Check if the native module is available */
@@ -281,31 +2485,444 @@ export declare class Scanner {
   addHierarchyProvider(classId: number): void
   /** Add a script container class ID (MonoBehaviour-like components). */
   addScriptContainer(classId: number): void
-  /** Set project root for GUID resolution */
-  setProjectRoot(path: string): void
+  /** Add a field name that holds script references (in addition to m_Script). */
+  addScriptField(field: string): void
+  /** Hide a property (e.g. "m_EditorHideFlags") on every component. */
+  addHiddenProperty(name: string): void
+  /**
+   * Only include the given properties for a component, identified by class ID ("4"),
+   * type name ("Transform") or script name ("PlayerController").
+   */
+  setPropertyWhitelist(component: string, properties: Array<string>): void
+  /** Always exclude the given properties for a component (class ID, type name or script name). */
+  setPropertyBlacklist(component: string, properties: Array<string>): void
+  /** Remove the whitelist/blacklist of a component. */
+  clearPropertyFilter(component: string): void
+  /** Restrict Transform/RectTransform/Camera/Light output to their key properties. */
+  useCompactPropertyPresets(): void
+  /**
+   * Set project root for GUID resolution. `options.guid_cache` warm-starts the resolver from a
+   * prebuilt `{ guid: path }` object or a persisted JSON file (falling back to a scan when the
+   * file cannot be read); `options.include_packages` also resolves Packages/ and
+   * Library/PackageCache/ assets (project assets win on conflicts).
+   */
+  setProjectRoot(path: string, options?: ProjectRootOptions | undefined | null): void
+  /**
+   * Validate GameObject tags and layers against the project's TagManager.asset during
+   * inspect/inspect_all: details get a `warnings` array for undefined tags and unnamed or
+   * out-of-range layers. Returns whether a TagManager was found (it is also looked up when
+   * the project root is set or detected later).
+   */
+  setTagValidation(enabled: boolean): boolean
+  /** Project-relative asset path of a GUID, if cached. */
+  getGuidPath(guid: string): string | null
+  /** GUID of an asset path (project-relative, or absolute under the project root), if cached. */
+  getPathGuid(path: string): string | null
+  /** Number of GUIDs in the cache. */
+  cacheSize(): number
+  /**
+   * Replace the GUID cache with a `{ guid: relative_path }` object (e.g. the output of
+   * `buildGuidCache`, persisted by the caller), so this Scanner skips its own Assets/ scan.
+   * Non-string entries are ignored. Returns the number of entries loaded.
+   */
+  setGuidCache(cache: any): number
+  /**
+   * Map GUIDs of moved or replaced assets to their successor: `{ old_guid: new_guid_or_path }`,
+   * or the path of such a JSON file (relative paths resolve against the project root).
+   * References to an old GUID are then reported as "moved to X" instead of missing.
+   * Replaces earlier aliases; returns the number of entries loaded.
+   */
+  setGuidAliases(aliases: any): number
+  /**
+   * Load an earlier `{ guid: relative_path }` GUID cache (e.g. a persisted `buildGuidCache`
+   * result from before a refactor), or the path of one, as the project's .meta history:
+   * a stale GUID resolves to the asset now at its former path, or to the only asset with
+   * its former file name. Snapshots accumulate, later ones winning; returns the entries loaded.
+   */
+  loadGuidHistory(snapshot: any): number
+  /**
+   * What became of an asset GUID: its current path, or for a GUID no longer in the project,
+   * where the alias map or GUID history says it went. None when nothing is known about it.
+   */
+  resolveGuid(guid: string): GuidResolution | null
+  /**
+   * Load a ComponentConfig JSON or `.toml` file (custom class IDs, script fields, property filters,
+   * class-name mappings). Defaults to `<project_root>/.unity-agentic/config.json`.
+   * The project config is also loaded automatically when the project root is set or detected.
+   */
+  loadConfig(path?: string | undefined | null): any
   /** Scan scene for basic GameObject information */
   scanSceneMinimal(file: string): Array<GameObject>
+  /** Scan in-memory scene content for basic GameObject information */
+  scanSceneMinimalFromContent(content: string): Array<GameObject>
   /** Scan scene with component information */
-  scanSceneWithComponents(file: string, options?: ScanOptions | undefined | null): Array<any>
+  scanSceneWithComponents(file: string, options?: ScanOptions | undefined | null): Array<SceneObjectOutput | PrefabInstanceOutput>
+  /**
+   * Scan in-memory scene content with component information.
+   * `file` is only used to locate the project for script GUID resolution.
+   */
+  scanSceneWithComponentsFromContent(file: string, content: string, options?: ScanOptions | undefined | null): Array<SceneObjectOutput | PrefabInstanceOutput>
   /**
    * Scan scene for GO metadata (name, tag, layer) without component/hierarchy extraction.
    * This is the "medium path" — faster than scan_scene_with_components for tag/layer filtering.
    */
   scanSceneMetadata(file: string): Array<any>
-  /** Find GameObjects and PrefabInstances by name pattern */
+  /** Scan in-memory scene content for GO metadata (name, tag, layer) */
+  scanSceneMetadataFromContent(content: string): Array<any>
+  /**
+   * Find GameObjects and PrefabInstances by name pattern.
+   * Exact and glob lookups use the scene's sidecar index when one has been built.
+   */
   findByName(file: string, pattern: string, fuzzy: boolean): Array<FindResult>
+  /**
+   * Find GameObjects and PrefabInstances by name pattern in in-memory content. Each result
+   * carries its hierarchy path and parent name to tell same-named objects apart.
+   */
+  findByNameFromContent(file: string, content: string, pattern: string, fuzzy: boolean): Array<FindResult>
+  /**
+   * Build (or refresh) the sidecar name/tag/component-type index of a scene or prefab under
+   * `.unity-agentic/scene-index/`. Once built, `find_by_name` answers from it until the file's
+   * mtime or size changes, at which point it is rebuilt on the next lookup.
+   */
+  buildSceneIndex(file: string): any
+  /**
+   * GameObjects/PrefabInstances matching all given criteria (exact name, tag, component type
+   * or script name), answered from the sidecar index (built on first use).
+   */
+  querySceneIndex(file: string, name?: string | undefined | null, tag?: string | undefined | null, componentType?: string | undefined | null): Array<FindResult>
+  /**
+   * Rows for the GameObjects matching `selector` (all when omitted) and the optional boolean
+   * `filter` expression, with computed columns such as `pos = transform.localPosition` or
+   * `dist = length(transform.localPosition)` evaluated natively (see `scanner::query`).
+   */
+  queryGameobjects(file: string, selector?: GameObjectSelector | undefined | null, columns?: Array<string> | undefined | null, filter?: string | undefined | null): Array<Record<string, any>> | ErrorOutput
   /** Inspect a specific GameObject */
-  inspect(options: InspectOptions): any | null
+  inspect(options: InspectOptions): GameObjectInspection | PrefabInstanceOutput | ErrorOutput | null
+  /**
+   * Inspect a specific GameObject in in-memory content.
+   * `options.file` is only used to locate the project for GUID resolution.
+   */
+  inspectFromContent(options: InspectOptions, content: string): GameObjectInspection | PrefabInstanceOutput | ErrorOutput | null
   /** Inspect entire file */
   inspectAll(file: string, includeProperties: boolean, verbose: boolean): SceneInspection
+  /** Inspect entire in-memory file content */
+  inspectAllFromContent(file: string, content: string, includeProperties: boolean, verbose: boolean): SceneInspection
+  /**
+   * Inspect entire file, keeping only the given fields of each GameObject and PrefabInstance
+   * (dotted paths such as ["name", "tag", "components.type"]). Selection happens before the
+   * result crosses into JS, so targeted queries on big scenes marshal far less data.
+   */
+  inspectAllFields(file: string, fields: Array<string>, includeProperties?: boolean | undefined | null, verbose?: boolean | undefined | null): { file: string; count: number; gameobjects: Partial<GameObjectDetail>[]; prefab_instances?: Partial<PrefabInstanceInfo>[] }
   /** Inspect entire file with pagination support */
   inspectAllPaginated(options: PaginationOptions): PaginatedInspection
+  /**
+   * Inspect in-memory file content with pagination support.
+   * `options.file` is only used for labeling and GUID resolution.
+   */
+  inspectAllPaginatedFromContent(options: PaginationOptions, content: string): PaginatedInspection
+  /**
+   * Build the fully expanded hierarchy of a scene or prefab.
+   * PrefabInstances are recursively replaced by their source prefab's hierarchy
+   * (up to `max_nesting_depth`, default 3); every node records its source file.
+   * Siblings follow document order, or the editor's Hierarchy window order with
+   * `sort: "hierarchy"`.
+   */
+  inspectExpanded(file: string, maxNestingDepth?: number | undefined | null, sort?: string | undefined | null): Array<NestedPrefabNode>
+  /** Build the fully expanded hierarchy of in-memory scene or prefab content */
+  inspectExpandedFromContent(file: string, content: string, maxNestingDepth?: number | undefined | null, sort?: string | undefined | null): Array<NestedPrefabNode>
+  /**
+   * Count the blocks of a scene/prefab per class ID from its block headers alone, without
+   * parsing bodies: the cheapest way to see how big a file is before choosing how to query it.
+   */
+  countObjects(file: string): ObjectCounts | null
+  /** Count blocks per class ID of in-memory content. `file` is only used for labeling. */
+  countObjectsFromContent(file: string, content: string): ObjectCounts
+  /** Compute aggregate statistics (GameObjects, components, prefab instances, scripts) for a file. */
+  sceneStats(file: string): SceneStats | null
+  /**
+   * Compute scene statistics from in-memory content (e.g. a file at a git revision).
+   * `file` is used for labeling and to locate the project for script GUID resolution.
+   */
+  sceneStatsFromContent(file: string, content: string): SceneStats
+  /**
+   * Compare scene statistics between two versions of the same file
+   * (e.g. `git show HEAD~1:Assets/Main.unity` vs the working copy).
+   */
+  diffSceneStats(file: string, oldContent: string, newContent: string): SceneStatsDiff
+  /**
+   * Descendant count, depth, breadth and per-type component totals of the subtree under a
+   * GameObject ("how heavy is this UI panel?"). Returns None when the file or GameObject is missing.
+   */
+  subtreeMetrics(file: string, goId: string): SubtreeMetrics | null
+  /**
+   * Local and world position/rotation/scale, ancestor names and sibling index of a
+   * GameObject ("where is it?") without inspecting its properties. Returns None when the
+   * file or GameObject is missing.
+   */
+  getTransformSummary(file: string, goId: string): TransformSummary | null
+  /**
+   * World-space box around every placed GameObject and prefab instance of a scene
+   * ("how big is this level?"), optionally per layer or tag, with a coarse occupancy grid
+   * for top-down overviews. Returns None when the file cannot be read.
+   */
+  computeSceneBounds(file: string, options?: SceneBoundsOptions | undefined | null): SceneBoundsReport | null
+  /**
+   * Rough memory/build impact of a scene or prefab: serialized size, instance counts and the
+   * transitive texture/mesh/audio/... dependencies costed from file size and import settings.
+   */
+  estimateSceneCost(file: string): SceneCostReport | null
+  /**
+   * Sorted list of every external GUID a scene or prefab references, with the resolved
+   * project path, asset category and reference count. Unlike `estimate_scene_cost`, only
+   * direct references are listed, so the manifest can be cached per revision and diffed.
+   */
+  exportGuidManifest(file: string): GuidManifest | null
+  /**
+   * Pixel dimensions (PNG/JPEG/GIF/BMP/PSD/TGA header) and sprite rects (from the .meta) of a
+   * texture asset. `path` may be absolute or relative to the project root.
+   */
+  getTextureInfo(path: string): TextureInfo | null
+  /**
+   * Check a material's m_SavedProperties against the Properties block of its shader: stale
+   * entries, entries stored under the wrong type, and declared properties without a value.
+   * `shader` defaults to the shader the material references (resolved by GUID in the project);
+   * built-in and Shader Graph shaders have no parsable source and must be passed explicitly.
+   */
+  checkMaterialShader(material: string, shader?: string | undefined | null): MaterialShaderCheck | ErrorOutput
+  /**
+   * Canonical form of a scene/prefab for semantic diffing (blocks sorted by fileID, whitespace
+   * normalized), returned as `content` or written to `output_path`. Purely a diff aid: the
+   * output is never written over the source file.
+   */
+  normalizeScene(file: string, outputPath?: string | undefined | null): any
+  /** Canonical form of in-memory Unity YAML (e.g. a git blob), for diffing. */
+  normalizeSceneFromContent(content: string): string
+  /**
+   * Start watching a scene or prefab for changes: snapshot its GameObjects so later
+   * poll_changes calls report only what differs. Subscribing again resets the snapshot.
+   * Returns the object count, or an error object when the file cannot be read.
+   */
+  subscribe(file: string): any
+  /**
+   * GameObjects added, removed or modified in a subscribed file since the previous poll (or
+   * the subscription), with the component types that changed. An unchanged file costs one
+   * content hash.
+   */
+  pollChanges(file: string): SceneChangesResult
+  /** Stop watching a file. Returns false when it was not subscribed. */
+  unsubscribe(file: string): boolean
+  /**
+   * Map fileIDs between two versions of a scene/prefab (e.g. before and after Unity rewrote
+   * it), matching GameObjects by fileID, hierarchy path, name and component set, and their
+   * components by type. Lets diffs and stored references survive reassigned fileIDs.
+   */
+  correlateObjects(oldContent: string, newContent: string): ObjectCorrelationResult
+  /**
+   * Check a scene or prefab against a JSON or TOML rule file (naming patterns, required and
+   * forbidden components, maximum hierarchy depth). Returns a RuleValidationReport, or an
+   * error object when either file cannot be read or the rules are invalid.
+   */
+  validateWithRules(file: string, rulesPath: string): any
+  /**
+   * One scored health report of a scene or prefab: missing references (objects, scripts and
+   * assets), broken UnityEvent listeners, physics and canvas issues, deprecated components
+   * and, with `rules_path`, rule violations. Each category is scored 0..100 and the overall
+   * score is their weighted mean, so CI can track one number. Returns a SceneHealthReport,
+   * or an error object when the file or rules cannot be read.
+   */
+  healthReport(file: string, rulesPath?: string | undefined | null): any
+  /**
+   * Find GameObjects that are (near-)identical copies of each other, e.g. hand-placed crates,
+   * by fingerprinting their components, property values and children. Each cluster is a
+   * candidate for extraction into a prefab. Returns None when the file cannot be read.
+   */
+  findDuplicateStructures(file: string, options?: DuplicateOptions | undefined | null): DuplicateStructureReport | null
+  /**
+   * Properties of a built-in component that differ from what Unity writes for a fresh one
+   * (BoxCollider, Camera, Light, ...), with the defaults they replace: the intentional part
+   * of its configuration. Scripts and types outside the bundled table report every property.
+   * Returns None when the file or component is missing.
+   */
+  diffAgainstDefaults(file: string, componentId: string): ComponentDefaultsDiff | null
+  /**
+   * Rank the GameObjects of `file` by how closely they are configured like `go_id`: same
+   * component types and same property values, placement ignored. Lists objects scoring at
+   * least `threshold` (0..1, default 0.5) with the components and values that set them apart,
+   * for spotting the siblings of an object that needs a consistency fix. Returns None when
+   * the file cannot be read or `go_id` is not one of its GameObjects.
+   */
+  findSimilar(file: string, goId: string, threshold?: number | undefined | null): SimilarObjectsReport | null
+  /**
+   * Find prefabs that (transitively) instantiate themselves through nested prefabs or
+   * variant bases, and nesting chains deeper than `max_depth` (default 8). Both make the
+   * editor hang on import. `include_packages` also walks embedded and local packages.
+   */
+  detectCircularPrefabRefs(projectRoot: string, maxDepth?: number | undefined | null, includePackages?: boolean | undefined | null): PrefabNestingReport
+  /**
+   * Before renaming GameObject `go_id` of `scene` to `new_name`, list what relies on its name:
+   * Animator/Animation curve paths running through it, `Find("...")` calls and other string
+   * literals naming it in C# scripts, UnityEvent string arguments in scenes/prefabs, and
+   * siblings already called `new_name`. Returns a RenameImpactReport or an error object.
+   */
+  analyzeRenameImpact(projectRoot: string, scene: string, goId: string, newName: string): RenameImpactReport | ErrorOutput
+  /**
+   * Attach a persistent note and/or labels to a GameObject (by fileID) of a scene or prefab.
+   * Stored in `.unity-agentic/annotations.json`; returns the annotation or an error object.
+   */
+  annotateObject(file: string, fileId: string, note?: string | undefined | null, labels?: Array<string> | undefined | null): ObjectAnnotation | ErrorOutput
+  /**
+   * Annotations of a scene or prefab, optionally for one GameObject and/or with a label.
+   * Annotations are re-anchored first, so objects whose fileIDs changed are still found
+   * (`remapped_from` set) and vanished ones are flagged `orphaned`.
+   */
+  getAnnotations(file: string, fileId?: string | undefined | null, label?: string | undefined | null): Array<ObjectAnnotation>
+  /** Delete an annotation by ID. Returns false when it does not exist. */
+  removeAnnotation(file: string, id: number): boolean
+  /**
+   * Parse the layer collision matrix of DynamicsManager.asset (or Physics2DSettings.asset).
+   * Layer names come from TagManager.asset in the same folder when present.
+   */
+  readCollisionMatrix(file: string): CollisionMatrix | null
+  /**
+   * Whether two physics layers (by name or index) collide according to the settings file.
+   * Returns None when the file or either layer cannot be resolved.
+   */
+  layersCollide(file: string, layerA: string, layerB: string): boolean | null
+  /** Parse InputManager.asset axes into typed entries */
+  readInputAxes(file: string): Array<InputAxis>
+  /** List every Camera in a scene or prefab with projection, clipping and culling mask decoded */
+  listCameras(file: string): Array<CameraInfo>
+  /** List every Light in a scene or prefab with type, intensity, shadows and bake mode */
+  listLights(file: string): Array<LightInfo>
+  /**
+   * Audit Canvases in a scene/prefab, or in every scene and prefab when `path` is a project directory.
+   * Flags duplicate sort orders and world-space canvases without an event camera.
+   */
+  auditCanvases(path: string): CanvasAudit
+  /**
+   * Audit Rigidbody/Collider setup of a scene or prefab. Uses the project's
+   * DynamicsManager/Physics2DSettings collision matrices and layer names when available.
+   */
+  auditPhysics(file: string): PhysicsAudit
+  /**
+   * List NavMeshAgents, NavMeshObstacles and OffMeshLinks of a scene with their key
+   * parameters, cross-checked against the scene's NavMeshSettings.
+   */
+  analyzeNavigation(file: string): NavMeshReport | null
+  /**
+   * Assemble the most relevant context around one GameObject, trimmed to a token budget
+   * (default 4000, estimated as chars / 4): its full detail, parent chain, children,
+   * serialized fields of its scripts, and the objects and assets its components reference.
+   * Items are ranked by relevance; whatever does not fit is listed under `omitted`.
+   */
+  buildContextPack(file: string, focusObject: string, budgetTokens?: number | undefined | null): any
+  /**
+   * UnityEvent fields with persistent listeners in a scene or prefab, with targets resolved to
+   * GameObject/script names and broken listeners flagged (missing object, asset or method).
+   */
+  listUnityEvents(file: string): Array<UnityEventInfo>
+  /**
+   * Project-wide (or single-file) report of UnityEvent listeners whose target object,
+   * target asset or method no longer exists.
+   */
+  auditUnityEvents(path: string): UnityEventReport
+  /**
+   * GlobalObjectId strings and SceneAsset references serialized in the components of a
+   * scene/prefab, or of every one under a project directory. Targets resolve to their asset
+   * path; references whose asset or target scene object no longer exists carry an `issue`.
+   */
+  findCrossSceneReferences(path: string): Array<CrossSceneReference>
+  /** Split a `GlobalObjectId_V1-{type}-{assetGUID}-{objectId}-{prefabId}` string into its parts. */
+  parseGlobalObjectId(value: string): GlobalObjectIdParts | null
+  /**
+   * Modernization report for a scene/prefab, a C# file, or a whole project directory: legacy
+   * built-in components (GUIText, GUITexture, legacy particles, NetworkView), MonoBehaviours
+   * backed by UnityScript/Boo scripts, and deprecated C# APIs (WWW, Application.LoadLevel, ...).
+   */
+  detectDeprecatedUsage(path: string): DeprecationReport
+  /**
+   * Check the curve bindings of an AnimatorController (all its clips) or a single clip against
+   * the hierarchy it animates: path segments must name children, the bound component must exist
+   * and carry the property. Without `game_object_id`, every Animator/Animation in `file` that uses
+   * the asset is checked.
+   */
+  validateAnimationBindings(animationPath: string, file: string, gameObjectId?: string | undefined | null): Array<AnimationBindingReport>
+  /**
+   * Move a GameObject subtree (components, children, nested prefab instances) from one
+   * scene/prefab into another, under `parent_id` (a GameObject or Transform fileID in the
+   * destination) or at the scene root. Moved blocks get fresh fileIDs; local references that
+   * cannot cross files are reset to 0 and reported. With `dry_run` (default: true) nothing
+   * is written and `diff` previews both files' changes. Both files keep their line endings;
+   * when the source cannot be written, the destination is restored.
+   */
+  moveGameobjectBetweenFiles(srcFile: string, goId: string, dstFile: string, parentId?: string | undefined | null, dryRun?: boolean | undefined | null): MoveGameObjectResult
+  /**
+   * Generate a new `.unity` or `.prefab` file from a declarative spec: a hierarchy of named
+   * objects with their components and property values (see `scaffold` for the format).
+   * Script paths resolve through the project's GUID cache. An existing file is only
+   * replaced with `overwrite`; with `dry_run` (default: true) nothing is written and the
+   * YAML is returned, with a diff against any file it would replace.
+   */
+  scaffoldFile(outputPath: string, spec: any, overwrite?: boolean | undefined | null, dryRun?: boolean | undefined | null): ScaffoldResult
+  /**
+   * Set tag, layer, active state and/or name on every GameObject of a scene or prefab that
+   * matches `selector`, in one pass that rewrites only the affected value text. The result
+   * reports each matched object's changes; with `dry_run` (default: true) nothing is written
+   * and `diff` previews the exact change.
+   */
+  bulkUpdate(file: string, selector: GameObjectSelector, changes: GameObjectChanges, dryRun?: boolean | undefined | null): BulkUpdateResult
+  /**
+   * Catalog every prefab in the project: root object, GameObject count, component type
+   * histogram, script types, nested prefab references and serialized size. Prefabs are
+   * read in parallel; entries are sorted by path.
+   */
+  catalogPrefabs(projectRoot: string): PrefabCatalog
+  /**
+   * Cross-check scripts' component expectations against scenes and prefabs. Expectations come
+   * from [RequireComponent] (errors) and GetComponent/TryGetComponent calls on the script's own
+   * GameObject (warnings); types the script adds itself via AddComponent are not expected.
+   * An optional `budget` caps the scripts, scenes and prefabs read.
+   */
+  analyzeComponentUsage(projectRoot: string, budget?: ResourceBudget | undefined | null): ComponentUsageReport
+  /**
+   * Every MonoBehaviour script of the project's type registry with the number of scenes and
+   * prefabs instantiating it; never-used scripts are flagged `unused` and listed first.
+   * Abstract classes and classes not named after their file are left out, as Unity cannot
+   * attach them. An optional `budget` caps the scenes and prefabs read.
+   */
+  scriptCoverage(projectRoot: string, budget?: ResourceBudget | undefined | null): ScriptCoverageReport
+  /**
+   * Infer the serialized schema of a component type (built-in type name such as "Rigidbody",
+   * or a MonoBehaviour script name) from every occurrence in the project's scenes, prefabs
+   * and assets: property paths, observed value types, numeric ranges and nullability.
+   * An optional `budget` caps the files read.
+   */
+  inferComponentSchema(projectRoot: string, typeName: string, budget?: ResourceBudget | undefined | null): ComponentSchema
+  /**
+   * Distribution of one property of a component type (built-in type name such as "Light",
+   * or a MonoBehaviour script name) across the project's scenes, prefabs and assets, e.g. all
+   * Light intensities. `property_path` is dotted (`m_Color.r`, `settings.speed`), matched
+   * case-insensitively with or without `m_`. Numbers are binned into `buckets` ranges
+   * (default 10) once there are more distinct values than that; other values are counted.
+   * Built-in fields renamed across Unity versions (m_Drag, now m_LinearDamping) are found under
+   * either name, listed in `aliases_applied`. An optional `budget` caps the files read.
+   */
+  propertyHistogram(projectRoot: string, componentType: string, propertyPath: string, buckets?: number | undefined | null, budget?: ResourceBudget | undefined | null): PropertyHistogram
+  /**
+   * Custom script execution order of a project, from script .meta files
+   * (MonoImporter executionOrder) and ProjectSettings/MonoManager.asset.
+   * Scripts with the default order (0) are omitted; results are sorted by order.
+   */
+  getExecutionOrder(projectRoot: string): Array<ExecutionOrderEntry>
   /**
    * Read a .asset file and return its root objects with properties.
    * When `decode_mesh` is true (default), Mesh assets (class 43) get their
    * hex vertex/index data decoded into structured arrays.
+   * `max_property_bytes` summarizes oversized arrays/strings (e.g. m_Tiles, keyframes).
    */
-  readAsset(file: string, decodeMesh?: boolean | undefined | null): any
+  readAsset(file: string, decodeMesh?: boolean | undefined | null, maxPropertyBytes?: number | undefined | null): Array<AssetObjectOutput>
+  /** Read in-memory .asset content and return its root objects with properties */
+  readAssetFromContent(file: string, content: string, decodeMesh?: boolean | undefined | null, maxPropertyBytes?: number | undefined | null): Array<AssetObjectOutput>
 }
 /** High-performance documentation indexer */
 export declare class Indexer {
@@ -314,10 +2931,31 @@ export declare class Indexer {
   indexFile(path: string): IndexResult
   /** Index a directory of files */
   indexDirectory(path: string): IndexResult
-  /** Search the index */
-  search(query: string): Array<SearchResult>
+  /** Search the index, optionally only documents whose frontmatter title, tags or version match */
+  search(query: string, filter?: DocSearchFilter | undefined | null): Array<SearchResult>
+  /**
+   * Find documents and sections by title or file path, as a table of contents to
+   * navigate before pulling chunk content
+   */
+  findSections(query: string): Array<SectionMatch>
+  /**
+   * Store embeddings of indexed chunks (parallel `ids` and `vectors`) in the approximate
+   * nearest-neighbor index saved next to the chunk store. Embeddings from a different
+   * `model` or dimension count replace the stored index.
+   */
+  storeEmbeddings(model: string, ids: Array<string>, vectors: Array<Array<number>>): any
+  /**
+   * Chunks whose embeddings (from `model`) are nearest to a query embedding, most similar
+   * first; score is cosine similarity
+   */
+  vectorSearch(model: string, vector: Array<number>, limit?: number | undefined | null): Array<SearchResult>
   /** Clear the index */
   clear(): void
+  /**
+   * Merge duplicate chunks, drop chunks whose source file is gone, and rewrite the
+   * storage file, reporting the space saved
+   */
+  compact(): CompactResult
   /** Get index statistics */
   stats(): any
 }
@@ -310,21 +310,53 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { ChunkType, extractDllTypes, extractDllFields, extractCsharpTypes, buildTypeRegistry, extractSerializedFields, Scanner, Indexer, EmbeddingGenerator, walkProjectFiles, grepProject, buildGuidCache, buildPackageGuidCache, buildLocalPackageGuidCache, getVersion, isNativeAvailable } = nativeBinding
+const { selfBenchmark, analyzeBuildReport, ChunkType, encodeYamlValue, analyzeScriptDependencies, extractDllTypes, extractDllFields, lintCsharp, scriptMetrics, summarizeScript, updateSymbolIndex, findSymbolDefinitions, listTypeMembers, findSymbolUsages, scanObsoleteApis, extractCsharpTypes, buildTypeRegistry, buildTypeRegistryWithBudget, extractSerializedFields, Scanner, Indexer, EmbeddingGenerator, parseUnityLog, analyzeShaderIncludes, readUnitypackage, vcsClassifyChanges, listFileTypeGroups, walkProjectFiles, grepProject, replacePropertyValues, buildGuidCache, buildPackageGuidCache, buildLocalPackageGuidCache, hashAsset, hashProject, projectSnapshot, setLogLevel, setCallTiming, getLastCallTiming, setSafetyConfig, registerProjectRoot, getSafetyConfig, clearSafetyConfig, buildSearchIndex, searchProjectIndex, getGuidChanges, getVersion, isNativeAvailable } = nativeBinding
 
+module.exports.selfBenchmark = selfBenchmark
+module.exports.analyzeBuildReport = analyzeBuildReport
 module.exports.ChunkType = ChunkType
+module.exports.encodeYamlValue = encodeYamlValue
+module.exports.analyzeScriptDependencies = analyzeScriptDependencies
 module.exports.extractDllTypes = extractDllTypes
 module.exports.extractDllFields = extractDllFields
+module.exports.lintCsharp = lintCsharp
+module.exports.scriptMetrics = scriptMetrics
+module.exports.summarizeScript = summarizeScript
+module.exports.updateSymbolIndex = updateSymbolIndex
+module.exports.findSymbolDefinitions = findSymbolDefinitions
+module.exports.listTypeMembers = listTypeMembers
+module.exports.findSymbolUsages = findSymbolUsages
+module.exports.scanObsoleteApis = scanObsoleteApis
 module.exports.extractCsharpTypes = extractCsharpTypes
 module.exports.buildTypeRegistry = buildTypeRegistry
+module.exports.buildTypeRegistryWithBudget = buildTypeRegistryWithBudget
 module.exports.extractSerializedFields = extractSerializedFields
 module.exports.Scanner = Scanner
 module.exports.Indexer = Indexer
 module.exports.EmbeddingGenerator = EmbeddingGenerator
+module.exports.parseUnityLog = parseUnityLog
+module.exports.analyzeShaderIncludes = analyzeShaderIncludes
+module.exports.readUnitypackage = readUnitypackage
+module.exports.vcsClassifyChanges = vcsClassifyChanges
+module.exports.listFileTypeGroups = listFileTypeGroups
 module.exports.walkProjectFiles = walkProjectFiles
 module.exports.grepProject = grepProject
+module.exports.replacePropertyValues = replacePropertyValues
 module.exports.buildGuidCache = buildGuidCache
 module.exports.buildPackageGuidCache = buildPackageGuidCache
 module.exports.buildLocalPackageGuidCache = buildLocalPackageGuidCache
+module.exports.hashAsset = hashAsset
+module.exports.hashProject = hashProject
+module.exports.projectSnapshot = projectSnapshot
+module.exports.setLogLevel = setLogLevel
+module.exports.setCallTiming = setCallTiming
+module.exports.getLastCallTiming = getLastCallTiming
+module.exports.setSafetyConfig = setSafetyConfig
+module.exports.registerProjectRoot = registerProjectRoot
+module.exports.getSafetyConfig = getSafetyConfig
+module.exports.clearSafetyConfig = clearSafetyConfig
+module.exports.buildSearchIndex = buildSearchIndex
+module.exports.searchProjectIndex = searchProjectIndex
+module.exports.getGuidChanges = getGuidChanges
 module.exports.getVersion = getVersion
 module.exports.isNativeAvailable = isNativeAvailable
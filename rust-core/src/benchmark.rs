@@ -0,0 +1,144 @@
+//! Synthetic-scene benchmarks shared by the criterion suite (`benches/scanner.rs`) and the
+//! napi-exposed `self_benchmark`, so users can attach environment-specific numbers to bug reports.
+
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Instant;
+
+use crate::common::{BenchmarkReport, BenchmarkResult, PaginationOptions};
+use crate::scanner::parser::{BlockIndex, UnityYamlParser};
+use crate::scanner::{component, Scanner};
+
+/// Measured operations, in report order.
+pub const CASES: &[&str] = &["block_index", "scan_scene_minimal", "inspect_all_paginated", "extract_properties"];
+
+const SCRIPT_GUID: &str = "5f1e2d3c4b5a69788796a5b4c3d2e1f0";
+const FAN_OUT: u32 = 8;
+
+/// A scene of `objects` GameObjects (Transform + MonoBehaviour each) arranged in a tree with a
+/// fan-out of 8, so hierarchy and property extraction see realistic nesting.
+pub fn synthetic_scene(objects: u32) -> String {
+    let mut out = String::from("%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n");
+    for i in 0..objects {
+        let (go, transform, script) = (i * 4 + 1, i * 4 + 2, i * 4 + 3);
+        let parent = (i > 0).then(|| (i - 1) / FAN_OUT);
+        let _ = write!(
+            out,
+            "--- !u!1 &{go}\nGameObject:\n  m_ObjectHideFlags: 0\n  serializedVersion: 6\n  m_Component:\n  - component: {{fileID: {transform}}}\n  - component: {{fileID: {script}}}\n  m_Layer: 0\n  m_Name: Object_{i}\n  m_TagString: Untagged\n  m_IsActive: 1\n"
+        );
+        let _ = write!(
+            out,
+            "--- !u!4 &{transform}\nTransform:\n  m_ObjectHideFlags: 0\n  m_GameObject: {{fileID: {go}}}\n  m_LocalRotation: {{x: 0, y: 0, z: 0, w: 1}}\n  m_LocalPosition: {{x: {i}, y: 0, z: 0}}\n  m_LocalScale: {{x: 1, y: 1, z: 1}}\n"
+        );
+        let children: Vec<u32> = (i * FAN_OUT + 1..=i * FAN_OUT + FAN_OUT).filter(|c| *c < objects).collect();
+        if children.is_empty() {
+            out.push_str("  m_Children: []\n");
+        } else {
+            out.push_str("  m_Children:\n");
+            for child in children {
+                let _ = writeln!(out, "  - {{fileID: {}}}", child * 4 + 2);
+            }
+        }
+        let _ = writeln!(out, "  m_Father: {{fileID: {}}}", parent.map_or(0, |p| p * 4 + 2));
+        let _ = write!(
+            out,
+            "--- !u!114 &{script}\nMonoBehaviour:\n  m_ObjectHideFlags: 0\n  m_GameObject: {{fileID: {go}}}\n  m_Enabled: 1\n  m_Script: {{fileID: 11500000, guid: {SCRIPT_GUID}, type: 3}}\n  m_Name: \n  speed: 3.5\n  label: Object {i}\n  target: {{fileID: {}}}\n  waypoints:\n  - {{x: 0, y: 1, z: 2}}\n  - {{x: 3, y: 4, z: 5}}\n",
+            parent.map_or(0, |p| p * 4 + 1)
+        );
+    }
+    out
+}
+
+/// Run one benchmark case over scene content. Returns a result size so the work cannot be
+/// optimized away.
+pub fn run_case(case: &str, content: &str) -> usize {
+    match case {
+        "block_index" => {
+            let index = BlockIndex::new(content);
+            usize::from(index.get("1").is_some())
+        }
+        "scan_scene_minimal" => Scanner::new().scan_scene_minimal_from_content(content.to_string()).len(),
+        "inspect_all_paginated" => {
+            let options = PaginationOptions {
+                file: "Benchmark.unity".to_string(),
+                include_properties: Some(true),
+                verbose: None,
+                page_size: Some(200),
+                cursor: None,
+                max_depth: None,
+                filter_component: None,
+                max_property_bytes: None,
+                sort: None,
+            };
+            Scanner::new().inspect_all_paginated_from_content(options, content.to_string()).gameobjects.len()
+        }
+        "extract_properties" => {
+            let guid_cache = HashMap::new();
+            UnityYamlParser::parse_all_blocks(content).iter()
+                .filter(|(class_id, _, _)| *class_id == 114)
+                .map(|(_, _, body)| component::extract_properties_from_block(body, &guid_cache).as_object().map_or(0, |o| o.len()))
+                .sum()
+        }
+        _ => 0,
+    }
+}
+
+/// Time every case on synthetic scenes of the given sizes (default 1k and 10k objects,
+/// 5 iterations each) and report them with the build/environment details.
+#[napi]
+pub fn self_benchmark(object_counts: Option<Vec<u32>>, iterations: Option<u32>) -> BenchmarkReport {
+    let _call = crate::telemetry::call("self_benchmark");
+    let iterations = iterations.unwrap_or(5).max(1);
+    let mut results = Vec::new();
+    for objects in object_counts.unwrap_or_else(|| vec![1_000, 10_000]) {
+        let content = synthetic_scene(objects);
+        for case in CASES {
+            let mut timings_ms: Vec<f64> = Vec::with_capacity(iterations as usize);
+            for _ in 0..iterations {
+                let start = Instant::now();
+                std::hint::black_box(run_case(case, &content));
+                timings_ms.push(start.elapsed().as_secs_f64() * 1000.0);
+            }
+            results.push(BenchmarkResult {
+                case: case.to_string(),
+                objects,
+                scene_bytes: content.len() as i64,
+                iterations,
+                mean_ms: timings_ms.iter().sum::<f64>() / iterations as f64,
+                min_ms: timings_ms.iter().copied().fold(f64::INFINITY, f64::min),
+                max_ms: timings_ms.iter().copied().fold(0.0, f64::max),
+            });
+        }
+    }
+
+    BenchmarkReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        threads: rayon::current_num_threads() as u32,
+        debug_build: cfg!(debug_assertions),
+        results,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthetic_scene_shape() {
+        let content = synthetic_scene(20);
+        let gameobjects = UnityYamlParser::extract_gameobjects(&content);
+        assert_eq!(gameobjects.len(), 20);
+        assert!(run_case("extract_properties", &content) > 0);
+        assert_eq!(run_case("scan_scene_minimal", &content), 20);
+    }
+
+    #[test]
+    fn test_self_benchmark_reports_every_case() {
+        let report = self_benchmark(Some(vec![10]), Some(1));
+        assert_eq!(report.results.len(), CASES.len());
+        assert!(report.results.iter().all(|r| r.objects == 10 && r.min_ms <= r.max_ms));
+    }
+}
@@ -0,0 +1,517 @@
+//! Build report summarization: per-asset size contributions, assets duplicated across
+//! output files (e.g. AssetBundles) and build step durations.
+//!
+//! Reads the binary `Library/LastBuild.buildreport` the editor writes after every build,
+//! or a JSON export of a BuildReport using the same field names (with or without the
+//! `m_` prefix, in any case), e.g. `{"summary": {...}, "steps": [...], "packedAssets": [...]}`.
+
+pub mod serialized;
+
+use napi_derive::napi;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// BuildReport and PackedAssets class IDs.
+const BUILD_REPORT_CLASS_ID: i32 = 1125;
+const PACKED_ASSETS_CLASS_ID: i32 = 1126;
+
+/// Assets and duplicates returned when no limit is given.
+const DEFAULT_LIMIT: u32 = 50;
+
+/// .NET ticks (100 ns) per millisecond, the unit of binary step durations.
+const TICKS_PER_MS: f64 = 10_000.0;
+
+/// Total packed size of one source asset across the build output.
+#[napi(object)]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BuildAssetSize {
+    pub path: String,
+    pub guid: Option<String>,
+    /// Packed bytes, summed over every output file containing the asset
+    pub size: i64,
+    /// Output files (bundles, sharedassets) containing the asset
+    pub files: u32,
+}
+
+/// A source asset packed into more than one output file.
+#[napi(object)]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BuildDuplicateAsset {
+    pub path: String,
+    pub files: Vec<String>,
+    pub size: i64,
+    /// Bytes beyond the largest single copy
+    pub wasted: i64,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BuildStepDuration {
+    pub name: String,
+    /// Nesting depth; nested steps are part of their parent's duration
+    pub depth: u32,
+    pub duration_ms: f64,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BuildReportAnalysis {
+    pub file: String,
+    /// "binary" or "json"
+    pub format: String,
+    pub platform: Option<String>,
+    /// "Succeeded", "Failed", "Cancelled" or "Unknown"
+    pub result: Option<String>,
+    pub total_size: Option<i64>,
+    pub total_time_ms: Option<f64>,
+    pub total_errors: Option<i64>,
+    pub total_warnings: Option<i64>,
+    /// Distinct source assets in the packed output
+    pub asset_count: u32,
+    /// Largest assets first
+    pub assets: Vec<BuildAssetSize>,
+    /// Most wasted bytes first
+    pub duplicates: Vec<BuildDuplicateAsset>,
+    /// Steps in build order
+    pub steps: Vec<BuildStepDuration>,
+}
+
+/// Summarize a Unity build report: a `.buildreport` file, a JSON export, or a project root
+/// (reads `Library/LastBuild.buildreport`). `limit` caps assets and duplicates (default 50).
+#[napi]
+pub fn analyze_build_report(path: String, limit: Option<u32>) -> serde_json::Value {
+    let _call = crate::telemetry::call("analyze_build_report");
+    let mut file = PathBuf::from(&path);
+    if file.is_dir() {
+        file = file.join("Library").join("LastBuild.buildreport");
+    }
+    let data = match crate::safety::read(&file) {
+        Ok(data) => data,
+        Err(e) => return serde_json::json!({"error": format!("Cannot read {}: {}", file.display(), e), "is_error": true}),
+    };
+    match analyze(&data, limit.unwrap_or(DEFAULT_LIMIT) as usize) {
+        Ok(mut analysis) => {
+            analysis.file = file.to_string_lossy().to_string();
+            serde_json::to_value(analysis).unwrap_or(Value::Null)
+        }
+        Err(message) => serde_json::json!({"error": format!("{}: {}", file.display(), message), "is_error": true}),
+    }
+}
+
+/// Analyze report bytes, detecting JSON exports by their leading brace.
+pub fn analyze(data: &[u8], limit: usize) -> Result<BuildReportAnalysis, String> {
+    let is_json = data.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{');
+    let report = if is_json {
+        serde_json::from_slice::<Value>(data).map_err(|e| format!("invalid JSON: {}", e))?
+    } else {
+        binary_report(data)?
+    };
+    Ok(summarize(&report, if is_json { "json" } else { "binary" }, limit))
+}
+
+/// The BuildReport object of a binary report, with its PackedAssets appendices under `packedAssets`.
+fn binary_report(data: &[u8]) -> Result<Value, String> {
+    let objects = serialized::read_objects(data, &[BUILD_REPORT_CLASS_ID, PACKED_ASSETS_CLASS_ID]).map_err(|e| e.to_string())?;
+    let mut report = None;
+    let mut packed = Vec::new();
+    for object in objects {
+        if object.class_id == BUILD_REPORT_CLASS_ID {
+            report.get_or_insert(object.value);
+        } else {
+            packed.push(object.value);
+        }
+    }
+    let mut report = report.ok_or("no BuildReport object found")?;
+    if let Value::Object(map) = &mut report {
+        map.insert("packedAssets".to_string(), Value::Array(packed));
+    }
+    Ok(report)
+}
+
+/// Field lookup ignoring case, underscores and the `m_` prefix, trying each alias in turn.
+fn field<'a>(value: &'a Value, aliases: &[&str]) -> Option<&'a Value> {
+    let normalize = |key: &str| key.strip_prefix("m_").unwrap_or(key).replace('_', "").to_lowercase();
+    let map = value.as_object()?;
+    aliases.iter().find_map(|alias| map.iter().find(|(key, _)| normalize(key) == *alias).map(|(_, v)| v))
+}
+
+fn field_str(value: &Value, aliases: &[&str]) -> Option<String> {
+    field(value, aliases).and_then(Value::as_str).map(str::to_string)
+}
+
+fn field_i64(value: &Value, aliases: &[&str]) -> Option<i64> {
+    field(value, aliases).and_then(|v| v.as_i64().or_else(|| v.as_u64().map(|n| i64::try_from(n).unwrap_or(i64::MAX))))
+}
+
+/// A duration in ms: `<name>Ms` in milliseconds, a TimeSpan string, or .NET ticks.
+fn duration_ms(value: &Value, ms_aliases: &[&str], aliases: &[&str]) -> Option<f64> {
+    if let Some(ms) = field(value, ms_aliases).and_then(Value::as_f64) {
+        return Some(ms);
+    }
+    match field(value, aliases)? {
+        Value::String(s) => parse_timespan_ms(s),
+        Value::Object(ticks) => ticks.get("ticks").and_then(Value::as_f64).map(|t| t / TICKS_PER_MS),
+        other => other.as_f64().map(|t| t / TICKS_PER_MS),
+    }
+}
+
+/// `[d.]hh:mm:ss[.fffffff]` in milliseconds.
+fn parse_timespan_ms(s: &str) -> Option<f64> {
+    let parts: Vec<&str> = s.trim().split(':').collect();
+    let [hours, minutes, seconds] = parts.as_slice() else { return None };
+    let hours = match hours.split_once('.') {
+        Some((days, hours)) => days.parse::<f64>().ok()? * 24.0 + hours.parse::<f64>().ok()?,
+        None => hours.parse::<f64>().ok()?,
+    };
+    let seconds = (hours * 60.0 + minutes.parse::<f64>().ok()?) * 60.0 + seconds.parse::<f64>().ok()?;
+    Some(seconds * 1000.0)
+}
+
+fn build_result_name(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        other => Some(match other.as_i64()? {
+            1 => "Succeeded",
+            2 => "Failed",
+            3 => "Cancelled",
+            _ => "Unknown",
+        }.to_string()),
+    }
+}
+
+fn summarize(report: &Value, format: &str, limit: usize) -> BuildReportAnalysis {
+    let summary = field(report, &["summary"]).unwrap_or(report);
+
+    let steps = field(report, &["buildsteps", "steps"])
+        .and_then(Value::as_array)
+        .map(|steps| steps.iter()
+            .map(|step| BuildStepDuration {
+                name: field_str(step, &["stepname", "name"]).unwrap_or_default(),
+                depth: field_i64(step, &["depth"]).unwrap_or(0).clamp(0, i64::from(u32::MAX)) as u32,
+                duration_ms: duration_ms(step, &["durationms"], &["duration", "durationticks"]).unwrap_or(0.0),
+            })
+            .collect())
+        .unwrap_or_default();
+
+    // (asset path, guid) -> packed bytes per output file
+    let mut per_asset: HashMap<String, (Option<String>, HashMap<String, i64>)> = HashMap::new();
+    for packed in field(report, &["packedassets"]).and_then(Value::as_array).into_iter().flatten() {
+        let output = field_str(packed, &["shortpath", "file", "path"]).unwrap_or_default();
+        for content in field(packed, &["contents"]).and_then(Value::as_array).into_iter().flatten() {
+            let guid = field_str(content, &["sourceassetguid", "guid"]);
+            let Some(path) = field_str(content, &["buildtimeassetpath", "sourceassetpath", "assetpath", "path"])
+                .filter(|p| !p.is_empty())
+                .or_else(|| guid.clone())
+            else { continue };
+            let size = field_i64(content, &["packedsize", "size"]).unwrap_or(0);
+            let entry = per_asset.entry(path).or_insert_with(|| (guid, HashMap::new()));
+            *entry.1.entry(output.clone()).or_insert(0) += size;
+        }
+    }
+
+    let mut assets: Vec<BuildAssetSize> = Vec::new();
+    let mut duplicates: Vec<BuildDuplicateAsset> = Vec::new();
+    for (path, (guid, outputs)) in &per_asset {
+        let size: i64 = outputs.values().sum();
+        if outputs.len() > 1 {
+            let mut files: Vec<String> = outputs.keys().cloned().collect();
+            files.sort();
+            duplicates.push(BuildDuplicateAsset {
+                path: path.clone(),
+                files,
+                size,
+                wasted: size - outputs.values().copied().max().unwrap_or(0),
+            });
+        }
+        assets.push(BuildAssetSize { path: path.clone(), guid: guid.clone(), size, files: outputs.len() as u32 });
+    }
+    assets.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    assets.truncate(limit);
+    duplicates.sort_by(|a, b| b.wasted.cmp(&a.wasted).then_with(|| a.path.cmp(&b.path)));
+    duplicates.truncate(limit);
+
+    BuildReportAnalysis {
+        file: String::new(),
+        format: format.to_string(),
+        platform: field_str(summary, &["platformname", "platform"]),
+        result: field(summary, &["buildresult", "result"]).and_then(build_result_name),
+        total_size: field_i64(summary, &["totalsize"]),
+        total_time_ms: duration_ms(summary, &["totaltimems"], &["totaltimeticks", "totaltime"]),
+        total_errors: field_i64(summary, &["totalerrors"]),
+        total_warnings: field_i64(summary, &["totalwarnings"]),
+        asset_count: per_asset.len() as u32,
+        assets,
+        duplicates,
+        steps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Little-endian SerializedFile (format 22) writer for tests.
+    struct FileBuilder {
+        types: Vec<u8>,
+        type_count: i32,
+        objects: Vec<(i32, Vec<u8>)>,
+    }
+
+    /// Type tree node: (level, type, name, is_array, align)
+    type Node<'a> = (u8, &'a str, &'a str, bool, bool);
+
+    impl FileBuilder {
+        fn new() -> Self {
+            FileBuilder { types: Vec::new(), type_count: 0, objects: Vec::new() }
+        }
+
+        fn add_type(&mut self, class_id: i32, nodes: &[Node]) -> i32 {
+            let mut strings: Vec<u8> = Vec::new();
+            let mut offset_of = |s: &str| -> u32 {
+                // Exercise the built-in string table for the names it has
+                if let Some(offset) = common_string_offset(s) {
+                    return offset | 0x8000_0000;
+                }
+                let offset = strings.len() as u32;
+                strings.extend_from_slice(s.as_bytes());
+                strings.push(0);
+                offset
+            };
+            let mut node_bytes = Vec::new();
+            for (level, type_name, name, is_array, align) in nodes {
+                node_bytes.extend_from_slice(&1u16.to_le_bytes());
+                node_bytes.push(*level);
+                node_bytes.push(u8::from(*is_array));
+                node_bytes.extend_from_slice(&offset_of(type_name).to_le_bytes());
+                node_bytes.extend_from_slice(&offset_of(name).to_le_bytes());
+                node_bytes.extend_from_slice(&(-1i32).to_le_bytes());
+                node_bytes.extend_from_slice(&0i32.to_le_bytes());
+                node_bytes.extend_from_slice(&(if *align { 0x4000i32 } else { 0 }).to_le_bytes());
+                node_bytes.extend_from_slice(&0u64.to_le_bytes());
+            }
+            self.types.extend_from_slice(&class_id.to_le_bytes());
+            self.types.push(0);
+            self.types.extend_from_slice(&(-1i16).to_le_bytes());
+            self.types.extend_from_slice(&[0u8; 16]);
+            self.types.extend_from_slice(&(nodes.len() as i32).to_le_bytes());
+            self.types.extend_from_slice(&(strings.len() as i32).to_le_bytes());
+            self.types.extend_from_slice(&node_bytes);
+            self.types.extend_from_slice(&strings);
+            self.types.extend_from_slice(&0i32.to_le_bytes()); // dependencies
+            self.type_count += 1;
+            self.type_count - 1
+        }
+
+        fn build(&self) -> Vec<u8> {
+            let mut meta = Vec::new();
+            meta.extend_from_slice(b"2022.3.10f1\0");
+            meta.extend_from_slice(&19i32.to_le_bytes());
+            meta.push(1);
+            meta.extend_from_slice(&self.type_count.to_le_bytes());
+            meta.extend_from_slice(&self.types);
+            meta.extend_from_slice(&(self.objects.len() as i32).to_le_bytes());
+            let header_len = 48;
+            let mut object_table = Vec::new();
+            let mut data = Vec::new();
+            for (path_id, (type_id, bytes)) in self.objects.iter().enumerate() {
+                while !(header_len + meta.len() + object_table.len()).is_multiple_of(4) {
+                    object_table.push(0);
+                }
+                object_table.extend_from_slice(&(path_id as i64 + 1).to_le_bytes());
+                object_table.extend_from_slice(&(data.len() as u64).to_le_bytes());
+                object_table.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+                object_table.extend_from_slice(&type_id.to_le_bytes());
+                data.extend_from_slice(bytes);
+                while !data.len().is_multiple_of(8) {
+                    data.push(0);
+                }
+            }
+            meta.extend_from_slice(&object_table);
+            let data_offset = (header_len + meta.len()).div_ceil(16) * 16;
+
+            let mut file = Vec::new();
+            file.extend_from_slice(&0u32.to_be_bytes());
+            file.extend_from_slice(&0u32.to_be_bytes());
+            file.extend_from_slice(&22u32.to_be_bytes());
+            file.extend_from_slice(&0u32.to_be_bytes());
+            file.extend_from_slice(&[0, 0, 0, 0]);
+            file.extend_from_slice(&(meta.len() as u32).to_be_bytes());
+            file.extend_from_slice(&((data_offset + data.len()) as u64).to_be_bytes());
+            file.extend_from_slice(&(data_offset as u64).to_be_bytes());
+            file.extend_from_slice(&0u64.to_be_bytes());
+            file.extend_from_slice(&meta);
+            file.resize(data_offset, 0);
+            file.extend_from_slice(&data);
+            file
+        }
+    }
+
+    fn common_string_offset(s: &str) -> Option<u32> {
+        let mut offset = 0u32;
+        for common in serialized::COMMON_STRINGS {
+            if *common == s {
+                return Some(offset);
+            }
+            offset += common.len() as u32 + 1;
+        }
+        None
+    }
+
+    /// Object bytes with Unity's 4-byte alignment after strings and arrays.
+    #[derive(Default)]
+    struct Data(Vec<u8>);
+
+    impl Data {
+        fn align(&mut self) -> &mut Self {
+            while !self.0.len().is_multiple_of(4) {
+                self.0.push(0);
+            }
+            self
+        }
+        fn string(&mut self, s: &str) -> &mut Self {
+            self.i32(s.len() as i32);
+            self.0.extend_from_slice(s.as_bytes());
+            self.align()
+        }
+        fn i32(&mut self, n: i32) -> &mut Self {
+            self.0.extend_from_slice(&n.to_le_bytes());
+            self
+        }
+        fn u32(&mut self, n: u32) -> &mut Self {
+            self.0.extend_from_slice(&n.to_le_bytes());
+            self
+        }
+        fn u64(&mut self, n: u64) -> &mut Self {
+            self.0.extend_from_slice(&n.to_le_bytes());
+            self
+        }
+    }
+
+    fn string_node<'a>(level: u8, name: &'a str) -> [Node<'a>; 4] {
+        [
+            (level, "string", name, false, false),
+            (level + 1, "Array", "Array", true, true),
+            (level + 2, "int", "size", false, false),
+            (level + 2, "char", "data", false, false),
+        ]
+    }
+
+    fn binary_report() -> Vec<u8> {
+        let mut builder = FileBuilder::new();
+        let mut report_nodes: Vec<Node> = vec![(0, "BuildReport", "Base", false, false)];
+        report_nodes.extend(string_node(1, "m_Name"));
+        report_nodes.extend([
+            (1, "BuildSummary", "m_Summary", false, false),
+            (2, "UInt64", "totalSize", false, false),
+            (2, "UInt64", "totalTimeTicks", false, false),
+            (2, "int", "buildResult", false, false),
+        ]);
+        report_nodes.extend(string_node(2, "platformName"));
+        report_nodes.extend([
+            (1, "vector", "m_BuildSteps", false, false),
+            (2, "Array", "Array", true, true),
+            (3, "int", "size", false, false),
+            (3, "BuildStepInfo", "data", false, false),
+        ]);
+        report_nodes.extend(string_node(4, "stepName"));
+        report_nodes.extend([
+            (4, "UInt64", "duration", false, false),
+            (4, "int", "depth", false, false),
+        ]);
+        let report_type = builder.add_type(BUILD_REPORT_CLASS_ID, &report_nodes);
+
+        let mut packed_nodes: Vec<Node> = vec![(0, "PackedAssets", "Base", false, false)];
+        packed_nodes.extend(string_node(1, "m_ShortPath"));
+        packed_nodes.extend([
+            (1, "vector", "m_Contents", false, false),
+            (2, "Array", "Array", true, true),
+            (3, "int", "size", false, false),
+            (3, "BuildReportPackedAssetInfo", "data", false, false),
+            (4, "UInt64", "packedSize", false, false),
+            (4, "GUID", "sourceAssetGUID", false, false),
+            (5, "unsigned int", "data[0]", false, false),
+            (5, "unsigned int", "data[1]", false, false),
+            (5, "unsigned int", "data[2]", false, false),
+            (5, "unsigned int", "data[3]", false, false),
+        ]);
+        packed_nodes.extend(string_node(4, "buildTimeAssetPath"));
+        let packed_type = builder.add_type(PACKED_ASSETS_CLASS_ID, &packed_nodes);
+
+        let mut report = Data::default();
+        report.string("Build AssetBundles")
+            .u64(3_000_000).u64(125_000_000).i32(1).string("StandaloneWindows64")
+            .i32(2)
+            .string("Build player").u64(120_000_000).i32(0)
+            .string("Write AssetBundles").u64(80_000_000).i32(1)
+            .align();
+        builder.objects.push((report_type, report.0));
+
+        let packed = |bundle: &str, assets: &[(u64, u32, &str)]| {
+            let mut data = Data::default();
+            data.string(bundle).i32(assets.len() as i32);
+            for (size, guid_part, path) in assets {
+                data.u64(*size).u32(*guid_part).u32(0).u32(0).u32(0).string(path);
+            }
+            data.align();
+            data.0
+        };
+        builder.objects.push((packed_type, packed("characters", &[(500_000, 0x21, "Assets/Textures/Atlas.png"), (40_000, 0x2, "Assets/Models/Hero.fbx")])));
+        builder.objects.push((packed_type, packed("environment", &[(500_000, 0x21, "Assets/Textures/Atlas.png"), (900_000, 0x3, "Assets/Models/Level.fbx")])));
+        builder.build()
+    }
+
+    #[test]
+    fn test_analyze_binary_buildreport() {
+        let analysis = analyze(&binary_report(), 10).unwrap();
+        assert_eq!(analysis.format, "binary");
+        assert_eq!(analysis.platform.as_deref(), Some("StandaloneWindows64"));
+        assert_eq!(analysis.result.as_deref(), Some("Succeeded"));
+        assert_eq!(analysis.total_size, Some(3_000_000));
+        assert_eq!(analysis.total_time_ms, Some(12_500.0));
+
+        let steps: Vec<(&str, u32, f64)> = analysis.steps.iter().map(|s| (s.name.as_str(), s.depth, s.duration_ms)).collect();
+        assert_eq!(steps, vec![("Build player", 0, 12_000.0), ("Write AssetBundles", 1, 8_000.0)]);
+
+        let assets: Vec<(&str, i64, u32)> = analysis.assets.iter().map(|a| (a.path.as_str(), a.size, a.files)).collect();
+        assert_eq!(assets, vec![
+            ("Assets/Textures/Atlas.png", 1_000_000, 2),
+            ("Assets/Models/Level.fbx", 900_000, 1),
+            ("Assets/Models/Hero.fbx", 40_000, 1),
+        ]);
+        assert_eq!(analysis.assets[0].guid.as_deref(), Some("12000000000000000000000000000000"));
+
+        assert_eq!(analysis.duplicates.len(), 1);
+        assert_eq!(analysis.duplicates[0].files, vec!["characters", "environment"]);
+        assert_eq!(analysis.duplicates[0].wasted, 500_000);
+    }
+
+    #[test]
+    fn test_analyze_json_export() {
+        let json = r#"{
+            "summary": {"platform": "Android", "result": "Failed", "totalSize": 2048, "totalTime": "00:01:30.5000000", "totalErrors": 2},
+            "steps": [{"name": "Compile scripts", "depth": 0, "durationMs": 4500}],
+            "packedAssets": [
+                {"shortPath": "sharedassets0.assets", "contents": [
+                    {"sourceAssetPath": "Assets/Audio/Theme.ogg", "packedSize": 1024},
+                    {"sourceAssetPath": "Assets/Audio/Theme.ogg", "packedSize": 16}
+                ]}
+            ]
+        }"#;
+        let analysis = analyze(json.as_bytes(), 10).unwrap();
+        assert_eq!(analysis.format, "json");
+        assert_eq!((analysis.platform.as_deref(), analysis.result.as_deref()), (Some("Android"), Some("Failed")));
+        assert_eq!(analysis.total_time_ms, Some(90_500.0));
+        assert_eq!(analysis.total_errors, Some(2));
+        assert_eq!(analysis.steps[0].duration_ms, 4500.0);
+        assert_eq!((analysis.assets[0].size, analysis.assets[0].files), (1040, 1));
+        assert!(analysis.duplicates.is_empty(), "sub-objects of one asset in one file are not duplicates");
+    }
+
+    #[test]
+    fn test_analyze_build_report_errors() {
+        let missing = analyze_build_report("/nonexistent/LastBuild.buildreport".to_string(), None);
+        assert_eq!(missing["is_error"], true);
+        assert!(analyze(b"\0\0\0\0\0\0\0\0\0\0\0\x05\0\0\0\0", 10).unwrap_err().contains("unsupported"));
+    }
+}
@@ -0,0 +1,364 @@
+//! Minimal reader for Unity's binary SerializedFile format (format version 17 and later,
+//! Unity 2017+), enough to decode objects through their embedded type trees.
+//!
+//! Editor-written files such as `Library/LastBuild.buildreport` always carry type trees,
+//! so no per-version class layouts are needed: every object is decoded into JSON using
+//! the field names and types the file itself declares.
+
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Oldest supported SerializedFile format version.
+const MIN_VERSION: u32 = 17;
+/// Newest format version this reader has been written against (Unity 6).
+const MAX_VERSION: u32 = 22;
+
+/// Type tree meta flag: the field is padded to 4 bytes after reading.
+const ALIGN_BYTES_FLAG: i32 = 0x4000;
+/// Type tree string offsets with this bit set index Unity's built-in string table.
+const COMMON_STRING_FLAG: u32 = 0x8000_0000;
+
+/// Unity's built-in type tree string table, in order; an entry's offset is the total
+/// length (plus NUL terminators) of the entries before it.
+pub(crate) const COMMON_STRINGS: &[&str] = &[
+    "AABB", "AnimationClip", "AnimationCurve", "AnimationState", "Array", "Base", "BitField",
+    "bitset", "bool", "char", "ColorRGBA", "Component", "data", "deque", "double",
+    "dynamic_array", "FastPropertyName", "first", "float", "Font", "GameObject", "Generic Mono",
+    "GradientNEW", "GUID", "GUIStyle", "int", "list", "long long", "map", "Matrix4x4f", "MdFour",
+    "MonoBehaviour", "MonoScript", "m_ByteSize", "m_Curve", "m_EditorClassIdentifier",
+    "m_EditorHideFlags", "m_Enabled", "m_ExtensionPtr", "m_GameObject", "m_Index", "m_IsArray",
+    "m_IsStatic", "m_MetaFlag", "m_Name", "m_ObjectHideFlags", "m_PrefabInternal",
+    "m_PrefabParentObject", "m_Script", "m_StaticEditorFlags", "m_Type", "m_Version", "Object",
+    "pair", "PPtr<Component>", "PPtr<GameObject>", "PPtr<Material>", "PPtr<MonoBehaviour>",
+    "PPtr<MonoScript>", "PPtr<Object>", "PPtr<Prefab>", "PPtr<Sprite>", "PPtr<TextAsset>",
+    "PPtr<Texture>", "PPtr<Texture2D>", "PPtr<Transform>", "Prefab", "Quaternionf", "Rectf",
+    "RectInt", "RectOffset", "second", "set", "short", "size", "SInt16", "SInt32", "SInt64",
+    "SInt8", "staticvector", "string", "TextAsset", "TextMesh", "Texture", "Texture2D",
+    "Transform", "TypelessData", "UInt16", "UInt32", "UInt64", "UInt8", "unsigned int",
+    "unsigned long long", "unsigned short", "vector", "Vector2f", "Vector3f", "Vector4f",
+    "m_ScriptingClassIdentifier", "Gradient", "Type*", "int2_storage", "int3_storage",
+    "BoundsInt", "m_CorrespondingSourceObject", "m_PrefabInstance", "m_PrefabAsset", "FileSize",
+    "Hash128", "RenderingLayerMask",
+];
+
+static COMMON_STRING_OFFSETS: LazyLock<HashMap<u32, &'static str>> = LazyLock::new(|| {
+    let mut offset = 0u32;
+    COMMON_STRINGS.iter()
+        .map(|s| {
+            let entry = (offset, *s);
+            offset += s.len() as u32 + 1;
+            entry
+        })
+        .collect()
+});
+
+#[derive(Debug, PartialEq)]
+pub enum SerializedError {
+    Truncated,
+    UnsupportedVersion(u32),
+    NoTypeTree,
+}
+
+impl std::fmt::Display for SerializedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SerializedError::Truncated => write!(f, "file is truncated or not a Unity serialized file"),
+            SerializedError::UnsupportedVersion(v) => write!(f, "unsupported serialized file version {}", v),
+            SerializedError::NoTypeTree => write!(f, "file has no type trees (player build data cannot be decoded)"),
+        }
+    }
+}
+
+/// One decoded object.
+#[derive(Debug)]
+pub struct SerializedObject {
+    pub path_id: i64,
+    pub class_id: i32,
+    pub value: Value,
+}
+
+struct TypeNode {
+    type_name: String,
+    name: String,
+    level: u8,
+    is_array: bool,
+    align: bool,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+macro_rules! read_number {
+    ($name:ident, $ty:ty) => {
+        fn $name(&mut self) -> Result<$ty, SerializedError> {
+            let bytes: [u8; std::mem::size_of::<$ty>()] = self.bytes(std::mem::size_of::<$ty>())?
+                .try_into()
+                .map_err(|_| SerializedError::Truncated)?;
+            Ok(if self.little_endian { <$ty>::from_le_bytes(bytes) } else { <$ty>::from_be_bytes(bytes) })
+        }
+    };
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8], SerializedError> {
+        let end = self.pos.checked_add(n).filter(|end| *end <= self.data.len()).ok_or(SerializedError::Truncated)?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    read_number!(u8, u8);
+    read_number!(i8, i8);
+    read_number!(u16, u16);
+    read_number!(i16, i16);
+    read_number!(u32, u32);
+    read_number!(i32, i32);
+    read_number!(u64, u64);
+    read_number!(i64, i64);
+    read_number!(f32, f32);
+    read_number!(f64, f64);
+
+    /// A length prefix, checked against the remaining bytes (each element takes at least one).
+    fn count(&mut self) -> Result<usize, SerializedError> {
+        let n = self.i32()?;
+        usize::try_from(n).ok()
+            .filter(|n| *n <= self.data.len() - self.pos)
+            .ok_or(SerializedError::Truncated)
+    }
+
+    fn cstring(&mut self) -> Result<String, SerializedError> {
+        let len = self.data[self.pos.min(self.data.len())..].iter().position(|b| *b == 0).ok_or(SerializedError::Truncated)?;
+        let s = String::from_utf8_lossy(self.bytes(len)?).to_string();
+        self.pos += 1;
+        Ok(s)
+    }
+
+    /// Pad to a 4-byte boundary relative to `base`.
+    fn align(&mut self, base: usize) {
+        let rel = self.pos - base;
+        self.pos = base + rel.div_ceil(4) * 4;
+    }
+}
+
+/// Decode the objects of the given class IDs from a SerializedFile.
+pub fn read_objects(data: &[u8], class_ids: &[i32]) -> Result<Vec<SerializedObject>, SerializedError> {
+    // The header is always big-endian
+    let mut r = Reader { data, pos: 0, little_endian: false };
+    r.u32()?; // metadata size
+    r.u32()?; // file size
+    let version = r.u32()?;
+    let mut data_offset = u64::from(r.u32()?);
+    if !(MIN_VERSION..=MAX_VERSION).contains(&version) {
+        return Err(SerializedError::UnsupportedVersion(version));
+    }
+    let little_endian = r.u8()? == 0;
+    r.bytes(3)?;
+    if version >= 22 {
+        r.u32()?; // metadata size
+        r.u64()?; // file size
+        data_offset = r.u64()?;
+        r.u64()?;
+    }
+    let data_offset = usize::try_from(data_offset).map_err(|_| SerializedError::Truncated)?;
+    r.little_endian = little_endian;
+
+    r.cstring()?; // Unity version
+    r.i32()?; // target platform
+    if r.u8()? == 0 {
+        return Err(SerializedError::NoTypeTree);
+    }
+
+    let type_count = r.count()?;
+    let mut types: Vec<(i32, Vec<TypeNode>)> = Vec::with_capacity(type_count);
+    for _ in 0..type_count {
+        let class_id = r.i32()?;
+        r.u8()?; // stripped
+        r.i16()?; // script type index
+        if class_id == 114 {
+            r.bytes(16)?; // script ID
+        }
+        r.bytes(16)?; // type hash
+        let nodes = read_type_tree(&mut r, version)?;
+        if version >= 21 {
+            let dependencies = r.count()?;
+            r.bytes(dependencies * 4)?;
+        }
+        types.push((class_id, nodes));
+    }
+
+    let object_count = r.count()?;
+    let mut objects = Vec::new();
+    for _ in 0..object_count {
+        r.align(0);
+        let path_id = r.i64()?;
+        let byte_start = if version >= 22 { r.u64()? } else { u64::from(r.u32()?) };
+        r.u32()?; // byte size
+        let type_index = r.i32()?;
+        let Some((class_id, nodes)) = usize::try_from(type_index).ok().and_then(|i| types.get(i)) else {
+            return Err(SerializedError::Truncated);
+        };
+        if !class_ids.contains(class_id) || nodes.is_empty() {
+            continue;
+        }
+        let start = usize::try_from(byte_start).ok()
+            .and_then(|s| s.checked_add(data_offset))
+            .ok_or(SerializedError::Truncated)?;
+        let mut object_reader = Reader { data, pos: start, little_endian };
+        let ends = subtree_ends(nodes);
+        let value = read_value(&mut object_reader, nodes, &ends, 0, start)?;
+        objects.push(SerializedObject { path_id, class_id: *class_id, value });
+    }
+    Ok(objects)
+}
+
+fn read_type_tree(r: &mut Reader, version: u32) -> Result<Vec<TypeNode>, SerializedError> {
+    let node_count = r.count()?;
+    let buffer_size = r.count()?;
+    let mut raw = Vec::with_capacity(node_count);
+    for _ in 0..node_count {
+        r.u16()?; // version
+        let level = r.u8()?;
+        let flags = r.u8()?;
+        let type_offset = r.u32()?;
+        let name_offset = r.u32()?;
+        r.i32()?; // byte size
+        r.i32()?; // index
+        let meta_flag = r.i32()?;
+        if version >= 19 {
+            r.u64()?; // ref type hash
+        }
+        raw.push((level, flags, type_offset, name_offset, meta_flag));
+    }
+    let buffer = r.bytes(buffer_size)?;
+    let string_at = |offset: u32| -> String {
+        if offset & COMMON_STRING_FLAG != 0 {
+            return COMMON_STRING_OFFSETS.get(&(offset & !COMMON_STRING_FLAG)).map_or_else(String::new, |s| s.to_string());
+        }
+        let tail = buffer.get(offset as usize..).unwrap_or_default();
+        let end = tail.iter().position(|b| *b == 0).unwrap_or(tail.len());
+        String::from_utf8_lossy(&tail[..end]).to_string()
+    };
+    Ok(raw.into_iter()
+        .map(|(level, flags, type_offset, name_offset, meta_flag)| TypeNode {
+            type_name: string_at(type_offset),
+            name: string_at(name_offset),
+            level,
+            is_array: flags & 1 != 0,
+            align: meta_flag & ALIGN_BYTES_FLAG != 0,
+        })
+        .collect())
+}
+
+/// Exclusive end index of each node's subtree in the flattened tree.
+fn subtree_ends(nodes: &[TypeNode]) -> Vec<usize> {
+    (0..nodes.len())
+        .map(|i| (i + 1..nodes.len()).find(|&j| nodes[j].level <= nodes[i].level).unwrap_or(nodes.len()))
+        .collect()
+}
+
+fn children(ends: &[usize], i: usize) -> Vec<usize> {
+    let mut result = Vec::new();
+    let mut j = i + 1;
+    while j < ends[i] {
+        result.push(j);
+        j = ends[j];
+    }
+    result
+}
+
+fn read_value(r: &mut Reader, nodes: &[TypeNode], ends: &[usize], i: usize, base: usize) -> Result<Value, SerializedError> {
+    let node = &nodes[i];
+    let kids = children(ends, i);
+    let mut align = node.align;
+    let value = match node.type_name.as_str() {
+        "bool" => Value::from(r.u8()? != 0),
+        "SInt8" => Value::from(r.i8()?),
+        "UInt8" | "char" => Value::from(r.u8()?),
+        "SInt16" | "short" => Value::from(r.i16()?),
+        "UInt16" | "unsigned short" => Value::from(r.u16()?),
+        "SInt32" | "int" | "Type*" => Value::from(r.i32()?),
+        "UInt32" | "unsigned int" => Value::from(r.u32()?),
+        "SInt64" | "long long" => Value::from(r.i64()?),
+        "UInt64" | "unsigned long long" | "FileSize" => Value::from(r.u64()?),
+        "float" => Value::from(f64::from(r.f32()?)),
+        "double" => Value::from(r.f64()?),
+        "string" => {
+            let len = r.count()?;
+            let s = String::from_utf8_lossy(r.bytes(len)?).to_string();
+            align |= (i + 1..ends[i]).any(|j| nodes[j].align);
+            Value::String(s)
+        }
+        "TypelessData" => {
+            let len = r.count()?;
+            r.bytes(len)?;
+            Value::from(len)
+        }
+        _ if kids.first().is_some_and(|&k| nodes[k].is_array) => {
+            let array = kids[0];
+            align |= nodes[array].align;
+            let element = *children(ends, array).get(1).ok_or(SerializedError::Truncated)?;
+            let len = r.count()?;
+            if matches!(nodes[element].type_name.as_str(), "UInt8" | "SInt8" | "char") {
+                // Raw byte buffers are reported by size only
+                r.bytes(len)?;
+                Value::from(len)
+            } else {
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(read_value(r, nodes, ends, element, base)?);
+                }
+                Value::Array(items)
+            }
+        }
+        _ => {
+            let mut map = Map::new();
+            for &k in &kids {
+                map.insert(nodes[k].name.clone(), read_value(r, nodes, ends, k, base)?);
+            }
+            if node.type_name == "GUID" {
+                guid_string(&map).map_or(Value::Object(map), Value::String)
+            } else {
+                Value::Object(map)
+            }
+        }
+    };
+    if align {
+        r.align(base);
+    }
+    Ok(value)
+}
+
+/// Unity's text form of a GUID: each 32-bit part as hex, least significant nibble first.
+fn guid_string(map: &Map<String, Value>) -> Option<String> {
+    let parts = (0..4)
+        .map(|i| map.get(&format!("data[{}]", i)).and_then(Value::as_u64))
+        .collect::<Option<Vec<u64>>>()?;
+    Some(parts.iter()
+        .flat_map(|part| (0..8).map(move |nibble| format!("{:x}", (part >> (nibble * 4)) & 0xf)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_string_offsets() {
+        let offsets = &*COMMON_STRING_OFFSETS;
+        assert_eq!(offsets.get(&0), Some(&"AABB"));
+        assert_eq!(offsets.get(&222), Some(&"int"));
+        assert_eq!(offsets.get(&840), Some(&"string"));
+        assert_eq!(offsets.get(&1093), Some(&"m_CorrespondingSourceObject"));
+        assert_eq!(offsets.get(&1161), Some(&"Hash128"));
+    }
+
+    #[test]
+    fn test_rejects_unsupported_input() {
+        assert_eq!(read_objects(b"not a serialized file", &[1125]).unwrap_err(), SerializedError::UnsupportedVersion(0x7269_616c));
+        assert_eq!(read_objects(&[0u8; 8], &[1125]).unwrap_err(), SerializedError::Truncated);
+    }
+}
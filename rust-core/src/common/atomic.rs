@@ -0,0 +1,145 @@
+//! Crash-safe file replacement: write a temp file next to the target, fsync it, then rename
+//! it over the target, so readers see either the old or the new content, never a torn write.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Atomically replace (or create) `path` with `contents`.
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    write(path.as_ref(), contents.as_ref(), false)
+}
+
+/// Like `write_atomic`, first copying the current file (if any) to `<path>.bak`.
+pub fn write_atomic_with_backup<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    write(path.as_ref(), contents.as_ref(), true)
+}
+
+/// `<path>.bak`, where `write_atomic_with_backup` keeps the previous content.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+fn write(path: &Path, contents: &[u8], keep_backup: bool) -> io::Result<()> {
+    crate::safety::check_write(path)?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let file_name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    // Same directory as the target, so the rename never crosses filesystems
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.{}.tmp", std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+    let temp = dir.join(temp_name);
+
+    let result = write_temp(&temp, path, contents).and_then(|()| {
+        if keep_backup && path.is_file() {
+            fs::copy(path, backup_path(path))?;
+        }
+        rename_over(&temp, path)
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+        return result;
+    }
+    sync_dir(dir);
+    Ok(())
+}
+
+fn write_temp(temp: &Path, target: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(temp)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    // Keep the target's permissions rather than the temp file's defaults
+    if let Ok(meta) = fs::metadata(target) {
+        fs::set_permissions(temp, meta.permissions())?;
+    }
+    Ok(())
+}
+
+/// Renames replace the target atomically on POSIX systems.
+#[cfg(not(windows))]
+fn rename_over(from: &Path, to: &Path) -> io::Result<()> {
+    fs::rename(from, to)
+}
+
+/// On Windows the rename (MoveFileEx with REPLACE_EXISTING) fails with access denied while
+/// another process, such as the Unity editor or a virus scanner, briefly holds the target
+/// open; retry with backoff before giving up.
+#[cfg(windows)]
+fn rename_over(from: &Path, to: &Path) -> io::Result<()> {
+    const ATTEMPTS: u32 = 10;
+    let mut attempt = 0;
+    loop {
+        match fs::rename(from, to) {
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied && attempt + 1 < ATTEMPTS => {
+                attempt += 1;
+                std::thread::sleep(std::time::Duration::from_millis(10 * u64::from(attempt)));
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Persist the rename itself (POSIX); directories cannot be opened for syncing on Windows.
+fn sync_dir(dir: &Path) {
+    if cfg!(not(windows)) {
+        if let Ok(handle) = File::open(dir) {
+            let _ = handle.sync_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leftover_temps(dir: &Path) -> Vec<String> {
+        fs::read_dir(dir).unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".tmp"))
+            .collect()
+    }
+
+    #[test]
+    fn test_write_atomic_creates_and_replaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Main.unity");
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert!(!backup_path(&path).exists());
+        assert!(leftover_temps(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_write_atomic_with_backup_keeps_previous_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.json");
+        write_atomic_with_backup(&path, "v1").unwrap();
+        assert!(!backup_path(&path).exists(), "nothing to back up on first write");
+        write_atomic_with_backup(&path, "v2").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v2");
+        assert_eq!(fs::read_to_string(dir.path().join("index.json.bak")).unwrap(), "v1");
+    }
+
+    #[test]
+    fn test_write_atomic_failure_leaves_target_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(write_atomic(dir.path().join("missing").join("a.txt"), "x").is_err());
+
+        // Renaming a file over a directory fails after the temp file was written
+        let target = dir.path().join("Scenes");
+        fs::create_dir(&target).unwrap();
+        assert!(write_atomic(&target, "x").is_err());
+        assert!(target.is_dir());
+        assert!(leftover_temps(dir.path()).is_empty());
+    }
+}
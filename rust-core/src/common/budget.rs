@@ -0,0 +1,95 @@
+//! Per-call resource budgets for project-wide scans, so a pathological project yields partial
+//! results flagged `budget_exhausted` instead of an unbounded run.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+
+use super::ResourceBudget;
+
+/// Tracks files and bytes admitted against a `ResourceBudget`. Shared across rayon workers;
+/// which files make it in once the budget runs out mid-scan depends on scheduling.
+pub struct Meter {
+    max_files: Option<u64>,
+    max_bytes: Option<u64>,
+    max_ms: Option<u128>,
+    start: Instant,
+    /// Files and bytes asked for, including ones turned away
+    files: AtomicU64,
+    bytes: AtomicU64,
+    admitted: AtomicU64,
+    exhausted: AtomicBool,
+}
+
+impl Meter {
+    /// A meter for `budget`; unlimited when None.
+    pub fn new(budget: Option<&ResourceBudget>) -> Self {
+        Meter {
+            max_files: budget.and_then(|b| b.max_files).map(u64::from),
+            max_bytes: budget.and_then(|b| b.max_bytes).map(|n| n.max(0) as u64),
+            max_ms: budget.and_then(|b| b.max_ms).map(u128::from),
+            start: Instant::now(),
+            files: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            admitted: AtomicU64::new(0),
+            exhausted: AtomicBool::new(false),
+        }
+    }
+
+    /// Admit one more file of `bytes`. False, and the budget marked exhausted, once the file
+    /// count, byte total or wall time would go past its limit.
+    pub fn admit(&self, bytes: u64) -> bool {
+        if self.exhausted.load(Ordering::Relaxed) {
+            return false;
+        }
+        let files = self.files.fetch_add(1, Ordering::Relaxed) + 1;
+        let total_bytes = self.bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        let out_of_time = self.max_ms.is_some_and(|max| self.start.elapsed().as_millis() >= max);
+        if out_of_time || self.max_files.is_some_and(|max| files > max) || self.max_bytes.is_some_and(|max| total_bytes > max) {
+            self.exhausted.store(true, Ordering::Relaxed);
+            return false;
+        }
+        self.admitted.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+
+    /// Admit a file by its size on disk (0 when unknown).
+    pub fn admit_file<P: AsRef<std::path::Path>>(&self, path: P) -> bool {
+        self.admit(std::fs::metadata(path).map_or(0, |m| m.len()))
+    }
+
+    /// Files admitted so far.
+    pub fn files_admitted(&self) -> u32 {
+        u32::try_from(self.admitted.load(Ordering::Relaxed)).unwrap_or(u32::MAX)
+    }
+
+    /// Whether any file was turned away.
+    pub fn exhausted(&self) -> bool {
+        self.exhausted.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meter_limits() {
+        let unlimited = Meter::new(None);
+        assert!((0..1000).all(|_| unlimited.admit(1 << 20)));
+        assert!(!unlimited.exhausted());
+
+        let files = Meter::new(Some(&ResourceBudget { max_files: Some(2), max_bytes: None, max_ms: None }));
+        assert!(files.admit(10) && files.admit(10));
+        assert!(!files.admit(10));
+        assert!(files.exhausted());
+        assert!(!files.admit(0), "stays exhausted");
+        assert_eq!(files.files_admitted(), 2);
+
+        let bytes = Meter::new(Some(&ResourceBudget { max_files: None, max_bytes: Some(100), max_ms: None }));
+        assert!(bytes.admit(60) && bytes.admit(40));
+        assert!(!bytes.admit(1));
+
+        let time = Meter::new(Some(&ResourceBudget { max_files: None, max_bytes: None, max_ms: Some(0) }));
+        assert!(!time.admit(0));
+    }
+}
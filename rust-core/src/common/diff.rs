@@ -0,0 +1,190 @@
+//! Line-based unified diffs (Myers' algorithm), used to preview what a write would change.
+//! Hunks carry each line's original ending, so a diff matches the bytes written.
+
+/// Context lines around each hunk, as in `diff -u`.
+const CONTEXT: usize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Middle snake of a shortest edit script turning `a` into `b` (both non-empty, with no
+/// common prefix or suffix): `a[x..u] == b[y..v]` lies on an optimal path. Searches forward
+/// from the start and backward from the end until the two meet, keeping only one row each.
+fn middle_snake(a: &[&str], b: &[&str]) -> (usize, usize, usize, usize) {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let max = (n + m + 1) / 2;
+    let offset = max + 1;
+    let at = |k: isize| (offset + k) as usize;
+    // Furthest x on each diagonal, counted from the start (forward) or the end (backward)
+    let mut forward = vec![0isize; 2 * offset as usize + 1];
+    let mut backward = vec![0isize; 2 * offset as usize + 1];
+
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) { forward[at(k + 1)] } else { forward[at(k - 1)] + 1 };
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[at(k)] = x;
+            // Backward diagonal delta - k has had d - 1 steps
+            if delta % 2 != 0 && (delta - k).abs() < d && x + backward[at(delta - k)] >= n {
+                return (x0 as usize, y0 as usize, x as usize, y as usize);
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && backward[at(k - 1)] < backward[at(k + 1)]) { backward[at(k + 1)] } else { backward[at(k - 1)] + 1 };
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[(n - x - 1) as usize] == b[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[at(k)] = x;
+            if delta % 2 == 0 && (delta - k).abs() <= d && x + forward[at(delta - k)] >= n {
+                return ((n - x) as usize, (m - y) as usize, (n - x0) as usize, (m - y0) as usize);
+            }
+        }
+    }
+    unreachable!("the searches meet within (n + m) / 2 steps")
+}
+
+/// Append a shortest edit script turning `a` into `b` to `ops`, one op per line. Divides at
+/// the middle snake (Myers' linear-space refinement), so memory stays O(n + m).
+fn edit_script(a: &[&str], b: &[&str], ops: &mut Vec<Op>) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(x, y)| x == y).count();
+    ops.extend(std::iter::repeat_n(Op::Equal, prefix));
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    if a_mid.is_empty() || b_mid.is_empty() {
+        ops.extend(std::iter::repeat_n(Op::Delete, a_mid.len()));
+        ops.extend(std::iter::repeat_n(Op::Insert, b_mid.len()));
+    } else {
+        let (x, y, u, v) = middle_snake(a_mid, b_mid);
+        edit_script(&a_mid[..x], &b_mid[..y], ops);
+        ops.extend(std::iter::repeat_n(Op::Equal, u - x));
+        edit_script(&a_mid[u..], &b_mid[v..], ops);
+    }
+    ops.extend(std::iter::repeat_n(Op::Equal, suffix));
+}
+
+/// Unified diff (`--- a/path`, `+++ b/path`, `@@` hunks with 3 lines of context) turning
+/// `old` into `new`. Empty when the contents are equal.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    // Lines keep their endings, so the diff describes the exact bytes (CRLF included)
+    let a: Vec<&str> = old.split_inclusive('\n').collect();
+    let b: Vec<&str> = new.split_inclusive('\n').collect();
+    let mut ops = Vec::with_capacity(a.len().max(b.len()));
+    edit_script(&a, &b, &mut ops);
+
+    // Line positions in a and b before each op
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut i, mut j) = (0usize, 0usize);
+    for op in &ops {
+        positions.push((i, j));
+        match op {
+            Op::Equal => { i += 1; j += 1; }
+            Op::Delete => i += 1,
+            Op::Insert => j += 1,
+        }
+    }
+    positions.push((i, j));
+
+    // Group changed ops into hunks, merging those whose context would overlap
+    let changed: Vec<usize> = (0..ops.len()).filter(|&idx| ops[idx] != Op::Equal).collect();
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &idx in &changed {
+        let start = idx.saturating_sub(CONTEXT);
+        let end = (idx + 1 + CONTEXT).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = format!("--- a/{0}\n+++ b/{0}\n", path);
+    for (start, end) in hunks {
+        let (a_start, b_start) = positions[start];
+        let (a_end, b_end) = positions[end];
+        let range = |from: usize, count: usize| if count == 0 { format!("{},0", from) } else { format!("{},{}", from + 1, count) };
+        out.push_str(&format!("@@ -{} +{} @@\n", range(a_start, a_end - a_start), range(b_start, b_end - b_start)));
+        for idx in start..end {
+            let (i, j) = positions[idx];
+            let (marker, line) = match ops[idx] {
+                Op::Equal => (' ', a[i]),
+                Op::Delete => ('-', a[i]),
+                Op::Insert => ('+', b[j]),
+            };
+            out.push(marker);
+            out.push_str(line);
+            if !line.ends_with('\n') {
+                out.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_hunks_and_context() {
+        let old: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let new = old.replace("line 2\n", "line two\n").replace("line 15\n", "").replace("line 20\n", "line 20\nline 21\n");
+        let diff = unified_diff("Assets/A.unity", &old, &new);
+        assert_eq!(diff, "--- a/Assets/A.unity\n+++ b/Assets/A.unity\n\
+@@ -1,5 +1,5 @@\n line 1\n-line 2\n+line two\n line 3\n line 4\n line 5\n\
+@@ -12,9 +12,9 @@\n line 12\n line 13\n line 14\n-line 15\n line 16\n line 17\n line 18\n line 19\n line 20\n+line 21\n");
+        assert_eq!(unified_diff("x", &old, &old), "");
+    }
+
+    #[test]
+    fn test_unified_diff_keeps_crlf_and_missing_final_newline() {
+        let diff = unified_diff("f", "a\r\nb\r\nc", "a\r\nB\r\nc");
+        assert_eq!(diff, "--- a/f\n+++ b/f\n@@ -1,3 +1,3 @@\n a\r\n-b\r\n+B\r\n c\n\\ No newline at end of file\n");
+        assert!(unified_diff("f", "a\r\n", "a\n").contains("-a\r\n+a\n"), "a line ending change is a change");
+    }
+
+    #[test]
+    fn test_edit_script_is_minimal() {
+        let lines = |s: &'static str| s.split_inclusive('\n').collect::<Vec<_>>();
+        for (old, new, edits) in [
+            ("a\nb\nc\na\nb\nb\na\n", "c\nb\na\nb\na\nc\n", 5),
+            ("x\ny\nz\n", "1\n2\n", 5),
+            ("a\nb\n", "b\na\nb\na\n", 2),
+        ] {
+            let (a, b) = (lines(old), lines(new));
+            let mut ops = Vec::new();
+            edit_script(&a, &b, &mut ops);
+            assert_eq!(ops.iter().filter(|op| **op != Op::Equal).count(), edits, "{:?} -> {:?}", old, new);
+            // Replaying the script rebuilds the new side
+            let (mut i, mut j, mut rebuilt) = (0, 0, Vec::new());
+            for op in ops {
+                match op {
+                    Op::Equal => { assert_eq!(a[i], b[j]); rebuilt.push(a[i]); i += 1; j += 1; }
+                    Op::Delete => i += 1,
+                    Op::Insert => { rebuilt.push(b[j]); j += 1; }
+                }
+            }
+            assert_eq!((i, rebuilt), (a.len(), b));
+        }
+    }
+
+    #[test]
+    fn test_unified_diff_from_and_to_empty() {
+        assert_eq!(unified_diff("f", "", "a\nb\n"), "--- a/f\n+++ b/f\n@@ -0,0 +1,2 @@\n+a\n+b\n");
+        assert_eq!(unified_diff("f", "a\n", ""), "--- a/f\n+++ b/f\n@@ -1,1 +0,0 @@\n-a\n");
+    }
+}
@@ -1,21 +1,71 @@
+pub mod atomic;
+pub mod budget;
+pub mod diff;
+pub mod projection;
 pub mod types;
+pub mod yaml;
 
 pub use types::*;
 
-use std::fs;
+use regex::Regex;
 use std::io;
 use std::path::Path;
+use std::sync::LazyLock;
+
+/// GUID of an asset's `.meta` file (`guid: <32 hex>` at the start of a line).
+pub static META_GUID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^guid:[ \t]*([a-f0-9]{32})").unwrap()
+});
+
+/// Source prefab GUID of a PrefabInstance (`m_SourcePrefab: {fileID: ..., guid: ..., type: 3}`).
+pub static SOURCE_PREFAB_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_SourcePrefab:[ \t]*\{[^}\n]*guid:[ \t]*([a-f0-9]{32})").unwrap()
+});
 
 /// Read a Unity file from disk and normalize line endings (CRLF → LF).
 ///
 /// All Unity YAML parsing depends on LF-only content — regex patterns use literal \n
 /// for block header matching, and split('\n') is used for grep line indexing.
 pub fn read_unity_file<P: AsRef<Path>>(path: P) -> io::Result<String> {
-    let content = fs::read_to_string(path)?;
+    let _io = crate::telemetry::phase(crate::telemetry::Phase::Io);
+    let content = crate::safety::read_to_string(path)?;
+    Ok(normalize_line_endings(content))
+}
+
+/// Read a Unity file from disk as is, for edits that write it back with its own line endings.
+pub fn read_raw_unity_file<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let _io = crate::telemetry::phase(crate::telemetry::Phase::Io);
+    crate::safety::read_to_string(path)
+}
+
+/// A length or count as i64 for napi outputs (exact in JS up to 2^53), saturating instead of
+/// wrapping the way `as u32` does past 4 billion.
+pub fn to_count(n: usize) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
+}
+
+/// An offset taken from napi input as a usize; negative values clamp to 0.
+pub fn to_offset(n: i64) -> usize {
+    usize::try_from(n.max(0)).unwrap_or(usize::MAX)
+}
+
+/// Normalize line endings (CRLF → LF) of in-memory Unity content, e.g. unsaved editor
+/// buffers or git blobs passed to the `*_from_content` APIs.
+pub fn normalize_line_endings(content: String) -> String {
     if content.contains('\r') {
-        Ok(content.replace("\r\n", "\n"))
+        content.replace("\r\n", "\n")
+    } else {
+        content
+    }
+}
+
+/// `content` (LF-only, as parsed) with the line endings of `raw`, the file it was read from,
+/// so a rewritten CRLF file stays CRLF.
+pub fn match_line_endings(raw: &str, content: String) -> String {
+    if raw.contains("\r\n") {
+        content.replace('\n', "\r\n")
     } else {
-        Ok(content)
+        content
     }
 }
 
@@ -46,4 +96,27 @@ mod io_tests {
         let result = read_unity_file("/nonexistent/path/12345.unity");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_count_and_offset_conversions_saturate() {
+        assert_eq!(to_count(5_000_000_000), 5_000_000_000, "no wrap past u32::MAX");
+        assert_eq!(to_count(usize::MAX), i64::MAX);
+        assert_eq!(to_offset(-3), 0);
+        assert_eq!(to_offset(i64::MAX), usize::try_from(i64::MAX).unwrap_or(usize::MAX));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guid_patterns_stay_on_one_line() {
+        let guid = "0123456789abcdef0123456789abcdef";
+        assert_eq!(&META_GUID_RE.captures(&format!("fileFormatVersion: 2\nguid: {guid}\n")).unwrap()[1], guid);
+        assert!(!META_GUID_RE.is_match(&format!("guid:\n{guid}\n")));
+        let instance = format!("  m_SourcePrefab: {{fileID: 100100000, guid: {guid}, type: 3}}\n");
+        assert_eq!(&SOURCE_PREFAB_RE.captures(&instance).unwrap()[1], guid);
+        assert!(!SOURCE_PREFAB_RE.is_match(&format!("  m_SourcePrefab:\n  {{fileID: 100100000, guid: {guid}, type: 3}}\n")));
+    }
 }
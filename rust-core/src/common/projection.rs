@@ -0,0 +1,132 @@
+//! Field selection for JSON outputs, so targeted queries only marshal the fields they need.
+
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Parsed field selection: dotted paths (`"name"`, `"components.type"`) as a tree.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FieldSelection {
+    /// The path itself was selected, so its value is kept whole
+    whole: bool,
+    children: BTreeMap<String, FieldSelection>,
+}
+
+impl FieldSelection {
+    /// Build a selection from dotted paths. Selecting a parent (`"components"`) keeps it whole
+    /// even when a narrower path (`"components.type"`) is also given. Returns None when no
+    /// non-empty path was given, meaning "keep everything".
+    pub fn parse(fields: &[String]) -> Option<Self> {
+        let mut root = FieldSelection::default();
+        for field in fields {
+            let mut node = &mut root;
+            let mut descended = false;
+            for segment in field.split('.').map(str::trim).filter(|s| !s.is_empty()) {
+                if node.whole {
+                    break;
+                }
+                node = node.children.entry(segment.to_string()).or_default();
+                descended = true;
+            }
+            if descended {
+                node.whole = true;
+                node.children.clear();
+            }
+        }
+        if root.children.is_empty() { None } else { Some(root) }
+    }
+
+    /// Whether any part of the value at a dotted path survives the selection, so producers can
+    /// skip computing fields nobody asked for.
+    pub fn selects(&self, path: &str) -> bool {
+        let mut node = self;
+        for segment in path.split('.') {
+            if node.whole {
+                return true;
+            }
+            match node.children.get(segment) {
+                Some(child) => node = child,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Keep only the selected fields. Arrays apply the selection to each element; scalars
+    /// below a selected path are kept as they are.
+    pub fn apply(&self, value: Value) -> Value {
+        if self.whole {
+            return value;
+        }
+        match value {
+            Value::Object(map) => {
+                let mut out = Map::new();
+                for (key, value) in map {
+                    if let Some(child) = self.children.get(&key) {
+                        out.insert(key, child.apply(value));
+                    }
+                }
+                Value::Object(out)
+            }
+            Value::Array(items) => Value::Array(items.into_iter().map(|item| self.apply(item)).collect()),
+            other => other,
+        }
+    }
+}
+
+/// Apply an optional field list to a JSON output. Error objects are returned unchanged.
+pub fn project(value: Value, fields: Option<&[String]>) -> Value {
+    if value.get("is_error").and_then(Value::as_bool) == Some(true) {
+        return value;
+    }
+    match fields.and_then(FieldSelection::parse) {
+        Some(selection) => selection.apply(value),
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_project_nested_paths_through_arrays() {
+        let value = json!({
+            "name": "Player",
+            "tag": "Player",
+            "layer": 0,
+            "components": [
+                {"type": "Transform", "properties": {"m_LocalPosition": {"x": 0}}},
+                {"type": "MonoBehaviour", "script": "Assets/Player.cs", "properties": {}}
+            ]
+        });
+        let projected = project(value, Some(&fields(&["name", "components.type", "missing"])));
+        assert_eq!(projected, json!({
+            "name": "Player",
+            "components": [{"type": "Transform"}, {"type": "MonoBehaviour"}]
+        }));
+    }
+
+    #[test]
+    fn test_project_parent_selection_wins_and_errors_pass_through() {
+        let value = json!({"components": [{"type": "Transform", "file_id": "2"}], "name": "A"});
+        let projected = project(value.clone(), Some(&fields(&["components.type", "components"])));
+        assert_eq!(projected, json!({"components": [{"type": "Transform", "file_id": "2"}]}));
+        assert_eq!(project(value.clone(), Some(&fields(&["components", "components.type"]))), projected);
+        assert_eq!(project(value.clone(), Some(&[])), value);
+
+        let selection = FieldSelection::parse(&fields(&["name", "components.type"])).unwrap();
+        assert!(selection.selects("components"));
+        assert!(!selection.selects("components.properties"));
+        assert!(!selection.selects("children"));
+        let selection = FieldSelection::parse(&fields(&["components"])).unwrap();
+        assert!(selection.selects("components.properties"));
+
+        let error = json!({"error": "not found", "is_error": true});
+        assert_eq!(project(error.clone(), Some(&fields(&["name"]))), error);
+    }
+}
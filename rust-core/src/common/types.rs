@@ -26,8 +26,30 @@ pub struct Component {
     pub script_guid: Option<String>,
     #[napi(ts_type = "string | undefined")]
     pub script_name: Option<String>,
+    /// Parsed `m_EditorClassIdentifier` ("Assembly::Namespace.Class")
+    #[napi(ts_type = "EditorClassIdentifier | undefined")]
+    pub editor_class_identifier: Option<EditorClassIdentifier>,
+    /// `m_Enabled` of Behaviours, Renderers and Colliders (None for classes without it)
+    #[napi(ts_type = "boolean | undefined")]
+    #[serde(default)]
+    pub enabled: Option<bool>,
     #[napi(ts_type = "Record<string, any> | undefined")]
     pub properties: Option<serde_json::Value>,
+    /// Field renames across Unity versions the property filters matched through
+    #[napi(ts_type = "string[] | undefined")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aliases_applied: Option<Vec<String>>,
+}
+
+/// Script identity from `m_EditorClassIdentifier`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorClassIdentifier {
+    #[napi(ts_type = "string | undefined")]
+    pub assembly: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub namespace: Option<String>,
+    pub class_name: String,
 }
 
 /// GameObject with detailed component information
@@ -44,8 +66,54 @@ pub struct GameObjectDetail {
     pub components: Vec<Component>,
     #[napi(ts_type = "string[] | undefined")]
     pub children: Option<Vec<String>>,
+    /// Children in sibling order, resolved to their GameObjects (or nested prefab instances)
+    #[napi(ts_type = "ChildEntry[] | undefined")]
+    pub ordered_children: Option<Vec<ChildEntry>>,
     #[napi(ts_type = "string | undefined")]
     pub parent_transform_id: Option<String>,
+    /// Undefined tag / unnamed or out-of-range layer (when tag validation is enabled)
+    #[napi(ts_type = "string[] | undefined")]
+    pub warnings: Option<Vec<String>>,
+    #[napi(ts_type = "ObjectProvenance | undefined")]
+    pub provenance: Option<ObjectProvenance>,
+}
+
+/// Where a GameObject comes from
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectProvenance {
+    /// "scene" (defined directly in this scene or prefab), "prefab_instance" (part of a prefab
+    /// instance) or "added_to_prefab_instance" (added in this file under a prefab instance)
+    pub kind: String,
+    #[napi(ts_type = "string | undefined")]
+    pub prefab_instance_id: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub source_guid: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub source_prefab: Option<String>,
+    /// Prefabs the source prefab is a variant of, nearest base first
+    pub variant_chain: Vec<String>,
+    /// "project", "package", "package_sample" or "unknown" (source prefab not resolved)
+    pub origin: String,
+    /// Package name (display name for imported samples)
+    #[napi(ts_type = "string | undefined")]
+    pub package: Option<String>,
+}
+
+/// A child of a GameObject, in m_Children (sibling) order
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChildEntry {
+    /// Position among siblings (Transform.GetSiblingIndex)
+    pub sibling_index: u32,
+    pub transform_id: String,
+    /// Child GameObject name, or the nested prefab instance's name override (empty when unresolved)
+    pub name: String,
+    #[napi(ts_type = "string | undefined")]
+    pub game_object_id: Option<String>,
+    /// Set when the child Transform is the stripped root of a nested prefab instance
+    #[napi(ts_type = "string | undefined")]
+    pub prefab_instance_id: Option<String>,
 }
 
 /// PrefabInstance information
@@ -58,6 +126,56 @@ pub struct PrefabInstanceInfo {
     #[napi(ts_type = "string | undefined")]
     pub source_prefab: Option<String>,
     pub modifications_count: u32,
+    #[napi(ts_type = "RemovedPrefabObject[] | undefined")]
+    pub removed_components: Option<Vec<RemovedPrefabObject>>,
+    #[napi(ts_type = "RemovedPrefabObject[] | undefined")]
+    pub removed_game_objects: Option<Vec<RemovedPrefabObject>>,
+    /// Components added to the instance's objects in this file
+    #[napi(ts_type = "AddedPrefabComponent[] | undefined")]
+    pub added_components: Option<Vec<AddedPrefabComponent>>,
+    /// Transform the instance is parented to (m_Modification.m_TransformParent); undefined at the root
+    #[napi(ts_type = "string | undefined")]
+    pub parent_transform_id: Option<String>,
+    /// GameObject owning the parent Transform
+    #[napi(ts_type = "string | undefined")]
+    pub parent_game_object_id: Option<String>,
+    /// PrefabInstance owning the parent Transform, when it is a stripped object of another instance
+    #[napi(ts_type = "string | undefined")]
+    pub parent_prefab_instance_id: Option<String>,
+}
+
+/// A source-prefab object stripped by a PrefabInstance (m_RemovedComponents / m_RemovedGameObjects).
+/// `class_id`, `type_name` and `name` are filled in when the source prefab can be loaded;
+/// for components, `name` is the owning GameObject's name.
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedPrefabObject {
+    pub file_id: String,
+    #[napi(ts_type = "string | undefined")]
+    pub guid: Option<String>,
+    #[napi(ts_type = "number | undefined")]
+    pub class_id: Option<u32>,
+    #[napi(ts_type = "string | undefined")]
+    pub type_name: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub name: Option<String>,
+}
+
+/// A component added in this file to a GameObject of a prefab instance. It hangs off the
+/// stripped placeholder of the source GameObject it extends; `source_object_name` is filled in
+/// when the source prefab can be loaded.
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddedPrefabComponent {
+    /// Stripped GameObject placeholder the component is attached to
+    pub game_object_id: String,
+    /// The extended GameObject in the source prefab (m_CorrespondingSourceObject)
+    pub source_object_id: String,
+    #[napi(ts_type = "string | undefined")]
+    pub source_guid: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub source_object_name: Option<String>,
+    pub component: Component,
 }
 
 /// A single property override in a PrefabInstance
@@ -73,6 +191,55 @@ pub struct PrefabModification {
     pub object_reference: Option<String>,
 }
 
+/// A prefab whose nested-prefab chain exceeds the depth threshold
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefabNestingChain {
+    /// Outermost prefab of the chain
+    pub prefab: String,
+    /// Nesting levels below the prefab
+    pub depth: u32,
+    /// The deepest chain, from the prefab down
+    pub chain: Vec<String>,
+}
+
+/// Circular and over-deep prefab nesting in a project
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefabNestingReport {
+    pub project_root: String,
+    pub prefab_count: u32,
+    /// Depth threshold applied
+    pub max_depth: u32,
+    /// Groups of prefabs that (transitively) instantiate each other, each sorted by path
+    pub cycles: Vec<Vec<String>>,
+    /// Deepest first
+    pub deep_chains: Vec<PrefabNestingChain>,
+}
+
+/// A node in a nested-prefab expanded hierarchy.
+/// `source_file` records which scene/prefab the object was loaded from.
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NestedPrefabNode {
+    pub name: String,
+    pub file_id: String,
+    #[napi(js_name = "type")]
+    pub node_type: String,  // "GameObject" or "PrefabInstance"
+    pub source_file: String,
+    pub nesting_level: u32,
+    #[napi(ts_type = "boolean | undefined")]
+    pub active: Option<bool>,
+    #[napi(ts_type = "string | undefined")]
+    pub source_guid: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub source_prefab: Option<String>,
+    /// Why a PrefabInstance was not expanded: "depth_limit", "cycle", or "missing_source"
+    #[napi(ts_type = "string | undefined")]
+    pub unexpanded_reason: Option<String>,
+    pub children: Vec<NestedPrefabNode>,
+}
+
 /// Union result from find_by_name: either a GameObject or PrefabInstance
 #[napi(object)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,6 +257,14 @@ pub struct FindResult {
     pub source_prefab: Option<String>,
     #[napi(ts_type = "number | undefined")]
     pub modifications_count: Option<u32>,
+    /// Hierarchy path from the scene root, e.g. "Canvas/ShopPanel/BuyButton"
+    #[napi(ts_type = "string | undefined")]
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Name of the parent GameObject or PrefabInstance (None at the root)
+    #[napi(ts_type = "string | undefined")]
+    #[serde(default)]
+    pub parent_name: Option<String>,
 }
 
 impl FindResult {
@@ -103,6 +278,8 @@ impl FindResult {
             source_guid: None,
             source_prefab: None,
             modifications_count: None,
+            path: None,
+            parent_name: None,
         }
     }
 
@@ -116,6 +293,16 @@ impl FindResult {
             source_guid: Some(pi.source_guid.clone()),
             source_prefab: pi.source_prefab.clone(),
             modifications_count: Some(pi.modifications_count),
+            path: None,
+            parent_name: None,
+        }
+    }
+
+    /// Fill `path` and `parent_name` from `gameobject::hierarchy_breadcrumbs` output.
+    pub fn set_breadcrumb(&mut self, breadcrumbs: &std::collections::HashMap<String, (String, Option<String>)>) {
+        if let Some((path, parent_name)) = breadcrumbs.get(&self.file_id) {
+            self.path = Some(path.clone());
+            self.parent_name = parent_name.clone();
         }
     }
 }
@@ -125,7 +312,7 @@ impl FindResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SceneInspection {
     pub file: String,
-    pub count: u32,
+    pub count: i64,
     pub gameobjects: Vec<GameObjectDetail>,
     #[napi(ts_type = "PrefabInstanceInfo[] | undefined")]
     pub prefab_instances: Option<Vec<PrefabInstanceInfo>>,
@@ -137,6 +324,9 @@ pub struct SceneInspection {
 pub struct ScanOptions {
     #[napi(ts_type = "boolean | undefined")]
     pub verbose: Option<bool>,
+    /// Keep only these fields of each result (dotted paths, e.g. ["name", "components.type"])
+    #[napi(ts_type = "string[] | undefined")]
+    pub fields: Option<Vec<String>>,
 }
 
 /// Options for inspecting
@@ -150,6 +340,40 @@ pub struct InspectOptions {
     pub include_properties: Option<bool>,
     #[napi(ts_type = "boolean | undefined")]
     pub verbose: Option<bool>,
+    /// Recursively load nested prefab sources when inspecting a PrefabInstance
+    #[napi(ts_type = "boolean | undefined")]
+    pub expand_nested: Option<bool>,
+    #[napi(ts_type = "number | undefined")]
+    pub max_nesting_depth: Option<u32>,
+    /// Summarize property values (arrays, long strings) whose serialized size exceeds this many bytes
+    #[napi(ts_type = "number | undefined")]
+    pub max_property_bytes: Option<u32>,
+    /// Append pixel dimensions (and sprite name/size) to Texture2D/Sprite references
+    #[napi(ts_type = "boolean | undefined")]
+    pub resolve_images: Option<bool>,
+    /// Keep only these fields of the result (dotted paths, e.g. ["name", "components.type"])
+    #[napi(ts_type = "string[] | undefined")]
+    pub fields: Option<Vec<String>>,
+}
+
+/// Options for Scanner::set_project_root
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ProjectRootOptions {
+    /// Prebuilt `{ guid: relative_path }` cache, or the path of a persisted cache JSON file
+    /// (relative paths resolve against the project root)
+    #[napi(ts_type = "Record<string, string> | string | undefined")]
+    pub guid_cache: Option<serde_json::Value>,
+    /// Also resolve GUIDs of Packages/ and Library/PackageCache/ assets
+    #[napi(ts_type = "boolean | undefined")]
+    pub include_packages: Option<bool>,
+    /// `{ old_guid: new_guid_or_path }` for assets moved or replaced, or the path of such a JSON
+    /// file (see `set_guid_aliases`)
+    #[napi(ts_type = "Record<string, string> | string | undefined")]
+    pub guid_aliases: Option<serde_json::Value>,
+    /// An earlier `{ guid: relative_path }` cache, or the path of one (see `load_guid_history`)
+    #[napi(ts_type = "Record<string, string> | string | undefined")]
+    pub guid_history: Option<serde_json::Value>,
 }
 
 /// Pagination options for inspect_all
@@ -163,12 +387,20 @@ pub struct PaginationOptions {
     pub verbose: Option<bool>,
     #[napi(ts_type = "number | undefined")]
     pub page_size: Option<u32>,
+    /// Index of the first GameObject to return; negative values are treated as 0
     #[napi(ts_type = "number | undefined")]
-    pub cursor: Option<u32>,
+    pub cursor: Option<i64>,
     #[napi(ts_type = "number | undefined")]
     pub max_depth: Option<u32>,
     #[napi(ts_type = "string | undefined")]
     pub filter_component: Option<String>,
+    /// Summarize property values (arrays, long strings) whose serialized size exceeds this many bytes
+    #[napi(ts_type = "number | undefined")]
+    pub max_property_bytes: Option<u32>,
+    /// GameObject order: "document" (file order, the default) or "hierarchy" (the editor's
+    /// Hierarchy window order, from m_RootOrder / SceneRoots and m_Children)
+    #[napi(ts_type = "string | undefined")]
+    pub sort: Option<String>,
 }
 
 /// Paginated inspection result
@@ -176,11 +408,11 @@ pub struct PaginationOptions {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaginatedInspection {
     pub file: String,
-    pub total: u32,
-    pub total_in_scene: u32,
-    pub cursor: u32,
+    pub total: i64,
+    pub total_in_scene: i64,
+    pub cursor: i64,
     #[napi(ts_type = "number | undefined")]
-    pub next_cursor: Option<u32>,
+    pub next_cursor: Option<i64>,
     pub truncated: bool,
     pub page_size: u32,
     pub gameobjects: Vec<GameObjectDetail>,
@@ -190,61 +422,1823 @@ pub struct PaginatedInspection {
     pub error: Option<String>,
 }
 
-/// Chunk types for indexing
-#[napi(string_enum)]
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum ChunkType {
-    Prose,
-    Code,
-    Api,
-    Example,
+/// Aggregate statistics for a scene/prefab file
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneStats {
+    pub file: String,
+    pub size_bytes: i64,
+    pub gameobject_count: u32,
+    pub component_count: u32,
+    pub prefab_instance_count: u32,
+    pub script_count: u32,
+    /// Component count per type name (e.g. { "Transform": 12, "MonoBehaviour": 3 })
+    #[napi(ts_type = "Record<string, number>")]
+    pub component_types: serde_json::Value,
+    /// Distinct MonoBehaviour script types (script name, or "guid:<guid>" when unresolved)
+    pub script_types: Vec<String>,
 }
 
-/// Chunk metadata
+/// One prefab asset in the project catalog (see `catalog_prefabs`)
 #[napi(object)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ChunkMetadata {
-    pub file_path: String,
+pub struct PrefabCatalogEntry {
+    /// Project-relative path
+    pub path: String,
+    /// File name without extension
+    pub name: String,
     #[napi(ts_type = "string | undefined")]
-    pub section: Option<String>,
+    pub guid: Option<String>,
+    /// Root GameObject name; for variants, the name given to the base prefab instance
     #[napi(ts_type = "string | undefined")]
-    pub language: Option<String>,
+    pub root_object: Option<String>,
     #[napi(ts_type = "string | undefined")]
-    pub unity_class: Option<String>,
+    pub root_file_id: Option<String>,
+    /// Base prefab path of a variant ("guid:<guid>" when unresolved)
     #[napi(ts_type = "string | undefined")]
-    pub unity_method: Option<String>,
+    pub variant_of: Option<String>,
+    pub gameobject_count: u32,
+    pub component_count: u32,
+    /// Component count per type name
+    #[napi(ts_type = "Record<string, number>")]
+    pub component_types: serde_json::Value,
+    /// Distinct MonoBehaviour script types (script name, or "guid:<guid>" when unresolved)
+    pub script_types: Vec<String>,
+    /// Distinct prefabs instantiated inside this one, variant base included ("guid:<guid>" when unresolved)
+    pub nested_prefabs: Vec<String>,
+    /// Serialized size on disk
+    pub size_bytes: i64,
 }
 
-/// A chunk of indexed content
+/// Every prefab of a project with its size and contents, sorted by path
 #[napi(object)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Chunk {
-    pub id: String,
-    pub content: String,
-    pub tokens: u32,
-    #[napi(js_name = "type")]
-    pub chunk_type: ChunkType,
-    pub metadata: ChunkMetadata,
+pub struct PrefabCatalog {
+    pub project_root: String,
+    pub prefab_count: u32,
+    pub total_size_bytes: i64,
+    pub prefabs: Vec<PrefabCatalogEntry>,
 }
 
-/// Result of indexing operation
+/// Number of blocks of one class in a file
 #[napi(object)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct IndexResult {
-    pub chunks_indexed: u32,
-    pub total_tokens: u32,
-    pub files_processed: u32,
-    pub elapsed_ms: u32,
+pub struct ClassCount {
+    pub class_id: u32,
+    pub class_name: String,
+    pub count: u32,
 }
 
-/// Search result from index
+/// Block counts of a scene/prefab from its headers alone (see `count_objects`)
 #[napi(object)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SearchResult {
-    pub id: String,
-    pub content: String,
-    pub score: f64,
-    pub metadata: ChunkMetadata,
+pub struct ObjectCounts {
+    pub file: String,
+    pub size_bytes: i64,
+    pub total_blocks: u32,
+    pub gameobject_count: u32,
+    pub prefab_instance_count: u32,
+    /// Blocks marked `stripped` (placeholders for objects of prefab instances)
+    pub stripped_count: u32,
+    /// Per class, in ascending class ID order
+    pub classes: Vec<ClassCount>,
+}
+
+/// Hierarchy and component totals for the subtree under one GameObject (see `subtree_metrics`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtreeMetrics {
+    pub file: String,
+    pub game_object_id: String,
+    pub name: String,
+    /// GameObjects below the node, at any depth (the node itself excluded)
+    pub descendant_count: u32,
+    /// Levels below the node (0 for a leaf)
+    pub max_depth: u32,
+    /// Largest number of GameObjects on a single level
+    pub max_breadth: u32,
+    /// Largest number of direct children of any one GameObject
+    pub max_children: u32,
+    /// GameObjects per level, starting with the node itself
+    pub level_counts: Vec<u32>,
+    /// GameObjects in the subtree with m_IsActive: 0
+    pub inactive_count: u32,
+    /// Components on the node and all descendants
+    pub component_count: u32,
+    /// Component count per type name
+    #[napi(ts_type = "Record<string, number>")]
+    pub component_types: serde_json::Value,
+    /// MonoBehaviour count per script (script name, or "guid:<guid>" when unresolved)
+    #[napi(ts_type = "Record<string, number>")]
+    pub script_types: serde_json::Value,
+    /// PrefabInstances parented to a Transform in the subtree (their contents are not counted)
+    pub prefab_instance_count: u32,
+}
+
+/// A serialized Vector3 (`{x: 0, y: 1, z: 0}`)
+#[napi(object)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// A serialized rotation quaternion (`{x: 0, y: 0, z: 0, w: 1}`)
+#[napi(object)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+/// Position, rotation and scale in one space
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformTrs {
+    pub position: Vector3,
+    pub rotation: Quaternion,
+    /// Rotation in degrees as the inspector shows it (Unity's Z, X, Y order)
+    pub euler_angles: Vector3,
+    pub scale: Vector3,
+}
+
+/// Where a GameObject is, without reading its Transform properties (see `get_transform_summary`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformSummary {
+    pub file: String,
+    pub game_object_id: String,
+    pub name: String,
+    pub transform_id: String,
+    pub is_rect_transform: bool,
+    pub local: TransformTrs,
+    /// Composed through the parents; scale is the lossy scale (skew from rotated,
+    /// non-uniformly scaled parents is dropped)
+    pub world: TransformTrs,
+    /// False when an ancestor is part of a nested prefab instance, whose transform values are
+    /// not in this file; `world` is then relative to that ancestor
+    pub world_exact: bool,
+    /// Names of the ancestors, root first
+    pub parent_chain: Vec<String>,
+    /// Position among the parent's children (or among the scene roots)
+    #[napi(ts_type = "number | undefined")]
+    pub sibling_index: Option<u32>,
+    pub child_count: u32,
+}
+
+/// Difference between two versions of the same scene/prefab
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneStatsDiff {
+    pub file: String,
+    pub old: SceneStats,
+    pub new: SceneStats,
+    pub gameobjects_delta: i64,
+    pub components_delta: i64,
+    pub prefab_instances_delta: i64,
+    pub scripts_delta: i64,
+    pub size_delta_bytes: i64,
+    pub added_script_types: Vec<String>,
+    pub removed_script_types: Vec<String>,
+    /// Non-zero per-type component count changes
+    #[napi(ts_type = "Record<string, number>")]
+    pub component_type_deltas: serde_json::Value,
+}
+
+/// Physics layer collision matrix parsed from DynamicsManager.asset / Physics2DSettings.asset
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollisionMatrix {
+    pub file: String,
+    /// One row per named layer (all 32 layers when no TagManager.asset is found)
+    pub layers: Vec<LayerCollisionRow>,
+    /// Layer pairs (a <= b) whose collision is disabled
+    pub disabled_pairs: Vec<LayerPair>,
+}
+
+/// Collision settings of a single physics layer
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerCollisionRow {
+    pub index: u32,
+    #[napi(ts_type = "string | undefined")]
+    pub name: Option<String>,
+    /// Indices of layers this layer collides with
+    pub collides_with: Vec<u32>,
+}
+
+/// A pair of physics layers
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerPair {
+    pub layer_a: u32,
+    pub layer_b: u32,
+    #[napi(ts_type = "string | undefined")]
+    pub name_a: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub name_b: Option<String>,
+}
+
+/// A legacy Input Manager axis from InputManager.asset
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputAxis {
+    pub name: String,
+    pub descriptive_name: String,
+    pub descriptive_negative_name: String,
+    pub negative_button: String,
+    pub positive_button: String,
+    pub alt_negative_button: String,
+    pub alt_positive_button: String,
+    pub gravity: f64,
+    pub dead: f64,
+    pub sensitivity: f64,
+    pub snap: bool,
+    pub invert: bool,
+    /// "key_or_mouse_button", "mouse_movement" or "joystick_axis"
+    pub axis_type: String,
+    /// 0-based axis index (0 = X axis, 1 = Y axis, 2 = 3rd axis, ...)
+    pub axis: u32,
+    /// 0 = all joysticks, otherwise the joystick number
+    pub joy_num: u32,
+}
+
+/// Summary of a Camera component (class 20)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraInfo {
+    pub file_id: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub enabled: bool,
+    /// "perspective" or "orthographic"
+    pub projection: String,
+    pub field_of_view: f64,
+    pub orthographic_size: f64,
+    pub near_clip: f64,
+    pub far_clip: f64,
+    pub depth: f64,
+    /// "skybox", "solid_color", "depth_only" or "nothing"
+    pub clear_flags: String,
+    pub culling_mask: u32,
+    /// Layer names in the culling mask ("Everything" when all layers are rendered)
+    pub culling_layers: Vec<String>,
+    pub hdr: bool,
+    pub target_display: u32,
+}
+
+/// Summary of a Light component (class 108)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightInfo {
+    pub file_id: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub enabled: bool,
+    /// "spot", "directional", "point", "area" or "disc"
+    pub light_type: String,
+    pub intensity: f64,
+    pub range: f64,
+    pub spot_angle: f64,
+    /// "none", "hard" or "soft"
+    pub shadows: String,
+    /// "realtime", "mixed" or "baked"
+    pub bake_mode: String,
+    pub culling_mask: u32,
+    pub culling_layers: Vec<String>,
+}
+
+/// A Canvas (class 223) found by the canvas audit
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanvasInfo {
+    pub file: String,
+    pub file_id: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    /// "screen_space_overlay", "screen_space_camera" or "world_space"
+    pub render_mode: String,
+    pub sort_order: i32,
+    pub sorting_layer_id: i64,
+    pub override_sorting: bool,
+    pub target_display: u32,
+    /// Number of ancestor Canvases (0 = root canvas)
+    pub nesting_depth: u32,
+    pub has_camera: bool,
+    /// e.g. "duplicate_sort_order", "world_space_without_camera"
+    pub issues: Vec<String>,
+}
+
+/// Result of auditing the Canvases of a file or project
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanvasAudit {
+    pub path: String,
+    pub files_scanned: u32,
+    pub canvases: Vec<CanvasInfo>,
+    pub issue_count: u32,
+}
+
+/// A GameObject carrying a Rigidbody and/or Colliders, found by the physics audit
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhysicsObjectInfo {
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub layer: u32,
+    #[napi(ts_type = "string | undefined")]
+    pub layer_name: Option<String>,
+    /// "Rigidbody" or "Rigidbody2D"
+    #[napi(ts_type = "string | undefined")]
+    pub rigidbody: Option<String>,
+    pub is_kinematic: bool,
+    /// Collider type names on this GameObject (e.g. ["BoxCollider", "MeshCollider"])
+    pub colliders: Vec<String>,
+    /// e.g. "moving_collider_without_rigidbody", "non_convex_mesh_collider_on_rigidbody"
+    pub issues: Vec<String>,
+}
+
+/// Result of auditing Rigidbody/Collider setup in a scene or prefab
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhysicsAudit {
+    pub file: String,
+    pub objects: Vec<PhysicsObjectInfo>,
+    pub issue_count: u32,
+}
+
+/// Custom script execution order entry
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionOrderEntry {
+    pub guid: String,
+    #[napi(ts_type = "string | undefined")]
+    pub script_path: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub script_name: Option<String>,
+    /// Execution order value (negative runs before default time, positive after)
+    pub order: i32,
+    /// "meta" (MonoImporter executionOrder) or "mono_manager" (ProjectSettings/MonoManager.asset)
+    pub source: String,
+}
+
+/// A NavMeshAgent (class 195) summary
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavMeshAgentInfo {
+    pub file_id: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub enabled: bool,
+    pub agent_type_id: i64,
+    pub radius: f64,
+    pub height: f64,
+    pub speed: f64,
+    pub angular_speed: f64,
+    pub acceleration: f64,
+    pub stopping_distance: f64,
+    pub base_offset: f64,
+    pub avoidance_priority: i32,
+    pub issues: Vec<String>,
+}
+
+/// A NavMeshObstacle (class 208) summary
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavMeshObstacleInfo {
+    pub file_id: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub enabled: bool,
+    /// "capsule" or "box"
+    pub shape: String,
+    pub carve: bool,
+    /// Raw extents vector, e.g. "{x: 0.5, y: 0.5, z: 0.5}"
+    pub extents: String,
+}
+
+/// An OffMeshLink (class 191) summary
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffMeshLinkInfo {
+    pub file_id: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub enabled: bool,
+    /// Name of the GameObject whose Transform is the link start
+    #[napi(ts_type = "string | undefined")]
+    pub start: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub end: Option<String>,
+    pub bi_directional: bool,
+    pub activated: bool,
+    pub cost_override: f64,
+    pub issues: Vec<String>,
+}
+
+/// Navigation overview of a scene: NavMeshSettings bake parameters and all nav components
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavMeshReport {
+    pub file: String,
+    pub has_baked_navmesh: bool,
+    #[napi(ts_type = "number | undefined")]
+    pub baked_agent_radius: Option<f64>,
+    #[napi(ts_type = "number | undefined")]
+    pub baked_agent_height: Option<f64>,
+    #[napi(ts_type = "number | undefined")]
+    pub baked_agent_slope: Option<f64>,
+    #[napi(ts_type = "number | undefined")]
+    pub baked_agent_climb: Option<f64>,
+    pub agents: Vec<NavMeshAgentInfo>,
+    pub obstacles: Vec<NavMeshObstacleInfo>,
+    pub links: Vec<OffMeshLinkInfo>,
+    /// Scene-level problems, e.g. "agents_without_baked_navmesh"
+    pub issues: Vec<String>,
+}
+
+/// A local fileID reference that could not be carried across a GameObject move
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenReference {
+    /// "outgoing" (a moved object referenced one left behind) or
+    /// "incoming" (an object left behind referenced a moved one)
+    pub direction: String,
+    /// fileID (in the source file) of the block holding the reference
+    pub from_file_id: String,
+    pub property: String,
+    /// fileID (in the source file) the reference pointed at; it is reset to 0
+    pub target_file_id: String,
+}
+
+/// Result of transplanting a GameObject subtree from one scene/prefab into another
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveGameObjectResult {
+    pub success: bool,
+    #[napi(ts_type = "string | undefined")]
+    pub error: Option<String>,
+    pub src_file: String,
+    pub dst_file: String,
+    /// True when no file was written
+    pub dry_run: bool,
+    pub moved_block_count: u32,
+    /// fileID of the moved root GameObject in the destination
+    #[napi(ts_type = "string | undefined")]
+    pub new_file_id: Option<String>,
+    /// Source fileID -> destination fileID for every moved block
+    #[napi(ts_type = "Record<string, string>")]
+    pub file_id_map: serde_json::Value,
+    pub broken_references: Vec<BrokenReference>,
+    /// Unified diff of both files (destination first), returned on dry runs only
+    #[napi(ts_type = "string | undefined")]
+    pub diff: Option<String>,
+}
+
+/// Result of generating a new scene or prefab from a scaffold spec
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaffoldResult {
+    pub success: bool,
+    #[napi(ts_type = "string | undefined")]
+    pub error: Option<String>,
+    pub file: String,
+    /// True when no file was written
+    pub dry_run: bool,
+    pub object_count: u32,
+    pub block_count: u32,
+    /// Object path ("Root/Child") -> GameObject fileID
+    #[napi(ts_type = "Record<string, string>")]
+    pub file_ids: serde_json::Value,
+    /// Generated YAML, returned on dry runs only
+    #[napi(ts_type = "string | undefined")]
+    pub content: Option<String>,
+    /// Unified diff against the file being replaced (empty old side for a new file),
+    /// returned on dry runs only
+    #[napi(ts_type = "string | undefined")]
+    pub diff: Option<String>,
+}
+
+/// Which GameObjects a bulk edit applies to; every given criterion must match
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameObjectSelector {
+    /// Exact name, or a case-insensitive glob with `*` and `?`
+    #[napi(ts_type = "string | undefined")]
+    pub name: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub tag: Option<String>,
+    #[napi(ts_type = "number | undefined")]
+    pub layer: Option<u32>,
+    /// Component class (e.g. "Light") or script name (e.g. "PlayerController")
+    #[napi(ts_type = "string | undefined")]
+    pub component_type: Option<String>,
+    #[napi(ts_type = "boolean | undefined")]
+    pub active: Option<bool>,
+    /// Only descendants of the GameObject with this fileID
+    #[napi(ts_type = "string | undefined")]
+    pub under: Option<String>,
+}
+
+/// Values a bulk edit sets on every matched GameObject; omitted fields are left alone
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameObjectChanges {
+    #[napi(ts_type = "string | undefined")]
+    pub name: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub tag: Option<String>,
+    #[napi(ts_type = "number | undefined")]
+    pub layer: Option<u32>,
+    #[napi(ts_type = "boolean | undefined")]
+    pub active: Option<bool>,
+}
+
+/// One rewritten GameObject property
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyChange {
+    /// "m_Name", "m_TagString", "m_Layer" or "m_IsActive"
+    pub property: String,
+    pub old_value: String,
+    pub new_value: String,
+    /// 1-based line number
+    pub line: u32,
+}
+
+/// Changes made to one matched GameObject (empty when it already had the requested values)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameObjectChangeReport {
+    pub file_id: String,
+    /// Name before the edit
+    pub name: String,
+    pub changes: Vec<PropertyChange>,
+}
+
+/// Result of a bulk tag/layer/active/name edit of one scene or prefab
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkUpdateResult {
+    pub success: bool,
+    #[napi(ts_type = "string | undefined")]
+    pub error: Option<String>,
+    pub file: String,
+    /// True when no file was written
+    pub dry_run: bool,
+    pub matched_count: u32,
+    pub changed_count: u32,
+    pub objects: Vec<GameObjectChangeReport>,
+    /// Tags or layers TagManager does not define (only with tag validation on)
+    pub warnings: Vec<String>,
+    /// Unified diff of the would-be file change, returned on dry runs only
+    #[napi(ts_type = "string | undefined")]
+    pub diff: Option<String>,
+}
+
+/// A GameObject added, removed or modified since the last poll of a subscribed file
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneObjectChange {
+    pub file_id: String,
+    pub name: String,
+    /// Hierarchy path (names from the root, joined with '/')
+    pub path: String,
+    /// The GameObject block itself changed: name, tag, layer, active state, component list
+    /// or parent
+    pub gameobject_changed: bool,
+    /// Types of components added, removed or edited
+    pub changed_components: Vec<String>,
+}
+
+/// Changes to a subscribed scene or prefab since the previous poll (or the subscription)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneChangesResult {
+    pub success: bool,
+    #[napi(ts_type = "string | undefined")]
+    pub error: Option<String>,
+    pub file: String,
+    /// True when any GameObject was added, removed or modified
+    pub changed: bool,
+    pub added: Vec<SceneObjectChange>,
+    pub removed: Vec<SceneObjectChange>,
+    pub modified: Vec<SceneObjectChange>,
+}
+
+/// A component type a script expects on its own GameObject
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentExpectation {
+    pub type_name: String,
+    /// "RequireComponent", "GetComponent", "GetComponents" or "TryGetComponent"
+    pub source: String,
+    /// True for [RequireComponent]; runtime lookups are advisory
+    pub required: bool,
+}
+
+/// Components a script expects (and adds itself) at runtime
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptComponentUsage {
+    pub script_path: String,
+    #[napi(ts_type = "string | undefined")]
+    pub script_guid: Option<String>,
+    pub expects: Vec<ComponentExpectation>,
+    /// Types added via AddComponent<T>() on its own GameObject
+    pub adds: Vec<String>,
+}
+
+/// A GameObject whose script expects a component it does not have
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissingComponentIssue {
+    pub file: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub script_path: String,
+    pub missing_type: String,
+    pub source: String,
+    /// "error" for [RequireComponent], "warning" for runtime lookups
+    pub severity: String,
+}
+
+/// Result of cross-checking scripts' component expectations against scenes and prefabs
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentUsageReport {
+    pub project_root: String,
+    pub scripts: Vec<ScriptComponentUsage>,
+    pub files_checked: u32,
+    pub issues: Vec<MissingComponentIssue>,
+    /// The scan stopped early at its resource budget
+    pub budget_exhausted: bool,
+}
+
+/// How widely one MonoBehaviour script is instantiated (see `script_coverage`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptCoverageEntry {
+    pub name: String,
+    #[napi(ts_type = "string | undefined")]
+    pub namespace: Option<String>,
+    pub script_path: String,
+    pub guid: String,
+    /// Scenes with at least one instance
+    pub scene_count: u32,
+    /// Prefabs with at least one instance
+    pub prefab_count: u32,
+    /// Instances across all scanned files
+    pub instance_count: u32,
+    /// Scenes and prefabs instantiating the script, sorted
+    pub used_in: Vec<String>,
+    /// No scanned scene or prefab instantiates the script
+    pub unused: bool,
+}
+
+/// MonoBehaviour scripts of a project with their scene/prefab usage, unused scripts first
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptCoverageReport {
+    pub project_root: String,
+    pub script_count: u32,
+    pub unused_count: u32,
+    /// Scenes and prefabs read
+    pub files_scanned: u32,
+    pub scripts: Vec<ScriptCoverageEntry>,
+    /// The scan stopped early at its resource budget
+    pub budget_exhausted: bool,
+}
+
+/// One persistent listener of a serialized UnityEvent
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnityEventListener {
+    pub method: String,
+    /// "event_defined", "void", "object", "int", "float", "string" or "bool"
+    pub mode: String,
+    /// Argument for the listener's mode (object arguments are resolved like targets)
+    #[napi(ts_type = "string | undefined")]
+    pub argument: Option<String>,
+    /// "off", "editor_and_runtime" or "runtime_only"
+    pub call_state: String,
+    pub target_file_id: String,
+    #[napi(ts_type = "string | undefined")]
+    pub target_guid: Option<String>,
+    /// Asset path for cross-file targets
+    #[napi(ts_type = "string | undefined")]
+    pub target_path: Option<String>,
+    /// Component type or script name of the target
+    #[napi(ts_type = "string | undefined")]
+    pub target_type: Option<String>,
+    /// Name of the target GameObject (or asset)
+    #[napi(ts_type = "string | undefined")]
+    pub target_name: Option<String>,
+    /// "missing_target", "missing_target_asset", "missing_method" or "method_not_found"
+    #[napi(ts_type = "string | undefined")]
+    pub issue: Option<String>,
+}
+
+/// A UnityEvent field on a component with its persistent listeners
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnityEventInfo {
+    pub file: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub component_file_id: String,
+    pub component_type: String,
+    /// Dotted property path of the event (e.g. "m_OnClick")
+    pub event: String,
+    pub listeners: Vec<UnityEventListener>,
+}
+
+/// Project-wide UnityEvent audit
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnityEventReport {
+    pub path: String,
+    pub files_scanned: u32,
+    pub event_count: u32,
+    pub listener_count: u32,
+    /// Events with at least one broken listener (only the broken listeners are kept)
+    pub broken: Vec<UnityEventInfo>,
+}
+
+/// The parts of a `GlobalObjectId_V1-{type}-{assetGUID}-{objectId}-{prefabId}` string
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalObjectIdParts {
+    /// 0 null, 1 imported asset, 2 scene object, 3 source asset
+    pub identifier_type: u32,
+    pub asset_guid: String,
+    /// Local fileID of the object (of the source object, for objects inside a prefab instance)
+    pub target_object_id: String,
+    /// fileID of the PrefabInstance holding the object, "0" when it is not in one
+    pub target_prefab_id: String,
+}
+
+/// A reference into another scene serialized in a component: a GlobalObjectId string or a
+/// SceneAsset reference
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossSceneReference {
+    pub file: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub component_file_id: String,
+    pub component_type: String,
+    /// Serialized field holding the reference
+    pub property: String,
+    /// "global_object_id" or "scene_asset"
+    pub kind: String,
+    /// The serialized GlobalObjectId string or reference
+    pub value: String,
+    pub asset_guid: String,
+    #[napi(ts_type = "GlobalObjectIdParts | undefined")]
+    pub global_object_id: Option<GlobalObjectIdParts>,
+    /// Project-relative path of the referenced asset
+    #[napi(ts_type = "string | undefined")]
+    pub target_path: Option<String>,
+    /// "missing_asset" (GUID not in the project) or "missing_object" (the target scene has no such object)
+    #[napi(ts_type = "string | undefined")]
+    pub issue: Option<String>,
+}
+
+/// A mismatch between a material's serialized properties and its shader's Properties block
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialPropertyIssue {
+    /// "stale" (not declared by the shader), "type_mismatch" (wrong section) or "missing" (no serialized value)
+    pub kind: String,
+    pub property: String,
+    /// m_SavedProperties section: m_TexEnvs, m_Ints, m_Floats or m_Colors
+    pub section: String,
+    pub message: String,
+}
+
+/// Result of checking a material against its shader (see `check_material_shader`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialShaderCheck {
+    pub material: String,
+    /// Shader source path
+    pub shader: String,
+    #[napi(ts_type = "string | undefined")]
+    pub shader_name: Option<String>,
+    pub declared_count: u32,
+    pub serialized_count: u32,
+    pub issues: Vec<MaterialPropertyIssue>,
+}
+
+/// A legacy component, UnityScript/Boo script or deprecated C# API in use
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecatedUsage {
+    /// "component", "script" or "api"
+    pub category: String,
+    pub file: String,
+    /// 1-based source line (api)
+    #[napi(ts_type = "number | undefined")]
+    pub line: Option<u32>,
+    /// Owning GameObject (component, script)
+    #[napi(ts_type = "string | undefined")]
+    pub game_object_id: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub game_object_name: Option<String>,
+    /// Component type, script path or API name
+    pub name: String,
+    /// What to migrate to
+    pub replacement: String,
+}
+
+/// Modernization report of legacy/deprecated usage in a file or project
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecationReport {
+    pub path: String,
+    /// Scenes and prefabs scanned
+    pub files_scanned: u32,
+    /// C# sources scanned
+    pub scripts_scanned: u32,
+    /// Usage count per name (e.g. { "GUIText": 3, "WWW": 1 })
+    #[napi(ts_type = "Record<string, number>")]
+    pub summary: serde_json::Value,
+    pub usages: Vec<DeprecatedUsage>,
+}
+
+/// An animation curve binding that does not resolve against the animated hierarchy
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnimationBindingIssue {
+    /// Clip asset path
+    pub clip: String,
+    /// Hierarchy path relative to the animated GameObject ("" for the object itself)
+    pub path: String,
+    pub attribute: String,
+    pub class_id: u32,
+    pub target_type: String,
+    /// "missing_path", "missing_component" or "missing_property"
+    pub issue: String,
+    pub detail: String,
+}
+
+/// Binding validation of an AnimatorController or clip against one animated GameObject
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnimationBindingReport {
+    pub animation_path: String,
+    pub file: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub clips_checked: u32,
+    pub bindings_checked: u32,
+    pub issues: Vec<AnimationBindingIssue>,
+}
+
+/// An asset referenced (directly or through other assets) by a scene, with its estimated cost
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetCost {
+    pub path: String,
+    pub guid: String,
+    /// "texture", "mesh", "audio", "video", "font", "material", "prefab", "animation", "shader", "script" or "other"
+    pub category: String,
+    /// Source file size on disk
+    pub file_bytes: i64,
+    /// Heuristic runtime memory from file size and import settings
+    pub estimated_memory_bytes: i64,
+    /// Number of references across the scene and followed assets
+    pub references: u32,
+    /// Referenced by the scene itself rather than only through another asset
+    pub direct: bool,
+}
+
+/// Asset cost totals of one category
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetCostCategory {
+    pub category: String,
+    pub count: u32,
+    pub file_bytes: i64,
+    pub estimated_memory_bytes: i64,
+}
+
+/// Rough memory/build impact estimate of a scene or prefab
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneCostReport {
+    pub file: String,
+    pub serialized_bytes: i64,
+    pub gameobject_count: u32,
+    pub component_count: u32,
+    pub prefab_instance_count: u32,
+    pub dependency_count: u32,
+    /// Referenced GUIDs not found in the project (package or deleted assets)
+    pub unresolved_references: u32,
+    /// Total source size of all dependencies (build impact)
+    pub total_asset_bytes: i64,
+    /// Serialized scene size plus the estimated memory of all dependencies
+    pub estimated_memory_bytes: i64,
+    /// Per-category totals, largest estimated memory first
+    pub categories: Vec<AssetCostCategory>,
+    /// Most expensive dependencies by estimated memory
+    pub largest_assets: Vec<AssetCost>,
+}
+
+/// An external asset referenced by a scene or prefab
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuidManifestEntry {
+    pub guid: String,
+    /// Project path of the asset; unset when the GUID is not in the project
+    pub path: Option<String>,
+    /// Asset category as in SceneCostReport ("other" when unresolved)
+    pub category: String,
+    /// Number of references in the file
+    pub references: u32,
+    /// Where an unresolved GUID's asset went, from the GUID aliases or history
+    #[napi(ts_type = "GuidResolution | undefined")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<GuidResolution>,
+}
+
+/// What became of an asset GUID (see `resolve_guid`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuidResolution {
+    pub guid: String,
+    /// "current" (in the GUID cache), "aliased" (from the alias map), "regenerated" (its former
+    /// path has a new GUID), "moved" (its file now lives elsewhere) or "deleted"
+    pub status: String,
+    /// Current project path of the asset
+    #[napi(ts_type = "string | undefined")]
+    pub path: Option<String>,
+    /// The asset's GUID now, when it changed
+    #[napi(ts_type = "string | undefined")]
+    pub new_guid: Option<String>,
+    /// Path the GUID had in the GUID history
+    #[napi(ts_type = "string | undefined")]
+    pub former_path: Option<String>,
+    /// e.g. "moved to Assets/Art/Rock.mat"
+    pub message: String,
+}
+
+/// Every external GUID a scene or prefab references, sorted by GUID
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuidManifest {
+    pub file: String,
+    pub guid_count: u32,
+    pub reference_count: u32,
+    /// GUIDs not found in the project (package or deleted assets)
+    pub unresolved_count: u32,
+    pub entries: Vec<GuidManifestEntry>,
+}
+
+/// Options for compute_scene_bounds
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SceneBoundsOptions {
+    /// Also report bounds per layer or per tag
+    #[napi(ts_type = "'layer' | 'tag' | undefined")]
+    pub group_by: Option<String>,
+    /// Cells along the longer side of the occupancy grid (default: 32, max: 256; 0 skips the grid)
+    #[napi(ts_type = "number | undefined")]
+    pub grid_resolution: Option<u32>,
+    /// Plane the grid projects onto: "xz" (top-down, default) or "xy" (2D games)
+    #[napi(ts_type = "'xz' | 'xy' | undefined")]
+    pub plane: Option<String>,
+    /// Skip inactive objects and their children (default: false)
+    #[napi(ts_type = "boolean | undefined")]
+    pub active_only: Option<bool>,
+}
+
+/// Axis-aligned box around a set of world positions
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneAabb {
+    pub min: Vector3,
+    pub max: Vector3,
+    pub center: Vector3,
+    pub size: Vector3,
+    pub object_count: u32,
+}
+
+/// Bounds of the objects sharing one layer or tag
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneBoundsGroup {
+    /// Layer index or tag
+    pub key: String,
+    pub bounds: SceneAabb,
+}
+
+/// Object counts per cell of a 2D grid laid over the scene bounds
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OccupancyGrid {
+    pub plane: String,
+    pub columns: u32,
+    pub rows: u32,
+    /// World units per (square) cell
+    pub cell_size: f64,
+    /// Minimum corner on the plane's first axis (x)
+    pub origin_u: f64,
+    /// Minimum corner on the plane's second axis (z or y)
+    pub origin_v: f64,
+    /// Row-major counts, first row at the maximum of the second axis (north up)
+    pub cells: Vec<u32>,
+    /// One string per row: '.' empty, 1-9 objects, '#' ten or more
+    pub rendered: Vec<String>,
+}
+
+/// Extent of a scene's positioned objects, for overviews and "how big is this level"
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneBoundsReport {
+    pub file: String,
+    /// GameObjects and prefab instances placed (RectTransform UI is not)
+    pub object_count: u32,
+    /// Objects inside prefab instances, placed at their instance's root
+    pub approximate_count: u32,
+    /// Box around object pivots (renderer extents are not included); undefined when nothing is placed
+    #[napi(ts_type = "SceneAabb | undefined")]
+    pub bounds: Option<SceneAabb>,
+    pub groups: Vec<SceneBoundsGroup>,
+    #[napi(ts_type = "OccupancyGrid | undefined")]
+    pub grid: Option<OccupancyGrid>,
+}
+
+/// A sprite declared by a texture's import settings
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpriteInfo {
+    pub name: String,
+    /// fileID used by references to this sprite
+    pub file_id: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Pixel dimensions and sprites of a texture asset
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextureInfo {
+    pub path: String,
+    /// "png", "jpeg", "gif", "bmp", "psd" or "tga" (undefined when the header is not recognized)
+    #[napi(ts_type = "string | undefined")]
+    pub format: Option<String>,
+    #[napi(ts_type = "number | undefined")]
+    pub width: Option<u32>,
+    #[napi(ts_type = "number | undefined")]
+    pub height: Option<u32>,
+    /// TextureImporter spriteMode (0 none, 1 single, 2 multiple)
+    pub sprite_mode: u32,
+    pub sprites: Vec<SpriteInfo>,
+}
+
+/// Timing of one benchmark case on one synthetic scene size
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    /// "block_index", "scan_scene_minimal", "inspect_all_paginated" or "extract_properties"
+    pub case: String,
+    pub objects: u32,
+    pub scene_bytes: i64,
+    pub iterations: u32,
+    pub mean_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Result of `self_benchmark`, with the environment it ran in
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    /// Rayon worker threads
+    pub threads: u32,
+    /// Built without optimizations (numbers are not representative)
+    pub debug_build: bool,
+    pub results: Vec<BenchmarkResult>,
+}
+
+/// Inferred schema of one serialized property path
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertySchema {
+    /// Dotted serialized path; sequence items use `name[]`
+    pub path: String,
+    /// Observed value types, most frequent first ("int", "float", "string", "reference", "vector3", "array", ...)
+    pub types: Vec<String>,
+    /// Number of samples containing the property
+    pub occurrences: u32,
+    /// Missing from some samples (older serialized versions, conditional fields)
+    pub optional: bool,
+    /// Observed empty or `{fileID: 0}`
+    pub nullable: bool,
+    /// Numeric range (array length range for sequences)
+    #[napi(ts_type = "number | undefined")]
+    pub min: Option<f64>,
+    #[napi(ts_type = "number | undefined")]
+    pub max: Option<f64>,
+    pub examples: Vec<String>,
+}
+
+/// Schema of a component type inferred from its serialized occurrences across a project
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentSchema {
+    pub type_name: String,
+    /// Script backing the type, for MonoBehaviours
+    #[napi(ts_type = "string | undefined")]
+    pub script_path: Option<String>,
+    pub samples: u32,
+    /// Files containing at least one sample
+    pub files: Vec<String>,
+    pub properties: Vec<PropertySchema>,
+    /// The scan stopped early at its resource budget
+    pub budget_exhausted: bool,
+}
+
+/// One bin of a property histogram: a distinct value, or a numeric range
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBin {
+    /// The value, or "min..max" for a numeric range
+    pub label: String,
+    /// Inclusive numeric bounds (equal for a single numeric value)
+    #[napi(ts_type = "number | undefined")]
+    pub min: Option<f64>,
+    #[napi(ts_type = "number | undefined")]
+    pub max: Option<f64>,
+    pub count: u32,
+}
+
+/// Distribution of one component property across a project
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyHistogram {
+    pub component_type: String,
+    pub property_path: String,
+    /// Components of the type found
+    pub samples: u32,
+    /// Components of the type that do not serialize the property
+    pub missing: u32,
+    /// All values are numbers; bins are then ordered by value, else by count
+    pub numeric: bool,
+    #[napi(ts_type = "number | undefined")]
+    pub min: Option<f64>,
+    #[napi(ts_type = "number | undefined")]
+    pub max: Option<f64>,
+    #[napi(ts_type = "number | undefined")]
+    pub mean: Option<f64>,
+    pub bins: Vec<HistogramBin>,
+    /// Files containing at least one sample
+    pub files: Vec<String>,
+    /// Field renames across Unity versions the lookup fell back to, e.g. an m_Drag read for m_LinearDamping
+    pub aliases_applied: Vec<String>,
+    /// The scan stopped early at its resource budget
+    pub budget_exhausted: bool,
+}
+
+/// A GameObject or component matched across two versions of a scene
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectCorrelation {
+    /// "GameObject" or "Component"
+    pub kind: String,
+    pub old_file_id: String,
+    pub new_file_id: String,
+    /// GameObject name, or component type
+    pub name: String,
+    /// Hierarchy path of the (owning) GameObject in the new version
+    pub path: String,
+    /// "same_id", "path_and_components", "path" or "name_and_components"
+    pub method: String,
+    pub confidence: f64,
+}
+
+/// fileID mapping between two versions of a scene
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectCorrelationResult {
+    pub mappings: Vec<ObjectCorrelation>,
+    /// Matched objects whose fileID changed
+    pub remapped_count: u32,
+    /// Old GameObjects with no counterpart (removed or ambiguous)
+    pub unmatched_old: Vec<String>,
+    /// New GameObjects with no counterpart (added or ambiguous)
+    pub unmatched_new: Vec<String>,
+}
+
+/// One broken scene convention
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleViolation {
+    /// Rule `id`, or its kind and position ("naming[0]", "max_depth")
+    pub rule: String,
+    /// Rule severity, "error" unless the rule sets one
+    pub severity: String,
+    pub game_object_id: String,
+    pub game_object_name: String,
+    pub path: String,
+    pub message: String,
+}
+
+/// Result of checking a scene or prefab against a rule file
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleValidationReport {
+    pub file: String,
+    pub rules_path: String,
+    pub rules_checked: u32,
+    pub objects_checked: u32,
+    pub violations: Vec<RuleViolation>,
+}
+
+/// Component as returned by scan/inspect (snake_case keys; verbose fields only in verbose mode)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentOutput {
+    #[napi(js_name = "type")]
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Script path (non-verbose mode)
+    #[napi(ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    #[napi(js_name = "class_id", ts_type = "number | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_id: Option<u32>,
+    #[napi(js_name = "file_id", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+    #[napi(js_name = "script_path", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_path: Option<String>,
+    #[napi(js_name = "script_guid", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_guid: Option<String>,
+    #[napi(js_name = "script_name", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_name: Option<String>,
+    #[napi(js_name = "editor_class_identifier", ts_type = "EditorClassIdentifier | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub editor_class_identifier: Option<EditorClassIdentifier>,
+    /// `m_Enabled` of enableable components, in default and verbose output alike
+    #[napi(ts_type = "boolean | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[napi(ts_type = "Record<string, any> | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<serde_json::Value>,
+    /// Field renames across Unity versions the property filters matched through
+    #[napi(js_name = "aliases_applied", ts_type = "string[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aliases_applied: Option<Vec<String>>,
+}
+
+/// GameObject entry of scan_scene_with_components
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneObjectOutput {
+    pub name: String,
+    pub active: bool,
+    #[napi(js_name = "file_id", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+    #[napi(js_name = "component_count", ts_type = "number | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_count: Option<u32>,
+    pub components: Vec<ComponentOutput>,
+    pub tag: String,
+    pub layer: u32,
+}
+
+/// One PrefabInstance override, grouped by target in PrefabInstanceOutput
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefabModificationOutput {
+    #[napi(js_name = "propertyPath")]
+    #[serde(rename = "propertyPath")]
+    pub property_path: String,
+    pub value: String,
+}
+
+/// PrefabInstance entry of scan_scene_with_components, or the result of inspecting one
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefabInstanceOutput {
+    /// Always "PrefabInstance"
+    #[napi(js_name = "type", ts_type = "'PrefabInstance'")]
+    #[serde(rename = "type")]
+    pub object_type: String,
+    pub name: String,
+    #[napi(js_name = "file_id", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+    #[napi(js_name = "source_guid")]
+    pub source_guid: String,
+    #[napi(js_name = "source_prefab", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_prefab: Option<String>,
+    #[napi(js_name = "modifications_count")]
+    pub modifications_count: u32,
+    #[napi(js_name = "removed_components", ts_type = "RemovedPrefabObject[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_components: Option<Vec<RemovedPrefabObject>>,
+    #[napi(js_name = "removed_game_objects", ts_type = "RemovedPrefabObject[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_game_objects: Option<Vec<RemovedPrefabObject>>,
+    #[napi(js_name = "added_components", ts_type = "AddedPrefabComponent[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_components: Option<Vec<AddedPrefabComponent>>,
+    #[napi(js_name = "parent_transform_id", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_transform_id: Option<String>,
+    #[napi(js_name = "parent_game_object_id", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_game_object_id: Option<String>,
+    #[napi(js_name = "parent_prefab_instance_id", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_prefab_instance_id: Option<String>,
+    /// Overrides keyed by target fileID (with include_properties)
+    #[napi(ts_type = "Record<string, PrefabModificationOutput[]> | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modifications: Option<std::collections::HashMap<String, Vec<PrefabModificationOutput>>>,
+    /// Nested prefab tree (with expand_nested)
+    #[napi(ts_type = "NestedPrefabNode[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expanded: Option<Vec<NestedPrefabNode>>,
+    #[napi(js_name = "unexpanded_reason", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unexpanded_reason: Option<String>,
+}
+
+/// Result of inspecting one GameObject
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameObjectInspection {
+    pub name: String,
+    #[napi(js_name = "file_id")]
+    pub file_id: String,
+    pub active: bool,
+    pub tag: String,
+    pub layer: u32,
+    pub components: Vec<ComponentOutput>,
+    /// Child Transform fileIDs (verbose mode)
+    #[napi(ts_type = "string[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<String>>,
+    /// Children in sibling order with their resolved names
+    #[napi(js_name = "ordered_children", ts_type = "ChildEntry[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ordered_children: Option<Vec<ChildEntry>>,
+    #[napi(js_name = "parent_transform_id", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_transform_id: Option<String>,
+    /// Notes attached with annotate_object
+    #[napi(ts_type = "ObjectAnnotation[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Vec<ObjectAnnotation>>,
+    /// Undefined tag / unnamed or out-of-range layer (when tag validation is enabled)
+    #[napi(ts_type = "string[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<String>>,
+    /// Prefab instance / package origin (always for non-local objects, otherwise verbose mode)
+    #[napi(ts_type = "ObjectProvenance | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ObjectProvenance>,
+}
+
+/// Root object of a .asset file as returned by read_asset
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetObjectOutput {
+    #[napi(js_name = "class_id")]
+    pub class_id: u32,
+    #[napi(js_name = "file_id")]
+    pub file_id: String,
+    #[napi(js_name = "type_name")]
+    pub type_name: String,
+    pub name: String,
+    #[napi(ts_type = "Record<string, any>")]
+    pub properties: serde_json::Value,
+    #[napi(js_name = "script_guid", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_guid: Option<String>,
+    #[napi(js_name = "script_path", ts_type = "string | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_path: Option<String>,
+    /// Field renames across Unity versions the property filters matched through
+    #[napi(js_name = "aliases_applied", ts_type = "string[] | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aliases_applied: Option<Vec<String>>,
+}
+
+/// Error result of APIs that report failures in-band
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorOutput {
+    pub error: String,
+    #[napi(js_name = "is_error", ts_type = "true")]
+    pub is_error: bool,
+}
+
+/// Persistent note/labels attached to a scene or prefab object
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectAnnotation {
+    pub id: u32,
+    /// Scene/prefab path relative to the project root
+    pub file: String,
+    /// Current fileID of the annotated GameObject
+    pub file_id: String,
+    /// Object name and hierarchy path when last seen
+    pub object_name: String,
+    pub object_path: String,
+    #[napi(ts_type = "string | undefined")]
+    pub note: Option<String>,
+    pub labels: Vec<String>,
+    pub created_ms: i64,
+    pub updated_ms: i64,
+    /// Previous fileID when the object was found again under a new one
+    #[napi(ts_type = "string | undefined")]
+    pub remapped_from: Option<String>,
+    /// The object could not be found in the current file
+    pub orphaned: bool,
+}
+
+/// Something that may rely on a GameObject's current name
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameRisk {
+    /// "animation_path", "find_call", "string_literal", "unity_event_argument" or "name_conflict"
+    pub kind: String,
+    /// "high" (breaks after the rename) or "medium" (may break)
+    pub severity: String,
+    pub file: String,
+    /// 1-based line for C# sources
+    #[napi(ts_type = "number | undefined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// Curve path, source line or event that references the name
+    pub reference: String,
+    pub message: String,
+}
+
+/// What might break when a GameObject is renamed
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameImpactReport {
+    pub file: String,
+    pub game_object_id: String,
+    pub old_name: String,
+    pub new_name: String,
+    /// Hierarchy path before the rename
+    pub path: String,
+    pub new_path: String,
+    /// C# sources, scenes/prefabs and clips checked
+    pub files_scanned: u32,
+    /// High-severity risks first
+    pub risks: Vec<RenameRisk>,
+}
+
+/// Options for duplicate-structure detection
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateOptions {
+    /// Smallest cluster reported (default: 2)
+    #[napi(ts_type = "number | undefined")]
+    pub min_count: Option<u32>,
+    /// Property values copies may differ in and still be clustered together (default: 1)
+    #[napi(ts_type = "number | undefined")]
+    pub max_differences: Option<u32>,
+}
+
+/// Group of (near-)identical GameObject copies: a prefab extraction candidate
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCluster {
+    /// Component types of each copy (scripts by name), sorted
+    pub components: Vec<String>,
+    pub count: u32,
+    pub file_ids: Vec<String>,
+    /// Distinct names of the copies, without Unity's " (N)" suffix
+    pub names: Vec<String>,
+    /// Hierarchy path of the first copy
+    pub sample_path: String,
+    /// Objects below each copy (identical across the cluster)
+    pub descendant_count: u32,
+    /// Every copy has the same property values
+    pub identical: bool,
+    /// "Component.m_Field" values that differ between copies
+    pub varying_properties: Vec<String>,
+    pub suggestion: String,
+}
+
+/// Duplicate GameObject structures of a scene or prefab
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateStructureReport {
+    pub file: String,
+    pub objects_checked: u32,
+    /// Objects that belong to a cluster
+    pub duplicate_objects: u32,
+    /// Largest first (copies × objects per copy)
+    pub clusters: Vec<DuplicateCluster>,
+}
+
+/// A component property whose value is not what Unity writes for a fresh component
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyDefaultDiff {
+    /// Dotted property path (`m_Shadows.m_Type`, `m_Materials`)
+    pub path: String,
+    pub value: String,
+    /// Unity's default, unset when not known (scripts, types missing from the table)
+    #[napi(ts_type = "string | undefined")]
+    pub default: Option<String>,
+}
+
+/// Non-default properties of one component (see `diff_against_defaults`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentDefaultsDiff {
+    pub file: String,
+    pub component_id: String,
+    pub component_type: String,
+    pub class_id: u32,
+    /// The built-in defaults table covers this type
+    pub known_type: bool,
+    /// Properties compared (bookkeeping and hierarchy fields excluded)
+    pub total_properties: u32,
+    /// Properties left at their default
+    pub default_count: u32,
+    pub differences: Vec<PropertyDefaultDiff>,
+}
+
+/// GameObject compared by `find_similar`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarObject {
+    pub file_id: String,
+    pub name: String,
+    pub path: String,
+    /// 0..1: mean of the component overlap and the share of equal property values
+    pub similarity: f64,
+    /// Component types (scripts by name), sorted
+    pub components: Vec<String>,
+    /// Components of the target this object lacks
+    pub missing_components: Vec<String>,
+    /// Components this object has beyond the target's
+    pub extra_components: Vec<String>,
+    /// "Component.m_Field" values both objects have but that differ
+    pub differing_properties: Vec<String>,
+}
+
+/// Objects of a scene or prefab configured like a target GameObject
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarObjectsReport {
+    pub file: String,
+    pub target: SimilarObject,
+    pub threshold: f64,
+    pub objects_checked: u32,
+    /// Most similar first
+    pub matches: Vec<SimilarObject>,
+}
+
+/// One finding of a scene health report
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthIssue {
+    /// "error" or "warning"
+    pub severity: String,
+    pub message: String,
+    #[napi(ts_type = "string | undefined")]
+    pub game_object_id: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub game_object_name: Option<String>,
+}
+
+/// Score and findings of one health category
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCategory {
+    /// "missing_references", "events", "physics", "canvas", "deprecated" or "lint"
+    pub name: String,
+    /// 0..100; each error costs 20 points, each warning 5
+    pub score: u32,
+    /// Share of the category in the overall score
+    pub weight: u32,
+    pub error_count: u32,
+    pub warning_count: u32,
+    /// The first findings (the counts cover all of them)
+    pub issues: Vec<HealthIssue>,
+}
+
+/// Composite quality score of a scene or prefab (see `health_report`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneHealthReport {
+    pub file: String,
+    /// 0..100: weighted mean of the category scores
+    pub score: u32,
+    /// "A" (90+) to "F" (below 60)
+    pub grade: String,
+    pub issue_count: u32,
+    pub categories: Vec<HealthCategory>,
+    /// Checks left out and why (e.g. GUID references without a project)
+    pub skipped: Vec<String>,
+}
+
+/// Optional limits on one project-wide scan; a scan that hits one returns what it has so far
+/// with `budget_exhausted` set
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceBudget {
+    /// Files read at most
+    #[napi(ts_type = "number | undefined")]
+    pub max_files: Option<u32>,
+    /// Bytes read at most (file sizes on disk)
+    #[napi(ts_type = "number | undefined")]
+    pub max_bytes: Option<i64>,
+    /// Wall time in milliseconds after which no further files are read
+    #[napi(ts_type = "number | undefined")]
+    pub max_ms: Option<u32>,
+}
+
+/// Guardrails of safety mode (see `set_safety_config`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafetyConfig {
+    /// Directories file access is confined to
+    pub project_roots: Vec<String>,
+    /// Allow writes inside the project roots (default false)
+    #[napi(ts_type = "boolean | undefined")]
+    pub allow_writes: Option<bool>,
+    /// Bytes one call may read before its reads fail
+    #[napi(ts_type = "number | undefined")]
+    pub max_bytes_per_call: Option<i64>,
+}
+
+/// Phase timings of one napi call (see `set_call_timing`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallTiming {
+    /// napi function name
+    pub call: String,
+    #[napi(js_name = "total_ms")]
+    pub total_ms: f64,
+    /// Reading files from disk
+    #[napi(js_name = "io_ms")]
+    pub io_ms: f64,
+    /// Splitting YAML into blocks and GameObjects
+    #[napi(js_name = "parse_ms")]
+    pub parse_ms: f64,
+    /// Regex-driven component/property extraction
+    #[napi(js_name = "regex_ms")]
+    pub regex_ms: f64,
+}
+
+/// Chunk types for indexing
+#[napi(string_enum)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+// napi derives these itself, except in the `noop` build
+#[cfg_attr(feature = "noop", derive(Clone, Copy))]
+pub enum ChunkType {
+    Prose,
+    Code,
+    Api,
+    Example,
+}
+
+/// Chunk metadata
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkMetadata {
+    pub file_path: String,
+    /// Heading trail to the chunk, outermost first ("Physics > Rigidbody > AddForce")
+    #[napi(ts_type = "string | undefined")]
+    pub section: Option<String>,
+    /// Level (1-6) of the chunk's nearest heading
+    #[napi(ts_type = "number | undefined")]
+    #[serde(default)]
+    pub heading_level: Option<u32>,
+    #[napi(ts_type = "string | undefined")]
+    pub language: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub unity_class: Option<String>,
+    #[napi(ts_type = "string | undefined")]
+    pub unity_method: Option<String>,
+    /// 1-based page of the PDF manual the chunk was extracted from
+    #[napi(ts_type = "number | undefined")]
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Document title from markdown frontmatter
+    #[napi(ts_type = "string | undefined")]
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Document tags from markdown frontmatter
+    #[napi(ts_type = "string[] | undefined")]
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Unity version the document applies to (frontmatter `unity_version` or `version`)
+    #[napi(ts_type = "string | undefined")]
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// Restricts `search` to chunks whose document frontmatter and heading trail match every given field
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DocSearchFilter {
+    /// Case-insensitive substring of the document title
+    #[napi(ts_type = "string | undefined")]
+    pub title: Option<String>,
+    /// Tags the document must all carry (case-insensitive)
+    #[napi(ts_type = "string[] | undefined")]
+    pub tags: Option<Vec<String>>,
+    /// Version prefix: "2022" matches "2022.3"
+    #[napi(ts_type = "string | undefined")]
+    pub version: Option<String>,
+    /// Case-insensitive substring of the heading trail, e.g. "Physics >" for a manual chapter
+    #[napi(ts_type = "string | undefined")]
+    pub section: Option<String>,
+}
+
+/// A chunk of indexed content
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chunk {
+    pub id: String,
+    pub content: String,
+    pub tokens: u32,
+    #[napi(js_name = "type")]
+    pub chunk_type: ChunkType,
+    pub metadata: ChunkMetadata,
+}
+
+/// Result of indexing operation
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexResult {
+    pub chunks_indexed: u32,
+    pub total_tokens: u32,
+    pub files_processed: u32,
+    pub elapsed_ms: u32,
+    /// Chunks not stored because the index already holds the same content for the same file
+    pub duplicates_skipped: u32,
+}
+
+/// Result of compacting the index storage
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactResult {
+    /// Chunks merged into an earlier chunk with the same content and source file
+    pub duplicates_removed: u32,
+    /// Chunks dropped because their source file no longer exists
+    pub orphans_removed: u32,
+    pub chunks_remaining: u32,
+    /// Storage file size before and after rewriting, in bytes
+    pub bytes_before: i64,
+    pub bytes_after: i64,
+    pub bytes_saved: i64,
+}
+
+/// Search result from index
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub id: String,
+    pub content: String,
+    pub score: f64,
+    pub metadata: ChunkMetadata,
+}
+
+/// A document section whose title or file path matches a `find_sections` query
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionMatch {
+    pub file_path: String,
+    #[napi(ts_type = "string | undefined")]
+    pub section: Option<String>,
+    /// Where the query matched: "section", "path" or "both"
+    pub matched_in: String,
+    pub chunk_count: u32,
+    pub total_tokens: u32,
+    /// IDs of the section's chunks, for pulling content
+    pub chunk_ids: Vec<String>,
 }
 
 #[cfg(test)]
@@ -291,6 +2285,12 @@ mod tests {
             source_guid: "aabbccdd".to_string(),
             source_prefab: Some("Assets/Prefabs/Enemy.prefab".to_string()),
             modifications_count: 3,
+            removed_components: None,
+            removed_game_objects: None,
+            added_components: None,
+            parent_transform_id: None,
+            parent_game_object_id: None,
+            parent_prefab_instance_id: None,
         };
         let result = FindResult::from_prefab_instance(&pi, Some(70.0));
         assert_eq!(result.name, "Enemy");
@@ -311,6 +2311,12 @@ mod tests {
             source_guid: "11223344".to_string(),
             source_prefab: None,
             modifications_count: 0,
+            removed_components: None,
+            removed_game_objects: None,
+            added_components: None,
+            parent_transform_id: None,
+            parent_game_object_id: None,
+            parent_prefab_instance_id: None,
         };
         let result = FindResult::from_prefab_instance(&pi, None);
         assert!(result.source_prefab.is_none());
@@ -0,0 +1,183 @@
+//! Encoding of values as Unity YAML scalars, so writes serialize values the way the editor does.
+
+use napi_derive::napi;
+use serde_json::Value;
+
+/// Key orders Unity uses for common flow mappings; other keys follow alphabetically.
+const KEY_ORDERS: &[&[&str]] = &[
+    &["fileID", "guid", "type"],
+    &["x", "y", "z", "w"],
+    &["r", "g", "b", "a"],
+    &["m_X", "m_Y", "m_Width", "m_Height"],
+];
+
+/// Characters that cannot start a plain (unquoted) YAML scalar.
+const INDICATORS: &[char] = &['-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`'];
+
+/// Format a float like Unity: shortest single-precision round-trip digits, `-0` kept,
+/// exponent notation (`-4.371139e-8`, `1e+20`) only for very small or large magnitudes.
+pub fn format_float(value: f64) -> String {
+    let value = value as f32;
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    if value == 0.0 {
+        return if value.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+    let scientific = format!("{:e}", value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    if (-5..15).contains(&exponent) {
+        value.to_string()
+    } else if exponent < 0 {
+        format!("{}e{}", mantissa, exponent)
+    } else {
+        format!("{}e+{}", mantissa, exponent)
+    }
+}
+
+/// Quote a string only when a plain scalar would be misread: single quotes for YAML syntax
+/// (leading indicators, `: `, ` #`, surrounding spaces), double quotes with escapes for
+/// control characters, line breaks and non-ASCII text (`\u65B0`, as Unity writes it).
+/// The empty string is written as nothing.
+pub fn encode_string(value: &str) -> String {
+    if value.chars().any(|c| c.is_control() || !c.is_ascii()) {
+        let mut out = String::from("\"");
+        for c in value.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() && c.is_ascii() => out.push_str(&format!("\\x{:02X}", c as u32)),
+                c if (c as u32) > 0xFFFF => out.push_str(&format!("\\U{:08X}", c as u32)),
+                c if !c.is_ascii() => out.push_str(&format!("\\u{:04X}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        return out;
+    }
+    let needs_quotes = value.starts_with(INDICATORS)
+        || value.starts_with(' ')
+        || value.ends_with(' ')
+        || value.ends_with(':')
+        || value.contains(": ")
+        || value.contains(" #");
+    if needs_quotes {
+        format!("'{}'", value.replace('\'', "''"))
+    } else {
+        value.to_string()
+    }
+}
+
+fn encode_number(number: &serde_json::Number) -> String {
+    match (number.as_i64(), number.as_u64(), number.as_f64()) {
+        (Some(i), _, _) => i.to_string(),
+        (_, Some(u), _) => u.to_string(),
+        (_, _, Some(f)) if f.fract() == 0.0 && f.abs() < 1e15 => format!("{}", f as i64),
+        (_, _, Some(f)) => format_float(f),
+        _ => number.to_string(),
+    }
+}
+
+/// Encode a JSON value as a Unity YAML value: numbers as Unity writes them, booleans as
+/// `0`/`1`, strings quoted only when needed, flat objects as flow mappings in Unity's key
+/// order (`{x: 1, y: 2, z: 3}`, `{r: 1, g: 1, b: 1, a: 1}`, `{fileID: 11500000, guid: ..., type: 3}`).
+/// Nested objects and non-empty arrays have no single-line form and are rejected.
+pub fn encode_value(value: &Value) -> Result<String, String> {
+    match value {
+        Value::Null => Ok(String::new()),
+        Value::Bool(b) => Ok(if *b { "1" } else { "0" }.to_string()),
+        Value::Number(n) => Ok(encode_number(n)),
+        Value::String(s) => Ok(encode_string(s)),
+        Value::Array(items) if items.is_empty() => Ok("[]".to_string()),
+        Value::Array(_) => Err("Arrays are written as block sequences, one item per line".to_string()),
+        Value::Object(map) if map.is_empty() => Ok("{}".to_string()),
+        Value::Object(map) => {
+            let order = KEY_ORDERS.iter().find(|order| map.keys().all(|k| order.contains(&k.as_str()))).copied().unwrap_or(&[]);
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort_by_key(|k| (order.iter().position(|o| o == k).unwrap_or(usize::MAX), k.as_str()));
+            let mut entries = Vec::with_capacity(keys.len());
+            for key in keys {
+                let encoded = match &map[key] {
+                    // Reference IDs may exceed JS's safe integer range, so strings are accepted
+                    Value::String(s) if key == "fileID" && s.parse::<i64>().is_ok() => s.clone(),
+                    Value::String(s) if key == "guid" => s.to_lowercase(),
+                    Value::Object(_) | Value::Array(_) => return Err(format!("Nested value for \"{}\" has no flow form", key)),
+                    other => encode_value(other)?,
+                };
+                entries.push(format!("{}: {}", key, encoded));
+            }
+            Ok(format!("{{{}}}", entries.join(", ")))
+        }
+    }
+}
+
+/// Encode a JS value as a Unity YAML scalar or flow mapping (see `encode_value`), e.g.
+/// `0.1` → `0.1`, `true` → `1`, `"#Tag"` → `'#Tag'`, `{x: 0, y: 1, z: 0}` → `{x: 0, y: 1, z: 0}`,
+/// `{fileID: 2100000, guid, type: 2}` → `{fileID: 2100000, guid: ..., type: 2}`.
+/// Returns the encoded text, or an error object for values without a single-line form.
+#[napi(ts_return_type = "string | ErrorOutput")]
+pub fn encode_yaml_value(value: Value) -> Value {
+    match encode_value(&value) {
+        Ok(text) => Value::String(text),
+        Err(e) => serde_json::json!({ "error": e, "is_error": true }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_format_float_matches_unity() {
+        assert_eq!(format_float(0.1), "0.1");
+        assert_eq!(format_float(0.70710677), "0.70710677");
+        assert_eq!(format_float(-4.371139e-8), "-4.371139e-8");
+        assert_eq!(format_float(1e20), "1e+20");
+        assert_eq!(format_float(-0.0), "-0");
+        assert_eq!(format_float(f64::INFINITY), "Infinity");
+        assert_eq!(format_float(1000.5), "1000.5");
+    }
+
+    #[test]
+    fn test_encode_string_quoting() {
+        assert_eq!(encode_string("Main Camera"), "Main Camera");
+        assert_eq!(encode_string("#Header"), "'#Header'");
+        assert_eq!(encode_string("it's: here"), "'it''s: here'");
+        assert_eq!(encode_string(" padded"), "' padded'");
+        assert_eq!(encode_string("Line1\nLine2"), "\"Line1\\nLine2\"");
+        assert_eq!(encode_string("-"), "'-'");
+        assert_eq!(encode_string("a-b"), "a-b");
+    }
+
+    #[test]
+    fn test_encode_string_escapes_non_ascii() {
+        assert_eq!(encode_string("新規"), "\"\\u65B0\\u898F\"");
+        assert_eq!(encode_string("Café \"B\""), "\"Caf\\u00E9 \\\"B\\\"\"");
+        assert_eq!(encode_string("🙂"), "\"\\U0001F642\"");
+        assert_eq!(encode_string("\u{85}"), "\"\\u0085\"", "C1 controls use the \\u form");
+    }
+
+    #[test]
+    fn test_encode_value_flow_mappings_and_refs() {
+        assert_eq!(encode_value(&json!({"w": 1, "z": 0, "y": 0, "x": 0})).unwrap(), "{x: 0, y: 0, z: 0, w: 1}");
+        assert_eq!(encode_value(&json!({"a": 1, "b": 0.5, "g": 0.25, "r": 1})).unwrap(), "{r: 1, g: 0.25, b: 0.5, a: 1}");
+        assert_eq!(
+            encode_value(&json!({"type": 2, "guid": "ABCDEF0123456789ABCDEF0123456789", "fileID": "9223372036854775807"})).unwrap(),
+            "{fileID: 9223372036854775807, guid: abcdef0123456789abcdef0123456789, type: 2}"
+        );
+        assert_eq!(encode_value(&json!({"fileID": 0})).unwrap(), "{fileID: 0}");
+        assert_eq!(encode_value(&json!(true)).unwrap(), "1");
+        assert_eq!(encode_value(&json!(3.0)).unwrap(), "3");
+        assert_eq!(encode_value(&json!([])).unwrap(), "[]");
+        assert!(encode_value(&json!([1, 2])).is_err());
+        assert!(encode_yaml_value(json!({"x": {"y": 1}}))["is_error"].as_bool().unwrap());
+    }
+}
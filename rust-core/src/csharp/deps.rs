@@ -0,0 +1,417 @@
+//! Script-level dependency graph of a Unity project.
+//!
+//! Each script is mapped to the project types it references (bare names resolved through its
+//! using directives and enclosing namespaces, or namespace-qualified names), and each script is
+//! assigned to its assembly (nearest .asmdef/.asmref, or Unity's predefined assemblies).
+//! Script edges are then lifted to assembly edges to find cycles and missing asmdef references.
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::common;
+use super::summary::USING_RE;
+use super::{collect_cs_files, parse_csharp_types, strip_block_comments, strip_string_literals, GUID_RE};
+
+static QUALIFIED_NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b[A-Za-z_]\w*(?:\s*\.\s*[A-Za-z_]\w*)*").unwrap()
+});
+
+const PREDEFINED_RUNTIME: &str = "Assembly-CSharp";
+const PREDEFINED_EDITOR: &str = "Assembly-CSharp-Editor";
+const PREDEFINED_FIRSTPASS: &str = "Assembly-CSharp-firstpass";
+const PREDEFINED_EDITOR_FIRSTPASS: &str = "Assembly-CSharp-Editor-firstpass";
+
+/// Dependencies of one script on other scripts of the project.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct ScriptDependency {
+    /// Script path relative to the project root
+    pub path: String,
+    /// Assembly the script compiles into (asmdef name or a predefined Assembly-CSharp* name)
+    pub assembly: String,
+    /// Namespaces declared in the script
+    pub namespaces: Vec<String>,
+    pub usings: Vec<String>,
+    /// Type names declared in the script
+    pub declared_types: Vec<String>,
+    /// Project types the script references, as "Namespace.Type" (or "Type" in the global namespace)
+    pub referenced_types: Vec<String>,
+    /// Scripts declaring the referenced types
+    pub depends_on: Vec<String>,
+}
+
+/// Usage-derived dependencies of one assembly.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct AssemblyDependency {
+    pub name: String,
+    /// Defining .asmdef path (None for predefined assemblies)
+    pub asmdef_path: Option<String>,
+    pub script_count: u32,
+    /// References declared in the asmdef (implicit ones for predefined assemblies)
+    pub declared_references: Vec<String>,
+    /// Assemblies whose types this assembly's scripts actually reference
+    pub used_references: Vec<String>,
+    /// Used but not declared: these references fail to compile in Unity
+    pub undeclared_references: Vec<String>,
+}
+
+/// Script and assembly dependency graph of a project.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct ScriptDependencyReport {
+    pub project_root: String,
+    pub scripts: Vec<ScriptDependency>,
+    pub assemblies: Vec<AssemblyDependency>,
+    /// Groups of assemblies that depend on each other through script usage, each sorted by name
+    pub assembly_cycles: Vec<Vec<String>>,
+}
+
+/// An assembly definition (.asmdef) found under Assets/.
+#[derive(Clone, Debug)]
+pub(super) struct AsmDef {
+    name: String,
+    path: String,
+    /// Resolved assembly names
+    references: Vec<String>,
+    auto_referenced: bool,
+}
+
+/// Map every script under Assets/ to the project scripts it depends on, assign scripts to
+/// assemblies, and report assembly-level cycles and asmdef references that are used but missing.
+#[napi]
+pub fn analyze_script_dependencies(project_root: String) -> ScriptDependencyReport {
+    let _call = crate::telemetry::call("analyze_script_dependencies");
+    let root = PathBuf::from(&project_root);
+    let mut files: Vec<PathBuf> = Vec::new();
+    let assets = root.join("Assets");
+    if assets.is_dir() {
+        let filter = crate::walker::DirFilter::for_project(&root, &["bin".to_string()]);
+        collect_cs_files(&assets, &filter, &mut files);
+    }
+    files.sort();
+
+    let (asmdefs, assembly_dirs) = collect_assemblies(&root);
+    let sources: Vec<(String, String)> = files.par_iter()
+        .filter_map(|file| {
+            let content = common::read_unity_file(file).ok()?;
+            Some((relative(&root, file), content))
+        })
+        .collect();
+    let assemblies_by_script: Vec<String> = sources.iter().map(|(path, _)| assembly_for(path, &assembly_dirs)).collect();
+    let scripts = script_dependencies(&sources, &assemblies_by_script);
+    build_report(project_root, scripts, &asmdefs)
+}
+
+pub(super) fn relative(root: &Path, file: &Path) -> String {
+    file.strip_prefix(root).unwrap_or(file).to_string_lossy().replace('\\', "/")
+}
+
+/// Asmdefs under Assets/ plus a directory → assembly map covering .asmdef and .asmref folders.
+pub(super) fn collect_assemblies(root: &Path) -> (Vec<AsmDef>, Vec<(String, String)>) {
+    let mut raw: Vec<(String, serde_json::Value, Option<String>)> = Vec::new();
+    let mut asmrefs: Vec<(String, String)> = Vec::new();
+    for entry in crate::safety::walk_dir(root.join("Assets")).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if !matches!(ext, "asmdef" | "asmref") {
+            continue;
+        }
+        let Some(json) = common::read_unity_file(path).ok().and_then(|t| serde_json::from_str::<serde_json::Value>(&t).ok()) else { continue };
+        let rel = relative(root, path);
+        if ext == "asmref" {
+            if let Some(reference) = json["reference"].as_str() {
+                asmrefs.push((rel, reference.to_string()));
+            }
+            continue;
+        }
+        let guid = common::read_unity_file(format!("{}.meta", path.display())).ok()
+            .and_then(|meta| GUID_RE.captures(&meta).map(|c| c[1].to_string()));
+        raw.push((rel, json, guid));
+    }
+
+    let name_by_guid: HashMap<String, String> = raw.iter()
+        .filter_map(|(_, json, guid)| Some((guid.clone()?, json["name"].as_str()?.to_string())))
+        .collect();
+    let resolve = |reference: &str| match reference.strip_prefix("GUID:") {
+        Some(guid) => name_by_guid.get(&guid.to_lowercase()).cloned(),
+        None => Some(reference.to_string()),
+    };
+
+    let asmdefs: Vec<AsmDef> = raw.iter()
+        .filter_map(|(path, json, _)| {
+            let references = json["references"].as_array().into_iter().flatten()
+                .filter_map(|r| resolve(r.as_str()?))
+                .collect();
+            Some(AsmDef {
+                name: json["name"].as_str()?.to_string(),
+                path: path.clone(),
+                references,
+                auto_referenced: json["autoReferenced"].as_bool().unwrap_or(true),
+            })
+        })
+        .collect();
+
+    let parent_dir = |path: &str| path.rsplit_once('/').map_or(String::new(), |(dir, _)| dir.to_string());
+    let mut dirs: Vec<(String, String)> = asmdefs.iter().map(|a| (parent_dir(&a.path), a.name.clone())).collect();
+    dirs.extend(asmrefs.iter().filter_map(|(path, reference)| Some((parent_dir(path), resolve(reference)?))));
+    // Deepest folders first so the nearest definition wins
+    dirs.sort_by_key(|(dir, _)| std::cmp::Reverse(dir.matches('/').count()));
+    (asmdefs, dirs)
+}
+
+/// Assembly of a script: nearest asmdef/asmref folder, else Unity's predefined assemblies
+/// (Editor folders → *-Editor, Plugins/Standard Assets → *-firstpass).
+pub(super) fn assembly_for(path: &str, assembly_dirs: &[(String, String)]) -> String {
+    if let Some((_, name)) = assembly_dirs.iter().find(|(dir, _)| path.starts_with(&format!("{}/", dir))) {
+        return name.clone();
+    }
+    let segments: Vec<&str> = path.split('/').collect();
+    let folders = &segments[..segments.len().saturating_sub(1)];
+    let editor = folders.contains(&"Editor");
+    let firstpass = matches!(folders.get(1), Some(&"Plugins" | &"Standard Assets" | &"Pro Standard Assets"));
+    match (editor, firstpass) {
+        (true, true) => PREDEFINED_EDITOR_FIRSTPASS,
+        (true, false) => PREDEFINED_EDITOR,
+        (false, true) => PREDEFINED_FIRSTPASS,
+        (false, false) => PREDEFINED_RUNTIME,
+    }
+    .to_string()
+}
+
+/// Strip comments and string contents so identifiers in them are not read as type references.
+pub(super) fn code_only(content: &str) -> String {
+    strip_string_literals(&strip_block_comments(content))
+        .lines()
+        .map(|line| line.split_once("//").map_or(line, |(code, _)| code))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Resolve type references of every script against the types declared across all scripts.
+/// `sources` are (relative path, content); `assemblies` holds each script's assembly.
+fn script_dependencies(sources: &[(String, String)], assemblies: &[String]) -> Vec<ScriptDependency> {
+    let declared: Vec<Vec<(Option<String>, String)>> = sources.iter()
+        .map(|(path, content)| parse_csharp_types(content, path, None).into_iter().map(|t| (t.namespace, t.name)).collect())
+        .collect();
+    // Type name → (namespace, declaring script index)
+    let mut by_name: HashMap<&str, Vec<(Option<&str>, usize)>> = HashMap::new();
+    for (i, types) in declared.iter().enumerate() {
+        for (namespace, name) in types {
+            by_name.entry(name.as_str()).or_default().push((namespace.as_deref(), i));
+        }
+    }
+
+    sources.iter().enumerate()
+        .map(|(i, (path, content))| {
+            let cleaned = code_only(content);
+            let usings: Vec<String> = USING_RE.captures_iter(&cleaned).map(|c| c[1].to_string()).collect();
+            let namespaces: BTreeSet<String> = declared[i].iter().filter_map(|(ns, _)| ns.clone()).collect();
+            // Namespaces whose types are visible unqualified: usings, plus each enclosing namespace and its parents
+            let mut visible: HashSet<&str> = usings.iter().map(String::as_str).collect();
+            for ns in &namespaces {
+                let mut end = ns.len();
+                loop {
+                    visible.insert(&ns[..end]);
+                    match ns[..end].rfind('.') {
+                        Some(dot) => end = dot,
+                        None => break,
+                    }
+                }
+            }
+            let own_types: HashSet<&str> = declared[i].iter().map(|(_, name)| name.as_str()).collect();
+
+            let mut referenced: BTreeSet<String> = BTreeSet::new();
+            let mut depends_on: BTreeSet<usize> = BTreeSet::new();
+            let mut seen_names: HashSet<String> = HashSet::new();
+            for chain in QUALIFIED_NAME_RE.find_iter(&cleaned) {
+                let chain: String = chain.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                if !seen_names.insert(chain.clone()) {
+                    continue;
+                }
+                let segments: Vec<&str> = chain.split('.').collect();
+                // Bare name, or the first segment after a namespace qualifier
+                let candidates = segments.iter().enumerate().find_map(|(k, name)| {
+                    let qualifier = segments[..k].join(".");
+                    let found: Vec<(Option<&str>, usize)> = by_name.get(name)?.iter()
+                        .filter(|(ns, _)| match k {
+                            0 => ns.is_none_or(|ns| visible.contains(&ns)),
+                            _ => *ns == Some(qualifier.as_str()),
+                        })
+                        .copied()
+                        .collect();
+                    (!found.is_empty()).then_some((name, found))
+                });
+                let Some((name, found)) = candidates else { continue };
+                if segments.len() == 1 && own_types.contains(name) {
+                    continue;
+                }
+                for (ns, j) in found {
+                    if j == i {
+                        continue;
+                    }
+                    referenced.insert(ns.map_or(name.to_string(), |ns| format!("{}.{}", ns, name)));
+                    depends_on.insert(j);
+                }
+            }
+
+            ScriptDependency {
+                path: path.clone(),
+                assembly: assemblies[i].clone(),
+                namespaces: namespaces.into_iter().collect(),
+                usings,
+                declared_types: declared[i].iter().map(|(_, name)| name.clone()).collect(),
+                referenced_types: referenced.into_iter().collect(),
+                depends_on: depends_on.into_iter().map(|j| sources[j].0.clone()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// References Unity adds implicitly to a predefined assembly.
+fn implicit_references(name: &str, asmdefs: &[AsmDef]) -> Vec<String> {
+    let mut references: Vec<String> = asmdefs.iter().filter(|a| a.auto_referenced).map(|a| a.name.clone()).collect();
+    let predefined: &[&str] = match name {
+        PREDEFINED_RUNTIME => &[PREDEFINED_FIRSTPASS],
+        PREDEFINED_EDITOR => &[PREDEFINED_RUNTIME, PREDEFINED_FIRSTPASS, PREDEFINED_EDITOR_FIRSTPASS],
+        PREDEFINED_EDITOR_FIRSTPASS => &[PREDEFINED_FIRSTPASS],
+        _ => &[],
+    };
+    references.extend(predefined.iter().map(|p| p.to_string()));
+    references
+}
+
+/// Groups of mutually reachable nodes (strongly connected components with more than one node).
+pub(crate) fn cycles(edges: &BTreeMap<String, BTreeSet<String>>) -> Vec<Vec<String>> {
+    let reachable_from = |start: &str| {
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for next in edges.get(node).into_iter().flatten() {
+                if seen.insert(next.as_str()) {
+                    stack.push(next.as_str());
+                }
+            }
+        }
+        seen
+    };
+    let reach: BTreeMap<&str, HashSet<&str>> = edges.keys().map(|k| (k.as_str(), reachable_from(k))).collect();
+    let mut assigned: HashSet<&str> = HashSet::new();
+    let mut groups = Vec::new();
+    for (node, reachable) in &reach {
+        if assigned.contains(node) || !reachable.contains(node) {
+            continue;
+        }
+        let group: Vec<String> = reach.iter()
+            .filter(|(other, other_reach)| reachable.contains(*other) && other_reach.contains(node))
+            .map(|(other, _)| other.to_string())
+            .collect();
+        if group.len() > 1 {
+            assigned.extend(reach.keys().copied().filter(|k| group.iter().any(|g| g == k)));
+            groups.push(group);
+        }
+    }
+    groups
+}
+
+fn build_report(project_root: String, scripts: Vec<ScriptDependency>, asmdefs: &[AsmDef]) -> ScriptDependencyReport {
+    let assembly_of: HashMap<&str, &str> = scripts.iter().map(|s| (s.path.as_str(), s.assembly.as_str())).collect();
+    let mut used: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut script_counts: BTreeMap<&str, u32> = BTreeMap::new();
+    for script in &scripts {
+        *script_counts.entry(script.assembly.as_str()).or_default() += 1;
+        let targets = used.entry(script.assembly.clone()).or_default();
+        for dep in &script.depends_on {
+            if let Some(target) = assembly_of.get(dep.as_str()).filter(|t| **t != script.assembly) {
+                targets.insert(target.to_string());
+            }
+        }
+    }
+
+    let assemblies = script_counts.iter()
+        .map(|(name, count)| {
+            let asmdef = asmdefs.iter().find(|a| a.name == *name);
+            let declared_references = asmdef.map_or_else(|| implicit_references(name, asmdefs), |a| a.references.clone());
+            let used_references: Vec<String> = used.get(*name).into_iter().flatten().cloned().collect();
+            AssemblyDependency {
+                name: name.to_string(),
+                asmdef_path: asmdef.map(|a| a.path.clone()),
+                script_count: *count,
+                undeclared_references: used_references.iter().filter(|u| !declared_references.contains(u)).cloned().collect(),
+                declared_references,
+                used_references,
+            }
+        })
+        .collect();
+
+    ScriptDependencyReport {
+        project_root,
+        assembly_cycles: cycles(&used),
+        scripts,
+        assemblies,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_assembly_for_predefined_folders() {
+        let dirs = vec![("Assets/Game/Core".to_string(), "Game.Core".to_string())];
+        assert_eq!(assembly_for("Assets/Game/Core/Health.cs", &dirs), "Game.Core");
+        assert_eq!(assembly_for("Assets/Scripts/Player.cs", &dirs), PREDEFINED_RUNTIME);
+        assert_eq!(assembly_for("Assets/Scripts/Editor/PlayerEditor.cs", &dirs), PREDEFINED_EDITOR);
+        assert_eq!(assembly_for("Assets/Plugins/Lib.cs", &dirs), PREDEFINED_FIRSTPASS);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let mut edges: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        edges.insert("A".into(), BTreeSet::from(["B".to_string()]));
+        edges.insert("B".into(), BTreeSet::from(["C".to_string()]));
+        edges.insert("C".into(), BTreeSet::from(["A".to_string()]));
+        edges.insert("D".into(), BTreeSet::from(["A".to_string()]));
+        assert_eq!(cycles(&edges), vec![vec!["A".to_string(), "B".to_string(), "C".to_string()]]);
+    }
+
+    #[test]
+    fn test_analyze_script_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "Assets/Game/Core/Game.Core.asmdef", r#"{"name": "Game.Core", "references": ["GUID:bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"]}"#);
+        write(root, "Assets/Game/UI/Game.UI.asmdef", r#"{"name": "Game.UI", "references": ["Game.Core"]}"#);
+        write(root, "Assets/Game/UI/Game.UI.asmdef.meta", "fileFormatVersion: 2\nguid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n");
+        write(root, "Assets/Game/Core/Health.cs", "namespace Game.Core {\n    public class Health\n    {\n        // HealthBar is mentioned only in a comment\n        public Game.UI.HealthBar bar;\n    }\n}\n");
+        write(root, "Assets/Game/UI/HealthBar.cs", "using Game.Core;\n\nnamespace Game.UI {\n    public class HealthBar\n    {\n        Health target; string label = \"Player\";\n    }\n}\n");
+        write(root, "Assets/Scripts/Player.cs", "using UnityEngine;\n\npublic class Player : MonoBehaviour\n{\n    Game.Core.Health health;\n}\n");
+        write(root, "Assets/Scripts/Spawner.cs", "public class Spawner\n{\n    Player player;\n}\n");
+
+        let report = analyze_script_dependencies(root.to_string_lossy().to_string());
+        let script = |name: &str| report.scripts.iter().find(|s| s.path.ends_with(name)).unwrap();
+        assert_eq!(script("HealthBar.cs").referenced_types, vec!["Game.Core.Health"]);
+        assert_eq!(script("Health.cs").depends_on, vec!["Assets/Game/UI/HealthBar.cs"]);
+        assert_eq!(script("Player.cs").assembly, PREDEFINED_RUNTIME);
+        assert_eq!(script("Spawner.cs").depends_on, vec!["Assets/Scripts/Player.cs"]);
+        assert!(script("Player.cs").referenced_types.iter().all(|t| t != "Player"));
+
+        assert_eq!(report.assembly_cycles, vec![vec!["Game.Core".to_string(), "Game.UI".to_string()]]);
+        let core = report.assemblies.iter().find(|a| a.name == "Game.Core").unwrap();
+        assert_eq!(core.declared_references, vec!["Game.UI"]);
+        assert!(core.undeclared_references.is_empty());
+        let runtime = report.assemblies.iter().find(|a| a.name == PREDEFINED_RUNTIME).unwrap();
+        assert!(runtime.declared_references.contains(&"Game.Core".to_string()));
+        assert_eq!(runtime.script_count, 2);
+    }
+}
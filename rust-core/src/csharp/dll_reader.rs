@@ -58,13 +58,14 @@ const ELEMENT_TYPE_SZARRAY: u8 = 0x1D;
 /// GUID is always None for DLL types (they have no .meta files).
 #[napi]
 pub fn extract_dll_types(path: String) -> Vec<CSharpTypeRef> {
+    let _call = crate::telemetry::call("extract_dll_types");
     let p = Path::new(&path);
     extract_types_from_dll(p, &path)
 }
 
 /// Internal extraction from a DLL file.
 pub(crate) fn extract_types_from_dll(path: &Path, rel_path: &str) -> Vec<CSharpTypeRef> {
-    let data = match std::fs::read(path) {
+    let data = match crate::safety::read(path) {
         Ok(d) => d,
         Err(_) => return vec![],
     };
@@ -300,8 +301,9 @@ fn parse_dotnet_types(data: &[u8], file_path: &str) -> Result<Vec<CSharpTypeRef>
 /// and struct/enum distinction via the Extends column.
 #[napi]
 pub fn extract_dll_fields(path: String) -> Vec<CSharpTypeInfo> {
+    let _call = crate::telemetry::call("extract_dll_fields");
     let p = Path::new(&path);
-    let data = match std::fs::read(p) {
+    let data = match crate::safety::read(p) {
         Ok(d) => d,
         Err(_) => return vec![],
     };
@@ -985,7 +987,7 @@ mod tests {
 
         // Find any .dll files
         let mut found_dlls = false;
-        for entry in walkdir::WalkDir::new(&dll_dir).into_iter().filter_map(|e| e.ok()) {
+        for entry in crate::safety::walk_dir(&dll_dir).into_iter().filter_map(|e| e.ok()) {
             if entry.path().extension().map(|e| e == "dll").unwrap_or(false) {
                 found_dlls = true;
                 let types = extract_types_from_dll(entry.path(), &entry.path().display().to_string());
@@ -0,0 +1,342 @@
+//! Fast syntax sanity check for C# scripts.
+//!
+//! Not a parser: one lexical pass that skips comments, strings, chars and preprocessor lines,
+//! then checks delimiter balance and attribute shape. Catches the mistakes that stop Unity from
+//! parsing a script at all, before an editor round-trip.
+
+use napi_derive::napi;
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::common;
+
+// Attribute section body: optional target, then comma-separated `Name` or `Name(args)`
+static ATTRIBUTE_BODY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)^\s*(?:(?:assembly|module|field|event|method|param|property|return|type)\s*:\s*)?@?[A-Za-z_][\w.]*(?:<[^>]*>)?\s*(?:\(.*\))?\s*(?:,\s*@?[A-Za-z_][\w.]*(?:<[^>]*>)?\s*(?:\(.*\))?\s*)*,?\s*$").unwrap()
+});
+
+/// One syntax problem in a C# script.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct CSharpLintIssue {
+    /// 1-based line
+    pub line: u32,
+    /// 1-based column (characters)
+    pub column: u32,
+    /// "unbalanced_delimiter", "unclosed_delimiter", "unterminated_string", "unterminated_char",
+    /// "unterminated_comment" or "malformed_attribute"
+    pub kind: String,
+    pub message: String,
+}
+
+/// Syntax sanity result of a C# script.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct CSharpLintReport {
+    pub path: String,
+    /// No issues found
+    pub ok: bool,
+    pub issues: Vec<CSharpLintIssue>,
+}
+
+/// Check a .cs file for unbalanced braces/brackets/parentheses, unterminated strings, chars
+/// and block comments, and malformed attributes. Returns None if the file cannot be read.
+#[napi]
+pub fn lint_csharp(path: String) -> Option<CSharpLintReport> {
+    let _call = crate::telemetry::call("lint_csharp");
+    let content = common::read_unity_file(Path::new(&path)).ok()?;
+    let issues = lint_source(&content);
+    Some(CSharpLintReport { path, ok: issues.is_empty(), issues })
+}
+
+/// An open delimiter: (byte, offset, starts an attribute section)
+type Open = (u8, usize, bool);
+
+fn closer_for(open: u8) -> u8 {
+    match open {
+        b'(' => b')',
+        b'[' => b']',
+        _ => b'}',
+    }
+}
+
+struct Lint<'a> {
+    content: &'a str,
+    line_starts: Vec<usize>,
+    issues: Vec<CSharpLintIssue>,
+}
+
+impl Lint<'_> {
+    fn report(&mut self, offset: usize, kind: &str, message: String) {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let column = self.content[self.line_starts[line]..offset].chars().count() + 1;
+        self.issues.push(CSharpLintIssue {
+            line: line as u32 + 1,
+            column: column as u32,
+            kind: kind.to_string(),
+            message,
+        });
+    }
+
+    fn line_of(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset)
+    }
+}
+
+/// Skip a regular or verbatim string starting at its opening quote; returns the offset past the
+/// closing quote, or None if it is unterminated (a newline ends a regular string).
+fn skip_string(bytes: &[u8], quote: usize, verbatim: bool) -> Option<usize> {
+    let mut i = quote + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' if verbatim && bytes.get(i + 1) == Some(&b'"') => i += 2,
+            b'"' => return Some(i + 1),
+            b'\\' if !verbatim => i += 2,
+            b'\n' if !verbatim => return None,
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Skip an interpolated string, including nested strings inside `{...}` holes.
+fn skip_interpolated(bytes: &[u8], quote: usize, verbatim: bool) -> Option<usize> {
+    let mut i = quote + 1;
+    let mut hole_depth = 0usize;
+    while i < bytes.len() {
+        let ch = bytes[i];
+        if hole_depth > 0 {
+            match ch {
+                b'{' => hole_depth += 1,
+                b'}' => hole_depth -= 1,
+                b'"' => {
+                    i = skip_string(bytes, i, false)?;
+                    continue;
+                }
+                b'\n' if !verbatim => return None,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+        match ch {
+            b'{' | b'}' if bytes.get(i + 1) == Some(&ch) => i += 2,
+            b'{' => {
+                hole_depth = 1;
+                i += 1;
+            }
+            b'"' if verbatim && bytes.get(i + 1) == Some(&b'"') => i += 2,
+            b'"' => return Some(i + 1),
+            b'\\' if !verbatim => i += 2,
+            b'\n' if !verbatim => return None,
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Every syntax issue of C# source, in source order of detection.
+pub(crate) fn lint_source(content: &str) -> Vec<CSharpLintIssue> {
+    let bytes = content.as_bytes();
+    let mut lint = Lint {
+        content,
+        line_starts: std::iter::once(0).chain(content.match_indices('\n').map(|(i, _)| i + 1)).collect(),
+        issues: Vec::new(),
+    };
+    let mut stack: Vec<Open> = Vec::new();
+    // Last significant byte outside comments/strings, and whether a `]` closed an attribute
+    let mut last_significant: Option<u8> = None;
+    let mut after_attribute = false;
+    let mut at_line_start = true;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let ch = bytes[i];
+        if ch == b'\n' {
+            at_line_start = true;
+            i += 1;
+            continue;
+        }
+        if ch.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        let line_start = std::mem::replace(&mut at_line_start, false);
+
+        // Preprocessor directives run to the end of the line
+        if ch == b'#' && line_start {
+            i = content[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            continue;
+        }
+        if ch == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            i = content[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            continue;
+        }
+        if ch == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            match content[i + 2..].find("*/") {
+                Some(n) => i += n + 4,
+                None => {
+                    lint.report(i, "unterminated_comment", "Block comment is never closed".to_string());
+                    i = bytes.len();
+                }
+            }
+            continue;
+        }
+
+        // String and char literals, with their @ / $ prefixes
+        let prefix_len = bytes[i..].iter().take(2).take_while(|b| matches!(b, b'@' | b'$')).count();
+        if bytes.get(i + prefix_len) == Some(&b'"') {
+            let prefix = &bytes[i..i + prefix_len];
+            let verbatim = prefix.contains(&b'@');
+            let quote = i + prefix_len;
+            let end = if prefix.contains(&b'$') { skip_interpolated(bytes, quote, verbatim) } else { skip_string(bytes, quote, verbatim) };
+            match end {
+                Some(end) => i = end,
+                None => {
+                    lint.report(i, "unterminated_string", "String literal is never closed".to_string());
+                    // Resume on the next line so one bad string does not hide later issues
+                    i = if verbatim { bytes.len() } else { content[i..].find('\n').map_or(bytes.len(), |n| i + n) };
+                }
+            }
+            last_significant = Some(b'"');
+            after_attribute = false;
+            continue;
+        }
+        if ch == b'\'' {
+            let close = bytes[i + 1..].iter()
+                .enumerate()
+                .skip(usize::from(bytes.get(i + 1) == Some(&b'\\')) + 1)
+                .take_while(|(_, b)| **b != b'\n')
+                .find(|(_, b)| **b == b'\'')
+                .map(|(n, _)| i + 1 + n);
+            match close {
+                Some(close) => i = close + 1,
+                None => {
+                    lint.report(i, "unterminated_char", "Character literal is never closed".to_string());
+                    i = content[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                }
+            }
+            last_significant = Some(b'\'');
+            after_attribute = false;
+            continue;
+        }
+
+        match ch {
+            b'(' | b'{' => stack.push((ch, i, false)),
+            b'[' => {
+                // Attribute sections start declarations: after `;`, `{`, `}`, another attribute, or at file start
+                let is_attribute = after_attribute || matches!(last_significant, None | Some(b';' | b'{' | b'}'));
+                stack.push((ch, i, is_attribute));
+            }
+            b')' | b']' | b'}' => {
+                after_attribute = false;
+                match stack.iter().rposition(|(open, _, _)| closer_for(*open) == ch) {
+                    Some(pos) if pos + 1 == stack.len() => {
+                        let (_, start, is_attribute) = stack.pop().unwrap_or_default();
+                        let next = content[i + 1..].trim_start().bytes().next();
+                        // `[key] = value` index initializers share the attribute position
+                        if is_attribute && next != Some(b'=') {
+                            if ATTRIBUTE_BODY_RE.is_match(&content[start + 1..i]) {
+                                after_attribute = true;
+                            } else {
+                                lint.report(start, "malformed_attribute", format!("Malformed attribute [{}]", content[start + 1..i].trim()));
+                            }
+                        }
+                    }
+                    Some(pos) => {
+                        // Everything opened after the matching delimiter was left unclosed
+                        for (open, start, _) in stack.split_off(pos + 1) {
+                            let message = format!(
+                                "'{}' opened on line {} is closed by '{}' on line {}",
+                                open as char, lint.line_of(start), ch as char, lint.line_of(i)
+                            );
+                            lint.report(start, "unbalanced_delimiter", message);
+                        }
+                        stack.pop();
+                    }
+                    None => lint.report(i, "unbalanced_delimiter", format!("Unexpected '{}' with nothing to close", ch as char)),
+                }
+            }
+            b';' => {
+                if let Some(pos) = stack.iter().rposition(|(open, _, is_attribute)| *open == b'[' && *is_attribute) {
+                    if stack[pos + 1..].iter().all(|(open, _, _)| *open != b'{') {
+                        let (_, start, _) = stack.remove(pos);
+                        lint.report(start, "malformed_attribute", "Attribute is missing its closing ']'".to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+        if ch != b']' {
+            after_attribute = false;
+        }
+        last_significant = Some(ch);
+        i += 1;
+    }
+
+    for (open, start, _) in stack {
+        lint.report(start, "unclosed_delimiter", format!("'{}' is never closed", open as char));
+    }
+    lint.issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<(String, u32)> {
+        lint_source(source).into_iter().map(|i| (i.kind, i.line)).collect()
+    }
+
+    #[test]
+    fn test_clean_script() {
+        let source = r#"using UnityEngine;
+#if UNITY_EDITOR
+using UnityEditor;
+#endif
+
+[RequireComponent(typeof(Rigidbody)), DisallowMultipleComponent]
+public class Player : MonoBehaviour
+{
+    [SerializeField, Range(0, 10)] private float speed = 2f; // tricky ) comment
+    [Header("Look [settings]")] public string label = "a \"quoted\" } brace";
+    /* block { comment */
+    private char open = '{';
+    private char quote = '\'';
+    private string path = @"C:\dir\""x""";
+    private int[] grid = new int[3];
+    private Dictionary<int, string> names = new() { [0] = "zero" };
+
+    void Update()
+    {
+        var text = $"Speed {speed:F2} {{literal}} {(speed > 1 ? "fast" : "slow")}";
+        grid[0] = grid[1];
+    }
+}
+"#;
+        assert!(lint_source(source).is_empty(), "{:?}", lint_source(source));
+    }
+
+    #[test]
+    fn test_unbalanced_delimiters() {
+        assert_eq!(kinds("class A {\n  void F() {\n    Call(1;\n  }\n}\n"), vec![("unbalanced_delimiter".to_string(), 3)]);
+        assert_eq!(kinds("class A {\n  void F() { }\n"), vec![("unclosed_delimiter".to_string(), 1)]);
+        assert_eq!(kinds("class A { }\n}\n"), vec![("unbalanced_delimiter".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_unterminated_literals() {
+        let issues = kinds("class A {\n  string s = \"open;\n  char c = 'x;\n}\n");
+        assert_eq!(issues, vec![("unterminated_string".to_string(), 2), ("unterminated_char".to_string(), 3)]);
+        assert_eq!(kinds("class A { } /* never closed\n"), vec![("unterminated_comment".to_string(), 1)]);
+    }
+
+    #[test]
+    fn test_malformed_attributes() {
+        let issues = lint_source("class A {\n  [SerializeField private float x;\n  [] public int y;\n  [42] public int z;\n}\n");
+        let found: Vec<(&str, u32)> = issues.iter().map(|i| (i.kind.as_str(), i.line)).collect();
+        assert_eq!(found, vec![("malformed_attribute", 2), ("malformed_attribute", 3), ("malformed_attribute", 4)]);
+        assert_eq!((issues[0].column, issues[2].message.as_str()), (3, "Malformed attribute [42]"));
+    }
+}
@@ -0,0 +1,294 @@
+//! Size and complexity metrics of a project's C# scripts, per script and per assembly.
+//!
+//! Lexical like the rest of this module: complexity is a rough cyclomatic count (one per
+//! method plus one per `if`, `case`, loop, `catch`, `&&`, `||` and `??`), not a control-flow
+//! analysis. Good enough to rank hotspots.
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use crate::common;
+use super::deps::{assembly_for, code_only, collect_assemblies, relative};
+use super::summary::{is_modifier_or_statement, METHOD_DECL_RE};
+use super::{collect_cs_files, count_braces_simple, extract_fields_from_source, is_keyword, strip_attributes};
+
+static DECISION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:if|case|for|foreach|while|catch)\b|&&|\|\||\?\?").unwrap()
+});
+
+/// Methods listed in `ScriptMetricsReport::hotspots`.
+const HOTSPOT_COUNT: usize = 10;
+
+/// Metrics of one script.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptMetrics {
+    /// Script path relative to the project root
+    pub path: String,
+    pub assembly: String,
+    pub lines: u32,
+    /// Lines with code (not blank, not comment-only)
+    pub code_lines: u32,
+    pub comment_lines: u32,
+    pub type_count: u32,
+    pub method_count: u32,
+    pub serialized_field_count: u32,
+    /// Rough cyclomatic complexity of the whole script
+    pub complexity: u32,
+    pub max_method_complexity: u32,
+}
+
+/// Script metrics summed over one assembly.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssemblyMetrics {
+    pub name: String,
+    pub script_count: u32,
+    pub lines: u32,
+    pub code_lines: u32,
+    pub comment_lines: u32,
+    pub type_count: u32,
+    pub method_count: u32,
+    pub serialized_field_count: u32,
+    pub complexity: u32,
+    pub max_method_complexity: u32,
+}
+
+/// Complexity of one method.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodComplexity {
+    pub path: String,
+    pub method: String,
+    /// 1-based line of the declaration
+    pub line: u32,
+    pub complexity: u32,
+}
+
+/// Metrics of every script under Assets/.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptMetricsReport {
+    pub project_root: String,
+    pub scripts: Vec<ScriptMetrics>,
+    /// Sorted by name
+    pub assemblies: Vec<AssemblyMetrics>,
+    /// The most complex methods of the project, most complex first
+    pub hotspots: Vec<MethodComplexity>,
+}
+
+/// Lines of code, type, method and serialized field counts, and rough cyclomatic complexity
+/// of every script under Assets/, summed per assembly (asmdef or predefined Assembly-CSharp*),
+/// with the project's most complex methods as hotspots.
+#[napi]
+pub fn script_metrics(project_root: String) -> ScriptMetricsReport {
+    let _call = crate::telemetry::call("script_metrics");
+    let root = PathBuf::from(&project_root);
+    let mut files: Vec<PathBuf> = Vec::new();
+    let assets = root.join("Assets");
+    if assets.is_dir() {
+        let filter = crate::walker::DirFilter::for_project(&root, &["bin".to_string()]);
+        collect_cs_files(&assets, &filter, &mut files);
+    }
+    files.sort();
+
+    let (_, assembly_dirs) = collect_assemblies(&root);
+    let measured: Vec<(ScriptMetrics, Vec<MethodComplexity>)> = files.par_iter()
+        .filter_map(|file| {
+            let content = common::read_unity_file(file).ok()?;
+            let path = relative(&root, file);
+            let assembly = assembly_for(&path, &assembly_dirs);
+            Some(measure(&path, assembly, &content))
+        })
+        .collect();
+
+    let mut assemblies: BTreeMap<String, AssemblyMetrics> = BTreeMap::new();
+    let mut hotspots = Vec::new();
+    let mut scripts = Vec::with_capacity(measured.len());
+    for (script, methods) in measured {
+        let total = assemblies.entry(script.assembly.clone()).or_insert_with(|| AssemblyMetrics {
+            name: script.assembly.clone(),
+            script_count: 0,
+            lines: 0,
+            code_lines: 0,
+            comment_lines: 0,
+            type_count: 0,
+            method_count: 0,
+            serialized_field_count: 0,
+            complexity: 0,
+            max_method_complexity: 0,
+        });
+        total.script_count += 1;
+        total.lines += script.lines;
+        total.code_lines += script.code_lines;
+        total.comment_lines += script.comment_lines;
+        total.type_count += script.type_count;
+        total.method_count += script.method_count;
+        total.serialized_field_count += script.serialized_field_count;
+        total.complexity += script.complexity;
+        total.max_method_complexity = total.max_method_complexity.max(script.max_method_complexity);
+        hotspots.extend(methods);
+        scripts.push(script);
+    }
+    hotspots.sort_by(|a, b| b.complexity.cmp(&a.complexity).then_with(|| a.path.cmp(&b.path)).then(a.line.cmp(&b.line)));
+    hotspots.truncate(HOTSPOT_COUNT);
+
+    ScriptMetricsReport {
+        project_root,
+        scripts,
+        assemblies: assemblies.into_values().collect(),
+        hotspots,
+    }
+}
+
+/// Metrics of one script plus the complexity of each of its methods.
+fn measure(path: &str, assembly: String, content: &str) -> (ScriptMetrics, Vec<MethodComplexity>) {
+    let code = code_only(content);
+    let mut code_lines = 0;
+    let mut comment_lines = 0;
+    for (source, code) in content.lines().zip(code.lines()) {
+        if !code.trim().is_empty() {
+            code_lines += 1;
+        } else if !source.trim().is_empty() {
+            comment_lines += 1;
+        }
+    }
+
+    let methods = method_complexities(path, &code);
+    let decisions: u32 = code.lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .map(|line| DECISION_RE.find_iter(line).count() as u32)
+        .sum();
+    let types = extract_fields_from_source(content);
+
+    let metrics = ScriptMetrics {
+        path: path.to_string(),
+        assembly,
+        lines: content.lines().count() as u32,
+        code_lines,
+        comment_lines,
+        type_count: types.len() as u32,
+        method_count: methods.len() as u32,
+        serialized_field_count: types.iter().map(|t| t.fields.len() as u32).sum(),
+        complexity: methods.len() as u32 + decisions,
+        max_method_complexity: methods.iter().map(|m| m.complexity).max().unwrap_or(0),
+    };
+    (metrics, methods)
+}
+
+/// Complexity of each method declared in comment- and string-free source. Local functions and
+/// lambdas count towards their enclosing method.
+fn method_complexities(path: &str, code: &str) -> Vec<MethodComplexity> {
+    let mut methods = Vec::new();
+    // (method, brace depth at the declaration, body entered)
+    let mut current: Option<(MethodComplexity, i32, bool)> = None;
+    let mut depth: i32 = 0;
+
+    for (i, line) in code.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if current.is_none() {
+            let stripped = strip_attributes(trimmed);
+            let declared = METHOD_DECL_RE.captures(&stripped)
+                .filter(|c| !is_modifier_or_statement(&c[2]) && !is_keyword(&c[3]))
+                .map(|c| c[3].to_string());
+            if let Some(method) = declared {
+                let method = MethodComplexity { path: path.to_string(), method, line: i as u32 + 1, complexity: 1 };
+                current = Some((method, depth, false));
+            }
+        }
+
+        depth += count_braces_simple(trimmed);
+        if let Some((method, start_depth, entered)) = current.as_mut() {
+            method.complexity += DECISION_RE.find_iter(trimmed).count() as u32;
+            *entered |= depth > *start_depth || trimmed.contains('{');
+            // Body closed, or a bodiless / expression-bodied declaration ended
+            if (*entered && depth <= *start_depth) || (!*entered && trimmed.ends_with(';')) {
+                methods.push(current.take().unwrap().0);
+            }
+        }
+    }
+    methods
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const PLAYER: &str = r#"using UnityEngine;
+
+/* Player movement
+   and input */
+public class Player : MonoBehaviour
+{
+    [SerializeField] private float speed = 2f;
+    public int lives;
+    private bool grounded;
+
+    // Per-frame input
+    void Update()
+    {
+        if (Input.GetKey(KeyCode.Space) && grounded)
+        {
+            Jump();
+        }
+        for (int i = 0; i < 3; i++) { Debug.Log("if while for"); }
+    }
+
+    void Jump() => grounded = false;
+
+    public abstract class State
+    {
+        public abstract void Enter();
+    }
+}
+"#;
+
+    #[test]
+    fn test_measure_script() {
+        let (metrics, methods) = measure("Assets/Player.cs", "Assembly-CSharp".to_string(), PLAYER);
+        assert_eq!(metrics.lines, 27);
+        assert_eq!((metrics.code_lines, metrics.comment_lines), (20, 3));
+        assert_eq!((metrics.type_count, metrics.method_count, metrics.serialized_field_count), (2, 3, 2));
+        let summary: Vec<(&str, u32, u32)> = methods.iter().map(|m| (m.method.as_str(), m.line, m.complexity)).collect();
+        assert_eq!(summary, vec![("Update", 12, 4), ("Jump", 21, 1), ("Enter", 25, 1)]);
+        assert_eq!(metrics.complexity, 6);
+        assert_eq!(metrics.max_method_complexity, 4);
+    }
+
+    #[test]
+    fn test_script_metrics_per_assembly() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (rel, content) in [
+            ("Assets/Scripts/Player.cs", PLAYER),
+            ("Assets/Scripts/Editor/PlayerEditor.cs", "public class PlayerEditor\n{\n    void OnInspectorGUI() { }\n}\n"),
+            ("Assets/Game/Game.asmdef", r#"{"name": "Game"}"#),
+            ("Assets/Game/Score.cs", "public class Score\n{\n    public int Add(int a, int b) { return a > 0 || b > 0 ? a + b : 0; }\n}\n"),
+        ] {
+            let path = root.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let report = script_metrics(root.to_string_lossy().to_string());
+        let scripts: Vec<(&str, &str)> = report.scripts.iter().map(|s| (s.path.as_str(), s.assembly.as_str())).collect();
+        assert_eq!(scripts, vec![
+            ("Assets/Game/Score.cs", "Game"),
+            ("Assets/Scripts/Editor/PlayerEditor.cs", "Assembly-CSharp-Editor"),
+            ("Assets/Scripts/Player.cs", "Assembly-CSharp"),
+        ]);
+        let assemblies: Vec<(&str, u32, u32)> = report.assemblies.iter().map(|a| (a.name.as_str(), a.script_count, a.complexity)).collect();
+        assert_eq!(assemblies, vec![("Assembly-CSharp", 1, 6), ("Assembly-CSharp-Editor", 1, 1), ("Game", 1, 2)]);
+        assert_eq!(report.hotspots.len(), 5);
+        assert_eq!((report.hotspots[0].method.as_str(), report.hotspots[0].complexity), ("Update", 4));
+        assert_eq!((report.hotspots[1].method.as_str(), report.hotspots[1].path.as_str()), ("Add", "Assets/Game/Score.cs"));
+    }
+}
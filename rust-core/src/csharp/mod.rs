@@ -1,13 +1,20 @@
+pub mod deps;
 pub mod dll_reader;
+pub mod lint;
+pub mod metrics;
+pub mod summary;
+pub mod symbols;
+pub mod upgrade;
 
 use napi_derive::napi;
 use rayon::prelude::*;
 use regex::Regex;
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
-use walkdir::WalkDir;
 
-use crate::common;
+use crate::common::{self, ResourceBudget};
+use crate::common::budget::Meter;
+use crate::walker::DirFilter;
 
 /// A serializable field extracted from a C# type.
 #[napi(object)]
@@ -65,9 +72,21 @@ pub struct CSharpTypeRef {
 /// with their namespace context and the GUID from the adjacent .meta file.
 #[napi]
 pub fn extract_csharp_types(path: String) -> Vec<CSharpTypeRef> {
+    let _call = crate::telemetry::call("extract_csharp_types");
     extract_types_from_file(Path::new(&path), None)
 }
 
+/// Type registry of a budgeted scan (see `build_type_registry_with_budget`).
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct TypeRegistryResult {
+    pub types: Vec<CSharpTypeRef>,
+    /// Source files and DLLs read
+    pub files_scanned: u32,
+    /// Files were left unread because the budget ran out
+    pub budget_exhausted: bool,
+}
+
 /// Build a type registry by scanning all .cs files in a Unity project.
 ///
 /// Scans Assets/ and optionally Library/PackageCache/ for .cs files,
@@ -80,34 +99,51 @@ pub fn build_type_registry(
     include_packages: Option<bool>,
     include_dlls: Option<bool>,
 ) -> Vec<CSharpTypeRef> {
+    let _call = crate::telemetry::call("build_type_registry");
+    build_type_registry_with_budget(project_root, include_packages, include_dlls, None).types
+}
+
+/// `build_type_registry` stopping at a resource budget (files, bytes, wall time), returning
+/// the types found so far with `budget_exhausted` set.
+#[napi]
+pub fn build_type_registry_with_budget(
+    project_root: String,
+    include_packages: Option<bool>,
+    include_dlls: Option<bool>,
+    budget: Option<ResourceBudget>,
+) -> TypeRegistryResult {
+    let _call = crate::telemetry::call("build_type_registry_with_budget");
     let root = PathBuf::from(&project_root);
     let include_packages = include_packages.unwrap_or(false);
     let include_dlls = include_dlls.unwrap_or(false);
+    let meter = Meter::new(budget.as_ref());
 
     let mut cs_files: Vec<PathBuf> = Vec::new();
+    let filter = DirFilter::for_project(&root, &["bin".to_string()]);
 
     // Scan Assets/ for .cs files
     let assets_dir = root.join("Assets");
     if assets_dir.is_dir() {
-        collect_cs_files(&assets_dir, &mut cs_files);
+        collect_cs_files(&assets_dir, &filter, &mut cs_files);
     }
 
     // Optionally scan Library/PackageCache/ and Packages/ for .cs files
     if include_packages {
         let package_cache = root.join("Library").join("PackageCache");
         if package_cache.is_dir() {
-            collect_cs_files(&package_cache, &mut cs_files);
+            collect_cs_files(&package_cache, &filter, &mut cs_files);
         }
 
         let packages_dir = root.join("Packages");
         if packages_dir.is_dir() {
-            collect_cs_files(&packages_dir, &mut cs_files);
+            collect_cs_files(&packages_dir, &filter, &mut cs_files);
         }
     }
 
     // Parallel extraction from .cs files
     let mut types: Vec<CSharpTypeRef> = cs_files
         .par_iter()
+        .filter(|file| meter.admit_file(file))
         .flat_map(|file| extract_types_from_file(file, Some(&root)))
         .collect();
 
@@ -120,6 +156,7 @@ pub fn build_type_registry(
 
             let dll_types: Vec<CSharpTypeRef> = dll_files
                 .par_iter()
+                .filter(|file| meter.admit_file(file))
                 .flat_map(|file| {
                     let rel = file
                         .strip_prefix(&root)
@@ -134,22 +171,18 @@ pub fn build_type_registry(
         }
     }
 
-    types
+    TypeRegistryResult {
+        types,
+        files_scanned: meter.files_admitted(),
+        budget_exhausted: meter.exhausted(),
+    }
 }
 
-/// Collect all .cs files under a directory.
-fn collect_cs_files(dir: &Path, result: &mut Vec<PathBuf>) {
-    for entry in WalkDir::new(dir)
+/// Collect all .cs files under a directory, skipping directories the project's walk filter excludes.
+fn collect_cs_files(dir: &Path, filter: &DirFilter, result: &mut Vec<PathBuf>) {
+    for entry in crate::safety::walk_dir(dir)
         .into_iter()
-        .filter_entry(|e| {
-            // Skip common noise directories
-            if e.file_type().is_dir() {
-                if let Some(name) = e.file_name().to_str() {
-                    return !matches!(name, ".git" | "node_modules" | "obj" | "bin");
-                }
-            }
-            true
-        })
+        .filter_entry(|e| filter.keeps(e))
         .filter_map(|e| e.ok())
     {
         if entry.file_type().is_file() {
@@ -164,7 +197,7 @@ fn collect_cs_files(dir: &Path, result: &mut Vec<PathBuf>) {
 
 /// Collect all .dll files under a directory.
 fn collect_dll_files(dir: &Path, result: &mut Vec<PathBuf>) {
-    for entry in WalkDir::new(dir)
+    for entry in crate::safety::walk_dir(dir)
         .into_iter()
         .filter_map(|e| e.ok())
     {
@@ -552,7 +585,7 @@ fn strip_string_literals(content: &str) -> String {
 }
 
 /// Strip block comments (/* ... */) from C# source to avoid false matches.
-fn strip_block_comments(content: &str) -> String {
+pub(crate) fn strip_block_comments(content: &str) -> String {
     let mut result = String::with_capacity(content.len());
     let bytes = content.as_bytes();
     let len = bytes.len();
@@ -587,6 +620,7 @@ fn strip_block_comments(content: &str) -> String {
 /// This is called on-demand during component creation, not during registry builds.
 #[napi]
 pub fn extract_serialized_fields(path: String) -> Vec<CSharpTypeInfo> {
+    let _call = crate::telemetry::call("extract_serialized_fields");
     let file = Path::new(&path);
     let content = match common::read_unity_file(file) {
         Ok(c) => c,
@@ -1175,6 +1209,12 @@ namespace Game {
 
         let bar = types.iter().find(|t| t.name == "Bar").unwrap();
         assert_eq!(bar.namespace.as_deref(), Some("MyGame"));
+
+        let budget = ResourceBudget { max_files: Some(1), ..Default::default() };
+        let partial = build_type_registry_with_budget(tmp.path().to_string_lossy().to_string(), None, None, Some(budget));
+        assert_eq!((partial.types.len(), partial.files_scanned, partial.budget_exhausted), (1, 1, true));
+        let full = build_type_registry_with_budget(tmp.path().to_string_lossy().to_string(), None, None, None);
+        assert_eq!((full.types.len(), full.files_scanned, full.budget_exhausted), (2, 2, false));
     }
 
     // ===== External fixtures tests =====
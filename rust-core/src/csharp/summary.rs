@@ -0,0 +1,422 @@
+//! Compact structured summary of a C# script for agent context.
+//!
+//! Reuses the field extractor for types and serialized fields, then makes one more
+//! pass for method signatures, implemented Unity messages and Unity API usage.
+
+use napi_derive::napi;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::common;
+use super::{
+    count_braces_simple, extract_fields_from_source, is_keyword, strip_attributes,
+    strip_block_comments, strip_string_literals, CSharpFieldRef, TYPE_DECL_WITH_BASE_RE,
+};
+
+// Method declaration: (1) modifiers, (2) return type, (3) name, (4) generic params, (5) parameters
+pub(super) static METHOD_DECL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^((?:(?:public|private|protected|internal|static|virtual|override|abstract|sealed|async|new|extern|unsafe|partial)\s+)*)([\w.]+(?:<[^()]*>)?(?:\[\s*\])*\??)\s+(\w+)\s*(<[^()]*>)?\s*\(([^)]*)\)?",
+    )
+    .unwrap()
+});
+static BASE_LIST_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(class|struct|interface)\s+\w+(?:<[^>]*>)?\s*:\s*([^{]+?)\s*(?:\bwhere\b|\{|$)").unwrap()
+});
+static GENERIC_API_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b(GetComponents?(?:InChildren|InParent)?|TryGetComponent|AddComponent|RequireComponent|FindObjectsOfType|FindObjectOfType|FindFirstObjectByType|FindAnyObjectByType|FindObjectsByType)\s*<\s*([\w.]+)\s*>",
+    )
+    .unwrap()
+});
+static TYPEOF_API_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b(GetComponents?(?:InChildren|InParent)?|TryGetComponent|AddComponent|RequireComponent|FindObjectsOfType|FindObjectOfType)\s*\(\s*typeof\s*\(\s*([\w.]+)\s*\)",
+    )
+    .unwrap()
+});
+static INSTANTIATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bInstantiate\s*(?:<\s*[\w.]+\s*>)?\s*\(\s*([\w.\[\]]+)").unwrap()
+});
+pub(super) static USING_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\s*using\s+(?:static\s+)?([\w.]+)\s*;").unwrap()
+});
+
+// Component lookups on the script's own GameObject (bare, this., gameObject. or transform. receivers)
+static SELF_COMPONENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[^\w.]|\b(?:this|gameObject|transform)\.)(GetComponents?|TryGetComponent|AddComponent)\s*<\s*([\w.]+)\s*>").unwrap()
+});
+static REQUIRE_COMPONENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[\s*RequireComponent\s*\(([^\]]*)\)\s*\]").unwrap()
+});
+static TYPEOF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"typeof\s*\(\s*([\w.]+)\s*\)").unwrap()
+});
+
+/// MonoBehaviour/ScriptableObject/Editor callbacks Unity invokes by name.
+const UNITY_MESSAGES: &[&str] = &[
+    "Awake", "Start", "Update", "FixedUpdate", "LateUpdate", "OnEnable", "OnDisable", "OnDestroy",
+    "OnValidate", "Reset", "OnGUI", "OnApplicationQuit", "OnApplicationPause", "OnApplicationFocus",
+    "OnBecameVisible", "OnBecameInvisible", "OnDrawGizmos", "OnDrawGizmosSelected",
+    "OnTriggerEnter", "OnTriggerExit", "OnTriggerStay", "OnCollisionEnter", "OnCollisionExit", "OnCollisionStay",
+    "OnTriggerEnter2D", "OnTriggerExit2D", "OnTriggerStay2D", "OnCollisionEnter2D", "OnCollisionExit2D", "OnCollisionStay2D",
+    "OnControllerColliderHit", "OnMouseDown", "OnMouseUp", "OnMouseEnter", "OnMouseExit", "OnMouseOver", "OnMouseDrag",
+    "OnAnimatorMove", "OnAnimatorIK", "OnParticleCollision", "OnParticleTrigger", "OnTransformParentChanged",
+    "OnTransformChildrenChanged", "OnRectTransformDimensionsChange", "OnRenderObject", "OnWillRenderObject",
+    "OnPreCull", "OnPreRender", "OnPostRender", "OnRenderImage", "OnJointBreak", "OnJointBreak2D",
+    "OnBeforeTransformParentChanged", "OnCanvasGroupChanged", "OnServerInitialized", "OnInspectorGUI", "OnSceneGUI",
+];
+
+/// A method declared directly in a type body.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct CSharpMethodInfo {
+    pub name: String,
+    pub return_type: String,
+    /// Parameter list as written (e.g. "Vector3 target, float speed = 1f")
+    pub parameters: String,
+    /// Compact one-line signature (e.g. "public void MoveTo(Vector3 target)")
+    pub signature: String,
+    pub is_public: bool,
+    pub is_static: bool,
+}
+
+/// Per-type part of a script summary.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct CSharpTypeSummary {
+    pub name: String,
+    /// "class", "struct", "enum", or "interface"
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub base_class: Option<String>,
+    /// Implemented interfaces (remaining entries of the base list)
+    pub interfaces: Vec<String>,
+    pub serialized_fields: Vec<CSharpFieldRef>,
+    pub public_methods: Vec<CSharpMethodInfo>,
+    /// Unity callbacks implemented by this type (any access level)
+    pub unity_messages: Vec<String>,
+}
+
+/// Usage of a type-parameterised Unity API (e.g. GetComponent<Rigidbody>).
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct CSharpApiUsage {
+    /// API name, e.g. "GetComponent", "AddComponent", "RequireComponent"
+    pub api: String,
+    pub type_name: String,
+    pub count: u32,
+}
+
+/// Compact structured summary of a C# script.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct CSharpScriptSummary {
+    pub path: String,
+    pub line_count: u32,
+    pub usings: Vec<String>,
+    pub types: Vec<CSharpTypeSummary>,
+    pub api_usages: Vec<CSharpApiUsage>,
+    /// First argument of each Instantiate call (field, local or expression head), deduplicated
+    pub instantiate_targets: Vec<String>,
+}
+
+/// Summarize a C# script: types, base classes, serialized fields, public methods,
+/// implemented Unity messages and Unity API usage. Returns None if the file cannot be read.
+#[napi]
+pub fn summarize_script(path: String) -> Option<CSharpScriptSummary> {
+    let _call = crate::telemetry::call("summarize_script");
+    let content = common::read_unity_file(Path::new(&path)).ok()?;
+    Some(summarize_source(&path, &content))
+}
+
+/// Internal: build the summary from source text.
+pub(crate) fn summarize_source(path: &str, content: &str) -> CSharpScriptSummary {
+    let cleaned = strip_string_literals(&strip_block_comments(content));
+    let (methods, interfaces) = collect_methods(&cleaned);
+
+    let types = extract_fields_from_source(content)
+        .into_iter()
+        .map(|t| {
+            let declared = methods.get(&t.name).cloned().unwrap_or_default();
+            let unity_messages: Vec<String> = declared.iter()
+                .filter(|m| UNITY_MESSAGES.contains(&m.name.as_str()))
+                .map(|m| m.name.clone())
+                .collect();
+            let is_interface = t.kind == "interface";
+            CSharpTypeSummary {
+                interfaces: interfaces.get(&t.name).cloned().unwrap_or_default(),
+                public_methods: declared.into_iter().filter(|m| m.is_public || is_interface).collect(),
+                unity_messages,
+                serialized_fields: t.fields,
+                name: t.name,
+                kind: t.kind,
+                namespace: t.namespace,
+                base_class: t.base_class,
+            }
+        })
+        .collect();
+
+    let mut usages: BTreeMap<(String, String), u32> = BTreeMap::new();
+    for cap in GENERIC_API_RE.captures_iter(&cleaned).chain(TYPEOF_API_RE.captures_iter(&cleaned)) {
+        *usages.entry((cap[1].to_string(), cap[2].to_string())).or_default() += 1;
+    }
+    let mut instantiate_targets: Vec<String> = Vec::new();
+    for cap in INSTANTIATE_RE.captures_iter(&cleaned) {
+        if !instantiate_targets.iter().any(|t| t == &cap[1]) {
+            instantiate_targets.push(cap[1].to_string());
+        }
+    }
+
+    CSharpScriptSummary {
+        path: path.to_string(),
+        line_count: content.lines().count() as u32,
+        usings: USING_RE.captures_iter(&cleaned).map(|c| c[1].to_string()).collect(),
+        types,
+        api_usages: usages.into_iter()
+            .map(|((api, type_name), count)| CSharpApiUsage { api, type_name, count })
+            .collect(),
+        instantiate_targets,
+    }
+}
+
+/// Component types a script expects on its own GameObject, from [RequireComponent] and
+/// self-directed GetComponent/TryGetComponent calls, and the types it adds itself.
+/// Returns (expectations as (type, source), added types); each type appears once.
+pub fn component_expectations(content: &str) -> (Vec<(String, String)>, Vec<String>) {
+    let cleaned = strip_string_literals(&strip_block_comments(content));
+    let mut expects: Vec<(String, String)> = Vec::new();
+    let mut adds: Vec<String> = Vec::new();
+
+    for attr in REQUIRE_COMPONENT_RE.captures_iter(&cleaned) {
+        for ty in TYPEOF_RE.captures_iter(&attr[1]) {
+            if !expects.iter().any(|(t, _)| t == &ty[1]) {
+                expects.push((ty[1].to_string(), "RequireComponent".to_string()));
+            }
+        }
+    }
+    for cap in SELF_COMPONENT_RE.captures_iter(&cleaned) {
+        let ty = cap[2].to_string();
+        if &cap[1] == "AddComponent" {
+            if !adds.contains(&ty) {
+                adds.push(ty);
+            }
+        } else if !expects.iter().any(|(t, _)| t == &ty) {
+            expects.push((ty, cap[1].to_string()));
+        }
+    }
+
+    // Components the script adds itself are satisfied at runtime
+    expects.retain(|(ty, source)| source == "RequireComponent" || !adds.contains(ty));
+    (expects, adds)
+}
+
+/// Base classes whose members are known, so a method missing from the script really is missing.
+const UNITY_BASE_CLASSES: &[&str] = &["MonoBehaviour", "ScriptableObject", "Behaviour", "Component", "UIBehaviour", "StateMachineBehaviour"];
+
+/// Whether a script defines `method` (a method, or a property for `set_X`/`get_X`).
+/// Returns None when the method may be inherited from a non-Unity base class.
+pub fn defines_method(content: &str, method: &str) -> Option<bool> {
+    let cleaned = strip_string_literals(&strip_block_comments(content));
+    let pattern = match method.strip_prefix("set_").or_else(|| method.strip_prefix("get_")) {
+        Some(property) => format!(r"\b{}\s*(?:\{{|=>)", regex::escape(property)),
+        None => format!(r"\b{}\s*(?:<[^>()]*>)?\s*\(", regex::escape(method)),
+    };
+    if Regex::new(&pattern).ok()?.is_match(&cleaned) {
+        return Some(true);
+    }
+    let inherits_unknown = extract_fields_from_source(content).iter()
+        .filter(|t| t.kind == "class")
+        .any(|t| t.base_class.as_deref().is_some_and(|b| !UNITY_BASE_CLASSES.contains(&b.rsplit('.').next().unwrap_or(b))));
+    if inherits_unknown { None } else { Some(false) }
+}
+
+/// Methods declared directly in each type body, plus each type's interface list.
+fn collect_methods(cleaned: &str) -> (BTreeMap<String, Vec<CSharpMethodInfo>>, BTreeMap<String, Vec<String>>) {
+    let mut methods: BTreeMap<String, Vec<CSharpMethodInfo>> = BTreeMap::new();
+    let mut interfaces: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    // (type name, brace depth at declaration, body entered)
+    let mut type_stack: Vec<(String, i32, bool)> = Vec::new();
+    let mut brace_depth: i32 = 0;
+
+    for line in cleaned.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let in_type_body = type_stack.last().is_some_and(|(_, depth, entered)| *entered && brace_depth == depth + 1);
+        let type_decl = TYPE_DECL_WITH_BASE_RE.captures(trimmed)
+            .filter(|c| !is_keyword(&c[2]) && !trimmed.contains('('));
+        if let Some(caps) = type_decl {
+            let name = caps[2].to_string();
+            if let Some(bases) = BASE_LIST_RE.captures(trimmed) {
+                let mut list: Vec<String> = bases[2].split(',').map(|b| b.trim().to_string()).filter(|b| !b.is_empty()).collect();
+                // For classes the first entry is the base class unless it looks like an interface (IFoo)
+                let first_is_interface = list.first().is_some_and(|b| looks_like_interface(b));
+                if &bases[1] == "class" && !first_is_interface && !list.is_empty() {
+                    list.remove(0);
+                }
+                interfaces.insert(name.clone(), list);
+            }
+            type_stack.push((name, brace_depth, false));
+        } else if in_type_body {
+            let stripped = strip_attributes(trimmed);
+            if let Some(caps) = METHOD_DECL_RE.captures(&stripped) {
+                let modifiers = caps[1].trim().to_string();
+                let return_type = caps[2].to_string();
+                let name = caps[3].to_string();
+                if !is_modifier_or_statement(&return_type) && !is_keyword(&name) {
+                    let parameters = caps[5].trim().to_string();
+                    let generics = caps.get(4).map_or("", |m| m.as_str());
+                    let params_display = if stripped.contains(')') { parameters.clone() } else { format!("{parameters}...") };
+                    let signature = format!("{}{}{} {}{}({})",
+                        modifiers, if modifiers.is_empty() { "" } else { " " }, return_type, name, generics, params_display);
+                    let owner = type_stack.last().map(|(n, _, _)| n.clone()).unwrap_or_default();
+                    methods.entry(owner).or_default().push(CSharpMethodInfo {
+                        is_public: modifiers.split_whitespace().any(|m| m == "public"),
+                        is_static: modifiers.split_whitespace().any(|m| m == "static"),
+                        name,
+                        return_type,
+                        parameters,
+                        signature,
+                    });
+                }
+            }
+        }
+
+        brace_depth += count_braces_simple(trimmed);
+        for entry in type_stack.iter_mut() {
+            if !entry.2 && brace_depth > entry.1 {
+                entry.2 = true;
+            }
+        }
+        while type_stack.last().is_some_and(|(_, depth, entered)| *entered && brace_depth <= *depth) {
+            type_stack.pop();
+        }
+    }
+
+    (methods, interfaces)
+}
+
+fn looks_like_interface(name: &str) -> bool {
+    let short = name.rsplit('.').next().unwrap_or(name);
+    let mut chars = short.chars();
+    chars.next() == Some('I') && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+pub(super) fn is_modifier_or_statement(word: &str) -> bool {
+    matches!(
+        word,
+        "public" | "private" | "protected" | "internal" | "static" | "virtual" | "override"
+            | "abstract" | "sealed" | "async" | "new" | "extern" | "unsafe" | "partial"
+            | "return" | "if" | "while" | "for" | "foreach" | "switch" | "using" | "lock"
+            | "catch" | "else" | "await" | "throw" | "yield" | "delegate" | "event" | "operator"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"using UnityEngine;
+using System.Collections.Generic;
+
+namespace Game
+{
+    [RequireComponent(typeof(Rigidbody))]
+    public class Enemy : MonoBehaviour, IDamageable
+    {
+        public float speed = 2f;
+        [SerializeField] private GameObject projectilePrefab;
+        private int hits;
+
+        void Awake()
+        {
+            var body = GetComponent<Rigidbody>();
+            var anim = GetComponentInChildren<Animator>();
+        }
+
+        private void OnTriggerEnter(Collider other)
+        {
+            if (other.TryGetComponent<Health>(out var h)) { h.Hit(); }
+        }
+
+        public void Fire(Vector3 direction, float force = 1f)
+        {
+            var p = Instantiate(projectilePrefab, transform.position, Quaternion.identity);
+            p.AddComponent<Rigidbody>();
+        }
+
+        public static Enemy Spawn<T>(T data) where T : class { return null; }
+
+        public void TakeDamage(int amount) => hits += amount;
+    }
+
+    public interface IDamageable
+    {
+        void TakeDamage(int amount);
+    }
+}
+"#;
+
+    #[test]
+    fn test_summarize_types_and_methods() {
+        let summary = summarize_source("Assets/Enemy.cs", SOURCE);
+        assert_eq!(summary.usings, vec!["UnityEngine", "System.Collections.Generic"]);
+
+        let enemy = summary.types.iter().find(|t| t.name == "Enemy").unwrap();
+        assert_eq!(enemy.base_class.as_deref(), Some("MonoBehaviour"));
+        assert_eq!(enemy.interfaces, vec!["IDamageable"]);
+        let fields: Vec<&str> = enemy.serialized_fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, vec!["speed", "projectilePrefab"]);
+        let methods: Vec<&str> = enemy.public_methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(methods, vec!["Fire", "Spawn", "TakeDamage"]);
+        assert_eq!(enemy.public_methods[0].signature, "public void Fire(Vector3 direction, float force = 1f)");
+        assert!(enemy.public_methods[1].is_static);
+        assert_eq!(enemy.unity_messages, vec!["Awake", "OnTriggerEnter"]);
+
+        let iface = summary.types.iter().find(|t| t.name == "IDamageable").unwrap();
+        assert_eq!(iface.public_methods.len(), 1, "interface members are implicitly public");
+    }
+
+    #[test]
+    fn test_component_expectations() {
+        let (expects, adds) = component_expectations(SOURCE);
+        assert_eq!(expects, vec![
+            ("Rigidbody".to_string(), "RequireComponent".to_string()),
+        ]);
+        assert!(adds.is_empty(), "p.AddComponent targets another object");
+
+        let (expects, adds) = component_expectations(
+            "class A : MonoBehaviour { void Awake() { a = GetComponent<Animator>(); b = gameObject.GetComponent<AudioSource>(); c = other.GetComponent<Light>(); d = gameObject.AddComponent<Light>(); e = GetComponent<BoxCollider>(); AddComponent<BoxCollider>(); } }",
+        );
+        let types: Vec<&str> = expects.iter().map(|(t, _)| t.as_str()).collect();
+        assert_eq!(types, vec!["Animator", "AudioSource"]);
+        assert_eq!(adds, vec!["Light", "BoxCollider"]);
+    }
+
+    #[test]
+    fn test_defines_method() {
+        assert_eq!(defines_method(SOURCE, "Fire"), Some(true));
+        assert_eq!(defines_method(SOURCE, "Reload"), Some(false));
+        assert_eq!(defines_method("class A : MonoBehaviour { public int Hp { get; set; } }", "set_Hp"), Some(true));
+        assert_eq!(defines_method("class A : BaseMenu { }", "Open"), None);
+    }
+
+    #[test]
+    fn test_summarize_api_usages() {
+        let summary = summarize_source("Assets/Enemy.cs", SOURCE);
+        let usage = |api: &str, ty: &str| summary.api_usages.iter()
+            .find(|u| u.api == api && u.type_name == ty)
+            .map(|u| u.count);
+        assert_eq!(usage("GetComponent", "Rigidbody"), Some(1));
+        assert_eq!(usage("GetComponentInChildren", "Animator"), Some(1));
+        assert_eq!(usage("TryGetComponent", "Health"), Some(1));
+        assert_eq!(usage("AddComponent", "Rigidbody"), Some(1));
+        assert_eq!(usage("RequireComponent", "Rigidbody"), Some(1));
+        assert_eq!(summary.instantiate_targets, vec!["projectilePrefab"]);
+    }
+}
@@ -0,0 +1,587 @@
+//! Persistent cross-file symbol index of a project's C# scripts.
+//!
+//! Each script contributes its type and member declarations and the positions of every
+//! identifier it uses. The index lives in `.unity-agentic/symbol-index.json` next to the scene
+//! indexes and is refreshed incrementally: only scripts whose mtime or size changed are
+//! re-parsed, deleted scripts are dropped. Queries refresh it first, so answers are never stale.
+//!
+//! Usages are textual: every occurrence of the identifier outside comments, strings and its
+//! own declarations. Receivers are not type-checked, so same-named members of different
+//! types share usages.
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::common;
+use crate::scanner::scene_index::source_stamp;
+use crate::walker::DirFilter;
+use super::summary::{is_modifier_or_statement, METHOD_DECL_RE};
+use super::{collect_cs_files, is_keyword, strip_attributes, strip_block_comments, strip_string_literals, FIELD_DECL_RE, TYPE_DECL_WITH_BASE_RE};
+
+/// Index file, relative to the project root.
+pub const SYMBOL_INDEX_PATH: &str = ".unity-agentic/symbol-index.json";
+
+/// Bumped whenever the index layout or extraction changes; older files are rebuilt.
+const INDEX_VERSION: u32 = 1;
+
+static NAMESPACE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*namespace\s+([\w.]+)\s*(;)?").unwrap()
+});
+static IDENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"@?\b[A-Za-z_]\w*\b").unwrap()
+});
+static CTOR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:(?:public|private|protected|internal|static|extern|unsafe)\s+)*(\w+)\s*\(").unwrap()
+});
+static PROPERTY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^((?:(?:public|private|protected|internal|static|virtual|override|abstract|sealed|new|readonly|required|unsafe|extern)\s+)*)([\w.]+(?:<[^;=(]*>)?(?:\[\s*\])*\??)\s+(\w+)\s*(?:\{|=>|$)").unwrap()
+});
+static EVENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bevent\s+([\w.]+(?:<[^;=]*>)?\??)\s+(\w+)\s*(?:;|=|\{|$)").unwrap()
+});
+static ENUM_MEMBER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(\w+)\s*(?:=[^,]*)?$").unwrap()
+});
+
+/// C# reserved words: never symbols, never recorded as usages.
+const RESERVED: &[&str] = &[
+    "abstract", "as", "base", "bool", "break", "byte", "case", "catch", "char", "checked", "class",
+    "const", "continue", "decimal", "default", "delegate", "do", "double", "else", "enum", "event",
+    "explicit", "extern", "false", "finally", "fixed", "float", "for", "foreach", "goto", "if",
+    "implicit", "in", "int", "interface", "internal", "is", "lock", "long", "namespace", "new", "null",
+    "object", "operator", "out", "override", "params", "private", "protected", "public", "readonly",
+    "ref", "return", "sbyte", "sealed", "short", "sizeof", "stackalloc", "static", "string", "struct",
+    "switch", "this", "throw", "true", "try", "typeof", "uint", "ulong", "unchecked", "unsafe",
+    "ushort", "using", "var", "virtual", "void", "volatile", "while",
+];
+
+/// Identifier → (line, column) of each occurrence in one script.
+type IdentifierPositions = BTreeMap<String, Vec<(u32, u32)>>;
+
+/// A type or member declaration.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CSharpSymbol {
+    pub name: String,
+    /// "class", "struct", "interface", "enum", "method", "constructor", "property", "field",
+    /// "event" or "enum_member"
+    pub kind: String,
+    /// Declaring type for members and nested types ("Outer.Inner" for nested declaring types)
+    pub container: Option<String>,
+    pub namespace: Option<String>,
+    /// Script path relative to the project root
+    pub file_path: String,
+    /// 1-based line and column of the name
+    pub line: u32,
+    pub column: u32,
+    /// Method signature, or the declared type of fields, properties and events
+    pub signature: Option<String>,
+}
+
+impl CSharpSymbol {
+    /// `Namespace.Container.Name`
+    fn qualified_name(&self) -> String {
+        [self.namespace.as_deref(), self.container.as_deref(), Some(self.name.as_str())]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    fn is_type(&self) -> bool {
+        matches!(self.kind.as_str(), "class" | "struct" | "interface" | "enum")
+    }
+}
+
+/// One occurrence of an identifier.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CSharpSymbolUsage {
+    pub file_path: String,
+    pub line: u32,
+    pub column: u32,
+    /// The source line, trimmed
+    pub text: String,
+}
+
+/// Outcome of an incremental index refresh.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolIndexStats {
+    pub index_path: String,
+    pub file_count: u32,
+    /// Scripts parsed in this refresh (new or changed)
+    pub files_updated: u32,
+    pub files_removed: u32,
+    pub symbol_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FileSymbols {
+    mtime_ms: u64,
+    size_bytes: u64,
+    declarations: Vec<CSharpSymbol>,
+    identifiers: IdentifierPositions,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SymbolIndex {
+    version: u32,
+    include_packages: bool,
+    files: BTreeMap<String, FileSymbols>,
+}
+
+impl SymbolIndex {
+    fn load(path: &Path) -> Option<Self> {
+        let text = crate::safety::read_to_string(path).ok()?;
+        serde_json::from_str::<SymbolIndex>(&text).ok().filter(|i| i.version == INDEX_VERSION)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            crate::safety::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        common::atomic::write_atomic(path, json).map_err(|e| e.to_string())
+    }
+
+    fn declarations(&self) -> impl Iterator<Item = &CSharpSymbol> {
+        self.files.values().flat_map(|f| f.declarations.iter())
+    }
+}
+
+/// Scripts under Assets/ (and Library/PackageCache/ and Packages/ with packages), as
+/// (absolute, project-relative) paths.
+fn project_scripts(root: &Path, include_packages: bool) -> Vec<(PathBuf, String)> {
+    let filter = DirFilter::for_project(root, &["bin".to_string()]);
+    let mut dirs = vec![root.join("Assets")];
+    if include_packages {
+        dirs.push(root.join("Library").join("PackageCache"));
+        dirs.push(root.join("Packages"));
+    }
+    let mut files = Vec::new();
+    for dir in dirs.iter().filter(|d| d.is_dir()) {
+        collect_cs_files(dir, &filter, &mut files);
+    }
+    files.into_iter()
+        .map(|file| {
+            let relative = file.strip_prefix(root).unwrap_or(&file).to_string_lossy().replace('\\', "/");
+            (file, relative)
+        })
+        .collect()
+}
+
+/// Load the index and re-parse scripts that are new or changed since it was saved.
+fn refresh(root: &Path, include_packages: Option<bool>) -> Result<(SymbolIndex, SymbolIndexStats), String> {
+    let index_path = root.join(SYMBOL_INDEX_PATH);
+    let mut index = SymbolIndex::load(&index_path).unwrap_or_default();
+    let include_packages = include_packages.unwrap_or(index.include_packages);
+    if index.version != INDEX_VERSION || index.include_packages != include_packages {
+        index = SymbolIndex { version: INDEX_VERSION, include_packages, files: BTreeMap::new() };
+    }
+
+    let scripts = project_scripts(root, include_packages);
+    let present: HashSet<&str> = scripts.iter().map(|(_, rel)| rel.as_str()).collect();
+    let before = index.files.len();
+    index.files.retain(|path, _| present.contains(path.as_str()));
+    let files_removed = (before - index.files.len()) as u32;
+
+    let updated: Vec<(String, FileSymbols)> = scripts.par_iter()
+        .filter_map(|(file, relative)| {
+            let (mtime_ms, size_bytes) = source_stamp(file)?;
+            let fresh = index.files.get(relative).is_some_and(|f| f.mtime_ms == mtime_ms && f.size_bytes == size_bytes);
+            if fresh {
+                return None;
+            }
+            let content = common::read_unity_file(file).ok()?;
+            let (declarations, identifiers) = parse_symbols(relative, &content);
+            Some((relative.clone(), FileSymbols { mtime_ms, size_bytes, declarations, identifiers }))
+        })
+        .collect();
+    let files_updated = updated.len() as u32;
+    index.files.extend(updated);
+    if files_updated > 0 || files_removed > 0 || !index_path.is_file() {
+        index.save(&index_path)?;
+    }
+
+    let stats = SymbolIndexStats {
+        index_path: index_path.to_string_lossy().to_string(),
+        file_count: index.files.len() as u32,
+        files_updated,
+        files_removed,
+        symbol_count: index.declarations().count() as u32,
+    };
+    Ok((index, stats))
+}
+
+/// Whether `query` names `symbol`: its simple name, or a dotted suffix of its qualified name
+/// (`Enemy.TakeDamage`, `Game.Enemy`).
+fn names_symbol(symbol: &CSharpSymbol, query: &str) -> bool {
+    if !query.contains('.') {
+        return symbol.name == query;
+    }
+    let qualified = symbol.qualified_name();
+    qualified == query || qualified.ends_with(&format!(".{}", query))
+}
+
+/// Build (or incrementally refresh) the project's symbol index. `include_packages` also indexes
+/// Library/PackageCache/ and Packages/ scripts; when omitted the previous setting is kept.
+#[napi]
+pub fn update_symbol_index(project_root: String, include_packages: Option<bool>) -> serde_json::Value {
+    let _call = crate::telemetry::call("update_symbol_index");
+    match refresh(Path::new(&project_root), include_packages) {
+        Ok((_, stats)) => serde_json::json!({
+            "index_path": stats.index_path,
+            "file_count": stats.file_count,
+            "files_updated": stats.files_updated,
+            "files_removed": stats.files_removed,
+            "symbol_count": stats.symbol_count,
+        }),
+        Err(e) => serde_json::json!({ "error": e, "is_error": true }),
+    }
+}
+
+/// Go to definition: declarations named `name` (a simple name, or qualified like
+/// `Enemy.TakeDamage` / `Game.Enemy`). Partial types yield one entry per declaring file.
+#[napi]
+pub fn find_symbol_definitions(project_root: String, name: String) -> Vec<CSharpSymbol> {
+    let _call = crate::telemetry::call("find_symbol_definitions");
+    let Ok((index, _)) = refresh(Path::new(&project_root), None) else { return Vec::new() };
+    index.declarations().filter(|s| names_symbol(s, &name)).cloned().collect()
+}
+
+/// Members declared in the type `type_name` (simple or qualified name) across all of its
+/// partial declarations, in file and line order. Nested types are listed as members too.
+#[napi]
+pub fn list_type_members(project_root: String, type_name: String) -> Vec<CSharpSymbol> {
+    let _call = crate::telemetry::call("list_type_members");
+    let Ok((index, _)) = refresh(Path::new(&project_root), None) else { return Vec::new() };
+    let types: HashSet<String> = index.declarations()
+        .filter(|s| s.is_type() && names_symbol(s, &type_name))
+        .map(|s| s.qualified_name())
+        .collect();
+    index.declarations()
+        .filter(|s| {
+            let owner = [s.namespace.as_deref(), s.container.as_deref()].into_iter().flatten().collect::<Vec<_>>().join(".");
+            s.container.is_some() && types.contains(&owner)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Find usages: occurrences of the symbol's identifier (the last segment of a qualified name)
+/// outside comments, strings and the symbol's own declarations.
+#[napi]
+pub fn find_symbol_usages(project_root: String, name: String, limit: Option<u32>) -> Vec<CSharpSymbolUsage> {
+    let _call = crate::telemetry::call("find_symbol_usages");
+    let root = Path::new(&project_root);
+    let Ok((index, _)) = refresh(root, None) else { return Vec::new() };
+    let identifier = name.rsplit('.').next().unwrap_or(&name);
+    let declared_at: HashSet<(&str, u32, u32)> = index.declarations()
+        .filter(|s| s.name == identifier)
+        .map(|s| (s.file_path.as_str(), s.line, s.column))
+        .collect();
+
+    let mut usages = Vec::new();
+    for (path, file) in &index.files {
+        let Some(positions) = file.identifiers.get(identifier) else { continue };
+        let positions: Vec<(u32, u32)> = positions.iter()
+            .filter(|(line, column)| !declared_at.contains(&(path.as_str(), *line, *column)))
+            .copied()
+            .collect();
+        if positions.is_empty() {
+            continue;
+        }
+        let content = common::read_unity_file(root.join(path)).unwrap_or_default();
+        let lines: Vec<&str> = content.lines().collect();
+        for (line, column) in positions {
+            usages.push(CSharpSymbolUsage {
+                file_path: path.clone(),
+                line,
+                column,
+                text: lines.get(line as usize - 1).map_or(String::new(), |l| l.trim().to_string()),
+            });
+        }
+    }
+    usages.truncate(limit.map_or(usize::MAX, |l| l as usize));
+    usages
+}
+
+#[derive(Debug)]
+enum Scope {
+    Namespace(String),
+    /// (name, kind)
+    Type(String, String),
+}
+
+/// Declarations and identifier positions of one script.
+fn parse_symbols(file_path: &str, content: &str) -> (Vec<CSharpSymbol>, IdentifierPositions) {
+    let cleaned = strip_string_literals(&strip_block_comments(content));
+    let mut declarations = Vec::new();
+    let mut identifiers = IdentifierPositions::new();
+    let mut file_namespace: Option<String> = None;
+    // Open scopes with the brace depth outside them; `pending` opens at the next `{`
+    let mut scopes: Vec<(Scope, i32)> = Vec::new();
+    let mut pending: Option<Scope> = None;
+    let mut depth: i32 = 0;
+
+    for (i, raw) in cleaned.lines().enumerate() {
+        let line_no = i as u32 + 1;
+        let line = raw.find("//").map_or(raw, |pos| &raw[..pos]);
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        for m in IDENT_RE.find_iter(line) {
+            let ident = m.as_str().trim_start_matches('@');
+            if !RESERVED.contains(&ident) {
+                identifiers.entry(ident.to_string()).or_default().push((line_no, m.start() as u32 + 1 + u32::from(m.as_str().starts_with('@'))));
+            }
+        }
+
+        let namespace = || {
+            let mut parts: Vec<&str> = file_namespace.iter().map(String::as_str).collect();
+            parts.extend(scopes.iter().filter_map(|(s, _)| match s { Scope::Namespace(n) => Some(n.as_str()), _ => None }));
+            (!parts.is_empty()).then(|| parts.join("."))
+        };
+        let container = || {
+            let types: Vec<&str> = scopes.iter().filter_map(|(s, _)| match s { Scope::Type(n, _) => Some(n.as_str()), _ => None }).collect();
+            (!types.is_empty()).then(|| types.join("."))
+        };
+        let in_type_body = match scopes.last() {
+            Some((Scope::Type(_, kind), outer)) if depth == outer + 1 => Some(kind.clone()),
+            _ => None,
+        };
+        let mut declare = |name: &str, kind: &str, column: usize, signature: Option<String>| {
+            declarations.push(CSharpSymbol {
+                name: name.to_string(),
+                kind: kind.to_string(),
+                container: container(),
+                namespace: namespace(),
+                file_path: file_path.to_string(),
+                line: line_no,
+                column: column as u32 + 1,
+                signature,
+            });
+        };
+
+        let type_decl = TYPE_DECL_WITH_BASE_RE.captures(line).filter(|c| !is_keyword(&c[2]) && !line.contains('('));
+        if let Some(caps) = NAMESPACE_RE.captures(line) {
+            if caps.get(2).is_some() {
+                file_namespace = Some(caps[1].to_string());
+            } else {
+                pending = Some(Scope::Namespace(caps[1].to_string()));
+            }
+        } else if let Some(caps) = type_decl {
+            let name = caps.get(2).unwrap();
+            declare(name.as_str(), &caps[1], name.start(), None);
+            // Single-line enum bodies: `enum State { Idle, Dead }`
+            if let Some(open) = line[name.end()..].find('{').filter(|_| &caps[1] == "enum") {
+                let body_start = name.end() + open + 1;
+                let owner = container().map_or(name.as_str().to_string(), |c| format!("{}.{}", c, name.as_str()));
+                for (member, column) in enum_members(&line[body_start..], body_start) {
+                    declarations.push(CSharpSymbol {
+                        name: member.to_string(),
+                        kind: "enum_member".to_string(),
+                        container: Some(owner.clone()),
+                        namespace: namespace(),
+                        file_path: file_path.to_string(),
+                        line: line_no,
+                        column: column as u32 + 1,
+                        signature: None,
+                    });
+                }
+            }
+            pending = Some(Scope::Type(name.as_str().to_string(), caps[1].to_string()));
+        } else if let Some(kind) = in_type_body {
+            let stripped = strip_attributes(line);
+            let offset = line.len() - stripped.len();
+            let stripped = stripped.trim_end();
+            if kind == "enum" {
+                for (name, column) in enum_members(stripped, offset) {
+                    declare(name, "enum_member", column, None);
+                }
+            } else if let Some(caps) = EVENT_RE.captures(stripped) {
+                declare(&caps[2], "event", offset + caps.get(2).unwrap().start(), Some(caps[1].to_string()));
+            } else if let Some(caps) = METHOD_DECL_RE.captures(stripped).filter(|c| !is_modifier_or_statement(&c[2]) && !is_keyword(&c[3])) {
+                let modifiers = caps[1].trim();
+                let signature = format!("{}{}{} {}{}({})", modifiers, if modifiers.is_empty() { "" } else { " " },
+                    &caps[2], &caps[3], caps.get(4).map_or("", |m| m.as_str()), caps[5].trim());
+                declare(&caps[3], "method", offset + caps.get(3).unwrap().start(), Some(signature));
+            } else if let Some(caps) = CTOR_RE.captures(stripped).filter(|c| matches!(scopes.last(), Some((Scope::Type(n, _), _)) if *n == c[1])) {
+                declare(&caps[1], "constructor", offset + caps.get(1).unwrap().start(), None);
+            } else if let Some(caps) = PROPERTY_RE.captures(stripped).filter(|c| !is_modifier_or_statement(&c[2]) && !RESERVED.contains(&&c[3])) {
+                declare(&caps[3], "property", offset + caps.get(3).unwrap().start(), Some(caps[2].to_string()));
+            } else if let Some(caps) = FIELD_DECL_RE.captures(stripped).filter(|c| !is_modifier_or_statement(&c[2]) && !RESERVED.contains(&&c[3])) {
+                declare(&caps[3], "field", offset + caps.get(3).unwrap().start(), Some(caps[2].to_string()));
+            }
+        }
+
+        for ch in line.chars() {
+            match ch {
+                '{' => {
+                    if let Some(scope) = pending.take() {
+                        scopes.push((scope, depth));
+                    }
+                    depth += 1;
+                }
+                '}' => {
+                    depth -= 1;
+                    while scopes.last().is_some_and(|(_, outer)| *outer >= depth) {
+                        scopes.pop();
+                    }
+                }
+                // `class Foo;`-style declarations without a body
+                ';' if pending.is_some() && scopes.last().is_none_or(|(_, outer)| *outer < depth) => pending = None,
+                _ => {}
+            }
+        }
+    }
+
+    (declarations, identifiers)
+}
+
+/// Enum member names in a (partial) enum body line, with the 0-based column of each given the
+/// line offset of `text`.
+fn enum_members(text: &str, offset: usize) -> Vec<(&str, usize)> {
+    let mut members = Vec::new();
+    let mut start = offset;
+    for part in text.split(',') {
+        let part_start = start;
+        start += part.len() + 1;
+        let trimmed = part.trim_matches(['{', '}', ';']);
+        if let Some(name) = ENUM_MEMBER_RE.captures(trimmed).and_then(|c| c.get(1)) {
+            let column = part_start + (part.len() - part.trim_start_matches(['{', '}', ';']).len()) + name.start();
+            members.push((name.as_str(), column));
+        }
+    }
+    members
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const ENEMY: &str = r#"using UnityEngine;
+
+namespace Game.Actors
+{
+    /// <summary>Enemy health</summary>
+    [RequireComponent(typeof(Rigidbody))]
+    public partial class Enemy : MonoBehaviour, IDamageable
+    {
+        [SerializeField] private float speed = 2f;
+        public int Health { get; private set; }
+        public event System.Action Died;
+        // public void Commented() {}
+
+        public Enemy(int health)
+        {
+            Health = health;
+        }
+
+        public void TakeDamage(int amount)
+        {
+            Health -= amount;
+            var label = "TakeDamage";
+        }
+
+        public enum State { Idle, Chasing = 2, Dead }
+
+        private class Memory
+        {
+            public Vector3 lastSeen;
+        }
+    }
+}
+"#;
+
+    const PLAYER: &str = r#"namespace Game;
+
+public class Player
+{
+    void Hit(Game.Actors.Enemy enemy)
+    {
+        enemy.TakeDamage(10);
+        if (enemy.Health <= 0) { }
+    }
+}
+"#;
+
+    fn summary(symbols: &[CSharpSymbol]) -> Vec<(String, String, Option<String>, u32, u32)> {
+        symbols.iter().map(|s| (s.name.clone(), s.kind.clone(), s.container.clone(), s.line, s.column)).collect()
+    }
+
+    #[test]
+    fn test_parse_symbols() {
+        let (declarations, identifiers) = parse_symbols("Assets/Enemy.cs", ENEMY);
+        let s = |name: &str, kind: &str, container: Option<&str>, line: u32, column: u32| {
+            (name.to_string(), kind.to_string(), container.map(str::to_string), line, column)
+        };
+        assert_eq!(summary(&declarations), vec![
+            s("Enemy", "class", None, 7, 26),
+            s("speed", "field", Some("Enemy"), 9, 40),
+            s("Health", "property", Some("Enemy"), 10, 20),
+            s("Died", "event", Some("Enemy"), 11, 36),
+            s("Enemy", "constructor", Some("Enemy"), 14, 16),
+            s("TakeDamage", "method", Some("Enemy"), 19, 21),
+            s("State", "enum", Some("Enemy"), 25, 21),
+            s("Idle", "enum_member", Some("Enemy.State"), 25, 29),
+            s("Chasing", "enum_member", Some("Enemy.State"), 25, 35),
+            s("Dead", "enum_member", Some("Enemy.State"), 25, 48),
+            s("Memory", "class", Some("Enemy"), 27, 23),
+            s("lastSeen", "field", Some("Enemy.Memory"), 29, 28),
+        ]);
+        assert!(declarations.iter().all(|d| d.namespace.as_deref() == Some("Game.Actors")));
+        assert_eq!(declarations[5].signature.as_deref(), Some("public void TakeDamage(int amount)"));
+        assert_eq!(declarations[2].signature.as_deref(), Some("int"));
+        // Strings and comments are not usages
+        assert_eq!(identifiers["TakeDamage"], vec![(19, 21)]);
+        assert!(!identifiers.contains_key("Commented"));
+        assert!(!identifiers.contains_key("summary"));
+        assert_eq!(identifiers["Health"].len(), 3);
+    }
+
+    #[test]
+    fn test_queries_and_incremental_refresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("Assets/Scripts")).unwrap();
+        fs::write(root.join("Assets/Scripts/Enemy.cs"), ENEMY).unwrap();
+        fs::write(root.join("Assets/Scripts/Player.cs"), PLAYER).unwrap();
+        let project = root.to_string_lossy().to_string();
+
+        let stats = update_symbol_index(project.clone(), None);
+        assert_eq!((stats["file_count"].as_u64(), stats["files_updated"].as_u64()), (Some(2), Some(2)));
+        assert!(root.join(SYMBOL_INDEX_PATH).is_file());
+        assert_eq!(update_symbol_index(project.clone(), None)["files_updated"], 0);
+
+        let defs = find_symbol_definitions(project.clone(), "Enemy.TakeDamage".to_string());
+        assert_eq!(summary(&defs), vec![("TakeDamage".to_string(), "method".to_string(), Some("Enemy".to_string()), 19, 21)]);
+        assert_eq!(find_symbol_definitions(project.clone(), "Game.Actors.Enemy".to_string()).len(), 1);
+        assert_eq!(find_symbol_definitions(project.clone(), "Player".to_string())[0].namespace.as_deref(), Some("Game"));
+        assert!(find_symbol_definitions(project.clone(), "Other.TakeDamage".to_string()).is_empty());
+
+        let members: Vec<String> = list_type_members(project.clone(), "Enemy".to_string()).into_iter().map(|m| m.name).collect();
+        assert_eq!(members, vec!["speed", "Health", "Died", "Enemy", "TakeDamage", "State", "Memory"]);
+
+        let usages = find_symbol_usages(project.clone(), "Enemy.TakeDamage".to_string(), None);
+        assert_eq!(usages.len(), 1);
+        assert_eq!((usages[0].file_path.as_str(), usages[0].line, usages[0].column), ("Assets/Scripts/Player.cs", 7, 15));
+        assert_eq!(usages[0].text, "enemy.TakeDamage(10);");
+
+        // A partial declaration in a new file is picked up; deleting it drops it again
+        fs::write(root.join("Assets/Scripts/Enemy.AI.cs"), "namespace Game.Actors {\n    public partial class Enemy {\n        void Think() { TakeDamage(0); }\n    }\n}\n").unwrap();
+        assert_eq!(find_symbol_usages(project.clone(), "TakeDamage".to_string(), None).len(), 2);
+        assert!(list_type_members(project.clone(), "Game.Actors.Enemy".to_string()).iter().any(|m| m.name == "Think"));
+        fs::remove_file(root.join("Assets/Scripts/Enemy.AI.cs")).unwrap();
+        let stats = update_symbol_index(project.clone(), None);
+        assert_eq!((stats["file_count"].as_u64(), stats["files_removed"].as_u64()), (Some(2), Some(1)));
+        assert_eq!(find_symbol_usages(project, "TakeDamage".to_string(), Some(5)).len(), 1);
+    }
+}
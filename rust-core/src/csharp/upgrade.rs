@@ -0,0 +1,309 @@
+//! Obsolete Unity API scan with replacement suggestions.
+//!
+//! A curated table maps removed or deprecated APIs (component shortcut properties,
+//! `Application.LoadLevel` and friends, `FindObjectOfType`, ...) to their replacements. Projects
+//! extend or override it with `obsolete_apis` entries in `.unity-agentic/config.json`. Matching
+//! is lexical over comment- and string-free source, so it does not resolve types: identifiers
+//! the script itself declares (a local named `rigidbody`) are not reported.
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::common;
+use crate::scanner::config::{self, ObsoleteApiRule};
+use crate::walker::DirFilter;
+use super::{collect_cs_files, strip_block_comments, strip_string_literals};
+
+/// Component shortcut properties removed in Unity 5: (property, component type).
+const LEGACY_SHORTCUTS: &[(&str, &str)] = &[
+    ("rigidbody", "Rigidbody"),
+    ("rigidbody2D", "Rigidbody2D"),
+    ("collider", "Collider"),
+    ("collider2D", "Collider2D"),
+    ("renderer", "Renderer"),
+    ("audio", "AudioSource"),
+    ("camera", "Camera"),
+    ("light", "Light"),
+    ("animation", "Animation"),
+    ("constantForce", "ConstantForce"),
+    ("hingeJoint", "HingeJoint"),
+    ("particleSystem", "ParticleSystem"),
+];
+
+/// Built-in rules: (id, pattern, replacement, note).
+const BUILTIN_RULES: &[(&str, &str, &str, &str)] = &[
+    ("load-level", r"\bApplication\.LoadLevel\b", "SceneManager.LoadScene", "Add `using UnityEngine.SceneManagement;`"),
+    ("load-level-async", r"\bApplication\.LoadLevelAsync\b", "SceneManager.LoadSceneAsync", "Add `using UnityEngine.SceneManagement;`"),
+    ("load-level-additive", r"\bApplication\.LoadLevelAdditive\b", "SceneManager.LoadScene", "Pass LoadSceneMode.Additive as the second argument"),
+    ("load-level-additive-async", r"\bApplication\.LoadLevelAdditiveAsync\b", "SceneManager.LoadSceneAsync", "Pass LoadSceneMode.Additive as the second argument"),
+    ("loaded-level", r"\bApplication\.loadedLevel\b", "SceneManager.GetActiveScene().buildIndex", ""),
+    ("loaded-level-name", r"\bApplication\.loadedLevelName\b", "SceneManager.GetActiveScene().name", ""),
+    ("level-count", r"\bApplication\.levelCount\b", "SceneManager.sceneCountInBuildSettings", ""),
+    ("unload-level", r"\b(?:Application\.UnloadLevel|SceneManager\.UnloadScene)\b", "SceneManager.UnloadSceneAsync", ""),
+    ("find-object-of-type", r"\bFindObjectOfType\b", "FindFirstObjectByType", "Obsolete since Unity 2023.1; FindAnyObjectByType is faster when any instance will do"),
+    ("find-objects-of-type", r"\bFindObjectsOfType\b", "FindObjectsByType", "Pass FindObjectsSortMode.None unless the order matters"),
+    ("find-scene-objects-of-type", r"\bFindSceneObjectsOfType\b", "FindObjectsByType", "Pass FindObjectsSortMode.None unless the order matters"),
+    ("destroy-object", r"\bDestroyObject\b", "Destroy", ""),
+    ("set-active-recursively", r"\bSetActiveRecursively\b", "SetActive", ""),
+    ("gameobject-active", r"\bgameObject\.(?P<api>active)\b", "activeSelf", "Assignments become SetActive(value)"),
+    ("find-child", r"\.(?P<api>FindChild)\s*\(", "Find", ""),
+    ("random-range", r"\bRandom\.RandomRange\b", "Random.Range", ""),
+    ("lock-cursor", r"\bScreen\.lockCursor\b", "Cursor.lockState", "Assign CursorLockMode.Locked / CursorLockMode.None instead of a bool"),
+    ("show-cursor", r"\bScreen\.showCursor\b", "Cursor.visible", ""),
+    ("register-log-callback", r"\bApplication\.RegisterLogCallback(?:Threaded)?\b", "Application.logMessageReceived", "Subscribe with += instead of calling"),
+    ("www", r"\b(?P<api>new\s+WWW)\s*\(", "UnityWebRequest.Get", "Add `using UnityEngine.Networking;` and yield on SendWebRequest()"),
+    ("playmode-state-changed", r"\bEditorApplication\.playmodeStateChanged\b", "EditorApplication.playModeStateChanged", "Handlers take a PlayModeStateChange argument"),
+    ("prefab-get-parent", r"\bPrefabUtility\.(?P<api>GetPrefabParent)\b", "GetCorrespondingObjectFromSource", ""),
+    ("prefab-create", r"\bPrefabUtility\.(?P<api>CreatePrefab|ReplacePrefab)\b", "SaveAsPrefabAsset", ""),
+    ("prefab-get-type", r"\bPrefabUtility\.(?P<api>GetPrefabType)\b", "GetPrefabAssetType", "Use GetPrefabInstanceStatus for scene instances"),
+    ("physics-auto-simulation", r"\bPhysics\.autoSimulation\b", "Physics.simulationMode", "Assign SimulationMode.FixedUpdate / SimulationMode.Script instead of a bool"),
+    ("gui-text", r"\bGUIText\b", "Text", "Removed in Unity 2019.2; use UnityEngine.UI.Text or TextMeshPro"),
+    ("gui-texture", r"\bGUITexture\b", "Image", "Removed in Unity 2019.2; use UnityEngine.UI.Image"),
+];
+
+/// One obsolete API use.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObsoleteApiUsage {
+    /// Script path relative to the project root
+    pub file_path: String,
+    /// 1-based line and column (characters)
+    pub line: u32,
+    pub column: u32,
+    pub rule_id: String,
+    /// The obsolete source text
+    pub api: String,
+    pub replacement: String,
+    pub note: Option<String>,
+    /// The source line, trimmed
+    pub text: String,
+    /// The source line with the replacement applied, trimmed
+    pub suggested: String,
+}
+
+/// Result of an obsolete API scan.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObsoleteApiReport {
+    pub project_root: String,
+    pub files_scanned: u32,
+    pub rule_count: u32,
+    pub usages: Vec<ObsoleteApiUsage>,
+    /// Config rules that were skipped because their pattern does not compile
+    pub rule_errors: Vec<String>,
+}
+
+struct CompiledRule {
+    rule: ObsoleteApiRule,
+    regex: Regex,
+}
+
+/// The built-in mapping.
+pub fn builtin_rules() -> Vec<ObsoleteApiRule> {
+    let shortcuts = LEGACY_SHORTCUTS.iter().map(|(property, component)| ObsoleteApiRule {
+        id: format!("legacy-{}", property.to_lowercase()),
+        // `rigidbody.velocity`, `gameObject.rigidbody.velocity`; not `other.rigidbody` fields
+        pattern: format!(r"(?:^|[^\w.]|\b(?:gameObject|this)\.)(?P<api>{})\s*\.\s*\w", property),
+        replacement: format!("GetComponent<{}>()", component),
+        note: Some("Shortcut removed in Unity 5; cache the component in Awake".to_string()),
+    });
+    let table = BUILTIN_RULES.iter().map(|(id, pattern, replacement, note)| ObsoleteApiRule {
+        id: id.to_string(),
+        pattern: pattern.to_string(),
+        replacement: replacement.to_string(),
+        note: (!note.is_empty()).then(|| note.to_string()),
+    });
+    shortcuts.chain(table).collect()
+}
+
+/// Built-in rules followed by config rules; a config rule with a built-in id replaces it.
+fn compile_rules(extra: Vec<ObsoleteApiRule>) -> (Vec<CompiledRule>, Vec<String>) {
+    let mut rules = builtin_rules();
+    for rule in extra {
+        match rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => *existing = rule,
+            None => rules.push(rule),
+        }
+    }
+    let mut errors = Vec::new();
+    let compiled = rules.into_iter()
+        .filter_map(|rule| match Regex::new(&rule.pattern) {
+            Ok(regex) => Some(CompiledRule { rule, regex }),
+            Err(e) => {
+                errors.push(format!("{}: {}", rule.id, e));
+                None
+            }
+        })
+        .collect();
+    (compiled, errors)
+}
+
+/// Whether the (comment- and string-free) source declares a variable, field or parameter `name`.
+fn declares(cleaned: &str, name: &str) -> bool {
+    Regex::new(&format!(r"\b[\w.]+(?:<[^;=()]*>)?(?:\[\s*\])*\??\s+{}\s*[;=,)]", regex::escape(name)))
+        .is_ok_and(|re| re.is_match(cleaned))
+}
+
+fn scan_source(file_path: &str, content: &str, rules: &[CompiledRule]) -> Vec<ObsoleteApiUsage> {
+    let cleaned = strip_string_literals(&strip_block_comments(content));
+    let source_lines: Vec<&str> = content.lines().collect();
+    let mut declared: HashMap<String, bool> = HashMap::new();
+    let mut usages = Vec::new();
+
+    for (i, raw) in cleaned.lines().enumerate() {
+        let line = raw.find("//").map_or(raw, |pos| &raw[..pos]);
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        let source = source_lines.get(i).copied().unwrap_or(line);
+        for compiled in rules {
+            for caps in compiled.regex.captures_iter(line) {
+                let Some(span) = caps.name("api").or_else(|| caps.get(0)) else { continue };
+                let api = span.as_str();
+                let is_identifier = api.chars().all(|c| c.is_alphanumeric() || c == '_');
+                if is_identifier && *declared.entry(api.to_string()).or_insert_with(|| declares(&cleaned, api)) {
+                    continue;
+                }
+                // Offsets come from the cleaned line; only rewrite the source when they still line up
+                let suggested = match source.get(span.range()) {
+                    Some(text) if text == api => format!("{}{}{}", &source[..span.start()], compiled.rule.replacement, &source[span.end()..]),
+                    _ => line.replacen(api, &compiled.rule.replacement, 1),
+                };
+                usages.push(ObsoleteApiUsage {
+                    file_path: file_path.to_string(),
+                    line: i as u32 + 1,
+                    column: line[..span.start()].chars().count() as u32 + 1,
+                    rule_id: compiled.rule.id.clone(),
+                    api: api.to_string(),
+                    replacement: compiled.rule.replacement.clone(),
+                    note: compiled.rule.note.clone(),
+                    text: source.trim().to_string(),
+                    suggested: suggested.trim().to_string(),
+                });
+            }
+        }
+    }
+    usages.sort_by_key(|u| (u.line, u.column));
+    usages
+}
+
+/// Scan the project's scripts under Assets/ for obsolete Unity APIs, with a suggested
+/// replacement for each use. The built-in mapping is extended (or overridden by id) with
+/// `obsolete_apis` rules from the global and project `.unity-agentic/config.json`.
+#[napi]
+pub fn scan_obsolete_apis(project_root: String) -> ObsoleteApiReport {
+    let _call = crate::telemetry::call("scan_obsolete_apis");
+    let root = Path::new(&project_root);
+    let global = config::global_config_path();
+    let (rules, rule_errors) = compile_rules(config::obsolete_api_rules(root, global.as_deref()));
+
+    let filter = DirFilter::for_project(root, &["bin".to_string()]);
+    let mut files = Vec::new();
+    let assets = root.join("Assets");
+    if assets.is_dir() {
+        collect_cs_files(&assets, &filter, &mut files);
+    }
+    files.sort();
+
+    let usages = files.par_iter()
+        .filter_map(|file| {
+            let content = common::read_unity_file(file).ok()?;
+            let relative = file.strip_prefix(root).unwrap_or(file).to_string_lossy().replace('\\', "/");
+            Some(scan_source(&relative, &content, &rules))
+        })
+        .flatten()
+        .collect();
+
+    ObsoleteApiReport {
+        project_root,
+        files_scanned: files.len() as u32,
+        rule_count: rules.len() as u32,
+        usages,
+        rule_errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const LEGACY: &str = r#"using UnityEngine;
+
+public class Legacy : MonoBehaviour
+{
+    public Rigidbody body;
+
+    void Start()
+    {
+        rigidbody.velocity = Vector3.zero; // rigidbody.velocity in a comment
+        gameObject.renderer.enabled = false;
+        body.velocity = Vector3.one;
+        var enemy = FindObjectOfType<Enemy>();
+        Application.LoadLevel("Menu");
+        Debug.Log("Application.LoadLevel");
+        /* Application.loadedLevel */
+        int level = Application.loadedLevel;
+    }
+}
+"#;
+
+    fn ids(usages: &[ObsoleteApiUsage]) -> Vec<(String, u32, u32)> {
+        usages.iter().map(|u| (u.rule_id.clone(), u.line, u.column)).collect()
+    }
+
+    #[test]
+    fn test_builtin_rules_compile() {
+        let (rules, errors) = compile_rules(Vec::new());
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(rules.len(), LEGACY_SHORTCUTS.len() + BUILTIN_RULES.len());
+    }
+
+    #[test]
+    fn test_scan_source() {
+        let (rules, _) = compile_rules(Vec::new());
+        let usages = scan_source("Assets/Legacy.cs", LEGACY, &rules);
+        assert_eq!(ids(&usages), vec![
+            ("legacy-rigidbody".to_string(), 9, 9),
+            ("legacy-renderer".to_string(), 10, 20),
+            ("find-object-of-type".to_string(), 12, 21),
+            ("load-level".to_string(), 13, 9),
+            ("loaded-level".to_string(), 16, 21),
+        ]);
+        assert_eq!(usages[0].suggested, "GetComponent<Rigidbody>().velocity = Vector3.zero; // rigidbody.velocity in a comment");
+        assert_eq!(usages[1].suggested, "gameObject.GetComponent<Renderer>().enabled = false;");
+        assert_eq!(usages[3].suggested, "SceneManager.LoadScene(\"Menu\");");
+        assert_eq!(usages[3].note.as_deref(), Some("Add `using UnityEngine.SceneManagement;`"));
+
+        // A script's own `rigidbody` variable is not the legacy shortcut
+        let local = "void F() {\n    Rigidbody rigidbody = GetComponent<Rigidbody>();\n    rigidbody.velocity = Vector3.zero;\n}\n";
+        assert!(scan_source("Assets/Local.cs", local, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_scan_obsolete_apis_with_config_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("Assets/Scripts")).unwrap();
+        fs::create_dir_all(root.join(".unity-agentic")).unwrap();
+        fs::write(root.join("Assets/Scripts/Legacy.cs"), LEGACY).unwrap();
+        fs::write(root.join(".unity-agentic/config.json"), r#"{"obsolete_apis": [
+            {"id": "debug-log", "pattern": "\\bDebug\\.Log\\b", "replacement": "Logger.Info", "note": "Project logging"},
+            {"id": "load-level", "pattern": "\\bApplication\\.LoadLevel\\b", "replacement": "Scenes.Load"},
+            {"id": "broken", "pattern": "(", "replacement": "x"}
+        ]}"#).unwrap();
+
+        let report = scan_obsolete_apis(root.to_string_lossy().to_string());
+        assert_eq!(report.files_scanned, 1);
+        assert_eq!(report.rule_errors.len(), 1);
+        assert!(report.rule_errors[0].starts_with("broken:"));
+        let load = report.usages.iter().find(|u| u.rule_id == "load-level").unwrap();
+        assert_eq!((load.replacement.as_str(), load.note.as_deref()), ("Scenes.Load", None));
+        let log = report.usages.iter().find(|u| u.rule_id == "debug-log").unwrap();
+        assert_eq!((log.file_path.as_str(), log.line, log.suggested.as_str()), ("Assets/Scripts/Legacy.cs", 14, "Logger.Info(\"Application.LoadLevel\");"));
+        assert_eq!(report.usages.len(), 6);
+    }
+}
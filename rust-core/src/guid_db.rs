@@ -0,0 +1,318 @@
+//! Persistent GUID → path database of a project's Assets/, with a change journal so
+//! long-running callers can mirror the map by asking only for what changed since their last
+//! sync token.
+//!
+//! The database lives in `.unity-agentic/guid-db.json`. Each refresh re-reads only `.meta`
+//! files whose mtime or size changed, diffs the resulting map against the stored one and, when
+//! anything changed, appends the difference to the journal under a new token. Tokens carry the
+//! database's epoch, so a token from a deleted or rebuilt database (or one older than the
+//! journal keeps) asks for a full resync instead of a wrong diff.
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::common;
+use crate::scanner::scene_index::source_stamp;
+use crate::walker::DirFilter;
+
+/// Database file, relative to the project root.
+pub const GUID_DB_PATH: &str = ".unity-agentic/guid-db.json";
+
+/// Bumped whenever the database layout changes; older files are rebuilt (with a new epoch).
+const DB_VERSION: u32 = 1;
+
+/// Journal entries kept; older tokens get a full resync.
+const MAX_JOURNAL: usize = 100;
+
+static META_GUID_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^guid:[ \t]*([a-f0-9]{32})").unwrap());
+
+/// A GUID and the asset path it maps to.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuidMapping {
+    pub guid: String,
+    /// Asset path relative to the project root
+    pub path: String,
+}
+
+/// A GUID whose asset moved or was renamed.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuidPathChange {
+    pub guid: String,
+    pub previous_path: String,
+    pub path: String,
+}
+
+/// Changes to a project's GUID map since a sync token.
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuidChanges {
+    /// Pass to the next call to receive only later changes
+    pub token: String,
+    /// True when `since_token` was missing, unknown or too old: `added` holds the whole map and
+    /// the caller should replace its mirror
+    pub full_resync: bool,
+    pub added: Vec<GuidMapping>,
+    pub removed: Vec<GuidMapping>,
+    pub modified: Vec<GuidPathChange>,
+    #[napi(ts_type = "string | undefined")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MetaEntry {
+    mtime_ms: u64,
+    size_bytes: u64,
+    guid: Option<String>,
+}
+
+/// GUID → (path before, path after), None where absent.
+type PathChanges = BTreeMap<String, (Option<String>, Option<String>)>;
+
+/// The paths one refresh changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct JournalEntry {
+    token: u64,
+    changes: PathChanges,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct GuidDb {
+    version: u32,
+    epoch: String,
+    token: u64,
+    /// `.meta` path (project-relative) → stamp and GUID
+    metas: BTreeMap<String, MetaEntry>,
+    journal: Vec<JournalEntry>,
+}
+
+impl GuidDb {
+    /// An empty database with a new epoch.
+    fn fresh() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        GuidDb { version: DB_VERSION, epoch: format!("{:x}", nanos), ..Default::default() }
+    }
+
+    fn load(path: &Path) -> Option<Self> {
+        let text = crate::safety::read_to_string(path).ok()?;
+        serde_json::from_str::<GuidDb>(&text).ok().filter(|db| db.version == DB_VERSION)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            crate::safety::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        common::atomic::write_atomic(path, json).map_err(|e| e.to_string())
+    }
+
+    /// GUID → asset path. A GUID claimed by several `.meta` files maps to the first path.
+    fn map(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+        for (meta, entry) in &self.metas {
+            if let Some(guid) = &entry.guid {
+                let asset = meta.strip_suffix(".meta").unwrap_or(meta);
+                map.entry(guid.clone()).or_insert_with(|| asset.to_string());
+            }
+        }
+        map
+    }
+
+    fn token_string(&self) -> String {
+        format!("{}-{}", self.epoch, self.token)
+    }
+
+    /// Net changes after `since` (a token of this epoch), or None when the journal no longer
+    /// reaches back that far.
+    fn changes_since(&self, since: u64) -> Option<PathChanges> {
+        if since > self.token {
+            return None;
+        }
+        if since < self.token && self.journal.first().is_none_or(|e| e.token > since + 1) {
+            return None;
+        }
+        let mut net = PathChanges::new();
+        for entry in self.journal.iter().filter(|e| e.token > since) {
+            for (guid, (before, after)) in &entry.changes {
+                net.entry(guid.clone())
+                    .and_modify(|(_, last)| *last = after.clone())
+                    .or_insert_with(|| (before.clone(), after.clone()));
+            }
+        }
+        Some(net)
+    }
+}
+
+/// `.meta` files under Assets/, as (absolute, project-relative) paths.
+fn meta_files(root: &Path) -> Vec<(PathBuf, String)> {
+    let filter = DirFilter::for_project(root, &[]);
+    crate::safety::walk_dir(root.join("Assets"))
+        .into_iter()
+        .filter_entry(|e| filter.keeps(e))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "meta"))
+        .map(|e| {
+            let relative = e.path().strip_prefix(root).unwrap_or(e.path()).to_string_lossy().replace('\\', "/");
+            (e.into_path(), relative)
+        })
+        .collect()
+}
+
+/// Load the database, re-read changed `.meta` files and journal the map's differences.
+fn refresh(root: &Path) -> Result<GuidDb, String> {
+    let db_path = root.join(GUID_DB_PATH);
+    let mut db = GuidDb::load(&db_path).unwrap_or_else(GuidDb::fresh);
+    let before = db.map();
+
+    let metas = meta_files(root);
+    let stale = metas.len() != db.metas.len() || metas.iter().any(|(_, rel)| !db.metas.contains_key(rel));
+    let updated: Vec<(String, MetaEntry)> = metas.par_iter()
+        .filter_map(|(file, relative)| {
+            let (mtime_ms, size_bytes) = source_stamp(file)?;
+            let fresh = db.metas.get(relative).is_some_and(|m| m.mtime_ms == mtime_ms && m.size_bytes == size_bytes);
+            if fresh {
+                return None;
+            }
+            let guid = common::read_unity_file(file).ok()
+                .and_then(|content| META_GUID_RE.captures(&content).map(|c| c[1].to_string()));
+            Some((relative.clone(), MetaEntry { mtime_ms, size_bytes, guid }))
+        })
+        .collect();
+    if !stale && updated.is_empty() && db_path.is_file() {
+        return Ok(db);
+    }
+
+    let present: std::collections::HashSet<&str> = metas.iter().map(|(_, rel)| rel.as_str()).collect();
+    db.metas.retain(|path, _| present.contains(path.as_str()));
+    db.metas.extend(updated);
+
+    let after = db.map();
+    let mut changes = BTreeMap::new();
+    for (guid, path) in &before {
+        if after.get(guid) != Some(path) {
+            changes.insert(guid.clone(), (Some(path.clone()), after.get(guid).cloned()));
+        }
+    }
+    for (guid, path) in &after {
+        if !before.contains_key(guid) {
+            changes.insert(guid.clone(), (None, Some(path.clone())));
+        }
+    }
+    if !changes.is_empty() {
+        db.token += 1;
+        db.journal.push(JournalEntry { token: db.token, changes });
+        let excess = db.journal.len().saturating_sub(MAX_JOURNAL);
+        db.journal.drain(..excess);
+    }
+    db.save(&db_path)?;
+    Ok(db)
+}
+
+/// Added, removed and moved GUID → path mappings of a project's Assets/ since `since_token`
+/// (from a previous call). Without a token, or with one the database cannot answer (another
+/// epoch, or older than its journal), `full_resync` is set and `added` holds the whole map.
+/// The database is refreshed first, re-reading only changed `.meta` files.
+#[napi]
+pub fn get_guid_changes(project_root: String, since_token: Option<String>) -> GuidChanges {
+    let _call = crate::telemetry::call("get_guid_changes");
+    let mut result = GuidChanges {
+        token: String::new(),
+        full_resync: true,
+        added: Vec::new(),
+        removed: Vec::new(),
+        modified: Vec::new(),
+        error: None,
+    };
+    let root = Path::new(&project_root);
+    if !root.join("Assets").is_dir() {
+        result.error = Some(format!("No Assets/ folder in {}", project_root));
+        return result;
+    }
+    let db = match refresh(root) {
+        Ok(db) => db,
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+    result.token = db.token_string();
+
+    let since = since_token.as_deref()
+        .and_then(|token| token.rsplit_once('-'))
+        .filter(|(epoch, _)| *epoch == db.epoch)
+        .and_then(|(_, n)| n.parse::<u64>().ok());
+    let Some(net) = since.and_then(|since| db.changes_since(since)) else {
+        result.added = db.map().into_iter().map(|(guid, path)| GuidMapping { guid, path }).collect();
+        return result;
+    };
+
+    result.full_resync = false;
+    for (guid, (before, after)) in net {
+        match (before, after) {
+            (None, Some(path)) => result.added.push(GuidMapping { guid, path }),
+            (Some(path), None) => result.removed.push(GuidMapping { guid, path }),
+            (Some(previous_path), Some(path)) if previous_path != path => {
+                result.modified.push(GuidPathChange { guid, previous_path, path });
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_meta(root: &Path, asset: &str, guid: &str) {
+        let path = root.join(format!("{}.meta", asset));
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("fileFormatVersion: 2\nguid: {}\n", guid)).unwrap();
+    }
+
+    #[test]
+    fn test_guid_changes_since_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let (a, b, c) = ("a".repeat(32), "b".repeat(32), "c".repeat(32));
+        write_meta(root, "Assets/Player.prefab", &a);
+        write_meta(root, "Assets/Enemy.prefab", &b);
+        let project = root.to_string_lossy().to_string();
+
+        let first = get_guid_changes(project.clone(), None);
+        assert!(first.full_resync && first.error.is_none());
+        assert_eq!(first.added.len(), 2);
+
+        let unchanged = get_guid_changes(project.clone(), Some(first.token.clone()));
+        assert!(!unchanged.full_resync);
+        assert_eq!(unchanged.token, first.token);
+        assert!(unchanged.added.is_empty() && unchanged.removed.is_empty() && unchanged.modified.is_empty());
+
+        fs::remove_file(root.join("Assets/Enemy.prefab.meta")).unwrap();
+        write_meta(root, "Assets/Boss.prefab", &c);
+        let second = get_guid_changes(project.clone(), Some(first.token.clone()));
+        assert_eq!(second.added, vec![GuidMapping { guid: c.clone(), path: "Assets/Boss.prefab".to_string() }]);
+        assert_eq!(second.removed, vec![GuidMapping { guid: b.clone(), path: "Assets/Enemy.prefab".to_string() }]);
+
+        fs::rename(root.join("Assets/Player.prefab.meta"), root.join("Assets/Hero.prefab.meta")).unwrap();
+        let third = get_guid_changes(project.clone(), Some(second.token.clone()));
+        assert_eq!(third.modified, vec![GuidPathChange { guid: a.clone(), previous_path: "Assets/Player.prefab".to_string(), path: "Assets/Hero.prefab".to_string() }]);
+
+        // Changes compose across several syncs
+        let combined = get_guid_changes(project.clone(), Some(first.token.clone()));
+        assert_eq!((combined.added.len(), combined.removed.len(), combined.modified.len()), (1, 1, 1));
+
+        let foreign = get_guid_changes(project, Some("0-1".to_string()));
+        assert!(foreign.full_resync);
+        assert_eq!(foreign.added.len(), 2);
+    }
+}
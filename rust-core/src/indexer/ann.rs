@@ -0,0 +1,409 @@
+//! Approximate nearest-neighbor index over chunk embeddings (HNSW), persisted next to the
+//! chunk store so it is loaded, not rebuilt, on process start.
+//!
+//! The file records the embedding model and dimension count it was built with; loading it for
+//! a different model or dimension count yields an empty index, since vectors from different
+//! models are not comparable. Vectors are L2-normalized, so distance is `1 - cosine`.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Bumped whenever the file layout or graph construction changes; older files are rebuilt.
+pub const ANN_FORMAT_VERSION: u32 = 1;
+
+/// Neighbors kept per node on upper layers (twice this on layer 0).
+const DEFAULT_M: usize = 16;
+const DEFAULT_EF_CONSTRUCTION: usize = 100;
+/// Candidate list size of a query, raised to the requested result count if smaller.
+pub const DEFAULT_EF_SEARCH: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Node {
+    id: String,
+    vector: Vec<f32>,
+    /// Neighbor node indexes per layer, layer 0 first
+    layers: Vec<Vec<u32>>,
+    /// Replaced or removed: still routes searches, never returned
+    deleted: bool,
+}
+
+/// HNSW graph of embeddings for one model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnIndex {
+    version: u32,
+    pub model: String,
+    pub dimensions: usize,
+    m: usize,
+    ef_construction: usize,
+    entry: Option<u32>,
+    nodes: Vec<Node>,
+    #[serde(skip)]
+    by_id: HashMap<String, u32>,
+}
+
+/// A distance paired with a node, ordered by distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate(f32, u32);
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|v| v / norm).collect()
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>()
+}
+
+impl AnnIndex {
+    pub fn new(model: &str, dimensions: usize) -> Self {
+        AnnIndex {
+            version: ANN_FORMAT_VERSION,
+            model: model.to_string(),
+            dimensions,
+            m: DEFAULT_M,
+            ef_construction: DEFAULT_EF_CONSTRUCTION,
+            entry: None,
+            nodes: Vec::new(),
+            by_id: HashMap::new(),
+        }
+    }
+
+    /// The index stored at `path`, whatever model it was built with. None when the file is
+    /// missing, unreadable, from another format version or not a consistent graph (a node
+    /// index out of range, a node without layers, a vector of the wrong size).
+    pub fn load(path: &Path) -> Option<Self> {
+        let text = crate::safety::read_to_string(path).ok()?;
+        let mut index: AnnIndex = serde_json::from_str(&text).ok()?;
+        if index.version != ANN_FORMAT_VERSION || !index.is_consistent() {
+            return None;
+        }
+        index.by_id = index.nodes.iter().enumerate()
+            .filter(|(_, n)| !n.deleted)
+            .map(|(i, n)| (n.id.clone(), i as u32))
+            .collect();
+        Some(index)
+    }
+
+    /// Every node index in range and every node searchable, so graph walks cannot panic.
+    fn is_consistent(&self) -> bool {
+        let count = self.nodes.len();
+        self.entry.is_none_or(|e| (e as usize) < count)
+            && self.nodes.iter().all(|node| {
+                !node.layers.is_empty()
+                    && node.vector.len() == self.dimensions
+                    && node.layers.iter().flatten().all(|&n| (n as usize) < count)
+            })
+    }
+
+    /// The index stored at `path` if it was built for `model` and `dimensions`, else an empty
+    /// one (which replaces the stored index on the next save).
+    pub fn load_for(path: &Path, model: &str, dimensions: usize) -> Self {
+        Self::load(path)
+            .filter(|index| index.model == model && index.dimensions == dimensions)
+            .unwrap_or_else(|| Self::new(model, dimensions))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        crate::common::atomic::write_atomic(path, json).map_err(|e| e.to_string())
+    }
+
+    /// Live (searchable) vectors.
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.by_id.contains_key(id)
+    }
+
+    /// Ids of the live vectors.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.by_id.keys().map(String::as_str)
+    }
+
+    fn max_neighbors(&self, layer: usize) -> usize {
+        if layer == 0 { self.m * 2 } else { self.m }
+    }
+
+    /// Layer of a new node: exponentially distributed, derived from the id so rebuilding the
+    /// same data yields the same graph.
+    fn level_for(&self, id: &str) -> usize {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        let unit = (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+        let ml = 1.0 / (self.m as f64).ln();
+        (-(1.0 - unit).ln() * ml).floor() as usize
+    }
+
+    fn top_layer(&self) -> usize {
+        self.entry.map_or(0, |e| self.nodes[e as usize].layers.len() - 1)
+    }
+
+    /// Best `ef` nodes of one layer reachable from `entry`, closest first.
+    fn search_layer(&self, query: &[f32], entry: u32, ef: usize, layer: usize) -> Vec<Candidate> {
+        let start = Candidate(distance(query, &self.nodes[entry as usize].vector), entry);
+        let mut visited: HashSet<u32> = HashSet::from([entry]);
+        let mut candidates = BinaryHeap::from([Reverse(start)]);
+        let mut best = BinaryHeap::from([start]);
+        while let Some(Reverse(current)) = candidates.pop() {
+            if best.peek().is_some_and(|worst| current.0 > worst.0) && best.len() >= ef {
+                break;
+            }
+            for &next in self.nodes[current.1 as usize].layers.get(layer).into_iter().flatten() {
+                if !visited.insert(next) {
+                    continue;
+                }
+                let candidate = Candidate(distance(query, &self.nodes[next as usize].vector), next);
+                if best.len() < ef || best.peek().is_some_and(|worst| candidate.0 < worst.0) {
+                    candidates.push(Reverse(candidate));
+                    best.push(candidate);
+                    if best.len() > ef {
+                        best.pop();
+                    }
+                }
+            }
+        }
+        best.into_sorted_vec()
+    }
+
+    /// Add or replace the vector of `id`.
+    pub fn insert(&mut self, id: &str, vector: &[f32]) -> Result<(), String> {
+        if vector.len() != self.dimensions {
+            return Err(format!("Embedding for {} has {} dimensions, index expects {}", id, vector.len(), self.dimensions));
+        }
+        self.remove(id);
+        let vector = normalized(vector);
+        let node = self.nodes.len() as u32;
+        let level = self.level_for(id);
+        self.nodes.push(Node { id: id.to_string(), vector: vector.clone(), layers: vec![Vec::new(); level + 1], deleted: false });
+        self.by_id.insert(id.to_string(), node);
+
+        let Some(mut entry) = self.entry else {
+            self.entry = Some(node);
+            return Ok(());
+        };
+        let top = self.top_layer();
+        for layer in (level + 1..=top).rev() {
+            entry = self.search_layer(&vector, entry, 1, layer)[0].1;
+        }
+        for layer in (0..=level.min(top)).rev() {
+            let found = self.search_layer(&vector, entry, self.ef_construction, layer);
+            let limit = self.max_neighbors(layer);
+            let neighbors: Vec<u32> = found.iter().map(|c| c.1).take(limit).collect();
+            for &neighbor in &neighbors {
+                let links = &mut self.nodes[neighbor as usize].layers[layer];
+                links.push(node);
+                if links.len() > limit {
+                    self.prune(neighbor, layer, limit);
+                }
+            }
+            self.nodes[node as usize].layers[layer] = neighbors;
+            entry = found[0].1;
+        }
+        if level > top {
+            self.entry = Some(node);
+        }
+        Ok(())
+    }
+
+    /// Keep the `limit` closest links of a node on one layer.
+    fn prune(&mut self, node: u32, layer: usize, limit: usize) {
+        let origin = &self.nodes[node as usize].vector;
+        let mut links: Vec<Candidate> = self.nodes[node as usize].layers[layer].iter()
+            .map(|&n| Candidate(distance(origin, &self.nodes[n as usize].vector), n))
+            .collect();
+        links.sort();
+        self.nodes[node as usize].layers[layer] = links.into_iter().take(limit).map(|c| c.1).collect();
+    }
+
+    /// Stop returning `id`. Its node keeps routing searches until the next rebuild.
+    pub fn remove(&mut self, id: &str) -> bool {
+        match self.by_id.remove(id) {
+            Some(node) => {
+                self.nodes[node as usize].deleted = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removed nodes still in the graph.
+    pub fn deleted_count(&self) -> usize {
+        self.nodes.len() - self.by_id.len()
+    }
+
+    /// Rebuild the graph from the live vectors only.
+    pub fn rebuild(&mut self) {
+        let live: Vec<Node> = std::mem::take(&mut self.nodes).into_iter().filter(|n| !n.deleted).collect();
+        self.entry = None;
+        self.by_id.clear();
+        for node in live {
+            // Stored vectors are already normalized and of the right size
+            let _ = self.insert(&node.id, &node.vector);
+        }
+    }
+
+    /// Up to `limit` nearest ids with their cosine similarity, most similar first.
+    pub fn search(&self, query: &[f32], limit: usize, ef: usize) -> Vec<(String, f64)> {
+        let Some(mut entry) = self.entry.filter(|_| query.len() == self.dimensions) else { return Vec::new() };
+        let query = normalized(query);
+        for layer in (1..=self.top_layer()).rev() {
+            entry = self.search_layer(&query, entry, 1, layer)[0].1;
+        }
+        // Deleted nodes take candidate slots; widen the search by their share
+        let ef = ef.max(limit) + self.deleted_count().min(ef.max(limit));
+        self.search_layer(&query, entry, ef, 0).into_iter()
+            .filter(|c| !self.nodes[c.1 as usize].deleted)
+            .take(limit)
+            .map(|c| (self.nodes[c.1 as usize].id.clone(), f64::from(1.0 - c.0)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random vectors.
+    fn vectors(count: usize, dimensions: usize) -> Vec<Vec<f32>> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..count)
+            .map(|_| {
+                (0..dimensions)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        (state % 2000) as f32 / 1000.0 - 1.0
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn exact_nearest(data: &[Vec<f32>], query: &[f32], k: usize) -> Vec<String> {
+        let query = normalized(query);
+        let mut scored: Vec<(f32, usize)> = data.iter().enumerate().map(|(i, v)| (distance(&query, &normalized(v)), i)).collect();
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+        scored.into_iter().take(k).map(|(_, i)| format!("chunk_{}", i)).collect()
+    }
+
+    #[test]
+    fn test_search_recall() {
+        let data = vectors(500, 16);
+        let mut index = AnnIndex::new("test-model", 16);
+        for (i, v) in data.iter().enumerate() {
+            index.insert(&format!("chunk_{}", i), v).unwrap();
+        }
+        assert_eq!(index.len(), 500);
+
+        let queries = vectors(520, 16).split_off(500);
+        let mut hits = 0;
+        for query in &queries {
+            let expected = exact_nearest(&data, query, 10);
+            let found: Vec<String> = index.search(query, 10, DEFAULT_EF_SEARCH).into_iter().map(|(id, _)| id).collect();
+            hits += found.iter().filter(|id| expected.contains(id)).count();
+        }
+        assert!(hits >= 180, "recall@10 too low: {}/200", hits);
+
+        // A stored vector is its own nearest neighbor
+        let (id, score) = index.search(&data[42], 1, DEFAULT_EF_SEARCH).remove(0);
+        assert_eq!(id, "chunk_42");
+        assert!((score - 1.0).abs() < 1e-5);
+        assert!(index.insert("bad", &[1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_remove_replace_and_rebuild() {
+        let data = vectors(50, 8);
+        let mut index = AnnIndex::new("m", 8);
+        for (i, v) in data.iter().enumerate() {
+            index.insert(&format!("chunk_{}", i), v).unwrap();
+        }
+        assert!(index.remove("chunk_3"));
+        assert!(index.search(&data[3], 5, DEFAULT_EF_SEARCH).iter().all(|(id, _)| id != "chunk_3"));
+        index.insert("chunk_4", &data[10]).unwrap();
+        assert_eq!((index.len(), index.deleted_count()), (49, 2));
+        index.rebuild();
+        assert_eq!((index.len(), index.deleted_count()), (49, 0));
+        let top: Vec<String> = index.search(&data[10], 2, DEFAULT_EF_SEARCH).into_iter().map(|(id, _)| id).collect();
+        assert!(top.contains(&"chunk_4".to_string()) && top.contains(&"chunk_10".to_string()));
+    }
+
+    #[test]
+    fn test_persistence_is_tied_to_model_and_dimensions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.ann.json");
+        let data = vectors(20, 4);
+        let mut index = AnnIndex::new("all-MiniLM-L6-v2", 4);
+        for (i, v) in data.iter().enumerate() {
+            index.insert(&format!("chunk_{}", i), v).unwrap();
+        }
+        index.save(&path).unwrap();
+
+        let loaded = AnnIndex::load_for(&path, "all-MiniLM-L6-v2", 4);
+        assert_eq!(loaded.len(), 20);
+        assert_eq!(loaded.search(&data[7], 3, DEFAULT_EF_SEARCH), index.search(&data[7], 3, DEFAULT_EF_SEARCH));
+        assert!(AnnIndex::load_for(&path, "other-model", 4).is_empty());
+        assert!(AnnIndex::load_for(&path, "all-MiniLM-L6-v2", 8).is_empty());
+
+        std::fs::write(&path, std::fs::read_to_string(&path).unwrap().replacen("\"version\":1", "\"version\":0", 1)).unwrap();
+        assert!(AnnIndex::load(&path).is_none());
+    }
+
+    #[test]
+    fn test_load_rejects_out_of_range_indices() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.ann.json");
+        let mut index = AnnIndex::new("m", 4);
+        for (i, v) in vectors(10, 4).iter().enumerate() {
+            index.insert(&format!("chunk_{}", i), v).unwrap();
+        }
+        index.save(&path).unwrap();
+        assert!(AnnIndex::load(&path).is_some());
+
+        let mut corrupt = index.clone();
+        corrupt.nodes[3].layers[0].push(99);
+        corrupt.save(&path).unwrap();
+        assert!(AnnIndex::load(&path).is_none());
+        // A corrupt file yields an empty index to rebuild instead of a panic on search
+        let rebuilt = AnnIndex::load_for(&path, "m", 4);
+        assert!(rebuilt.is_empty() && rebuilt.search(&[1.0, 0.0, 0.0, 0.0], 3, DEFAULT_EF_SEARCH).is_empty());
+
+        let mut corrupt = index.clone();
+        corrupt.entry = Some(10);
+        corrupt.save(&path).unwrap();
+        assert!(AnnIndex::load(&path).is_none());
+
+        let mut corrupt = index;
+        corrupt.nodes[0].layers.clear();
+        corrupt.save(&path).unwrap();
+        assert!(AnnIndex::load(&path).is_none());
+    }
+}
@@ -1,9 +1,14 @@
 use regex::Regex;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
 
 use crate::common::{Chunk, ChunkMetadata, ChunkType};
+use super::markup::fence_language;
 use super::tokenizer::estimate_tokens;
 
+/// An ATX heading line: `## Title ##`
+static HEADING_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^ {0,3}(#{1,6})[ \t]+(.*?)(?:[ \t]+#+)?[ \t]*$").unwrap());
+
 static CHUNK_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 fn generate_id() -> String {
@@ -15,11 +20,142 @@ fn generate_id() -> String {
     format!("chunk_{}_{}", timestamp, count)
 }
 
+/// Document metadata from a markdown file's YAML frontmatter.
+#[derive(Debug, Default, PartialEq)]
+pub struct Frontmatter {
+    pub title: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub version: Option<String>,
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value.strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+impl Frontmatter {
+    /// Split a leading `---` ... `---` frontmatter block off `content`. Returns the parsed
+    /// metadata (empty when there is no block) and the remaining body.
+    pub fn split(content: &str) -> (Frontmatter, &str) {
+        let text = content.trim_start_matches('\u{feff}');
+        let Some(rest) = text.strip_prefix("---\n").or_else(|| text.strip_prefix("---\r\n")) else {
+            return (Frontmatter::default(), content);
+        };
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            if matches!(line.trim_end(), "---" | "...") {
+                let body = &rest[offset + line.len()..];
+                return (Self::parse(&rest[..offset]), body);
+            }
+            offset += line.len();
+        }
+        // No closing delimiter: a horizontal rule, not frontmatter
+        (Frontmatter::default(), content)
+    }
+
+    /// Top-level `title`, `tags` (flow list, block list or comma-separated) and
+    /// `unity_version`/`version` keys; anything else is ignored.
+    fn parse(yaml: &str) -> Frontmatter {
+        let mut frontmatter = Frontmatter::default();
+        let mut lines = yaml.lines().peekable();
+        while let Some(line) = lines.next() {
+            let Some((key, value)) = line.split_once(':').filter(|_| !line.starts_with([' ', '\t', '-', '#'])) else { continue };
+            let value = value.trim();
+            match key.trim() {
+                "title" => frontmatter.title = Some(unquote(value).to_string()).filter(|t| !t.is_empty()),
+                "unity_version" => frontmatter.version = Some(unquote(value).to_string()).filter(|v| !v.is_empty()),
+                "version" if frontmatter.version.is_none() => frontmatter.version = Some(unquote(value).to_string()).filter(|v| !v.is_empty()),
+                "tags" => {
+                    let mut tags: Vec<String> = if value.is_empty() {
+                        let mut items = Vec::new();
+                        while let Some(item) = lines.peek().and_then(|l| l.trim_start().strip_prefix('-')) {
+                            items.push(unquote(item).to_string());
+                            lines.next();
+                        }
+                        items
+                    } else {
+                        let list = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')).unwrap_or(value);
+                        list.split(',').map(|t| unquote(t).to_string()).collect()
+                    };
+                    tags.retain(|t| !t.is_empty());
+                    frontmatter.tags = Some(tags).filter(|t| !t.is_empty());
+                }
+                _ => {}
+            }
+        }
+        frontmatter
+    }
+}
+
+/// A fenced code block: its byte range in the document, body text and language.
+struct CodeFence<'a> {
+    start: usize,
+    end: usize,
+    body: &'a str,
+    language: Option<String>,
+}
+
+/// Language of a fence info string (```` ```cs title="Example.cs" ````): the first word, minus
+/// pandoc-style `{.lang}` braces, with aliases normalized (cs → csharp, cg → hlsl, yml → yaml).
+fn info_language(info: &str) -> Option<String> {
+    let word = info.split_whitespace().next()?.trim_start_matches(['{', '.']).trim_end_matches('}');
+    let word = word.to_ascii_lowercase();
+    match fence_language(&word) {
+        "" if word.is_empty() => None,
+        "" => Some(word),
+        known => Some(known.to_string()),
+    }
+}
+
+/// Fenced code blocks (``` or ~~~, up to three spaces of indent). A block closes at a fence
+/// of the same character at least as long as the opening one, or at the end of the document.
+fn code_fences(content: &str) -> Vec<CodeFence<'_>> {
+    let mut fences = Vec::new();
+    let mut open: Option<(usize, usize, char, usize, Option<String>)> = None;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        let indent = trimmed.len() - trimmed.trim_start_matches(' ').len();
+        let rest = &trimmed[indent..];
+        let Some(marker) = rest.chars().next().filter(|c| indent <= 3 && (*c == '`' || *c == '~')) else { continue };
+        let run = rest.len() - rest.trim_start_matches(marker).len();
+        if run < 3 {
+            continue;
+        }
+        match open.as_ref() {
+            None => {
+                let info = &rest[run..];
+                // A backtick fence's info string cannot contain backticks (that is inline code)
+                if marker == '`' && info.contains('`') {
+                    continue;
+                }
+                open = Some((line_start, offset, marker, run, info_language(info)));
+            }
+            Some(&(start, body_start, open_marker, open_run, _)) => {
+                if marker == open_marker && run >= open_run && rest[run..].trim().is_empty() {
+                    let language = open.take().and_then(|(.., language)| language);
+                    fences.push(CodeFence { start, end: offset, body: &content[body_start..line_start], language });
+                }
+            }
+        }
+    }
+    if let Some((start, body_start, _, _, language)) = open {
+        fences.push(CodeFence { start, end: content.len(), body: &content[body_start..], language });
+    }
+    fences
+}
+
 pub struct MarkdownChunker;
 
 impl MarkdownChunker {
-    /// Chunk markdown content into searchable pieces
+    /// Chunk markdown content into searchable pieces. YAML frontmatter is not chunked; its
+    /// title, tags and version go into every chunk's metadata.
     pub fn chunk_markdown(content: &str, file_path: &str) -> Vec<Chunk> {
+        let (frontmatter, content) = Frontmatter::split(content);
         let mut chunks = Vec::new();
 
         // Extract code blocks
@@ -29,43 +165,51 @@ impl MarkdownChunker {
         let prose_content = Self::remove_code_blocks(content);
         chunks.extend(Self::chunk_prose(&prose_content, file_path));
 
+        for chunk in &mut chunks {
+            chunk.metadata.title = frontmatter.title.clone();
+            chunk.metadata.tags = frontmatter.tags.clone();
+            chunk.metadata.version = frontmatter.version.clone();
+        }
         chunks
     }
 
+    /// Chunk the text of a paged document (a PDF manual), recording each chunk's 1-based page
+    pub fn chunk_pages(pages: &[String], file_path: &str) -> Vec<Chunk> {
+        pages.iter()
+            .enumerate()
+            .flat_map(|(i, text)| {
+                Self::chunk_markdown(text, file_path).into_iter().map(move |mut chunk| {
+                    chunk.metadata.page = Some(i as u32 + 1);
+                    chunk
+                })
+            })
+            .filter(|chunk| !chunk.content.is_empty())
+            .collect()
+    }
+
     /// Extract code blocks from markdown
     fn extract_code_blocks(content: &str, file_path: &str) -> Vec<Chunk> {
-        let pattern = Regex::new(r"```(?:csharp|javascript|typescript|cs)?\n([\s\S]+?)```")
-            .expect("Invalid regex");
-
-        pattern
-            .captures_iter(content)
-            .map(|cap| {
-                let code_content = cap.get(1).map_or("", |m| m.as_str());
-                let match_start = cap.get(0).map_or(0, |m| m.start());
-
-                let language = if content[..match_start + 3].ends_with("csharp")
-                    || content[..match_start + 3].ends_with("cs")
-                {
-                    Some("csharp".to_string())
-                } else if content[..match_start + 3].ends_with("javascript") {
-                    Some("javascript".to_string())
-                } else if content[..match_start + 3].ends_with("typescript") {
-                    Some("typescript".to_string())
-                } else {
-                    None
-                };
-
+        code_fences(content)
+            .into_iter()
+            .filter(|fence| !fence.body.trim().is_empty())
+            .map(|fence| {
+                let (section, heading_level) = Self::section_at(content, fence.start);
                 Chunk {
                     id: generate_id(),
-                    content: code_content.to_string(),
-                    tokens: estimate_tokens(code_content),
+                    content: fence.body.to_string(),
+                    tokens: estimate_tokens(fence.body),
                     chunk_type: ChunkType::Code,
                     metadata: ChunkMetadata {
                         file_path: file_path.to_string(),
-                        section: Self::extract_section_title(content, match_start),
-                        language,
+                        section,
+                        heading_level,
+                        language: fence.language,
                         unity_class: None,
                         unity_method: None,
+                        page: None,
+                        title: None,
+                        tags: None,
+                        version: None,
                     },
                 }
             })
@@ -74,23 +218,38 @@ impl MarkdownChunker {
 
     /// Remove code blocks from content
     fn remove_code_blocks(content: &str) -> String {
-        let pattern = Regex::new(r"```[\s\S]+?```").expect("Invalid regex");
-        pattern.replace_all(content, "").to_string()
+        let mut prose = String::with_capacity(content.len());
+        let mut last = 0;
+        for fence in code_fences(content) {
+            prose.push_str(&content[last..fence.start]);
+            last = fence.end;
+        }
+        prose.push_str(&content[last..]);
+        prose
     }
 
-    /// Extract section title from heading before position
-    fn extract_section_title(content: &str, position: usize) -> Option<String> {
-        let before = &content[..position];
-        let pattern = Regex::new(r"#{2,3}\s+(.+)$").ok()?;
-
-        // Find the last heading before this position
-        for line in before.lines().rev() {
-            if let Some(caps) = pattern.captures(line) {
-                return caps.get(1).map(|m| m.as_str().trim().to_string());
+    /// Heading trail to `position` ("Physics > Rigidbody > AddForce") and the level of its
+    /// last heading. Headings inside code fences do not count.
+    fn section_at(content: &str, position: usize) -> (Option<String>, Option<u32>) {
+        let fences = code_fences(content);
+        let mut trail: Vec<(u32, &str)> = Vec::new();
+        let mut offset = 0;
+        for line in content[..position].split_inclusive('\n') {
+            let line_start = offset;
+            offset += line.len();
+            if fences.iter().any(|f| (f.start..f.end).contains(&line_start)) {
+                continue;
+            }
+            let Some(caps) = HEADING_RE.captures(line.trim_end()) else { continue };
+            let level = caps[1].len() as u32;
+            let title = caps.get(2).map_or("", |m| m.as_str().trim());
+            while trail.last().is_some_and(|(l, _)| *l >= level) {
+                trail.pop();
             }
+            trail.push((level, title));
         }
-
-        None
+        let section = trail.iter().map(|(_, title)| *title).collect::<Vec<_>>().join(" > ");
+        (Some(section).filter(|s| !s.is_empty()), trail.last().map(|(level, _)| *level))
     }
 
     /// Chunk prose content by sections
@@ -113,8 +272,11 @@ impl MarkdownChunker {
             sections.push((content[last_end..].to_string(), last_end));
         }
 
-        for (section_text, index) in sections {
+        for (section_text, start) in sections {
             let tokens = estimate_tokens(&section_text);
+            // End of the heading line, so the section's own heading is the last one before it
+            let index = start + section_text.find('\n').unwrap_or(section_text.len());
+            let (section, heading_level) = Self::section_at(content, index);
 
             if tokens <= 1024 {
                 // Small enough to be one chunk
@@ -125,10 +287,15 @@ impl MarkdownChunker {
                     chunk_type: ChunkType::Prose,
                     metadata: ChunkMetadata {
                         file_path: file_path.to_string(),
-                        section: Self::extract_section_title(content, index),
+                        section,
+                        heading_level,
                         language: None,
                         unity_class: None,
                         unity_method: None,
+                        page: None,
+                        title: None,
+                        tags: None,
+                        version: None,
                     },
                 });
             } else {
@@ -148,6 +315,7 @@ impl MarkdownChunker {
         position: usize,
     ) -> Vec<Chunk> {
         let mut chunks = Vec::new();
+        let (section, heading_level) = Self::section_at(full_content, position);
         // Split after sentence-ending punctuation followed by whitespace.
         // Rust regex doesn't support lookbehind, so we find boundaries manually.
         let boundary = Regex::new(r"[.!?]\s+").expect("Invalid regex");
@@ -178,10 +346,15 @@ impl MarkdownChunker {
                         chunk_type: ChunkType::Prose,
                         metadata: ChunkMetadata {
                             file_path: file_path.to_string(),
-                            section: Self::extract_section_title(full_content, position),
+                            section: section.clone(),
+                            heading_level,
                             language: None,
                             unity_class: None,
                             unity_method: None,
+                            page: None,
+                            title: None,
+                            tags: None,
+                            version: None,
                         },
                     });
                 }
@@ -201,10 +374,15 @@ impl MarkdownChunker {
                 chunk_type: ChunkType::Prose,
                 metadata: ChunkMetadata {
                     file_path: file_path.to_string(),
-                    section: Self::extract_section_title(full_content, position),
+                    section,
+                    heading_level,
                     language: None,
                     unity_class: None,
                     unity_method: None,
+                    page: None,
+                    title: None,
+                    tags: None,
+                    version: None,
                 },
             });
         }
@@ -268,6 +446,7 @@ It should be chunked properly.
         assert!(!code_chunks.is_empty(), "Should extract at least one code chunk");
         // Code content should contain the function
         assert!(code_chunks[0].content.contains("Start"));
+        assert_eq!(code_chunks[0].metadata.language.as_deref(), Some("csharp"));
     }
 
     #[test]
@@ -278,6 +457,73 @@ It should be chunked properly.
         assert!(prose_chunks.len() >= 2, "Two sections should produce at least 2 prose chunks, got {}", prose_chunks.len());
     }
 
+    #[test]
+    fn test_chunk_pages_records_page_numbers() {
+        let pages = vec!["Setup\n\nCall Init() first.".to_string(), "\n".to_string(), "Pooling keeps spawns cheap.".to_string()];
+        let chunks = MarkdownChunker::chunk_pages(&pages, "Manual.pdf");
+        let located: Vec<(Option<u32>, &str)> = chunks.iter().map(|c| (c.metadata.page, c.content.as_str())).collect();
+        assert_eq!(located, vec![(Some(1), "Setup\n\nCall Init() first."), (Some(3), "Pooling keeps spawns cheap.")]);
+    }
+
+    #[test]
+    fn test_frontmatter_becomes_metadata() {
+        let content = "---\ntitle: \"Object Pooling\"\ntags: [performance, Spawning]\nunity_version: 2022.3\n---\n\nPooling keeps spawns cheap.\n\n```csharp\nvoid Init() {}\n```\n";
+        let chunks = MarkdownChunker::chunk_markdown(content, "pooling.md");
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].content, "Pooling keeps spawns cheap.", "frontmatter is not prose");
+        for chunk in &chunks {
+            assert_eq!(chunk.metadata.title.as_deref(), Some("Object Pooling"));
+            assert_eq!(chunk.metadata.tags, Some(vec!["performance".to_string(), "Spawning".to_string()]));
+            assert_eq!(chunk.metadata.version.as_deref(), Some("2022.3"));
+        }
+
+        let (block_list, body) = Frontmatter::split("---\ntags:\n  - ui\n  - 'input'\nversion: 6000.0\n...\nBody");
+        assert_eq!(block_list, Frontmatter { title: None, tags: Some(vec!["ui".to_string(), "input".to_string()]), version: Some("6000.0".to_string()) });
+        assert_eq!(body, "Body");
+
+        let rule = "---\nNot frontmatter, just a rule.";
+        assert_eq!(Frontmatter::split(rule), (Frontmatter::default(), rule));
+    }
+
+    #[test]
+    fn test_code_fence_languages() {
+        let content = "## Shader\n\n```shader\nShader \"Custom/Unlit\" {}\n```\n\n```HLSL\nfloat4 frag() : SV_Target { return 1; }\n```\n\n\
+## Data\n\n~~~yml\nkey: value\n~~~\n\n````{.json}\n{\"a\": \"```\"}\n````\n\n```cs title=\"Example.cs\"\nclass A {}\n```\n\n```rust\nfn main() {}\n```\n\n```\nplain\n```\n\nAfter ```inline``` code.\n";
+        let chunks = MarkdownChunker::chunk_markdown(content, "test.md");
+        let code: Vec<(Option<&str>, &str)> = chunks.iter()
+            .filter(|c| c.chunk_type == ChunkType::Code)
+            .map(|c| (c.metadata.language.as_deref(), c.content.trim_end()))
+            .collect();
+        assert_eq!(code, vec![
+            (Some("shaderlab"), "Shader \"Custom/Unlit\" {}"),
+            (Some("hlsl"), "float4 frag() : SV_Target { return 1; }"),
+            (Some("yaml"), "key: value"),
+            (Some("json"), "{\"a\": \"```\"}"),
+            (Some("csharp"), "class A {}"),
+            (Some("rust"), "fn main() {}"),
+            (None, "plain"),
+        ]);
+        let data = chunks.iter().find(|c| c.metadata.language.as_deref() == Some("yaml")).unwrap();
+        assert_eq!(data.metadata.section.as_deref(), Some("Data"));
+        let prose: String = chunks.iter().filter(|c| c.chunk_type == ChunkType::Prose).map(|c| c.content.as_str()).collect();
+        assert!(prose.contains("After ```inline``` code.") && !prose.contains("SV_Target") && !prose.contains("key: value"));
+    }
+
+    #[test]
+    fn test_section_breadcrumb_and_level() {
+        let content = "# Physics\n\nIntro.\n\n## Rigidbody\n\nBodies.\n\n### AddForce\n\n```yaml\n# not a heading\nforce: 1\n```\n\nPushes it.\n\n## Colliders ##\n\nShapes.\n";
+        let chunks = MarkdownChunker::chunk_markdown(content, "physics.md");
+        let located: Vec<(&str, Option<&str>, Option<u32>)> = chunks.iter()
+            .map(|c| (c.content.as_str(), c.metadata.section.as_deref(), c.metadata.heading_level))
+            .collect();
+        assert_eq!(located, vec![
+            ("# not a heading\nforce: 1\n", Some("Physics > Rigidbody > AddForce"), Some(3)),
+            ("Rigidbody\n\nBodies.", Some("Physics > Rigidbody"), Some(2)),
+            ("AddForce\n\n\nPushes it.", Some("Physics > Rigidbody > AddForce"), Some(3)),
+            ("Colliders ##\n\nShapes.", Some("Physics > Colliders"), Some(2)),
+        ]);
+    }
+
     #[test]
     fn test_section_title_in_metadata() {
         // Use two sections so the second one has a heading before it
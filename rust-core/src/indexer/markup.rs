@@ -0,0 +1,479 @@
+//! HTML and reStructuredText to markdown, so the markdown chunker can index Unity's offline
+//! documentation (HTML) and Sphinx-style docs directly.
+//!
+//! Both converters are lexical and aim at what the chunker needs: `##`/`###` headings for
+//! sections, fenced code blocks for examples, and readable prose. Markup they don't understand
+//! is dropped, its text kept.
+
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Extensions `Indexer::index_directory` picks up.
+#[cfg(not(feature = "pdf"))]
+pub const DOCUMENT_EXTENSIONS: &[&str] = &["md", "txt", "html", "htm", "rst"];
+/// Extensions `Indexer::index_directory` picks up.
+#[cfg(feature = "pdf")]
+pub const DOCUMENT_EXTENSIONS: &[&str] = &["md", "txt", "html", "htm", "rst", "pdf"];
+
+static COMMENT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<!--.*?-->|<!\[CDATA\[.*?\]\]>|<![^>]*>|<\?[^>]*>").unwrap());
+static TAG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<(/?)([a-zA-Z][a-zA-Z0-9]*)((?:[^>\x22']|\x22[^\x22]*\x22|'[^']*')*)>").unwrap());
+static CLASS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(?i)\b(?:class|id)\s*=\s*["']([^"']*)["']"#).unwrap());
+static ENTITY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap());
+static BLANK_LINES_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
+
+/// Elements whose content is never documentation.
+const SKIPPED_ELEMENTS: &[&str] = &["head", "script", "style", "noscript", "nav", "header", "footer", "form", "button", "svg", "iframe"];
+/// Class or id words of page chrome in Unity's manual and scripting reference.
+const BOILERPLATE_MARKERS: &[&str] = &["sidebar", "header", "footer", "toolbar", "breadcrumb", "nav", "menu", "feedback", "suggest", "cookie", "version-switch", "lang-switch", "search"];
+/// Elements without a closing tag.
+const VOID_ELEMENTS: &[&str] = &["br", "hr", "img", "input", "meta", "link", "area", "base", "col", "embed", "source", "track", "wbr"];
+
+/// Markdown for a documentation file: HTML and reStructuredText are converted, anything else
+/// is taken as markdown.
+pub fn to_markdown(path: &Path, content: &str) -> String {
+    match path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+        Some("html" | "htm") => html_to_markdown(content),
+        Some("rst") => rst_to_markdown(content),
+        _ => content.to_string(),
+    }
+}
+
+/// Fence language the chunker recognizes for a class list or code-block argument, if any.
+pub(super) fn fence_language(hint: &str) -> &'static str {
+    let hint = hint.to_ascii_lowercase();
+    let words: Vec<&str> = hint.split(|c: char| !c.is_ascii_alphanumeric() && c != '#').collect();
+    let any = |names: &[&str]| words.iter().any(|w| names.contains(w));
+    if any(&["csharp", "cs", "c#", "codeexamplecs"]) {
+        "csharp"
+    } else if any(&["javascript", "js", "codeexamplejs"]) {
+        "javascript"
+    } else if any(&["typescript", "ts"]) {
+        "typescript"
+    } else if any(&["shaderlab", "shader"]) {
+        "shaderlab"
+    } else if any(&["hlsl", "cg", "cginc"]) {
+        "hlsl"
+    } else if any(&["json", "jsonc"]) {
+        "json"
+    } else if any(&["yaml", "yml"]) {
+        "yaml"
+    } else {
+        ""
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    ENTITY_RE.replace_all(text, |caps: &regex::Captures| {
+        let name = &caps[1];
+        let decoded = if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        } else if let Some(dec) = name.strip_prefix('#') {
+            dec.parse().ok().and_then(char::from_u32)
+        } else {
+            match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "ndash" => Some('–'),
+                "mdash" => Some('—'),
+                "hellip" => Some('…'),
+                "copy" => Some('©'),
+                _ => None,
+            }
+        };
+        decoded.map_or_else(|| caps[0].to_string(), |c| c.to_string())
+    }).into_owned()
+}
+
+fn is_boilerplate(name: &str, attributes: &str) -> bool {
+    if SKIPPED_ELEMENTS.contains(&name) {
+        return true;
+    }
+    CLASS_RE.captures_iter(attributes).any(|caps| {
+        caps[1].to_ascii_lowercase()
+            .split(|c: char| c.is_whitespace() || c == '_')
+            .any(|word| BOILERPLATE_MARKERS.iter().any(|m| word == *m || word.starts_with(&format!("{}-", m))))
+    })
+}
+
+/// Convert an HTML page to markdown: page chrome (head, scripts, navigation, sidebars,
+/// headers, footers) dropped, `h1`/`h2` as `##` and deeper headings as `###` sections, `pre`
+/// blocks as fenced code (C# when the class says so, as in Unity's `codeExampleCS`), lists and
+/// table rows on their own lines.
+pub fn html_to_markdown(html: &str) -> String {
+    let html = COMMENT_RE.replace_all(html, "");
+    let mut out = String::new();
+    // (element, nesting of the same element inside it) while inside skipped chrome
+    let mut skipping: Option<(String, u32)> = None;
+    let mut pre_depth = 0u32;
+    let mut last = 0;
+
+    for caps in TAG_RE.captures_iter(&html) {
+        let whole = caps.get(0).unwrap();
+        let text = &html[last..whole.start()];
+        last = whole.end();
+        let closing = !caps[1].is_empty();
+        let name = caps[2].to_ascii_lowercase();
+        let attributes = &caps[3];
+
+        if let Some((skipped, depth)) = skipping.as_mut() {
+            if *skipped == name && !VOID_ELEMENTS.contains(&name.as_str()) && !attributes.trim_end().ends_with('/') {
+                if closing && *depth == 0 {
+                    skipping = None;
+                } else if closing {
+                    *depth -= 1;
+                } else {
+                    *depth += 1;
+                }
+            }
+            continue;
+        }
+
+        push_text(&mut out, text, pre_depth > 0);
+        if !closing && !VOID_ELEMENTS.contains(&name.as_str()) && !attributes.trim_end().ends_with('/') && is_boilerplate(&name, attributes) {
+            skipping = Some((name, 0));
+            continue;
+        }
+
+        match (name.as_str(), closing) {
+            ("h1" | "h2", false) => out.push_str("\n\n## "),
+            ("h3" | "h4" | "h5" | "h6", false) => out.push_str("\n\n### "),
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                if pre_depth == 0 {
+                    let hint = CLASS_RE.captures_iter(attributes).map(|c| c[1].to_string()).collect::<Vec<_>>().join(" ");
+                    out.push_str(&format!("\n\n```{}\n", fence_language(&hint)));
+                }
+                pre_depth += 1;
+            }
+            ("pre", true) if pre_depth > 0 => {
+                pre_depth -= 1;
+                if pre_depth == 0 {
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                }
+            }
+            ("code", _) if pre_depth == 0 => out.push('`'),
+            ("br", _) => out.push('\n'),
+            ("li", false) => out.push_str("\n- "),
+            ("tr", false) => out.push_str("\n|"),
+            ("td" | "th", true) => out.push_str(" |"),
+            ("td" | "th", false) => out.push(' '),
+            ("p" | "div" | "section" | "article" | "main" | "ul" | "ol" | "table" | "tr" | "dl" | "dt" | "dd" | "blockquote" | "hr", _) if pre_depth == 0 => out.push_str("\n\n"),
+            _ => {}
+        }
+    }
+    if skipping.is_none() {
+        push_text(&mut out, &html[last..], pre_depth > 0);
+    }
+    tidy(&out)
+}
+
+/// Append decoded text: verbatim inside `pre`, whitespace collapsed elsewhere.
+fn push_text(out: &mut String, text: &str, preformatted: bool) {
+    let decoded = decode_entities(text);
+    if preformatted {
+        out.push_str(&decoded);
+        return;
+    }
+    let collapsed = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        if decoded.chars().next().is_some_and(char::is_whitespace) && !out.ends_with(char::is_whitespace) {
+            out.push(' ');
+        }
+        return;
+    }
+    if decoded.starts_with(char::is_whitespace) && !out.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    out.push_str(&collapsed);
+    if decoded.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+/// Trim trailing whitespace and leading spaces outside code fences, and squeeze blank lines.
+fn tidy(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            lines.push(line.trim().to_string());
+        } else if in_fence {
+            lines.push(line.trim_end().to_string());
+        } else {
+            lines.push(line.trim().to_string());
+        }
+    }
+    let joined = lines.join("\n");
+    format!("{}\n", BLANK_LINES_RE.replace_all(joined.trim(), "\n\n"))
+}
+
+static RST_ROLE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r":[a-zA-Z:+-]+:`([^`<]*?)\s*(?:<[^>]*>)?`").unwrap());
+static RST_LINK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`([^`<]+?)\s*<[^>]*>`__?").unwrap());
+static RST_LITERAL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"``([^`]+)``").unwrap());
+static RST_DIRECTIVE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\.\.\s+([a-zA-Z][\w-]*)::\s*(.*)$").unwrap());
+
+/// Character of a heading adornment line (`=====`, `-----`, ...), if the line is one.
+fn adornment(line: &str) -> Option<char> {
+    let line = line.trim_end();
+    let first = line.chars().next()?;
+    (line.len() >= 2 && "=-~^\"'`#*+:._".contains(first) && line.chars().all(|c| c == first)).then_some(first)
+}
+
+/// Inline markup: roles (`:class:\`Rigidbody\``) and links reduced to their text, literals
+/// to markdown code spans.
+fn rst_inline(text: &str) -> String {
+    let text = RST_ROLE_RE.replace_all(text, "`$1`");
+    let text = RST_LINK_RE.replace_all(&text, "$1");
+    RST_LITERAL_RE.replace_all(&text, "`$1`").into_owned()
+}
+
+/// Indented block starting at `start` (after optional blank lines), dedented, and the index
+/// of the first line after it.
+fn indented_block(lines: &[&str], start: usize) -> (Vec<String>, usize) {
+    let mut end = start;
+    while end < lines.len() && (lines[end].trim().is_empty() || lines[end].starts_with([' ', '\t'])) {
+        end += 1;
+    }
+    // Trailing blank lines belong to what follows
+    while end > start && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+    let indent = lines[start..end].iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let block = lines[start..end].iter().map(|l| l.get(indent..).unwrap_or("").trim_end().to_string()).collect();
+    (block, end)
+}
+
+/// Convert reStructuredText to markdown: the first two heading styles as `##`, deeper ones as
+/// `###`, `code-block` directives and `::` literal blocks as fenced code, admonitions as
+/// labelled paragraphs; comments, targets, images and other directives without text dropped.
+pub fn rst_to_markdown(rst: &str) -> String {
+    let lines: Vec<&str> = rst.lines().collect();
+    // Heading styles in order of first use: (adornment char, overlined)
+    let mut styles: Vec<(char, bool)> = Vec::new();
+    let mut out = String::new();
+    let mut literal_next = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        // Heading with overline and underline
+        if let (Some(c), Some(title), Some(under)) = (adornment(line), lines.get(i + 1), lines.get(i + 2)) {
+            if !title.trim().is_empty() && adornment(under) == Some(c) && adornment(title).is_none() {
+                out.push_str(&rst_heading(&mut styles, (c, true), title.trim()));
+                i += 3;
+                continue;
+            }
+        }
+        // Heading with underline only
+        if let Some(under) = lines.get(i + 1) {
+            if let Some(c) = adornment(under) {
+                if !trimmed.is_empty() && !line.starts_with([' ', '\t']) && adornment(line).is_none() && under.trim_end().len() >= trimmed.len() {
+                    out.push_str(&rst_heading(&mut styles, (c, false), trimmed));
+                    i += 2;
+                    continue;
+                }
+            }
+        }
+
+        if let Some(caps) = RST_DIRECTIVE_RE.captures(trimmed).filter(|_| !line.starts_with([' ', '\t'])) {
+            let directive = caps[1].to_ascii_lowercase();
+            let argument = caps[2].trim().to_string();
+            let mut body_start = i + 1;
+            // Directive options (`:linenos:`) come before the body
+            while lines.get(body_start).is_some_and(|l| l.trim_start().starts_with(':') && l.starts_with([' ', '\t'])) {
+                body_start += 1;
+            }
+            let (body, end) = indented_block(&lines, body_start);
+            match directive.as_str() {
+                "code-block" | "code" | "sourcecode" => {
+                    out.push_str(&format!("\n```{}\n{}\n```\n\n", fence_language(&argument), body.join("\n").trim_matches('\n')));
+                }
+                "note" | "warning" | "tip" | "important" | "caution" | "danger" | "attention" | "hint" | "seealso" | "deprecated" | "versionadded" | "versionchanged" => {
+                    let mut label = directive.clone();
+                    label[..1].make_ascii_uppercase();
+                    let text = std::iter::once(argument).chain(body).collect::<Vec<_>>().join("\n");
+                    out.push_str(&format!("\n{}: {}\n\n", label, rst_inline(text.trim())));
+                }
+                _ if !body.is_empty() && !matches!(directive.as_str(), "image" | "figure" | "toctree" | "include" | "raw" | "only" | "meta") => {
+                    out.push_str(&format!("\n{}\n\n", rst_inline(body.join("\n").trim())));
+                }
+                _ => {}
+            }
+            i = end.max(i + 1);
+            continue;
+        }
+        // Comments and hyperlink targets
+        if trimmed.starts_with("..") && !line.starts_with([' ', '\t']) {
+            let (_, end) = indented_block(&lines, i + 1);
+            i = end.max(i + 1);
+            continue;
+        }
+
+        if literal_next && line.starts_with([' ', '\t']) && !trimmed.is_empty() {
+            let (body, end) = indented_block(&lines, i);
+            out.push_str(&format!("\n```\n{}\n```\n\n", body.join("\n").trim_matches('\n')));
+            literal_next = false;
+            i = end;
+            continue;
+        }
+        if !trimmed.is_empty() {
+            literal_next = trimmed.ends_with("::");
+        }
+
+        let text = if trimmed == "::" {
+            String::new()
+        } else if let Some(prefix) = trimmed.strip_suffix("::") {
+            // "Example::" reads "Example:"; "Example ::" reads "Example"
+            if prefix.ends_with(char::is_whitespace) { prefix.trim_end().to_string() } else { format!("{}:", prefix) }
+        } else {
+            trimmed.to_string()
+        };
+        out.push_str(&rst_inline(&text));
+        out.push('\n');
+        i += 1;
+    }
+    tidy(&out)
+}
+
+fn rst_heading(styles: &mut Vec<(char, bool)>, style: (char, bool), title: &str) -> String {
+    let level = styles.iter().position(|s| *s == style).unwrap_or_else(|| {
+        styles.push(style);
+        styles.len() - 1
+    });
+    let marker = if level < 2 { "##" } else { "###" };
+    format!("\n{} {}\n\n", marker, rst_inline(title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ChunkType;
+    use crate::indexer::chunker::MarkdownChunker;
+
+    const SCRIPT_REFERENCE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head><title>Unity - Scripting API: Rigidbody.AddForce</title>
+<script>var x = "<h2>not a heading</h2>";</script></head>
+<body>
+<div class="header-wrapper"><div id="header"><a href="/">Unity</a> Manual</div></div>
+<div id="sidebar"><div class="sidebar-wrap"><ul><li><a>Rigidbody</a></li></ul></div></div>
+<div class="content"><div class="section">
+<div class="mb20 clear"><h1 class="heading inherit"><a href="Rigidbody.html">Rigidbody</a>.AddForce</h1></div>
+<div class="subsection"><div class="signature">public void <b>AddForce</b>(<a>Vector3</a> <b>force</b>);</div></div>
+<div class="subsection"><h2>Parameters</h2>
+<table class="list"><tr><td class="name lbl">force</td><td class="desc">Force vector in world coordinates.</td></tr></table></div>
+<div class="subsection"><h2>Description</h2><p>Adds a force to the <code>Rigidbody</code>. Force is applied &amp; continuous&nbsp;along the direction.</p>
+<pre class="codeExampleCS">using UnityEngine;
+
+public class Example : MonoBehaviour
+{
+    void FixedUpdate()
+    {
+        if (speed &lt; 10) rb.AddForce(transform.forward);
+    }
+}</pre></div>
+</div></div>
+<div class="footer-wrapper"><p>Copyright © 2024 Unity Technologies</p></div>
+</body></html>"#;
+
+    #[test]
+    fn test_html_to_markdown_strips_chrome_and_keeps_structure() {
+        let markdown = html_to_markdown(SCRIPT_REFERENCE);
+        assert!(markdown.starts_with("## Rigidbody.AddForce\n"), "{}", markdown);
+        assert!(markdown.contains("public void AddForce(Vector3 force);"));
+        assert!(markdown.contains("## Parameters\n\n| force | Force vector in world coordinates. |"));
+        assert!(markdown.contains("Adds a force to the `Rigidbody`. Force is applied & continuous along the direction."));
+        assert!(markdown.contains("```csharp\nusing UnityEngine;\n\npublic class Example : MonoBehaviour\n{\n    void FixedUpdate()\n"));
+        assert!(markdown.contains("if (speed < 10) rb.AddForce(transform.forward);\n    }\n}\n```"));
+        for chrome in ["not a heading", "Manual", "Copyright", "Scripting API", "sidebar"] {
+            assert!(!markdown.contains(chrome), "{} leaked into {}", chrome, markdown);
+        }
+
+        let chunks = MarkdownChunker::chunk_markdown(&markdown, "ScriptReference/Rigidbody.AddForce.html");
+        let code: Vec<_> = chunks.iter().filter(|c| c.chunk_type == ChunkType::Code).collect();
+        assert_eq!(code.len(), 1);
+        assert_eq!(code[0].metadata.language.as_deref(), Some("csharp"));
+        assert_eq!(code[0].metadata.section.as_deref(), Some("Description"));
+        assert!(chunks.iter().any(|c| c.metadata.section.as_deref() == Some("Parameters") && c.content.contains("Force vector")));
+    }
+
+    #[test]
+    fn test_rst_to_markdown() {
+        let rst = "\
+=========
+Rigidbody
+=========
+
+.. _rigidbody-intro:
+
+Overview
+--------
+
+A :class:`Rigidbody <UnityEngine.Rigidbody>` puts an object under ``Physics`` control.
+See `the manual <https://docs.unity3d.com>`_.
+
+.. note:: Move it in FixedUpdate.
+
+.. code-block:: csharp
+   :linenos:
+
+   void FixedUpdate()
+   {
+       rb.AddForce(Vector3.up);
+   }
+
+Details
+~~~~~~~
+
+Shell example::
+
+    unity -batchmode
+
+.. image:: rigidbody.png
+.. This comment is dropped
+";
+        let markdown = rst_to_markdown(rst);
+        assert_eq!(markdown, "\
+## Rigidbody
+
+## Overview
+
+A `Rigidbody` puts an object under `Physics` control.
+See the manual.
+
+Note: Move it in FixedUpdate.
+
+```csharp
+void FixedUpdate()
+{
+    rb.AddForce(Vector3.up);
+}
+```
+
+### Details
+
+Shell example:
+
+```
+unity -batchmode
+```
+");
+        assert_eq!(to_markdown(Path::new("a/Doc.RST"), rst), markdown);
+        assert_eq!(to_markdown(Path::new("a/Doc.md"), "## Kept"), "## Kept");
+    }
+}
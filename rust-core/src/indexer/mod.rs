@@ -1,16 +1,32 @@
 pub mod chunker;
 pub mod tokenizer;
 pub mod storage;
+pub mod ann;
+pub mod markup;
+pub mod pdf;
 
 use napi_derive::napi;
 use std::fs;
 use std::path::Path;
 use std::time::Instant;
 
-use crate::common::{IndexResult, SearchResult};
+use crate::common::{Chunk, CompactResult, DocSearchFilter, IndexResult, SearchResult, SectionMatch};
 use chunker::MarkdownChunker;
 use storage::IndexStorage;
 
+/// Chunks of a documentation file: PDFs page by page, other formats through markdown.
+/// None when the file can't be read or its text extracted.
+fn chunk_document(path: &Path) -> Option<Vec<Chunk>> {
+    let path_str = path.to_string_lossy().to_string();
+    if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("pdf")) {
+        let pages = pdf::extract_pages(path).ok()?;
+        return Some(MarkdownChunker::chunk_pages(&pages, &path_str));
+    }
+    let content = crate::safety::read_to_string(path).ok()?;
+    let content = markup::to_markdown(path, &content);
+    Some(MarkdownChunker::chunk_markdown(&content, &path_str))
+}
+
 /// High-performance documentation indexer
 #[napi]
 pub struct Indexer {
@@ -29,6 +45,7 @@ impl Indexer {
     /// Index a single file
     #[napi]
     pub fn index_file(&mut self, path: String) -> IndexResult {
+        let _call = crate::telemetry::call("index_file");
         let start = Instant::now();
 
         let file_path = Path::new(&path);
@@ -38,41 +55,46 @@ impl Indexer {
                 total_tokens: 0,
                 files_processed: 0,
                 elapsed_ms: start.elapsed().as_millis() as u32,
+                duplicates_skipped: 0,
             };
         }
 
-        let content = match fs::read_to_string(file_path) {
-            Ok(c) => c,
-            Err(_) => {
+        let chunks = match chunk_document(file_path) {
+            Some(chunks) => chunks,
+            None => {
                 return IndexResult {
                     chunks_indexed: 0,
                     total_tokens: 0,
                     files_processed: 0,
                     elapsed_ms: start.elapsed().as_millis() as u32,
+                    duplicates_skipped: 0,
                 }
             }
         };
-
-        let chunks = MarkdownChunker::chunk_markdown(&content, &path);
         let total_tokens: u32 = chunks.iter().map(|c| c.tokens).sum();
 
+        let mut duplicates_skipped = 0u32;
         for chunk in &chunks {
-            self.storage.store_chunk(chunk.clone());
+            if !self.storage.store_chunk(chunk.clone()) {
+                duplicates_skipped += 1;
+            }
         }
 
         self.storage.save();
 
         IndexResult {
-            chunks_indexed: chunks.len() as u32,
+            chunks_indexed: chunks.len() as u32 - duplicates_skipped,
             total_tokens,
             files_processed: 1,
             elapsed_ms: start.elapsed().as_millis() as u32,
+            duplicates_skipped,
         }
     }
 
     /// Index a directory of files
     #[napi]
     pub fn index_directory(&mut self, path: String) -> IndexResult {
+        let _call = crate::telemetry::call("index_directory");
         let start = Instant::now();
 
         let dir_path = Path::new(&path);
@@ -82,20 +104,23 @@ impl Indexer {
                 total_tokens: 0,
                 files_processed: 0,
                 elapsed_ms: start.elapsed().as_millis() as u32,
+                duplicates_skipped: 0,
             };
         }
 
-        let extensions = ["md", "txt"];
+        let extensions = markup::DOCUMENT_EXTENSIONS;
         let mut total_chunks = 0u32;
         let mut total_tokens = 0u32;
         let mut files_processed = 0u32;
+        let mut duplicates_skipped = 0u32;
 
         self.process_directory(
             dir_path,
-            &extensions,
+            extensions,
             &mut total_chunks,
             &mut total_tokens,
             &mut files_processed,
+            &mut duplicates_skipped,
         );
 
         self.storage.save();
@@ -105,6 +130,7 @@ impl Indexer {
             total_tokens,
             files_processed,
             elapsed_ms: start.elapsed().as_millis() as u32,
+            duplicates_skipped,
         }
     }
 
@@ -115,8 +141,9 @@ impl Indexer {
         total_chunks: &mut u32,
         total_tokens: &mut u32,
         files_processed: &mut u32,
+        duplicates_skipped: &mut u32,
     ) {
-        if let Ok(entries) = fs::read_dir(dir) {
+        if let Ok(entries) = crate::safety::read_dir(dir) {
             for entry in entries.filter_map(|e| e.ok()) {
                 let path = entry.path();
 
@@ -127,19 +154,20 @@ impl Indexer {
                         total_chunks,
                         total_tokens,
                         files_processed,
+                        duplicates_skipped,
                     );
                 } else if let Some(ext) = path.extension() {
                     if extensions.iter().any(|e| ext == *e) {
-                        if let Ok(content) = fs::read_to_string(&path) {
-                            let path_str = path.to_string_lossy().to_string();
-                            let chunks = MarkdownChunker::chunk_markdown(&content, &path_str);
-
+                        if let Some(chunks) = chunk_document(&path) {
                             for chunk in &chunks {
                                 *total_tokens += chunk.tokens;
-                                self.storage.store_chunk(chunk.clone());
+                                if self.storage.store_chunk(chunk.clone()) {
+                                    *total_chunks += 1;
+                                } else {
+                                    *duplicates_skipped += 1;
+                                }
                             }
 
-                            *total_chunks += chunks.len() as u32;
                             *files_processed += 1;
                         }
                     }
@@ -148,10 +176,47 @@ impl Indexer {
         }
     }
 
-    /// Search the index
+    /// Search the index, optionally only documents whose frontmatter title, tags or version match
     #[napi]
-    pub fn search(&self, query: String) -> Vec<SearchResult> {
-        self.storage.keyword_search(&query)
+    pub fn search(&self, query: String, filter: Option<DocSearchFilter>) -> Vec<SearchResult> {
+        let _call = crate::telemetry::call("search");
+        self.storage.keyword_search(&query, filter.as_ref())
+    }
+
+    /// Find documents and sections by title or file path, as a table of contents to
+    /// navigate before pulling chunk content
+    #[napi]
+    pub fn find_sections(&self, query: String) -> Vec<SectionMatch> {
+        let _call = crate::telemetry::call("find_sections");
+        self.storage.section_search(&query)
+    }
+
+    /// Store embeddings of indexed chunks (parallel `ids` and `vectors`) in the approximate
+    /// nearest-neighbor index saved next to the chunk store. Embeddings from a different
+    /// `model` or dimension count replace the stored index.
+    #[napi]
+    pub fn store_embeddings(&mut self, model: String, ids: Vec<String>, vectors: Vec<Vec<f64>>) -> serde_json::Value {
+        let _call = crate::telemetry::call("store_embeddings");
+        if ids.len() != vectors.len() {
+            return serde_json::json!({"error": format!("{} ids but {} vectors", ids.len(), vectors.len()), "is_error": true});
+        }
+        let embeddings = ids.into_iter()
+            .zip(vectors)
+            .map(|(id, vector)| (id, vector.into_iter().map(|v| v as f32).collect()))
+            .collect();
+        match self.storage.store_embeddings(&model, embeddings) {
+            Ok(stored) => serde_json::json!({"stored": stored, "embedded_count": self.storage.embedded_count()}),
+            Err(e) => serde_json::json!({"error": e, "is_error": true}),
+        }
+    }
+
+    /// Chunks whose embeddings (from `model`) are nearest to a query embedding, most similar
+    /// first; score is cosine similarity
+    #[napi]
+    pub fn vector_search(&mut self, model: String, vector: Vec<f64>, limit: Option<u32>) -> Vec<SearchResult> {
+        let _call = crate::telemetry::call("vector_search");
+        let query: Vec<f32> = vector.into_iter().map(|v| v as f32).collect();
+        self.storage.vector_search(&model, &query, limit.unwrap_or(5) as usize)
     }
 
     /// Clear the index
@@ -160,13 +225,33 @@ impl Indexer {
         self.storage.clear();
     }
 
+    /// Merge duplicate chunks, drop chunks whose source file is gone, and rewrite the
+    /// storage file, reporting the space saved
+    #[napi]
+    pub fn compact(&mut self) -> CompactResult {
+        let _call = crate::telemetry::call("compact");
+        let file_size = |path: &Path| fs::metadata(path).map_or(0, |m| crate::common::to_count(m.len() as usize));
+        let bytes_before = file_size(self.storage.storage_path());
+        let (duplicates_removed, orphans_removed) = self.storage.compact();
+        let bytes_after = file_size(self.storage.storage_path());
+        CompactResult {
+            duplicates_removed,
+            orphans_removed,
+            chunks_remaining: self.storage.stats().0 as u32,
+            bytes_before,
+            bytes_after,
+            bytes_saved: bytes_before - bytes_after,
+        }
+    }
+
     /// Get index statistics
     #[napi]
     pub fn stats(&self) -> serde_json::Value {
         let (chunk_count, total_tokens) = self.storage.stats();
         serde_json::json!({
             "chunk_count": chunk_count,
-            "total_tokens": total_tokens
+            "total_tokens": total_tokens,
+            "embedded_count": self.storage.embedded_count()
         })
     }
 }
@@ -205,8 +290,9 @@ mod tests {
 
     #[test]
     fn test_index_nonexistent_file_returns_zero_chunks() {
+        let dir = TempDir::new();
         let mut indexer = Indexer {
-            storage: IndexStorage::new(),
+            storage: IndexStorage::at(dir.path().join("index.json")),
         };
         let result = indexer.index_file("/nonexistent/path/to/file.md".to_string());
         assert_eq!(result.chunks_indexed, 0);
@@ -220,7 +306,7 @@ mod tests {
         fs::write(&file_path, "## Test\n\nThis is test content for indexing.\n").unwrap();
 
         let mut indexer = Indexer {
-            storage: IndexStorage::new(),
+            storage: IndexStorage::at(dir.path().join("index.json")),
         };
         let result = indexer.index_file(file_path.to_string_lossy().to_string());
         assert!(result.chunks_indexed > 0);
@@ -232,13 +318,35 @@ mod tests {
         let dir = TempDir::new();
 
         let mut indexer = Indexer {
-            storage: IndexStorage::new(),
+            storage: IndexStorage::at(dir.path().join("index.json")),
         };
         let result = indexer.index_directory(dir.path().to_string_lossy().to_string());
         assert_eq!(result.files_processed, 0);
         assert_eq!(result.chunks_indexed, 0);
     }
 
+    #[test]
+    fn test_index_directory_reads_html_and_rst() {
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.path().join("ScriptReference")).unwrap();
+        fs::write(
+            dir.path().join("ScriptReference/Rigidbody.html"),
+            "<html><head><title>Rigidbody</title></head><body><div id=\"sidebar\">Index</div><h1>Rigidbody</h1><p>Controls physics.</p></body></html>",
+        ).unwrap();
+        fs::write(dir.path().join("guide.rst"), "Guide\n=====\n\nSome text.\n").unwrap();
+        fs::write(dir.path().join("ignored.js"), "var x;").unwrap();
+
+        let mut indexer = Indexer {
+            storage: IndexStorage::at(dir.path().join("index.json")),
+        };
+        let result = indexer.index_directory(dir.path().to_string_lossy().to_string());
+        assert_eq!(result.files_processed, 2);
+        let found = indexer.search("Controls physics.".to_string(), None);
+        assert_eq!(found.len(), 1);
+        assert!(!found[0].content.contains("Index"));
+        assert_eq!(indexer.find_sections("guide".to_string())[0].section.as_deref(), Some("Guide"));
+    }
+
     #[test]
     fn test_index_directory_with_two_md_files() {
         let dir = TempDir::new();
@@ -246,7 +354,7 @@ mod tests {
         fs::write(dir.path().join("b.md"), "## Second\n\nContent two.\n").unwrap();
 
         let mut indexer = Indexer {
-            storage: IndexStorage::new(),
+            storage: IndexStorage::at(dir.path().join("index.json")),
         };
         let result = indexer.index_directory(dir.path().to_string_lossy().to_string());
         assert_eq!(result.files_processed, 2);
@@ -265,11 +373,11 @@ mod tests {
         .unwrap();
 
         let mut indexer = Indexer {
-            storage: IndexStorage::new(),
+            storage: IndexStorage::at(dir.path().join("index.json")),
         };
         indexer.index_file(dir.path().join("unity.md").to_string_lossy().to_string());
 
-        let results = indexer.search("unity monobehaviour scripting".to_string());
+        let results = indexer.search("unity monobehaviour scripting".to_string(), None);
         assert!(!results.is_empty(), "Search should find indexed content");
     }
 }
@@ -0,0 +1,76 @@
+//! Text of PDF manuals, page by page (Asset Store packages often ship their documentation as
+//! PDF). Extraction needs the `pdf` cargo feature; without it PDFs are reported as unsupported.
+
+use std::path::Path;
+
+/// Text of each page of a PDF, first page first.
+#[cfg(feature = "pdf")]
+pub fn extract_pages(path: &Path) -> Result<Vec<String>, String> {
+    let bytes = crate::safety::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    // The extractor panics on some malformed files; treat that like any other bad PDF
+    std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem_by_pages(&bytes))
+        .map_err(|_| format!("Cannot extract text from {}", path.display()))?
+        .map_err(|e| format!("Cannot extract text from {}: {}", path.display(), e))
+}
+
+/// Text of each page of a PDF, first page first.
+#[cfg(not(feature = "pdf"))]
+pub fn extract_pages(path: &Path) -> Result<Vec<String>, String> {
+    Err(format!("Cannot index {}: built without PDF support (the `pdf` feature)", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A two-page PDF with one line of Helvetica text per page, xref offsets computed.
+    #[cfg(feature = "pdf")]
+    fn two_page_pdf() -> Vec<u8> {
+        let page = |text: &str| format!("BT /F1 12 Tf 72 720 Td ({}) Tj ET", text);
+        let contents = [page("Call Setup before Spawn."), page("Pools are warmed on load.")];
+        let objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>".to_string(),
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 5 0 R /Resources << /Font << /F1 7 0 R >> >> >>".to_string(),
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 6 0 R /Resources << /Font << /F1 7 0 R >> >> >>".to_string(),
+            format!("<< /Length {} >>\nstream\n{}\nendstream", contents[0].len(), contents[0]),
+            format!("<< /Length {} >>\nstream\n{}\nendstream", contents[1].len(), contents[1]),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+        ];
+        let mut pdf = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+        }
+        let xref = pdf.len();
+        pdf.push_str(&format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1));
+        for offset in offsets {
+            pdf.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        pdf.push_str(&format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref));
+        pdf.into_bytes()
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn test_extract_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Manual.pdf");
+        std::fs::write(&path, two_page_pdf()).unwrap();
+        let pages = extract_pages(&path).unwrap();
+        assert_eq!(pages.len(), 2);
+        assert!(pages[0].contains("Call Setup before Spawn."), "{:?}", pages);
+        assert!(pages[1].contains("Pools are warmed on load."), "{:?}", pages);
+
+        std::fs::write(&path, b"%PDF-1.4\nnot really").unwrap();
+        assert!(extract_pages(&path).is_err());
+    }
+
+    #[cfg(not(feature = "pdf"))]
+    #[test]
+    fn test_extract_pages_without_feature() {
+        let error = extract_pages(Path::new("Manual.pdf")).unwrap_err();
+        assert!(error.contains("`pdf` feature"));
+    }
+}
@@ -1,16 +1,52 @@
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 
-use crate::common::{Chunk, SearchResult};
+use crate::common::{Chunk, ChunkMetadata, DocSearchFilter, SearchResult, SectionMatch};
+use super::ann::{AnnIndex, DEFAULT_EF_SEARCH};
 
 const STORAGE_FILENAME: &str = ".unity-docs-index.json";
 
 /// Index storage for chunks
 pub struct IndexStorage {
     chunks: HashMap<String, Chunk>,
+    /// Content hash -> ID of the chunk stored for it
+    hashes: HashMap<u64, String>,
     storage_path: PathBuf,
     loaded: bool,
+    /// Embedding index, loaded on first use for the requested model
+    ann: Option<AnnIndex>,
+}
+
+/// Chunks are duplicates when they have the same content and source file.
+fn content_hash(chunk: &Chunk) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    chunk.metadata.file_path.hash(&mut hasher);
+    chunk.content.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn same_content(a: &Chunk, b: &Chunk) -> bool {
+    a.content == b.content && a.metadata.file_path == b.metadata.file_path
+}
+
+/// Whether a chunk's document frontmatter and heading trail satisfy every field of `filter`.
+fn matches_filter(metadata: &ChunkMetadata, filter: &DocSearchFilter) -> bool {
+    let title_ok = filter.title.as_ref().is_none_or(|wanted| {
+        metadata.title.as_ref().is_some_and(|title| title.to_lowercase().contains(&wanted.to_lowercase()))
+    });
+    let tags_ok = filter.tags.as_ref().is_none_or(|wanted| {
+        let tags = metadata.tags.as_deref().unwrap_or_default();
+        wanted.iter().all(|w| tags.iter().any(|t| t.eq_ignore_ascii_case(w)))
+    });
+    let version_ok = filter.version.as_ref().is_none_or(|wanted| {
+        metadata.version.as_ref().is_some_and(|version| version.starts_with(wanted.as_str()))
+    });
+    let section_ok = filter.section.as_ref().is_none_or(|wanted| {
+        metadata.section.as_ref().is_some_and(|section| section.to_lowercase().contains(&wanted.to_lowercase()))
+    });
+    title_ok && tags_ok && version_ok && section_ok
 }
 
 impl IndexStorage {
@@ -18,14 +54,79 @@ impl IndexStorage {
         let storage_path = std::env::current_dir()
             .unwrap_or_else(|_| PathBuf::from("."))
             .join(STORAGE_FILENAME);
+        Self::at(storage_path)
+    }
 
+    /// Storage backed by a specific index file.
+    pub fn at(storage_path: PathBuf) -> Self {
         IndexStorage {
             chunks: HashMap::new(),
+            hashes: HashMap::new(),
             storage_path,
             loaded: false,
+            ann: None,
         }
     }
 
+    pub fn storage_path(&self) -> &Path {
+        &self.storage_path
+    }
+
+    /// Embedding index file next to the chunk store (`<store>.ann.json`).
+    pub fn ann_path(&self) -> PathBuf {
+        self.storage_path.with_extension("ann.json")
+    }
+
+    /// The embedding index for `model` and `dimensions`: the cached one if it matches, else
+    /// the stored one, else a new empty one.
+    fn ann_for(&mut self, model: &str, dimensions: usize) -> &mut AnnIndex {
+        let path = self.ann_path();
+        let ann = self.ann.take()
+            .filter(|ann| ann.model == model && ann.dimensions == dimensions)
+            .unwrap_or_else(|| AnnIndex::load_for(&path, model, dimensions));
+        self.ann.insert(ann)
+    }
+
+    /// Add or replace the embeddings of stored chunks and save the embedding index. Embeddings
+    /// from another model or dimension count replace the whole index.
+    /// Returns the number of embeddings stored.
+    pub fn store_embeddings(&mut self, model: &str, embeddings: Vec<(String, Vec<f32>)>) -> Result<u32, String> {
+        self.load();
+        let Some(dimensions) = embeddings.first().map(|(_, v)| v.len()) else { return Ok(0) };
+        if dimensions == 0 {
+            return Err("Embeddings must not be empty".to_string());
+        }
+        if let Some((id, _)) = embeddings.iter().find(|(id, _)| !self.chunks.contains_key(id)) {
+            return Err(format!("Chunk not found: {}", id));
+        }
+        let path = self.ann_path();
+        let ann = self.ann_for(model, dimensions);
+        for (id, vector) in &embeddings {
+            ann.insert(id, vector)?;
+        }
+        ann.save(&path)?;
+        Ok(embeddings.len() as u32)
+    }
+
+    /// Chunks whose embeddings are nearest to `query`, most similar first. Empty when nothing
+    /// was embedded with `model` at the query's dimension count.
+    pub fn vector_search(&mut self, model: &str, query: &[f32], limit: usize) -> Vec<SearchResult> {
+        self.load();
+        let ann = self.ann_for(model, query.len());
+        let found = ann.search(query, limit, DEFAULT_EF_SEARCH);
+        found.into_iter()
+            .filter_map(|(id, score)| {
+                let chunk = self.chunks.get(&id)?;
+                Some(SearchResult { id, content: chunk.content.clone(), score, metadata: chunk.metadata.clone() })
+            })
+            .collect()
+    }
+
+    /// Chunks with an embedding in the loaded embedding index.
+    pub fn embedded_count(&self) -> usize {
+        self.ann.as_ref().map_or(0, AnnIndex::len)
+    }
+
     /// Load index from disk
     pub fn load(&mut self) {
         if self.loaded {
@@ -33,11 +134,13 @@ impl IndexStorage {
         }
 
         if self.storage_path.exists() {
-            if let Ok(content) = fs::read_to_string(&self.storage_path) {
+            if let Ok(content) = crate::safety::read_to_string(&self.storage_path) {
                 if let Ok(data) = serde_json::from_str::<serde_json::Value>(&content) {
                     if let Some(chunks_obj) = data.get("chunks").and_then(|c| c.as_object()) {
                         for (id, chunk_val) in chunks_obj {
                             if let Ok(chunk) = serde_json::from_value::<Chunk>(chunk_val.clone()) {
+                                // Duplicates already on disk stay until compact()
+                                self.hashes.entry(content_hash(&chunk)).or_insert_with(|| id.clone());
                                 self.chunks.insert(id.clone(), chunk);
                             }
                         }
@@ -62,22 +165,83 @@ impl IndexStorage {
         });
 
         if let Ok(json) = serde_json::to_string_pretty(&data) {
-            let _ = fs::write(&self.storage_path, json);
+            let _ = crate::common::atomic::write_atomic(&self.storage_path, json);
         }
     }
 
-    /// Store a chunk
-    pub fn store_chunk(&mut self, chunk: Chunk) {
+    /// Store a chunk unless the index already holds the same content for the same file.
+    /// Returns false for such duplicates.
+    pub fn store_chunk(&mut self, chunk: Chunk) -> bool {
         self.load();
+        let hash = content_hash(&chunk);
+        if let Some(existing) = self.hashes.get(&hash).and_then(|id| self.chunks.get(id)) {
+            if same_content(existing, &chunk) {
+                return false;
+            }
+        }
+        self.hashes.insert(hash, chunk.id.clone());
         self.chunks.insert(chunk.id.clone(), chunk);
+        true
     }
 
-    /// Keyword search
-    pub fn keyword_search(&self, query: &str) -> Vec<SearchResult> {
+    /// Merge duplicate chunks (keeping the lowest ID of each group) and drop chunks whose
+    /// source file no longer exists, then rewrite the storage file.
+    /// Returns (duplicates removed, orphans removed).
+    pub fn compact(&mut self) -> (u32, u32) {
+        self.load();
+        let mut ids: Vec<String> = self.chunks.keys().cloned().collect();
+        ids.sort();
+
+        let mut kept: HashMap<u64, Vec<String>> = HashMap::new();
+        let mut duplicates = 0u32;
+        let mut orphans = 0u32;
+        for id in ids {
+            let chunk = &self.chunks[&id];
+            let file = &chunk.metadata.file_path;
+            if !file.is_empty() && !Path::new(file).exists() {
+                self.chunks.remove(&id);
+                orphans += 1;
+                continue;
+            }
+            let group = kept.entry(content_hash(chunk)).or_default();
+            if group.iter().any(|other| same_content(&self.chunks[other], chunk)) {
+                self.chunks.remove(&id);
+                duplicates += 1;
+            } else {
+                group.push(id);
+            }
+        }
+
+        self.hashes = kept.into_iter()
+            .filter_map(|(hash, ids)| ids.into_iter().next().map(|id| (hash, id)))
+            .collect();
+        self.save();
+
+        // Drop embeddings of removed chunks and rebuild the graph without them
+        let path = self.ann_path();
+        if let Some(mut ann) = self.ann.take().or_else(|| AnnIndex::load(&path)) {
+            let removed: Vec<String> = ann.ids().filter(|id| !self.chunks.contains_key(*id)).map(str::to_string).collect();
+            for id in &removed {
+                ann.remove(id);
+            }
+            if ann.deleted_count() > 0 {
+                ann.rebuild();
+                let _ = ann.save(&path);
+            }
+            self.ann = Some(ann);
+        }
+        (duplicates, orphans)
+    }
+
+    /// Keyword search, restricted to documents matching `filter` when given
+    pub fn keyword_search(&self, query: &str, filter: Option<&DocSearchFilter>) -> Vec<SearchResult> {
         let lower_query = query.to_lowercase();
         let mut results: Vec<SearchResult> = Vec::new();
 
         for chunk in self.chunks.values() {
+            if filter.is_some_and(|f| !matches_filter(&chunk.metadata, f)) {
+                continue;
+            }
             let lower_content = chunk.content.to_lowercase();
 
             if lower_content.contains(&lower_query) {
@@ -99,10 +263,66 @@ impl IndexStorage {
         results
     }
 
+    /// Sections (chunks grouped by file and section title) whose title or file path contains
+    /// every word of the query, case-insensitively. Title matches sort before path-only matches.
+    pub fn section_search(&self, query: &str) -> Vec<SectionMatch> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut groups: HashMap<(&str, Option<&str>), Vec<&Chunk>> = HashMap::new();
+        for chunk in self.chunks.values() {
+            groups.entry((chunk.metadata.file_path.as_str(), chunk.metadata.section.as_deref()))
+                .or_default()
+                .push(chunk);
+        }
+
+        let mut results: Vec<SectionMatch> = groups.into_iter()
+            .filter_map(|((file_path, section), mut chunks)| {
+                let title = section.unwrap_or("").to_lowercase();
+                let path = file_path.to_lowercase();
+                let in_section = terms.iter().all(|t| title.contains(t.as_str()));
+                let in_path = terms.iter().all(|t| path.contains(t.as_str()));
+                let matched_in = match (in_section, in_path) {
+                    (true, true) => "both",
+                    (true, false) => "section",
+                    (false, true) => "path",
+                    (false, false) => {
+                        // Words may be split between the title and the path
+                        if !terms.iter().all(|t| title.contains(t.as_str()) || path.contains(t.as_str())) {
+                            return None;
+                        }
+                        "both"
+                    }
+                };
+                chunks.sort_by(|a, b| a.id.cmp(&b.id));
+                Some(SectionMatch {
+                    file_path: file_path.to_string(),
+                    section: section.map(str::to_string),
+                    matched_in: matched_in.to_string(),
+                    chunk_count: chunks.len() as u32,
+                    total_tokens: chunks.iter().map(|c| c.tokens).sum(),
+                    chunk_ids: chunks.iter().map(|c| c.id.clone()).collect(),
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| {
+            (a.matched_in == "path").cmp(&(b.matched_in == "path"))
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then_with(|| a.section.cmp(&b.section))
+        });
+        results
+    }
+
     /// Clear all chunks
     pub fn clear(&mut self) {
         self.chunks.clear();
+        self.hashes.clear();
         self.save();
+        self.ann = None;
+        let _ = crate::safety::remove_file(self.ann_path());
     }
 
     /// Get storage statistics
@@ -132,6 +352,14 @@ fn jaccard_similarity(str1: &str, str2: &str) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+
+    /// Storage whose index file lives in a fresh temp directory (kept alive by the guard).
+    fn temp_storage() -> (tempfile::TempDir, IndexStorage) {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = IndexStorage::at(dir.path().join(STORAGE_FILENAME));
+        (dir, storage)
+    }
 
     #[test]
     fn test_jaccard_similarity() {
@@ -141,7 +369,7 @@ mod tests {
 
     #[test]
     fn test_store_and_retrieve_via_keyword_search() {
-        let mut storage = IndexStorage::new();
+        let (_dir, mut storage) = temp_storage();
         storage.store_chunk(Chunk {
             id: "test1".to_string(),
             content: "Unity MonoBehaviour lifecycle methods".to_string(),
@@ -150,19 +378,56 @@ mod tests {
             metadata: crate::common::ChunkMetadata {
                 file_path: "test.md".to_string(),
                 section: None,
+                heading_level: None,
                 language: None,
                 unity_class: None,
                 unity_method: None,
+                page: None,
+                title: None,
+                tags: None,
+                version: None,
             },
         });
-        let results = storage.keyword_search("MonoBehaviour lifecycle");
+        let results = storage.keyword_search("MonoBehaviour lifecycle", None);
         assert!(!results.is_empty());
         assert_eq!(results[0].id, "test1");
     }
 
+    #[test]
+    fn test_keyword_search_filters_on_frontmatter() {
+        let (_dir, mut storage) = temp_storage();
+        for (id, tags, version) in [("old", "ui", "2021.3"), ("new", "ui", "2022.3"), ("other", "audio", "2022.3")] {
+            storage.store_chunk(Chunk {
+                id: id.to_string(),
+                content: format!("Canvas scaling notes {}", id),
+                tokens: 4,
+                chunk_type: crate::common::ChunkType::Prose,
+                metadata: crate::common::ChunkMetadata {
+                    file_path: format!("{}.md", id),
+                    section: (id == "new").then(|| "UI > Canvas".to_string()),
+                    heading_level: None,
+                    language: None,
+                    unity_class: None,
+                    unity_method: None,
+                    page: None,
+                    title: Some("Canvas Scaling".to_string()),
+                    tags: Some(vec![tags.to_string()]),
+                    version: Some(version.to_string()),
+                },
+            });
+        }
+        let filter = DocSearchFilter { tags: Some(vec!["UI".to_string()]), version: Some("2022".to_string()), title: Some("canvas".to_string()), section: None };
+        let ids: Vec<String> = storage.keyword_search("canvas scaling", Some(&filter)).into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["new"]);
+        assert_eq!(storage.keyword_search("canvas scaling", None).len(), 3);
+        let chapter = DocSearchFilter { section: Some("ui >".to_string()), ..Default::default() };
+        let ids: Vec<String> = storage.keyword_search("canvas scaling", Some(&chapter)).into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["new"]);
+    }
+
     #[test]
     fn test_case_insensitive_keyword_search() {
-        let mut storage = IndexStorage::new();
+        let (_dir, mut storage) = temp_storage();
         storage.store_chunk(Chunk {
             id: "case1".to_string(),
             content: "UNITY GAME ENGINE".to_string(),
@@ -171,26 +436,31 @@ mod tests {
             metadata: crate::common::ChunkMetadata {
                 file_path: "test.md".to_string(),
                 section: None,
+                heading_level: None,
                 language: None,
                 unity_class: None,
                 unity_method: None,
+                page: None,
+                title: None,
+                tags: None,
+                version: None,
             },
         });
         // Search lowercase should find uppercase content
-        let results = storage.keyword_search("unity game engine");
+        let results = storage.keyword_search("unity game engine", None);
         assert!(!results.is_empty());
     }
 
     #[test]
     fn test_empty_store_returns_empty() {
-        let storage = IndexStorage::new();
-        let results = storage.keyword_search("anything");
+        let (_dir, storage) = temp_storage();
+        let results = storage.keyword_search("anything", None);
         assert!(results.is_empty());
     }
 
     #[test]
     fn test_clear_removes_all() {
-        let mut storage = IndexStorage::new();
+        let (_dir, mut storage) = temp_storage();
         storage.store_chunk(Chunk {
             id: "clear1".to_string(),
             content: "some data here".to_string(),
@@ -199,21 +469,156 @@ mod tests {
             metadata: crate::common::ChunkMetadata {
                 file_path: "test.md".to_string(),
                 section: None,
+                heading_level: None,
                 language: None,
                 unity_class: None,
                 unity_method: None,
+                page: None,
+                title: None,
+                tags: None,
+                version: None,
             },
         });
         storage.clear();
-        let results = storage.keyword_search("data");
+        let results = storage.keyword_search("data", None);
         assert!(results.is_empty());
     }
 
+    fn chunk(id: &str, content: &str, file_path: &str) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            content: content.to_string(),
+            tokens: 2,
+            chunk_type: crate::common::ChunkType::Prose,
+            metadata: crate::common::ChunkMetadata {
+                file_path: file_path.to_string(),
+                section: None,
+                heading_level: None,
+                language: None,
+                unity_class: None,
+                unity_method: None,
+                page: None,
+                title: None,
+                tags: None,
+                version: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_store_skips_duplicate_content_from_same_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = IndexStorage::at(dir.path().join("index.json"));
+        assert!(storage.store_chunk(chunk("a1", "rigidbody physics", "a.md")));
+        assert!(!storage.store_chunk(chunk("a2", "rigidbody physics", "a.md")), "re-indexed chunk is a duplicate");
+        assert!(storage.store_chunk(chunk("b1", "rigidbody physics", "b.md")), "same text in another file is kept");
+        assert_eq!(storage.stats().0, 2);
+    }
+
+    #[test]
+    fn test_compact_merges_duplicates_and_drops_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("doc.md");
+        fs::write(&source, "## Doc").unwrap();
+        let source = source.to_string_lossy().to_string();
+        let gone = dir.path().join("deleted.md").to_string_lossy().to_string();
+        let index_path = dir.path().join("index.json");
+
+        // Duplicates written by an older index run, before store-time deduplication
+        let stale: HashMap<String, Chunk> = [
+            chunk("chunk_1", "animator states", &source),
+            chunk("chunk_2", "animator states", &source),
+            chunk("chunk_3", "blend trees", &source),
+            chunk("chunk_4", "removed page", &gone),
+        ].into_iter().map(|c| (c.id.clone(), c)).collect();
+        fs::write(&index_path, serde_json::json!({"chunks": stale}).to_string()).unwrap();
+
+        let mut storage = IndexStorage::at(index_path.clone());
+        assert_eq!(storage.compact(), (1, 1));
+        let mut ids: Vec<String> = storage.chunks.keys().cloned().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["chunk_1", "chunk_3"]);
+        assert!(!storage.store_chunk(chunk("chunk_5", "animator states", &source)));
+
+        let mut reloaded = IndexStorage::at(index_path);
+        reloaded.load();
+        assert_eq!(reloaded.stats().0, 2);
+    }
+
+    #[test]
+    fn test_embeddings_persist_next_to_chunk_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("doc.md");
+        fs::write(&source, "## Doc").unwrap();
+        let source = source.to_string_lossy().to_string();
+        let index_path = dir.path().join("index.json");
+
+        let mut storage = IndexStorage::at(index_path.clone());
+        for (id, content) in [("c1", "rigidbody"), ("c2", "animator"), ("c3", "navmesh")] {
+            storage.store_chunk(chunk(id, content, &source));
+        }
+        storage.save();
+        let embeddings = vec![
+            ("c1".to_string(), vec![1.0, 0.0, 0.0]),
+            ("c2".to_string(), vec![0.0, 1.0, 0.0]),
+            ("c3".to_string(), vec![0.0, 0.7, 0.7]),
+        ];
+        assert_eq!(storage.store_embeddings("model-a", embeddings), Ok(3));
+        assert!(storage.store_embeddings("model-a", vec![("nope".to_string(), vec![1.0, 0.0, 0.0])]).is_err());
+        assert_eq!(storage.ann_path(), dir.path().join("index.ann.json"));
+
+        // A new process loads the stored graph
+        let mut reloaded = IndexStorage::at(index_path.clone());
+        let found: Vec<String> = reloaded.vector_search("model-a", &[0.0, 1.0, 0.1], 2).into_iter().map(|r| r.id).collect();
+        assert_eq!(found, vec!["c2", "c3"]);
+        assert_eq!(reloaded.embedded_count(), 3);
+        assert!(reloaded.vector_search("model-b", &[0.0, 1.0, 0.1], 2).is_empty());
+        assert!(reloaded.vector_search("model-a", &[0.0, 1.0], 2).is_empty());
+
+        // Compaction drops embeddings of removed chunks
+        let mut stale = IndexStorage::at(index_path.clone());
+        stale.load();
+        stale.chunks.get_mut("c2").unwrap().metadata.file_path = dir.path().join("gone.md").to_string_lossy().to_string();
+        assert_eq!(stale.compact(), (0, 1));
+        let found: Vec<String> = IndexStorage::at(index_path.clone()).vector_search("model-a", &[0.0, 1.0, 0.1], 3).into_iter().map(|r| r.id).collect();
+        assert_eq!(found, vec!["c3", "c1"]);
+
+        stale.clear();
+        assert!(!dir.path().join("index.ann.json").exists());
+    }
+
+    #[test]
+    fn test_section_search_matches_titles_and_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = IndexStorage::at(dir.path().join("index.json"));
+        let mut sectioned = |id: &str, section: Option<&str>, file: &str| {
+            let mut c = chunk(id, id, file);
+            c.metadata.section = section.map(str::to_string);
+            storage.store_chunk(c);
+        };
+        sectioned("c1", Some("Rigidbody Interpolation"), "docs/Physics.md");
+        sectioned("c2", Some("Rigidbody Interpolation"), "docs/Physics.md");
+        sectioned("c3", Some("Colliders"), "docs/Physics.md");
+        sectioned("c4", Some("Overview"), "docs/Rigidbody2D.md");
+        sectioned("c5", Some("Animator"), "docs/Animation.md");
+
+        let found = storage.section_search("rigidbody");
+        let rows: Vec<(&str, Option<&str>, &str, u32)> = found.iter()
+            .map(|m| (m.file_path.as_str(), m.section.as_deref(), m.matched_in.as_str(), m.chunk_count))
+            .collect();
+        assert_eq!(rows, vec![
+            ("docs/Physics.md", Some("Rigidbody Interpolation"), "section", 2),
+            ("docs/Rigidbody2D.md", Some("Overview"), "path", 1),
+        ]);
+        assert_eq!(found[0].chunk_ids, vec!["c1", "c2"]);
+
+        assert_eq!(storage.section_search("physics colliders").len(), 1, "terms may span path and title");
+        assert!(storage.section_search("   ").is_empty());
+    }
+
     #[test]
     fn test_stats_returns_correct_counts() {
-        // Clear first to remove any residual data from other tests sharing CWD
-        let mut storage = IndexStorage::new();
-        storage.clear();
+        let (_dir, mut storage) = temp_storage();
         storage.store_chunk(Chunk {
             id: "s1".to_string(),
             content: "chunk one".to_string(),
@@ -222,9 +627,14 @@ mod tests {
             metadata: crate::common::ChunkMetadata {
                 file_path: "test.md".to_string(),
                 section: None,
+                heading_level: None,
                 language: None,
                 unity_class: None,
                 unity_method: None,
+                page: None,
+                title: None,
+                tags: None,
+                version: None,
             },
         });
         storage.store_chunk(Chunk {
@@ -235,9 +645,14 @@ mod tests {
             metadata: crate::common::ChunkMetadata {
                 file_path: "test.md".to_string(),
                 section: None,
+                heading_level: None,
                 language: None,
                 unity_class: None,
                 unity_method: None,
+                page: None,
+                title: None,
+                tags: None,
+                version: None,
             },
         });
         let (count, total_tokens) = storage.stats();
@@ -1,11 +1,21 @@
 #![deny(clippy::all)]
 
+pub mod benchmark;
+pub mod buildreport;
 pub mod common;
 pub mod csharp;
 pub mod scanner;
 pub mod indexer;
 pub mod embeddings;
+pub mod logs;
+pub mod shaders;
+pub mod unitypackage;
+pub mod vcs;
 pub mod walker;
+pub mod telemetry;
+pub mod safety;
+pub mod search;
+pub mod guid_db;
 
 use napi_derive::napi;
 
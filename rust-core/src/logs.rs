@@ -0,0 +1,390 @@
+//! Structured parsing of Unity `Editor.log` / `Player.log` files: compiler diagnostics,
+//! exceptions with their stack traces, asset import warnings and build reports.
+
+use napi_derive::napi;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use crate::common;
+
+/// `Assets/Scripts/Player.cs(12,34): error CS0103: message`
+static COMPILE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(.+?\.cs)\((\d+),(\d+)\):\s*(error|warning)\s+(\w+):\s*(.*)$").unwrap()
+});
+/// `NullReferenceException: Object reference not set ...`
+static EXCEPTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z_][\w.]*Exception)(?::\s*(.*))?$").unwrap()
+});
+/// Editor frames: `Player.Update () (at Assets/Scripts/Player.cs:42)`, `Player:Update()`
+static UNITY_FRAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([\w.:<>`+\[\],]+?)\s*\([^()]*\)(?:\s*\(at (.+):(\d+)\))?\s*$").unwrap()
+});
+/// Mono frames: `  at Player.Update () [0x00000] in <hash>:0`
+static MONO_FRAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*at\s+([\w.:<>`+\[\],]+?)\s*\([^()]*\)(?:\s*\[0x[0-9a-f]+\])?(?:\s+in\s+(.+):(\d+))?\s*$").unwrap()
+});
+/// Trailer Unity appends to every console message in Editor.log
+static FILENAME_TRAILER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\(Filename: .* Line: -?\d+\)$").unwrap()
+});
+static ASSET_PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"((?:Assets|Packages)/[^'"\n]+?\.[A-Za-z0-9]+)(?:['"\s:,)]|$)"#).unwrap()
+});
+static IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:\bimport(?:er|ing)?\b.*\b(?:warning|failed|error|inconsistent)\b|\b(?:warning|failed|error)\b.*\bimport(?:er|ing)?\b|meta data file \(\.meta\) exists but its asset|could not create asset from)").unwrap()
+});
+static BUILD_CATEGORY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*([A-Za-z][\w ]*?)\s+([\d.]+ [kmgKMG]?[bB])\s+([\d.]+)%\s*$").unwrap()
+});
+static BUILD_ASSET_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*([\d.]+ [kmgKMG]?[bB])\s+([\d.]+)%\s+(.+?)\s*$").unwrap()
+});
+static BUILD_RESULT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"Build completed with a result of '(\w+)' in \d+ seconds \((\d+) ms\)").unwrap()
+});
+
+/// Largest assets kept per build report.
+const MAX_BUILD_ASSETS: usize = 20;
+
+/// One frame of an exception stack trace.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogStackFrame {
+    /// Method as printed (e.g. "Player.Update", "Player:Update")
+    pub method: String,
+    /// Script path from the frame, or resolved from the declaring type through the project's scripts
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// A compiler diagnostic, exception or import warning from a Unity log.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// "compile_error", "compile_warning", "exception" or "import_warning"
+    pub kind: String,
+    /// 1-based line in the log where the entry first appears
+    pub log_line: u32,
+    pub message: String,
+    /// Script or asset the entry is about
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    /// Compiler code (CS0103) or exception type (NullReferenceException)
+    pub code: Option<String>,
+    pub stack: Vec<LogStackFrame>,
+    /// Times the identical entry was logged (Unity repeats compile errors on every refresh)
+    pub occurrences: u32,
+}
+
+/// Size of one category or asset in a build report.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct BuildReportItem {
+    pub name: String,
+    /// Size as printed (e.g. "12.3 mb")
+    pub size: String,
+    pub percent: f64,
+}
+
+/// The "Build Report" section Unity logs after a player build.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct BuildReportSummary {
+    /// "Succeeded", "Failed", "Cancelled", ...
+    pub result: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub total_size: Option<String>,
+    pub categories: Vec<BuildReportItem>,
+    /// Largest assets, as listed by Unity (at most 20)
+    pub largest_assets: Vec<BuildReportItem>,
+}
+
+/// Structured contents of a Unity log.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct LogReport {
+    pub file: String,
+    pub compile_errors: u32,
+    pub compile_warnings: u32,
+    pub exceptions: u32,
+    pub import_warnings: u32,
+    pub entries: Vec<LogEntry>,
+    pub build_reports: Vec<BuildReportSummary>,
+}
+
+/// Default Editor.log location for this platform.
+fn default_editor_log() -> Option<PathBuf> {
+    let home = PathBuf::from(std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok()?);
+    if cfg!(target_os = "windows") {
+        let local = std::env::var("LOCALAPPDATA").map(PathBuf::from).unwrap_or_else(|_| home.join("AppData").join("Local"));
+        Some(local.join("Unity").join("Editor").join("Editor.log"))
+    } else if cfg!(target_os = "macos") {
+        Some(home.join("Library").join("Logs").join("Unity").join("Editor.log"))
+    } else {
+        Some(home.join(".config").join("unity3d").join("Editor.log"))
+    }
+}
+
+/// Parse a Unity Editor.log or Player.log (default: this platform's Editor.log).
+///
+/// With `project_root`, stack frames without a source location are resolved to the script
+/// declaring their type. Returns None when the log cannot be read.
+#[napi]
+pub fn parse_unity_log(path: Option<String>, project_root: Option<String>) -> Option<LogReport> {
+    let _call = crate::telemetry::call("parse_unity_log");
+    let path = path.map(PathBuf::from).or_else(default_editor_log)?;
+    let content = common::read_unity_file(&path).ok()?;
+    let scripts = project_root.map(|root| {
+        crate::csharp::build_type_registry(root, None, None)
+            .into_iter()
+            .map(|t| (t.name, t.file_path))
+            .collect::<HashMap<String, String>>()
+    }).unwrap_or_default();
+    let mut report = parse_log(&content, &scripts);
+    report.file = path.to_string_lossy().to_string();
+    Some(report)
+}
+
+/// Parse log content. `scripts` maps type names to script paths for frame resolution.
+pub fn parse_log(content: &str, scripts: &HashMap<String, String>) -> LogReport {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut entries: Vec<LogEntry> = Vec::new();
+    let mut seen: HashMap<(String, Option<String>, Option<u32>, String), usize> = HashMap::new();
+    let mut build_reports: Vec<BuildReportSummary> = Vec::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        let log_line = i as u32 + 1;
+        i += 1;
+
+        let entry = if let Some(cap) = COMPILE_RE.captures(line.trim_start()) {
+            Some(LogEntry {
+                kind: format!("compile_{}", &cap[4]),
+                log_line,
+                message: cap[6].to_string(),
+                file: Some(cap[1].replace('\\', "/")),
+                line: cap[2].parse().ok(),
+                column: cap[3].parse().ok(),
+                code: Some(cap[5].to_string()),
+                stack: Vec::new(),
+                occurrences: 1,
+            })
+        } else if let Some(cap) = EXCEPTION_RE.captures(line) {
+            let mut stack = Vec::new();
+            while i < lines.len() {
+                let next = lines[i].trim_end();
+                if FILENAME_TRAILER_RE.is_match(next.trim()) {
+                    i += 1;
+                    break;
+                }
+                let Some(frame) = parse_frame(next, scripts) else { break };
+                stack.push(frame);
+                i += 1;
+            }
+            // The throwing frame, else the first frame in a project script
+            let origin = stack.iter().find(|f| f.file.as_deref().is_some_and(|p| p.starts_with("Assets/")))
+                .or_else(|| stack.first());
+            Some(LogEntry {
+                kind: "exception".to_string(),
+                log_line,
+                message: cap.get(2).map_or("", |m| m.as_str()).to_string(),
+                file: origin.and_then(|f| f.file.clone()),
+                line: origin.and_then(|f| f.line),
+                column: None,
+                code: Some(cap[1].to_string()),
+                stack,
+                occurrences: 1,
+            })
+        } else if IMPORT_RE.is_match(line) {
+            Some(LogEntry {
+                kind: "import_warning".to_string(),
+                log_line,
+                message: line.trim().to_string(),
+                file: ASSET_PATH_RE.captures(line).map(|c| c[1].to_string()),
+                line: None,
+                column: None,
+                code: None,
+                stack: Vec::new(),
+                occurrences: 1,
+            })
+        } else {
+            if line.trim() == "Build Report" {
+                i = parse_build_report(&lines, i, &mut build_reports);
+            } else if let Some(cap) = BUILD_RESULT_RE.captures(line) {
+                if build_reports.last().is_none_or(|r| r.result.is_some()) {
+                    build_reports.push(BuildReportSummary::default());
+                }
+                if let Some(report) = build_reports.last_mut() {
+                    report.result = Some(cap[1].to_string());
+                    report.duration_ms = cap[2].parse().ok();
+                }
+            }
+            None
+        };
+
+        if let Some(entry) = entry {
+            let key = (entry.kind.clone(), entry.file.clone(), entry.line, entry.message.clone());
+            match seen.get(&key) {
+                Some(&index) => entries[index].occurrences += 1,
+                None => {
+                    seen.insert(key, entries.len());
+                    entries.push(entry);
+                }
+            }
+        }
+    }
+
+    let count = |kind: &str| entries.iter().filter(|e| e.kind == kind).count() as u32;
+    LogReport {
+        file: String::new(),
+        compile_errors: count("compile_error"),
+        compile_warnings: count("compile_warning"),
+        exceptions: count("exception"),
+        import_warnings: count("import_warning"),
+        entries,
+        build_reports,
+    }
+}
+
+/// A stack frame line, with its script resolved from the declaring type when it has no location.
+fn parse_frame(line: &str, scripts: &HashMap<String, String>) -> Option<LogStackFrame> {
+    let cap = MONO_FRAME_RE.captures(line).or_else(|| UNITY_FRAME_RE.captures(line.trim()))?;
+    let method = cap[1].to_string();
+    let located = cap.get(2)
+        .map(|f| f.as_str().replace('\\', "/"))
+        // Release players print `<hash>:0` instead of a path
+        .filter(|f| !f.starts_with('<'));
+    let line = located.as_ref().and_then(|_| cap.get(3)).and_then(|l| l.as_str().parse().ok());
+    let file = located.or_else(|| {
+        let type_path = method.rsplit_once([':', '.']).map_or(method.as_str(), |(t, _)| t);
+        let type_name = type_path.rsplit(['.', '+', '/']).next().unwrap_or(type_path);
+        let type_name = type_name.split(['<', '`']).next().unwrap_or(type_name);
+        scripts.get(type_name).cloned()
+    });
+    Some(LogStackFrame { method, file, line })
+}
+
+/// Parse the lines following a "Build Report" header; returns the index after the section.
+fn parse_build_report(lines: &[&str], mut i: usize, reports: &mut Vec<BuildReportSummary>) -> usize {
+    let mut report = BuildReportSummary::default();
+    let mut in_assets = false;
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        if line.starts_with("---") {
+            i += 1;
+            break;
+        }
+        if let Some(size) = line.trim().strip_prefix("Complete build size") {
+            report.total_size = Some(size.trim().to_string());
+        } else if line.starts_with("Used Assets") {
+            in_assets = true;
+        } else if in_assets {
+            if let Some(cap) = BUILD_ASSET_RE.captures(line) {
+                if report.largest_assets.len() < MAX_BUILD_ASSETS {
+                    report.largest_assets.push(BuildReportItem {
+                        name: cap[3].to_string(),
+                        size: cap[1].to_string(),
+                        percent: cap[2].parse().unwrap_or(0.0),
+                    });
+                }
+            } else if !line.trim().is_empty() {
+                break;
+            }
+        } else if let Some(cap) = BUILD_CATEGORY_RE.captures(line) {
+            report.categories.push(BuildReportItem {
+                name: cap[1].to_string(),
+                size: cap[2].to_string(),
+                percent: cap[3].parse().unwrap_or(0.0),
+            });
+        }
+        i += 1;
+    }
+    reports.push(report);
+    i
+}
+
+// ========== Tests ==========
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EDITOR_LOG: &str = "\
+Refreshing native plugins compatible for Editor in 1.23 ms, found 3 plugins.
+Assets/Scripts/Player.cs(12,34): error CS0103: The name 'speeed' does not exist in the current context
+Assets/Scripts/Enemy.cs(5,13): warning CS0168: The variable 'e' is declared but never used
+Assets/Scripts/Player.cs(12,34): error CS0103: The name 'speeed' does not exist in the current context
+NullReferenceException: Object reference not set to an instance of an object
+Game.Spawner.Spawn (UnityEngine.Vector3 position) (at Assets/Scripts/Spawner.cs:42)
+Game.Spawner:Update()
+UnityEngine.Debug:Log (object)
+(Filename: Assets/Scripts/Spawner.cs Line: 42)
+
+A meta data file (.meta) exists but its asset 'Assets/Textures/Old.png' can't be found. When moving or deleting files outside of Unity, please ensure that the corresponding .meta file is moved or deleted along with it.
+Build Report
+Uncompressed usage by category (Percentages based on user generated assets only):
+Textures               12.3 mb\t 45.2%
+Meshes                 1.2 mb\t 4.4%
+Complete build size    120.5 mb
+Used Assets and files from the Resources folder, sorted by uncompressed size:
+ 5.3 mb\t 19.5% Assets/Textures/Big.png
+ 1.1 mb\t 4.0% Assets/Audio/Theme.ogg
+-------------------------------------------------------------------------------
+Build completed with a result of 'Succeeded' in 54 seconds (54123 ms)
+";
+
+    #[test]
+    fn test_parse_log_compile_errors_and_exceptions() {
+        let mut scripts = HashMap::new();
+        scripts.insert("Spawner".to_string(), "Assets/Scripts/Spawner.cs".to_string());
+        let report = parse_log(EDITOR_LOG, &scripts);
+        assert_eq!((report.compile_errors, report.compile_warnings, report.exceptions, report.import_warnings), (1, 1, 1, 1));
+
+        let error = &report.entries[0];
+        assert_eq!((error.file.as_deref(), error.line, error.column, error.code.as_deref()), (Some("Assets/Scripts/Player.cs"), Some(12), Some(34), Some("CS0103")));
+        assert_eq!(error.occurrences, 2);
+
+        let exception = report.entries.iter().find(|e| e.kind == "exception").unwrap();
+        assert_eq!(exception.code.as_deref(), Some("NullReferenceException"));
+        assert_eq!((exception.file.as_deref(), exception.line), (Some("Assets/Scripts/Spawner.cs"), Some(42)));
+        assert_eq!(exception.stack.len(), 3);
+        assert_eq!(exception.stack[1], LogStackFrame {
+            method: "Game.Spawner:Update".to_string(),
+            file: Some("Assets/Scripts/Spawner.cs".to_string()),
+            line: None,
+        });
+        assert_eq!(exception.stack[2].file, None);
+
+        let import = report.entries.iter().find(|e| e.kind == "import_warning").unwrap();
+        assert_eq!(import.file.as_deref(), Some("Assets/Textures/Old.png"));
+    }
+
+    #[test]
+    fn test_parse_log_build_report() {
+        let report = parse_log(EDITOR_LOG, &HashMap::new());
+        assert_eq!(report.build_reports.len(), 1);
+        let build = &report.build_reports[0];
+        assert_eq!(build.result.as_deref(), Some("Succeeded"));
+        assert_eq!(build.duration_ms, Some(54123));
+        assert_eq!(build.total_size.as_deref(), Some("120.5 mb"));
+        let categories: Vec<(&str, f64)> = build.categories.iter().map(|c| (c.name.as_str(), c.percent)).collect();
+        assert_eq!(categories, vec![("Textures", 45.2), ("Meshes", 4.4)]);
+        assert_eq!(build.largest_assets[0].name, "Assets/Textures/Big.png");
+        assert_eq!(build.largest_assets.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_player_log_mono_frames() {
+        let log = "ArgumentException: Invalid path\n  at Game.Loader.Load (System.String path) [0x00012] in <a1b2c3>:0 \n  at Game.Loader.Start () [0x00000] in /build/Assets/Scripts/Loader.cs:17 \n\nShutting down\n";
+        let report = parse_log(log, &HashMap::new());
+        let exception = &report.entries[0];
+        assert_eq!(exception.stack.len(), 2);
+        assert_eq!(exception.stack[0].file, None);
+        assert_eq!(exception.stack[1].file.as_deref(), Some("/build/Assets/Scripts/Loader.cs"));
+        assert_eq!(exception.stack[1].line, Some(17));
+    }
+}
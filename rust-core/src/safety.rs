@@ -0,0 +1,295 @@
+//! Guardrails for hosts that hand these functions to untrusted callers (MCP servers exposing
+//! them as LLM tools): confine file access to registered project roots, deny writes unless
+//! enabled, and cap the bytes one napi call may read.
+//!
+//! Off until `set_safety_config` or `register_project_root` is called. Enforced where the
+//! crate touches the filesystem (`read_unity_file`, `write_atomic`, the wrappers below and the
+//! roots of directory walks), so no checks are needed in JS. The read budget starts when a
+//! napi entry point opens its `telemetry::call` guard; entry points it calls in turn, and
+//! rayon workers, keep charging the same budget.
+//! Over-budget and out-of-root reads fail with `PermissionDenied`, which entry points report
+//! like any other unreadable file. The embedding model cache is outside this scope.
+
+use napi_derive::napi;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::RwLock;
+
+use crate::common::SafetyConfig;
+
+/// Active guardrails, with roots resolved.
+#[derive(Debug, Clone, Default)]
+struct Policy {
+    roots: Vec<PathBuf>,
+    allow_writes: bool,
+    max_bytes_per_call: Option<u64>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static POLICY: RwLock<Option<Policy>> = RwLock::new(None);
+/// Bytes read since the current outermost napi call started (summed across rayon workers)
+static BYTES_READ: ReadBudget = ReadBudget(AtomicU64::new(0));
+
+/// Bytes charged against the per-call read budget.
+struct ReadBudget(AtomicU64);
+
+impl ReadBudget {
+    fn begin(&self) {
+        self.0.store(0, Ordering::Relaxed);
+    }
+
+    fn charge(&self, policy: &Policy, bytes: u64) -> io::Result<()> {
+        policy.check_budget(self.0.fetch_add(bytes, Ordering::Relaxed) + bytes)
+    }
+}
+
+fn denied(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, message)
+}
+
+/// Absolute form of `path` with symlinks, `.` and `..` resolved as far as it exists. None for
+/// a path that climbs out of a missing directory (`missing/../x`).
+fn resolve(path: &Path) -> Option<PathBuf> {
+    let absolute = if path.is_absolute() { path.to_path_buf() } else { std::env::current_dir().ok()?.join(path) };
+    let mut existing = absolute.as_path();
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(resolved) = existing.canonicalize() {
+            return Some(missing.iter().rev().fold(resolved, |path, name| path.join(name)));
+        }
+        missing.push(existing.file_name()?);
+        existing = existing.parent()?;
+    }
+}
+
+impl Policy {
+    fn contains(&self, path: &Path) -> bool {
+        resolve(path).is_some_and(|resolved| self.roots.iter().any(|root| resolved.starts_with(root)))
+    }
+
+    fn check_read(&self, path: &Path) -> io::Result<()> {
+        if self.contains(path) {
+            return Ok(());
+        }
+        Err(denied(format!("Access denied: {} is outside the registered project roots", path.display())))
+    }
+
+    fn check_write(&self, path: &Path) -> io::Result<()> {
+        if !self.allow_writes {
+            return Err(denied(format!("Access denied: writes are disabled by the safety config ({})", path.display())));
+        }
+        self.check_read(path)
+    }
+
+    /// Fail once `total` bytes read in this call exceed the budget.
+    fn check_budget(&self, total: u64) -> io::Result<()> {
+        match self.max_bytes_per_call {
+            Some(max) if total > max => Err(denied(format!("Access denied: read budget of {} bytes per call exceeded", max))),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn with_policy(check: impl FnOnce(&Policy) -> io::Result<()>) -> io::Result<()> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    match POLICY.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(policy) => check(policy),
+        None => Ok(()),
+    }
+}
+
+/// Start a new read budget; called only by the outermost `telemetry::call` guard.
+pub(crate) fn begin_call() {
+    BYTES_READ.begin();
+}
+
+/// Fail unless `path` may be read.
+pub fn check_read(path: &Path) -> io::Result<()> {
+    with_policy(|policy| policy.check_read(path))
+}
+
+/// Fail unless `path` may be written, created or removed.
+pub fn check_write(path: &Path) -> io::Result<()> {
+    with_policy(|policy| policy.check_write(path))
+}
+
+/// Count `bytes` against the current call's read budget.
+pub fn charge(bytes: u64) -> io::Result<()> {
+    with_policy(|policy| BYTES_READ.charge(policy, bytes))
+}
+
+/// Check `path` and charge its size before reading it whole.
+fn admit(path: &Path) -> io::Result<()> {
+    with_policy(|policy| {
+        policy.check_read(path)?;
+        let size = fs::metadata(path).map_or(0, |m| m.len());
+        BYTES_READ.charge(policy, size)
+    })
+}
+
+/// `fs::read` under the safety config.
+pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    admit(path.as_ref())?;
+    fs::read(path)
+}
+
+/// `fs::read_to_string` under the safety config.
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    admit(path.as_ref())?;
+    fs::read_to_string(path)
+}
+
+/// `fs::read_dir` under the safety config.
+pub fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<fs::ReadDir> {
+    check_read(path.as_ref())?;
+    fs::read_dir(path)
+}
+
+/// `fs::create_dir_all` under the safety config.
+pub fn create_dir_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    check_write(path.as_ref())?;
+    fs::create_dir_all(path)
+}
+
+/// `fs::remove_file` under the safety config.
+pub fn remove_file<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    check_write(path.as_ref())?;
+    fs::remove_file(path)
+}
+
+/// A directory walk from `root`; yields nothing when `root` may not be read.
+pub fn walk_dir<P: AsRef<Path>>(root: P) -> walkdir::WalkDir {
+    let walk = walkdir::WalkDir::new(root.as_ref());
+    if check_read(root.as_ref()).is_ok() {
+        walk
+    } else {
+        // A minimum depth past the maximum depth makes an empty walk
+        walk.min_depth(1).max_depth(0)
+    }
+}
+
+fn install(policy: Option<Policy>) {
+    let mut active = POLICY.write().unwrap_or_else(|e| e.into_inner());
+    ENABLED.store(policy.is_some(), Ordering::Relaxed);
+    *active = policy;
+}
+
+fn resolve_root(root: &str) -> Result<PathBuf, String> {
+    Path::new(root).canonicalize()
+        .ok()
+        .filter(|p| p.is_dir())
+        .ok_or_else(|| format!("Project root not found: {}", root))
+}
+
+/// Turn on safety mode: file access confined to `project_roots`, writes denied unless
+/// `allow_writes`, and each call failing reads past `max_bytes_per_call`. Replaces any earlier
+/// config.
+#[napi]
+pub fn set_safety_config(config: SafetyConfig) -> serde_json::Value {
+    let roots: Result<Vec<PathBuf>, String> = config.project_roots.iter().map(|r| resolve_root(r)).collect();
+    let roots = match roots {
+        Ok(roots) => roots,
+        Err(e) => return serde_json::json!({"error": e, "is_error": true}),
+    };
+    install(Some(Policy {
+        roots,
+        allow_writes: config.allow_writes.unwrap_or(false),
+        max_bytes_per_call: config.max_bytes_per_call.map(|n| n.max(0) as u64),
+    }));
+    serde_json::json!({"success": true, "safety_config": get_safety_config()})
+}
+
+/// Add a project root to the safety config, turning safety mode on (read-only, no read
+/// budget) if it was off. Returns false when the directory does not exist.
+#[napi]
+pub fn register_project_root(project_root: String) -> bool {
+    let Ok(root) = resolve_root(&project_root) else { return false };
+    let mut policy = POLICY.read().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_default();
+    if !policy.roots.contains(&root) {
+        policy.roots.push(root);
+    }
+    install(Some(policy));
+    true
+}
+
+/// The active safety config, or None when safety mode is off.
+#[napi]
+pub fn get_safety_config() -> Option<SafetyConfig> {
+    POLICY.read().unwrap_or_else(|e| e.into_inner()).as_ref().map(|policy| SafetyConfig {
+        project_roots: policy.roots.iter().map(|r| r.to_string_lossy().to_string()).collect(),
+        allow_writes: Some(policy.allow_writes),
+        max_bytes_per_call: policy.max_bytes_per_call.map(|n| i64::try_from(n).unwrap_or(i64::MAX)),
+    })
+}
+
+/// Turn safety mode off.
+#[napi]
+pub fn clear_safety_config() {
+    install(None);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The napi setters change process-wide state shared with every other test, so these
+    // exercise a local Policy.
+    fn policy(root: &Path, allow_writes: bool, max_bytes_per_call: Option<u64>) -> Policy {
+        Policy { roots: vec![root.canonicalize().unwrap()], allow_writes, max_bytes_per_call }
+    }
+
+    #[test]
+    fn test_reads_confined_to_project_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("Project");
+        fs::create_dir_all(project.join("Assets")).unwrap();
+        fs::write(dir.path().join("secret.txt"), "token").unwrap();
+        let policy = policy(&project, false, None);
+
+        assert!(policy.check_read(&project.join("Assets")).is_ok());
+        assert!(policy.check_read(&project.join("Assets/New/Scene.unity")).is_ok(), "missing files inside a root");
+        assert!(policy.check_read(&dir.path().join("secret.txt")).is_err());
+        assert!(policy.check_read(&project.join("Assets/../../secret.txt")).is_err());
+        assert!(policy.check_read(&project.join("Missing/../../secret.txt")).is_err());
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(dir.path().join("secret.txt"), project.join("Assets/link.txt")).unwrap();
+            assert!(policy.check_read(&project.join("Assets/link.txt")).is_err(), "symlinks resolve before the check");
+        }
+        let error = policy.check_read(Path::new("/etc/passwd")).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn test_writes_need_allow_writes_and_budget_caps_reads() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("Assets/Player.prefab");
+        assert!(policy(dir.path(), false, None).check_write(&target).unwrap_err().to_string().contains("writes are disabled"));
+        assert!(policy(dir.path(), true, None).check_write(&target).is_ok());
+        assert!(policy(dir.path(), true, None).check_write(&std::env::temp_dir().join("elsewhere.txt")).is_err());
+
+        let capped = policy(dir.path(), false, Some(100));
+        assert!(capped.check_budget(100).is_ok());
+        assert!(capped.check_budget(101).unwrap_err().to_string().contains("read budget of 100 bytes"));
+    }
+
+    #[test]
+    fn test_read_budget_spans_nested_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let capped = policy(dir.path(), false, Some(100));
+        let budget = ReadBudget(AtomicU64::new(0));
+
+        // An entry point reads 60 bytes, then calls another entry point that reads 60 more;
+        // the nested guard is not outermost, so the budget carries over and is breached.
+        budget.begin();
+        assert!(budget.charge(&capped, 60).is_ok());
+        assert!(budget.charge(&capped, 60).unwrap_err().to_string().contains("read budget of 100 bytes"));
+        // The next outermost call starts over
+        budget.begin();
+        assert!(budget.charge(&capped, 60).is_ok());
+    }
+}
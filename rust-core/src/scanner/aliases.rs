@@ -0,0 +1,76 @@
+//! Serialized field renames across Unity versions, so a property lookup by either spelling
+//! also finds files written by editors that used the other one. Queries, property histograms,
+//! property filters and the project search index all resolve through this table.
+
+/// One renamed top-level field of a built-in component.
+pub struct PropertyAlias {
+    /// Component classes that serialize the field
+    pub components: &'static [&'static str],
+    pub modern: &'static str,
+    pub legacy: &'static str,
+    /// Unity version that introduced the modern name, when known
+    pub since: Option<&'static str>,
+}
+
+const RENDERERS: &[&str] = &[
+    "MeshRenderer", "SkinnedMeshRenderer", "SpriteRenderer", "LineRenderer",
+    "TrailRenderer", "ParticleSystemRenderer", "BillboardRenderer",
+];
+
+pub const ALIASES: &[PropertyAlias] = &[
+    PropertyAlias { components: &["Rigidbody"], modern: "m_UseGravity", legacy: "useGravity", since: None },
+    PropertyAlias { components: &["Rigidbody"], modern: "m_LinearDamping", legacy: "m_Drag", since: Some("6000.0") },
+    PropertyAlias { components: &["Rigidbody"], modern: "m_AngularDamping", legacy: "m_AngularDrag", since: Some("6000.0") },
+    PropertyAlias { components: &["Rigidbody2D"], modern: "m_LinearDamping", legacy: "m_LinearDrag", since: Some("6000.0") },
+    PropertyAlias { components: &["Rigidbody2D"], modern: "m_AngularDamping", legacy: "m_AngularDrag", since: Some("6000.0") },
+    PropertyAlias { components: &["Camera"], modern: "m_AllowHDR", legacy: "m_HDR", since: Some("5.6") },
+    PropertyAlias { components: RENDERERS, modern: "m_LightProbeUsage", legacy: "m_UseLightProbes", since: Some("5.4") },
+];
+
+/// Field name compared case-insensitively and without the `m_` prefix, as property paths are.
+fn normalize(name: &str) -> String {
+    name.strip_prefix("m_").unwrap_or(name).to_ascii_lowercase()
+}
+
+impl PropertyAlias {
+    /// How the rename is reported when a lookup on `component` falls back to it.
+    pub fn describe(&self, component: &str) -> String {
+        match self.since {
+            Some(version) => format!("{}.{} (named {} before Unity {})", component, self.modern, self.legacy, version),
+            None => format!("{}.{} (named {} in legacy scenes)", component, self.modern, self.legacy),
+        }
+    }
+}
+
+/// The other spellings of field `name` on `component`, with the rename each comes from.
+pub fn alternatives(component: &str, name: &str) -> Vec<(&'static str, &'static PropertyAlias)> {
+    let wanted = normalize(name);
+    ALIASES.iter()
+        .filter(|alias| alias.components.contains(&component))
+        .filter_map(|alias| {
+            if normalize(alias.modern) == wanted {
+                Some((alias.legacy, alias))
+            } else if normalize(alias.legacy) == wanted {
+                Some((alias.modern, alias))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alternatives_both_directions() {
+        let legacy: Vec<&str> = alternatives("Rigidbody", "linearDamping").iter().map(|(name, _)| *name).collect();
+        assert_eq!(legacy, vec!["m_Drag"]);
+        let (modern, alias) = alternatives("Rigidbody2D", "m_LinearDrag")[0];
+        assert_eq!(modern, "m_LinearDamping");
+        assert_eq!(alias.describe("Rigidbody2D"), "Rigidbody2D.m_LinearDamping (named m_LinearDrag before Unity 6000.0)");
+        assert_eq!(alternatives("SkinnedMeshRenderer", "m_LightProbeUsage")[0].0, "m_UseLightProbes");
+        assert!(alternatives("Light", "m_Drag").is_empty());
+    }
+}
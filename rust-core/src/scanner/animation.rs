@@ -0,0 +1,288 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::common::AnimationBindingIssue;
+use super::config::ComponentConfig;
+use super::gameobject::find_transform_id_indexed;
+use super::parser::BlockIndex;
+use super::rendering::{field, ref_file_id};
+use super::transplant::children_of;
+
+static CURVE_SECTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^  (m_\w+Curves):").unwrap()
+});
+static GUID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"guid:[ \t]*([0-9a-fA-F]{32})").unwrap()
+});
+static MOTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_Motion:[ \t]*\{fileID:[ \t]*-?\d+,[ \t]*guid:[ \t]*([0-9a-fA-F]{32})").unwrap()
+});
+static COMP_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"component:[ \t]*\{fileID:[ \t]*(-?\d+)\}").unwrap()
+});
+
+const TRANSFORM_CLASS_ID: u32 = 4;
+const RECT_TRANSFORM_CLASS_ID: u32 = 224;
+const MONO_BEHAVIOUR_CLASS_ID: u32 = 114;
+
+/// Abstract component classes and the concrete classes that satisfy them.
+const ABSTRACT_CLASSES: &[(u32, &[u32])] = &[
+    (8, &[]),                                       // Behaviour: any component
+    (25, &[23, 96, 120, 137, 199, 212, 483693784]), // Renderer
+    (56, &[64, 65, 135, 136]),                      // Collider
+    (53, &[58, 60, 61, 66, 68, 70]),                // Collider2D
+];
+
+/// Transform curve sections and the attribute they animate.
+const TRANSFORM_SECTIONS: &[(&str, &str)] = &[
+    ("m_RotationCurves", "m_LocalRotation"),
+    ("m_CompressedRotationCurves", "m_LocalRotation"),
+    ("m_EulerCurves", "m_LocalEulerAngles"),
+    ("m_PositionCurves", "m_LocalPosition"),
+    ("m_ScaleCurves", "m_LocalScale"),
+];
+
+/// One animated property of an AnimationClip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveBinding {
+    /// Hierarchy path relative to the animated GameObject ("" for the root)
+    pub path: String,
+    pub attribute: String,
+    pub class_id: u32,
+    pub script_guid: Option<String>,
+}
+
+/// Curve bindings of an AnimationClip (editor-only curve copies are skipped).
+pub fn parse_clip_bindings(content: &str) -> Vec<CurveBinding> {
+    let mut bindings: Vec<CurveBinding> = Vec::new();
+    let mut section: Option<String> = None;
+    let mut current: Option<CurveBinding> = None;
+
+    let flush = |current: &mut Option<CurveBinding>, bindings: &mut Vec<CurveBinding>| {
+        if let Some(binding) = current.take() {
+            if !bindings.contains(&binding) {
+                bindings.push(binding);
+            }
+        }
+    };
+
+    for line in content.lines() {
+        if let Some(cap) = CURVE_SECTION_RE.captures(line) {
+            flush(&mut current, &mut bindings);
+            let name = cap[1].to_string();
+            let animated = TRANSFORM_SECTIONS.iter().any(|(s, _)| *s == name)
+                || name == "m_FloatCurves" || name == "m_PPtrCurves";
+            section = Some(name).filter(|_| animated);
+            continue;
+        }
+        if line.starts_with("  ") && !line.starts_with("   ") && !line.starts_with("  - ") {
+            // Another top-level clip field ends the current section
+            flush(&mut current, &mut bindings);
+            section = None;
+            continue;
+        }
+        let Some(section_name) = section.as_deref() else { continue };
+
+        if line.starts_with("  - ") {
+            flush(&mut current, &mut bindings);
+            let (class_id, attribute) = TRANSFORM_SECTIONS.iter()
+                .find(|(s, _)| *s == section_name)
+                .map_or((0, String::new()), |(_, attr)| (TRANSFORM_CLASS_ID, attr.to_string()));
+            current = Some(CurveBinding { path: String::new(), attribute, class_id, script_guid: None });
+        }
+        let Some(binding) = current.as_mut() else { continue };
+        let Some(item_field) = line.strip_prefix("    ").filter(|l| !l.starts_with(' ')) else { continue };
+        let Some((key, value)) = item_field.split_once(':') else { continue };
+        let value = value.trim();
+        match key {
+            "path" => binding.path = value.to_string(),
+            "attribute" => binding.attribute = value.to_string(),
+            "classID" => binding.class_id = value.parse().unwrap_or(binding.class_id),
+            "script" => binding.script_guid = GUID_RE.captures(value).map(|c| c[1].to_lowercase()),
+            _ => {}
+        }
+    }
+    flush(&mut current, &mut bindings);
+
+    bindings
+}
+
+/// GUIDs of the clips referenced by an AnimatorController's states and blend trees.
+pub fn controller_clip_guids(content: &str) -> Vec<String> {
+    let mut guids: Vec<String> = Vec::new();
+    for cap in MOTION_RE.captures_iter(content) {
+        let guid = cap[1].to_lowercase();
+        if !guids.contains(&guid) {
+            guids.push(guid);
+        }
+    }
+    guids
+}
+
+/// Child GameObject of `go_id` with the given name.
+fn find_child(index: &BlockIndex, go_id: &str, name: &str, config: &ComponentConfig) -> Option<String> {
+    let go_block = index.get_by_class_and_id(config.gameobject_class_id, go_id)?;
+    let (_, transform) = index.get(find_transform_id_indexed(index, go_block, config)?)?;
+    children_of(transform).iter().find_map(|transform_id| {
+        let (_, transform) = index.get(transform_id)?;
+        let child_go = field(transform, "m_GameObject").and_then(ref_file_id)?;
+        let child_name = index.get_by_class_and_id(config.gameobject_class_id, child_go)
+            .and_then(|go| field(go, "m_Name"))?;
+        (child_name == name).then(|| child_go.to_string())
+    })
+}
+
+/// Whether a component of class `class_id` satisfies a binding to class `wanted`.
+fn class_satisfies(wanted: u32, class_id: u32) -> bool {
+    if wanted == TRANSFORM_CLASS_ID {
+        return class_id == TRANSFORM_CLASS_ID || class_id == RECT_TRANSFORM_CLASS_ID;
+    }
+    if let Some((_, concrete)) = ABSTRACT_CLASSES.iter().find(|(abstract_id, _)| *abstract_id == wanted) {
+        return concrete.is_empty() || concrete.contains(&class_id);
+    }
+    class_id == wanted
+}
+
+/// Whether a GameObject carries a component satisfying a binding's class (and script).
+fn has_component(index: &BlockIndex, go_block: &str, binding: &CurveBinding) -> bool {
+    let class_ids: Vec<(u32, &str)> = COMP_REF_RE.captures_iter(go_block)
+        .filter_map(|c| index.get(c.get(1)?.as_str()))
+        .collect();
+    let wanted = binding.class_id;
+    class_ids.iter().any(|(class_id, block)| {
+        if wanted == MONO_BEHAVIOUR_CLASS_ID {
+            return *class_id == MONO_BEHAVIOUR_CLASS_ID
+                && binding.script_guid.as_ref().is_none_or(|guid| {
+                    field(block, "m_Script").is_some_and(|s| s.to_lowercase().contains(guid.as_str()))
+                });
+        }
+        class_satisfies(wanted, *class_id)
+    })
+}
+
+/// Whether the bound component serializes the animated property. Material and blend-shape
+/// bindings address renderer internals and are not checked. Transform bindings always
+/// resolve: Euler curves and `localEulerAnglesRaw` drive rotation, which Transform
+/// serializes as `m_LocalRotation` (plus an editor-only `m_LocalEulerAnglesHint`).
+fn has_property(index: &BlockIndex, go_block: &str, binding: &CurveBinding) -> bool {
+    let attribute = binding.attribute.as_str();
+    if attribute.is_empty() || attribute.starts_with("material.") || attribute.starts_with("blendShape.") {
+        return true;
+    }
+    if binding.class_id == TRANSFORM_CLASS_ID {
+        return true;
+    }
+    let root = attribute.split('.').next().unwrap_or(attribute);
+    COMP_REF_RE.captures_iter(go_block)
+        .filter_map(|c| index.get(c.get(1)?.as_str()))
+        .filter(|(class_id, _)| class_satisfies(binding.class_id, *class_id))
+        .any(|(_, block)| field(block, root).is_some())
+}
+
+/// Validate clip bindings against the hierarchy under `root_go_id`.
+/// Returns the issues and the number of bindings checked.
+pub fn validate_bindings(
+    clip: &str,
+    bindings: &[CurveBinding],
+    index: &BlockIndex,
+    root_go_id: &str,
+    config: &ComponentConfig,
+    class_name: &dyn Fn(u32) -> String,
+) -> Vec<AnimationBindingIssue> {
+    let mut issues = Vec::new();
+    for binding in bindings {
+        let issue = |kind: &str, detail: String| AnimationBindingIssue {
+            clip: clip.to_string(),
+            path: binding.path.clone(),
+            attribute: binding.attribute.clone(),
+            class_id: binding.class_id,
+            target_type: class_name(binding.class_id),
+            issue: kind.to_string(),
+            detail,
+        };
+
+        let mut target = Some(root_go_id.to_string());
+        let mut resolved_path: Vec<&str> = Vec::new();
+        for segment in binding.path.split('/').filter(|s| !s.is_empty()) {
+            target = target.and_then(|go| find_child(index, &go, segment, config));
+            if target.is_none() {
+                break;
+            }
+            resolved_path.push(segment);
+        }
+        let Some(target) = target else {
+            let missing = binding.path.split('/').nth(resolved_path.len()).unwrap_or("");
+            issues.push(issue("missing_path", format!("No child \"{}\" under \"{}\"", missing, resolved_path.join("/"))));
+            continue;
+        };
+        if binding.class_id == config.gameobject_class_id {
+            continue;
+        }
+        let Some(go_block) = index.get_by_class_and_id(config.gameobject_class_id, &target) else { continue };
+        if !has_component(index, go_block, binding) {
+            issues.push(issue("missing_component", format!("No {} on \"{}\"", class_name(binding.class_id), binding.path)));
+        } else if !has_property(index, go_block, binding) {
+            issues.push(issue("missing_property", format!("{} has no property \"{}\"", class_name(binding.class_id), binding.attribute)));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIP: &str = "%YAML 1.1\n--- !u!74 &7400000\nAnimationClip:\n  m_Name: Wave\n  m_RotationCurves: []\n  m_PositionCurves:\n  - curve:\n      serializedVersion: 2\n      m_Curve:\n      - time: 0\n        value: {x: 0, y: 0, z: 0}\n    path: Arm/Hand\n  m_FloatCurves:\n  - curve:\n      m_Curve: []\n    attribute: m_Enabled\n    path: Arm\n    classID: 114\n    script: {fileID: 11500000, guid: BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB, type: 3}\n  - curve:\n      m_Curve: []\n    attribute: m_Intensity\n    path: Lamp\n    classID: 108\n    script: {fileID: 0}\n  - curve:\n      m_Curve: []\n    attribute: m_Color.r\n    path: \n    classID: 212\n    script: {fileID: 0}\n  m_PPtrCurves: []\n  m_EditorCurves:\n  - curve:\n      m_Curve: []\n    attribute: m_LocalPosition.x\n    path: Arm/Hand\n    classID: 4\n";
+
+    const SCENE: &str = "%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  - component: {fileID: 3}\n  m_Name: Robot\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Children:\n  - {fileID: 11}\n  m_Father: {fileID: 0}\n\
+--- !u!212 &3\nSpriteRenderer:\n  m_GameObject: {fileID: 1}\n  m_Color: {r: 1, g: 1, b: 1, a: 1}\n\
+--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {fileID: 11}\n  - component: {fileID: 12}\n  m_Name: Arm\n\
+--- !u!4 &11\nTransform:\n  m_GameObject: {fileID: 10}\n  m_Children: []\n  m_Father: {fileID: 2}\n\
+--- !u!114 &12\nMonoBehaviour:\n  m_GameObject: {fileID: 10}\n  m_Enabled: 1\n  m_Script: {fileID: 11500000, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 3}\n";
+
+    #[test]
+    fn test_parse_clip_bindings() {
+        let bindings = parse_clip_bindings(CLIP);
+        assert_eq!(bindings.len(), 4, "editor curves are skipped: {:?}", bindings);
+        assert_eq!(bindings[0], CurveBinding { path: "Arm/Hand".into(), attribute: "m_LocalPosition".into(), class_id: 4, script_guid: None });
+        assert_eq!(bindings[1].script_guid.as_deref(), Some("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"));
+        assert_eq!(bindings[3].path, "");
+    }
+
+    #[test]
+    fn test_controller_clip_guids() {
+        let controller = "--- !u!1102 &1\nAnimatorState:\n  m_Motion: {fileID: 7400000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 2}\n--- !u!1102 &2\nAnimatorState:\n  m_Motion: {fileID: 7400000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 2}\n--- !u!1102 &3\nAnimatorState:\n  m_Motion: {fileID: 0}\n";
+        assert_eq!(controller_clip_guids(controller), vec!["aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"]);
+    }
+
+    #[test]
+    fn test_validate_bindings() {
+        let index = BlockIndex::new(SCENE);
+        let bindings = parse_clip_bindings(CLIP);
+        let issues = validate_bindings("Wave.anim", &bindings, &index, "1", &ComponentConfig::default(), &|id| id.to_string());
+        let kinds: Vec<(&str, &str)> = issues.iter().map(|i| (i.issue.as_str(), i.path.as_str())).collect();
+        assert_eq!(kinds, vec![("missing_path", "Arm/Hand"), ("missing_path", "Lamp")]);
+        assert_eq!(issues[0].detail, "No child \"Hand\" under \"Arm\"");
+
+        let wrong_script: Vec<CurveBinding> = vec![CurveBinding { path: "Arm".into(), attribute: "m_Enabled".into(), class_id: 114, script_guid: Some("c".repeat(32)) }];
+        let issues = validate_bindings("Wave.anim", &wrong_script, &index, "1", &ComponentConfig::default(), &|id| id.to_string());
+        assert_eq!(issues[0].issue, "missing_component");
+
+        let missing_prop = vec![CurveBinding { path: String::new(), attribute: "m_FlipX".into(), class_id: 212, script_guid: None }];
+        let issues = validate_bindings("Wave.anim", &missing_prop, &index, "1", &ComponentConfig::default(), &|id| id.to_string());
+        assert_eq!(issues[0].issue, "missing_property");
+    }
+
+    #[test]
+    fn test_euler_and_abstract_class_bindings_resolve() {
+        let index = BlockIndex::new(SCENE);
+        let clip = "--- !u!74 &7400000\nAnimationClip:\n  m_EulerCurves:\n  - curve:\n      m_Curve: []\n    path: Arm\n  m_FloatCurves:\n  - curve:\n      m_Curve: []\n    attribute: localEulerAnglesRaw.y\n    path: Arm\n    classID: 4\n  - curve:\n      m_Curve: []\n    attribute: m_Color.a\n    path: \n    classID: 25\n  - curve:\n      m_Curve: []\n    attribute: m_Missing\n    path: \n    classID: 25\n";
+        let bindings = parse_clip_bindings(clip);
+        assert_eq!(bindings[0].attribute, "m_LocalEulerAngles");
+        let issues = validate_bindings("Spin.anim", &bindings, &index, "1", &ComponentConfig::default(), &|id| id.to_string());
+        let flagged: Vec<(&str, &str)> = issues.iter().map(|i| (i.issue.as_str(), i.attribute.as_str())).collect();
+        assert_eq!(flagged, vec![("missing_property", "m_Missing")], "Renderer bindings check the SpriteRenderer");
+    }
+}
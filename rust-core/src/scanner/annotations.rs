@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::common::ObjectAnnotation;
+use super::correlate::{correlate, ObjectFingerprint};
+
+/// Annotation store location, relative to the project root.
+pub const ANNOTATIONS_PATH: &str = ".unity-agentic/annotations.json";
+
+/// An annotation plus the object identity used to find it again after fileIDs change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredAnnotation {
+    #[serde(flatten)]
+    pub annotation: ObjectAnnotation,
+    /// Component types of the object when it was last seen
+    #[serde(default)]
+    pub components: Vec<String>,
+}
+
+/// Persisted notes and labels on scene/prefab objects of one project.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AnnotationStore {
+    pub next_id: u32,
+    pub annotations: Vec<StoredAnnotation>,
+}
+
+pub fn store_path(project_root: &Path) -> PathBuf {
+    project_root.join(ANNOTATIONS_PATH)
+}
+
+pub fn now_ms() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as i64)
+}
+
+impl AnnotationStore {
+    /// Load the project's store; a missing file is an empty store.
+    pub fn load(project_root: &Path) -> Result<Self, String> {
+        let path = store_path(project_root);
+        match crate::safety::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| format!("Invalid annotation store {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, project_root: &Path) -> Result<(), String> {
+        let path = store_path(project_root);
+        if let Some(dir) = path.parent() {
+            crate::safety::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        crate::common::atomic::write_atomic(&path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Attach a note/labels to an object; returns the new annotation.
+    pub fn add(&mut self, file: &str, object: &ObjectFingerprint, note: Option<String>, labels: Vec<String>) -> ObjectAnnotation {
+        self.next_id += 1;
+        let now = now_ms();
+        let annotation = ObjectAnnotation {
+            id: self.next_id,
+            file: file.to_string(),
+            file_id: object.file_id.clone(),
+            object_name: object.name.clone(),
+            object_path: object.path.clone(),
+            note,
+            labels,
+            created_ms: now,
+            updated_ms: now,
+            remapped_from: None,
+            orphaned: false,
+        };
+        self.annotations.push(StoredAnnotation {
+            annotation: annotation.clone(),
+            components: object.components.iter().map(|(_, t)| t.clone()).collect(),
+        });
+        annotation
+    }
+
+    /// Re-anchor the annotations of `file` to the objects currently in it. Annotations whose
+    /// fileID vanished or now names a different object are matched by path/name/components
+    /// (as in `correlate`); unmatched ones are flagged orphaned. Returns whether anything changed.
+    pub fn resolve(&mut self, file: &str, current: &[ObjectFingerprint]) -> bool {
+        let mut changed = false;
+        for stored in self.annotations.iter_mut().filter(|s| s.annotation.file == file) {
+            let old = ObjectFingerprint {
+                file_id: stored.annotation.file_id.clone(),
+                name: stored.annotation.object_name.clone(),
+                path: stored.annotation.object_path.clone(),
+                components: stored.components.iter().map(|t| (String::new(), t.clone())).collect(),
+            };
+            let (mappings, _, _) = correlate(std::slice::from_ref(&old), current);
+            let matched = mappings.into_iter()
+                .find(|m| m.kind == "GameObject")
+                .and_then(|m| current.iter().find(|o| o.file_id == m.new_file_id));
+            let a = &mut stored.annotation;
+            match matched {
+                Some(object) => {
+                    if object.file_id != a.file_id {
+                        a.remapped_from = Some(std::mem::replace(&mut a.file_id, object.file_id.clone()));
+                        changed = true;
+                    }
+                    if a.orphaned || a.object_name != object.name || a.object_path != object.path {
+                        a.object_name = object.name.clone();
+                        a.object_path = object.path.clone();
+                        a.orphaned = false;
+                        changed = true;
+                    }
+                    stored.components = object.components.iter().map(|(_, t)| t.clone()).collect();
+                }
+                None if !a.orphaned => {
+                    a.orphaned = true;
+                    changed = true;
+                }
+                None => {}
+            }
+        }
+        changed
+    }
+
+    /// Annotations of `file`, optionally only for one object and/or carrying a label.
+    pub fn query(&self, file: &str, file_id: Option<&str>, label: Option<&str>) -> Vec<ObjectAnnotation> {
+        self.annotations.iter()
+            .map(|s| &s.annotation)
+            .filter(|a| a.file == file)
+            .filter(|a| file_id.is_none_or(|id| a.file_id == id))
+            .filter(|a| label.is_none_or(|l| a.labels.iter().any(|x| x == l)))
+            .cloned()
+            .collect()
+    }
+
+    /// Remove an annotation by ID; false when it does not exist.
+    pub fn remove(&mut self, id: u32) -> bool {
+        let before = self.annotations.len();
+        self.annotations.retain(|s| s.annotation.id != id);
+        self.annotations.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(file_id: &str, name: &str, path: &str, components: &[&str]) -> ObjectFingerprint {
+        ObjectFingerprint {
+            file_id: file_id.to_string(),
+            name: name.to_string(),
+            path: path.to_string(),
+            components: components.iter().enumerate().map(|(i, t)| (format!("{}{}", file_id, i), t.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_follows_remapped_file_ids() {
+        let mut store = AnnotationStore::default();
+        let sun = object("3", "Sun", "World/Sun", &["Transform", "Light"]);
+        store.add("Assets/Main.unity", &sun, Some("Key light".into()), vec!["lighting".into()]);
+        store.add("Assets/Main.unity", &object("6", "Marker", "Marker", &[]), None, vec![]);
+        store.add("Assets/Other.unity", &object("3", "Sun", "Sun", &[]), None, vec![]);
+
+        let current = vec![object("30", "Sun", "World/Sun", &["Transform", "Light"]), object("7", "Probe", "Probe", &[])];
+        assert!(store.resolve("Assets/Main.unity", &current));
+        let main = store.query("Assets/Main.unity", None, None);
+        assert_eq!((main[0].file_id.as_str(), main[0].remapped_from.as_deref()), ("30", Some("3")));
+        assert!(main[1].orphaned);
+        assert_eq!(store.query("Assets/Other.unity", Some("3"), None).len(), 1, "other files untouched");
+        assert!(!store.resolve("Assets/Main.unity", &current), "second pass is stable");
+        assert_eq!(store.query("Assets/Main.unity", None, Some("lighting")).len(), 1);
+    }
+
+    #[test]
+    fn test_save_load_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = AnnotationStore::load(dir.path()).unwrap();
+        let id = store.add("Assets/Main.unity", &object("1", "Player", "Player", &["Transform"]), Some("spawn".into()), vec![]).id;
+        store.save(dir.path()).unwrap();
+        let mut loaded = AnnotationStore::load(dir.path()).unwrap();
+        assert_eq!(loaded.annotations[0].components, vec!["Transform"]);
+        assert_eq!(loaded.annotations[0].annotation.note.as_deref(), Some("spawn"));
+        assert!(loaded.remove(id));
+        assert!(!loaded.remove(id));
+    }
+}
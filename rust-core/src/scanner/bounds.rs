@@ -0,0 +1,270 @@
+//! World-space extent of a scene: the box around every placed object, optionally per layer or
+//! tag, and a coarse occupancy grid for top-down overviews.
+
+use std::collections::HashMap;
+
+use crate::common::{OccupancyGrid, Quaternion, SceneAabb, SceneBoundsGroup, SceneBoundsOptions, SceneBoundsReport, Vector3};
+use super::config::ComponentConfig;
+use super::gameobject::find_transform_id_indexed;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::prefab;
+use super::rendering::{field, ref_file_id};
+use super::transform::{local_trs, multiply, rotate, round, scale_by, vector_add, IDENTITY, ONE, ZERO};
+
+const TRANSFORM_CLASS_ID: u32 = 4;
+const PREFAB_INSTANCE_CLASS_ID: u32 = 1001;
+const DEFAULT_GRID_RESOLUTION: u32 = 32;
+const MAX_GRID_RESOLUTION: u32 = 256;
+
+/// World TRS of a Transform and whether its hierarchy is active.
+#[derive(Clone, Copy)]
+struct Frame {
+    position: Vector3,
+    rotation: Quaternion,
+    scale: Vector3,
+    /// False once the chain passes through an object inside a prefab instance
+    exact: bool,
+    active: bool,
+}
+
+const ROOT: Frame = Frame { position: ZERO, rotation: IDENTITY, scale: ONE, exact: true, active: true };
+
+fn compose(parent: Frame, position: Vector3, rotation: Quaternion, scale: Vector3) -> Frame {
+    Frame {
+        position: vector_add(parent.position, rotate(parent.rotation, scale_by(parent.scale, position))),
+        rotation: multiply(parent.rotation, rotation),
+        scale: scale_by(parent.scale, scale),
+        ..parent
+    }
+}
+
+/// Memoized world frames of the file's Transforms and PrefabInstance roots.
+struct Frames<'a> {
+    index: &'a BlockIndex,
+    memo: HashMap<String, Option<Frame>>,
+}
+
+impl Frames<'_> {
+    fn parent(&mut self, id: Option<&str>) -> Option<Frame> {
+        match id.filter(|id| *id != "0") {
+            Some(id) => self.transform(id),
+            None => Some(ROOT),
+        }
+    }
+
+    fn transform(&mut self, id: &str) -> Option<Frame> {
+        if let Some(frame) = self.memo.get(id) {
+            return *frame;
+        }
+        // Marks the id as in progress, so a cyclic m_Father chain ends instead of recursing
+        self.memo.insert(id.to_string(), None);
+        let index = self.index;
+        let (_, block) = index.get(id)?;
+        let frame = match local_trs(block) {
+            Some(local) => {
+                let active = field(block, "m_GameObject").and_then(ref_file_id)
+                    .and_then(|go| index.get(go))
+                    .is_none_or(|(_, go)| field(go, "m_IsActive") != Some("0"));
+                self.parent(field(block, "m_Father").and_then(ref_file_id))
+                    .map(|parent| Frame { active: parent.active && active, ..compose(parent, local.position, local.rotation, local.scale) })
+            }
+            // Stripped: an object of a prefab instance, placed at the instance root
+            None => field(block, "m_PrefabInstance").and_then(ref_file_id)
+                .and_then(|instance| self.instance(instance))
+                .map(|frame| Frame { exact: false, ..frame }),
+        };
+        self.memo.insert(id.to_string(), frame);
+        frame
+    }
+
+    /// Root frame of a PrefabInstance: the local TRS its modifications give the source root
+    /// Transform (the target of the m_LocalPosition overrides), under m_TransformParent.
+    fn instance(&mut self, id: &str) -> Option<Frame> {
+        let key = format!("instance:{}", id);
+        if let Some(frame) = self.memo.get(&key) {
+            return *frame;
+        }
+        self.memo.insert(key.clone(), None);
+        let block = self.index.get_by_class_and_id(PREFAB_INSTANCE_CLASS_ID, id)?;
+        let modifications = prefab::extract_modifications(block);
+        let root = modifications.iter()
+            .find(|m| m.property_path.starts_with("m_LocalPosition."))
+            .map(|m| m.target_file_id.as_str());
+        let value = |path: &str, default: f64| modifications.iter()
+            .find(|m| Some(m.target_file_id.as_str()) == root && m.property_path == path)
+            .and_then(|m| m.value.parse().ok())
+            .unwrap_or(default);
+        let position = Vector3 { x: value("m_LocalPosition.x", 0.0), y: value("m_LocalPosition.y", 0.0), z: value("m_LocalPosition.z", 0.0) };
+        let rotation = Quaternion { x: value("m_LocalRotation.x", 0.0), y: value("m_LocalRotation.y", 0.0), z: value("m_LocalRotation.z", 0.0), w: value("m_LocalRotation.w", 1.0) };
+        let scale = Vector3 { x: value("m_LocalScale.x", 1.0), y: value("m_LocalScale.y", 1.0), z: value("m_LocalScale.z", 1.0) };
+        let frame = self.parent(prefab::extract_transform_parent(block).as_deref())
+            .map(|parent| compose(parent, position, rotation, scale));
+        self.memo.insert(key, frame);
+        frame
+    }
+}
+
+/// A placed object: layer, tag and world position.
+struct Placed {
+    layer: u32,
+    tag: String,
+    position: Vector3,
+    exact: bool,
+}
+
+fn aabb(positions: &[Vector3]) -> Option<SceneAabb> {
+    let first = *positions.first()?;
+    let (mut min, mut max) = (first, first);
+    for p in &positions[1..] {
+        min = Vector3 { x: min.x.min(p.x), y: min.y.min(p.y), z: min.z.min(p.z) };
+        max = Vector3 { x: max.x.max(p.x), y: max.y.max(p.y), z: max.z.max(p.z) };
+    }
+    let rounded = |v: Vector3| Vector3 { x: round(v.x), y: round(v.y), z: round(v.z) };
+    Some(SceneAabb {
+        min: rounded(min),
+        max: rounded(max),
+        center: rounded(Vector3 { x: (min.x + max.x) / 2.0, y: (min.y + max.y) / 2.0, z: (min.z + max.z) / 2.0 }),
+        size: rounded(Vector3 { x: max.x - min.x, y: max.y - min.y, z: max.z - min.z }),
+        object_count: positions.len() as u32,
+    })
+}
+
+/// Object counts on a grid of square cells over the (u, v) extent, `resolution` cells along
+/// the longer side. Rows run from the largest v down, so the grid reads like a map.
+fn occupancy(points: &[(f64, f64)], plane: &str, resolution: u32) -> Option<OccupancyGrid> {
+    let (first_u, first_v) = *points.first()?;
+    let (mut min_u, mut max_u, mut min_v, mut max_v) = (first_u, first_u, first_v, first_v);
+    for &(u, v) in points {
+        (min_u, max_u, min_v, max_v) = (min_u.min(u), max_u.max(u), min_v.min(v), max_v.max(v));
+    }
+    let extent = (max_u - min_u).max(max_v - min_v);
+    let cell_size = if extent > 0.0 { extent / resolution as f64 } else { 1.0 };
+    let span = |extent: f64| ((extent / cell_size).ceil() as u32).clamp(1, resolution);
+    let (columns, rows) = (span(max_u - min_u), span(max_v - min_v));
+
+    let mut cells = vec![0u32; (columns * rows) as usize];
+    for &(u, v) in points {
+        let column = (((u - min_u) / cell_size) as u32).min(columns - 1);
+        let row = rows - 1 - (((v - min_v) / cell_size) as u32).min(rows - 1);
+        cells[(row * columns + column) as usize] += 1;
+    }
+    let rendered = cells.chunks(columns as usize)
+        .map(|row| row.iter().map(|&n| match n {
+            0 => '.',
+            1..=9 => char::from(b'0' + n as u8),
+            _ => '#',
+        }).collect())
+        .collect();
+    Some(OccupancyGrid {
+        plane: plane.to_string(),
+        columns,
+        rows,
+        cell_size: round(cell_size),
+        origin_u: round(min_u),
+        origin_v: round(min_v),
+        cells,
+        rendered,
+    })
+}
+
+/// Bounds of every GameObject with a Transform and every PrefabInstance in `content`, from
+/// composed world positions. RectTransform UI is skipped (it lives in canvas space).
+pub fn scene_bounds(file: &str, content: &str, options: &SceneBoundsOptions, config: &ComponentConfig) -> SceneBoundsReport {
+    let index = BlockIndex::new(content);
+    let mut frames = Frames { index: &index, memo: HashMap::new() };
+    let active_only = options.active_only.unwrap_or(false);
+    let mut placed = Vec::new();
+
+    for (class_id, id, block) in UnityYamlParser::parse_all_blocks(content) {
+        let (frame, layer, tag) = if class_id == config.gameobject_class_id {
+            let Some(transform_id) = find_transform_id_indexed(&index, &block, config)
+                .filter(|t| index.get(t).is_some_and(|(class_id, _)| class_id == TRANSFORM_CLASS_ID)) else { continue };
+            let layer = field(&block, "m_Layer").and_then(|l| l.parse().ok()).unwrap_or(0);
+            (frames.transform(transform_id), layer, field(&block, "m_TagString").unwrap_or("Untagged").to_string())
+        } else if class_id == PREFAB_INSTANCE_CLASS_ID {
+            let modifications = prefab::extract_modifications(&block);
+            let modified = |path: &str| modifications.iter().find(|m| m.property_path == path).map(|m| m.value.clone());
+            let layer = modified("m_Layer").and_then(|l| l.parse().ok()).unwrap_or(0);
+            (frames.instance(&id), layer, modified("m_TagString").unwrap_or_else(|| "Untagged".to_string()))
+        } else {
+            continue;
+        };
+        let Some(frame) = frame.filter(|f| f.active || !active_only) else { continue };
+        placed.push(Placed { layer, tag, position: frame.position, exact: frame.exact });
+    }
+
+    let positions: Vec<Vector3> = placed.iter().map(|p| p.position).collect();
+    let mut groups: Vec<SceneBoundsGroup> = match options.group_by.as_deref() {
+        Some(group_by @ ("layer" | "tag")) => {
+            let mut by_key: HashMap<String, Vec<Vector3>> = HashMap::new();
+            for p in &placed {
+                let key = if group_by == "layer" { p.layer.to_string() } else { p.tag.clone() };
+                by_key.entry(key).or_default().push(p.position);
+            }
+            by_key.into_iter()
+                .filter_map(|(key, positions)| Some(SceneBoundsGroup { key, bounds: aabb(&positions)? }))
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+    groups.sort_by(|a, b| b.bounds.object_count.cmp(&a.bounds.object_count).then_with(|| a.key.cmp(&b.key)));
+
+    let plane = if options.plane.as_deref() == Some("xy") { "xy" } else { "xz" };
+    let resolution = options.grid_resolution.unwrap_or(DEFAULT_GRID_RESOLUTION).min(MAX_GRID_RESOLUTION);
+    let grid = (resolution > 0).then(|| {
+        let points: Vec<(f64, f64)> = positions.iter().map(|p| (p.x, if plane == "xy" { p.y } else { p.z })).collect();
+        occupancy(&points, plane, resolution)
+    }).flatten();
+
+    SceneBoundsReport {
+        file: file.to_string(),
+        object_count: placed.len() as u32,
+        approximate_count: placed.iter().filter(|p| !p.exact).count() as u32,
+        bounds: aabb(&positions),
+        groups,
+        grid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(id: u32, name: &str, layer: u32, active: bool, position: &str, father: u32) -> String {
+        format!("--- !u!1 &{id}\nGameObject:\n  m_Component:\n  - component: {{fileID: {}}}\n  m_Layer: {layer}\n  m_Name: {name}\n  m_TagString: Untagged\n  m_IsActive: {}\n\
+--- !u!4 &{}\nTransform:\n  m_GameObject: {{fileID: {id}}}\n  m_LocalRotation: {{x: 0, y: 0, z: 0, w: 1}}\n  m_LocalPosition: {position}\n  m_LocalScale: {{x: 1, y: 1, z: 1}}\n  m_Children: []\n  m_Father: {{fileID: {father}}}\n",
+            id + 1, u8::from(active), id + 1)
+    }
+
+    #[test]
+    fn test_scene_bounds() {
+        let content = format!("%YAML 1.1\n{}{}{}{}\
+--- !u!1001 &50\nPrefabInstance:\n  m_Modification:\n    serializedVersion: 3\n    m_TransformParent: {{fileID: 0}}\n    m_Modifications:\n    - target: {{fileID: 400, guid: abc, type: 3}}\n      propertyPath: m_LocalPosition.x\n      value: -10\n      objectReference: {{fileID: 0}}\n    - target: {{fileID: 400, guid: abc, type: 3}}\n      propertyPath: m_LocalPosition.z\n      value: -10\n      objectReference: {{fileID: 0}}\n    - target: {{fileID: 100, guid: abc, type: 3}}\n      propertyPath: m_Layer\n      value: 8\n      objectReference: {{fileID: 0}}\n\
+--- !u!4 &60 stripped\nTransform:\n  m_CorrespondingSourceObject: {{fileID: 400, guid: abc, type: 3}}\n  m_PrefabInstance: {{fileID: 50}}\n{}",
+            object(10, "Ground", 0, true, "{x: 0, y: 0, z: 0}", 0),
+            object(20, "Tower", 0, true, "{x: 10, y: 5, z: 10}", 0),
+            object(30, "Flag", 8, true, "{x: 0, y: 2, z: 0}", 21),
+            object(40, "Hidden", 0, false, "{x: 30, y: 0, z: 0}", 0),
+            object(70, "Pickup", 0, true, "{x: 1, y: 0, z: 0}", 60));
+        let config = ComponentConfig::default();
+
+        let report = scene_bounds("Main.unity", &content, &SceneBoundsOptions::default(), &config);
+        assert_eq!((report.object_count, report.approximate_count), (6, 1));
+        let bounds = report.bounds.unwrap();
+        assert_eq!(bounds.min, Vector3 { x: -10.0, y: 0.0, z: -10.0 });
+        assert_eq!(bounds.max, Vector3 { x: 30.0, y: 7.0, z: 10.0 });
+        assert_eq!(bounds.size, Vector3 { x: 40.0, y: 7.0, z: 20.0 });
+
+        let options = SceneBoundsOptions { group_by: Some("layer".to_string()), grid_resolution: Some(4), active_only: Some(true), ..Default::default() };
+        let report = scene_bounds("Main.unity", &content, &options, &config);
+        assert_eq!(report.object_count, 5, "Hidden is skipped");
+        let layers: Vec<(&str, u32)> = report.groups.iter().map(|g| (g.key.as_str(), g.bounds.object_count)).collect();
+        assert_eq!(layers, vec![("0", 3), ("8", 2)]);
+        assert_eq!(report.groups[1].bounds.max, Vector3 { x: 10.0, y: 7.0, z: 10.0 }, "Flag sits on the Tower");
+
+        let grid = report.grid.unwrap();
+        assert_eq!((grid.columns, grid.rows, grid.cell_size), (4, 4, 5.0));
+        assert_eq!(grid.rendered, vec!["...2", "..1.", "....", "2..."]);
+        assert_eq!(grid.cells.iter().sum::<u32>(), 5);
+    }
+}
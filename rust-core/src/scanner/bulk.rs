@@ -0,0 +1,277 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::common::yaml::encode_string;
+use crate::common::{GameObjectChangeReport, GameObjectChanges, GameObjectSelector, PropertyChange};
+use super::config::ComponentConfig;
+use super::gameobject::{build_parent_map, extract_layer, extract_tag};
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::field;
+
+static HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^--- !u!(\d+) &(-?\d+)").unwrap()
+});
+
+/// Highest layer index Unity supports
+const MAX_LAYER: u32 = 31;
+
+/// GameObject properties a bulk edit can set, in the order they are reported.
+const PROPERTIES: [&str; 4] = ["m_Name", "m_TagString", "m_Layer", "m_IsActive"];
+
+/// Reject change sets that would write values Unity cannot load.
+pub fn validate_changes(changes: &GameObjectChanges) -> Result<(), String> {
+    if changes.name.is_none() && changes.tag.is_none() && changes.layer.is_none() && changes.active.is_none() {
+        return Err("No changes given".to_string());
+    }
+    if changes.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        return Err("Name must not be empty".to_string());
+    }
+    if changes.tag.as_deref().is_some_and(|t| t.trim().is_empty()) {
+        return Err("Tag must not be empty".to_string());
+    }
+    if let Some(layer) = changes.layer.filter(|&l| l > MAX_LAYER) {
+        return Err(format!("Layer {} is out of range (0-{})", layer, MAX_LAYER));
+    }
+    Ok(())
+}
+
+fn selector_is_empty(selector: &GameObjectSelector) -> bool {
+    selector.name.is_none() && selector.tag.is_none() && selector.layer.is_none()
+        && selector.component_type.is_none() && selector.active.is_none() && selector.under.is_none()
+}
+
+/// fileIDs of the GameObjects matching every criterion of `selector`, in file order.
+/// Stripped GameObjects (prefab instance placeholders) have no properties and never match.
+pub fn select_gameobjects(
+    content: &str,
+    selector: &GameObjectSelector,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> Vec<String> {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+    let parents = selector.under.as_ref().map(|_| build_parent_map(&blocks, config));
+    let name_glob = selector.name.as_deref().and_then(super::glob_to_regex);
+    let script_re = Regex::new(&config.script_ref_pattern()).expect("Invalid regex");
+
+    let has_component = |go_block: &str, wanted: &str| {
+        UnityYamlParser::parse_component_refs(go_block).iter().any(|comp_id| {
+            let Some((class_id, body)) = index.get(comp_id) else { return false };
+            if body.lines().next().unwrap_or("").trim_end_matches(':') == wanted {
+                return true;
+            }
+            config.is_script_container(class_id) && script_re.captures(body)
+                .and_then(|c| guid_cache.get(c.get(1)?.as_str()))
+                .and_then(|path| Path::new(path).file_stem())
+                .is_some_and(|stem| stem.to_string_lossy() == wanted)
+        })
+    };
+    let is_under = |file_id: &str, ancestor: &str| {
+        let Some(parents) = parents.as_ref() else { return true };
+        let mut seen = HashSet::new();
+        let mut current = file_id;
+        while let Some(parent) = parents.get(current) {
+            if parent == ancestor {
+                return true;
+            }
+            if !seen.insert(parent.as_str()) {
+                return false;
+            }
+            current = parent;
+        }
+        false
+    };
+
+    blocks.iter()
+        .filter(|(class_id, _, _)| *class_id == config.gameobject_class_id)
+        .filter_map(|(_, file_id, body)| Some((file_id, body, field(body, "m_Name")?)))
+        .filter(|(_, _, name)| match (&selector.name, &name_glob) {
+            (_, Some(glob)) => glob.is_match(name),
+            (Some(wanted), None) => name == wanted,
+            (None, None) => true,
+        })
+        .filter(|(_, body, _)| selector.tag.as_ref().is_none_or(|t| extract_tag(body) == *t))
+        .filter(|(_, body, _)| selector.layer.is_none_or(|l| extract_layer(body) == l))
+        .filter(|(_, body, _)| selector.active.is_none_or(|a| (field(body, "m_IsActive") == Some("1")) == a))
+        .filter(|(_, body, _)| selector.component_type.as_deref().is_none_or(|c| has_component(body, c)))
+        .filter(|(file_id, _, _)| selector.under.as_deref().is_none_or(|a| is_under(file_id, a)))
+        .map(|(file_id, _, _)| file_id.clone())
+        .collect()
+}
+
+/// Apply `changes` to every GameObject matching `selector` in one pass over the file.
+///
+/// Only the value text of `m_Name`, `m_TagString`, `m_Layer` and `m_IsActive` lines inside
+/// matched GameObject blocks is rewritten, so everything else stays byte-identical. Returns
+/// the new content and one report per matched object (with no changes when it already had
+/// the requested values). `content` may be the raw file: CRLF line endings are kept.
+pub fn bulk_update(
+    content: &str,
+    selector: &GameObjectSelector,
+    changes: &GameObjectChanges,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> Result<(String, Vec<GameObjectChangeReport>), String> {
+    if selector_is_empty(selector) {
+        return Err("Selector must have at least one criterion".to_string());
+    }
+    validate_changes(changes)?;
+
+    // Selection parses blocks, which needs LF-only content; the edit pass works on the raw lines
+    let matched = select_gameobjects(&crate::common::normalize_line_endings(content.to_string()), selector, guid_cache, config);
+    let wanted: HashSet<&str> = matched.iter().map(String::as_str).collect();
+    let new_values: HashMap<&str, String> = [
+        ("m_Name", changes.name.as_deref().map(encode_string)),
+        ("m_TagString", changes.tag.as_deref().map(encode_string)),
+        ("m_Layer", changes.layer.map(|l| l.to_string())),
+        ("m_IsActive", changes.active.map(|a| if a { "1" } else { "0" }.to_string())),
+    ].into_iter().filter_map(|(key, value)| Some((key, value?))).collect();
+
+    let mut reports: HashMap<&str, GameObjectChangeReport> = HashMap::new();
+    let mut out = String::with_capacity(content.len());
+    let mut current: Option<&str> = None;
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        if let Some(caps) = HEADER_RE.captures(line) {
+            let class_id: u32 = caps[1].parse().unwrap_or(0);
+            let file_id = caps.get(2).map_or("", |m| m.as_str());
+            current = wanted.get(file_id).copied().filter(|_| class_id == config.gameobject_class_id);
+            out.push_str(line);
+            continue;
+        }
+        let Some(file_id) = current else {
+            out.push_str(line);
+            continue;
+        };
+        let body = line.trim_end_matches(['\n', '\r']);
+        let ending = &line[body.len()..];
+        let edit = PROPERTIES.iter().find_map(|&key| {
+            let old = body.strip_prefix("  ")?.strip_prefix(key)?.strip_prefix(':')?;
+            Some((key, old.trim(), new_values.get(key)?))
+        });
+        let report = reports.entry(file_id).or_insert_with(|| GameObjectChangeReport {
+            file_id: file_id.to_string(),
+            name: String::new(),
+            changes: Vec::new(),
+        });
+        if let Some(old) = body.strip_prefix("  m_Name:") {
+            report.name = old.trim().to_string();
+        }
+        match edit {
+            Some((key, old, new)) if old != new => {
+                out.push_str(&format!("  {}: {}{}", key, new, ending));
+                report.changes.push(PropertyChange {
+                    property: key.to_string(),
+                    old_value: old.to_string(),
+                    new_value: new.clone(),
+                    line: (i + 1) as u32,
+                });
+            }
+            _ => out.push_str(line),
+        }
+    }
+
+    let reports = matched.iter()
+        .filter_map(|file_id| reports.remove(file_id.as_str()))
+        .map(|mut report| {
+            report.changes.sort_by_key(|c| PROPERTIES.iter().position(|&p| p == c.property));
+            report
+        })
+        .collect();
+    Ok((out, reports))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Layer: 0\n  m_Name: Level\n  m_TagString: Untagged\n  m_IsActive: 1\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Children:\n  - {fileID: 11}\n  - {fileID: 21}\n  m_Father: {fileID: 0}\n\
+--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {fileID: 11}\n  - component: {fileID: 12}\n  m_Layer: 0\n  m_Name: Enemy (1)\n  m_TagString: Untagged\n  m_IsActive: 1\n\
+--- !u!4 &11\nTransform:\n  m_GameObject: {fileID: 10}\n  m_Children: []\n  m_Father: {fileID: 2}\n\
+--- !u!114 &12\nMonoBehaviour:\n  m_GameObject: {fileID: 10}\n  m_Script: {fileID: 11500000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}\n\
+--- !u!1 &20\nGameObject:\n  m_Component:\n  - component: {fileID: 21}\n  m_Layer: 8\n  m_Name: Enemy (2)\n  m_TagString: Enemy\n  m_IsActive: 0\n\
+--- !u!4 &21\nTransform:\n  m_GameObject: {fileID: 20}\n  m_Children: []\n  m_Father: {fileID: 2}\n\
+--- !u!1 &30\nGameObject:\n  m_Component:\n  - component: {fileID: 31}\n  m_Layer: 0\n  m_Name: Enemy Spawner\n  m_TagString: Untagged\n  m_IsActive: 1\n\
+--- !u!4 &31\nTransform:\n  m_GameObject: {fileID: 30}\n  m_Children: []\n  m_Father: {fileID: 0}\n\
+--- !u!1 &40 stripped\nGameObject:\n  m_CorrespondingSourceObject: {fileID: 100, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 3}\n";
+
+    fn selector() -> GameObjectSelector {
+        GameObjectSelector { name: None, tag: None, layer: None, component_type: None, active: None, under: None }
+    }
+
+    fn changes() -> GameObjectChanges {
+        GameObjectChanges { name: None, tag: None, layer: None, active: None }
+    }
+
+    fn select(selector: GameObjectSelector) -> Vec<String> {
+        let mut cache = HashMap::new();
+        cache.insert("a".repeat(32), "Assets/Scripts/EnemyAI.cs".to_string());
+        select_gameobjects(SCENE, &selector, &cache, &ComponentConfig::default())
+    }
+
+    #[test]
+    fn test_select_gameobjects() {
+        assert_eq!(select(GameObjectSelector { name: Some("Enemy (?)".into()), ..selector() }), vec!["10", "20"]);
+        assert_eq!(select(GameObjectSelector { name: Some("enemy*".into()), ..selector() }), vec!["10", "20", "30"]);
+        assert_eq!(select(GameObjectSelector { name: Some("Enemy Spawner".into()), ..selector() }), vec!["30"]);
+        assert_eq!(select(GameObjectSelector { tag: Some("Untagged".into()), ..selector() }), vec!["1", "10", "30"]);
+        assert_eq!(select(GameObjectSelector { layer: Some(8), ..selector() }), vec!["20"]);
+        assert_eq!(select(GameObjectSelector { active: Some(false), ..selector() }), vec!["20"]);
+        assert_eq!(select(GameObjectSelector { component_type: Some("EnemyAI".into()), ..selector() }), vec!["10"]);
+        assert_eq!(select(GameObjectSelector { component_type: Some("MonoBehaviour".into()), ..selector() }), vec!["10"]);
+        assert_eq!(select(GameObjectSelector { under: Some("1".into()), ..selector() }), vec!["10", "20"]);
+        assert_eq!(select(GameObjectSelector { under: Some("1".into()), active: Some(true), ..selector() }), vec!["10"]);
+    }
+
+    #[test]
+    fn test_bulk_update_rewrites_only_matched_values() {
+        let sel = GameObjectSelector { under: Some("1".into()), ..selector() };
+        let set = GameObjectChanges { tag: Some("Enemy".into()), layer: Some(8), active: Some(true), ..changes() };
+        let (out, reports) = bulk_update(SCENE, &sel, &set, &HashMap::new(), &ComponentConfig::default()).unwrap();
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].file_id, "10");
+        assert_eq!(reports[0].name, "Enemy (1)");
+        let props: Vec<(&str, &str, &str)> = reports[0].changes.iter()
+            .map(|c| (c.property.as_str(), c.old_value.as_str(), c.new_value.as_str()))
+            .collect();
+        assert_eq!(props, vec![("m_TagString", "Untagged", "Enemy"), ("m_Layer", "0", "8")]);
+        let only: Vec<&str> = reports[1].changes.iter().map(|c| c.property.as_str()).collect();
+        assert_eq!(only, vec!["m_IsActive"], "unchanged values are not reported");
+
+        let expected = SCENE
+            .replacen("m_Layer: 0\n  m_Name: Enemy (1)\n  m_TagString: Untagged", "m_Layer: 8\n  m_Name: Enemy (1)\n  m_TagString: Enemy", 1)
+            .replacen("m_TagString: Enemy\n  m_IsActive: 0", "m_TagString: Enemy\n  m_IsActive: 1", 1);
+        assert_eq!(out, expected);
+        let line = reports[1].changes[0].line as usize;
+        assert_eq!(out.lines().nth(line - 1), Some("  m_IsActive: 1"));
+    }
+
+    #[test]
+    fn test_bulk_update_quotes_names_and_keeps_crlf() {
+        let content = SCENE.replace('\n', "\r\n");
+        let sel = GameObjectSelector { name: Some("Level".into()), ..selector() };
+        let set = GameObjectChanges { name: Some("Level: Intro".into()), ..changes() };
+        let (out, reports) = bulk_update(&content, &sel, &set, &HashMap::new(), &ComponentConfig::default()).unwrap();
+        assert!(out.contains("  m_Name: 'Level: Intro'\r\n  m_TagString"));
+        assert_eq!(out.len(), content.len() + "'Level: Intro'".len() - "Level".len());
+        assert_eq!(reports[0].changes[0].new_value, "'Level: Intro'");
+    }
+
+    #[test]
+    fn test_bulk_update_rejects_bad_input() {
+        let config = ComponentConfig::default();
+        let cache = HashMap::new();
+        let set = GameObjectChanges { layer: Some(3), ..changes() };
+        assert!(bulk_update(SCENE, &selector(), &set, &cache, &config).is_err(), "empty selector");
+        let sel = GameObjectSelector { tag: Some("Enemy".into()), ..selector() };
+        assert!(bulk_update(SCENE, &sel, &changes(), &cache, &config).is_err(), "no changes");
+        let set = GameObjectChanges { layer: Some(32), ..changes() };
+        assert!(bulk_update(SCENE, &sel, &set, &cache, &config).is_err());
+        let set = GameObjectChanges { tag: Some(" ".into()), ..changes() };
+        assert!(bulk_update(SCENE, &sel, &set, &cache, &config).is_err());
+    }
+}
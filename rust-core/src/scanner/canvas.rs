@@ -0,0 +1,166 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::common::CanvasInfo;
+use super::config::ComponentConfig;
+use super::gameobject::build_parent_map;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::{field, flag, owner_game_object, ref_file_id};
+
+const CANVAS_CLASS_ID: u32 = 223;
+
+/// Audit every Canvas in a scene or prefab: render mode, sorting, nesting depth and common mistakes.
+pub fn audit_canvases(file: &str, content: &str, config: &ComponentConfig) -> Vec<CanvasInfo> {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+
+    let parents = build_parent_map(&blocks, config);
+    let parent_go = |go: &str| parents.get(go).map(String::as_str);
+
+    let canvases: Vec<(&String, &String)> = blocks.iter()
+        .filter(|(cid, _, body)| *cid == CANVAS_CLASS_ID && field(body, "m_GameObject").is_some())
+        .map(|(_, file_id, body)| (file_id, body))
+        .collect();
+    let canvas_gos: HashSet<String> = canvases.iter()
+        .filter_map(|(_, body)| field(body, "m_GameObject").and_then(ref_file_id).map(str::to_string))
+        .collect();
+
+    let mut results: Vec<CanvasInfo> = canvases.iter()
+        .map(|(file_id, body)| {
+            let (game_object_id, game_object_name) = owner_game_object(&index, body);
+
+            let mut nesting_depth = 0u32;
+            let mut current = parent_go(&game_object_id);
+            let mut guard = 0;
+            while let Some(go) = current {
+                if canvas_gos.contains(go) {
+                    nesting_depth += 1;
+                }
+                guard += 1;
+                if guard > 1000 {
+                    break;
+                }
+                current = parent_go(go);
+            }
+
+            let render_mode = match field(body, "m_RenderMode") {
+                Some("1") => "screen_space_camera",
+                Some("2") => "world_space",
+                _ => "screen_space_overlay",
+            };
+            let has_camera = field(body, "m_Camera")
+                .and_then(ref_file_id)
+                .is_some_and(|id| id != "0");
+
+            let mut issues = Vec::new();
+            if render_mode == "world_space" && !has_camera {
+                issues.push("world_space_without_camera".to_string());
+            }
+            if render_mode == "screen_space_camera" && !has_camera {
+                issues.push("screen_space_camera_without_camera".to_string());
+            }
+
+            CanvasInfo {
+                file: file.to_string(),
+                file_id: file_id.to_string(),
+                game_object_id,
+                game_object_name,
+                render_mode: render_mode.to_string(),
+                sort_order: field(body, "m_SortingOrder").and_then(|v| v.parse().ok()).unwrap_or(0),
+                sorting_layer_id: field(body, "m_SortingLayerID").and_then(|v| v.parse().ok()).unwrap_or(0),
+                override_sorting: flag(body, "m_OverrideSorting"),
+                target_display: field(body, "m_TargetDisplay").and_then(|v| v.parse().ok()).unwrap_or(0),
+                nesting_depth,
+                has_camera,
+                issues,
+            }
+        })
+        .collect();
+
+    flag_duplicate_sort_orders(&mut results);
+    results
+}
+
+/// Flag screen-space canvases that draw in the same slot (display, sorting layer, sort order).
+/// Nested canvases only take part when they override sorting; otherwise they inherit the parent's.
+fn flag_duplicate_sort_orders(canvases: &mut [CanvasInfo]) {
+    let key = |c: &CanvasInfo| -> Option<(u32, i64, i32)> {
+        let sorts_independently = c.nesting_depth == 0 || c.override_sorting;
+        (sorts_independently && c.render_mode != "world_space")
+            .then_some((c.target_display, c.sorting_layer_id, c.sort_order))
+    };
+
+    let mut counts: HashMap<(u32, i64, i32), u32> = HashMap::new();
+    for canvas in canvases.iter() {
+        if let Some(k) = key(canvas) {
+            *counts.entry(k).or_insert(0) += 1;
+        }
+    }
+    for canvas in canvases.iter_mut() {
+        if key(canvas).is_some_and(|k| counts[&k] > 1) {
+            canvas.issues.push("duplicate_sort_order".to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas_block(id: u32, go: u32, render_mode: u32, sort_order: i32, override_sorting: u32) -> String {
+        format!("--- !u!223 &{id}
+Canvas:
+  m_GameObject: {{fileID: {go}}}
+  m_Enabled: 1
+  serializedVersion: 3
+  m_RenderMode: {render_mode}
+  m_Camera: {{fileID: 0}}
+  m_OverrideSorting: {override_sorting}
+  m_SortingLayerID: 0
+  m_SortingOrder: {sort_order}
+  m_TargetDisplay: 0
+")
+    }
+
+    fn go_block(id: u32, name: &str, transform: u32, father: u32) -> String {
+        format!("--- !u!1 &{id}
+GameObject:
+  m_Component:
+  - component: {{fileID: {transform}}}
+  m_Name: {name}
+  m_IsActive: 1
+--- !u!224 &{transform}
+RectTransform:
+  m_GameObject: {{fileID: {id}}}
+  m_Father: {{fileID: {father}}}
+")
+    }
+
+    #[test]
+    fn test_audit_canvases() {
+        let content = [
+            go_block(1, "HUD", 11, 0),
+            canvas_block(12, 1, 0, 5, 0),
+            go_block(2, "Menu", 21, 0),
+            canvas_block(22, 2, 0, 5, 0),
+            go_block(3, "Popup", 31, 11),
+            canvas_block(32, 3, 0, 5, 0),
+            go_block(4, "WorldLabel", 41, 0),
+            canvas_block(42, 4, 2, 0, 0),
+        ].concat();
+
+        let canvases = audit_canvases("UI.unity", &content, &ComponentConfig::default());
+        assert_eq!(canvases.len(), 4);
+
+        let by_name = |name: &str| canvases.iter().find(|c| c.game_object_name == name).unwrap();
+        assert_eq!(by_name("HUD").issues, vec!["duplicate_sort_order".to_string()]);
+        assert_eq!(by_name("Menu").issues, vec!["duplicate_sort_order".to_string()]);
+
+        let popup = by_name("Popup");
+        assert_eq!(popup.nesting_depth, 1);
+        assert!(popup.issues.is_empty(), "nested canvas without override inherits sorting");
+
+        let world = by_name("WorldLabel");
+        assert_eq!(world.render_mode, "world_space");
+        assert_eq!(world.issues, vec!["world_space_without_camera".to_string()]);
+    }
+}
@@ -0,0 +1,145 @@
+//! Prefab catalog: one summary per prefab asset in the project (root object, object and
+//! component counts, nested prefabs, size), built in a single parallel pass.
+
+use rayon::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use crate::common::{self, PrefabCatalog, PrefabCatalogEntry};
+use super::config::ComponentConfig;
+use super::parser::UnityYamlParser;
+use super::prefab::{extract_name_from_modifications, extract_source_guid, extract_transform_parent};
+use super::rendering::{field, ref_file_id};
+use super::stats::compute_stats;
+
+/// Label of a referenced prefab: its project-relative path, or "guid:<guid>" when unresolved.
+fn prefab_label(guid: &str, guid_cache: &HashMap<String, String>) -> String {
+    guid_cache.get(guid).cloned().unwrap_or_else(|| format!("guid:{}", guid))
+}
+
+/// Summarize one prefab. `relative` is its project-relative path.
+pub fn catalog_entry(
+    relative: &str,
+    content: &str,
+    size_bytes: i64,
+    guid: Option<String>,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> PrefabCatalogEntry {
+    let stats = compute_stats(relative, content, guid_cache, config);
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+
+    // The root GameObject owns the hierarchy provider without a father
+    let root_go = blocks.iter()
+        .filter(|(class_id, _, _)| config.hierarchy_providers.contains(class_id))
+        .filter(|(_, _, body)| field(body, "m_Father").and_then(ref_file_id) == Some("0"))
+        .find_map(|(_, _, body)| field(body, "m_GameObject").and_then(ref_file_id))
+        .and_then(|go_id| {
+            let go = UnityYamlParser::extract_gameobjects_with_config(content, config)
+                .into_iter()
+                .find(|go| go.file_id == go_id)?;
+            Some((go.file_id, go.name))
+        });
+
+    let instances: Vec<&(u32, String, String)> = blocks.iter().filter(|(class_id, _, _)| *class_id == 1001).collect();
+    // A variant has no GameObject of its own at the root, only the base prefab's instance
+    let root_instance = match root_go {
+        Some(_) => None,
+        None => instances.iter().find(|(_, _, body)| extract_transform_parent(body).is_none()),
+    };
+    let variant_of = root_instance
+        .and_then(|(_, _, body)| extract_source_guid(body))
+        .map(|g| prefab_label(&g, guid_cache));
+    let (root_file_id, root_object) = match (root_go, root_instance) {
+        (Some((id, name)), _) => (Some(id), Some(name)),
+        (None, Some((_, id, body))) => (Some(id.clone()), extract_name_from_modifications(body)),
+        (None, None) => (None, None),
+    };
+
+    let nested_prefabs: BTreeSet<String> = instances.iter()
+        .filter_map(|(_, _, body)| extract_source_guid(body))
+        .map(|g| prefab_label(&g, guid_cache))
+        .collect();
+
+    PrefabCatalogEntry {
+        name: Path::new(relative).file_stem().map_or_else(String::new, |s| s.to_string_lossy().to_string()),
+        path: relative.to_string(),
+        guid,
+        root_object,
+        root_file_id,
+        variant_of,
+        gameobject_count: stats.gameobject_count,
+        component_count: stats.component_count,
+        component_types: stats.component_types,
+        script_types: stats.script_types,
+        nested_prefabs: nested_prefabs.into_iter().collect(),
+        size_bytes,
+    }
+}
+
+/// Catalog every .prefab under `project_root`, reading and summarizing files in parallel.
+/// `guid_cache` maps GUIDs to project-relative paths; entries are sorted by path.
+pub fn catalog(project_root: &str, guid_cache: &HashMap<String, String>, config: &ComponentConfig) -> PrefabCatalog {
+    let root = Path::new(project_root);
+    let guid_by_path: HashMap<&str, &str> = guid_cache.iter().map(|(g, p)| (p.as_str(), g.as_str())).collect();
+    let files = crate::walker::walk_project_files(project_root.to_string(), vec![".prefab".to_string()], None, None);
+
+    let mut prefabs: Vec<PrefabCatalogEntry> = files.par_iter()
+        .filter_map(|file| {
+            let content = common::read_unity_file(file).ok()?;
+            let size_bytes = std::fs::metadata(file).map_or(content.len() as i64, |m| m.len() as i64);
+            let relative = Path::new(file).strip_prefix(root).unwrap_or(Path::new(file)).to_string_lossy().replace('\\', "/");
+            let guid = guid_by_path.get(relative.as_str()).map(|g| g.to_string());
+            Some(catalog_entry(&relative, &content, size_bytes, guid, guid_cache, config))
+        })
+        .collect();
+    prefabs.sort_by(|a, b| a.path.cmp(&b.path));
+
+    PrefabCatalog {
+        project_root: project_root.to_string(),
+        prefab_count: prefabs.len() as u32,
+        total_size_bytes: prefabs.iter().map(|p| p.size_bytes).sum(),
+        prefabs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_GUID: &str = "0123456789abcdef0123456789abcdef";
+    const SCRIPT_GUID: &str = "fedcba9876543210fedcba9876543210";
+
+    #[test]
+    fn test_catalog_entry_counts_and_nesting() {
+        let content = format!("--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {{fileID: 11}}\n  m_Name: Child\n  m_IsActive: 1\n\
+--- !u!4 &11\nTransform:\n  m_GameObject: {{fileID: 10}}\n  m_Children: []\n  m_Father: {{fileID: 21}}\n\
+--- !u!1 &20\nGameObject:\n  m_Component:\n  - component: {{fileID: 21}}\n  - component: {{fileID: 22}}\n  m_Name: Enemy\n  m_IsActive: 1\n\
+--- !u!4 &21\nTransform:\n  m_GameObject: {{fileID: 20}}\n  m_Children:\n  - {{fileID: 11}}\n  m_Father: {{fileID: 0}}\n\
+--- !u!114 &22\nMonoBehaviour:\n  m_GameObject: {{fileID: 20}}\n  m_Script: {{fileID: 11500000, guid: {SCRIPT_GUID}, type: 3}}\n\
+--- !u!1001 &30\nPrefabInstance:\n  m_Modification:\n    m_TransformParent: {{fileID: 21}}\n    m_Modifications: []\n  m_SourcePrefab: {{fileID: 100100000, guid: {BASE_GUID}, type: 3}}\n");
+        let mut cache = HashMap::new();
+        cache.insert(BASE_GUID.to_string(), "Assets/Weapon.prefab".to_string());
+        cache.insert(SCRIPT_GUID.to_string(), "Assets/Scripts/Enemy.cs".to_string());
+
+        let entry = catalog_entry("Assets/Enemy.prefab", &content, 42, None, &cache, &ComponentConfig::default());
+        assert_eq!(entry.name, "Enemy");
+        assert_eq!((entry.root_object.as_deref(), entry.root_file_id.as_deref()), (Some("Enemy"), Some("20")));
+        assert_eq!(entry.variant_of, None);
+        assert_eq!((entry.gameobject_count, entry.component_count), (2, 3));
+        assert_eq!(entry.component_types["Transform"], 2);
+        assert_eq!(entry.script_types, vec!["Enemy"]);
+        assert_eq!(entry.nested_prefabs, vec!["Assets/Weapon.prefab"]);
+    }
+
+    #[test]
+    fn test_catalog_entry_variant_root() {
+        let content = format!("--- !u!1001 &5\nPrefabInstance:\n  m_Modification:\n    m_TransformParent: {{fileID: 0}}\n    m_Modifications:\n    - target: {{fileID: 100, guid: {BASE_GUID}, type: 3}}\n      propertyPath: m_Name\n      value: EnemyVariant\n      objectReference: {{fileID: 0}}\n  m_SourcePrefab: {{fileID: 100100000, guid: {BASE_GUID}, type: 3}}\n");
+        let entry = catalog_entry("Assets/EnemyVariant.prefab", &content, 10, None, &HashMap::new(), &ComponentConfig::default());
+        assert_eq!(entry.root_object.as_deref(), Some("EnemyVariant"));
+        assert_eq!(entry.root_file_id.as_deref(), Some("5"));
+        assert_eq!(entry.variant_of, Some(format!("guid:{}", BASE_GUID)));
+        assert_eq!(entry.nested_prefabs, vec![format!("guid:{}", BASE_GUID)]);
+        assert_eq!(entry.gameobject_count, 0);
+    }
+}
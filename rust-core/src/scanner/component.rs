@@ -2,9 +2,10 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 
-use crate::common::Component;
+use crate::common::{Component, EditorClassIdentifier};
+use crate::telemetry::{self, Phase};
 use super::config::ComponentConfig;
-use super::parser::BlockIndex;
+use super::parser::{find_block, BlockIndex};
 
 // Cached regexes — compiled once, reused across all calls
 static COMP_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
@@ -13,29 +14,23 @@ static COMP_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
 static TYPE_NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^([A-Za-z][A-Za-z0-9_]*):").unwrap()
 });
+// Keys may contain single spaces: Camera serializes "field of view", "near clip plane", ...
 static PROP_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?m)^\s*(m_)?([A-Za-z0-9_]+):\s*(.+)$").unwrap()
+    Regex::new(r"(?m)^[ \t]*(m_)?([A-Za-z0-9_]+(?: [A-Za-z0-9_]+)*):[ \t]*([^\n]+)$").unwrap()
+});
+static EDITOR_CLASS_ID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*m_EditorClassIdentifier:[ \t]*([^\n]*)$").unwrap()
+});
+static MANAGED_TYPE_PART_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(class|ns|asm):[ \t]*([^,}]*)").unwrap()
 });
 static GUID_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"guid:\s*([a-f0-9]{32})").unwrap()
 });
 static EMPTY_KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"^(\s*)(m_)?([A-Za-z0-9_]+):[ \t]*$").unwrap()
+    Regex::new(r"^([ \t]*)(m_)?([A-Za-z0-9_]+(?: [A-Za-z0-9_]+)*):[ \t]*$").unwrap()
 });
 
-/// Extract a block from content by header
-fn extract_block<'a>(content: &'a str, header: &str) -> Option<&'a str> {
-    let start_pos = content.find(header)?;
-    let after_header = &content[start_pos..];
-
-    let end_offset = after_header[header.len()..]
-        .find("--- !u!")
-        .map(|pos| header.len() + pos)
-        .unwrap_or(after_header.len());
-
-    Some(&after_header[..end_offset])
-}
-
 /// Extract all components for a GameObject
 pub fn extract_components(
     content: &str,
@@ -53,8 +48,7 @@ pub fn extract_components_with_config(
     config: &ComponentConfig,
 ) -> Vec<Component> {
     // Find the GameObject block
-    let go_header = format!("--- !u!{} &{}", config.gameobject_class_id, gameobject_file_id);
-    let go_block = match extract_block(content, &go_header) {
+    let go_block = match find_block(content, config.gameobject_class_id, gameobject_file_id) {
         Some(block) => block,
         None => return Vec::new(),
     };
@@ -102,42 +96,24 @@ fn extract_single_component_with_config(
         script_path: None,
         script_guid: None,
         script_name: None,
+        editor_class_identifier: None,
+        enabled: None,
         properties: None,
+        aliases_applied: None,
     };
 
-    // For script containers (MonoBehaviour-like), try to extract script GUID
-    if config.is_script_container(class_id) {
-        let script_pattern = format!(
-            r"--- !u!{} &{}[\s\S]*?{}:\s*\{{fileID:\s*-?\d+,\s*guid:\s*([a-f0-9]{{32}})",
-            class_id,
-            file_id,
-            regex::escape(&config.script_field)
-        );
-        if let Ok(script_re) = Regex::new(&script_pattern) {
-            if let Some(script_caps) = script_re.captures(content) {
-                if let Some(guid_match) = script_caps.get(1) {
-                    let guid = guid_match.as_str().to_string();
-                    component.script_guid = Some(guid.clone());
-
-                    // Try to resolve GUID to path
-                    if let Some(path) = guid_cache.get(&guid) {
-                        component.script_path = Some(path.clone());
-
-                        // Derive script_name from file stem
-                        if let Some(stem) = std::path::Path::new(path)
-                            .file_stem()
-                            .and_then(|s| s.to_str())
-                        {
-                            component.script_name = Some(stem.to_string());
-                        }
-                    }
-                }
-            }
+    if let Some(block) = find_block(content, class_id, file_id) {
+        component.enabled = enabled_state(block);
+        // For script containers (MonoBehaviour-like), try to extract script GUID
+        if config.is_script_container(class_id) {
+            resolve_script_identity(&mut component, block, guid_cache, config);
         }
     }
 
     // Extract properties
-    component.properties = Some(extract_properties(content, file_id, class_id, guid_cache));
+    let mut properties = extract_properties(content, file_id, class_id, guid_cache);
+    apply_property_filters(&mut component, &mut properties, config);
+    component.properties = Some(properties);
 
     Some(component)
 }
@@ -149,6 +125,28 @@ pub fn extract_components_indexed(
     guid_cache: &HashMap<String, String>,
     config: &ComponentConfig,
 ) -> Vec<Component> {
+    collect_components_indexed(index, gameobject_file_id, guid_cache, config, true)
+}
+
+/// Like [`extract_components_indexed`], but skips property extraction (`properties` stays None)
+/// for outputs that never show properties.
+pub(crate) fn extract_component_identities_indexed(
+    index: &BlockIndex,
+    gameobject_file_id: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> Vec<Component> {
+    collect_components_indexed(index, gameobject_file_id, guid_cache, config, false)
+}
+
+fn collect_components_indexed(
+    index: &BlockIndex,
+    gameobject_file_id: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+    with_properties: bool,
+) -> Vec<Component> {
+    let _regex = telemetry::phase(Phase::Regex);
     let go_block = match index.get_by_class_and_id(config.gameobject_class_id, gameobject_file_id) {
         Some(block) => block,
         None => return Vec::new(),
@@ -161,15 +159,26 @@ pub fn extract_components_indexed(
 
     comp_refs
         .iter()
-        .filter_map(|ref_id| extract_single_component_indexed(index, ref_id, guid_cache, config))
+        .filter_map(|ref_id| extract_component_indexed(index, ref_id, guid_cache, config, with_properties))
         .collect()
 }
 
-fn extract_single_component_indexed(
+/// One component block by fileID, with its script identity and filtered properties.
+pub(crate) fn extract_single_component_indexed(
+    index: &BlockIndex,
+    file_id: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> Option<Component> {
+    extract_component_indexed(index, file_id, guid_cache, config, true)
+}
+
+fn extract_component_indexed(
     index: &BlockIndex,
     file_id: &str,
     guid_cache: &HashMap<String, String>,
     config: &ComponentConfig,
+    with_properties: bool,
 ) -> Option<Component> {
     let (class_id, block) = index.get(file_id)?;
 
@@ -184,37 +193,94 @@ fn extract_single_component_indexed(
         script_path: None,
         script_guid: None,
         script_name: None,
+        editor_class_identifier: None,
+        enabled: enabled_state(block),
         properties: None,
+        aliases_applied: None,
     };
 
     // For script containers, extract script GUID from block (not full content)
     if config.is_script_container(class_id) {
-        let script_pattern = format!(
-            r"{}:\s*\{{fileID:\s*-?\d+,\s*guid:\s*([a-f0-9]{{32}})",
-            regex::escape(&config.script_field)
-        );
-        if let Ok(script_re) = Regex::new(&script_pattern) {
-            if let Some(script_caps) = script_re.captures(block) {
-                if let Some(guid_match) = script_caps.get(1) {
-                    let guid = guid_match.as_str().to_string();
-                    component.script_guid = Some(guid.clone());
-                    if let Some(path) = guid_cache.get(&guid) {
-                        component.script_path = Some(path.clone());
-                        if let Some(stem) = std::path::Path::new(path)
-                            .file_stem()
-                            .and_then(|s| s.to_str())
-                        {
-                            component.script_name = Some(stem.to_string());
-                        }
-                    }
-                }
+        resolve_script_identity(&mut component, block, guid_cache, config);
+    }
+
+    if with_properties {
+        let mut properties = extract_properties_from_block(block, guid_cache);
+        apply_property_filters(&mut component, &mut properties, config);
+        component.properties = Some(properties);
+    }
+
+    Some(component)
+}
+
+/// Top-level `m_Enabled` of a component block; None for classes that cannot be disabled.
+fn enabled_state(block: &str) -> Option<bool> {
+    super::rendering::field(block, "m_Enabled").map(|value| value != "0")
+}
+
+/// Fill in script GUID/path/name from the configured script fields and `m_EditorClassIdentifier`.
+/// When the GUID cannot be resolved, the class identifier supplies the script name.
+fn resolve_script_identity(
+    component: &mut Component,
+    block: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) {
+    if let Ok(script_re) = Regex::new(&config.script_ref_pattern()) {
+        if let Some(guid_match) = script_re.captures(block).and_then(|c| c.get(1)) {
+            let guid = guid_match.as_str().to_string();
+            if let Some(path) = guid_cache.get(&guid) {
+                component.script_path = Some(path.clone());
+                component.script_name = std::path::Path::new(path)
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .map(|s| s.to_string());
             }
+            component.script_guid = Some(guid);
         }
     }
 
-    component.properties = Some(extract_properties_from_block(block, guid_cache));
+    component.editor_class_identifier = EDITOR_CLASS_ID_RE.captures(block)
+        .and_then(|c| c.get(1))
+        .and_then(|m| parse_editor_class_identifier(m.as_str()));
+    if component.script_name.is_none() {
+        component.script_name = component.editor_class_identifier.as_ref().map(|id| id.class_name.clone());
+    }
+}
 
-    Some(component)
+/// Parse `Assembly::Namespace.Class` (assembly and namespace are optional).
+pub fn parse_editor_class_identifier(value: &str) -> Option<EditorClassIdentifier> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let (assembly, qualified) = match value.split_once("::") {
+        Some((assembly, rest)) => (Some(assembly.to_string()).filter(|a| !a.is_empty()), rest),
+        None => (None, value),
+    };
+    let (namespace, class_name) = match qualified.rsplit_once('.') {
+        Some((ns, class)) => (Some(ns.to_string()), class),
+        None => (None, qualified),
+    };
+    if class_name.is_empty() {
+        return None;
+    }
+    Some(EditorClassIdentifier {
+        assembly,
+        namespace,
+        class_name: class_name.to_string(),
+    })
+}
+
+/// Apply the config's property whitelists/blacklists, matched by class ID, type name or script name.
+fn apply_property_filters(component: &mut Component, properties: &mut serde_json::Value, config: &ComponentConfig) {
+    let class_key = component.class_id.to_string();
+    let mut keys = vec![class_key.as_str(), component.type_name.as_str()];
+    if let Some(ref script) = component.script_name {
+        keys.push(script.as_str());
+    }
+    let applied = config.filter_properties(&keys, properties);
+    component.aliases_applied = (!applied.is_empty()).then_some(applied);
 }
 
 /// Unity metadata properties that are rarely useful for agents and waste tokens.
@@ -494,12 +560,184 @@ pub(crate) fn extract_properties_from_block(block: &str, guid_cache: &HashMap<St
         break;
     }
 
-    serde_json::Value::Object(parse_map(&lines, &mut i, 2, guid_cache))
+    let mut props = serde_json::Value::Object(parse_map(&lines, &mut i, 2, guid_cache));
+    link_managed_references(&mut props);
+    props
+}
+
+/// Null entry in the [SerializeReference] registry.
+const NULL_RID: &str = "-2";
+
+/// Resolve the `references:` registry written for [SerializeReference] fields (version 2,
+/// `RefIds` list). The raw registry is replaced by `managed_references` (rid, type parts, data)
+/// and every `{rid: N}` value elsewhere is expanded with the referenced type and data.
+fn link_managed_references(props: &mut serde_json::Value) {
+    let Some(map) = props.as_object_mut() else {
+        return;
+    };
+    let Some(ref_ids) = map.get("references")
+        .and_then(|r| r.get("RefIds"))
+        .and_then(|r| r.as_array())
+        .cloned()
+    else {
+        return;
+    };
+
+    let mut registry: HashMap<String, (String, serde_json::Value)> = HashMap::new();
+    let mut entries = Vec::new();
+    for entry in &ref_ids {
+        let Some(rid) = entry.get("rid").and_then(|r| r.as_str()) else {
+            continue;
+        };
+        if rid == NULL_RID {
+            continue;
+        }
+        let type_str = entry.get("type").and_then(|t| t.as_str()).unwrap_or("");
+        let part = |key: &str| -> Option<String> {
+            MANAGED_TYPE_PART_RE.captures_iter(type_str)
+                .find(|c| &c[1] == key)
+                .map(|c| c[2].trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let class_name = part("class").unwrap_or_default();
+        let namespace = part("ns");
+        let full_name = match &namespace {
+            Some(ns) => format!("{}.{}", ns, class_name),
+            None => class_name.clone(),
+        };
+        let data = entry.get("data").cloned().unwrap_or(serde_json::Value::Null);
+
+        entries.push(serde_json::json!({
+            "rid": rid,
+            "class": class_name,
+            "namespace": namespace,
+            "assembly": part("asm"),
+            "data": data,
+        }));
+        registry.insert(rid.to_string(), (full_name, data));
+    }
+
+    map.remove("references");
+    for value in map.values_mut() {
+        expand_rid_refs(value, &registry, &mut Vec::new());
+    }
+    map.insert("managed_references".to_string(), serde_json::Value::Array(entries));
+}
+
+/// Replace `{rid: N}` maps with the referenced object; `visiting` guards against cycles.
+fn expand_rid_refs(
+    value: &mut serde_json::Value,
+    registry: &HashMap<String, (String, serde_json::Value)>,
+    visiting: &mut Vec<String>,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let rid = map.get("rid").and_then(|r| r.as_str()).filter(|_| map.len() == 1).map(|r| r.to_string());
+            if let Some(rid) = rid {
+                if rid == NULL_RID {
+                    *value = serde_json::Value::Null;
+                } else if let Some((type_name, data)) = registry.get(&rid) {
+                    if visiting.contains(&rid) {
+                        *value = serde_json::json!({ "rid": rid, "type": type_name, "cycle": true });
+                    } else {
+                        let mut data = data.clone();
+                        visiting.push(rid.clone());
+                        expand_rid_refs(&mut data, registry, visiting);
+                        visiting.pop();
+                        *value = serde_json::json!({ "rid": rid, "type": type_name, "data": data });
+                    }
+                }
+                return;
+            }
+            for child in map.values_mut() {
+                expand_rid_refs(child, registry, visiting);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                expand_rid_refs(item, registry, visiting);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Number of leading/trailing elements kept when an array is summarized.
+const SUMMARY_EDGE_ITEMS: usize = 3;
+
+/// Replace property values whose serialized size exceeds `max_bytes` with summaries.
+///
+/// Arrays become `{"_summary": "array", "count", "element_type", "first", "last"}` and long
+/// strings (hex blobs, flattened flow sequences) become `{"_summary": "string", "length", "preview"}`.
+/// Works on the parsed structure, so values are never cut mid-token.
+pub fn summarize_large_values(value: &mut serde_json::Value, max_bytes: usize) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for child in map.values_mut() {
+                summarize_large_values(child, max_bytes);
+            }
+        }
+        serde_json::Value::Array(items) if serde_json::to_string(items).map_or(0, |s| s.len()) > max_bytes => {
+            let items = std::mem::take(items);
+            *value = summarize_array(items, max_bytes);
+        }
+        serde_json::Value::String(text) if text.len() > max_bytes => {
+            *value = summarize_string(text, max_bytes);
+        }
+        _ => {}
+    }
+}
+
+fn summarize_array(items: Vec<serde_json::Value>, max_bytes: usize) -> serde_json::Value {
+    let count = items.len();
+    let element_type = match items.first() {
+        Some(serde_json::Value::Object(_)) => "map",
+        Some(serde_json::Value::Array(_)) => "sequence",
+        Some(_) => "scalar",
+        None => "empty",
+    };
+    let edge = |slice: &[serde_json::Value]| -> Vec<serde_json::Value> {
+        slice.iter()
+            .cloned()
+            .map(|mut item| {
+                summarize_large_values(&mut item, max_bytes);
+                item
+            })
+            .collect()
+    };
+    let head = SUMMARY_EDGE_ITEMS.min(count);
+    let tail_start = count.saturating_sub(SUMMARY_EDGE_ITEMS).max(head);
+
+    serde_json::json!({
+        "_summary": "array",
+        "count": count,
+        "element_type": element_type,
+        "first": edge(&items[..head]),
+        "last": edge(&items[tail_start..]),
+    })
+}
+
+fn summarize_string(text: &str, max_bytes: usize) -> serde_json::Value {
+    // Cut the preview at a separator so flow values like "{x: 1, y: 2}" keep whole tokens
+    let mut end = max_bytes.min(text.len());
+    while end > 0 && !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    if let Some(sep) = text[..end].rfind([',', ' ']) {
+        if sep > 0 {
+            end = sep;
+        }
+    }
+
+    serde_json::json!({
+        "_summary": "string",
+        "length": text.len(),
+        "preview": &text[..end],
+    })
 }
 
 pub(crate) fn extract_properties(content: &str, file_id: &str, class_id: u32, guid_cache: &HashMap<String, String>) -> serde_json::Value {
-    let header = format!("--- !u!{} &{}", class_id, file_id);
-    let block = match extract_block(content, &header) {
+    let block = match find_block(content, class_id, file_id) {
         Some(b) => b,
         None => return serde_json::json!({}),
     };
@@ -510,6 +748,127 @@ pub(crate) fn extract_properties(content: &str, file_id: &str, class_id: u32, gu
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_multiple_script_fields_and_editor_class_identifier() {
+        let content = "--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  - component: {fileID: 3}\n  m_Name: Entity\n\
+--- !u!114 &2\nMonoBehaviour:\n  m_GameObject: {fileID: 1}\n  m_EntityScript: {fileID: 11500000, guid: abcdefabcdefabcdefabcdefabcdefab, type: 3}\n  m_EditorClassIdentifier: \n\
+--- !u!114 &3\nMonoBehaviour:\n  m_GameObject: {fileID: 1}\n  m_Script: {fileID: 11500000, guid: 00000000000000000000000000000001, type: 3}\n  m_EditorClassIdentifier: Unity.RenderPipelines.Universal.Runtime::UnityEngine.Rendering.Universal.UniversalAdditionalCameraData\n";
+        let mut cache = HashMap::new();
+        cache.insert("abcdefabcdefabcdefabcdefabcdefab".to_string(), "Assets/Ecs/EntityLink.cs".to_string());
+        let mut config = ComponentConfig::default();
+        config.add_script_field("m_EntityScript");
+
+        let comps = extract_components_with_config(content, "1", &cache, &config);
+        assert_eq!(comps[0].script_name.as_deref(), Some("EntityLink"));
+        assert!(comps[0].editor_class_identifier.is_none());
+
+        let id = comps[1].editor_class_identifier.as_ref().unwrap();
+        assert_eq!(id.assembly.as_deref(), Some("Unity.RenderPipelines.Universal.Runtime"));
+        assert_eq!(id.namespace.as_deref(), Some("UnityEngine.Rendering.Universal"));
+        assert_eq!(id.class_name, "UniversalAdditionalCameraData");
+        assert_eq!(comps[1].script_name.as_deref(), Some("UniversalAdditionalCameraData"), "falls back to the class identifier");
+
+        let indexed = extract_components_indexed(&BlockIndex::new(content), "1", &cache, &config);
+        assert_eq!(indexed[0].script_guid, comps[0].script_guid);
+    }
+
+    #[test]
+    fn test_parse_editor_class_identifier_without_assembly() {
+        let id = parse_editor_class_identifier("PlayerController").unwrap();
+        assert_eq!(id.assembly, None);
+        assert_eq!(id.namespace, None);
+        assert_eq!(id.class_name, "PlayerController");
+        assert!(parse_editor_class_identifier("  ").is_none());
+    }
+
+    #[test]
+    fn test_serialize_reference_registry() {
+        let content = "--- !u!114 &1
+MonoBehaviour:
+  m_Script: {fileID: 11500000, guid: 00000000000000000000000000000001, type: 3}
+  abilities:
+  - rid: 1000
+  - rid: -2
+  primary:
+    rid: 1001
+  references:
+    version: 2
+    RefIds:
+    - rid: 1000
+      type: {class: FireAbility, ns: Game.Abilities, asm: Assembly-CSharp}
+      data:
+        damage: 10
+    - rid: 1001
+      type: {class: ComboAbility, ns: , asm: Assembly-CSharp}
+      data:
+        next:
+          rid: 1001
+    - rid: -2
+      type: {class: , ns: , asm: }
+";
+        let props = extract_properties(content, "1", 114, &HashMap::new());
+        assert!(props.get("references").is_none());
+
+        let registry = props["managed_references"].as_array().unwrap();
+        assert_eq!(registry.len(), 2, "null rid is not listed");
+        assert_eq!(registry[0]["class"], "FireAbility");
+        assert_eq!(registry[0]["namespace"], "Game.Abilities");
+        assert_eq!(registry[0]["assembly"], "Assembly-CSharp");
+        assert!(registry[1]["namespace"].is_null());
+
+        assert_eq!(props["abilities"][0]["type"], "Game.Abilities.FireAbility");
+        assert_eq!(props["abilities"][0]["data"]["damage"], "10");
+        assert!(props["abilities"][1].is_null());
+
+        let primary = &props["primary"];
+        assert_eq!(primary["type"], "ComboAbility");
+        assert_eq!(primary["data"]["next"]["cycle"], true);
+    }
+
+    #[test]
+    fn test_camera_keys_with_spaces_and_whitelist() {
+        let content = "--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Name: Cam\n--- !u!20 &2\nCamera:\n  m_GameObject: {fileID: 1}\n  m_ClearFlags: 1\n  near clip plane: 0.3\n  field of view: 60\n";
+        let mut config = ComponentConfig::default();
+        config.apply_compact_presets();
+        let index = BlockIndex::new(content);
+        let comps = extract_components_indexed(&index, "1", &HashMap::new(), &config);
+        let props = comps[0].properties.as_ref().unwrap();
+        assert_eq!(props["field of view"], "60");
+        assert_eq!(props["near clip plane"], "0.3");
+        assert!(props.get("ClearFlags").is_none(), "filtered by the Camera preset");
+
+        // An empty value never takes the next line
+        assert!(PROP_RE.captures("  field of view:\n  m_Depth: 1").is_none_or(|c| &c[2] != "field of view"));
+        assert_eq!(&PROP_RE.captures("  field of view:\t60").unwrap()[3], "60");
+    }
+
+    #[test]
+    fn test_summarize_large_arrays() {
+        let tiles: String = (0..200)
+            .map(|i| format!("  - first: {{x: {i}, y: 0, z: 0}}\n    second:\n      m_TileIndex: {i}\n"))
+            .collect();
+        let content = format!("--- !u!1839735485 &1\nTilemap:\n  m_Tiles:\n{tiles}  m_AnimationFrameRate: 1\n");
+        let mut props = extract_properties(&content, "1", 1839735485, &HashMap::new());
+        summarize_large_values(&mut props, 1024);
+
+        let summary = &props["Tiles"];
+        assert_eq!(summary["_summary"], "array");
+        assert_eq!(summary["count"], 200);
+        assert_eq!(summary["element_type"], "map");
+        assert_eq!(summary["first"].as_array().unwrap().len(), 3);
+        assert_eq!(summary["last"][2]["second"]["TileIndex"], "199");
+        assert_eq!(props["AnimationFrameRate"], "1", "small values are untouched");
+    }
+
+    #[test]
+    fn test_summarize_long_strings_on_token_boundary() {
+        let mut value = serde_json::json!({"data": "{x: 1.25, y: 2.5, z: 3.75}", "blob": "ab".repeat(100)});
+        summarize_large_values(&mut value, 12);
+        assert_eq!(value["data"]["_summary"], "string");
+        assert_eq!(value["data"]["preview"], "{x: 1.25,");
+        assert_eq!(value["blob"]["length"], 200);
+    }
+
     #[test]
     fn test_extract_properties() {
         let content = "--- !u!4 &123\nTransform:\n  m_ObjectHideFlags: 0\n  m_LocalPosition: {x: 0, y: 0, z: 0}\n  m_LocalScale: {x: 1, y: 1, z: 1}\n";
@@ -935,4 +1294,18 @@ Font:
         let (class_id, _, _) = neg_block.unwrap();
         assert_eq!(*class_id, 114);
     }
+
+    #[test]
+    fn test_enabled_state_surfaced_for_enableable_components() {
+        let content = "--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  - component: {fileID: 3}\n  - component: {fileID: 4}\n  m_Name: Cam\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Father: {fileID: 0}\n\
+--- !u!20 &3\nCamera:\n  m_GameObject: {fileID: 1}\n  m_Enabled: 0\n\
+--- !u!114 &4\nMonoBehaviour:\n  m_GameObject: {fileID: 1}\n  m_Enabled: 1\n  m_Script: {fileID: 11500000, guid: aabbccdd11223344aabbccdd11223344, type: 3}\n";
+        let index = BlockIndex::new(content);
+        let indexed: Vec<Option<bool>> = extract_components_indexed(&index, "1", &HashMap::new(), &ComponentConfig::default())
+            .iter().map(|c| c.enabled).collect();
+        assert_eq!(indexed, vec![None, Some(false), Some(true)]);
+        let scanned: Vec<Option<bool>> = extract_components(content, "1", &HashMap::new()).iter().map(|c| c.enabled).collect();
+        assert_eq!(scanned, indexed);
+    }
 }
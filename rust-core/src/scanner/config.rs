@@ -1,4 +1,20 @@
-use std::collections::HashSet;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::aliases;
+
+/// Project config file location, relative to the project root.
+pub const PROJECT_CONFIG_PATH: &str = ".unity-agentic/config.json";
+
+/// Property include/exclude rules for one component type.
+#[derive(Clone, Debug, Default)]
+pub struct PropertyFilter {
+    /// When set, only these properties are kept.
+    pub include: Option<HashSet<String>>,
+    /// Properties that are always dropped.
+    pub exclude: HashSet<String>,
+}
 
 /// Configuration for Unity component parsing.
 /// Allows customization of which class IDs represent hierarchy providers,
@@ -25,9 +41,67 @@ pub struct ComponentConfig {
     /// Default: "m_Children"
     pub children_field: String,
 
-    /// Field name for script reference in script containers.
-    /// Default: "m_Script"
-    pub script_field: String,
+    /// Field names holding the script reference in script containers, in priority order.
+    /// Default: ["m_Script"]
+    pub script_fields: Vec<String>,
+
+    /// Properties hidden on every component, on top of the built-in metadata blacklist
+    /// (ObjectHideFlags, PrefabInstance, ...). Names are stored without the `m_` prefix.
+    /// Default: empty
+    pub hidden_properties: HashSet<String>,
+
+    /// Per-component property filters, keyed by class ID ("4"), type name ("Transform")
+    /// or script name ("PlayerController").
+    /// Default: empty
+    pub property_filters: HashMap<String, PropertyFilter>,
+
+    /// Human-readable names for custom class IDs (e.g. plugin or ECS types).
+    /// Default: empty
+    pub class_names: HashMap<u32, String>,
+}
+
+/// On-disk shape of `.unity-agentic/config.json`. Class ID lists extend the defaults;
+/// scalar fields replace them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    hierarchy_providers: Vec<u32>,
+    script_containers: Vec<u32>,
+    gameobject_class_id: Option<u32>,
+    parent_field: Option<String>,
+    children_field: Option<String>,
+    script_fields: Vec<String>,
+    hidden_properties: Vec<String>,
+    property_filters: HashMap<String, PropertyFilterFile>,
+    class_names: HashMap<String, String>,
+    /// Named grep file-type groups (`"shaders": [".shader", ".hlsl"]`); read by the walker
+    file_types: HashMap<String, Vec<String>>,
+    /// Directory names or project-relative globs project walks skip; read by the walker
+    skip_dirs: Vec<String>,
+    /// Directories walked even when a skip rule matches them; read by the walker
+    include_dirs: Vec<String>,
+    /// Extra obsolete-API upgrade rules; read by `scan_obsolete_apis`
+    obsolete_apis: Vec<ObsoleteApiRule>,
+}
+
+/// One obsolete-API mapping: a regex over comment- and string-free C# source and its
+/// replacement. A named `api` group narrows the reported span to the obsolete part.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ObsoleteApiRule {
+    /// Rules with the id of a built-in rule replace it
+    pub id: String,
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PropertyFilterFile {
+    include: Option<Vec<String>>,
+    exclude: Vec<String>,
 }
 
 impl Default for ComponentConfig {
@@ -45,7 +119,10 @@ impl Default for ComponentConfig {
             gameobject_class_id: 1,
             parent_field: "m_Father".to_string(),
             children_field: "m_Children".to_string(),
-            script_field: "m_Script".to_string(),
+            script_fields: vec!["m_Script".to_string()],
+            hidden_properties: HashSet::new(),
+            property_filters: HashMap::new(),
+            class_names: HashMap::new(),
         }
     }
 }
@@ -85,6 +162,214 @@ impl ComponentConfig {
     pub fn remove_script_container(&mut self, class_id: u32) {
         self.script_containers.remove(&class_id);
     }
+
+    /// Add a field name that may hold the script reference of a script container.
+    pub fn add_script_field(&mut self, field: &str) {
+        if !self.script_fields.iter().any(|f| f == field) {
+            self.script_fields.push(field.to_string());
+        }
+    }
+
+    /// Regex source matching `{field}: {fileID: N, guid: X` for any configured script field;
+    /// capture group 1 is the GUID.
+    pub fn script_ref_pattern(&self) -> String {
+        let fields: Vec<String> = self.script_fields.iter().map(|f| regex::escape(f)).collect();
+        format!(
+            r"(?:{}):[ \t]*\{{fileID:[ \t]*-?\d+,[ \t]*guid:[ \t]*([a-f0-9]{{32}})",
+            fields.join("|")
+        )
+    }
+
+    /// Hide a property on every component.
+    pub fn add_hidden_property(&mut self, name: &str) {
+        self.hidden_properties.insert(normalize_property_name(name));
+    }
+
+    /// Only keep the given properties for a component (class ID, type name or script name).
+    pub fn set_property_whitelist(&mut self, component: &str, properties: &[String]) {
+        let filter = self.property_filters.entry(component.to_string()).or_default();
+        filter.include = Some(properties.iter().map(|p| normalize_property_name(p)).collect());
+    }
+
+    /// Always drop the given properties for a component (class ID, type name or script name).
+    pub fn set_property_blacklist(&mut self, component: &str, properties: &[String]) {
+        let filter = self.property_filters.entry(component.to_string()).or_default();
+        filter.exclude = properties.iter().map(|p| normalize_property_name(p)).collect();
+    }
+
+    /// Remove any whitelist/blacklist for a component.
+    pub fn clear_property_filter(&mut self, component: &str) {
+        self.property_filters.remove(component);
+    }
+
+    /// Compact presets for common built-in components, to shrink agent-facing output.
+    pub fn apply_compact_presets(&mut self) {
+        let presets: &[(&str, &[&str])] = &[
+            ("Transform", &["LocalPosition", "LocalRotation", "LocalScale"]),
+            ("RectTransform", &["LocalPosition", "LocalRotation", "LocalScale", "AnchorMin", "AnchorMax", "AnchoredPosition", "SizeDelta", "Pivot"]),
+            ("Camera", &["field of view", "near clip plane", "far clip plane", "orthographic", "orthographic size"]),
+            ("Light", &["Type", "Color", "Intensity", "Range", "SpotAngle", "Shadows"]),
+        ];
+        for (component, properties) in presets {
+            let properties: Vec<String> = properties.iter().map(|p| p.to_string()).collect();
+            self.set_property_whitelist(component, &properties);
+        }
+    }
+
+    /// Load a config file and merge it into this config; the format follows the extension
+    /// (`.toml`, anything else is JSON).
+    pub fn load_file(&mut self, path: &Path) -> Result<(), String> {
+        let text = crate::safety::read_to_string(path)
+            .map_err(|e| format!("Cannot read config {}: {}", path.display(), e))?;
+        let is_toml = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let applied = if is_toml { self.apply_toml(&text) } else { self.apply_json(&text) };
+        applied.map_err(|e| format!("Invalid config {}: {}", path.display(), e))
+    }
+
+    /// Merge a JSON config document into this config.
+    pub fn apply_json(&mut self, text: &str) -> Result<(), String> {
+        let file: ConfigFile = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        self.apply_file(file)
+    }
+
+    /// Merge a TOML config document into this config.
+    pub fn apply_toml(&mut self, text: &str) -> Result<(), String> {
+        let file: ConfigFile = toml::from_str(text).map_err(|e| e.to_string())?;
+        self.apply_file(file)
+    }
+
+    fn apply_file(&mut self, file: ConfigFile) -> Result<(), String> {
+        self.hierarchy_providers.extend(file.hierarchy_providers);
+        self.script_containers.extend(file.script_containers);
+        if let Some(id) = file.gameobject_class_id {
+            self.gameobject_class_id = id;
+        }
+        if let Some(field) = file.parent_field {
+            self.parent_field = field;
+        }
+        if let Some(field) = file.children_field {
+            self.children_field = field;
+        }
+        for field in file.script_fields {
+            self.add_script_field(&field);
+        }
+        for name in &file.hidden_properties {
+            self.add_hidden_property(name);
+        }
+        for (component, filter) in file.property_filters {
+            if let Some(include) = filter.include {
+                self.set_property_whitelist(&component, &include);
+            }
+            if !filter.exclude.is_empty() {
+                self.set_property_blacklist(&component, &filter.exclude);
+            }
+        }
+        for (class_id, name) in file.class_names {
+            let id = class_id.parse::<u32>().map_err(|_| format!("class_names key \"{}\" is not a class ID", class_id))?;
+            self.class_names.insert(id, name);
+        }
+        Ok(())
+    }
+
+    /// Apply hidden properties and the filters matching any of `keys` to a properties object.
+    /// A filter naming a field renamed across Unity versions also matches the other spelling;
+    /// the renames that decided a property are returned (see `aliases`).
+    pub fn filter_properties(&self, keys: &[&str], properties: &mut serde_json::Value) -> Vec<String> {
+        if self.hidden_properties.is_empty() && self.property_filters.is_empty() {
+            return Vec::new();
+        }
+        let Some(map) = properties.as_object_mut() else {
+            return Vec::new();
+        };
+        let filters: Vec<&PropertyFilter> = keys.iter()
+            .filter_map(|key| self.property_filters.get(*key))
+            .collect();
+
+        let mut applied = BTreeSet::new();
+        map.retain(|name, _| {
+            let renames: Vec<(String, String)> = keys.iter()
+                .flat_map(|key| {
+                    aliases::alternatives(key, name).into_iter()
+                        .map(|(other, alias)| (normalize_property_name(other), alias.describe(key)))
+                })
+                .collect();
+            let mut listed = |names: &HashSet<String>| {
+                if names.contains(name) {
+                    return true;
+                }
+                match renames.iter().find(|(other, _)| names.contains(other)) {
+                    Some((_, rename)) => {
+                        applied.insert(rename.clone());
+                        true
+                    }
+                    None => false,
+                }
+            };
+            !listed(&self.hidden_properties)
+                && filters.iter().all(|f| f.include.as_ref().is_none_or(&mut listed) && !listed(&f.exclude))
+        });
+        applied.into_iter().collect()
+    }
+}
+
+/// Property names are emitted without Unity's `m_` prefix.
+fn normalize_property_name(name: &str) -> String {
+    name.strip_prefix("m_").unwrap_or(name).to_string()
+}
+
+/// User-level config applying to every project: `~/.unity-agentic/config.json`.
+pub fn global_config_path() -> Option<PathBuf> {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok()?;
+    Some(PathBuf::from(home).join(PROJECT_CONFIG_PATH))
+}
+
+/// Walk directory rules as (skip_dirs, include_dirs), merging the global config (when given)
+/// with the project's `.unity-agentic/config.json`. Missing or invalid configs add nothing.
+pub fn walk_dir_rules(project_root: &Path, global_config: Option<&Path>) -> (Vec<String>, Vec<String>) {
+    let mut skip = Vec::new();
+    let mut include = Vec::new();
+    let project_config = project_root.join(PROJECT_CONFIG_PATH);
+    for path in global_config.into_iter().chain(std::iter::once(project_config.as_path())) {
+        let Ok(text) = crate::safety::read_to_string(path) else { continue };
+        let Ok(file) = serde_json::from_str::<ConfigFile>(&text) else { continue };
+        skip.extend(file.skip_dirs);
+        include.extend(file.include_dirs);
+    }
+    (skip, include)
+}
+
+/// Obsolete-API rules from the global config (when given) and the project's
+/// `.unity-agentic/config.json`, in that order. Missing or invalid configs add nothing.
+pub fn obsolete_api_rules(project_root: &Path, global_config: Option<&Path>) -> Vec<ObsoleteApiRule> {
+    let project_config = project_root.join(PROJECT_CONFIG_PATH);
+    global_config.into_iter()
+        .chain(std::iter::once(project_config.as_path()))
+        .filter_map(|path| crate::safety::read_to_string(path).ok())
+        .filter_map(|text| serde_json::from_str::<ConfigFile>(&text).ok())
+        .flat_map(|file| file.obsolete_apis)
+        .collect()
+}
+
+/// File-type groups defined in a project's `.unity-agentic/config.json`, extensions
+/// lowercased with a leading dot. A missing or invalid config defines none.
+pub fn project_file_types(project_root: &Path) -> HashMap<String, Vec<String>> {
+    let Ok(text) = crate::safety::read_to_string(project_root.join(PROJECT_CONFIG_PATH)) else {
+        return HashMap::new();
+    };
+    let Ok(file) = serde_json::from_str::<ConfigFile>(&text) else {
+        return HashMap::new();
+    };
+    file.file_types.into_iter()
+        .map(|(name, extensions)| {
+            let extensions = extensions.iter()
+                .map(|e| {
+                    let e = e.trim().to_lowercase();
+                    if e.starts_with('.') { e } else { format!(".{e}") }
+                })
+                .collect();
+            (name, extensions)
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -110,6 +395,97 @@ mod tests {
         assert!(config.is_hierarchy_provider(999));
     }
 
+    #[test]
+    fn test_property_filters() {
+        let mut config = ComponentConfig::default();
+        config.set_property_whitelist("Transform", &["m_LocalPosition".to_string(), "LocalScale".to_string()]);
+        config.set_property_blacklist("114", &["Speed".to_string()]);
+        config.add_hidden_property("m_EditorHideFlags");
+
+        let mut transform = serde_json::json!({"LocalPosition": "{x: 0}", "LocalScale": "{x: 1}", "Father": "{fileID: 0}"});
+        config.filter_properties(&["4", "Transform"], &mut transform);
+        assert_eq!(transform.as_object().unwrap().len(), 2);
+        assert!(transform.get("Father").is_none());
+
+        let mut behaviour = serde_json::json!({"Speed": "3", "Health": "100", "EditorHideFlags": "0"});
+        config.filter_properties(&["114", "MonoBehaviour"], &mut behaviour);
+        assert_eq!(behaviour, serde_json::json!({"Health": "100"}));
+
+        config.set_property_whitelist("Rigidbody", &["m_LinearDamping".to_string()]);
+        let mut legacy = serde_json::json!({"Drag": "0.5", "Mass": "1"});
+        let applied = config.filter_properties(&["54", "Rigidbody"], &mut legacy);
+        assert_eq!(legacy, serde_json::json!({"Drag": "0.5"}));
+        assert_eq!(applied, vec!["Rigidbody.m_LinearDamping (named m_Drag before Unity 6000.0)".to_string()]);
+
+        config.clear_property_filter("Transform");
+        let mut unfiltered = serde_json::json!({"Father": "{fileID: 0}"});
+        config.filter_properties(&["4", "Transform"], &mut unfiltered);
+        assert!(unfiltered.get("Father").is_some());
+    }
+
+    #[test]
+    fn test_apply_json_config() {
+        let mut config = ComponentConfig::default();
+        config.apply_json(r#"{
+            "hierarchy_providers": [5000],
+            "script_containers": [5001],
+            "script_fields": ["m_EntityScript"],
+            "hidden_properties": ["m_EditorClassIdentifier"],
+            "property_filters": {"Transform": {"include": ["LocalPosition"]}, "5001": {"exclude": ["Cache"]}},
+            "class_names": {"5000": "EntityTransform"}
+        }"#).unwrap();
+
+        assert!(config.is_hierarchy_provider(4), "lists extend the defaults");
+        assert!(config.is_hierarchy_provider(5000));
+        assert!(config.is_script_container(5001));
+        assert_eq!(config.script_fields, vec!["m_Script".to_string(), "m_EntityScript".to_string()]);
+        assert!(config.hidden_properties.contains("EditorClassIdentifier"));
+        assert!(config.property_filters["Transform"].include.as_ref().unwrap().contains("LocalPosition"));
+        assert!(config.property_filters["5001"].exclude.contains("Cache"));
+        assert_eq!(config.class_names[&5000], "EntityTransform");
+    }
+
+    #[test]
+    fn test_apply_json_config_rejects_unknown_fields() {
+        let mut config = ComponentConfig::default();
+        let err = config.apply_json(r#"{"hierarchy_provider": [1]}"#).unwrap_err();
+        assert!(err.contains("unknown field"));
+    }
+
+    #[test]
+    fn test_load_toml_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, r#"
+hierarchy_providers = [5000]
+hidden_properties = ["m_EditorClassIdentifier"]
+
+[class_names]
+"5000" = "EntityTransform"
+
+[property_filters.Transform]
+include = ["m_LocalPosition"]
+"#).unwrap();
+
+        let mut config = ComponentConfig::default();
+        config.load_file(&path).unwrap();
+        assert!(config.is_hierarchy_provider(5000));
+        assert!(config.hidden_properties.contains("EditorClassIdentifier"));
+        assert_eq!(config.class_names[&5000], "EntityTransform");
+        assert!(config.property_filters["Transform"].include.as_ref().unwrap().contains("LocalPosition"));
+
+        std::fs::write(&path, "hierarchy_provider = [1]").unwrap();
+        assert!(config.load_file(&path).unwrap_err().contains("unknown field"));
+    }
+
+    #[test]
+    fn test_script_ref_pattern_stays_on_one_line() {
+        let re = regex::Regex::new(&ComponentConfig::default().script_ref_pattern()).unwrap();
+        let guid = "0123456789abcdef0123456789abcdef";
+        assert_eq!(&re.captures(&format!("  m_Script: {{fileID: 11500000, guid: {guid}, type: 3}}")).unwrap()[1], guid);
+        assert!(!re.is_match(&format!("  m_Script:\n  {{fileID: 11500000, guid: {guid}, type: 3}}")));
+    }
+
     #[test]
     fn test_add_script_container() {
         let mut config = ComponentConfig::default();
@@ -0,0 +1,119 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::indexer::tokenizer::estimate_tokens;
+
+static REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{fileID:[ \t]*(-?\d+)(?:,[ \t]*guid:[ \t]*([0-9a-fA-F]{32}))?").unwrap()
+});
+
+/// Bookkeeping references that say nothing about what an object is wired to.
+const STRUCTURAL_KEYS: &[&str] = &[
+    "m_GameObject", "m_Script", "m_Father", "m_Children", "m_PrefabInstance",
+    "m_PrefabAsset", "m_CorrespondingSourceObject", "m_Component", "component",
+];
+
+/// One candidate piece of a context pack, ranked by relevance (higher first).
+#[derive(Debug, Clone)]
+pub struct ContextItem {
+    pub kind: &'static str,
+    pub relevance: u32,
+    pub value: serde_json::Value,
+}
+
+/// An object reference found in a component block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockReference {
+    pub property: String,
+    pub file_id: String,
+    pub guid: Option<String>,
+}
+
+/// Non-null, non-structural references held by a component block, in file order.
+pub fn component_references(block: &str) -> Vec<BlockReference> {
+    let mut refs = Vec::new();
+    let mut last_key = String::new();
+    for line in block.lines().skip(1) {
+        let trimmed = line.trim_start();
+        let item = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+        if !item.starts_with('{') {
+            if let Some(key) = item.split(':').next().map(str::trim).filter(|k| !k.is_empty()) {
+                last_key = key.to_string();
+            }
+        }
+        if STRUCTURAL_KEYS.contains(&last_key.as_str()) {
+            continue;
+        }
+        for cap in REF_RE.captures_iter(line) {
+            let file_id = cap[1].to_string();
+            if file_id == "0" {
+                continue;
+            }
+            refs.push(BlockReference {
+                property: last_key.clone(),
+                file_id,
+                guid: cap.get(2).map(|g| g.as_str().to_lowercase()),
+            });
+        }
+    }
+    refs
+}
+
+/// Greedily fill the token budget with the most relevant items.
+/// Returns (included items, omitted summaries, tokens used). Ties keep insertion order.
+pub fn assemble(mut items: Vec<ContextItem>, budget_tokens: u32) -> (Vec<serde_json::Value>, Vec<serde_json::Value>, u32) {
+    items.sort_by_key(|item| std::cmp::Reverse(item.relevance));
+    let mut included = Vec::new();
+    let mut omitted = Vec::new();
+    let mut used = 0u32;
+
+    for item in items {
+        let tokens = estimate_tokens(&item.value.to_string());
+        if used + tokens <= budget_tokens {
+            used += tokens;
+            let mut value = item.value;
+            value["kind"] = serde_json::json!(item.kind);
+            included.push(value);
+        } else {
+            let mut summary = serde_json::json!({ "kind": item.kind, "tokens": tokens });
+            for key in ["name", "file_id", "path"] {
+                if let Some(v) = item.value.get(key) {
+                    summary[key] = v.clone();
+                }
+            }
+            omitted.push(summary);
+        }
+    }
+
+    (included, omitted, used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_references_skips_structural_and_null() {
+        let block = "--- !u!114 &5\nMonoBehaviour:\n  m_GameObject: {fileID: 1}\n  m_Script: {fileID: 11500000, guid: 0123456789abcdef0123456789abcdef, type: 3}\n  target: {fileID: 42}\n  empty: {fileID: 0}\n  spawns:\n  - {fileID: 100100000, guid: FEDCBA9876543210FEDCBA9876543210, type: 3}\n";
+        let refs = component_references(block);
+        assert_eq!(refs, vec![
+            BlockReference { property: "target".into(), file_id: "42".into(), guid: None },
+            BlockReference { property: "spawns".into(), file_id: "100100000".into(), guid: Some("fedcba9876543210fedcba9876543210".into()) },
+        ]);
+    }
+
+    #[test]
+    fn test_assemble_respects_budget_and_relevance() {
+        let items = vec![
+            ContextItem { kind: "child", relevance: 10, value: serde_json::json!({ "name": "Low", "pad": "x".repeat(400) }) },
+            ContextItem { kind: "target", relevance: 100, value: serde_json::json!({ "name": "Focus" }) },
+            ContextItem { kind: "parent", relevance: 50, value: serde_json::json!({ "name": "Mid" }) },
+        ];
+        let (included, omitted, used) = assemble(items, 30);
+        let kinds: Vec<&str> = included.iter().map(|v| v["kind"].as_str().unwrap()).collect();
+        assert_eq!(kinds, vec!["target", "parent"]);
+        assert_eq!(omitted.len(), 1);
+        assert_eq!(omitted[0]["name"], "Low");
+        assert!(used <= 30);
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::common::ObjectCorrelation;
+use super::config::ComponentConfig;
+use super::gameobject::build_parent_map;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::field;
+
+/// A GameObject with the identity features used to recognize it across versions.
+#[derive(Debug, Clone)]
+pub struct ObjectFingerprint {
+    pub file_id: String,
+    pub name: String,
+    /// Names from the root down to the object, joined with '/'
+    pub path: String,
+    /// Component (file ID, type) in GameObject order; MonoBehaviours carry their script GUID
+    pub components: Vec<(String, String)>,
+}
+
+impl ObjectFingerprint {
+    fn component_key(&self) -> String {
+        let mut types: Vec<&str> = self.components.iter().map(|(_, t)| t.as_str()).collect();
+        types.sort_unstable();
+        types.join(",")
+    }
+}
+
+/// Fingerprints of every non-stripped GameObject of Unity YAML content, in file order.
+pub fn fingerprints(content: &str, config: &ComponentConfig) -> Vec<ObjectFingerprint> {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+    let parents = build_parent_map(&blocks, config);
+    let script_re = regex::Regex::new(&config.script_ref_pattern()).expect("Invalid regex");
+    let name_of = |id: &str| index.get_by_class_and_id(config.gameobject_class_id, id)
+        .and_then(|go| field(go, "m_Name"))
+        .unwrap_or("")
+        .to_string();
+
+    blocks.iter()
+        .filter(|(class_id, _, body)| *class_id == config.gameobject_class_id && body.contains("m_Component"))
+        .map(|(_, go_id, body)| {
+            let mut names = vec![name_of(go_id)];
+            let mut seen: HashSet<&str> = HashSet::from([go_id.as_str()]);
+            let mut current = go_id.as_str();
+            while let Some(parent) = parents.get(current).filter(|p| seen.insert(p.as_str())) {
+                names.push(name_of(parent));
+                current = parent;
+            }
+            names.reverse();
+
+            let components = UnityYamlParser::parse_component_refs(body).into_iter()
+                .filter_map(|comp_id| {
+                    let (class_id, comp) = index.get(&comp_id)?;
+                    let mut type_name = comp.lines().next().unwrap_or("").trim_end_matches(':').to_string();
+                    if config.is_script_container(class_id) {
+                        if let Some(guid) = script_re.captures(comp).and_then(|c| c.get(1)) {
+                            type_name = format!("{}:{}", type_name, guid.as_str());
+                        }
+                    }
+                    Some((comp_id, type_name))
+                })
+                .collect();
+
+            ObjectFingerprint {
+                file_id: go_id.clone(),
+                name: name_of(go_id),
+                path: names.join("/"),
+                components,
+            }
+        })
+        .collect()
+}
+
+/// Identity key of one matching pass.
+type MatchKey = fn(&ObjectFingerprint) -> String;
+
+/// Pair up old and new objects whose key is unique on both sides.
+fn match_unique(
+    old: &[ObjectFingerprint],
+    new: &[ObjectFingerprint],
+    old_done: &HashSet<usize>,
+    new_done: &HashSet<usize>,
+    key: MatchKey,
+) -> Vec<(usize, usize)> {
+    let group = |objects: &[ObjectFingerprint], done: &HashSet<usize>| {
+        let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, object) in objects.iter().enumerate().filter(|(i, _)| !done.contains(i)) {
+            groups.entry(key(object)).or_default().push(i);
+        }
+        groups
+    };
+    let old_groups = group(old, old_done);
+    let new_groups = group(new, new_done);
+    let mut pairs: Vec<(usize, usize)> = old_groups.iter()
+        .filter_map(|(k, olds)| {
+            let news = new_groups.get(k)?;
+            (olds.len() == 1 && news.len() == 1).then(|| (olds[0], news[0]))
+        })
+        .collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Match GameObjects (and their components) across two versions of a scene.
+/// Passes, most to least certain: unchanged fileID with the same name; same path and component
+/// set; same path; same name and component set. Keys must be unique on both sides to match.
+/// Returns the mappings plus the unmatched old and new GameObject file IDs.
+pub fn correlate(old: &[ObjectFingerprint], new: &[ObjectFingerprint]) -> (Vec<ObjectCorrelation>, Vec<String>, Vec<String>) {
+    let mut old_done: HashSet<usize> = HashSet::new();
+    let mut new_done: HashSet<usize> = HashSet::new();
+    let mut pairs: Vec<(usize, usize, &str, f64)> = Vec::new();
+
+    let new_by_id: HashMap<&str, usize> = new.iter().enumerate().map(|(i, o)| (o.file_id.as_str(), i)).collect();
+    for (i, object) in old.iter().enumerate() {
+        if let Some(&j) = new_by_id.get(object.file_id.as_str()).filter(|&&j| new[j].name == object.name) {
+            pairs.push((i, j, "same_id", 1.0));
+            old_done.insert(i);
+            new_done.insert(j);
+        }
+    }
+
+    let passes: [(&str, f64, MatchKey); 3] = [
+        ("path_and_components", 0.95, |o| format!("{}|{}", o.path, o.component_key())),
+        ("path", 0.8, |o| o.path.clone()),
+        ("name_and_components", 0.6, |o| format!("{}|{}", o.name, o.component_key())),
+    ];
+    for (method, confidence, key) in passes {
+        for (i, j) in match_unique(old, new, &old_done, &new_done, key) {
+            pairs.push((i, j, method, confidence));
+            old_done.insert(i);
+            new_done.insert(j);
+        }
+    }
+    pairs.sort_by_key(|(i, _, _, _)| *i);
+
+    let mut mappings = Vec::new();
+    for (i, j, method, confidence) in pairs {
+        let (o, n) = (&old[i], &new[j]);
+        mappings.push(ObjectCorrelation {
+            kind: "GameObject".to_string(),
+            old_file_id: o.file_id.clone(),
+            new_file_id: n.file_id.clone(),
+            name: n.name.clone(),
+            path: n.path.clone(),
+            method: method.to_string(),
+            confidence,
+        });
+        // Components pair by type, in order of appearance within each type
+        let mut used: HashSet<usize> = HashSet::new();
+        for (old_comp, comp_type) in &o.components {
+            let Some((k, (new_comp, _))) = n.components.iter().enumerate()
+                .find(|(k, (_, t))| t == comp_type && !used.contains(k)) else { continue };
+            used.insert(k);
+            mappings.push(ObjectCorrelation {
+                kind: "Component".to_string(),
+                old_file_id: old_comp.clone(),
+                new_file_id: new_comp.clone(),
+                name: comp_type.split(':').next().unwrap_or(comp_type).to_string(),
+                path: n.path.clone(),
+                method: method.to_string(),
+                confidence,
+            });
+        }
+    }
+
+    let unmatched_old = old.iter().enumerate().filter(|(i, _)| !old_done.contains(i)).map(|(_, o)| o.file_id.clone()).collect();
+    let unmatched_new = new.iter().enumerate().filter(|(j, _)| !new_done.contains(j)).map(|(_, n)| n.file_id.clone()).collect();
+    (mappings, unmatched_old, unmatched_new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(ids: [u32; 6], extra: &str) -> String {
+        let [root, root_t, child, child_t, light, other] = ids;
+        format!("%YAML 1.1\n\
+--- !u!1 &{root}\nGameObject:\n  m_Component:\n  - component: {{fileID: {root_t}}}\n  m_Name: World\n\
+--- !u!4 &{root_t}\nTransform:\n  m_GameObject: {{fileID: {root}}}\n  m_Children:\n  - {{fileID: {child_t}}}\n  m_Father: {{fileID: 0}}\n\
+--- !u!1 &{child}\nGameObject:\n  m_Component:\n  - component: {{fileID: {child_t}}}\n  - component: {{fileID: {light}}}\n  m_Name: Sun\n\
+--- !u!4 &{child_t}\nTransform:\n  m_GameObject: {{fileID: {child}}}\n  m_Children: []\n  m_Father: {{fileID: {root_t}}}\n\
+--- !u!108 &{light}\nLight:\n  m_GameObject: {{fileID: {child}}}\n\
+--- !u!1 &{other}\nGameObject:\n  m_Component: []\n  m_Name: Marker\n{extra}")
+    }
+
+    #[test]
+    fn test_fingerprints() {
+        let objects = fingerprints(&scene([1, 2, 3, 4, 5, 6], ""), &ComponentConfig::default());
+        let sun = objects.iter().find(|o| o.name == "Sun").unwrap();
+        assert_eq!(sun.path, "World/Sun");
+        assert_eq!(sun.component_key(), "Light,Transform");
+    }
+
+    #[test]
+    fn test_correlate_reassigned_ids() {
+        let config = ComponentConfig::default();
+        let old = fingerprints(&scene([1, 2, 3, 4, 5, 6], ""), &config);
+        let new = fingerprints(&scene([1, 2, 30, 40, 50, 60], "--- !u!1 &70\nGameObject:\n  m_Component: []\n  m_Name: Marker\n"), &config);
+        let (mappings, unmatched_old, unmatched_new) = correlate(&old, &new);
+
+        let find = |old_id: &str| mappings.iter().find(|m| m.old_file_id == old_id).unwrap();
+        assert_eq!(find("1").method, "same_id");
+        assert_eq!((find("3").new_file_id.as_str(), find("3").method.as_str()), ("30", "path_and_components"));
+        assert_eq!(find("5").new_file_id, "50");
+        assert_eq!(find("5").kind, "Component");
+        // Two "Marker" objects in the new version make the match ambiguous
+        assert_eq!(unmatched_old, vec!["6"]);
+        assert_eq!(unmatched_new.len(), 2);
+    }
+}
@@ -0,0 +1,258 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::common::{self, AssetCost, GuidManifest, GuidManifestEntry};
+use super::guid_alias::GuidAliases;
+
+static GUID_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"guid:[ \t]*([0-9a-fA-F]{32})").unwrap()
+});
+
+/// Asset extensions per cost category (lowercase, without the dot).
+const CATEGORIES: &[(&str, &[&str])] = &[
+    ("texture", &["png", "jpg", "jpeg", "tga", "psd", "tif", "tiff", "bmp", "gif", "exr", "hdr", "iff", "pict"]),
+    ("mesh", &["fbx", "obj", "blend", "dae", "3ds", "max", "ma", "mb"]),
+    ("audio", &["wav", "mp3", "ogg", "aif", "aiff", "flac", "mod", "it", "s3m", "xm"]),
+    ("video", &["mp4", "mov", "webm", "avi", "m4v"]),
+    ("font", &["ttf", "otf", "fontsettings"]),
+    ("material", &["mat"]),
+    ("prefab", &["prefab"]),
+    ("animation", &["anim", "controller", "overridecontroller", "mask", "playable"]),
+    ("shader", &["shader", "shadergraph", "compute", "cginc", "hlsl"]),
+    ("script", &["cs", "dll", "asmdef"]),
+];
+
+/// YAML assets whose own references are followed (textures behind materials, meshes behind prefabs, ...).
+const FOLLOWED_EXTENSIONS: &[&str] = &[
+    "prefab", "mat", "controller", "overridecontroller", "anim", "asset", "playable",
+    "spriteatlas", "rendertexture", "physicmaterial", "mixer", "lighting",
+];
+
+/// Source formats that are stored compressed on disk and decoded on import.
+const COMPRESSED_SOURCES: &[&str] = &["png", "jpg", "jpeg", "gif", "mp3", "ogg"];
+
+/// Rough resident size of a streamed AudioClip (stream buffer).
+const AUDIO_STREAM_BUFFER_BYTES: i64 = 200 * 1024;
+
+fn extension(path: &str) -> String {
+    Path::new(path).extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default()
+}
+
+/// Cost category of an asset path ("other" when unknown).
+pub fn asset_category(path: &str) -> &'static str {
+    let ext = extension(path);
+    CATEGORIES.iter()
+        .find(|(_, extensions)| extensions.contains(&ext.as_str()))
+        .map_or("other", |(category, _)| category)
+}
+
+/// GUIDs referenced by Unity YAML content with their reference counts, in first-seen order.
+/// Built-in resources (all-zero GUID prefix) are skipped.
+pub fn referenced_guids(content: &str) -> Vec<(String, u32)> {
+    let mut refs: Vec<(String, u32)> = Vec::new();
+    for cap in GUID_REF_RE.captures_iter(content) {
+        let guid = cap[1].to_lowercase();
+        if guid.starts_with("0000000000000000") {
+            continue;
+        }
+        match refs.iter_mut().find(|(g, _)| *g == guid) {
+            Some((_, count)) => *count += 1,
+            None => refs.push((guid, 1)),
+        }
+    }
+    refs
+}
+
+fn meta_flag(meta: &str, key: &str) -> Option<i64> {
+    meta.lines()
+        .find_map(|line| line.trim_start().strip_prefix(key)?.strip_prefix(':'))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// Heuristic runtime memory of an asset from its source size and import settings (.meta).
+/// Textures: compressed sources decode to ~4x, GPU compression divides by ~4, mipmaps add a
+/// third, Read/Write keeps a CPU copy. Audio: decompress-on-load expands compressed sources ~10x,
+/// streaming keeps only a buffer. Scripts cost nothing at runtime.
+pub fn estimate_memory(category: &str, path: &str, file_bytes: i64, meta: &str) -> i64 {
+    let compressed_source = COMPRESSED_SOURCES.contains(&extension(path).as_str());
+    let readable = meta_flag(meta, "isReadable") == Some(1);
+    match category {
+        "texture" => {
+            let mut bytes = if compressed_source { file_bytes * 4 } else { file_bytes };
+            if meta_flag(meta, "textureCompression").is_some_and(|c| c != 0) {
+                bytes /= 4;
+            }
+            if meta_flag(meta, "enableMipMap").unwrap_or(1) == 1 {
+                bytes += bytes / 3;
+            }
+            if readable { bytes * 2 } else { bytes }
+        }
+        "audio" => match meta_flag(meta, "loadType").unwrap_or(0) {
+            0 if compressed_source => file_bytes * 10,
+            2 => file_bytes.min(AUDIO_STREAM_BUFFER_BYTES),
+            _ => file_bytes,
+        },
+        "mesh" => if readable { file_bytes * 2 } else { file_bytes },
+        "video" => file_bytes.min(AUDIO_STREAM_BUFFER_BYTES),
+        "script" => 0,
+        _ => file_bytes,
+    }
+}
+
+/// Transitive asset dependencies of a scene/prefab. YAML assets (prefabs, materials,
+/// controllers, ...) are followed so their textures and meshes are counted once.
+/// Returns the costed assets and the number of GUIDs missing from the cache.
+pub fn collect_dependencies(
+    content: &str,
+    guid_cache: &HashMap<String, String>,
+    project_root: &Path,
+) -> (Vec<AssetCost>, u32) {
+    let mut assets: Vec<AssetCost> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut unresolved: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<(String, bool)> = VecDeque::from([(content.to_string(), true)]);
+
+    while let Some((source, direct)) = queue.pop_front() {
+        for (guid, count) in referenced_guids(&source) {
+            let Some(relative) = guid_cache.get(&guid) else {
+                unresolved.insert(guid);
+                continue;
+            };
+            if let Some(&i) = positions.get(&guid) {
+                assets[i].references += count;
+                assets[i].direct |= direct;
+                continue;
+            }
+
+            let full = project_root.join(relative);
+            let file_bytes = std::fs::metadata(&full).map(|m| m.len() as i64).unwrap_or(0);
+            let meta = common::read_unity_file(format!("{}.meta", full.to_string_lossy())).unwrap_or_default();
+            let category = asset_category(relative);
+            positions.insert(guid.clone(), assets.len());
+            assets.push(AssetCost {
+                path: relative.clone(),
+                guid,
+                category: category.to_string(),
+                file_bytes,
+                estimated_memory_bytes: estimate_memory(category, relative, file_bytes, &meta),
+                references: count,
+                direct,
+            });
+
+            if FOLLOWED_EXTENSIONS.contains(&extension(relative).as_str()) {
+                if let Ok(nested) = common::read_unity_file(&full) {
+                    queue.push_back((nested, false));
+                }
+            }
+        }
+    }
+
+    (assets, unresolved.len() as u32)
+}
+
+/// The external GUIDs referenced directly by a scene/prefab, sorted by GUID so manifests of two
+/// revisions diff line by line. Built-in resources are left out. Unresolved GUIDs carry what
+/// `aliases` knows of their asset's whereabouts.
+pub fn guid_manifest(file: String, content: &str, guid_cache: &HashMap<String, String>, aliases: &GuidAliases) -> GuidManifest {
+    let mut entries: Vec<GuidManifestEntry> = referenced_guids(content).into_iter()
+        .map(|(guid, references)| {
+            let path = guid_cache.get(&guid).cloned();
+            GuidManifestEntry {
+                category: path.as_deref().map_or("other", asset_category).to_string(),
+                resolution: if path.is_none() { aliases.resolve(&guid, guid_cache) } else { None },
+                guid,
+                path,
+                references,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| a.guid.cmp(&b.guid));
+    GuidManifest {
+        file,
+        guid_count: entries.len() as u32,
+        reference_count: entries.iter().map(|e| e.references).sum(),
+        unresolved_count: entries.iter().filter(|e| e.path.is_none()).count() as u32,
+        entries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_category() {
+        assert_eq!(asset_category("Assets/Art/Hero.PNG"), "texture");
+        assert_eq!(asset_category("Assets/Models/Hero.fbx"), "mesh");
+        assert_eq!(asset_category("Assets/Anim/Hero.controller"), "animation");
+        assert_eq!(asset_category("Assets/Data/Thing.asset"), "other");
+    }
+
+    #[test]
+    fn test_referenced_guids_counts_and_skips_builtin() {
+        let content = "  m_Script: {fileID: 11500000, guid: AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA, type: 3}\n  m_Mesh: {fileID: 10202, guid: 0000000000000000e000000000000000, type: 0}\n  other: {fileID: 2, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}\n";
+        assert_eq!(referenced_guids(content), vec![("a".repeat(32), 2)]);
+    }
+
+    #[test]
+    fn test_guid_manifest_sorted_and_resolved() {
+        let (tex, script) = ("b".repeat(32), "a".repeat(32));
+        let content = format!(
+            "  m_Script: {{fileID: 11500000, guid: {script}, type: 3}}\n  icon: {{fileID: 2800000, guid: {tex}, type: 3}}\n  \
+icon2: {{fileID: 2800000, guid: {tex}, type: 3}}\n  gone: {{fileID: 2, guid: {}, type: 2}}\n", "c".repeat(32)
+        );
+        let cache = HashMap::from([(tex.clone(), "Assets/Art/Icon.png".to_string()), (script.clone(), "Assets/Player.cs".to_string())]);
+        let mut aliases = GuidAliases::default();
+        aliases.history.insert("c".repeat(32), "Assets/Old/Icon.png".to_string());
+        let manifest = guid_manifest("Main.unity".to_string(), &content, &cache, &aliases);
+        let entries: Vec<(&str, Option<&str>, &str, u32)> = manifest.entries.iter()
+            .map(|e| (&e.guid[..1], e.path.as_deref(), e.category.as_str(), e.references))
+            .collect();
+        assert_eq!(entries, vec![
+            ("a", Some("Assets/Player.cs"), "script", 1),
+            ("b", Some("Assets/Art/Icon.png"), "texture", 2),
+            ("c", None, "other", 1),
+        ]);
+        assert_eq!((manifest.guid_count, manifest.reference_count, manifest.unresolved_count), (3, 4, 1));
+        let moved = manifest.entries[2].resolution.as_ref().unwrap();
+        assert_eq!((moved.status.as_str(), moved.message.as_str()), ("moved", "moved to Assets/Art/Icon.png"));
+        assert!(manifest.entries[0].resolution.is_none());
+    }
+
+    #[test]
+    fn test_estimate_memory_heuristics() {
+        let meta = "TextureImporter:\n  mipmaps:\n    enableMipMap: 0\n  isReadable: 1\n  textureCompression: 0\n";
+        assert_eq!(estimate_memory("texture", "a.png", 1000, meta), 8000);
+        let meta = "TextureImporter:\n  mipmaps:\n    enableMipMap: 1\n  textureCompression: 1\n";
+        assert_eq!(estimate_memory("texture", "a.tga", 3000, meta), 1000);
+        assert_eq!(estimate_memory("audio", "a.ogg", 1000, "AudioImporter:\n  loadType: 0\n"), 10000);
+        assert_eq!(estimate_memory("audio", "a.wav", 10_000_000, "AudioImporter:\n  loadType: 2\n"), AUDIO_STREAM_BUFFER_BYTES);
+        assert_eq!(estimate_memory("script", "a.cs", 5000, ""), 0);
+    }
+
+    #[test]
+    fn test_collect_dependencies_follows_materials() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("Assets")).unwrap();
+        std::fs::write(root.join("Assets/Hero.mat"), "Material:\n  m_Texture: {fileID: 2800000, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 3}\n").unwrap();
+        std::fs::write(root.join("Assets/Hero.png"), vec![0u8; 100]).unwrap();
+        std::fs::write(root.join("Assets/Hero.png.meta"), "TextureImporter:\n  enableMipMap: 0\n").unwrap();
+
+        let mut cache = HashMap::new();
+        cache.insert("a".repeat(32), "Assets/Hero.mat".to_string());
+        cache.insert("b".repeat(32), "Assets/Hero.png".to_string());
+        let scene = format!("  m_Materials:\n  - {{fileID: 2100000, guid: {}, type: 2}}\n  missing: {{fileID: 1, guid: {}, type: 2}}\n", "a".repeat(32), "c".repeat(32));
+
+        let (assets, unresolved) = collect_dependencies(&scene, &cache, root);
+        assert_eq!(unresolved, 1);
+        assert_eq!(assets.len(), 2);
+        assert!(assets[0].direct);
+        assert_eq!(assets[1].path, "Assets/Hero.png");
+        assert!(!assets[1].direct);
+        assert_eq!(assets[1].file_bytes, 100);
+        assert_eq!(assets[1].estimated_memory_bytes, 400);
+    }
+}
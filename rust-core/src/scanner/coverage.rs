@@ -0,0 +1,170 @@
+//! Script-to-scene coverage: how many scenes and prefabs instantiate each MonoBehaviour type
+//! of the project's type registry, so never-used scripts stand out.
+
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::common::budget::Meter;
+use crate::common::{self, ScriptCoverageEntry, ScriptCoverageReport};
+use crate::csharp::{build_type_registry, CSharpTypeRef};
+use crate::csharp::summary::summarize_source;
+use super::config::ComponentConfig;
+
+/// Base classes from Unity and common packages that make a type an attachable component.
+const BEHAVIOUR_ROOTS: &[&str] = &["MonoBehaviour", "NetworkBehaviour", "UIBehaviour"];
+
+/// `abstract class Name` declarations, with any other modifiers in between.
+static ABSTRACT_CLASS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\babstract[ \t]+(?:\w+[ \t]+)*class[ \t]+(\w+)").unwrap());
+
+/// Whether `name` derives (transitively) from a behaviour root, following `bases`.
+fn is_behaviour(name: &str, bases: &HashMap<String, String>) -> bool {
+    let mut current = name;
+    // Bounded walk: inheritance cycles in broken code must not hang the scan
+    for _ in 0..32 {
+        let Some(base) = bases.get(current) else { return false };
+        let base = base.rsplit('.').next().unwrap_or(base);
+        if BEHAVIOUR_ROOTS.contains(&base) {
+            return true;
+        }
+        current = base;
+    }
+    false
+}
+
+/// Registry types that Unity can attach through a script asset: non-abstract behaviours
+/// named after their file, with a GUID from the script's .meta.
+fn attachable_scripts(project_root: &str) -> Vec<CSharpTypeRef> {
+    let root = Path::new(project_root);
+    let registry: Vec<CSharpTypeRef> = build_type_registry(project_root.to_string(), Some(false), Some(false))
+        .into_iter()
+        .filter(|t| t.kind == "class" && t.guid.is_some())
+        .filter(|t| Path::new(&t.file_path).file_stem().is_some_and(|stem| stem.to_string_lossy() == t.name))
+        .collect();
+
+    let files: BTreeSet<String> = registry.iter().map(|t| t.file_path.clone()).collect();
+    let sources: HashMap<String, String> = files.into_par_iter()
+        .filter_map(|file| {
+            let content = common::read_unity_file(root.join(&file)).ok()?;
+            Some((file, content))
+        })
+        .collect();
+    // Base classes of every class declared in the scanned scripts, not just the attachable ones
+    let bases: HashMap<String, String> = sources.iter()
+        .flat_map(|(file, content)| summarize_source(file, content).types)
+        .filter_map(|t| Some((t.name, t.base_class?)))
+        .collect();
+    let abstract_classes: HashSet<String> = sources.values()
+        .flat_map(|content| ABSTRACT_CLASS_RE.captures_iter(content).map(|c| c[1].to_string()))
+        .collect();
+
+    registry.into_iter()
+        .filter(|t| is_behaviour(&t.name, &bases))
+        .filter(|t| !abstract_classes.contains(&t.name))
+        .collect()
+}
+
+/// Script GUIDs referenced by script containers in one scene/prefab, with instance counts.
+fn script_instances(content: &str, script_re: &Regex) -> HashMap<String, u32> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for cap in script_re.captures_iter(content) {
+        *counts.entry(cap[1].to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Count, for every attachable MonoBehaviour script of the project, the scenes and prefabs
+/// that instantiate it. Unused scripts come first, then by path. `meter` caps the scene and
+/// prefab files read.
+pub fn coverage(project_root: &str, config: &ComponentConfig, meter: &Meter) -> ScriptCoverageReport {
+    let root = Path::new(project_root);
+    let scripts = attachable_scripts(project_root);
+    let script_re = Regex::new(&config.script_ref_pattern()).expect("Invalid regex");
+
+    let files = crate::walker::walk_project_files(project_root.to_string(), vec![".unity".to_string(), ".prefab".to_string()], None, None);
+    let usages: Vec<(String, HashMap<String, u32>)> = files.par_iter()
+        .filter(|file| meter.admit_file(file))
+        .filter_map(|file| {
+            let content = common::read_unity_file(file).ok()?;
+            let relative = Path::new(file).strip_prefix(root).unwrap_or(Path::new(file)).to_string_lossy().replace('\\', "/");
+            Some((relative, script_instances(&content, &script_re)))
+        })
+        .collect();
+
+    let mut entries: Vec<ScriptCoverageEntry> = scripts.into_iter()
+        .map(|script| {
+            let guid = script.guid.clone().unwrap_or_default();
+            let mut used_in: Vec<String> = Vec::new();
+            let mut instance_count = 0u32;
+            for (file, counts) in &usages {
+                if let Some(n) = counts.get(&guid) {
+                    used_in.push(file.clone());
+                    instance_count += n;
+                }
+            }
+            used_in.sort();
+            let scene_count = used_in.iter().filter(|f| f.ends_with(".unity")).count() as u32;
+            ScriptCoverageEntry {
+                prefab_count: used_in.len() as u32 - scene_count,
+                unused: used_in.is_empty(),
+                name: script.name,
+                namespace: script.namespace,
+                script_path: script.file_path.replace('\\', "/"),
+                guid,
+                scene_count,
+                instance_count,
+                used_in,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| b.unused.cmp(&a.unused).then_with(|| a.script_path.cmp(&b.script_path)));
+
+    ScriptCoverageReport {
+        project_root: project_root.to_string(),
+        script_count: entries.len() as u32,
+        unused_count: entries.iter().filter(|e| e.unused).count() as u32,
+        files_scanned: usages.len() as u32,
+        scripts: entries,
+        budget_exhausted: meter.exhausted(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_script(root: &Path, name: &str, guid: &str, source: &str) {
+        fs::write(root.join(format!("Assets/{}.cs", name)), source).unwrap();
+        fs::write(root.join(format!("Assets/{}.cs.meta", name)), format!("fileFormatVersion: 2\nguid: {}\n", guid)).unwrap();
+    }
+
+    #[test]
+    fn test_coverage_counts_scenes_prefabs_and_unused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("Assets")).unwrap();
+        write_script(root, "Enemy", "00000000000000000000000000000001", "using UnityEngine;\npublic class Enemy : EnemyBase { }\n");
+        write_script(root, "EnemyBase", "00000000000000000000000000000002", "using UnityEngine;\npublic abstract class EnemyBase : MonoBehaviour { }\n");
+        write_script(root, "Legacy", "00000000000000000000000000000003", "using UnityEngine;\nnamespace Old {\n    public class Legacy : UnityEngine.MonoBehaviour { }\n}\n");
+        write_script(root, "Settings", "00000000000000000000000000000004", "using UnityEngine;\npublic class Settings : ScriptableObject { }\n");
+
+        let mono = |id: u32, guid: &str| format!("--- !u!114 &{}\nMonoBehaviour:\n  m_GameObject: {{fileID: 1}}\n  m_Script: {{fileID: 11500000, guid: {}, type: 3}}\n", id, guid);
+        fs::write(root.join("Assets/Level.unity"), format!("{}{}", mono(2, "00000000000000000000000000000001"), mono(3, "00000000000000000000000000000001"))).unwrap();
+        fs::write(root.join("Assets/Enemy.prefab"), mono(2, "00000000000000000000000000000001")).unwrap();
+
+        let report = coverage(&root.to_string_lossy(), &ComponentConfig::default(), &Meter::new(None));
+        let names: Vec<&str> = report.scripts.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Legacy", "Enemy"]);
+        assert_eq!((report.script_count, report.unused_count, report.files_scanned), (2, 1, 2));
+
+        let legacy = &report.scripts[0];
+        assert!(legacy.unused);
+        assert_eq!(legacy.namespace.as_deref(), Some("Old"));
+        let enemy = &report.scripts[1];
+        assert_eq!((enemy.scene_count, enemy.prefab_count, enemy.instance_count), (1, 1, 3));
+        assert_eq!(enemy.used_in, vec!["Assets/Enemy.prefab", "Assets/Level.unity"]);
+    }
+}
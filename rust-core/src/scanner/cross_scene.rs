@@ -0,0 +1,163 @@
+//! Cross-scene references: GlobalObjectId strings and SceneAsset references that tooling
+//! serializes in MonoBehaviours, resolved to the scene (or asset) they point into.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::common::{CrossSceneReference, GlobalObjectIdParts};
+use super::config::ComponentConfig;
+use super::events::component_type;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::owner_game_object;
+
+static GLOBAL_OBJECT_ID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"GlobalObjectId_V1-([0-3])-([0-9a-fA-F]{32})-(\d+)-(\d+)").unwrap()
+});
+/// A serialized reference to a SceneAsset (its main object is always fileID 102900000)
+static SCENE_ASSET_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{fileID:[ \t]*102900000,[ \t]*guid:[ \t]*([0-9a-fA-F]{32}),[ \t]*type:[ \t]*3\}").unwrap()
+});
+static KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[ \t]*(?:- )?([A-Za-z_][A-Za-z0-9_]*):").unwrap()
+});
+
+/// Split a `GlobalObjectId_V1-{type}-{assetGUID}-{objectId}-{prefabId}` string into its parts.
+pub fn parse_global_object_id(value: &str) -> Option<GlobalObjectIdParts> {
+    let caps = GLOBAL_OBJECT_ID_RE.captures(value.trim())?;
+    Some(GlobalObjectIdParts {
+        identifier_type: caps[1].parse().ok()?,
+        asset_guid: caps[2].to_lowercase(),
+        target_object_id: caps[3].to_string(),
+        target_prefab_id: caps[4].to_string(),
+    })
+}
+
+/// The fileID a scene serializes for a GlobalObjectId object id. Ids are printed unsigned,
+/// while YAML fileIDs are signed 64-bit.
+fn serialized_file_id(id: &str) -> Option<String> {
+    id.parse::<u64>().ok().map(|n| (n as i64).to_string())
+}
+
+/// Key of the field a line belongs to: its own key, or that of the nearest less indented line
+/// (for list items and block values).
+fn property_at(lines: &[&str], line_idx: usize) -> String {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let is_item = |line: &str| line.trim_start().starts_with("- ");
+    let own = lines[line_idx];
+    if let Some(caps) = KEY_RE.captures(own) {
+        return caps[1].to_string();
+    }
+    // Unity writes sequence items at their key's indentation
+    lines[..line_idx].iter().rev()
+        .filter(|line| indent(line) < indent(own) || is_item(own) && indent(line) == indent(own) && !is_item(line))
+        .find_map(|line| KEY_RE.captures(line).map(|caps| caps[1].to_string()))
+        .unwrap_or_default()
+}
+
+/// GlobalObjectId strings and SceneAsset references in the components of a scene/prefab.
+/// `object_exists(scene_path, file_id)` answers whether a scene has a block with that fileID
+/// (None when the scene cannot be read); references whose asset or object is gone carry an issue.
+pub fn find_references(
+    file: &str,
+    content: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+    object_exists: &mut dyn FnMut(&str, &str) -> Option<bool>,
+) -> Vec<CrossSceneReference> {
+    let index = BlockIndex::new(content);
+    let mut references = Vec::new();
+
+    for (class_id, file_id, block) in UnityYamlParser::parse_all_blocks(content) {
+        if class_id == config.gameobject_class_id || !(block.contains("GlobalObjectId_V1-") || block.contains("102900000")) {
+            continue;
+        }
+        let (game_object_id, game_object_name) = owner_game_object(&index, &block);
+        let component = component_type(&block, guid_cache);
+        let lines: Vec<&str> = block.lines().collect();
+
+        for (line_idx, line) in lines.iter().enumerate() {
+            let global_ids = GLOBAL_OBJECT_ID_RE.find_iter(line).filter_map(|m| {
+                let parts = parse_global_object_id(m.as_str()).filter(|p| p.identifier_type != 0)?;
+                Some((m.as_str().to_string(), parts.asset_guid.clone(), Some(parts)))
+            });
+            let scene_assets = SCENE_ASSET_REF_RE.captures_iter(line)
+                .map(|caps| (caps[0].to_string(), caps[1].to_lowercase(), None));
+
+            for (value, asset_guid, global_object_id) in global_ids.chain(scene_assets).collect::<Vec<_>>() {
+                let target_path = guid_cache.get(&asset_guid).cloned();
+                let issue = match (&target_path, &global_object_id) {
+                    (None, _) => Some("missing_asset"),
+                    // Scene objects inside a prefab instance are found through the instance
+                    (Some(path), Some(parts)) if parts.identifier_type == 2 => {
+                        let target = if parts.target_prefab_id == "0" { &parts.target_object_id } else { &parts.target_prefab_id };
+                        let exists = serialized_file_id(target).and_then(|id| object_exists(path, &id));
+                        (exists == Some(false)).then_some("missing_object")
+                    }
+                    _ => None,
+                };
+                references.push(CrossSceneReference {
+                    file: file.to_string(),
+                    game_object_id: game_object_id.clone(),
+                    game_object_name: game_object_name.clone(),
+                    component_file_id: file_id.clone(),
+                    component_type: component.clone(),
+                    property: property_at(&lines, line_idx),
+                    kind: if global_object_id.is_some() { "global_object_id" } else { "scene_asset" }.to_string(),
+                    value,
+                    asset_guid,
+                    global_object_id,
+                    target_path,
+                    issue: issue.map(str::to_string),
+                });
+            }
+        }
+    }
+
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVEL_GUID: &str = "0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn test_parse_global_object_id() {
+        let parts = parse_global_object_id("GlobalObjectId_V1-2-0123456789ABCDEF0123456789ABCDEF-18446744073709551615-0").unwrap();
+        assert_eq!((parts.identifier_type, parts.asset_guid.as_str()), (2, LEVEL_GUID));
+        assert_eq!((parts.target_object_id.as_str(), parts.target_prefab_id.as_str()), ("18446744073709551615", "0"));
+        assert_eq!(serialized_file_id(&parts.target_object_id).as_deref(), Some("-1"));
+        assert!(parse_global_object_id("GlobalObjectId_V1-2-nothex-1-0").is_none());
+    }
+
+    #[test]
+    fn test_find_references_resolves_and_flags() {
+        let content = format!("--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {{fileID: 2}}\n  m_Name: Portal\n\
+--- !u!114 &2\nMonoBehaviour:\n  m_GameObject: {{fileID: 1}}\n  m_Script: {{fileID: 11500000, guid: ffffffffffffffffffffffffffffffff, type: 3}}\n\
+  targetId: GlobalObjectId_V1-2-{LEVEL_GUID}-500-0\n  spawnPoints:\n  - GlobalObjectId_V1-2-{LEVEL_GUID}-999-0\n  - GlobalObjectId_V1-2-{LEVEL_GUID}-7-600\n\
+  unset: GlobalObjectId_V1-0-00000000000000000000000000000000-0-0\n  nextLevel: {{fileID: 102900000, guid: {LEVEL_GUID}, type: 3}}\n\
+  lostLevel: {{fileID: 102900000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}}\n");
+        let mut cache = HashMap::new();
+        cache.insert(LEVEL_GUID.to_string(), "Assets/Level2.unity".to_string());
+        let mut lookups = Vec::new();
+        let mut object_exists = |path: &str, id: &str| {
+            lookups.push(id.to_string());
+            Some(path == "Assets/Level2.unity" && (id == "500" || id == "600"))
+        };
+
+        let refs = find_references("Assets/Level1.unity", &content, &cache, &ComponentConfig::default(), &mut object_exists);
+        let summary: Vec<(&str, &str, Option<&str>)> = refs.iter().map(|r| (r.property.as_str(), r.kind.as_str(), r.issue.as_deref())).collect();
+        assert_eq!(summary, vec![
+            ("targetId", "global_object_id", None),
+            ("spawnPoints", "global_object_id", Some("missing_object")),
+            ("spawnPoints", "global_object_id", None),
+            ("nextLevel", "scene_asset", None),
+            ("lostLevel", "scene_asset", Some("missing_asset")),
+        ]);
+        assert_eq!(lookups, vec!["500", "999", "600"]);
+        assert_eq!(refs[0].target_path.as_deref(), Some("Assets/Level2.unity"));
+        assert_eq!((refs[0].game_object_name.as_str(), refs[0].component_file_id.as_str()), ("Portal", "2"));
+    }
+}
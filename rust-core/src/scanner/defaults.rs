@@ -0,0 +1,360 @@
+//! Built-in component properties that differ from what Unity writes for a fresh component.
+
+use crate::common::{ComponentDefaultsDiff, PropertyDefaultDiff};
+use super::parser::BlockIndex;
+use super::query::Value;
+use super::schema::{flatten_block, FieldValue};
+
+/// Bookkeeping and hierarchy fields, never reported.
+const IGNORED_FIELDS: &[&str] = &[
+    "m_CorrespondingSourceObject", "m_PrefabInstance", "m_PrefabAsset", "m_PrefabInternal",
+    "m_PrefabParentObject", "m_GameObject", "m_Father", "m_Children", "m_RootOrder", "serializedVersion",
+];
+
+/// Property path → default serialized value.
+type Defaults = &'static [(&'static str, &'static str)];
+
+/// Defaults shared by every component.
+const COMMON_DEFAULTS: Defaults = &[("m_ObjectHideFlags", "0"), ("m_Enabled", "1")];
+
+/// Collider fields shared by every 3D collider.
+const COLLIDER_DEFAULTS: Defaults = &[
+    ("m_Material", "{fileID: 0}"),
+    ("m_IncludeLayers.m_Bits", "0"),
+    ("m_ExcludeLayers.m_Bits", "0"),
+    ("m_LayerOverridePriority", "0"),
+    ("m_IsTrigger", "0"),
+    ("m_ProvidesContacts", "0"),
+];
+
+/// What Unity 2022.3 serializes for a freshly added built-in component, by class ID, as the
+/// dotted paths `flatten_block` produces. `[]` is an empty sequence.
+const BUILTIN_DEFAULTS: &[(u32, &[Defaults])] = &[
+    (4, &[&[
+        ("m_LocalRotation", "{x: 0, y: 0, z: 0, w: 1}"),
+        ("m_LocalPosition", "{x: 0, y: 0, z: 0}"),
+        ("m_LocalScale", "{x: 1, y: 1, z: 1}"),
+        ("m_ConstrainProportionsScale", "0"),
+        ("m_LocalEulerAnglesHint", "{x: 0, y: 0, z: 0}"),
+    ]]),
+    (20, &[&[
+        ("m_ClearFlags", "1"),
+        ("m_BackGroundColor", "{r: 0.19215687, g: 0.3019608, b: 0.4745098, a: 0}"),
+        ("m_projectionMatrixMode", "1"),
+        ("m_GateFitMode", "2"),
+        ("m_FOVAxisMode", "0"),
+        ("m_Iso", "200"),
+        ("m_ShutterSpeed", "0.005"),
+        ("m_Aperture", "16"),
+        ("m_FocusDistance", "10"),
+        ("m_FocalLength", "50"),
+        ("m_BladeCount", "5"),
+        ("m_Curvature", "{x: 2, y: 11}"),
+        ("m_BarrelClipping", "0.25"),
+        ("m_Anamorphism", "0"),
+        ("m_SensorSize", "{x: 36, y: 24}"),
+        ("m_LensShift", "{x: 0, y: 0}"),
+        ("m_NormalizedViewPortRect.x", "0"),
+        ("m_NormalizedViewPortRect.y", "0"),
+        ("m_NormalizedViewPortRect.width", "1"),
+        ("m_NormalizedViewPortRect.height", "1"),
+        ("near clip plane", "0.3"),
+        ("far clip plane", "1000"),
+        ("field of view", "60"),
+        ("orthographic", "0"),
+        ("orthographic size", "5"),
+        ("m_Depth", "0"),
+        ("m_CullingMask.m_Bits", "4294967295"),
+        ("m_RenderingPath", "-1"),
+        ("m_TargetTexture", "{fileID: 0}"),
+        ("m_TargetDisplay", "0"),
+        ("m_TargetEye", "3"),
+        ("m_HDR", "1"),
+        ("m_AllowMSAA", "1"),
+        ("m_AllowDynamicResolution", "0"),
+        ("m_ForceIntoRT", "0"),
+        ("m_OcclusionCulling", "1"),
+        ("m_StereoConvergence", "10"),
+        ("m_StereoSeparation", "0.022"),
+    ]]),
+    (23, &[&[
+        ("m_CastShadows", "1"),
+        ("m_ReceiveShadows", "1"),
+        ("m_DynamicOccludee", "1"),
+        ("m_StaticShadowCaster", "0"),
+        ("m_MotionVectors", "1"),
+        ("m_LightProbeUsage", "1"),
+        ("m_ReflectionProbeUsage", "1"),
+        ("m_RayTracingMode", "2"),
+        ("m_RayTraceProcedural", "0"),
+        ("m_RenderingLayerMask", "1"),
+        ("m_RendererPriority", "0"),
+        ("m_Materials", "[]"),
+        ("m_StaticBatchInfo.firstSubMesh", "0"),
+        ("m_StaticBatchInfo.subMeshCount", "0"),
+        ("m_StaticBatchRoot", "{fileID: 0}"),
+        ("m_ProbeAnchor", "{fileID: 0}"),
+        ("m_LightProbeVolumeOverride", "{fileID: 0}"),
+        ("m_ScaleInLightmap", "1"),
+        ("m_ReceiveGI", "1"),
+        ("m_PreserveUVs", "0"),
+        ("m_IgnoreNormalsForChartDetection", "0"),
+        ("m_ImportantGI", "0"),
+        ("m_StitchLightmapSeams", "1"),
+        ("m_SelectedEditorRenderState", "3"),
+        ("m_MinimumChartSize", "4"),
+        ("m_AutoUVMaxDistance", "0.5"),
+        ("m_AutoUVMaxAngle", "89"),
+        ("m_LightmapParameters", "{fileID: 0}"),
+        ("m_SortingLayerID", "0"),
+        ("m_SortingLayer", "0"),
+        ("m_SortingOrder", "0"),
+        ("m_AdditionalVertexStreams", "{fileID: 0}"),
+        ("m_LightmapIndex", "65535"),
+        ("m_LightmapIndexDynamic", "65535"),
+        ("m_LightmapTilingOffset", "{x: 1, y: 1, z: 0, w: 0}"),
+        ("m_LightmapTilingOffsetDynamic", "{x: 1, y: 1, z: 0, w: 0}"),
+    ]]),
+    (54, &[&[
+        ("m_Mass", "1"),
+        ("m_Drag", "0"),
+        ("m_AngularDrag", "0.05"),
+        ("m_CenterOfMass", "{x: 0, y: 0, z: 0}"),
+        ("m_InertiaTensor", "{x: 1, y: 1, z: 1}"),
+        ("m_InertiaRotation", "{x: 0, y: 0, z: 0, w: 1}"),
+        ("m_IncludeLayers.m_Bits", "0"),
+        ("m_ExcludeLayers.m_Bits", "0"),
+        ("m_ImplicitCom", "1"),
+        ("m_ImplicitTensor", "1"),
+        ("m_UseGravity", "1"),
+        ("m_IsKinematic", "0"),
+        ("m_Interpolate", "0"),
+        ("m_Constraints", "0"),
+        ("m_CollisionDetection", "0"),
+    ]]),
+    (64, &[COLLIDER_DEFAULTS, &[("m_Convex", "0"), ("m_CookingOptions", "30")]]),
+    (65, &[COLLIDER_DEFAULTS, &[("m_Size", "{x: 1, y: 1, z: 1}"), ("m_Center", "{x: 0, y: 0, z: 0}")]]),
+    (82, &[&[
+        ("OutputAudioMixerGroup", "{fileID: 0}"),
+        ("m_audioClip", "{fileID: 0}"),
+        ("m_PlayOnAwake", "1"),
+        ("m_Volume", "1"),
+        ("m_Pitch", "1"),
+        ("Loop", "0"),
+        ("Mute", "0"),
+        ("Spatialize", "0"),
+        ("SpatializePostEffects", "0"),
+        ("Priority", "128"),
+        ("DopplerLevel", "1"),
+        ("MinDistance", "1"),
+        ("MaxDistance", "500"),
+        ("Pan2D", "0"),
+        ("rolloffMode", "0"),
+        ("BypassEffects", "0"),
+        ("BypassListenerEffects", "0"),
+        ("BypassReverbZones", "0"),
+    ]]),
+    (95, &[&[
+        ("m_Avatar", "{fileID: 0}"),
+        ("m_Controller", "{fileID: 0}"),
+        ("m_CullingMode", "0"),
+        ("m_UpdateMode", "0"),
+        ("m_ApplyRootMotion", "0"),
+        ("m_LinearVelocityBlending", "0"),
+        ("m_StabilizeFeet", "0"),
+        ("m_WarningMessage", ""),
+        ("m_HasTransformHierarchy", "1"),
+        ("m_AllowConstantClipSamplingOptimization", "1"),
+        ("m_KeepAnimatorStateOnDisable", "0"),
+        ("m_WriteDefaultValuesOnDisable", "0"),
+    ]]),
+    (108, &[&[
+        ("m_Type", "2"),
+        ("m_Shape", "0"),
+        ("m_Color", "{r: 1, g: 1, b: 1, a: 1}"),
+        ("m_Intensity", "1"),
+        ("m_Range", "10"),
+        ("m_SpotAngle", "30"),
+        ("m_InnerSpotAngle", "21.80208"),
+        ("m_CookieSize", "10"),
+        ("m_Shadows.m_Type", "0"),
+        ("m_Shadows.m_Resolution", "-1"),
+        ("m_Shadows.m_CustomResolution", "-1"),
+        ("m_Shadows.m_Strength", "1"),
+        ("m_Shadows.m_Bias", "0.05"),
+        ("m_Shadows.m_NormalBias", "0.4"),
+        ("m_Shadows.m_NearPlane", "0.2"),
+        ("m_Cookie", "{fileID: 0}"),
+        ("m_DrawHalo", "0"),
+        ("m_Flare", "{fileID: 0}"),
+        ("m_RenderMode", "0"),
+        ("m_CullingMask.m_Bits", "4294967295"),
+        ("m_RenderingLayerMask", "1"),
+        ("m_Lightmapping", "4"),
+        ("m_LightShadowCasterMode", "0"),
+        ("m_AreaSize", "{x: 1, y: 1}"),
+        ("m_BounceIntensity", "1"),
+        ("m_ColorTemperature", "6570"),
+        ("m_UseColorTemperature", "0"),
+        ("m_BoundingSphereOverride", "{x: 0, y: 0, z: 0, w: 0}"),
+        ("m_UseBoundingSphereOverride", "0"),
+        ("m_UseViewFrustumForShadowCasterCull", "1"),
+        ("m_ShadowRadius", "0"),
+        ("m_ShadowAngle", "0"),
+    ]]),
+    (135, &[COLLIDER_DEFAULTS, &[("m_Radius", "0.5"), ("m_Center", "{x: 0, y: 0, z: 0}")]]),
+    (136, &[COLLIDER_DEFAULTS, &[("m_Radius", "0.5"), ("m_Height", "2"), ("m_Direction", "1"), ("m_Center", "{x: 0, y: 0, z: 0}")]]),
+    (143, &[COLLIDER_DEFAULTS, &[
+        ("m_Height", "2"),
+        ("m_Radius", "0.5"),
+        ("m_SlopeLimit", "45"),
+        ("m_StepOffset", "0.3"),
+        ("m_SkinWidth", "0.08"),
+        ("m_MinMoveDistance", "0.001"),
+        ("m_Center", "{x: 0, y: 0, z: 0}"),
+    ]]),
+];
+
+/// Default value of a property path of a built-in component; None when the class or path is
+/// not in the table.
+pub fn default_value(class_id: u32, path: &str) -> Option<&'static str> {
+    let (_, groups) = BUILTIN_DEFAULTS.iter().find(|(id, _)| *id == class_id)?;
+    groups.iter()
+        .copied()
+        .chain(std::iter::once(COMMON_DEFAULTS))
+        .flat_map(|group| group.iter())
+        .find(|(p, _)| *p == path)
+        .map(|(_, value)| *value)
+}
+
+/// Every default of a built-in component as (path, value), common fields first; None when
+/// the class is not in the table.
+pub fn builtin_defaults(class_id: u32) -> Option<Vec<(&'static str, &'static str)>> {
+    let (_, groups) = BUILTIN_DEFAULTS.iter().find(|(id, _)| *id == class_id)?;
+    Some(std::iter::once(COMMON_DEFAULTS).chain(groups.iter().copied()).flat_map(|group| group.iter().copied()).collect())
+}
+
+/// Whether the table knows the defaults of `class_id`.
+pub fn has_defaults(class_id: u32) -> bool {
+    BUILTIN_DEFAULTS.iter().any(|(id, _)| *id == class_id)
+}
+
+/// Serialized values compared as numbers and vectors where they parse (`1` equals `1.0`).
+fn same_value(value: &str, default: &str) -> bool {
+    Value::from_scalar(value) == Value::from_scalar(default)
+}
+
+/// Properties of component `component_id` in Unity YAML content that differ from a fresh
+/// component's. Properties without a known default (every property of scripts and of types
+/// missing from the table) are reported with `default` unset. Sequences are compared as a
+/// whole against an empty default. None when `component_id` is not a component block.
+pub fn diff_against_defaults(file: &str, content: &str, component_id: &str) -> Option<ComponentDefaultsDiff> {
+    let index = BlockIndex::new(content);
+    let (class_id, body) = index.get(component_id)?;
+    let component_type = body.lines().next()?.trim_end_matches(':').trim().to_string();
+    if component_type == "GameObject" {
+        return None;
+    }
+
+    let mut total_properties = 0u32;
+    let mut differences = Vec::new();
+    let mut covered_sequence: Option<String> = None;
+    for (path, value) in flatten_block(body) {
+        let root = path.split(['.', '[']).next().unwrap_or_default();
+        if IGNORED_FIELDS.contains(&root) || path.ends_with(".serializedVersion") {
+            continue;
+        }
+        if covered_sequence.as_ref().is_some_and(|seq| path.starts_with(seq.as_str())) {
+            continue;
+        }
+        covered_sequence = None;
+        let default = default_value(class_id, &path);
+        let value = match value {
+            FieldValue::Map => continue,
+            FieldValue::Sequence(count) => {
+                if default.is_none() {
+                    continue;
+                }
+                covered_sequence = Some(format!("{}[]", path));
+                match count {
+                    0 => "[]".to_string(),
+                    1 => "[1 item]".to_string(),
+                    n => format!("[{} items]", n),
+                }
+            }
+            FieldValue::Scalar(value) => value,
+        };
+        total_properties += 1;
+        if default.is_none_or(|d| !same_value(&value, d)) {
+            differences.push(PropertyDefaultDiff { path, value, default: default.map(str::to_string) });
+        }
+    }
+
+    Some(ComponentDefaultsDiff {
+        file: file.to_string(),
+        component_id: component_id.to_string(),
+        component_type,
+        class_id,
+        known_type: has_defaults(class_id),
+        total_properties,
+        default_count: total_properties - differences.len() as u32,
+        differences,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_value_lookup() {
+        assert_eq!(default_value(65, "m_Size"), Some("{x: 1, y: 1, z: 1}"));
+        assert_eq!(default_value(65, "m_IsTrigger"), Some("0"), "shared collider defaults");
+        assert_eq!(default_value(108, "m_Enabled"), Some("1"), "common defaults");
+        assert_eq!(default_value(108, "m_Shadows.m_Bias"), Some("0.05"));
+        assert_eq!(default_value(114, "m_Enabled"), None, "scripts have no table");
+        assert!(has_defaults(20) && !has_defaults(114));
+    }
+
+    #[test]
+    fn test_diff_against_defaults() {
+        let content = [
+            "%YAML 1.1",
+            "--- !u!65 &200",
+            "BoxCollider:",
+            "  m_ObjectHideFlags: 0",
+            "  m_GameObject: {fileID: 100}",
+            "  m_Material: {fileID: 0}",
+            "  m_IncludeLayers:",
+            "    serializedVersion: 2",
+            "    m_Bits: 0",
+            "  m_IsTrigger: 1",
+            "  m_Enabled: 1",
+            "  serializedVersion: 3",
+            "  m_Size: {x: 1.0, y: 1, z: 1}",
+            "  m_Center: {x: 0, y: 0.5, z: 0}",
+            "--- !u!23 &300",
+            "MeshRenderer:",
+            "  m_Enabled: 1",
+            "  m_Materials:",
+            "  - {fileID: 2100000, guid: abc, type: 2}",
+            "  m_SortingOrder: 0",
+            "  m_NewField: 3",
+        ].join("\n");
+        let content = content.as_str();
+        let collider = diff_against_defaults("Main.unity", content, "200").unwrap();
+        assert_eq!((collider.component_type.as_str(), collider.class_id, collider.known_type), ("BoxCollider", 65, true));
+        let diffs: Vec<(&str, &str, Option<&str>)> = collider.differences.iter()
+            .map(|d| (d.path.as_str(), d.value.as_str(), d.default.as_deref()))
+            .collect();
+        assert_eq!(diffs, vec![("m_IsTrigger", "1", Some("0")), ("m_Center", "{x: 0, y: 0.5, z: 0}", Some("{x: 0, y: 0, z: 0}"))]);
+        assert_eq!((collider.total_properties, collider.default_count), (7, 5));
+
+        let renderer = diff_against_defaults("Main.unity", content, "300").unwrap();
+        let paths: Vec<(&str, &str)> = renderer.differences.iter().map(|d| (d.path.as_str(), d.value.as_str())).collect();
+        assert_eq!(paths, vec![("m_Materials", "[1 item]"), ("m_NewField", "3")]);
+        assert_eq!(renderer.differences[1].default, None);
+
+        assert!(diff_against_defaults("Main.unity", content, "999").is_none());
+    }
+}
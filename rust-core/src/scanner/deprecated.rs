@@ -0,0 +1,169 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::common::DeprecatedUsage;
+use super::config::ComponentConfig;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::owner_game_object;
+
+/// Built-in components removed or superseded in modern Unity: (class ID, name, replacement).
+const LEGACY_COMPONENTS: &[(u32, &str, &str)] = &[
+    (12, "ParticleAnimator", "ParticleSystem (legacy particles were removed in 2018.3)"),
+    (15, "EllipsoidParticleEmitter", "ParticleSystem"),
+    (26, "ParticleRenderer", "ParticleSystemRenderer"),
+    (76, "WorldParticleCollider", "ParticleSystem Collision module"),
+    (87, "MeshParticleEmitter", "ParticleSystem with a Mesh shape"),
+    (92, "GUILayer", "nothing: remove it (only needed by GUIText/GUITexture)"),
+    (131, "GUITexture", "UI Image / RawImage"),
+    (132, "GUIText", "TextMeshPro or UI Text"),
+    (148, "NetworkView", "Netcode for GameObjects (legacy networking was removed in 2018.2)"),
+];
+
+/// Script extensions of languages Unity no longer compiles.
+const LEGACY_SCRIPT_EXTENSIONS: &[(&str, &str)] = &[
+    (".js", "UnityScript"),
+    (".boo", "Boo"),
+];
+
+/// Deprecated or removed C# APIs: (pattern, API name, replacement).
+static DEPRECATED_APIS: LazyLock<Vec<(Regex, &'static str, &'static str)>> = LazyLock::new(|| {
+    [
+        (r"\bnew\s+WWW\s*\(|\bWWW\s+\w+\s*[=;]", "WWW", "UnityWebRequest"),
+        (r"\bApplication\.LoadLevel(?:Async|Additive|AdditiveAsync)?\s*\(", "Application.LoadLevel", "SceneManager.LoadScene / LoadSceneAsync"),
+        (r"\bApplication\.loadedLevel(?:Name)?\b", "Application.loadedLevel", "SceneManager.GetActiveScene()"),
+        (r"\bApplication\.levelCount\b", "Application.levelCount", "SceneManager.sceneCountInBuildSettings"),
+        (r"\bApplication\.External(?:Call|Eval)\s*\(", "Application.ExternalCall", "a .jslib plugin"),
+        (r"\bGUI(?:Text|Texture)\b", "GUIText/GUITexture", "UI Text / Image or TextMeshPro"),
+        (r"\.(?:guiText|guiTexture|particleEmitter|networkView)\b", "legacy component property", "GetComponent<T>() with the modern component"),
+        (r"\b(?:gameObject|transform|this)\.(?:rigidbody2D|rigidbody|collider2D|collider|renderer|audio|hingeJoint|constantForce)\b", "component shortcut property", "GetComponent<T>()"),
+        (r"\bNetwork\.(?:Instantiate|Connect|InitializeServer|Disconnect)\s*\(", "legacy Network", "Netcode for GameObjects"),
+        (r"\bFindObjectsOfType\s*[<(]", "FindObjectsOfType", "FindObjectsByType"),
+        (r"\bFindObjectOfType\s*[<(]", "FindObjectOfType", "FindFirstObjectByType / FindAnyObjectByType"),
+    ]
+    .into_iter()
+    .map(|(pattern, api, replacement)| (Regex::new(pattern).unwrap(), api, replacement))
+    .collect()
+});
+
+/// Legacy built-in components and MonoBehaviours backed by UnityScript/Boo scripts in a scene or prefab.
+pub fn scene_usages(
+    file: &str,
+    content: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> Vec<DeprecatedUsage> {
+    let index = BlockIndex::new(content);
+    let script_re = Regex::new(&config.script_ref_pattern()).expect("Invalid regex");
+    let mut usages = Vec::new();
+    for (class_id, _, body) in UnityYamlParser::parse_all_blocks(content) {
+        let (category, name, replacement) = if let Some((_, name, replacement)) = LEGACY_COMPONENTS.iter().find(|(id, _, _)| *id == class_id) {
+            ("component", name.to_string(), replacement.to_string())
+        } else if config.is_script_container(class_id) {
+            let Some(script) = script_re.captures(&body)
+                .and_then(|c| c.get(1))
+                .and_then(|guid| guid_cache.get(guid.as_str()))
+            else { continue };
+            let lower = script.to_lowercase();
+            let Some((_, language)) = LEGACY_SCRIPT_EXTENSIONS.iter().find(|(ext, _)| lower.ends_with(ext)) else { continue };
+            ("script", script.clone(), format!("a C# port ({} is no longer compiled)", language))
+        } else {
+            continue;
+        };
+        let (game_object_id, game_object_name) = owner_game_object(&index, &body);
+        usages.push(DeprecatedUsage {
+            category: category.to_string(),
+            file: file.to_string(),
+            line: None,
+            game_object_id: Some(game_object_id),
+            game_object_name: Some(game_object_name),
+            name,
+            replacement,
+        });
+    }
+    usages
+}
+
+/// Deprecated API calls in C# source. Line comments are skipped.
+pub fn code_usages(file: &str, source: &str) -> Vec<DeprecatedUsage> {
+    let mut usages = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let code = line.split("//").next().unwrap_or("");
+        for (re, api, replacement) in DEPRECATED_APIS.iter() {
+            if re.is_match(code) {
+                usages.push(DeprecatedUsage {
+                    category: "api".to_string(),
+                    file: file.to_string(),
+                    line: Some(i as u32 + 1),
+                    game_object_id: None,
+                    game_object_name: None,
+                    name: api.to_string(),
+                    replacement: replacement.to_string(),
+                });
+            }
+        }
+    }
+    usages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scene_usages_flags_legacy_components_and_unityscript() {
+        let content = "%YAML 1.1
+--- !u!1 &1
+GameObject:
+  m_Component:
+  - component: {fileID: 2}
+  - component: {fileID: 3}
+  - component: {fileID: 4}
+  m_Name: HUD
+--- !u!4 &2
+Transform:
+  m_GameObject: {fileID: 1}
+--- !u!132 &3
+GUIText:
+  m_GameObject: {fileID: 1}
+--- !u!114 &4
+MonoBehaviour:
+  m_GameObject: {fileID: 1}
+  m_Script: {fileID: 11500000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}
+--- !u!114 &5
+MonoBehaviour:
+  m_GameObject: {fileID: 1}
+  m_Script: {fileID: 11500000, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 3}
+";
+        let mut cache = HashMap::new();
+        cache.insert("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(), "Assets/Scripts/Score.js".to_string());
+        cache.insert("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(), "Assets/Scripts/Health.cs".to_string());
+        let usages = scene_usages("HUD.prefab", content, &cache, &ComponentConfig::default());
+        let found: Vec<(&str, &str)> = usages.iter().map(|u| (u.category.as_str(), u.name.as_str())).collect();
+        assert_eq!(found, vec![("component", "GUIText"), ("script", "Assets/Scripts/Score.js")]);
+        assert_eq!(usages[0].game_object_name.as_deref(), Some("HUD"));
+    }
+
+    #[test]
+    fn test_code_usages_finds_deprecated_apis() {
+        let source = "using UnityEngine;\n\
+class Loader : MonoBehaviour {\n\
+    IEnumerator Start() {\n\
+        var www = new WWW(url);\n\
+        Application.LoadLevel(\"Menu\");\n\
+        // Application.LoadLevel(\"Old\");\n\
+        gameObject.rigidbody.AddForce(Vector3.up);\n\
+        var cam = FindObjectOfType<Camera>();\n\
+        var level = SceneManager.GetActiveScene();\n\
+    }\n\
+}\n";
+        let usages = code_usages("Assets/Loader.cs", source);
+        let found: Vec<(Option<u32>, &str)> = usages.iter().map(|u| (u.line, u.name.as_str())).collect();
+        assert_eq!(found, vec![
+            (Some(4), "WWW"),
+            (Some(5), "Application.LoadLevel"),
+            (Some(7), "component shortcut property"),
+            (Some(8), "FindObjectOfType"),
+        ]);
+    }
+}
@@ -0,0 +1,316 @@
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::common::DuplicateCluster;
+use super::config::ComponentConfig;
+use super::correlate::fingerprints;
+use super::gameobject::build_parent_map;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::field;
+
+/// Fields that place an object (or wire it into the hierarchy) rather than describe it;
+/// copies differ in these by definition.
+const PLACEMENT_FIELDS: &[&str] = &[
+    "m_ObjectHideFlags", "m_CorrespondingSourceObject", "m_PrefabInstance", "m_PrefabAsset",
+    "m_PrefabInternal", "m_PrefabParentObject", "m_GameObject", "m_Father", "m_Children",
+    "m_RootOrder", "m_LocalPosition", "m_LocalRotation", "m_LocalEulerAnglesHint",
+    "m_AnchoredPosition", "m_AnchorMin", "m_AnchorMax", "m_SizeDelta", "m_Pivot",
+];
+
+/// GameObject fields that are part of an object's identity.
+const GAMEOBJECT_FIELDS: &[&str] = &["m_Layer", "m_TagString", "m_IsActive", "m_StaticEditorFlags"];
+
+/// Components that alone do not make an object worth reporting (empty grouping objects).
+const TRIVIAL_COMPONENTS: &[&str] = &["Transform", "RectTransform"];
+
+/// Local (same-file) object references; every copy points at its own objects.
+static LOCAL_REF_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{fileID: -?[1-9]\d*\}").unwrap());
+
+/// Unity's " (12)" suffix on duplicated objects.
+static COPY_SUFFIX_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s*\(\d+\)$").unwrap());
+
+/// A GameObject reduced to what makes two copies identical.
+pub(super) struct Shape {
+    pub file_id: String,
+    pub name: String,
+    pub path: String,
+    /// Component types (scripts by name when the GUID resolves), sorted
+    pub components: Vec<String>,
+    /// "Type.m_Field" (or "Type#2.m_Field" for repeated types) → normalized value
+    pub properties: BTreeMap<String, String>,
+    parent: Option<String>,
+    children: Vec<String>,
+}
+
+/// Top-level fields of a block with their (possibly multi-line) values.
+fn top_level_fields(body: &str) -> Vec<(&str, String)> {
+    let mut fields: Vec<(&str, String)> = Vec::new();
+    for line in body.lines().skip(1) {
+        let is_key = line.strip_prefix("  ").is_some_and(|rest| !rest.starts_with([' ', '-']));
+        match line.trim_start().split_once(':') {
+            Some((key, value)) if is_key => fields.push((key, value.trim().to_string())),
+            _ => {
+                if let Some((_, value)) = fields.last_mut() {
+                    value.push('\n');
+                    value.push_str(line.trim_end());
+                }
+            }
+        }
+    }
+    fields
+}
+
+pub(super) fn shapes(content: &str, guid_cache: &HashMap<String, String>, config: &ComponentConfig) -> Vec<Shape> {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+    let parents = build_parent_map(&blocks, config);
+    let mut children: HashMap<&str, Vec<String>> = HashMap::new();
+    for (child, parent) in &parents {
+        children.entry(parent.as_str()).or_default().push(child.clone());
+    }
+
+    fingerprints(content, config).into_iter()
+        .map(|object| {
+            let mut properties = BTreeMap::new();
+            if let Some(go) = index.get_by_class_and_id(config.gameobject_class_id, &object.file_id) {
+                for key in GAMEOBJECT_FIELDS {
+                    if let Some(value) = field(go, key) {
+                        properties.insert(format!("GameObject.{}", key), value.to_string());
+                    }
+                }
+            }
+
+            let mut components: Vec<String> = Vec::new();
+            let mut seen: HashMap<String, usize> = HashMap::new();
+            for (comp_id, type_name) in &object.components {
+                let display = match type_name.split_once(':') {
+                    Some((_, guid)) => guid_cache.get(guid)
+                        .and_then(|path| Path::new(path).file_stem())
+                        .map_or_else(|| type_name.clone(), |stem| stem.to_string_lossy().to_string()),
+                    None => type_name.clone(),
+                };
+                let n = seen.entry(display.clone()).or_default();
+                *n += 1;
+                let prefix = if *n > 1 { format!("{}#{}", display, n) } else { display.clone() };
+                if let Some((_, body)) = index.get(comp_id) {
+                    for (key, value) in top_level_fields(body) {
+                        if !PLACEMENT_FIELDS.contains(&key) {
+                            properties.insert(format!("{}.{}", prefix, key), LOCAL_REF_RE.replace_all(&value, "{fileID: local}").into_owned());
+                        }
+                    }
+                }
+                components.push(display);
+            }
+            components.sort();
+
+            let mut kids = children.get(object.file_id.as_str()).cloned().unwrap_or_default();
+            kids.sort();
+            let parent = parents.get(&object.file_id).cloned();
+            Shape { file_id: object.file_id, name: object.name, path: object.path, components, properties, parent, children: kids }
+        })
+        .collect()
+}
+
+/// Hash of an object's components, properties and (recursively) its children's signatures.
+fn subtree_signature(i: usize, shapes: &[Shape], by_id: &HashMap<&str, usize>, memo: &mut HashMap<usize, u64>, visiting: &mut HashSet<usize>) -> u64 {
+    if let Some(sig) = memo.get(&i) {
+        return *sig;
+    }
+    let mut child_sigs: Vec<u64> = Vec::new();
+    if visiting.insert(i) {
+        for child in &shapes[i].children {
+            if let Some(&c) = by_id.get(child.as_str()) {
+                child_sigs.push(subtree_signature(c, shapes, by_id, memo, visiting));
+            }
+        }
+        visiting.remove(&i);
+    }
+    child_sigs.sort_unstable();
+    let sig = hash_of(&(&shapes[i].components, &shapes[i].properties, &child_sigs));
+    memo.insert(i, sig);
+    sig
+}
+
+fn hash_of<T: std::hash::Hash>(value: &T) -> u64 {
+    use std::hash::Hasher;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Property keys whose values differ between two objects.
+fn differing_properties(a: &Shape, b: &Shape) -> Vec<String> {
+    a.properties.keys().chain(b.properties.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|key| a.properties.get(*key) != b.properties.get(*key))
+        .cloned()
+        .collect()
+}
+
+fn base_name(name: &str) -> String {
+    COPY_SUFFIX_RE.replace(name, "").into_owned()
+}
+
+struct Cluster {
+    archetype: u64,
+    representative: usize,
+    members: Vec<usize>,
+    varying: BTreeSet<String>,
+}
+
+/// Cluster the GameObjects of Unity YAML content into groups of (near-)identical copies.
+///
+/// Objects match when their component types, non-placement property values and child
+/// subtrees are equal; placement (transform, parent, sibling order) and the `" (N)"` copy
+/// suffix are ignored. Groups with the same components and children whose property values
+/// differ in at most `max_differences` fields are merged, listing those fields as varying.
+/// Clusters nested in another cluster's copies (their parts) are left out.
+/// Returns the number of objects checked and the clusters with at least `min_count` members.
+pub fn find_duplicates(
+    content: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+    min_count: usize,
+    max_differences: usize,
+) -> (u32, Vec<DuplicateCluster>) {
+    let shapes = shapes(content, guid_cache, config);
+    let by_id: HashMap<&str, usize> = shapes.iter().enumerate().map(|(i, s)| (s.file_id.as_str(), i)).collect();
+    let mut memo = HashMap::new();
+    let signatures: Vec<u64> = (0..shapes.len())
+        .map(|i| subtree_signature(i, &shapes, &by_id, &mut memo, &mut HashSet::new()))
+        .collect();
+    let archetype = |i: usize| {
+        let mut child_sigs: Vec<u64> = shapes[i].children.iter()
+            .filter_map(|c| by_id.get(c.as_str()).map(|&c| signatures[c]))
+            .collect();
+        child_sigs.sort_unstable();
+        hash_of(&(&shapes[i].components, child_sigs))
+    };
+
+    let mut exact: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (i, shape) in shapes.iter().enumerate() {
+        let trivial = shape.children.is_empty() && shape.components.iter().all(|c| TRIVIAL_COMPONENTS.contains(&c.as_str()));
+        if !trivial {
+            exact.entry(signatures[i]).or_default().push(i);
+        }
+    }
+    let mut groups: Vec<Vec<usize>> = exact.into_values().collect();
+    groups.sort_by_key(|g| (std::cmp::Reverse(g.len()), g[0]));
+
+    let mut clusters: Vec<Cluster> = Vec::new();
+    for group in groups {
+        let rep = group[0];
+        let arch = archetype(rep);
+        let near = clusters.iter_mut().find_map(|c| {
+            if c.archetype != arch {
+                return None;
+            }
+            let diff = differing_properties(&shapes[c.representative], &shapes[rep]);
+            (diff.len() <= max_differences).then_some((c, diff))
+        });
+        match near {
+            Some((cluster, diff)) => {
+                cluster.members.extend(group);
+                cluster.varying.extend(diff);
+            }
+            None => clusters.push(Cluster { archetype: arch, representative: rep, members: group, varying: BTreeSet::new() }),
+        }
+    }
+    clusters.retain(|c| c.members.len() >= min_count.max(2));
+
+    let clustered: HashSet<&str> = clusters.iter()
+        .flat_map(|c| c.members.iter().map(|&i| shapes[i].file_id.as_str()))
+        .collect();
+    clusters.retain(|c| !c.members.iter().all(|&i| shapes[i].parent.as_deref().is_some_and(|p| clustered.contains(p))));
+
+    let descendants = |i: usize| {
+        let mut count = 0;
+        let mut stack = vec![i];
+        let mut seen = HashSet::from([i]);
+        while let Some(j) = stack.pop() {
+            for child in &shapes[j].children {
+                if let Some(&c) = by_id.get(child.as_str()).filter(|c| seen.insert(**c)) {
+                    count += 1;
+                    stack.push(c);
+                }
+            }
+        }
+        count
+    };
+
+    let mut result: Vec<DuplicateCluster> = clusters.into_iter()
+        .map(|mut c| {
+            c.members.sort_unstable();
+            let rep = &shapes[c.representative];
+            let names: BTreeSet<String> = c.members.iter().map(|&i| base_name(&shapes[i].name)).collect();
+            let count = c.members.len() as u32;
+            DuplicateCluster {
+                suggestion: format!("Extract \"{}\" into a prefab and replace its {} copies with prefab instances", base_name(&rep.name), count),
+                components: rep.components.clone(),
+                count,
+                file_ids: c.members.iter().map(|&i| shapes[i].file_id.clone()).collect(),
+                names: names.into_iter().collect(),
+                sample_path: rep.path.clone(),
+                descendant_count: descendants(c.representative),
+                identical: c.varying.is_empty(),
+                varying_properties: c.varying.into_iter().collect(),
+            }
+        })
+        .collect();
+    result.sort_by_key(|c| std::cmp::Reverse(c.count * (1 + c.descendant_count)));
+    (shapes.len() as u32, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A crate (Transform + BoxCollider) at `x` with a "Lid" child collider.
+    fn crate_object(id: u32, name: &str, x: u32, size: u32) -> String {
+        let (t, col, lid, lid_t, lid_col) = (id + 1, id + 2, id + 3, id + 4, id + 5);
+        format!("--- !u!1 &{id}\nGameObject:\n  m_Component:\n  - component: {{fileID: {t}}}\n  - component: {{fileID: {col}}}\n  m_Layer: 0\n  m_Name: {name}\n\
+--- !u!4 &{t}\nTransform:\n  m_GameObject: {{fileID: {id}}}\n  m_LocalPosition: {{x: {x}, y: 0, z: 0}}\n  m_Children:\n  - {{fileID: {lid_t}}}\n  m_Father: {{fileID: 0}}\n\
+--- !u!65 &{col}\nBoxCollider:\n  m_GameObject: {{fileID: {id}}}\n  m_Size: {{x: {size}, y: 1, z: 1}}\n\
+--- !u!1 &{lid}\nGameObject:\n  m_Component:\n  - component: {{fileID: {lid_t}}}\n  - component: {{fileID: {lid_col}}}\n  m_Layer: 0\n  m_Name: Lid\n\
+--- !u!4 &{lid_t}\nTransform:\n  m_GameObject: {{fileID: {lid}}}\n  m_Children: []\n  m_Father: {{fileID: {t}}}\n\
+--- !u!65 &{lid_col}\nBoxCollider:\n  m_GameObject: {{fileID: {lid}}}\n  m_Size: {{x: 1, y: 0.1, z: 1}}\n")
+    }
+
+    #[test]
+    fn test_top_level_fields_join_nested_values() {
+        let fields = top_level_fields("Transform:\n  m_Children:\n  - {fileID: 4}\n  m_Father: {fileID: 0}\n");
+        assert_eq!(fields, vec![("m_Children", "\n  - {fileID: 4}".to_string()), ("m_Father", "{fileID: 0}".to_string())]);
+    }
+
+    #[test]
+    fn test_find_duplicates_clusters_copies_not_their_parts() {
+        let content = format!("%YAML 1.1\n{}{}{}{}--- !u!1 &90\nGameObject:\n  m_Component: []\n  m_Name: Empty\n",
+            crate_object(10, "Crate", 0, 1),
+            crate_object(20, "Crate (1)", 5, 1),
+            crate_object(30, "Crate (2)", 9, 1),
+            crate_object(40, "BigCrate", 2, 3));
+        let config = ComponentConfig::default();
+
+        let (checked, clusters) = find_duplicates(&content, &HashMap::new(), &config, 2, 0);
+        assert_eq!(checked, 9);
+        // The BigCrate's lid is not part of a clustered crate, so the lids form their own cluster
+        assert_eq!(clusters.len(), 2);
+        assert_eq!((clusters[1].names[0].as_str(), clusters[1].count), ("Lid", 4));
+        let crates = &clusters[0];
+        assert_eq!((crates.count, crates.descendant_count, crates.identical), (3, 1, true));
+        assert_eq!(crates.file_ids, vec!["10", "20", "30"]);
+        assert_eq!(crates.names, vec!["Crate"]);
+        assert_eq!(crates.components, vec!["BoxCollider", "Transform"]);
+
+        let (_, near) = find_duplicates(&content, &HashMap::new(), &config, 2, 1);
+        assert_eq!(near.len(), 1, "lids are parts of the crates, not a separate cluster");
+        assert_eq!(near[0].count, 4);
+        assert_eq!(near[0].varying_properties, vec!["BoxCollider.m_Size"]);
+        assert_eq!(near[0].names, vec!["BigCrate", "Crate"]);
+
+        assert!(find_duplicates(&content, &HashMap::new(), &config, 5, 1).1.is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::common::{UnityEventInfo, UnityEventListener};
+use super::config::ComponentConfig;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::{field, owner_game_object, ref_file_id};
+
+static GUID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"guid:[ \t]*([0-9a-fA-F]{32})").unwrap()
+});
+
+/// A serialized UnityEvent: its property path and each `m_Calls` entry as flattened key/values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawEvent {
+    pub path: String,
+    pub calls: Vec<HashMap<String, String>>,
+}
+
+/// Find every `m_PersistentCalls` section in a component block.
+pub fn parse_persistent_calls(block: &str) -> Vec<RawEvent> {
+    let lines: Vec<&str> = block.lines().collect();
+    let mut events = Vec::new();
+    // (indent, key) of the enclosing mappings
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        i += 1;
+        if indent == 0 || trimmed.is_empty() {
+            continue;
+        }
+        let (key_indent, rest) = match trimmed.strip_prefix("- ") {
+            Some(rest) => (indent + 2, rest),
+            None => (indent, trimmed),
+        };
+        let Some((key, _)) = rest.split_once(':') else { continue };
+        if key.starts_with('{') {
+            continue;
+        }
+        stack.retain(|(ind, _)| *ind < key_indent);
+
+        if key == "m_PersistentCalls" {
+            let path = stack.iter().map(|(_, k)| k.as_str()).collect::<Vec<_>>().join(".");
+            let mut calls: Vec<HashMap<String, String>> = Vec::new();
+            let mut calls_indent = None;
+            while i < lines.len() {
+                let inner = lines[i];
+                let inner_indent = inner.len() - inner.trim_start().len();
+                let inner_trimmed = inner.trim();
+                if inner_trimmed.is_empty() {
+                    i += 1;
+                    continue;
+                }
+                if inner_indent <= key_indent && !(inner_trimmed.starts_with("- ") && Some(inner_indent) == calls_indent) {
+                    break;
+                }
+                i += 1;
+                if inner_trimmed.starts_with("m_Calls:") {
+                    calls_indent = Some(inner_indent);
+                    continue;
+                }
+                let item = match inner_trimmed.strip_prefix("- ") {
+                    Some(rest) if Some(inner_indent) == calls_indent => {
+                        calls.push(HashMap::new());
+                        rest
+                    }
+                    _ => inner_trimmed,
+                };
+                if let (Some(call), Some((k, v))) = (calls.last_mut(), item.split_once(':')) {
+                    call.entry(k.trim().to_string()).or_insert_with(|| v.trim().to_string());
+                }
+            }
+            events.push(RawEvent { path, calls });
+            continue;
+        }
+        stack.push((key_indent, key.trim().to_string()));
+    }
+
+    events
+}
+
+fn mode_name(mode: &str) -> &'static str {
+    match mode {
+        "0" => "event_defined",
+        "2" => "object",
+        "3" => "int",
+        "4" => "float",
+        "5" => "string",
+        "6" => "bool",
+        _ => "void",
+    }
+}
+
+fn call_state_name(state: &str) -> &'static str {
+    match state {
+        "0" => "off",
+        "1" => "editor_and_runtime",
+        _ => "runtime_only",
+    }
+}
+
+/// Short class name from `Namespace.Class, Assembly`.
+fn short_type_name(assembly_type: &str) -> Option<String> {
+    let qualified = assembly_type.split(',').next()?.trim();
+    Some(qualified.rsplit('.').next()?.to_string()).filter(|s| !s.is_empty())
+}
+
+/// Script asset path of a MonoBehaviour block, from its `m_Script` GUID.
+fn script_path<'a>(block: &str, guid_cache: &'a HashMap<String, String>) -> Option<&'a String> {
+    let script = field(block, "m_Script")?;
+    guid_cache.get(&GUID_RE.captures(script)?[1].to_lowercase())
+}
+
+/// Script name of a MonoBehaviour block, or the class name of any other component block.
+pub fn component_type(block: &str, guid_cache: &HashMap<String, String>) -> String {
+    script_path(block, guid_cache)
+        .and_then(|p| Path::new(p).file_stem())
+        .map(|s| s.to_string_lossy().to_string())
+        .or_else(|| block.lines().next().map(|l| l.trim_end_matches(':').to_string()))
+        .unwrap_or_default()
+}
+
+/// Resolved description of an object reference: (file id, guid, asset path, type, name, issue).
+struct ResolvedRef {
+    file_id: String,
+    guid: Option<String>,
+    path: Option<String>,
+    type_name: Option<String>,
+    name: Option<String>,
+    script: Option<String>,
+    issue: Option<&'static str>,
+}
+
+fn resolve_ref(value: &str, index: &BlockIndex, guid_cache: &HashMap<String, String>, config: &ComponentConfig) -> ResolvedRef {
+    let file_id = ref_file_id(value).unwrap_or("0").to_string();
+    let guid = GUID_RE.captures(value).map(|c| c[1].to_lowercase());
+    let mut resolved = ResolvedRef { file_id, guid, path: None, type_name: None, name: None, script: None, issue: None };
+
+    if resolved.file_id == "0" {
+        resolved.issue = Some("missing_target");
+    } else if let Some(guid) = &resolved.guid {
+        match guid_cache.get(guid) {
+            Some(path) => {
+                resolved.name = Path::new(path).file_stem().map(|s| s.to_string_lossy().to_string());
+                resolved.path = Some(path.clone());
+            }
+            None => resolved.issue = Some("missing_target_asset"),
+        }
+    } else {
+        match index.get(&resolved.file_id) {
+            Some((class_id, block)) if class_id == config.gameobject_class_id => {
+                resolved.type_name = Some("GameObject".to_string());
+                resolved.name = field(block, "m_Name").map(str::to_string);
+            }
+            Some((_, block)) => {
+                resolved.type_name = Some(component_type(block, guid_cache));
+                resolved.name = Some(owner_game_object(index, block).1);
+                resolved.script = script_path(block, guid_cache).cloned();
+            }
+            None => resolved.issue = Some("missing_target"),
+        }
+    }
+    resolved
+}
+
+/// All UnityEvents with persistent listeners in a scene/prefab, targets resolved to names.
+/// `method_exists(script_path, method)` answers whether a script defines a method
+/// (None when it cannot tell, e.g. an inherited method).
+pub fn list_events(
+    file: &str,
+    content: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+    method_exists: &mut dyn FnMut(&str, &str) -> Option<bool>,
+) -> Vec<UnityEventInfo> {
+    let index = BlockIndex::new(content);
+    let mut events = Vec::new();
+
+    for (class_id, file_id, block) in UnityYamlParser::parse_all_blocks(content) {
+        if class_id == config.gameobject_class_id || !block.contains("m_PersistentCalls:") {
+            continue;
+        }
+        let (game_object_id, game_object_name) = owner_game_object(&index, &block);
+        let component = component_type(&block, guid_cache);
+
+        for raw in parse_persistent_calls(&block) {
+            if raw.calls.is_empty() {
+                continue;
+            }
+            let listeners = raw.calls.iter().map(|call| {
+                let get = |k: &str| call.get(k).map(String::as_str).unwrap_or("");
+                let target = resolve_ref(get("m_Target"), &index, guid_cache, config);
+                let method = get("m_MethodName").to_string();
+                let mode = mode_name(get("m_Mode"));
+                let argument = match mode {
+                    "object" => {
+                        let arg = resolve_ref(get("m_ObjectArgument"), &index, guid_cache, config);
+                        Some(arg.path.or(arg.name).unwrap_or_else(|| "None".to_string()))
+                    }
+                    "int" => Some(get("m_IntArgument").to_string()),
+                    "float" => Some(get("m_FloatArgument").to_string()),
+                    "string" => Some(get("m_StringArgument").to_string()),
+                    "bool" => Some((get("m_BoolArgument") == "1").to_string()),
+                    _ => None,
+                };
+
+                let issue = target.issue.or_else(|| {
+                    if method.is_empty() {
+                        return Some("missing_method");
+                    }
+                    let script = target.script.as_deref().filter(|p| p.ends_with(".cs"))?;
+                    match method_exists(script, &method) {
+                        Some(false) => Some("method_not_found"),
+                        _ => None,
+                    }
+                });
+
+                UnityEventListener {
+                    method,
+                    mode: mode.to_string(),
+                    argument,
+                    call_state: call_state_name(get("m_CallState")).to_string(),
+                    target_file_id: target.file_id,
+                    target_guid: target.guid,
+                    target_path: target.path,
+                    target_type: target.type_name.or_else(|| short_type_name(get("m_TargetAssemblyTypeName"))),
+                    target_name: target.name,
+                    issue: issue.map(str::to_string),
+                }
+            }).collect();
+
+            events.push(UnityEventInfo {
+                file: file.to_string(),
+                game_object_id: game_object_id.clone(),
+                game_object_name: game_object_name.clone(),
+                component_file_id: file_id.clone(),
+                component_type: component.clone(),
+                event: raw.path,
+                listeners,
+            });
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Name: StartButton\n  m_IsActive: 1\n\
+--- !u!114 &2\nMonoBehaviour:\n  m_GameObject: {fileID: 1}\n  m_Script: {fileID: 11500000, guid: 4e29b1a8efbd4b44bb3f3716e73f07ff, type: 3}\n  m_OnClick:\n    m_PersistentCalls:\n      m_Calls:\n      - m_Target: {fileID: 4}\n        m_TargetAssemblyTypeName: Game.Menu, Assembly-CSharp\n        m_MethodName: StartGame\n        m_Mode: 5\n        m_Arguments:\n          m_ObjectArgument: {fileID: 0}\n          m_StringArgument: Level1\n        m_CallState: 2\n      - m_Target: {fileID: 99}\n        m_MethodName: Gone\n        m_Mode: 1\n        m_Arguments:\n          m_ObjectArgument: {fileID: 0}\n        m_CallState: 2\n      - m_Target: {fileID: 4}\n        m_MethodName: Typo\n        m_Mode: 1\n        m_CallState: 1\n  m_Empty:\n    m_PersistentCalls:\n      m_Calls: []\n\
+--- !u!1 &3\nGameObject:\n  m_Component:\n  - component: {fileID: 4}\n  m_Name: MenuRoot\n  m_IsActive: 1\n\
+--- !u!114 &4\nMonoBehaviour:\n  m_GameObject: {fileID: 3}\n  m_Script: {fileID: 11500000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}\n";
+
+    #[test]
+    fn test_parse_persistent_calls() {
+        let index = BlockIndex::new(SCENE);
+        let (_, block) = index.get("2").unwrap();
+        let events = parse_persistent_calls(block);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].path, "m_OnClick");
+        assert_eq!(events[0].calls.len(), 3);
+        assert_eq!(events[0].calls[0]["m_MethodName"], "StartGame");
+        assert_eq!(events[0].calls[0]["m_StringArgument"], "Level1");
+        assert_eq!(events[0].calls[1]["m_Target"], "{fileID: 99}");
+        assert!(events[1].calls.is_empty());
+    }
+
+    #[test]
+    fn test_list_events_resolves_and_flags() {
+        let mut cache = HashMap::new();
+        cache.insert("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(), "Assets/Menu.cs".to_string());
+        let mut exists = |script: &str, method: &str| Some(script == "Assets/Menu.cs" && method == "StartGame");
+        let events = list_events("S.unity", SCENE, &cache, &ComponentConfig::default(), &mut exists);
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.game_object_name, "StartButton");
+        assert_eq!(event.event, "m_OnClick");
+
+        let ok = &event.listeners[0];
+        assert_eq!(ok.target_name.as_deref(), Some("MenuRoot"));
+        assert_eq!(ok.target_type.as_deref(), Some("Menu"));
+        assert_eq!(ok.mode, "string");
+        assert_eq!(ok.argument.as_deref(), Some("Level1"));
+        assert_eq!(ok.call_state, "runtime_only");
+        assert!(ok.issue.is_none());
+
+        assert_eq!(event.listeners[1].issue.as_deref(), Some("missing_target"));
+        assert_eq!(event.listeners[2].issue.as_deref(), Some("method_not_found"));
+        assert_eq!(event.listeners[2].call_state, "editor_and_runtime");
+    }
+}
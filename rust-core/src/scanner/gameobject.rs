@@ -1,7 +1,13 @@
 use regex::Regex;
+use std::collections::HashMap;
 use std::sync::LazyLock;
+use crate::common::ChildEntry;
 use super::config::ComponentConfig;
-use super::parser::BlockIndex;
+use super::parser::{find_block, BlockIndex};
+use super::prefab;
+use super::rendering::field;
+
+const SCENE_ROOTS_CLASS_ID: u32 = 1660057539;
 
 // Cached regexes — compiled once, reused across all calls
 static TAG_RE: LazyLock<Regex> = LazyLock::new(|| {
@@ -13,29 +19,21 @@ static LAYER_RE: LazyLock<Regex> = LazyLock::new(|| {
 static FATHER_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"m_Father:\s*\{fileID:\s*(-?\d+)\}").unwrap()
 });
-static CHILDREN_SECTION_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"m_Children:[\s\S]*?\[[\s\S]*?\]").unwrap()
-});
 static CHILD_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"\{fileID:\s*(-?\d+)\}").unwrap()
 });
 static COMP_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"component:\s*\{fileID:\s*(-?\d+)\}").unwrap()
 });
-
-/// Extract a block from content by header
-fn extract_block<'a>(content: &'a str, header: &str) -> Option<&'a str> {
-    let start_pos = content.find(header)?;
-    let after_header = &content[start_pos..];
-
-    // Find the end of this block (start of next block or end of content)
-    let end_offset = after_header[header.len()..]
-        .find("--- !u!")
-        .map(|pos| header.len() + pos)
-        .unwrap_or(after_header.len());
-
-    Some(&after_header[..end_offset])
-}
+static OWNER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_GameObject:[ \t]*\{fileID:[ \t]*(-?\d+)\}").unwrap()
+});
+static NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_Name:[ \t]*([^\n]*)").unwrap()
+});
+static PREFAB_INSTANCE_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_PrefabInstance:[ \t]*\{fileID:[ \t]*(-?\d+)\}").unwrap()
+});
 
 /// Extract metadata from a GameObject block
 pub fn extract_metadata(content: &str, file_id: &str) -> (String, u32, Option<String>, Vec<String>) {
@@ -44,8 +42,7 @@ pub fn extract_metadata(content: &str, file_id: &str) -> (String, u32, Option<St
 
 /// Extract metadata from a GameObject block with custom config
 pub fn extract_metadata_with_config(content: &str, file_id: &str, config: &ComponentConfig) -> (String, u32, Option<String>, Vec<String>) {
-    let header = format!("--- !u!{} &{}", config.gameobject_class_id, file_id);
-    let go_block = match extract_block(content, &header) {
+    let go_block = match find_block(content, config.gameobject_class_id, file_id) {
         Some(block) => block,
         None => return ("Untagged".to_string(), 0, None, Vec::new()),
     };
@@ -79,8 +76,7 @@ fn extract_hierarchy(content: &str, file_id: &str) -> (Option<String>, Vec<Strin
 
 fn extract_hierarchy_with_config(content: &str, file_id: &str, config: &ComponentConfig) -> (Option<String>, Vec<String>) {
     // Find the GameObject block
-    let go_header = format!("--- !u!{} &{}", config.gameobject_class_id, file_id);
-    let go_block = match extract_block(content, &go_header) {
+    let go_block = match find_block(content, config.gameobject_class_id, file_id) {
         Some(block) => block,
         None => return (None, Vec::new()),
     };
@@ -94,8 +90,7 @@ fn extract_hierarchy_with_config(content: &str, file_id: &str, config: &Componen
     // Find hierarchy provider components (Transform-like) among the component refs
     for ref_id in &comp_refs {
         for &class_id in &config.hierarchy_providers {
-            let header = format!("--- !u!{} &{}", class_id, ref_id);
-            if let Some(block) = extract_block(content, &header) {
+            if let Some(block) = find_block(content, class_id, ref_id) {
                 let parent_id = extract_parent_from_transform(block);
                 let children = extract_children_from_transform(block);
                 return (parent_id, children);
@@ -147,6 +142,233 @@ fn extract_hierarchy_indexed(
     (None, Vec::new())
 }
 
+/// Resolve child Transform fileIDs (in m_Children order) to their GameObjects and names.
+/// A stripped child Transform is the root of a nested prefab instance and resolves to that
+/// PrefabInstance, named from its m_Name override.
+pub fn resolve_children_indexed(index: &BlockIndex, children: &[String], config: &ComponentConfig) -> Vec<ChildEntry> {
+    let capture = |re: &Regex, block: &str| re.captures(block)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|id| id != "0");
+
+    children
+        .iter()
+        .enumerate()
+        .map(|(i, transform_id)| {
+            let block = index.get(transform_id).map_or("", |(_, body)| body);
+            let game_object_id = capture(&OWNER_RE, block);
+            let prefab_instance_id = if game_object_id.is_none() { capture(&PREFAB_INSTANCE_REF_RE, block) } else { None };
+            let name = match (&game_object_id, &prefab_instance_id) {
+                (Some(go_id), _) => index.get_by_class_and_id(config.gameobject_class_id, go_id)
+                    .and_then(|go| capture(&NAME_RE, go)),
+                (None, Some(pi_id)) => index.get_by_class_and_id(1001, pi_id)
+                    .and_then(prefab::extract_name_from_modifications),
+                (None, None) => None,
+            };
+            ChildEntry {
+                sibling_index: i as u32,
+                transform_id: transform_id.clone(),
+                name: name.unwrap_or_default(),
+                game_object_id,
+                prefab_instance_id,
+            }
+        })
+        .collect()
+}
+
+/// Find the file_id of the hierarchy provider (Transform-like) component referenced by a GameObject block.
+pub fn find_transform_id_indexed<'a>(
+    index: &BlockIndex,
+    go_block: &'a str,
+    config: &ComponentConfig,
+) -> Option<&'a str> {
+    COMP_REF_RE
+        .captures_iter(go_block)
+        .filter_map(|c| c.get(1).map(|m| m.as_str()))
+        .find(|ref_id| {
+            index.get(ref_id)
+                .is_some_and(|(class_id, _)| config.hierarchy_providers.contains(&class_id))
+        })
+}
+
+/// Map each GameObject file_id to its parent GameObject file_id, using the
+/// hierarchy providers' m_GameObject / m_Father references (`parse_all_blocks` output).
+pub fn build_parent_map(blocks: &[(u32, String, String)], config: &ComponentConfig) -> HashMap<String, String> {
+    let mut transform_owner: HashMap<&str, &str> = HashMap::new();
+    let mut transform_father: Vec<(&str, String)> = Vec::new();
+    for (class_id, file_id, body) in blocks {
+        if !config.hierarchy_providers.contains(class_id) {
+            continue;
+        }
+        if let Some(go) = OWNER_RE.captures(body).and_then(|c| c.get(1)) {
+            transform_owner.insert(file_id.as_str(), go.as_str());
+        }
+        if let Some(father) = extract_parent_from_transform(body) {
+            transform_father.push((file_id.as_str(), father));
+        }
+    }
+
+    transform_father
+        .iter()
+        .filter_map(|(transform, father)| {
+            let go = transform_owner.get(transform)?;
+            let parent = transform_owner.get(father.as_str())?;
+            Some((go.to_string(), parent.to_string()))
+        })
+        .collect()
+}
+
+/// Hierarchy path ("Canvas/ShopPanel/BuyButton") and parent name of every GameObject and
+/// PrefabInstance in `names` (file_id -> name), from the transform graph of `parse_all_blocks`
+/// output. A stripped Transform stands for its PrefabInstance, so objects parented under a
+/// nested prefab continue their path through the instance's name.
+pub fn hierarchy_breadcrumbs(
+    blocks: &[(u32, String, String)],
+    names: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> HashMap<String, (String, Option<String>)> {
+    let capture = |re: &Regex, block: &str| re.captures(block)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
+        .filter(|id| id != "0");
+
+    // Transform -> owning object (GameObject, or PrefabInstance for stripped transforms)
+    let mut owner: HashMap<&str, String> = HashMap::new();
+    // Object -> its parent's Transform
+    let mut edges: Vec<(String, String)> = Vec::new();
+    for (class_id, file_id, body) in blocks {
+        if config.hierarchy_providers.contains(class_id) {
+            let Some(node) = capture(&OWNER_RE, body).or_else(|| capture(&PREFAB_INSTANCE_REF_RE, body)) else { continue };
+            if let Some(father) = extract_parent_from_transform(body) {
+                edges.push((node.clone(), father));
+            }
+            owner.insert(file_id.as_str(), node);
+        } else if *class_id == 1001 {
+            if let Some(parent) = prefab::extract_transform_parent(body) {
+                edges.push((file_id.clone(), parent));
+            }
+        }
+    }
+    let parent_of: HashMap<String, String> = edges.into_iter()
+        .filter_map(|(node, father)| Some((node, owner.get(father.as_str())?.clone())))
+        .collect();
+
+    names.keys()
+        .map(|id| {
+            let mut segments = vec![names[id].as_str()];
+            let mut seen = std::collections::HashSet::from([id.as_str()]);
+            let mut current = id.as_str();
+            while let Some(parent) = parent_of.get(current).filter(|p| seen.insert(p.as_str())) {
+                segments.push(names.get(parent).map_or("", String::as_str));
+                current = parent;
+            }
+            segments.reverse();
+            let parent_name = parent_of.get(id).and_then(|p| names.get(p)).cloned();
+            (id.clone(), (segments.join("/"), parent_name))
+        })
+        .collect()
+}
+
+/// Position in the editor's Hierarchy window of every GameObject and PrefabInstance of
+/// `parse_all_blocks` output: a preorder walk with siblings ordered by their parent's
+/// `m_Children`, and roots by SceneRoots' `m_Roots` (Unity 2022+) or else `m_RootOrder`.
+/// Objects neither source places keep their document order after the placed siblings.
+pub fn hierarchy_order(blocks: &[(u32, String, String)], config: &ComponentConfig) -> HashMap<String, usize> {
+    let capture = |re: &Regex, block: &str| re.captures(block)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
+        .filter(|id| id != "0");
+
+    // Objects in document order, and the parent transform of each
+    let mut nodes: Vec<&str> = Vec::new();
+    let mut owner: HashMap<&str, String> = HashMap::new();
+    let mut edges: Vec<(String, String)> = Vec::new();
+    let mut root_order: HashMap<String, usize> = HashMap::new();
+    let mut sibling_lists: Vec<Vec<String>> = Vec::new();
+    for (class_id, file_id, body) in blocks {
+        if *class_id == config.gameobject_class_id || *class_id == 1001 {
+            nodes.push(file_id);
+        }
+        if config.hierarchy_providers.contains(class_id) {
+            let Some(node) = capture(&OWNER_RE, body).or_else(|| capture(&PREFAB_INSTANCE_REF_RE, body)) else { continue };
+            match extract_parent_from_transform(body) {
+                Some(father) => edges.push((node.clone(), father)),
+                None => {
+                    if let Some(order) = field(body, "m_RootOrder").and_then(|v| v.parse().ok()) {
+                        root_order.insert(node.clone(), order);
+                    }
+                }
+            }
+            sibling_lists.push(extract_children_from_transform(body));
+            owner.insert(file_id.as_str(), node);
+        } else if *class_id == 1001 {
+            match prefab::extract_transform_parent(body) {
+                Some(parent) => edges.push((file_id.clone(), parent)),
+                None => {
+                    // Pre-2022 root instances carry their root order as a modification
+                    let order = prefab::extract_modifications(body).into_iter()
+                        .find(|m| m.property_path == "m_RootOrder")
+                        .and_then(|m| m.value.parse().ok());
+                    if let Some(order) = order {
+                        root_order.insert(file_id.clone(), order);
+                    }
+                }
+            }
+        } else if *class_id == SCENE_ROOTS_CLASS_ID {
+            sibling_lists.push(extract_ref_list(body, "m_Roots"));
+        }
+    }
+
+    let mut position: HashMap<String, usize> = HashMap::new();
+    for list in &sibling_lists {
+        for (idx, transform) in list.iter().enumerate() {
+            if let Some(node) = owner.get(transform.as_str()) {
+                position.entry(node.clone()).or_insert(idx);
+            }
+        }
+    }
+    for (node, order) in root_order {
+        position.entry(node).or_insert(order);
+    }
+
+    let document: HashMap<&str, usize> = nodes.iter().enumerate().map(|(idx, id)| (*id, idx)).collect();
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut roots: Vec<&str> = Vec::new();
+    let parent_of: HashMap<String, &str> = edges.into_iter()
+        .filter_map(|(node, father)| Some((node, owner.get(father.as_str())?.as_str())))
+        .collect();
+    for &node in &nodes {
+        match parent_of.get(node).filter(|p| document.contains_key(**p) && **p != node) {
+            Some(parent) => children.entry(*parent).or_default().push(node),
+            None => roots.push(node),
+        }
+    }
+    let sibling_key = |id: &&str| (position.get(*id).copied().unwrap_or(usize::MAX), document[*id]);
+    roots.sort_by_key(sibling_key);
+    for list in children.values_mut() {
+        list.sort_by_key(sibling_key);
+    }
+
+    let mut order: HashMap<String, usize> = HashMap::with_capacity(nodes.len());
+    let mut stack: Vec<&str> = roots.into_iter().rev().collect();
+    while let Some(node) = stack.pop() {
+        if order.contains_key(node) {
+            continue;
+        }
+        order.insert(node.to_string(), order.len());
+        if let Some(list) = children.get(node) {
+            stack.extend(list.iter().rev());
+        }
+    }
+    // Parent cycles in broken files are unreachable from any root
+    for node in nodes {
+        if !order.contains_key(node) {
+            order.insert(node.to_string(), order.len());
+        }
+    }
+    order
+}
+
 fn extract_parent_from_transform(block: &str) -> Option<String> {
     FATHER_RE.captures(block)
         .and_then(|c| c.get(1))
@@ -154,17 +376,32 @@ fn extract_parent_from_transform(block: &str) -> Option<String> {
         .filter(|s| s != "0")
 }
 
+/// Child Transform fileIDs in sibling order, from either the block sequence Unity writes
+/// (`m_Children:` followed by `- {fileID: N}` lines) or the flow form (`m_Children: []`).
 fn extract_children_from_transform(block: &str) -> Vec<String> {
-    if let Some(m) = CHILDREN_SECTION_RE.find(block) {
-        let children_section = m.as_str();
-        return CHILD_REF_RE
-            .captures_iter(children_section)
-            .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
-            .filter(|s| s != "0")
-            .collect();
-    }
+    extract_ref_list(block, "m_Children")
+}
 
-    Vec::new()
+/// Non-null fileIDs of a reference list field (`m_Children`, SceneRoots' `m_Roots`), in order.
+pub fn extract_ref_list(block: &str, key: &str) -> Vec<String> {
+    let key = format!("{}:", key);
+    let Some(start) = block.find(&key) else { return Vec::new() };
+    let rest = &block[start + key.len()..];
+    let (first_line, following) = rest.split_once('\n').unwrap_or((rest, ""));
+    let section = if first_line.trim().is_empty() {
+        let end = following.lines()
+            .take_while(|line| line.trim_start().starts_with("- "))
+            .map(|line| line.len() + 1)
+            .sum::<usize>();
+        &following[..end.min(following.len())]
+    } else {
+        first_line
+    };
+    CHILD_REF_RE
+        .captures_iter(section)
+        .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
+        .filter(|s| s != "0")
+        .collect()
 }
 
 #[cfg(test)]
@@ -215,5 +452,108 @@ mod tests {
         assert_eq!(original.1, indexed.1); // layer
         assert_eq!(original.2, indexed.2); // parent_id
         assert_eq!(original.3, indexed.3); // children
+        assert_eq!(indexed.3, vec!["400".to_string(), "500".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_children_in_sibling_order() {
+        let content = "%YAML 1.1
+--- !u!1 &1
+GameObject:
+  m_Component:
+  - component: {fileID: 2}
+  m_Name: Menu
+--- !u!224 &2
+RectTransform:
+  m_GameObject: {fileID: 1}
+  m_Children:
+  - {fileID: 31}
+  - {fileID: 21}
+  - {fileID: 41}
+  m_Father: {fileID: 0}
+--- !u!1 &20
+GameObject:
+  m_Name: Background
+--- !u!224 &21
+RectTransform:
+  m_GameObject: {fileID: 20}
+  m_Father: {fileID: 2}
+--- !u!1 &30
+GameObject:
+  m_Name: Title
+--- !u!224 &31
+RectTransform:
+  m_GameObject: {fileID: 30}
+  m_Father: {fileID: 2}
+--- !u!224 &41 stripped
+RectTransform:
+  m_CorrespondingSourceObject: {fileID: 100, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}
+  m_PrefabInstance: {fileID: 50}
+--- !u!1001 &50
+PrefabInstance:
+  m_Modification:
+    m_TransformParent: {fileID: 2}
+    m_Modifications:
+    - target: {fileID: 101, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}
+      propertyPath: m_Name
+      value: CloseButton
+      objectReference: {fileID: 0}
+";
+        let index = BlockIndex::new(content);
+        let config = ComponentConfig::default();
+        let (_, _, _, children) = extract_metadata_indexed(&index, "1", &config);
+        let entries = resolve_children_indexed(&index, &children, &config);
+        let summary: Vec<(u32, &str, Option<&str>, Option<&str>)> = entries.iter()
+            .map(|c| (c.sibling_index, c.name.as_str(), c.game_object_id.as_deref(), c.prefab_instance_id.as_deref()))
+            .collect();
+        assert_eq!(summary, vec![
+            (0, "Title", Some("30"), None),
+            (1, "Background", Some("20"), None),
+            (2, "CloseButton", None, Some("50")),
+        ]);
+    }
+
+    #[test]
+    fn test_hierarchy_breadcrumbs_through_nested_prefab() {
+        let content = "--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Name: Canvas\n\
+--- !u!224 &2\nRectTransform:\n  m_GameObject: {fileID: 1}\n  m_Father: {fileID: 0}\n\
+--- !u!1 &3\nGameObject:\n  m_Component:\n  - component: {fileID: 4}\n  m_Name: Button\n\
+--- !u!224 &4\nRectTransform:\n  m_GameObject: {fileID: 3}\n  m_Father: {fileID: 2}\n\
+--- !u!1001 &50\nPrefabInstance:\n  m_Modification:\n    m_TransformParent: {fileID: 2}\n    m_Modifications: []\n\
+--- !u!224 &51 stripped\nRectTransform:\n  m_PrefabInstance: {fileID: 50}\n\
+--- !u!1 &5\nGameObject:\n  m_Component:\n  - component: {fileID: 6}\n  m_Name: Button\n\
+--- !u!224 &6\nRectTransform:\n  m_GameObject: {fileID: 5}\n  m_Father: {fileID: 51}\n";
+        let names: HashMap<String, String> = [("1", "Canvas"), ("3", "Button"), ("50", "ShopPanel"), ("5", "Button")]
+            .into_iter().map(|(id, name)| (id.to_string(), name.to_string())).collect();
+        let crumbs = hierarchy_breadcrumbs(&crate::scanner::parser::UnityYamlParser::parse_all_blocks(content), &names, &ComponentConfig::default());
+        assert_eq!(crumbs["1"], ("Canvas".to_string(), None));
+        assert_eq!(crumbs["3"], ("Canvas/Button".to_string(), Some("Canvas".to_string())));
+        assert_eq!(crumbs["50"], ("Canvas/ShopPanel".to_string(), Some("Canvas".to_string())));
+        assert_eq!(crumbs["5"], ("Canvas/ShopPanel/Button".to_string(), Some("ShopPanel".to_string())));
+    }
+
+    #[test]
+    fn test_hierarchy_order_root_order_and_children() {
+        let content = "--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Name: B\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Children:\n  - {fileID: 51}\n  - {fileID: 4}\n  m_Father: {fileID: 0}\n  m_RootOrder: 1\n\
+--- !u!1 &3\nGameObject:\n  m_Component:\n  - component: {fileID: 4}\n  m_Name: B2\n\
+--- !u!4 &4\nTransform:\n  m_GameObject: {fileID: 3}\n  m_Children: []\n  m_Father: {fileID: 2}\n  m_RootOrder: 1\n\
+--- !u!1 &5\nGameObject:\n  m_Component:\n  - component: {fileID: 6}\n  m_Name: A\n\
+--- !u!4 &6\nTransform:\n  m_GameObject: {fileID: 5}\n  m_Children: []\n  m_Father: {fileID: 0}\n  m_RootOrder: 0\n\
+--- !u!1001 &50\nPrefabInstance:\n  m_Modification:\n    m_TransformParent: {fileID: 2}\n    m_Modifications: []\n\
+--- !u!4 &51 stripped\nTransform:\n  m_PrefabInstance: {fileID: 50}\n\
+--- !u!1001 &60\nPrefabInstance:\n  m_Modification:\n    m_TransformParent: {fileID: 0}\n    m_Modifications:\n    - target: {fileID: 7, guid: 0123456789abcdef0123456789abcdef, type: 3}\n      propertyPath: m_RootOrder\n      value: 2\n      objectReference: {fileID: 0}\n\
+--- !u!4 &61 stripped\nTransform:\n  m_PrefabInstance: {fileID: 60}\n";
+        let ranked = |content: &str| {
+            let order = hierarchy_order(&crate::scanner::parser::UnityYamlParser::parse_all_blocks(content), &ComponentConfig::default());
+            let mut ids: Vec<(usize, String)> = order.into_iter().map(|(id, rank)| (rank, id)).collect();
+            ids.sort();
+            ids.into_iter().map(|(_, id)| id).collect::<Vec<_>>()
+        };
+        assert_eq!(ranked(content), vec!["5", "1", "50", "3", "60"]);
+
+        // SceneRoots (Unity 2022+) takes precedence over m_RootOrder
+        let with_roots = format!("{}--- !u!1660057539 &9223372036854775807\nSceneRoots:\n  m_Roots:\n  - {{fileID: 61}}\n  - {{fileID: 2}}\n  - {{fileID: 6}}\n", content);
+        assert_eq!(ranked(&with_roots), vec!["60", "1", "50", "3", "5"]);
     }
 }
@@ -0,0 +1,132 @@
+//! Stale GUIDs: references a rarely opened scene still holds after a refactor moved, replaced
+//! or re-imported the asset. They resolve through a user alias map (old GUID to new GUID or
+//! path) or an earlier GUID cache snapshot standing in for the project's .meta history.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::common::GuidResolution;
+
+/// Alias hops followed before giving up (alias maps may chain across several refactors).
+const MAX_HOPS: usize = 8;
+
+fn is_guid(text: &str) -> bool {
+    text.len() == 32 && text.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// `{ key: string }` entries of a JSON object, GUID keys lowercased and paths with `/`.
+/// Non-string entries are ignored.
+pub fn string_entries(map: &serde_json::Map<String, serde_json::Value>) -> impl Iterator<Item = (String, String)> + '_ {
+    map.iter().filter_map(|(guid, value)| {
+        let value = value.as_str()?.trim();
+        let value = if is_guid(value) { value.to_lowercase() } else { value.replace('\\', "/") };
+        Some((guid.to_lowercase(), value))
+    })
+}
+
+#[derive(Debug, Default)]
+pub struct GuidAliases {
+    /// Old GUID to its replacement: a GUID or a project-relative path
+    pub aliases: HashMap<String, String>,
+    /// GUID to the path it had in an earlier snapshot
+    pub history: HashMap<String, String>,
+}
+
+impl GuidAliases {
+    fn guid_of_path(guid_cache: &HashMap<String, String>, path: &str) -> Option<String> {
+        guid_cache.iter().find(|(_, p)| *p == path).map(|(g, _)| g.clone())
+    }
+
+    fn resolution(guid: &str, status: &str, path: Option<String>, new_guid: Option<String>, former_path: Option<String>, message: String) -> GuidResolution {
+        GuidResolution { guid: guid.to_string(), status: status.to_string(), path, new_guid, former_path, message }
+    }
+
+    /// Follow the alias map from `guid` to an asset in the current cache (or a bare path).
+    fn follow_aliases(&self, guid: &str, guid_cache: &HashMap<String, String>) -> Option<GuidResolution> {
+        let mut current = self.aliases.get(guid)?;
+        for _ in 0..MAX_HOPS {
+            if !is_guid(current) {
+                let new_guid = Self::guid_of_path(guid_cache, current);
+                return Some(Self::resolution(guid, "aliased", Some(current.clone()), new_guid, None, format!("moved to {}", current)));
+            }
+            if let Some(path) = guid_cache.get(current) {
+                return Some(Self::resolution(guid, "aliased", Some(path.clone()), Some(current.clone()), None, format!("moved to {}", path)));
+            }
+            current = self.aliases.get(current)?;
+        }
+        None
+    }
+
+    /// Where the asset behind a GUID lives now: the cache itself, then the alias map, then the
+    /// history (same path under a new GUID, or the only asset with the same file name).
+    /// None when nothing is known about the GUID.
+    pub fn resolve(&self, guid: &str, guid_cache: &HashMap<String, String>) -> Option<GuidResolution> {
+        let guid = guid.to_lowercase();
+        if let Some(path) = guid_cache.get(&guid) {
+            return Some(Self::resolution(&guid, "current", Some(path.clone()), None, None, path.clone()));
+        }
+        if let Some(resolved) = self.follow_aliases(&guid, guid_cache) {
+            return Some(resolved);
+        }
+
+        let former = self.history.get(&guid)?;
+        if let Some(new_guid) = Self::guid_of_path(guid_cache, former) {
+            let message = format!("regenerated: {} now has GUID {}", former, new_guid);
+            return Some(Self::resolution(&guid, "regenerated", Some(former.clone()), Some(new_guid), Some(former.clone()), message));
+        }
+        let file_name = Path::new(former).file_name();
+        let mut same_name = guid_cache.iter().filter(|(_, p)| Path::new(p.as_str()).file_name() == file_name);
+        match (same_name.next(), same_name.next()) {
+            (Some((new_guid, path)), None) => {
+                Some(Self::resolution(&guid, "moved", Some(path.clone()), Some(new_guid.clone()), Some(former.clone()), format!("moved to {}", path)))
+            }
+            _ => Some(Self::resolution(&guid, "deleted", None, None, Some(former.clone()), format!("deleted (was {})", former))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "11111111111111111111111111111111";
+    const MID: &str = "22222222222222222222222222222222";
+    const NEW: &str = "33333333333333333333333333333333";
+
+    fn guid_cache() -> HashMap<String, String> {
+        [(NEW, "Assets/Art/Rock.mat"), ("44444444444444444444444444444444", "Assets/Levels/Forest.unity")]
+            .into_iter().map(|(g, p)| (g.to_string(), p.to_string())).collect()
+    }
+
+    #[test]
+    fn test_resolve_through_alias_chain_and_path() {
+        let mut aliases = GuidAliases::default();
+        let map = serde_json::json!({ OLD: MID, MID: NEW, "55555555555555555555555555555555": "Assets\\Gone\\Tree.prefab" });
+        aliases.aliases.extend(string_entries(map.as_object().unwrap()));
+        let cache = guid_cache();
+
+        let chained = aliases.resolve(&OLD.to_uppercase(), &cache).unwrap();
+        assert_eq!((chained.status.as_str(), chained.path.as_deref(), chained.new_guid.as_deref()), ("aliased", Some("Assets/Art/Rock.mat"), Some(NEW)));
+        assert_eq!(chained.message, "moved to Assets/Art/Rock.mat");
+        let to_path = aliases.resolve("55555555555555555555555555555555", &cache).unwrap();
+        assert_eq!((to_path.path.as_deref(), to_path.new_guid), (Some("Assets/Gone/Tree.prefab"), None));
+        assert_eq!(aliases.resolve(NEW, &cache).unwrap().status, "current");
+        assert!(aliases.resolve("66666666666666666666666666666666", &cache).is_none());
+    }
+
+    #[test]
+    fn test_resolve_from_history() {
+        let mut aliases = GuidAliases::default();
+        for (guid, path) in [(OLD, "Assets/Rock.mat"), (MID, "Assets/Levels/Forest.unity"), ("77777777777777777777777777777777", "Assets/Old.anim")] {
+            aliases.history.insert(guid.to_string(), path.to_string());
+        }
+        let cache = guid_cache();
+
+        let moved = aliases.resolve(OLD, &cache).unwrap();
+        assert_eq!((moved.status.as_str(), moved.path.as_deref(), moved.former_path.as_deref()), ("moved", Some("Assets/Art/Rock.mat"), Some("Assets/Rock.mat")));
+        let regenerated = aliases.resolve(MID, &cache).unwrap();
+        assert_eq!((regenerated.status.as_str(), regenerated.new_guid.as_deref()), ("regenerated", Some("44444444444444444444444444444444")));
+        let deleted = aliases.resolve("77777777777777777777777777777777", &cache).unwrap();
+        assert_eq!((deleted.status.as_str(), deleted.message.as_str()), ("deleted", "deleted (was Assets/Old.anim)"));
+    }
+}
@@ -0,0 +1,166 @@
+//! Composite health score of a scene or prefab from the individual audits, and the
+//! missing-reference check it adds.
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use crate::common::{HealthCategory, HealthIssue, SceneHealthReport};
+use super::config::ComponentConfig;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::{field, owner_game_object};
+
+/// `{fileID: N}` (local) or `{fileID: N, guid: G, type: T}` (another asset)
+static REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{fileID:[ \t]*(-?\d+)(?:,[ \t]*guid:[ \t]*([0-9a-f]{32}))?").unwrap()
+});
+
+/// Findings kept per category; the counts cover all of them.
+pub const MAX_ISSUES_PER_CATEGORY: usize = 25;
+
+const ERROR_PENALTY: u32 = 20;
+const WARNING_PENALTY: u32 = 5;
+
+/// Built-in resources (default materials, meshes, the UI sprites) have no .meta anywhere.
+fn is_builtin_guid(guid: &str) -> bool {
+    guid.starts_with("0000000000000000")
+}
+
+pub fn issue(severity: &str, message: String, game_object: Option<(String, String)>) -> HealthIssue {
+    let (game_object_id, game_object_name) = game_object.map_or((None, None), |(id, name)| (Some(id), Some(name)));
+    HealthIssue { severity: severity.to_string(), message, game_object_id, game_object_name }
+}
+
+/// References to objects missing from the file, and (with `known_guids`) to scripts and
+/// assets whose GUID is not in the project or its packages. `stale(guid)` says where a missing
+/// GUID's asset went (e.g. "moved to X"), when known.
+pub fn missing_references(
+    content: &str,
+    known_guids: Option<&HashSet<String>>,
+    stale: &dyn Fn(&str) -> Option<String>,
+    config: &ComponentConfig,
+) -> Vec<HealthIssue> {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+    let mut issues = Vec::new();
+    for (class_id, file_id, body) in &blocks {
+        let type_name = body.lines().next().unwrap_or_default().trim_end_matches(':').trim();
+        let owner = if *class_id == config.gameobject_class_id {
+            (file_id.clone(), field(body, "m_Name").unwrap_or_default().to_string())
+        } else {
+            owner_game_object(&index, body)
+        };
+        let owner = Some(owner).filter(|(id, _)| id != "0");
+        for line in body.lines().skip(1) {
+            let key = line.trim_start().trim_start_matches("- ").split(':').next().unwrap_or_default().trim();
+            for caps in REF_RE.captures_iter(line) {
+                let target = &caps[1];
+                match caps.get(2).map(|g| g.as_str()) {
+                    None if target != "0" && index.get(target).is_none() => issues.push(issue(
+                        "error",
+                        format!("{} ({}) {} references object {} that is not in the file", type_name, file_id, key, target),
+                        owner.clone(),
+                    )),
+                    Some(guid) if !is_builtin_guid(guid) && known_guids.is_some_and(|known| !known.contains(guid)) => {
+                        let mut message = if key == "m_Script" {
+                            format!("Missing script on {} ({}): no script has GUID {}", type_name, file_id, guid)
+                        } else {
+                            format!("{} ({}) {} references missing asset {}", type_name, file_id, key, guid)
+                        };
+                        if let Some(whereabouts) = stale(guid) {
+                            message.push_str(&format!(" ({})", whereabouts));
+                        }
+                        issues.push(issue("error", message, owner.clone()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// Score a category: 100 minus 20 per error and 5 per warning, at least 0.
+pub fn category(name: &str, weight: u32, mut issues: Vec<HealthIssue>) -> HealthCategory {
+    let error_count = issues.iter().filter(|i| i.severity == "error").count() as u32;
+    let warning_count = issues.len() as u32 - error_count;
+    let penalty = error_count.saturating_mul(ERROR_PENALTY).saturating_add(warning_count.saturating_mul(WARNING_PENALTY));
+    // Errors first, so the kept findings are the worst ones
+    issues.sort_by_key(|i| i.severity != "error");
+    issues.truncate(MAX_ISSUES_PER_CATEGORY);
+    HealthCategory {
+        name: name.to_string(),
+        score: 100u32.saturating_sub(penalty),
+        weight,
+        error_count,
+        warning_count,
+        issues,
+    }
+}
+
+fn grade(score: u32) -> &'static str {
+    match score {
+        90.. => "A",
+        80..=89 => "B",
+        70..=79 => "C",
+        60..=69 => "D",
+        _ => "F",
+    }
+}
+
+/// Combine category scores into the report's weighted overall score.
+pub fn report(file: String, categories: Vec<HealthCategory>, skipped: Vec<String>) -> SceneHealthReport {
+    let total_weight: u32 = categories.iter().map(|c| c.weight).sum();
+    let weighted: u32 = categories.iter().map(|c| c.score * c.weight).sum();
+    let score = if total_weight == 0 { 100 } else { (weighted as f64 / total_weight as f64).round() as u32 };
+    SceneHealthReport {
+        file,
+        score,
+        grade: grade(score).to_string(),
+        issue_count: categories.iter().map(|c| c.error_count + c.warning_count).sum(),
+        categories,
+        skipped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  - component: {fileID: 3}\n  m_Name: Player\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Father: {fileID: 0}\n\
+--- !u!114 &3\nMonoBehaviour:\n  m_GameObject: {fileID: 1}\n  m_Script: {fileID: 11500000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}\n  target: {fileID: 99}\n  icon: {fileID: 10905, guid: 0000000000000000f000000000000000, type: 0}\n  skin: {fileID: 2100000, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 2}\n";
+
+    #[test]
+    fn test_missing_references() {
+        let config = ComponentConfig::default();
+        let local_only = missing_references(SCENE, None, &|_| None, &config);
+        assert_eq!(local_only.len(), 1);
+        assert!(local_only[0].message.contains("target references object 99"), "{}", local_only[0].message);
+        assert_eq!(local_only[0].game_object_name.as_deref(), Some("Player"));
+
+        let known: HashSet<String> = HashSet::from(["bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string()]);
+        let issues = missing_references(SCENE, Some(&known), &|_| None, &config);
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages.len(), 2, "{:?}", messages);
+        assert!(messages[0].starts_with("Missing script on MonoBehaviour (3)"));
+
+        let moved = |guid: &str| (guid == "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa").then(|| "moved to Assets/Player.cs".to_string());
+        let issues = missing_references(SCENE, Some(&known), &moved, &config);
+        assert!(issues[0].message.ends_with("no script has GUID aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa (moved to Assets/Player.cs)"), "{}", issues[0].message);
+    }
+
+    #[test]
+    fn test_scores_and_grade() {
+        let warnings = (0..3).map(|i| issue("warning", format!("w{}", i), None)).collect();
+        let canvas = category("canvas", 1, warnings);
+        assert_eq!((canvas.score, canvas.warning_count), (85, 3));
+        let errors = (0..30).map(|i| issue("error", format!("e{}", i), None)).collect();
+        let missing = category("missing_references", 3, errors);
+        assert_eq!((missing.score, missing.error_count, missing.issues.len()), (0, 30, MAX_ISSUES_PER_CATEGORY));
+
+        let health = report("Main.unity".to_string(), vec![canvas, missing, category("physics", 2, Vec::new())], Vec::new());
+        assert_eq!((health.score, health.grade.as_str(), health.issue_count), (48, "F", 33));
+        assert_eq!(report("Empty.unity".to_string(), Vec::new(), Vec::new()).grade, "A");
+    }
+}
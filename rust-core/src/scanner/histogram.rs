@@ -0,0 +1,203 @@
+//! Distribution of one component property across many serialized components.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::common::HistogramBin;
+use super::aliases;
+use super::query::Value;
+use super::schema::{flatten_block, FieldValue};
+
+/// Number of numeric ranges when no bucket count is given.
+pub const DEFAULT_BUCKETS: u32 = 10;
+
+/// Path segment compared case-insensitively and without the `m_` prefix.
+fn normalize(segment: &str) -> String {
+    segment.strip_prefix("m_").unwrap_or(segment).to_ascii_lowercase()
+}
+
+/// Values of a dotted property path in a component block (several for paths through arrays,
+/// e.g. `waypoints[].x`). A final segment past a vector or color selects one component.
+pub fn property_values(block_text: &str, path: &str) -> Vec<Value> {
+    let wanted: Vec<String> = path.split('.').map(normalize).collect();
+    let mut values = Vec::new();
+    for (field_path, value) in flatten_block(block_text) {
+        let FieldValue::Scalar(text) = value else { continue };
+        let segments: Vec<String> = field_path.split('.').map(normalize).collect();
+        if segments == wanted {
+            match Value::from_scalar(&text) {
+                number @ Value::Number(_) => values.push(number),
+                _ => values.push(Value::Text(text.trim().to_string())),
+            }
+        } else if segments.len() + 1 == wanted.len() && wanted.starts_with(&segments) {
+            let member = Value::from_scalar(&text).member(&wanted[segments.len()]);
+            if member != Value::Null {
+                values.push(member);
+            }
+        }
+    }
+    values
+}
+
+fn format_number(n: f64) -> String {
+    let rounded = (n * 1e6).round() / 1e6;
+    format!("{}", rounded)
+}
+
+/// Collects property values of matching components.
+#[derive(Default)]
+pub struct HistogramBuilder {
+    pub samples: u32,
+    pub missing: u32,
+    /// Field renames the lookup fell back to (see `aliases`)
+    pub aliases: BTreeSet<String>,
+    numbers: Vec<f64>,
+    texts: BTreeMap<String, u32>,
+}
+
+/// Summary of the collected values.
+pub struct Histogram {
+    pub numeric: bool,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub bins: Vec<HistogramBin>,
+}
+
+impl HistogramBuilder {
+    pub fn add_sample(&mut self, block_text: &str, path: &str) {
+        self.samples += 1;
+        let mut values = property_values(block_text, path);
+        if values.is_empty() {
+            // The component may serialize the field under another Unity version's name
+            let component = block_text.lines().find(|l| !l.starts_with("--- ")).unwrap_or("").trim_end_matches(':');
+            let (first, rest) = path.split_once('.').map_or((path, None), |(f, r)| (f, Some(r)));
+            for (name, alias) in aliases::alternatives(component, first) {
+                let renamed = rest.map_or_else(|| name.to_string(), |rest| format!("{}.{}", name, rest));
+                values = property_values(block_text, &renamed);
+                if !values.is_empty() {
+                    self.aliases.insert(alias.describe(component));
+                    break;
+                }
+            }
+        }
+        if values.is_empty() {
+            self.missing += 1;
+        }
+        for value in values {
+            match value {
+                Value::Number(n) => self.numbers.push(n),
+                Value::Text(text) => *self.texts.entry(text).or_insert(0) += 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Numeric values get one bin per distinct value when there are at most `buckets` of them,
+    /// else `buckets` equal-width ranges. Any text value makes every value a distinct-value bin,
+    /// most frequent first.
+    pub fn finish(self, buckets: u32) -> Histogram {
+        let numeric = self.texts.is_empty() && !self.numbers.is_empty();
+        let min = self.numbers.iter().copied().reduce(f64::min);
+        let max = self.numbers.iter().copied().reduce(f64::max);
+        let mean = (!self.numbers.is_empty()).then(|| self.numbers.iter().sum::<f64>() / self.numbers.len() as f64);
+
+        let mut distinct: BTreeMap<String, (f64, u32)> = BTreeMap::new();
+        for n in &self.numbers {
+            distinct.entry(format_number(*n)).or_insert((*n, 0)).1 += 1;
+        }
+        let bins = match (numeric, min, max) {
+            (true, Some(lo), Some(hi)) if distinct.len() > buckets.max(1) as usize => {
+                let buckets = buckets.max(1) as usize;
+                let width = (hi - lo) / buckets as f64;
+                let mut counts = vec![0u32; buckets];
+                for n in &self.numbers {
+                    counts[(((n - lo) / width) as usize).min(buckets - 1)] += 1;
+                }
+                counts.into_iter().enumerate()
+                    .map(|(i, count)| {
+                        let (start, end) = (lo + width * i as f64, if i + 1 == buckets { hi } else { lo + width * (i + 1) as f64 });
+                        HistogramBin { label: format!("{}..{}", format_number(start), format_number(end)), min: Some(start), max: Some(end), count }
+                    })
+                    .collect()
+            }
+            (true, _, _) => {
+                let mut bins: Vec<HistogramBin> = distinct.into_iter()
+                    .map(|(label, (n, count))| HistogramBin { label, min: Some(n), max: Some(n), count })
+                    .collect();
+                bins.sort_by(|a, b| a.min.partial_cmp(&b.min).unwrap_or(std::cmp::Ordering::Equal));
+                bins
+            }
+            (false, _, _) => {
+                let mut bins: Vec<HistogramBin> = distinct.into_iter()
+                    .map(|(label, (n, count))| HistogramBin { label, min: Some(n), max: Some(n), count })
+                    .chain(self.texts.into_iter().map(|(label, count)| HistogramBin { label, min: None, max: None, count }))
+                    .collect();
+                bins.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+                bins
+            }
+        };
+        Histogram { numeric, min, max, mean, bins }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn light(intensity: &str) -> String {
+        format!("--- !u!108 &1\nLight:\n  m_GameObject: {{fileID: 2}}\n  m_Type: 2\n  m_Color: {{r: 1, g: 0.5, b: 0, a: 1}}\n  m_Intensity: {}\n", intensity)
+    }
+
+    #[test]
+    fn test_property_values() {
+        let block = light("1.5");
+        assert_eq!(property_values(&block, "intensity"), vec![Value::Number(1.5)]);
+        assert_eq!(property_values(&block, "m_Color.g"), vec![Value::Number(0.5)]);
+        assert_eq!(property_values(&block, "m_Color"), vec![Value::Text("{r: 1, g: 0.5, b: 0, a: 1}".to_string())]);
+        assert!(property_values(&block, "m_Range").is_empty());
+        let waypoints = "MonoBehaviour:\n  waypoints:\n  - {x: 0, y: 1, z: 2}\n  - {x: 3, y: 4, z: 5}\n";
+        assert_eq!(property_values(waypoints, "waypoints[].x"), vec![Value::Number(0.0), Value::Number(3.0)]);
+    }
+
+    #[test]
+    fn test_histogram_bins() {
+        let mut builder = HistogramBuilder::default();
+        for intensity in ["1", "1", "2", "0.5"] {
+            builder.add_sample(&light(intensity), "m_Intensity");
+        }
+        builder.add_sample("Light:\n  m_Type: 2\n", "m_Intensity");
+        assert_eq!((builder.samples, builder.missing), (5, 1));
+        let histogram = builder.finish(DEFAULT_BUCKETS);
+        assert!(histogram.numeric);
+        assert_eq!((histogram.min, histogram.max, histogram.mean), (Some(0.5), Some(2.0), Some(1.125)));
+        let bins: Vec<(&str, u32)> = histogram.bins.iter().map(|b| (b.label.as_str(), b.count)).collect();
+        assert_eq!(bins, vec![("0.5", 1), ("1", 2), ("2", 1)]);
+
+        let mut ranged = HistogramBuilder::default();
+        for n in 0..10 {
+            ranged.add_sample(&light(&n.to_string()), "Intensity");
+        }
+        let bins: Vec<(String, u32)> = ranged.finish(3).bins.into_iter().map(|b| (b.label, b.count)).collect();
+        assert_eq!(bins, vec![("0..3".to_string(), 3), ("3..6".to_string(), 3), ("6..9".to_string(), 4)]);
+
+        let mut text = HistogramBuilder::default();
+        for shadows in ["Hard", "Soft", "Soft", "2"] {
+            text.add_sample(&format!("Light:\n  m_Shadows: {}\n", shadows), "Shadows");
+        }
+        let histogram = text.finish(DEFAULT_BUCKETS);
+        assert!(!histogram.numeric);
+        let bins: Vec<(&str, u32)> = histogram.bins.iter().map(|b| (b.label.as_str(), b.count)).collect();
+        assert_eq!(bins, vec![("Soft", 2), ("2", 1), ("Hard", 1)]);
+    }
+
+    #[test]
+    fn test_histogram_legacy_field_names() {
+        let mut builder = HistogramBuilder::default();
+        builder.add_sample("--- !u!54 &1\nRigidbody:\n  m_LinearDamping: 0.5\n", "m_LinearDamping");
+        builder.add_sample("--- !u!54 &2\nRigidbody:\n  m_Drag: 2\n", "m_LinearDamping");
+        builder.add_sample("--- !u!54 &3\nRigidbody:\n  m_Mass: 1\n", "linearDamping");
+        assert_eq!((builder.samples, builder.missing), (3, 1));
+        assert_eq!(builder.aliases.iter().collect::<Vec<_>>(), vec!["Rigidbody.m_LinearDamping (named m_Drag before Unity 6000.0)"]);
+        assert_eq!(builder.finish(DEFAULT_BUCKETS).max, Some(2.0));
+    }
+}
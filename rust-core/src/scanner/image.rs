@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::common::{self, SpriteInfo, TextureInfo};
+use super::rendering::ref_file_id;
+
+/// Enough of a file to reach the JPEG frame header past typical EXIF/ICC segments.
+const HEADER_READ_LIMIT: u64 = 256 * 1024;
+
+/// fileID of the Texture2D sub-asset of a texture importer.
+const TEXTURE2D_FILE_ID: &str = "2800000";
+/// fileID of the sprite of a single-sprite texture (and the first legacy multi-sprite entry).
+const SINGLE_SPRITE_FILE_ID: &str = "21300000";
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "tga", "psd"];
+
+/// Whether a path looks like a texture source Unity imports as Texture2D/Sprite.
+pub fn is_image_path(path: &str) -> bool {
+    Path::new(path).extension()
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_string_lossy().to_lowercase().as_str()))
+}
+
+fn u16_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u32_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Walk JPEG segments up to the first start-of-frame marker.
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut pos = 2;
+    while pos + 4 <= bytes.len() {
+        if bytes[pos] != 0xFF {
+            return None;
+        }
+        let marker = bytes[pos + 1];
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        let length = u16_be(bytes, pos + 2)? as usize;
+        // SOF0..SOF15, excluding DHT (C4), JPG (C8) and DAC (CC)
+        if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+            return Some((u16_be(bytes, pos + 7)?, u16_be(bytes, pos + 5)?));
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+/// Format and pixel dimensions from the leading bytes of an image file.
+/// TGA has no signature and is only recognized when `extension` says so.
+pub fn image_dimensions(bytes: &[u8], extension: &str) -> Option<(&'static str, u32, u32)> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some(("png", u32_be(bytes, 16)?, u32_be(bytes, 20)?));
+    }
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        let (width, height) = jpeg_dimensions(bytes)?;
+        return Some(("jpeg", width, height));
+    }
+    if bytes.starts_with(b"GIF8") {
+        return Some(("gif", u16_le(bytes, 6)?, u16_le(bytes, 8)?));
+    }
+    if bytes.starts_with(b"BM") {
+        return Some(("bmp", u32_le(bytes, 18)?, (u32_le(bytes, 22)? as i32).unsigned_abs()));
+    }
+    if bytes.starts_with(b"8BPS") {
+        return Some(("psd", u32_be(bytes, 18)?, u32_be(bytes, 14)?));
+    }
+    if extension.eq_ignore_ascii_case("tga") {
+        return Some(("tga", u16_le(bytes, 12)?, u16_le(bytes, 14)?));
+    }
+    None
+}
+
+/// Sprites declared by a TextureImporter .meta. Single-sprite textures report one sprite named
+/// after the texture covering the whole image (`texture_size`), multiple mode lists the sheet.
+pub fn parse_sprites(meta: &str, texture_name: &str, texture_size: Option<(u32, u32)>) -> (u32, Vec<SpriteInfo>) {
+    let sprite_mode: u32 = meta.lines()
+        .find_map(|l| l.strip_prefix("  spriteMode:"))
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0);
+    if sprite_mode == 1 {
+        let (width, height) = texture_size.unwrap_or((0, 0));
+        return (sprite_mode, vec![SpriteInfo {
+            name: texture_name.to_string(),
+            file_id: SINGLE_SPRITE_FILE_ID.to_string(),
+            x: 0.0,
+            y: 0.0,
+            width: width as f64,
+            height: height as f64,
+        }]);
+    }
+
+    // Legacy metas map recycled fileIDs to sprite names instead of storing internalIDs
+    let mut legacy_ids: HashMap<String, String> = HashMap::new();
+    let mut in_recycle = false;
+    let mut sprites: Vec<SpriteInfo> = Vec::new();
+    let mut in_sprites = false;
+    let mut in_rect = false;
+    for line in meta.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        if indent <= 2 {
+            in_recycle = trimmed == "fileIDToRecycleName:";
+        } else if in_recycle {
+            if let Some((id, name)) = trimmed.split_once(':') {
+                legacy_ids.insert(name.trim().to_string(), id.trim().to_string());
+            }
+            continue;
+        }
+        if trimmed == "sprites:" && indent == 4 {
+            in_sprites = true;
+            continue;
+        }
+        if !in_sprites {
+            continue;
+        }
+        if indent < 4 || (indent == 4 && !trimmed.starts_with("- ")) {
+            in_sprites = false;
+            continue;
+        }
+        if indent == 4 {
+            sprites.push(SpriteInfo { name: String::new(), file_id: String::new(), x: 0.0, y: 0.0, width: 0.0, height: 0.0 });
+            in_rect = false;
+        }
+        let Some(sprite) = sprites.last_mut() else { continue };
+        let item = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+        let Some((key, value)) = item.split_once(':') else { continue };
+        let value = value.trim();
+        match (indent, key) {
+            (4 | 6, "rect") => in_rect = true,
+            (4 | 6, "name") => { sprite.name = value.to_string(); in_rect = false; }
+            (4 | 6, "internalID") => { sprite.file_id = value.to_string(); in_rect = false; }
+            (4 | 6, _) => in_rect = false,
+            (8, "x") if in_rect => sprite.x = value.parse().unwrap_or(0.0),
+            (8, "y") if in_rect => sprite.y = value.parse().unwrap_or(0.0),
+            (8, "width") if in_rect => sprite.width = value.parse().unwrap_or(0.0),
+            (8, "height") if in_rect => sprite.height = value.parse().unwrap_or(0.0),
+            _ => {}
+        }
+    }
+    for sprite in sprites.iter_mut().filter(|s| s.file_id.is_empty() || s.file_id == "0") {
+        if let Some(id) = legacy_ids.get(&sprite.name) {
+            sprite.file_id = id.clone();
+        }
+    }
+    (sprite_mode, sprites)
+}
+
+/// Pixel dimensions and sprites of a texture asset (header sniffing plus its .meta).
+pub fn texture_info(full_path: &Path, display_path: &str) -> Option<TextureInfo> {
+    let mut bytes = Vec::new();
+    crate::safety::check_read(full_path).ok()?;
+    File::open(full_path).ok()?.take(HEADER_READ_LIMIT).read_to_end(&mut bytes).ok()?;
+    let extension = full_path.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+    let dimensions = image_dimensions(&bytes, &extension);
+    let meta = common::read_unity_file(format!("{}.meta", full_path.to_string_lossy())).unwrap_or_default();
+    let name = full_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let (sprite_mode, sprites) = parse_sprites(&meta, &name, dimensions.map(|(_, w, h)| (w, h)));
+    Some(TextureInfo {
+        path: display_path.to_string(),
+        format: dimensions.map(|(format, _, _)| format.to_string()),
+        width: dimensions.map(|(_, w, _)| w),
+        height: dimensions.map(|(_, _, h)| h),
+        sprite_mode,
+        sprites,
+    })
+}
+
+/// Inline description of a reference into a texture ("256x256", or "sprite Hero_0 32x32 of 256x256").
+pub fn describe_reference(file_id: &str, info: &TextureInfo) -> Option<String> {
+    let size = format!("{}x{}", info.width?, info.height?);
+    if file_id == TEXTURE2D_FILE_ID {
+        return Some(size);
+    }
+    match info.sprites.iter().find(|s| s.file_id == file_id) {
+        Some(sprite) => Some(format!("sprite {} {}x{} of {}", sprite.name, sprite.width, sprite.height, size)),
+        None => Some(size),
+    }
+}
+
+/// Append pixel dimensions (and sprite name/size) to resolved `{fileID, guid} -> path` property
+/// values that reference images. `lookup` returns (and may cache) the texture info of a path.
+pub fn annotate_references(value: &mut serde_json::Value, lookup: &mut dyn FnMut(&str) -> Option<TextureInfo>) {
+    match value {
+        serde_json::Value::String(s) => {
+            let Some((reference, path)) = s.split_once(" -> ") else { return };
+            if !is_image_path(path) {
+                return;
+            }
+            let Some(file_id) = ref_file_id(reference) else { return };
+            if let Some(description) = lookup(path).and_then(|info| describe_reference(file_id, &info)) {
+                s.push_str(&format!(" ({})", description));
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| annotate_references(v, lookup)),
+        serde_json::Value::Object(map) => map.values_mut().for_each(|v| annotate_references(v, lookup)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn test_image_dimensions() {
+        assert_eq!(image_dimensions(&png_header(256, 128), "png"), Some(("png", 256, 128)));
+        // SOI, APP0 (length 16), SOF0 with height 480 and width 640
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+        jpeg.extend_from_slice(&[0; 14]);
+        jpeg.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01, 0xE0, 0x02, 0x80, 0x03]);
+        assert_eq!(image_dimensions(&jpeg, "jpg"), Some(("jpeg", 640, 480)));
+        let mut tga = vec![0u8; 18];
+        tga[12] = 64;
+        tga[14] = 32;
+        assert_eq!(image_dimensions(&tga, "TGA"), Some(("tga", 64, 32)));
+        assert_eq!(image_dimensions(&tga, "dat"), None);
+    }
+
+    #[test]
+    fn test_parse_sprites_multiple_and_legacy() {
+        let meta = "fileFormatVersion: 2\nguid: 0123\nTextureImporter:\n  fileIDToRecycleName:\n    21300000: Hero_0\n    21300002: Hero_1\n  spriteMode: 2\n  spriteSheet:\n    serializedVersion: 2\n    sprites:\n    - serializedVersion: 2\n      name: Hero_0\n      rect:\n        serializedVersion: 2\n        x: 0\n        y: 32\n        width: 32\n        height: 32\n      alignment: 0\n    - serializedVersion: 2\n      name: Hero_1\n      rect:\n        serializedVersion: 2\n        x: 32\n        y: 32\n        width: 16\n        height: 16\n      internalID: 987654321\n    outline: []\n  spritePackingTag: \n";
+        let (mode, sprites) = parse_sprites(meta, "Hero", Some((64, 64)));
+        assert_eq!(mode, 2);
+        assert_eq!(sprites.len(), 2);
+        assert_eq!((sprites[0].name.as_str(), sprites[0].file_id.as_str(), sprites[0].y), ("Hero_0", "21300000", 32.0));
+        assert_eq!((sprites[1].file_id.as_str(), sprites[1].width), ("987654321", 16.0));
+
+        let (_, single) = parse_sprites("TextureImporter:\n  spriteMode: 1\n", "Icon", Some((128, 128)));
+        assert_eq!(single[0].name, "Icon");
+        assert_eq!(single[0].width, 128.0);
+    }
+
+    #[test]
+    fn test_annotate_references() {
+        let info = TextureInfo {
+            path: "Assets/Hero.png".into(),
+            format: Some("png".into()),
+            width: Some(64),
+            height: Some(64),
+            sprite_mode: 2,
+            sprites: vec![SpriteInfo { name: "Hero_1".into(), file_id: "987654321".into(), x: 32.0, y: 32.0, width: 16.0, height: 16.0 }],
+        };
+        let mut props = serde_json::json!({
+            "m_Sprite": "{fileID: 987654321, guid: 0123456789abcdef0123456789abcdef, type: 3} -> Assets/Hero.png",
+            "m_Textures": ["{fileID: 2800000, guid: 0123456789abcdef0123456789abcdef, type: 3} -> Assets/Hero.png"],
+            "m_Material": "{fileID: 2100000, guid: 0123456789abcdef0123456789abcdef, type: 2} -> Assets/Hero.mat",
+        });
+        annotate_references(&mut props, &mut |_| Some(info.clone()));
+        assert_eq!(props["m_Sprite"], "{fileID: 987654321, guid: 0123456789abcdef0123456789abcdef, type: 3} -> Assets/Hero.png (sprite Hero_1 16x16 of 64x64)");
+        assert!(props["m_Textures"][0].as_str().unwrap().ends_with("(64x64)"));
+        assert!(props["m_Material"].as_str().unwrap().ends_with("Hero.mat"));
+    }
+}
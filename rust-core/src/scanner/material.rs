@@ -0,0 +1,247 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::common::MaterialPropertyIssue;
+
+static SHADER_NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*Shader\s+"([^"]*)""#).unwrap()
+});
+/// `[Attr] _Name ("Display", Type) = default`
+static SHADER_PROPERTY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^(?:\[[^\]]*\]\s*)*([A-Za-z_][A-Za-z0-9_]*)\s*\(\s*"[^"]*"\s*,\s*([A-Za-z0-9]+)"#).unwrap()
+});
+static SAVED_ENTRY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^-\s+([A-Za-z_][A-Za-z0-9_]*):").unwrap()
+});
+static SHADER_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_Shader:[ \t]*\{fileID:[ \t]*(-?\d+)(?:,[ \t]*guid:[ \t]*([0-9a-f]{32}))?").unwrap()
+});
+
+/// Serialized sections of m_SavedProperties, in the order Unity writes them.
+pub const SAVED_SECTIONS: &[&str] = &["m_TexEnvs", "m_Ints", "m_Floats", "m_Colors"];
+
+/// A property declared in a shader's Properties block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderProperty {
+    pub name: String,
+    /// Declared type as written (Float, Range, Color, 2D, ...)
+    pub kind: String,
+}
+
+impl ShaderProperty {
+    /// The m_SavedProperties section a material stores this property in.
+    pub fn section(&self) -> &'static str {
+        match self.kind.to_ascii_lowercase().as_str() {
+            "color" | "vector" => "m_Colors",
+            "2d" | "3d" | "cube" | "2darray" | "cubearray" | "any" => "m_TexEnvs",
+            "integer" => "m_Ints",
+            _ => "m_Floats",
+        }
+    }
+}
+
+/// Shader name and the properties of its Properties block. Comments are ignored;
+/// nothing is returned for files without a Properties block (e.g. Shader Graph output).
+pub fn parse_shader_properties(source: &str) -> (Option<String>, Vec<ShaderProperty>) {
+    let mut name = None;
+    let mut properties = Vec::new();
+    let mut in_properties = false;
+    let mut depth = 0i32;
+    let mut in_block_comment = false;
+    for raw in source.lines() {
+        let mut line = raw.to_string();
+        if in_block_comment {
+            match line.find("*/") {
+                Some(end) => { line = line[end + 2..].to_string(); in_block_comment = false; }
+                None => continue,
+            }
+        }
+        if let Some(start) = line.find("/*") {
+            match line[start..].find("*/") {
+                Some(end) => line.replace_range(start..start + end + 2, ""),
+                None => { line.truncate(start); in_block_comment = true; }
+            }
+        }
+        if let Some(start) = line.find("//") {
+            line.truncate(start);
+        }
+        let trimmed = line.trim();
+
+        if name.is_none() {
+            if let Some(cap) = SHADER_NAME_RE.captures(trimmed) {
+                name = Some(cap[1].to_string());
+            }
+        }
+        if !in_properties {
+            if let Some(rest) = trimmed.strip_prefix("Properties") {
+                if rest.is_empty() || rest.starts_with('{') || rest.starts_with(char::is_whitespace) {
+                    in_properties = true;
+                    depth = 0;
+                }
+            }
+            if !in_properties {
+                continue;
+            }
+        }
+        if let Some(cap) = SHADER_PROPERTY_RE.captures(trimmed.trim_start_matches('{').trim_start()) {
+            properties.push(ShaderProperty { name: cap[1].to_string(), kind: cap[2].to_string() });
+        }
+        // Braces inside the block belong to texture defaults ("white" {})
+        depth += trimmed.matches('{').count() as i32 - trimmed.matches('}').count() as i32;
+        if depth <= 0 && trimmed.contains('}') {
+            break;
+        }
+    }
+    (name, properties)
+}
+
+/// Property names per m_SavedProperties section of a material, as (section, name) in file order.
+pub fn saved_properties(content: &str) -> Vec<(&'static str, String)> {
+    let mut entries = Vec::new();
+    let mut current: Option<(&'static str, usize)> = None;
+    for line in content.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        if let Some(section) = SAVED_SECTIONS.iter().find(|s| trimmed.strip_prefix(**s).is_some_and(|r| r.starts_with(':'))) {
+            current = Some((section, indent));
+            continue;
+        }
+        let Some((section, section_indent)) = current else { continue };
+        if trimmed.is_empty() {
+            continue;
+        }
+        // Entries sit at the section's indent ("- _Name: ...") or deeper (their nested fields)
+        if indent < section_indent || (indent == section_indent && !trimmed.starts_with("- ")) {
+            current = None;
+            continue;
+        }
+        if indent == section_indent {
+            if let Some(cap) = SAVED_ENTRY_RE.captures(trimmed) {
+                entries.push((section, cap[1].to_string()));
+            }
+        }
+    }
+    entries
+}
+
+/// The material's m_Shader reference as (fileID, GUID).
+pub fn shader_reference(content: &str) -> Option<(String, Option<String>)> {
+    SHADER_REF_RE.captures(content).map(|c| (c[1].to_string(), c.get(2).map(|g| g.as_str().to_string())))
+}
+
+/// Compare a material's serialized properties against its shader's declarations:
+/// `stale` entries the shader no longer declares, `type_mismatch` entries stored in another
+/// section than the declared type implies, and `missing` declared properties without a
+/// serialized value (the shader default applies until the material is re-saved).
+pub fn check_properties(saved: &[(&'static str, String)], declared: &[ShaderProperty]) -> Vec<MaterialPropertyIssue> {
+    let mut issues = Vec::new();
+    for (section, name) in saved {
+        match declared.iter().find(|p| &p.name == name) {
+            None => issues.push(MaterialPropertyIssue {
+                kind: "stale".to_string(),
+                property: name.clone(),
+                section: section.to_string(),
+                message: format!("{} entry \"{}\" is not declared by the shader", section, name),
+            }),
+            // Int properties were stored as floats before m_Ints existed
+            Some(p) if p.section() != *section && !(p.section() == "m_Ints" && *section == "m_Floats") => issues.push(MaterialPropertyIssue {
+                kind: "type_mismatch".to_string(),
+                property: name.clone(),
+                section: section.to_string(),
+                message: format!("\"{}\" is declared as {} (stored in {}) but serialized in {}", name, p.kind, p.section(), section),
+            }),
+            Some(_) => {}
+        }
+    }
+    for property in declared {
+        if !saved.iter().any(|(_, name)| name == &property.name) {
+            issues.push(MaterialPropertyIssue {
+                kind: "missing".to_string(),
+                property: property.name.clone(),
+                section: property.section().to_string(),
+                message: format!("{} property \"{}\" has no serialized value; the shader default is used", property.kind, property.name),
+            });
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHADER: &str = r#"Shader "Custom/Water"
+{
+    Properties
+    {
+        _Color ("Tint", Color) = (1,1,1,1)
+        [NoScaleOffset] _MainTex ("Albedo", 2D) = "white" {}
+        [HDR] _Glow ("Glow", Vector) = (0,0,0,0)
+        _Speed ("Speed", Range(0, 10)) = 1
+        // _Old ("Old", Float) = 0
+        _Steps ("Steps", Integer) = 4
+    }
+    SubShader
+    {
+        Pass { float _Speed; }
+    }
+}
+"#;
+
+    const MATERIAL: &str = "%YAML 1.1
+--- !u!21 &2100000
+Material:
+  m_Name: Water
+  m_Shader: {fileID: 4800000, guid: 0123456789abcdef0123456789abcdef, type: 3}
+  m_SavedProperties:
+    serializedVersion: 3
+    m_TexEnvs:
+    - _MainTex:
+        m_Texture: {fileID: 0}
+        m_Scale: {x: 1, y: 1}
+        m_Offset: {x: 0, y: 0}
+    - _DetailTex:
+        m_Texture: {fileID: 0}
+        m_Scale: {x: 1, y: 1}
+        m_Offset: {x: 0, y: 0}
+    m_Ints: []
+    m_Floats:
+    - _Speed: 2
+    - _Steps: 4
+    - _Color: 1
+    m_Colors:
+    - _Glow: {r: 0, g: 0, b: 0, a: 0}
+  m_BuildTextureStacks: []
+";
+
+    #[test]
+    fn test_parse_shader_properties() {
+        let (name, properties) = parse_shader_properties(SHADER);
+        assert_eq!(name.as_deref(), Some("Custom/Water"));
+        let found: Vec<(&str, &str)> = properties.iter().map(|p| (p.name.as_str(), p.section())).collect();
+        assert_eq!(found, vec![
+            ("_Color", "m_Colors"),
+            ("_MainTex", "m_TexEnvs"),
+            ("_Glow", "m_Colors"),
+            ("_Speed", "m_Floats"),
+            ("_Steps", "m_Ints"),
+        ]);
+    }
+
+    #[test]
+    fn test_check_material_against_shader() {
+        let saved = saved_properties(MATERIAL);
+        assert_eq!(saved.len(), 6);
+        assert_eq!(shader_reference(MATERIAL).unwrap().1.as_deref(), Some("0123456789abcdef0123456789abcdef"));
+
+        let (_, declared) = parse_shader_properties(SHADER);
+        let issues = check_properties(&saved, &declared);
+        let found: Vec<(&str, &str)> = issues.iter().map(|i| (i.kind.as_str(), i.property.as_str())).collect();
+        assert_eq!(found, vec![
+            ("stale", "_DetailTex"),
+            ("type_mismatch", "_Color"),
+        ]);
+        let issues = check_properties(&saved[..5], &declared);
+        assert!(issues.iter().any(|i| i.kind == "missing" && i.property == "_Glow" && i.section == "m_Colors"));
+    }
+}
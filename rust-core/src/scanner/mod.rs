@@ -3,14 +3,58 @@ pub mod gameobject;
 pub mod component;
 pub mod config;
 pub mod prefab;
+pub mod material;
 pub mod mesh;
+pub mod stats;
+pub mod settings;
+pub mod rendering;
+pub mod canvas;
+pub mod catalog;
+pub mod aliases;
+pub mod animation;
+pub mod annotations;
+pub mod bounds;
+pub mod bulk;
+pub mod context;
+pub mod correlate;
+pub mod deprecated;
+pub mod duplicates;
+pub mod image;
+pub mod cost;
+pub mod coverage;
+pub mod cross_scene;
+pub mod defaults;
+pub mod events;
+pub mod guid_alias;
+pub mod health;
+pub mod histogram;
+pub mod physics;
+pub mod navigation;
+pub mod nesting;
+pub mod normalize;
+pub mod provenance;
+pub mod query;
+pub mod rename;
+pub mod replace;
+pub mod rules;
+pub mod scaffold;
+pub mod scene_index;
+pub mod schema;
+pub mod similar;
+pub mod subscription;
+pub mod transform;
+pub mod transplant;
+pub mod usage;
 
 use napi_derive::napi;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
-use crate::common::{self, Component, FindResult, GameObject, GameObjectDetail, InspectOptions, PrefabInstanceInfo, SceneInspection, ScanOptions, PaginationOptions, PaginatedInspection};
+use crate::telemetry;
+use crate::common::budget::Meter;
+use crate::common::projection::{self, FieldSelection};
+use crate::common::{self, Component, FindResult, GameObject, GameObjectDetail, InspectOptions, NestedPrefabNode, PrefabCatalog, PrefabNestingReport, PrefabInstanceInfo, SceneInspection, SceneStats, SceneStatsDiff, SubtreeMetrics, TransformSummary, ClassCount, ObjectCounts, CollisionMatrix, InputAxis, CameraInfo, LightInfo, CanvasAudit, PhysicsAudit, ExecutionOrderEntry, NavMeshReport, SceneBoundsOptions, SceneBoundsReport, MoveGameObjectResult, ScaffoldResult, BulkUpdateResult, SceneChangesResult, GameObjectChanges, GameObjectSelector, ComponentUsageReport, ComponentExpectation, ScriptCoverageReport, ScriptComponentUsage, CrossSceneReference, GlobalObjectIdParts, UnityEventInfo, UnityEventReport, DeprecationReport, AnimationBindingReport, AssetCostCategory, SceneCostReport, GuidManifest, GuidResolution, TextureInfo, MaterialShaderCheck, ComponentSchema, ComponentDefaultsDiff, PropertyHistogram, ObjectCorrelationResult, RuleValidationReport, HealthIssue, RenameImpactReport, RenameRisk, DuplicateOptions, DuplicateStructureReport, SimilarObjectsReport, ProjectRootOptions, ObjectAnnotation, ComponentOutput, SceneObjectOutput, PrefabInstanceOutput, PrefabModificationOutput, GameObjectInspection, AssetObjectOutput, ScanOptions, PaginationOptions, PaginatedInspection, ResourceBudget};
 use parser::{UnityYamlParser, BlockIndex};
 use config::ComponentConfig;
 
@@ -18,8 +62,15 @@ use config::ComponentConfig;
 #[napi]
 pub struct Scanner {
     guid_cache: HashMap<String, String>,
+    /// Fallbacks for GUIDs missing from the cache (see set_guid_aliases, load_guid_history)
+    guid_aliases: guid_alias::GuidAliases,
     project_root: Option<String>,
     config: ComponentConfig,
+    /// Tags/layers to validate GameObject metadata against (see set_tag_validation)
+    tag_manager: Option<settings::TagManagerInfo>,
+    validate_tags: bool,
+    /// Snapshots of subscribed files, keyed by path as given (see subscribe)
+    subscriptions: HashMap<String, subscription::Snapshot>,
 }
 
 #[napi]
@@ -28,8 +79,12 @@ impl Scanner {
     pub fn new() -> Self {
         Scanner {
             guid_cache: HashMap::new(),
+            guid_aliases: guid_alias::GuidAliases::default(),
             project_root: None,
             config: ComponentConfig::default(),
+            tag_manager: None,
+            validate_tags: false,
+            subscriptions: HashMap::new(),
         }
     }
 
@@ -45,21 +100,193 @@ impl Scanner {
         self.config.add_script_container(class_id);
     }
 
+    /// Add a field name that holds script references (in addition to m_Script).
+    #[napi]
+    pub fn add_script_field(&mut self, field: String) {
+        self.config.add_script_field(&field);
+    }
+
+    /// Hide a property (e.g. "m_EditorHideFlags") on every component.
+    #[napi]
+    pub fn add_hidden_property(&mut self, name: String) {
+        self.config.add_hidden_property(&name);
+    }
+
+    /// Only include the given properties for a component, identified by class ID ("4"),
+    /// type name ("Transform") or script name ("PlayerController").
+    #[napi]
+    pub fn set_property_whitelist(&mut self, component: String, properties: Vec<String>) {
+        self.config.set_property_whitelist(&component, &properties);
+    }
+
+    /// Always exclude the given properties for a component (class ID, type name or script name).
+    #[napi]
+    pub fn set_property_blacklist(&mut self, component: String, properties: Vec<String>) {
+        self.config.set_property_blacklist(&component, &properties);
+    }
+
+    /// Remove the whitelist/blacklist of a component.
+    #[napi]
+    pub fn clear_property_filter(&mut self, component: String) {
+        self.config.clear_property_filter(&component);
+    }
+
+    /// Restrict Transform/RectTransform/Camera/Light output to their key properties.
+    #[napi]
+    pub fn use_compact_property_presets(&mut self) {
+        self.config.apply_compact_presets();
+    }
+
     /// Get the current component configuration.
     pub fn get_config(&self) -> &ComponentConfig {
         &self.config
     }
 
-    /// Set project root for GUID resolution
+    /// Set project root for GUID resolution. `options.guid_cache` warm-starts the resolver from a
+    /// prebuilt `{ guid: path }` object or a persisted JSON file (falling back to a scan when the
+    /// file cannot be read); `options.include_packages` also resolves Packages/ and
+    /// Library/PackageCache/ assets (project assets win on conflicts).
     #[napi]
-    pub fn set_project_root(&mut self, path: String) {
+    pub fn set_project_root(&mut self, path: String, options: Option<ProjectRootOptions>) {
+        let _call = crate::telemetry::call("set_project_root");
+        let options = options.unwrap_or_default();
         self.project_root = Some(path.clone());
-        self.build_guid_cache(&path);
+
+        match options.guid_cache.and_then(|cache| json_object_or_file(Some(&path), cache)) {
+            Some(cache) => {
+                self.set_guid_cache(serde_json::Value::Object(cache));
+            }
+            None => self.build_guid_cache(&path),
+        }
+        if let Some(aliases) = options.guid_aliases {
+            self.set_guid_aliases(aliases);
+        }
+        if let Some(history) = options.guid_history {
+            self.load_guid_history(history);
+        }
+
+        if options.include_packages.unwrap_or(false) {
+            let package_caches = [
+                crate::walker::build_local_package_guid_cache(path.clone()),
+                crate::walker::build_package_guid_cache(path.clone()),
+            ];
+            for (guid, asset) in package_caches.iter().filter_map(|c| c.as_object()).flatten() {
+                if let Some(asset) = asset.as_str() {
+                    self.guid_cache.entry(guid.clone()).or_insert_with(|| asset.replace('\\', "/"));
+                }
+            }
+        }
+        self.load_project_config(&path);
+        self.load_tag_manager();
+    }
+
+    /// Validate GameObject tags and layers against the project's TagManager.asset during
+    /// inspect/inspect_all: details get a `warnings` array for undefined tags and unnamed or
+    /// out-of-range layers. Returns whether a TagManager was found (it is also looked up when
+    /// the project root is set or detected later).
+    #[napi]
+    pub fn set_tag_validation(&mut self, enabled: bool) -> bool {
+        self.validate_tags = enabled;
+        self.load_tag_manager();
+        self.tag_manager.is_some()
+    }
+
+    /// Project-relative asset path of a GUID, if cached.
+    #[napi]
+    pub fn get_guid_path(&self, guid: String) -> Option<String> {
+        self.guid_cache.get(&guid.to_lowercase()).cloned()
+    }
+
+    /// GUID of an asset path (project-relative, or absolute under the project root), if cached.
+    #[napi]
+    pub fn get_path_guid(&self, path: String) -> Option<String> {
+        let relative = self.relative_to_project(&path).replace('\\', "/");
+        self.guid_cache.iter()
+            .find(|(_, cached)| **cached == relative)
+            .map(|(guid, _)| guid.clone())
+    }
+
+    /// Number of GUIDs in the cache.
+    #[napi]
+    pub fn cache_size(&self) -> u32 {
+        self.guid_cache.len() as u32
+    }
+
+    /// Replace the GUID cache with a `{ guid: relative_path }` object (e.g. the output of
+    /// `buildGuidCache`, persisted by the caller), so this Scanner skips its own Assets/ scan.
+    /// Non-string entries are ignored. Returns the number of entries loaded.
+    #[napi]
+    pub fn set_guid_cache(&mut self, cache: serde_json::Value) -> u32 {
+        let Some(entries) = cache.as_object() else {
+            return 0;
+        };
+        self.guid_cache = entries.iter()
+            .filter_map(|(guid, path)| Some((guid.to_lowercase(), path.as_str()?.replace('\\', "/"))))
+            .collect();
+        self.guid_cache.len() as u32
+    }
+
+    /// Map GUIDs of moved or replaced assets to their successor: `{ old_guid: new_guid_or_path }`,
+    /// or the path of such a JSON file (relative paths resolve against the project root).
+    /// References to an old GUID are then reported as "moved to X" instead of missing.
+    /// Replaces earlier aliases; returns the number of entries loaded.
+    #[napi]
+    pub fn set_guid_aliases(&mut self, aliases: serde_json::Value) -> u32 {
+        let _call = crate::telemetry::call("set_guid_aliases");
+        let Some(map) = json_object_or_file(self.project_root.as_deref(), aliases) else { return 0 };
+        self.guid_aliases.aliases = guid_alias::string_entries(&map).collect();
+        self.guid_aliases.aliases.len() as u32
+    }
+
+    /// Load an earlier `{ guid: relative_path }` GUID cache (e.g. a persisted `buildGuidCache`
+    /// result from before a refactor), or the path of one, as the project's .meta history:
+    /// a stale GUID resolves to the asset now at its former path, or to the only asset with
+    /// its former file name. Snapshots accumulate, later ones winning; returns the entries loaded.
+    #[napi]
+    pub fn load_guid_history(&mut self, snapshot: serde_json::Value) -> u32 {
+        let _call = crate::telemetry::call("load_guid_history");
+        let Some(map) = json_object_or_file(self.project_root.as_deref(), snapshot) else { return 0 };
+        let entries: Vec<(String, String)> = guid_alias::string_entries(&map).collect();
+        let loaded = entries.len() as u32;
+        self.guid_aliases.history.extend(entries);
+        loaded
+    }
+
+    /// What became of an asset GUID: its current path, or for a GUID no longer in the project,
+    /// where the alias map or GUID history says it went. None when nothing is known about it.
+    #[napi]
+    pub fn resolve_guid(&self, guid: String) -> Option<GuidResolution> {
+        self.guid_aliases.resolve(&guid, &self.guid_cache)
+    }
+
+    /// Load a ComponentConfig JSON or `.toml` file (custom class IDs, script fields, property filters,
+    /// class-name mappings). Defaults to `<project_root>/.unity-agentic/config.json`.
+    /// The project config is also loaded automatically when the project root is set or detected.
+    #[napi]
+    pub fn load_config(&mut self, path: Option<String>) -> serde_json::Value {
+        let _call = crate::telemetry::call("load_config");
+        let path = match path.or_else(|| {
+            self.project_root.as_ref().map(|root| Path::new(root).join(config::PROJECT_CONFIG_PATH).to_string_lossy().to_string())
+        }) {
+            Some(p) => p,
+            None => {
+                return serde_json::json!({
+                    "error": "No config path given and no project root set",
+                    "is_error": true
+                })
+            }
+        };
+
+        match self.config.load_file(Path::new(&path)) {
+            Ok(()) => serde_json::json!({ "loaded": path }),
+            Err(e) => serde_json::json!({ "error": e, "is_error": true }),
+        }
     }
 
     /// Scan scene for basic GameObject information
     #[napi]
     pub fn scan_scene_minimal(&self, file: String) -> Vec<GameObject> {
+        let _call = telemetry::call("scan_scene_minimal");
         let path = Path::new(&file);
         if !path.exists() {
             return Vec::new();
@@ -70,12 +297,21 @@ impl Scanner {
             Err(_) => return Vec::new(),
         };
 
+        self.scan_scene_minimal_from_content(content)
+    }
+
+    /// Scan in-memory scene content for basic GameObject information
+    #[napi]
+    pub fn scan_scene_minimal_from_content(&self, content: String) -> Vec<GameObject> {
+        let _call = telemetry::call("scan_scene_minimal_from_content");
+        let content = common::normalize_line_endings(content);
         UnityYamlParser::extract_gameobjects(&content)
     }
 
     /// Scan scene with component information
-    #[napi]
+    #[napi(ts_return_type = "Array<SceneObjectOutput | PrefabInstanceOutput>")]
     pub fn scan_scene_with_components(&mut self, file: String, options: Option<ScanOptions>) -> Vec<serde_json::Value> {
+        let _call = telemetry::call("scan_scene_with_components");
         let path = Path::new(&file);
         if !path.exists() {
             return Vec::new();
@@ -86,7 +322,18 @@ impl Scanner {
             Err(_) => return Vec::new(),
         };
 
+        self.scan_scene_with_components_from_content(file, content, options)
+    }
+
+    /// Scan in-memory scene content with component information.
+    /// `file` is only used to locate the project for script GUID resolution.
+    #[napi(ts_return_type = "Array<SceneObjectOutput | PrefabInstanceOutput>")]
+    pub fn scan_scene_with_components_from_content(&mut self, file: String, content: String, options: Option<ScanOptions>) -> Vec<serde_json::Value> {
+        let _call = telemetry::call("scan_scene_with_components_from_content");
+        let content = common::normalize_line_endings(content);
         let verbose = options.as_ref().and_then(|o| o.verbose).unwrap_or(false);
+        let selection = options.as_ref().and_then(|o| o.fields.as_deref()).and_then(FieldSelection::parse);
+        let with_components = selection.as_ref().is_none_or(|s| s.selects("components") || s.selects("component_count"));
         let gameobjects = UnityYamlParser::extract_gameobjects(&content);
 
         self.ensure_guid_resolver(&file);
@@ -95,34 +342,34 @@ impl Scanner {
         let mut results: Vec<serde_json::Value> = gameobjects
             .into_iter()
             .map(|obj| {
-                let components = component::extract_components_indexed(&index, &obj.file_id, &self.guid_cache, &self.config);
-                let mut output = self.build_gameobject_output(&obj, &components, verbose, false);
-
+                // Properties are never part of this output
+                let components = if with_components {
+                    component::extract_component_identities_indexed(&index, &obj.file_id, &self.guid_cache, &self.config)
+                } else {
+                    Vec::new()
+                };
                 // Always include tag and layer for search filtering support
                 let (tag, layer, _, _) = gameobject::extract_metadata_indexed(&index, &obj.file_id, &self.config);
-                output["tag"] = serde_json::json!(tag);
-                output["layer"] = serde_json::json!(layer);
-
-                output
+                let output = self.build_gameobject_output(&obj, &components, verbose, false, tag, layer);
+                project_with(serde_json::to_value(output).unwrap_or_default(), selection.as_ref())
             })
             .collect();
 
         // Append PrefabInstances
-        let prefab_instances = prefab::extract_prefab_instances(&content, &self.guid_cache);
-        for pi in &prefab_instances {
-            let mut entry = serde_json::json!({
-                "type": "PrefabInstance",
-                "name": pi.name,
-                "source_guid": pi.source_guid,
-                "modifications_count": pi.modifications_count,
-            });
-            if let Some(ref src) = pi.source_prefab {
-                entry["source_prefab"] = serde_json::json!(src);
-            }
-            if verbose {
-                entry["file_id"] = serde_json::json!(pi.file_id);
+        let mut prefab_instances = prefab::extract_prefab_instances(&content, &self.guid_cache);
+        prefab::attach_added_components(&mut prefab_instances, &content, &self.guid_cache, &self.config);
+        for pi in &mut prefab_instances {
+            for added in pi.added_components.iter_mut().flatten() {
+                added.component.properties = None;
             }
-            results.push(entry);
+            let entry = PrefabInstanceOutput {
+                file_id: verbose.then(|| pi.file_id.clone()),
+                removed_components: None,
+                removed_game_objects: None,
+                parent_transform_id: pi.parent_transform_id.clone().filter(|_| verbose),
+                ..prefab_instance_output(pi)
+            };
+            results.push(project_with(serde_json::to_value(entry).unwrap_or_default(), selection.as_ref()));
         }
 
         results
@@ -132,6 +379,7 @@ impl Scanner {
     /// This is the "medium path" — faster than scan_scene_with_components for tag/layer filtering.
     #[napi]
     pub fn scan_scene_metadata(&self, file: String) -> Vec<serde_json::Value> {
+        let _call = telemetry::call("scan_scene_metadata");
         let path = Path::new(&file);
         if !path.exists() {
             return Vec::new();
@@ -142,6 +390,14 @@ impl Scanner {
             Err(_) => return Vec::new(),
         };
 
+        self.scan_scene_metadata_from_content(content)
+    }
+
+    /// Scan in-memory scene content for GO metadata (name, tag, layer)
+    #[napi]
+    pub fn scan_scene_metadata_from_content(&self, content: String) -> Vec<serde_json::Value> {
+        let _call = crate::telemetry::call("scan_scene_metadata_from_content");
+        let content = common::normalize_line_endings(content);
         let gameobjects = UnityYamlParser::extract_gameobjects(&content);
         let index = BlockIndex::new(&content);
 
@@ -167,25 +423,42 @@ impl Scanner {
             .collect()
     }
 
-    /// Find GameObjects and PrefabInstances by name pattern
+    /// Find GameObjects and PrefabInstances by name pattern.
+    /// Exact and glob lookups use the scene's sidecar index when one has been built.
     #[napi]
     pub fn find_by_name(&mut self, file: String, pattern: String, fuzzy: bool) -> Vec<FindResult> {
+        let _call = telemetry::call("find_by_name");
         let path = Path::new(&file);
         if !path.exists() {
             return Vec::new();
         }
 
+        if !fuzzy {
+            if let Some(index) = self.fresh_scene_index(&file, false) {
+                return index.find_by_name(&pattern, glob_to_regex(&pattern).as_ref());
+            }
+        }
+
         let content = match common::read_unity_file(path) {
             Ok(c) => c,
             Err(_) => return Vec::new(),
         };
 
+        self.find_by_name_from_content(file, content, pattern, fuzzy)
+    }
+
+    /// Find GameObjects and PrefabInstances by name pattern in in-memory content. Each result
+    /// carries its hierarchy path and parent name to tell same-named objects apart.
+    #[napi]
+    pub fn find_by_name_from_content(&mut self, file: String, content: String, pattern: String, fuzzy: bool) -> Vec<FindResult> {
+        let _call = telemetry::call("find_by_name_from_content");
+        let content = common::normalize_line_endings(content);
         let gameobjects = UnityYamlParser::extract_gameobjects(&content);
 
         self.ensure_guid_resolver(&file);
         let prefab_instances = prefab::extract_prefab_instances(&content, &self.guid_cache);
 
-        if fuzzy {
+        let mut matches = if fuzzy {
             let glob_re = glob_to_regex(&pattern);
             let lower_pattern = pattern.to_lowercase();
             let norm_pattern = lower_pattern.replace('_', "");
@@ -259,12 +532,99 @@ impl Scanner {
             }
 
             matches
+        };
+
+        if !matches.is_empty() {
+            let names: HashMap<String, String> = gameobjects.iter().map(|go| (go.file_id.clone(), go.name.clone()))
+                .chain(prefab_instances.iter().map(|pi| (pi.file_id.clone(), pi.name.clone())))
+                .collect();
+            let breadcrumbs = gameobject::hierarchy_breadcrumbs(&UnityYamlParser::parse_all_blocks(&content), &names, &self.config);
+            for found in &mut matches {
+                found.set_breadcrumb(&breadcrumbs);
+            }
         }
+        matches
     }
 
-    /// Inspect a specific GameObject
+    /// Build (or refresh) the sidecar name/tag/component-type index of a scene or prefab under
+    /// `.unity-agentic/scene-index/`. Once built, `find_by_name` answers from it until the file's
+    /// mtime or size changes, at which point it is rebuilt on the next lookup.
+    #[napi]
+    pub fn build_scene_index(&mut self, file: String) -> serde_json::Value {
+        let _call = crate::telemetry::call("build_scene_index");
+        self.ensure_guid_resolver(&file);
+        let Some(root) = self.project_root.clone() else {
+            return serde_json::json!({ "error": format!("No Unity project found for {}", file), "is_error": true });
+        };
+        let Some(stamp) = scene_index::source_stamp(Path::new(&file)) else {
+            return serde_json::json!({ "error": format!("File not found: {}", file), "is_error": true });
+        };
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", file, e), "is_error": true }),
+        };
+        let relative = self.relative_to_project(&file);
+        let index = scene_index::SceneIndex::build(&relative, &content, stamp, &self.guid_cache, &self.config);
+        let path = scene_index::index_path(Path::new(&root), &relative);
+        if let Err(e) = index.save(&path) {
+            return serde_json::json!({ "error": e, "is_error": true });
+        }
+        serde_json::json!({
+            "file": relative,
+            "index_path": path.to_string_lossy(),
+            "objects": index.objects.len(),
+            "names": index.by_name.len(),
+            "tags": index.by_tag.len(),
+            "component_types": index.by_component.len(),
+        })
+    }
+
+    /// GameObjects/PrefabInstances matching all given criteria (exact name, tag, component type
+    /// or script name), answered from the sidecar index (built on first use).
     #[napi]
+    pub fn query_scene_index(
+        &mut self,
+        file: String,
+        name: Option<String>,
+        tag: Option<String>,
+        component_type: Option<String>,
+    ) -> Vec<FindResult> {
+        let _call = crate::telemetry::call("query_scene_index");
+        self.fresh_scene_index(&file, true)
+            .map(|index| index.query(name.as_deref(), tag.as_deref(), component_type.as_deref()))
+            .unwrap_or_default()
+    }
+
+    /// Rows for the GameObjects matching `selector` (all when omitted) and the optional boolean
+    /// `filter` expression, with computed columns such as `pos = transform.localPosition` or
+    /// `dist = length(transform.localPosition)` evaluated natively (see `scanner::query`).
+    #[napi(ts_return_type = "Array<Record<string, any>> | ErrorOutput")]
+    pub fn query_gameobjects(
+        &mut self,
+        file: String,
+        selector: Option<GameObjectSelector>,
+        columns: Option<Vec<String>>,
+        filter: Option<String>,
+    ) -> serde_json::Value {
+        let _call = telemetry::call("query_gameobjects");
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", file, e), "is_error": true }),
+        };
+        self.ensure_guid_resolver(&file);
+        let selector = selector.unwrap_or(GameObjectSelector {
+            name: None, tag: None, layer: None, component_type: None, active: None, under: None,
+        });
+        match query::query_rows(&content, &selector, &columns.unwrap_or_default(), filter.as_deref(), &self.guid_cache, &self.config) {
+            Ok(rows) => serde_json::Value::Array(rows),
+            Err(e) => serde_json::json!({ "error": e, "is_error": true }),
+        }
+    }
+
+    /// Inspect a specific GameObject
+    #[napi(ts_return_type = "GameObjectInspection | PrefabInstanceOutput | ErrorOutput | null")]
     pub fn inspect(&mut self, options: InspectOptions) -> Option<serde_json::Value> {
+        let _call = telemetry::call("inspect");
         let path = Path::new(&options.file);
         if !path.exists() {
             return None;
@@ -275,6 +635,21 @@ impl Scanner {
             Err(_) => return None,
         };
 
+        self.inspect_from_content(options, content)
+    }
+
+    /// Inspect a specific GameObject in in-memory content.
+    /// `options.file` is only used to locate the project for GUID resolution.
+    #[napi(ts_return_type = "GameObjectInspection | PrefabInstanceOutput | ErrorOutput | null")]
+    pub fn inspect_from_content(&mut self, options: InspectOptions, content: String) -> Option<serde_json::Value> {
+        let _call = telemetry::call("inspect_from_content");
+        let fields = options.fields.clone();
+        let output = self.inspect_unprojected(options, content)?;
+        Some(projection::project(output, fields.as_deref()))
+    }
+
+    fn inspect_unprojected(&mut self, options: InspectOptions, content: String) -> Option<serde_json::Value> {
+        let content = common::normalize_line_endings(content);
         let identifier = options.identifier.as_ref()?;
 
         self.ensure_guid_resolver(&options.file);
@@ -285,7 +660,7 @@ impl Scanner {
         let target_file_id = if is_file_id {
             identifier.clone()
         } else {
-            let matches = self.find_by_name(options.file.clone(), identifier.clone(), false);
+            let matches = self.find_by_name_from_content(options.file.clone(), content.clone(), identifier.clone(), false);
             if matches.len() > 1 {
                 let ids: Vec<String> = matches.iter().map(|m| m.file_id.clone()).collect();
                 return Some(serde_json::json!({
@@ -299,9 +674,18 @@ impl Scanner {
         let include_properties = options.include_properties.unwrap_or(false);
 
         // Check if target_file_id matches a PrefabInstance
-        let prefabs = prefab::extract_prefab_instances(&content, &self.guid_cache);
-        if let Some(pi) = prefabs.iter().find(|p| p.file_id == target_file_id) {
-            return Some(self.build_prefab_instance_output(pi, Some(&content), include_properties));
+        let mut prefabs = prefab::extract_prefab_instances(&content, &self.guid_cache);
+        prefabs.retain(|p| p.file_id == target_file_id);
+        self.complete_prefab_instances(&mut prefabs, &content, include_properties);
+        if let Some(pi) = prefabs.first() {
+            let mut output = self.build_prefab_instance_output(pi, Some(&content), include_properties);
+            if options.expand_nested.unwrap_or(false) {
+                match self.nested_expansion(options.max_nesting_depth).expand_source(pi) {
+                    Ok(nodes) => output.expanded = Some(nodes),
+                    Err(reason) => output.unexpanded_reason = Some(reason),
+                }
+            }
+            return serde_json::to_value(output).ok();
         }
 
         let gameobjects = UnityYamlParser::extract_gameobjects(&content);
@@ -309,7 +693,7 @@ impl Scanner {
             Some(obj) => obj,
             None => {
                 // Check if the ID matches any block (could be a non-GO or stripped GO)
-                let block_pattern = format!("--- !u!(\\d+) &{}(?: stripped)?", regex::escape(&target_file_id));
+                let block_pattern = format!("(?m)^--- !u!(\\d+) &{}(?: stripped)?[ \\t]*\\r?$", regex::escape(&target_file_id));
                 if let Ok(re) = regex::Regex::new(&block_pattern) {
                     if let Some(caps) = re.captures(&content) {
                         let class_id: u32 = caps.get(1).unwrap().as_str().parse().unwrap_or(0);
@@ -323,7 +707,9 @@ impl Scanner {
                             }));
                         }
 
-                        let type_name = class_id_to_name(class_id);
+                        let type_name = self.config.class_names.get(&class_id)
+                            .map(String::as_str)
+                            .unwrap_or_else(|| class_id_to_name(class_id));
                         return Some(serde_json::json!({
                             "error": format!("ID {} is a {} (class_id {}), not a GameObject. Use the parent GameObject's ID or name instead.", target_file_id, type_name, class_id),
                             "is_error": true
@@ -335,17 +721,25 @@ impl Scanner {
         };
 
         let index = BlockIndex::new(&content);
-        let components = component::extract_components_indexed(&index, &target_file_id, &self.guid_cache, &self.config);
+        let mut components = component::extract_components_indexed(&index, &target_file_id, &self.guid_cache, &self.config);
+        summarize_component_properties(&mut components, options.max_property_bytes);
+        if options.resolve_images.unwrap_or(false) {
+            self.annotate_image_references(&mut components);
+        }
         let verbose = options.verbose.unwrap_or(false);
 
-        let detail = self.extract_gameobject_details_indexed(&index, target_obj, &components);
+        let detail = self.extract_gameobject_details_indexed(&options.file, &index, target_obj, &components);
 
-        Some(self.build_detail_output(&detail, verbose, include_properties))
+        let mut output = self.build_detail_output(&detail, verbose, include_properties);
+        let annotations = self.resolved_annotations(&options.file, &content, Some(&target_file_id), None);
+        output.annotations = (!annotations.is_empty()).then_some(annotations);
+        serde_json::to_value(output).ok()
     }
 
     /// Inspect entire file
     #[napi]
     pub fn inspect_all(&mut self, file: String, include_properties: bool, verbose: bool) -> SceneInspection {
+        let _call = telemetry::call("inspect_all");
         let path = Path::new(&file);
         if !path.exists() {
             return SceneInspection {
@@ -368,15 +762,33 @@ impl Scanner {
             }
         };
 
+        self.inspect_all_from_content(file, content, include_properties, verbose)
+    }
+
+    /// Inspect entire in-memory file content
+    #[napi]
+    pub fn inspect_all_from_content(&mut self, file: String, content: String, include_properties: bool, verbose: bool) -> SceneInspection {
+        let _call = telemetry::call("inspect_all_from_content");
+        let content = common::normalize_line_endings(content);
+        self.inspect_all_content(file, &content, include_properties, verbose, true)
+    }
+
+    /// Shared body of the inspect_all variants; without `with_components` every GameObject
+    /// gets an empty component list.
+    fn inspect_all_content(&mut self, file: String, content: &str, include_properties: bool, verbose: bool, with_components: bool) -> SceneInspection {
         self.ensure_guid_resolver(&file);
-        let index = BlockIndex::new(&content);
+        let index = BlockIndex::new(content);
 
-        let gameobjects = UnityYamlParser::extract_gameobjects(&content);
+        let gameobjects = UnityYamlParser::extract_gameobjects(content);
         let detailed: Vec<GameObjectDetail> = gameobjects
             .iter()
             .map(|obj| {
-                let components = component::extract_components_indexed(&index, &obj.file_id, &self.guid_cache, &self.config);
-                let mut detail = self.extract_gameobject_details_indexed(&index, obj, &components);
+                let components = match (with_components, include_properties) {
+                    (false, _) => Vec::new(),
+                    (true, true) => component::extract_components_indexed(&index, &obj.file_id, &self.guid_cache, &self.config),
+                    (true, false) => component::extract_component_identities_indexed(&index, &obj.file_id, &self.guid_cache, &self.config),
+                };
+                let mut detail = self.extract_gameobject_details_indexed(&file, &index, obj, &components);
 
                 if !include_properties {
                     for comp in &mut detail.components {
@@ -394,7 +806,8 @@ impl Scanner {
             })
             .collect();
 
-        let prefab_instances = prefab::extract_prefab_instances(&content, &self.guid_cache);
+        let mut prefab_instances = prefab::extract_prefab_instances(content, &self.guid_cache);
+        self.complete_prefab_instances(&mut prefab_instances, content, include_properties);
         let prefab_opt = if prefab_instances.is_empty() {
             None
         } else {
@@ -403,62 +816,88 @@ impl Scanner {
 
         SceneInspection {
             file,
-            count: detailed.len() as u32,
+            count: common::to_count(detailed.len()),
             gameobjects: detailed,
             prefab_instances: prefab_opt,
         }
     }
 
+    /// Inspect entire file, keeping only the given fields of each GameObject and PrefabInstance
+    /// (dotted paths such as ["name", "tag", "components.type"]). Selection happens before the
+    /// result crosses into JS, so targeted queries on big scenes marshal far less data.
+    #[napi(ts_return_type = "{ file: string; count: number; gameobjects: Partial<GameObjectDetail>[]; prefab_instances?: Partial<PrefabInstanceInfo>[] }")]
+    pub fn inspect_all_fields(&mut self, file: String, fields: Vec<String>, include_properties: Option<bool>, verbose: Option<bool>) -> serde_json::Value {
+        let _call = telemetry::call("inspect_all_fields");
+        let selection = FieldSelection::parse(&fields);
+        // Component and property extraction dominate; skip them when no selected path shows them
+        let with_components = selection.as_ref().is_none_or(|s| s.selects("components"));
+        let include_properties = include_properties.unwrap_or(false)
+            && selection.as_ref().is_none_or(|s| s.selects("components.properties") || s.selects("added_components"));
+        let path = Path::new(&file);
+        let content = if path.exists() { common::read_unity_file(path).ok() } else { None };
+        let inspection = match content {
+            Some(content) => self.inspect_all_content(file, &content, include_properties, verbose.unwrap_or(false), with_components),
+            None => SceneInspection { file, count: 0, gameobjects: Vec::new(), prefab_instances: None },
+        };
+        let project_all = |items: Vec<serde_json::Value>| -> Vec<serde_json::Value> {
+            items.into_iter().map(|item| project_with(item, selection.as_ref())).collect()
+        };
+        let gameobjects = inspection.gameobjects.iter().filter_map(|go| serde_json::to_value(go).ok()).collect();
+        let mut output = serde_json::json!({
+            "file": inspection.file,
+            "count": inspection.count,
+            "gameobjects": project_all(gameobjects),
+        });
+        if let Some(pis) = inspection.prefab_instances {
+            let pis = pis.iter().filter_map(|pi| serde_json::to_value(pi).ok()).collect();
+            output["prefab_instances"] = serde_json::Value::Array(project_all(pis));
+        }
+        output
+    }
+
     /// Inspect entire file with pagination support
     #[napi]
     pub fn inspect_all_paginated(&mut self, options: PaginationOptions) -> PaginatedInspection {
-        let file = options.file;
-        let include_properties = options.include_properties.unwrap_or(false);
-        let verbose = options.verbose.unwrap_or(false);
-        let page_size = options.page_size.unwrap_or(200).min(1000);
-        let cursor = options.cursor.unwrap_or(0);
-        let max_depth = options.max_depth.unwrap_or(10).min(50);
-        let filter_component = options.filter_component;
-
-        let path = Path::new(&file);
+        let _call = crate::telemetry::call("inspect_all_paginated");
+        let path = Path::new(&options.file);
         if !path.exists() {
-            return PaginatedInspection {
-                file: file.clone(),
-                total: 0,
-                total_in_scene: 0,
-                cursor,
-                next_cursor: None,
-                truncated: false,
-                page_size,
-                gameobjects: Vec::new(),
-                prefab_instances: None,
-                error: Some(format!("File not found: {}", file)),
-            };
+            let error = format!("File not found: {}", options.file);
+            return paginated_error(options, error);
         }
 
-        let content = match common::read_unity_file(path) {
-            Ok(c) => c,
+        match common::read_unity_file(path) {
+            Ok(content) => self.inspect_all_paginated_from_content(options, content),
             Err(_) => {
-                return PaginatedInspection {
-                    file: file.clone(),
-                    total: 0,
-                    total_in_scene: 0,
-                    cursor,
-                    next_cursor: None,
-                    truncated: false,
-                    page_size,
-                    gameobjects: Vec::new(),
-                    prefab_instances: None,
-                    error: Some(format!("Cannot read file: {}", file)),
-                }
+                let error = format!("Cannot read file: {}", options.file);
+                paginated_error(options, error)
             }
+        }
+    }
+
+    /// Inspect in-memory file content with pagination support.
+    /// `options.file` is only used for labeling and GUID resolution.
+    #[napi]
+    pub fn inspect_all_paginated_from_content(&mut self, options: PaginationOptions, content: String) -> PaginatedInspection {
+        let _call = crate::telemetry::call("inspect_all_paginated_from_content");
+        let content = common::normalize_line_endings(content);
+        let hierarchy_sort = match hierarchy_sort(options.sort.as_deref()) {
+            Ok(hierarchy_sort) => hierarchy_sort,
+            Err(error) => return paginated_error(options, error),
         };
+        let file = options.file;
+        let include_properties = options.include_properties.unwrap_or(false);
+        let verbose = options.verbose.unwrap_or(false);
+        let page_size = options.page_size.unwrap_or(200).min(1000);
+        let cursor = options.cursor.unwrap_or(0).max(0);
+        let max_depth = options.max_depth.unwrap_or(10).min(50);
+        let filter_component = options.filter_component;
+        let max_property_bytes = options.max_property_bytes;
 
         self.ensure_guid_resolver(&file);
         let index = BlockIndex::new(&content);
 
         let gameobjects = UnityYamlParser::extract_gameobjects(&content);
-        let total_in_scene = gameobjects.len() as u32;
+        let total_in_scene = common::to_count(gameobjects.len());
 
         // Phase 1: Extract lightweight hierarchy info for depth calculation.
         // This avoids full component extraction for ALL GOs — just find transform parent.
@@ -568,21 +1007,27 @@ impl Scanner {
             });
         }
 
-        let total = filtered.len() as u32;
+        if hierarchy_sort {
+            let order = gameobject::hierarchy_order(&UnityYamlParser::parse_all_blocks(&content), &self.config);
+            filtered.sort_by_key(|gwd| order.get(&gameobjects[gwd.go_idx].file_id).copied().unwrap_or(usize::MAX));
+        }
+
+        let total = common::to_count(filtered.len());
 
         // Extract prefab instances (only on first page)
         let prefab_instances = if cursor == 0 {
-            let pis = prefab::extract_prefab_instances(&content, &self.guid_cache);
+            let mut pis = prefab::extract_prefab_instances(&content, &self.guid_cache);
+            self.complete_prefab_instances(&mut pis, &content, include_properties);
             if pis.is_empty() { None } else { Some(pis) }
         } else {
             None
         };
 
         // Phase 3: Apply pagination BEFORE full extraction
-        let start = cursor as usize;
-        let end = (start + page_size as usize).min(filtered.len());
-        let truncated = end < filtered.len();
-        let next_cursor = if truncated { Some(end as u32) } else { None };
+        let start = common::to_offset(cursor);
+        let end = start.saturating_add(page_size as usize).min(filtered.len());
+        let truncated = start < end && end < filtered.len();
+        let next_cursor = if truncated { Some(common::to_count(end)) } else { None };
 
         let page_slice = if start < filtered.len() {
             &filtered[start..end]
@@ -595,12 +1040,16 @@ impl Scanner {
             .iter()
             .map(|gwd| {
                 let obj = &gameobjects[gwd.go_idx];
-                let components = component::extract_components_indexed(&index, &obj.file_id, &self.guid_cache, &self.config);
-                let mut detail = self.extract_gameobject_details_indexed(&index, obj, &components);
+                let mut components = component::extract_components_indexed(&index, &obj.file_id, &self.guid_cache, &self.config);
+                if include_properties {
+                    summarize_component_properties(&mut components, max_property_bytes);
+                }
+                let mut detail = self.extract_gameobject_details_indexed(&file, &index, obj, &components);
                 detail.depth = Some(gwd.depth);
 
                 if gwd.at_boundary {
                     detail.children = None;
+                    detail.ordered_children = None;
                 }
 
                 if !include_properties {
@@ -633,113 +1082,1927 @@ impl Scanner {
         }
     }
 
-    /// Read a .asset file and return its root objects with properties.
-    /// When `decode_mesh` is true (default), Mesh assets (class 43) get their
-    /// hex vertex/index data decoded into structured arrays.
+    /// Build the fully expanded hierarchy of a scene or prefab.
+    /// PrefabInstances are recursively replaced by their source prefab's hierarchy
+    /// (up to `max_nesting_depth`, default 3); every node records its source file.
+    /// Siblings follow document order, or the editor's Hierarchy window order with
+    /// `sort: "hierarchy"`.
     #[napi]
-    pub fn read_asset(&mut self, file: String, decode_mesh: Option<bool>) -> serde_json::Value {
+    pub fn inspect_expanded(&mut self, file: String, max_nesting_depth: Option<u32>, sort: Option<String>) -> Vec<NestedPrefabNode> {
+        let _call = crate::telemetry::call("inspect_expanded");
         let path = Path::new(&file);
         if !path.exists() {
-            return serde_json::json!([]);
+            return Vec::new();
         }
 
         let content = match common::read_unity_file(path) {
             Ok(c) => c,
-            Err(_) => return serde_json::json!([]),
+            Err(_) => return Vec::new(),
         };
 
-        self.ensure_guid_resolver(&file);
+        self.inspect_expanded_from_content(file, content, max_nesting_depth, sort)
+    }
 
-        let blocks = UnityYamlParser::extract_asset_objects(&content);
-        let mut objects = Vec::new();
+    /// Build the fully expanded hierarchy of in-memory scene or prefab content
+    #[napi]
+    pub fn inspect_expanded_from_content(
+        &mut self,
+        file: String,
+        content: String,
+        max_nesting_depth: Option<u32>,
+        sort: Option<String>,
+    ) -> Vec<NestedPrefabNode> {
+        let _call = crate::telemetry::call("inspect_expanded_from_content");
+        let Ok(hierarchy_sort) = hierarchy_sort(sort.as_deref()) else { return Vec::new() };
+        let content = common::normalize_line_endings(content);
+        self.ensure_guid_resolver(&file);
+        let source_file = self.relative_to_project(&file);
+        let mut expansion = self.nested_expansion(max_nesting_depth);
+        expansion.hierarchy_order = hierarchy_sort;
+        expansion.expand_file(&content, &source_file)
+    }
 
-        for (class_id, file_id, block_content) in &blocks {
-            // Extract m_Name from block
-            let name = regex::Regex::new(r"m_Name:[ \t]*([^\n]*)")
-                .ok()
-                .and_then(|re| re.captures(block_content))
-                .and_then(|caps| caps.get(1))
-                .map(|m| m.as_str().trim().to_string())
-                .unwrap_or_default();
+    /// Count the blocks of a scene/prefab per class ID from its block headers alone, without
+    /// parsing bodies: the cheapest way to see how big a file is before choosing how to query it.
+    #[napi]
+    pub fn count_objects(&self, file: String) -> Option<ObjectCounts> {
+        let _call = crate::telemetry::call("count_objects");
+        // Headers are counted in place, so CRLF files need no normalized copy
+        let content = common::read_raw_unity_file(&file).ok()?;
+        Some(self.object_counts(file, &content))
+    }
 
-            // Determine type name from block (first line after header like "MonoBehaviour:")
-            let type_name = regex::Regex::new(r"^([A-Za-z][A-Za-z0-9_]*):")
-                .ok()
-                .and_then(|re| re.captures(block_content))
-                .and_then(|caps| caps.get(1))
-                .map(|m| m.as_str().to_string())
-                .unwrap_or_else(|| format!("ClassID_{}", class_id));
+    /// Count blocks per class ID of in-memory content. `file` is only used for labeling.
+    #[napi]
+    pub fn count_objects_from_content(&self, file: String, content: String) -> ObjectCounts {
+        let _call = telemetry::call("count_objects_from_content");
+        self.object_counts(file, &content)
+    }
 
-            // Extract script GUID for MonoBehaviour (class_id 114)
-            let mut script_guid: Option<String> = None;
-            let mut script_path: Option<String> = None;
+    fn object_counts(&self, file: String, content: &str) -> ObjectCounts {
+        let (counts, stripped_count) = stats::count_blocks(content);
+        let count_of = |class_id: u32| counts.iter().find(|(c, _)| *c == class_id).map_or(0, |(_, n)| *n);
+        ObjectCounts {
+            size_bytes: content.len() as i64,
+            total_blocks: counts.iter().map(|(_, n)| n).sum(),
+            gameobject_count: count_of(self.config.gameobject_class_id),
+            prefab_instance_count: count_of(1001),
+            stripped_count,
+            classes: counts.iter()
+                .map(|&(class_id, count)| ClassCount {
+                    class_id,
+                    class_name: self.config.class_names.get(&class_id)
+                        .cloned()
+                        .unwrap_or_else(|| class_id_to_name(class_id).to_string()),
+                    count,
+                })
+                .collect(),
+            file,
+        }
+    }
 
-            if *class_id == 114 {
-                let guid_re = regex::Regex::new(r"m_Script:\s*\{[^}]*guid:\s*([a-f0-9]{32})").ok();
-                if let Some(re) = guid_re {
-                    if let Some(caps) = re.captures(block_content) {
-                        if let Some(guid_match) = caps.get(1) {
-                            let guid = guid_match.as_str().to_string();
-                            script_guid = Some(guid.clone());
-                            script_path = self.guid_cache.get(&guid).cloned();
-                        }
-                    }
-                }
-            }
+    /// Compute aggregate statistics (GameObjects, components, prefab instances, scripts) for a file.
+    #[napi]
+    pub fn scene_stats(&mut self, file: String) -> Option<SceneStats> {
+        let _call = crate::telemetry::call("scene_stats");
+        let path = Path::new(&file);
+        if !path.exists() {
+            return None;
+        }
 
-            // Extract properties using existing infrastructure
-            // We need the full content with header for extract_properties
-            let full_block = format!("--- !u!{} &{}\n{}", class_id, file_id, block_content);
-            let mut properties = component::extract_properties(&full_block, file_id, *class_id, &self.guid_cache);
+        let content = common::read_unity_file(path).ok()?;
+        Some(self.scene_stats_from_content(file, content))
+    }
 
-            // Auto-decode Mesh binary data (class 43) unless opted out
-            if decode_mesh.unwrap_or(true) && *class_id == 43 {
-                mesh::decode_mesh_data(&mut properties);
-            }
+    /// Compute scene statistics from in-memory content (e.g. a file at a git revision).
+    /// `file` is used for labeling and to locate the project for script GUID resolution.
+    #[napi]
+    pub fn scene_stats_from_content(&mut self, file: String, content: String) -> SceneStats {
+        let _call = crate::telemetry::call("scene_stats_from_content");
+        let content = common::normalize_line_endings(content);
+        self.ensure_guid_resolver(&file);
+        stats::compute_stats(&file, &content, &self.guid_cache, &self.config)
+    }
 
-            let mut obj = serde_json::json!({
-                "class_id": class_id,
-                "file_id": file_id,
-                "type_name": type_name,
-                "name": name,
-                "properties": properties,
-            });
+    /// Compare scene statistics between two versions of the same file
+    /// (e.g. `git show HEAD~1:Assets/Main.unity` vs the working copy).
+    #[napi]
+    pub fn diff_scene_stats(&mut self, file: String, old_content: String, new_content: String) -> SceneStatsDiff {
+        let _call = crate::telemetry::call("diff_scene_stats");
+        let old = self.scene_stats_from_content(file.clone(), old_content);
+        let new = self.scene_stats_from_content(file.clone(), new_content);
+        stats::diff_stats(&file, old, new)
+    }
 
-            if let Some(ref guid) = script_guid {
-                obj["script_guid"] = serde_json::json!(guid);
-            }
-            if let Some(ref path) = script_path {
-                obj["script_path"] = serde_json::json!(path);
-            }
+    /// Descendant count, depth, breadth and per-type component totals of the subtree under a
+    /// GameObject ("how heavy is this UI panel?"). Returns None when the file or GameObject is missing.
+    #[napi]
+    pub fn subtree_metrics(&mut self, file: String, go_id: String) -> Option<SubtreeMetrics> {
+        let _call = telemetry::call("subtree_metrics");
+        let content = common::read_unity_file(&file).ok()?;
+        self.ensure_guid_resolver(&file);
+        stats::subtree_metrics(&file, &content, &go_id, &self.guid_cache, &self.config)
+    }
 
-            objects.push(obj);
-        }
+    /// Local and world position/rotation/scale, ancestor names and sibling index of a
+    /// GameObject ("where is it?") without inspecting its properties. Returns None when the
+    /// file or GameObject is missing.
+    #[napi]
+    pub fn get_transform_summary(&self, file: String, go_id: String) -> Option<TransformSummary> {
+        let _call = telemetry::call("get_transform_summary");
+        let content = common::read_unity_file(&file).ok()?;
+        transform::summarize(&self.relative_to_project(&file), &content, &go_id, &self.config)
+    }
 
-        serde_json::json!(objects)
+    /// World-space box around every placed GameObject and prefab instance of a scene
+    /// ("how big is this level?"), optionally per layer or tag, with a coarse occupancy grid
+    /// for top-down overviews. Returns None when the file cannot be read.
+    #[napi]
+    pub fn compute_scene_bounds(&self, file: String, options: Option<SceneBoundsOptions>) -> Option<SceneBoundsReport> {
+        let _call = telemetry::call("compute_scene_bounds");
+        let content = common::read_unity_file(&file).ok()?;
+        Some(bounds::scene_bounds(&self.relative_to_project(&file), &content, &options.unwrap_or_default(), &self.config))
     }
 
-    fn ensure_guid_resolver(&mut self, file: &str) {
-        if self.project_root.is_none() {
-            if let Some(root) = find_project_root(file) {
-                self.project_root = Some(root.clone());
-                self.build_guid_cache(&root);
-            }
+    /// Rough memory/build impact of a scene or prefab: serialized size, instance counts and the
+    /// transitive texture/mesh/audio/... dependencies costed from file size and import settings.
+    #[napi]
+    pub fn estimate_scene_cost(&mut self, file: String) -> Option<SceneCostReport> {
+        let _call = crate::telemetry::call("estimate_scene_cost");
+        let content = common::read_unity_file(&file).ok()?;
+        self.ensure_guid_resolver(&file);
+        let stats = stats::compute_stats(&file, &content, &self.guid_cache, &self.config);
+        let root = self.project_root.clone().unwrap_or_default();
+        let (mut assets, unresolved_references) = cost::collect_dependencies(&content, &self.guid_cache, Path::new(&root));
+
+        let mut categories: Vec<AssetCostCategory> = Vec::new();
+        for asset in &assets {
+            let entry = match categories.iter().position(|c| c.category == asset.category) {
+                Some(i) => &mut categories[i],
+                None => {
+                    categories.push(AssetCostCategory { category: asset.category.clone(), count: 0, file_bytes: 0, estimated_memory_bytes: 0 });
+                    categories.last_mut()?
+                }
+            };
+            entry.count += 1;
+            entry.file_bytes += asset.file_bytes;
+            entry.estimated_memory_bytes += asset.estimated_memory_bytes;
         }
+        categories.sort_by_key(|c| std::cmp::Reverse(c.estimated_memory_bytes));
+
+        let total_asset_bytes = assets.iter().map(|a| a.file_bytes).sum();
+        let asset_memory: i64 = assets.iter().map(|a| a.estimated_memory_bytes).sum();
+        assets.sort_by_key(|a| std::cmp::Reverse(a.estimated_memory_bytes));
+        let dependency_count = assets.len() as u32;
+        assets.truncate(15);
+
+        Some(SceneCostReport {
+            file: self.relative_to_project(&file),
+            serialized_bytes: stats.size_bytes,
+            gameobject_count: stats.gameobject_count,
+            component_count: stats.component_count,
+            prefab_instance_count: stats.prefab_instance_count,
+            dependency_count,
+            unresolved_references,
+            total_asset_bytes,
+            estimated_memory_bytes: stats.size_bytes + asset_memory,
+            categories,
+            largest_assets: assets,
+        })
+    }
+
+    /// Sorted list of every external GUID a scene or prefab references, with the resolved
+    /// project path, asset category and reference count. Unlike `estimate_scene_cost`, only
+    /// direct references are listed, so the manifest can be cached per revision and diffed.
+    #[napi]
+    pub fn export_guid_manifest(&mut self, file: String) -> Option<GuidManifest> {
+        let _call = telemetry::call("export_guid_manifest");
+        let content = common::read_unity_file(&file).ok()?;
+        self.ensure_guid_resolver(&file);
+        Some(cost::guid_manifest(self.relative_to_project(&file), &content, &self.guid_cache, &self.guid_aliases))
+    }
+
+    /// Pixel dimensions (PNG/JPEG/GIF/BMP/PSD/TGA header) and sprite rects (from the .meta) of a
+    /// texture asset. `path` may be absolute or relative to the project root.
+    #[napi]
+    pub fn get_texture_info(&mut self, path: String) -> Option<TextureInfo> {
+        let _call = crate::telemetry::call("get_texture_info");
+        let full = match &self.project_root {
+            Some(root) if !Path::new(&path).is_absolute() => Path::new(root).join(&path),
+            _ => Path::new(&path).to_path_buf(),
+        };
+        image::texture_info(&full, &path)
+    }
+
+    /// Check a material's m_SavedProperties against the Properties block of its shader: stale
+    /// entries, entries stored under the wrong type, and declared properties without a value.
+    /// `shader` defaults to the shader the material references (resolved by GUID in the project);
+    /// built-in and Shader Graph shaders have no parsable source and must be passed explicitly.
+    #[napi(ts_return_type = "MaterialShaderCheck | ErrorOutput")]
+    pub fn check_material_shader(&mut self, material: String, shader: Option<String>) -> serde_json::Value {
+        let _call = telemetry::call("check_material_shader");
+        let content = match common::read_unity_file(&material) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", material, e), "is_error": true }),
+        };
+        self.ensure_guid_resolver(&material);
+        let shader_path = match shader {
+            Some(path) => match &self.project_root {
+                Some(root) if !Path::new(&path).is_absolute() => Path::new(root).join(&path).to_string_lossy().to_string(),
+                _ => path,
+            },
+            None => {
+                let guid = material::shader_reference(&content).and_then(|(_, guid)| guid);
+                let resolved = guid.as_deref()
+                    .and_then(|guid| self.guid_cache.get(guid))
+                    .zip(self.project_root.as_deref())
+                    .map(|(rel, root)| Path::new(root).join(rel).to_string_lossy().to_string());
+                match resolved {
+                    Some(path) => path,
+                    None => return serde_json::json!({
+                        "error": format!("Could not resolve the shader of {} (built-in or missing); pass the shader path", material),
+                        "is_error": true
+                    }),
+                }
+            }
+        };
+        let source = match crate::safety::read_to_string(&shader_path) {
+            Ok(s) => s,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", shader_path, e), "is_error": true }),
+        };
+        let (shader_name, declared) = material::parse_shader_properties(&source);
+        if declared.is_empty() && shader_name.is_none() {
+            return serde_json::json!({ "error": format!("{} has no Shader \"...\" declaration", shader_path), "is_error": true });
+        }
+        let saved = material::saved_properties(&content);
+        let check = MaterialShaderCheck {
+            material: self.relative_to_project(&material),
+            shader: self.relative_to_project(&shader_path),
+            shader_name,
+            declared_count: declared.len() as u32,
+            serialized_count: saved.len() as u32,
+            issues: material::check_properties(&saved, &declared),
+        };
+        serde_json::to_value(check).unwrap_or_default()
+    }
+
+    /// Canonical form of a scene/prefab for semantic diffing (blocks sorted by fileID, whitespace
+    /// normalized), returned as `content` or written to `output_path`. Purely a diff aid: the
+    /// output is never written over the source file.
+    #[napi]
+    pub fn normalize_scene(&self, file: String, output_path: Option<String>) -> serde_json::Value {
+        let _call = crate::telemetry::call("normalize_scene");
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", file, e), "is_error": true }),
+        };
+        let (normalized, block_count) = normalize::normalize(&content);
+        let Some(output_path) = output_path else {
+            return serde_json::json!({ "file": file, "block_count": block_count, "content": normalized });
+        };
+        let same_file = fs::canonicalize(&output_path).ok().is_some_and(|out| fs::canonicalize(&file).ok() == Some(out));
+        if same_file {
+            return serde_json::json!({ "error": "Refusing to overwrite the source file; normalized output is for diffing only", "is_error": true });
+        }
+        match common::atomic::write_atomic(&output_path, &normalized) {
+            Ok(()) => serde_json::json!({ "file": file, "block_count": block_count, "output_path": output_path }),
+            Err(e) => serde_json::json!({ "error": format!("Failed to write {}: {}", output_path, e), "is_error": true }),
+        }
+    }
+
+    /// Canonical form of in-memory Unity YAML (e.g. a git blob), for diffing.
+    #[napi]
+    pub fn normalize_scene_from_content(&self, content: String) -> String {
+        let _call = crate::telemetry::call("normalize_scene_from_content");
+        normalize::normalize(&content).0
+    }
+
+    /// Start watching a scene or prefab for changes: snapshot its GameObjects so later
+    /// poll_changes calls report only what differs. Subscribing again resets the snapshot.
+    /// Returns the object count, or an error object when the file cannot be read.
+    #[napi]
+    pub fn subscribe(&mut self, file: String) -> serde_json::Value {
+        let _call = crate::telemetry::call("subscribe");
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", file, e), "is_error": true }),
+        };
+        let snapshot = subscription::snapshot(&content, &self.config);
+        let object_count = snapshot.object_count();
+        self.subscriptions.insert(file.clone(), snapshot);
+        serde_json::json!({ "file": file, "object_count": object_count })
+    }
+
+    /// GameObjects added, removed or modified in a subscribed file since the previous poll (or
+    /// the subscription), with the component types that changed. An unchanged file costs one
+    /// content hash.
+    #[napi]
+    pub fn poll_changes(&mut self, file: String) -> SceneChangesResult {
+        let _call = telemetry::call("poll_changes");
+        let mut result = SceneChangesResult {
+            success: false,
+            error: None,
+            file: file.clone(),
+            changed: false,
+            added: Vec::new(),
+            removed: Vec::new(),
+            modified: Vec::new(),
+        };
+        let Some(previous) = self.subscriptions.get(&file) else {
+            result.error = Some(format!("Not subscribed to {}; call subscribe first", file));
+            return result;
+        };
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => {
+                result.error = Some(format!("Failed to read {}: {}", file, e));
+                return result;
+            }
+        };
+        let current = subscription::resnapshot(previous, &content, &self.config);
+        let changes = subscription::diff(previous, &current);
+        self.subscriptions.insert(file, current);
+
+        result.success = true;
+        result.changed = !(changes.added.is_empty() && changes.removed.is_empty() && changes.modified.is_empty());
+        result.added = changes.added;
+        result.removed = changes.removed;
+        result.modified = changes.modified;
+        result
+    }
+
+    /// Stop watching a file. Returns false when it was not subscribed.
+    #[napi]
+    pub fn unsubscribe(&mut self, file: String) -> bool {
+        self.subscriptions.remove(&file).is_some()
+    }
+
+    /// Map fileIDs between two versions of a scene/prefab (e.g. before and after Unity rewrote
+    /// it), matching GameObjects by fileID, hierarchy path, name and component set, and their
+    /// components by type. Lets diffs and stored references survive reassigned fileIDs.
+    #[napi]
+    pub fn correlate_objects(&self, old_content: String, new_content: String) -> ObjectCorrelationResult {
+        let _call = crate::telemetry::call("correlate_objects");
+        let old = correlate::fingerprints(&common::normalize_line_endings(old_content), &self.config);
+        let new = correlate::fingerprints(&common::normalize_line_endings(new_content), &self.config);
+        let (mappings, unmatched_old, unmatched_new) = correlate::correlate(&old, &new);
+        ObjectCorrelationResult {
+            remapped_count: mappings.iter().filter(|m| m.old_file_id != m.new_file_id).count() as u32,
+            mappings,
+            unmatched_old,
+            unmatched_new,
+        }
+    }
+
+    /// Check a scene or prefab against a JSON or TOML rule file (naming patterns, required and
+    /// forbidden components, maximum hierarchy depth). Returns a RuleValidationReport, or an
+    /// error object when either file cannot be read or the rules are invalid.
+    #[napi]
+    pub fn validate_with_rules(&mut self, file: String, rules_path: String) -> serde_json::Value {
+        let _call = crate::telemetry::call("validate_with_rules");
+        let rules = match rules::RuleSet::load(Path::new(&rules_path)) {
+            Ok(r) => r,
+            Err(e) => return serde_json::json!({ "error": e, "is_error": true }),
+        };
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", file, e), "is_error": true }),
+        };
+        self.ensure_guid_resolver(&file);
+        let subjects = rules::subjects(&content, &self.guid_cache, &self.config);
+        let violations = match rules.check(&subjects) {
+            Ok(v) => v,
+            Err(e) => return serde_json::json!({ "error": e, "is_error": true }),
+        };
+        let report = RuleValidationReport {
+            file: self.relative_to_project(&file),
+            rules_path,
+            rules_checked: rules.rule_count(),
+            objects_checked: subjects.len() as u32,
+            violations,
+        };
+        serde_json::to_value(report).unwrap_or_default()
+    }
+
+    /// One scored health report of a scene or prefab: missing references (objects, scripts and
+    /// assets), broken UnityEvent listeners, physics and canvas issues, deprecated components
+    /// and, with `rules_path`, rule violations. Each category is scored 0..100 and the overall
+    /// score is their weighted mean, so CI can track one number. Returns a SceneHealthReport,
+    /// or an error object when the file or rules cannot be read.
+    #[napi]
+    pub fn health_report(&mut self, file: String, rules_path: Option<String>) -> serde_json::Value {
+        let _call = telemetry::call("health_report");
+        let rules = match rules_path.as_deref().map(|p| rules::RuleSet::load(Path::new(p))).transpose() {
+            Ok(r) => r,
+            Err(e) => return serde_json::json!({ "error": e, "is_error": true }),
+        };
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", file, e), "is_error": true }),
+        };
+        self.ensure_guid_resolver(&file);
+        let relative = self.relative_to_project(&file);
+        let mut skipped = Vec::new();
+
+        // Package GUIDs are not in the scanner's cache; without them every package script would be missing
+        let known_guids = self.project_root.clone().map(|root| {
+            let mut known: HashSet<String> = self.guid_cache.keys().cloned().collect();
+            for cache in [crate::walker::build_package_guid_cache(root.clone()), crate::walker::build_local_package_guid_cache(root)] {
+                known.extend(cache.as_object().into_iter().flat_map(|m| m.keys().cloned()));
+            }
+            known
+        });
+        if known_guids.is_none() {
+            skipped.push("GUID references (scripts, assets): no Unity project found for the file".to_string());
+        }
+        let stale = |guid: &str| self.guid_aliases.resolve(guid, &self.guid_cache).map(|r| r.message);
+        let missing = health::missing_references(&content, known_guids.as_ref(), &stale, &self.config);
+
+        let mut method_exists = self.script_method_lookup();
+        let broken_listeners: Vec<HealthIssue> = events::list_events(&relative, &content, &self.guid_cache, &self.config, &mut method_exists)
+            .into_iter()
+            .flat_map(|event| {
+                event.listeners.iter()
+                    .filter_map(|l| Some(health::issue(
+                        "error",
+                        format!("{}.{} listener {} is broken: {}", event.component_type, event.event, l.method, l.issue.as_deref()?),
+                        Some((event.game_object_id.clone(), event.game_object_name.clone())),
+                    )))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let physics = self.audit_physics(file.clone()).objects.into_iter()
+            .flat_map(|o| o.issues.iter().map(|i| health::issue("warning", i.replace('_', " "), Some((o.game_object_id.clone(), o.game_object_name.clone())))).collect::<Vec<_>>())
+            .collect();
+        let canvas = canvas::audit_canvases(&relative, &content, &self.config).into_iter()
+            .flat_map(|c| c.issues.iter().map(|i| health::issue("warning", format!("Canvas: {}", i.replace('_', " ")), Some((c.game_object_id.clone(), c.game_object_name.clone())))).collect::<Vec<_>>())
+            .collect();
+        let deprecated = deprecated::scene_usages(&relative, &content, &self.guid_cache, &self.config).into_iter()
+            .map(|u| {
+                let owner = u.game_object_id.clone().zip(u.game_object_name.clone());
+                health::issue("warning", format!("{} is deprecated; use {}", u.name, u.replacement), owner)
+            })
+            .collect();
+
+        let mut categories = vec![
+            health::category("missing_references", 3, missing),
+            health::category("events", 2, broken_listeners),
+            health::category("physics", 2, physics),
+            health::category("canvas", 1, canvas),
+            health::category("deprecated", 1, deprecated),
+        ];
+        match rules {
+            Some(rules) => {
+                let subjects = rules::subjects(&content, &self.guid_cache, &self.config);
+                let violations = match rules.check(&subjects) {
+                    Ok(v) => v,
+                    Err(e) => return serde_json::json!({ "error": e, "is_error": true }),
+                };
+                let lint = violations.into_iter()
+                    .map(|v| {
+                        let severity = if v.severity == "error" { "error" } else { "warning" };
+                        health::issue(severity, format!("{}: {}", v.rule, v.message), Some((v.game_object_id, v.game_object_name)))
+                    })
+                    .collect();
+                categories.push(health::category("lint", 2, lint));
+            }
+            None => skipped.push("lint: no rules_path given".to_string()),
+        }
+
+        serde_json::to_value(health::report(relative, categories, skipped)).unwrap_or_default()
+    }
+
+    /// Find GameObjects that are (near-)identical copies of each other, e.g. hand-placed crates,
+    /// by fingerprinting their components, property values and children. Each cluster is a
+    /// candidate for extraction into a prefab. Returns None when the file cannot be read.
+    #[napi]
+    pub fn find_duplicate_structures(&mut self, file: String, options: Option<DuplicateOptions>) -> Option<DuplicateStructureReport> {
+        let _call = crate::telemetry::call("find_duplicate_structures");
+        let content = common::read_unity_file(&file).ok()?;
+        self.ensure_guid_resolver(&file);
+        let options = options.unwrap_or_default();
+        let (objects_checked, clusters) = duplicates::find_duplicates(
+            &content,
+            &self.guid_cache,
+            &self.config,
+            options.min_count.unwrap_or(2) as usize,
+            options.max_differences.unwrap_or(1) as usize,
+        );
+        Some(DuplicateStructureReport {
+            file: self.relative_to_project(&file),
+            objects_checked,
+            duplicate_objects: clusters.iter().map(|c| c.count * (1 + c.descendant_count)).sum(),
+            clusters,
+        })
+    }
+
+    /// Properties of a built-in component that differ from what Unity writes for a fresh one
+    /// (BoxCollider, Camera, Light, ...), with the defaults they replace: the intentional part
+    /// of its configuration. Scripts and types outside the bundled table report every property.
+    /// Returns None when the file or component is missing.
+    #[napi]
+    pub fn diff_against_defaults(&self, file: String, component_id: String) -> Option<ComponentDefaultsDiff> {
+        let _call = telemetry::call("diff_against_defaults");
+        let content = common::read_unity_file(&file).ok()?;
+        defaults::diff_against_defaults(&self.relative_to_project(&file), &content, &component_id)
+    }
+
+    /// Rank the GameObjects of `file` by how closely they are configured like `go_id`: same
+    /// component types and same property values, placement ignored. Lists objects scoring at
+    /// least `threshold` (0..1, default 0.5) with the components and values that set them apart,
+    /// for spotting the siblings of an object that needs a consistency fix. Returns None when
+    /// the file cannot be read or `go_id` is not one of its GameObjects.
+    #[napi]
+    pub fn find_similar(&mut self, file: String, go_id: String, threshold: Option<f64>) -> Option<SimilarObjectsReport> {
+        let _call = telemetry::call("find_similar");
+        let content = common::read_unity_file(&file).ok()?;
+        self.ensure_guid_resolver(&file);
+        let threshold = threshold.unwrap_or(similar::DEFAULT_THRESHOLD).clamp(0.0, 1.0);
+        let (target, objects_checked, matches) = similar::find_similar(&content, &self.guid_cache, &self.config, &go_id, threshold)?;
+        Some(SimilarObjectsReport {
+            file: self.relative_to_project(&file),
+            target,
+            threshold,
+            objects_checked,
+            matches,
+        })
+    }
+
+    /// Find prefabs that (transitively) instantiate themselves through nested prefabs or
+    /// variant bases, and nesting chains deeper than `max_depth` (default 8). Both make the
+    /// editor hang on import. `include_packages` also walks embedded and local packages.
+    #[napi]
+    pub fn detect_circular_prefab_refs(&self, project_root: String, max_depth: Option<u32>, include_packages: Option<bool>) -> PrefabNestingReport {
+        let _call = telemetry::call("detect_circular_prefab_refs");
+        nesting::detect(&project_root, max_depth, include_packages.unwrap_or(false))
+    }
+
+    /// Before renaming GameObject `go_id` of `scene` to `new_name`, list what relies on its name:
+    /// Animator/Animation curve paths running through it, `Find("...")` calls and other string
+    /// literals naming it in C# scripts, UnityEvent string arguments in scenes/prefabs, and
+    /// siblings already called `new_name`. Returns a RenameImpactReport or an error object.
+    #[napi(ts_return_type = "RenameImpactReport | ErrorOutput")]
+    pub fn analyze_rename_impact(&mut self, project_root: String, scene: String, go_id: String, new_name: String) -> serde_json::Value {
+        let _call = crate::telemetry::call("analyze_rename_impact");
+        if self.project_root.as_deref() != Some(project_root.as_str()) {
+            self.set_project_root(project_root.clone(), None);
+        }
+        let scene_path = if Path::new(&scene).is_relative() {
+            Path::new(&project_root).join(&scene).to_string_lossy().to_string()
+        } else {
+            scene
+        };
+        let content = match common::read_unity_file(&scene_path) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", scene_path, e), "is_error": true }),
+        };
+        let objects = correlate::fingerprints(&content, &self.config);
+        let Some(object) = objects.iter().find(|o| o.file_id == go_id) else {
+            return serde_json::json!({ "error": format!("No GameObject with fileID {} in {}", go_id, scene_path), "is_error": true });
+        };
+        let file = self.relative_to_project(&scene_path);
+        let parent_path = object.path.rsplit_once('/').map(|(parent, _)| parent);
+        let new_path = parent_path.map_or_else(|| new_name.clone(), |parent| format!("{}/{}", parent, new_name));
+        let mut risks: Vec<RenameRisk> = objects.iter()
+            .filter(|o| o.file_id != go_id && o.path == new_path)
+            .map(|o| RenameRisk {
+                kind: "name_conflict".to_string(),
+                severity: "medium".to_string(),
+                file: file.clone(),
+                line: None,
+                reference: o.file_id.clone(),
+                message: format!("Sibling \"{}\" (fileID {}) already has this name; lookups and curve paths become ambiguous", o.path, o.file_id),
+            })
+            .collect();
+        let mut files_scanned = 1;
+
+        let read_guid = |guid: &str| {
+            let rel = self.guid_cache.get(guid)?;
+            let content = common::read_unity_file(Path::new(&project_root).join(rel)).ok()?;
+            Some((rel.clone(), content))
+        };
+        for ancestor in rename::animated_ancestors(&content, &go_id, &self.config) {
+            let mut clip_guids = ancestor.clip_guids.clone();
+            if let Some((_, controller)) = ancestor.controller_guid.as_deref().and_then(read_guid) {
+                clip_guids.extend(animation::controller_clip_guids(&controller));
+            }
+            for (clip, clip_content) in clip_guids.iter().filter_map(|guid| read_guid(guid)) {
+                files_scanned += 1;
+                risks.extend(rename::binding_risks(&clip, &animation::parse_clip_bindings(&clip_content), &ancestor));
+            }
+        }
+
+        for script in crate::walker::walk_project_files(project_root.clone(), vec![".cs".to_string()], None, None) {
+            let Ok(source) = common::read_unity_file(&script) else { continue };
+            files_scanned += 1;
+            risks.extend(rename::code_risks(&self.relative_to_project(&script), &source, &object.name));
+        }
+        for other in crate::walker::walk_project_files(project_root.clone(), vec![".unity".to_string(), ".prefab".to_string()], None, None) {
+            let other_content = if other == scene_path {
+                content.clone()
+            } else {
+                let Ok(c) = common::read_unity_file(&other) else { continue };
+                files_scanned += 1;
+                c
+            };
+            risks.extend(rename::event_risks(&self.relative_to_project(&other), &other_content, &object.name, &self.config));
+        }
+        risks.sort_by_key(|r| r.severity != "high");
+
+        let report = RenameImpactReport {
+            file,
+            game_object_id: go_id,
+            old_name: object.name.clone(),
+            new_name,
+            path: object.path.clone(),
+            new_path,
+            files_scanned,
+            risks,
+        };
+        serde_json::to_value(report).unwrap_or_default()
+    }
+
+    /// Attach a persistent note and/or labels to a GameObject (by fileID) of a scene or prefab.
+    /// Stored in `.unity-agentic/annotations.json`; returns the annotation or an error object.
+    #[napi(ts_return_type = "ObjectAnnotation | ErrorOutput")]
+    pub fn annotate_object(&mut self, file: String, file_id: String, note: Option<String>, labels: Option<Vec<String>>) -> serde_json::Value {
+        let _call = crate::telemetry::call("annotate_object");
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", file, e), "is_error": true }),
+        };
+        self.ensure_guid_resolver(&file);
+        let Some(root) = self.project_root.clone() else {
+            return serde_json::json!({ "error": format!("No Unity project found for {}", file), "is_error": true });
+        };
+        let objects = correlate::fingerprints(&content, &self.config);
+        let Some(object) = objects.iter().find(|o| o.file_id == file_id) else {
+            return serde_json::json!({ "error": format!("No GameObject with fileID {} in {}", file_id, file), "is_error": true });
+        };
+        let mut store = match annotations::AnnotationStore::load(Path::new(&root)) {
+            Ok(s) => s,
+            Err(e) => return serde_json::json!({ "error": e, "is_error": true }),
+        };
+        let annotation = store.add(&self.relative_to_project(&file), object, note, labels.unwrap_or_default());
+        if let Err(e) = store.save(Path::new(&root)) {
+            return serde_json::json!({ "error": e, "is_error": true });
+        }
+        serde_json::to_value(annotation).unwrap_or_default()
+    }
+
+    /// Annotations of a scene or prefab, optionally for one GameObject and/or with a label.
+    /// Annotations are re-anchored first, so objects whose fileIDs changed are still found
+    /// (`remapped_from` set) and vanished ones are flagged `orphaned`.
+    #[napi]
+    pub fn get_annotations(&mut self, file: String, file_id: Option<String>, label: Option<String>) -> Vec<ObjectAnnotation> {
+        let _call = crate::telemetry::call("get_annotations");
+        let Ok(content) = common::read_unity_file(&file) else {
+            return Vec::new();
+        };
+        self.resolved_annotations(&file, &content, file_id.as_deref(), label.as_deref())
+    }
+
+    /// Delete an annotation by ID. Returns false when it does not exist.
+    #[napi]
+    pub fn remove_annotation(&mut self, file: String, id: u32) -> bool {
+        let _call = crate::telemetry::call("remove_annotation");
+        self.ensure_guid_resolver(&file);
+        let Some(root) = self.project_root.clone() else {
+            return false;
+        };
+        let Ok(mut store) = annotations::AnnotationStore::load(Path::new(&root)) else {
+            return false;
+        };
+        store.remove(id) && store.save(Path::new(&root)).is_ok()
+    }
+
+    /// Parse the layer collision matrix of DynamicsManager.asset (or Physics2DSettings.asset).
+    /// Layer names come from TagManager.asset in the same folder when present.
+    #[napi]
+    pub fn read_collision_matrix(&self, file: String) -> Option<CollisionMatrix> {
+        let _call = crate::telemetry::call("read_collision_matrix");
+        let content = common::read_unity_file(&file).ok()?;
+        let layer_names = sibling_layer_names(&file);
+        settings::build_collision_matrix(&file, &content, &layer_names)
+    }
+
+    /// Whether two physics layers (by name or index) collide according to the settings file.
+    /// Returns None when the file or either layer cannot be resolved.
+    #[napi]
+    pub fn layers_collide(&self, file: String, layer_a: String, layer_b: String) -> Option<bool> {
+        let _call = crate::telemetry::call("layers_collide");
+        let content = common::read_unity_file(&file).ok()?;
+        let masks = settings::parse_collision_masks(&content)?;
+        let layer_names = sibling_layer_names(&file);
+        let a = settings::resolve_layer(&layer_a, &layer_names)?;
+        let b = settings::resolve_layer(&layer_b, &layer_names)?;
+        Some(settings::layers_collide(&masks, a, b))
+    }
+
+    /// Parse InputManager.asset axes into typed entries
+    #[napi]
+    pub fn read_input_axes(&self, file: String) -> Vec<InputAxis> {
+        let _call = crate::telemetry::call("read_input_axes");
+        match common::read_unity_file(&file) {
+            Ok(content) => settings::parse_input_axes(&content),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// List every Camera in a scene or prefab with projection, clipping and culling mask decoded
+    #[napi]
+    pub fn list_cameras(&mut self, file: String) -> Vec<CameraInfo> {
+        let _call = crate::telemetry::call("list_cameras");
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(_) => return Vec::new(),
+        };
+        self.ensure_guid_resolver(&file);
+        rendering::list_cameras(&content, &self.project_layer_names())
+    }
+
+    /// List every Light in a scene or prefab with type, intensity, shadows and bake mode
+    #[napi]
+    pub fn list_lights(&mut self, file: String) -> Vec<LightInfo> {
+        let _call = crate::telemetry::call("list_lights");
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(_) => return Vec::new(),
+        };
+        self.ensure_guid_resolver(&file);
+        rendering::list_lights(&content, &self.project_layer_names())
+    }
+
+    /// Audit Canvases in a scene/prefab, or in every scene and prefab when `path` is a project directory.
+    /// Flags duplicate sort orders and world-space canvases without an event camera.
+    #[napi]
+    pub fn audit_canvases(&mut self, path: String) -> CanvasAudit {
+        let _call = crate::telemetry::call("audit_canvases");
+        let files = if Path::new(&path).is_dir() {
+            crate::walker::walk_project_files(path.clone(), vec![".unity".to_string(), ".prefab".to_string()], None, None)
+        } else {
+            vec![path.clone()]
+        };
+
+        let mut canvases = Vec::new();
+        let mut files_scanned = 0u32;
+        for file in &files {
+            let content = match common::read_unity_file(file) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            files_scanned += 1;
+            canvases.extend(canvas::audit_canvases(file, &content, &self.config));
+        }
+
+        let issue_count = canvases.iter().map(|c| c.issues.len() as u32).sum();
+        CanvasAudit {
+            path,
+            files_scanned,
+            canvases,
+            issue_count,
+        }
+    }
+
+    /// Audit Rigidbody/Collider setup of a scene or prefab. Uses the project's
+    /// DynamicsManager/Physics2DSettings collision matrices and layer names when available.
+    #[napi]
+    pub fn audit_physics(&mut self, file: String) -> PhysicsAudit {
+        let _call = crate::telemetry::call("audit_physics");
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(_) => return PhysicsAudit { file, objects: Vec::new(), issue_count: 0 },
+        };
+        self.ensure_guid_resolver(&file);
+
+        let settings_dir = self.project_root.as_ref().map(|root| Path::new(root).join("ProjectSettings"));
+        let read_masks = |name: &str| {
+            let path = settings_dir.as_ref()?.join(name);
+            settings::parse_collision_masks(&common::read_unity_file(path).ok()?)
+        };
+        let masks_3d = read_masks("DynamicsManager.asset");
+        let masks_2d = read_masks("Physics2DSettings.asset");
+        let layer_names = self.project_layer_names();
+
+        let objects = physics::audit_physics(&content, &self.config, &physics::PhysicsSettings {
+            layer_names: &layer_names,
+            masks_3d: masks_3d.as_deref(),
+            masks_2d: masks_2d.as_deref(),
+        });
+        let issue_count = objects.iter().map(|o| o.issues.len() as u32).sum();
+        PhysicsAudit { file, objects, issue_count }
+    }
+
+    /// List NavMeshAgents, NavMeshObstacles and OffMeshLinks of a scene with their key
+    /// parameters, cross-checked against the scene's NavMeshSettings.
+    #[napi]
+    pub fn analyze_navigation(&self, file: String) -> Option<NavMeshReport> {
+        let _call = crate::telemetry::call("analyze_navigation");
+        let content = common::read_unity_file(&file).ok()?;
+        Some(navigation::analyze_navigation(&file, &content))
+    }
+
+    /// Assemble the most relevant context around one GameObject, trimmed to a token budget
+    /// (default 4000, estimated as chars / 4): its full detail, parent chain, children,
+    /// serialized fields of its scripts, and the objects and assets its components reference.
+    /// Items are ranked by relevance; whatever does not fit is listed under `omitted`.
+    #[napi]
+    pub fn build_context_pack(&mut self, file: String, focus_object: String, budget_tokens: Option<u32>) -> serde_json::Value {
+        let _call = crate::telemetry::call("build_context_pack");
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => return serde_json::json!({ "error": format!("Failed to read {}: {}", file, e), "is_error": true }),
+        };
+        self.ensure_guid_resolver(&file);
+        let budget_tokens = budget_tokens.unwrap_or(4000);
+
+        let gameobjects = UnityYamlParser::extract_gameobjects_with_config(&content, &self.config);
+        let focus = match gameobjects.iter().find(|o| o.file_id == focus_object) {
+            Some(obj) => obj,
+            None => {
+                let named: Vec<&GameObject> = gameobjects.iter().filter(|o| o.name == focus_object).collect();
+                match named.as_slice() {
+                    [obj] => *obj,
+                    [] => return serde_json::json!({ "error": format!("GameObject \"{}\" not found", focus_object), "is_error": true }),
+                    _ => {
+                        let ids: Vec<&str> = named.iter().map(|o| o.file_id.as_str()).collect();
+                        return serde_json::json!({
+                            "error": format!("Multiple GameObjects named \"{}\" found (fileIDs: {}). Use numeric fileID.", focus_object, ids.join(", ")),
+                            "is_error": true
+                        });
+                    }
+                }
+            }
+        };
+
+        let index = BlockIndex::new(&content);
+        let blocks = UnityYamlParser::parse_all_blocks(&content);
+        let parents = gameobject::build_parent_map(&blocks, &self.config);
+        let names: HashMap<&str, &str> = gameobjects.iter().map(|o| (o.file_id.as_str(), o.name.as_str())).collect();
+        let summary_of = |go_id: &str| -> serde_json::Value {
+            let components: Vec<String> = component::extract_components_indexed(&index, go_id, &self.guid_cache, &self.config)
+                .into_iter()
+                .map(|c| c.script_name.unwrap_or(c.type_name))
+                .collect();
+            serde_json::json!({
+                "file_id": go_id,
+                "name": names.get(go_id).copied().unwrap_or(""),
+                "components": components,
+            })
+        };
+
+        let mut items = Vec::new();
+
+        // The focus object itself; fall back to a property-less view when it alone busts the budget
+        let components = component::extract_components_indexed(&index, &focus.file_id, &self.guid_cache, &self.config);
+        let detail = self.extract_gameobject_details_indexed(&file, &index, focus, &components);
+        let full = serde_json::to_value(self.build_detail_output(&detail, true, true)).unwrap_or_default();
+        let target = if crate::indexer::tokenizer::estimate_tokens(&full.to_string()) <= budget_tokens {
+            full
+        } else {
+            serde_json::to_value(self.build_detail_output(&detail, true, false)).unwrap_or_default()
+        };
+        items.push(context::ContextItem { kind: "target", relevance: 100, value: target });
+
+        // Parent chain, nearest first
+        let mut current = parents.get(&focus.file_id);
+        let mut distance = 0u32;
+        while let Some(parent) = current {
+            items.push(context::ContextItem { kind: "parent", relevance: 90u32.saturating_sub(distance * 5).max(40), value: summary_of(parent) });
+            distance += 1;
+            if distance > 64 {
+                break;
+            }
+            current = parents.get(parent);
+        }
+
+        // Children and grandchildren
+        for obj in &gameobjects {
+            let Some(parent) = parents.get(&obj.file_id) else { continue };
+            if *parent == focus.file_id {
+                items.push(context::ContextItem { kind: "child", relevance: 75, value: summary_of(&obj.file_id) });
+            } else if parents.get(parent).is_some_and(|p| *p == focus.file_id) {
+                items.push(context::ContextItem { kind: "grandchild", relevance: 45, value: summary_of(&obj.file_id) });
+            }
+        }
+
+        // Serialized fields of the focus object's scripts
+        if let Some(root) = self.project_root.clone() {
+            for comp in &components {
+                let Some(script) = comp.script_path.as_ref().filter(|p| p.ends_with(".cs")) else { continue };
+                let types: Vec<serde_json::Value> = crate::csharp::extract_serialized_fields(Path::new(&root).join(script).to_string_lossy().to_string())
+                    .into_iter()
+                    .map(|t| serde_json::json!({
+                        "name": t.name,
+                        "base_class": t.base_class,
+                        "fields": t.fields.iter().map(|f| serde_json::json!({ "name": f.name, "type": f.type_name })).collect::<Vec<_>>(),
+                    }))
+                    .collect();
+                if !types.is_empty() {
+                    items.push(context::ContextItem { kind: "script", relevance: 85, value: serde_json::json!({ "path": script, "types": types }) });
+                }
+            }
+        }
+
+        // Objects and assets the focus object's components point at
+        let mut seen = std::collections::HashSet::new();
+        for comp in &components {
+            let Some((_, block)) = index.get(&comp.file_id) else { continue };
+            for reference in context::component_references(block) {
+                let key = (reference.file_id.clone(), reference.guid.clone());
+                if !seen.insert(key) {
+                    continue;
+                }
+                match &reference.guid {
+                    None => {
+                        let Some((class_id, target_block)) = index.get(&reference.file_id) else { continue };
+                        let (go_id, go_name) = if class_id == self.config.gameobject_class_id {
+                            (reference.file_id.clone(), names.get(reference.file_id.as_str()).copied().unwrap_or("").to_string())
+                        } else {
+                            rendering::owner_game_object(&index, target_block)
+                        };
+                        let type_name = self.config.class_names.get(&class_id)
+                            .map(String::as_str)
+                            .unwrap_or_else(|| class_id_to_name(class_id));
+                        items.push(context::ContextItem { kind: "reference", relevance: 70, value: serde_json::json!({
+                            "from": comp.script_name.as_ref().unwrap_or(&comp.type_name),
+                            "property": reference.property,
+                            "file_id": reference.file_id,
+                            "type": type_name,
+                            "game_object_id": go_id,
+                            "name": go_name,
+                        }) });
+                    }
+                    Some(guid) => {
+                        let path = self.guid_cache.get(guid).cloned();
+                        let is_prefab = path.as_deref().is_some_and(|p| p.ends_with(".prefab"));
+                        let name = path.as_deref()
+                            .and_then(|p| Path::new(p).file_stem())
+                            .map(|s| s.to_string_lossy().to_string());
+                        items.push(context::ContextItem {
+                            kind: if is_prefab { "prefab" } else { "asset" },
+                            relevance: if is_prefab { 65 } else { 55 },
+                            value: serde_json::json!({
+                                "from": comp.script_name.as_ref().unwrap_or(&comp.type_name),
+                                "property": reference.property,
+                                "guid": guid,
+                                "path": path,
+                                "name": name,
+                            }),
+                        });
+                    }
+                }
+            }
+        }
+
+        let (sections, omitted, used_tokens) = context::assemble(items, budget_tokens);
+        serde_json::json!({
+            "file": file,
+            "focus": { "file_id": focus.file_id, "name": focus.name },
+            "budget_tokens": budget_tokens,
+            "used_tokens": used_tokens,
+            "sections": sections,
+            "omitted": omitted,
+        })
+    }
+
+    /// UnityEvent fields with persistent listeners in a scene or prefab, with targets resolved to
+    /// GameObject/script names and broken listeners flagged (missing object, asset or method).
+    #[napi]
+    pub fn list_unity_events(&mut self, file: String) -> Vec<UnityEventInfo> {
+        let _call = crate::telemetry::call("list_unity_events");
+        let content = match common::read_unity_file(&file) {
+            Ok(c) => c,
+            Err(_) => return Vec::new(),
+        };
+        self.ensure_guid_resolver(&file);
+        let mut method_exists = self.script_method_lookup();
+        events::list_events(&file, &content, &self.guid_cache, &self.config, &mut method_exists)
+    }
+
+    /// Project-wide (or single-file) report of UnityEvent listeners whose target object,
+    /// target asset or method no longer exists.
+    #[napi]
+    pub fn audit_unity_events(&mut self, path: String) -> UnityEventReport {
+        let _call = crate::telemetry::call("audit_unity_events");
+        let files = if Path::new(&path).is_dir() {
+            if self.project_root.as_deref() != Some(path.as_str()) {
+                self.set_project_root(path.clone(), None);
+            }
+            crate::walker::walk_project_files(path.clone(), vec![".unity".to_string(), ".prefab".to_string()], None, None)
+        } else {
+            self.ensure_guid_resolver(&path);
+            vec![path.clone()]
+        };
+
+        let mut method_exists = self.script_method_lookup();
+        let mut report = UnityEventReport { path, files_scanned: 0, event_count: 0, listener_count: 0, broken: Vec::new() };
+        for file in &files {
+            let Ok(content) = common::read_unity_file(file) else { continue };
+            report.files_scanned += 1;
+            let relative = self.relative_to_project(file);
+            for mut event in events::list_events(&relative, &content, &self.guid_cache, &self.config, &mut method_exists) {
+                report.event_count += 1;
+                report.listener_count += event.listeners.len() as u32;
+                event.listeners.retain(|l| l.issue.is_some());
+                if !event.listeners.is_empty() {
+                    report.broken.push(event);
+                }
+            }
+        }
+        report
+    }
+
+    /// GlobalObjectId strings and SceneAsset references serialized in the components of a
+    /// scene/prefab, or of every one under a project directory. Targets resolve to their asset
+    /// path; references whose asset or target scene object no longer exists carry an `issue`.
+    #[napi]
+    pub fn find_cross_scene_references(&mut self, path: String) -> Vec<CrossSceneReference> {
+        let _call = telemetry::call("find_cross_scene_references");
+        let files = if Path::new(&path).is_dir() {
+            if self.project_root.as_deref() != Some(path.as_str()) {
+                self.set_project_root(path.clone(), None);
+            }
+            crate::walker::walk_project_files(path, vec![".unity".to_string(), ".prefab".to_string()], None, None)
+        } else {
+            self.ensure_guid_resolver(&path);
+            vec![path]
+        };
+
+        let mut object_exists = self.scene_object_lookup();
+        let mut references = Vec::new();
+        for file in &files {
+            let Ok(content) = common::read_unity_file(file) else { continue };
+            let relative = self.relative_to_project(file);
+            references.extend(cross_scene::find_references(&relative, &content, &self.guid_cache, &self.config, &mut object_exists));
+        }
+        references
+    }
+
+    /// Split a `GlobalObjectId_V1-{type}-{assetGUID}-{objectId}-{prefabId}` string into its parts.
+    #[napi]
+    pub fn parse_global_object_id(&self, value: String) -> Option<GlobalObjectIdParts> {
+        cross_scene::parse_global_object_id(&value)
+    }
+
+    /// Modernization report for a scene/prefab, a C# file, or a whole project directory: legacy
+    /// built-in components (GUIText, GUITexture, legacy particles, NetworkView), MonoBehaviours
+    /// backed by UnityScript/Boo scripts, and deprecated C# APIs (WWW, Application.LoadLevel, ...).
+    #[napi]
+    pub fn detect_deprecated_usage(&mut self, path: String) -> DeprecationReport {
+        let _call = telemetry::call("detect_deprecated_usage");
+        let (files, scripts) = if Path::new(&path).is_dir() {
+            if self.project_root.as_deref() != Some(path.as_str()) {
+                self.set_project_root(path.clone(), None);
+            }
+            (
+                crate::walker::walk_project_files(path.clone(), vec![".unity".to_string(), ".prefab".to_string()], None, None),
+                crate::walker::walk_project_files(path.clone(), vec![".cs".to_string()], None, None),
+            )
+        } else if path.ends_with(".cs") {
+            (Vec::new(), vec![path.clone()])
+        } else {
+            self.ensure_guid_resolver(&path);
+            (vec![path.clone()], Vec::new())
+        };
+
+        let mut report = DeprecationReport { path, files_scanned: 0, scripts_scanned: 0, summary: serde_json::json!({}), usages: Vec::new() };
+        for file in &files {
+            let Ok(content) = common::read_unity_file(file) else { continue };
+            report.files_scanned += 1;
+            let relative = self.relative_to_project(file);
+            report.usages.extend(deprecated::scene_usages(&relative, &content, &self.guid_cache, &self.config));
+        }
+        for file in &scripts {
+            let Ok(source) = crate::safety::read_to_string(file) else { continue };
+            report.scripts_scanned += 1;
+            report.usages.extend(deprecated::code_usages(&self.relative_to_project(file), &source));
+        }
+
+        let mut summary: std::collections::BTreeMap<&str, u32> = std::collections::BTreeMap::new();
+        for usage in &report.usages {
+            *summary.entry(usage.name.as_str()).or_insert(0) += 1;
+        }
+        report.summary = serde_json::json!(summary);
+        report
+    }
+
+    /// Check the curve bindings of an AnimatorController (all its clips) or a single clip against
+    /// the hierarchy it animates: path segments must name children, the bound component must exist
+    /// and carry the property. Without `game_object_id`, every Animator/Animation in `file` that uses
+    /// the asset is checked.
+    #[napi]
+    pub fn validate_animation_bindings(
+        &mut self,
+        animation_path: String,
+        file: String,
+        game_object_id: Option<String>,
+    ) -> Vec<AnimationBindingReport> {
+        let _call = crate::telemetry::call("validate_animation_bindings");
+        let (Ok(animation), Ok(content)) = (common::read_unity_file(&animation_path), common::read_unity_file(&file)) else {
+            return Vec::new();
+        };
+        self.ensure_guid_resolver(&file);
+
+        let is_controller = !animation.contains("AnimationClip:");
+        let clip_paths: Vec<String> = if is_controller {
+            animation::controller_clip_guids(&animation).iter()
+                .filter_map(|guid| self.guid_cache.get(guid))
+                .filter_map(|rel| Some(Path::new(self.project_root.as_deref()?).join(rel).to_string_lossy().to_string()))
+                .collect()
+        } else {
+            vec![animation_path.clone()]
+        };
+        let clips: Vec<(String, Vec<animation::CurveBinding>)> = clip_paths.iter()
+            .filter_map(|path| {
+                let clip = common::read_unity_file(path).ok()?;
+                Some((self.relative_to_project(path), animation::parse_clip_bindings(&clip)))
+            })
+            .collect();
+
+        let index = BlockIndex::new(&content);
+        let targets: Vec<String> = match game_object_id {
+            Some(id) => vec![id],
+            None => {
+                let asset_guid = common::read_unity_file(format!("{}.meta", animation_path)).ok()
+                    .and_then(|meta| extract_guid_from_meta(&meta));
+                let Some(asset_guid) = asset_guid else { return Vec::new() };
+                UnityYamlParser::parse_all_blocks(&content).iter()
+                    .filter(|(class_id, _, body)| match class_id {
+                        95 => rendering::field(body, "m_Controller").is_some_and(|c| c.contains(asset_guid.as_str())),
+                        111 => body.contains(asset_guid.as_str()),
+                        _ => false,
+                    })
+                    .map(|(_, _, body)| rendering::owner_game_object(&index, body).0)
+                    .collect()
+            }
+        };
+
+        let class_names = &self.config.class_names;
+        let class_name = |class_id: u32| class_names.get(&class_id)
+            .map(String::as_str)
+            .unwrap_or_else(|| class_id_to_name(class_id))
+            .to_string();
+        let relative_file = self.relative_to_project(&file);
+        targets.into_iter()
+            .filter_map(|go_id| {
+                let go_block = index.get_by_class_and_id(self.config.gameobject_class_id, &go_id)?;
+                let mut report = AnimationBindingReport {
+                    animation_path: self.relative_to_project(&animation_path),
+                    file: relative_file.clone(),
+                    game_object_name: rendering::field(go_block, "m_Name").unwrap_or("").to_string(),
+                    game_object_id: go_id.clone(),
+                    clips_checked: clips.len() as u32,
+                    bindings_checked: 0,
+                    issues: Vec::new(),
+                };
+                for (clip, bindings) in &clips {
+                    report.bindings_checked += bindings.len() as u32;
+                    report.issues.extend(animation::validate_bindings(clip, bindings, &index, &go_id, &self.config, &class_name));
+                }
+                Some(report)
+            })
+            .collect()
+    }
+
+    /// Move a GameObject subtree (components, children, nested prefab instances) from one
+    /// scene/prefab into another, under `parent_id` (a GameObject or Transform fileID in the
+    /// destination) or at the scene root. Moved blocks get fresh fileIDs; local references that
+    /// cannot cross files are reset to 0 and reported. With `dry_run` (default: true) nothing
+    /// is written and `diff` previews both files' changes. Both files keep their line endings;
+    /// when the source cannot be written, the destination is restored.
+    #[napi]
+    pub fn move_gameobject_between_files(
+        &self,
+        src_file: String,
+        go_id: String,
+        dst_file: String,
+        parent_id: Option<String>,
+        dry_run: Option<bool>,
+    ) -> MoveGameObjectResult {
+        let _call = crate::telemetry::call("move_gameobject_between_files");
+        let dry_run = dry_run.unwrap_or(true);
+        let mut result = MoveGameObjectResult {
+            success: false,
+            error: None,
+            src_file: src_file.clone(),
+            dst_file: dst_file.clone(),
+            dry_run,
+            moved_block_count: 0,
+            new_file_id: None,
+            file_id_map: serde_json::Value::Object(serde_json::Map::new()),
+            broken_references: Vec::new(),
+            diff: None,
+        };
+        let same_file = match (fs::canonicalize(&src_file), fs::canonicalize(&dst_file)) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => src_file == dst_file,
+        };
+        if same_file {
+            result.error = Some("Source and destination are the same file".to_string());
+            return result;
+        }
+        let (src_raw, dst_raw) = match (common::read_raw_unity_file(&src_file), common::read_raw_unity_file(&dst_file)) {
+            (Ok(s), Ok(d)) => (s, d),
+            (Err(e), _) | (_, Err(e)) => {
+                result.error = Some(e.to_string());
+                return result;
+            }
+        };
+        let src_content = common::normalize_line_endings(src_raw.clone());
+        let dst_content = common::normalize_line_endings(dst_raw.clone());
+
+        let dst_is_prefab = dst_file.ends_with(".prefab");
+        let transplant = match transplant::move_gameobject(
+            &src_content, &go_id, &dst_content, parent_id.as_deref(), dst_is_prefab, &self.config,
+        ) {
+            Ok(t) => t,
+            Err(e) => {
+                result.error = Some(e);
+                return result;
+            }
+        };
+
+        let new_dst = common::match_line_endings(&dst_raw, transplant.dst_content);
+        let new_src = common::match_line_endings(&src_raw, transplant.src_content);
+        if !dry_run {
+            if let Err(e) = common::atomic::write_atomic(&dst_file, &new_dst) {
+                result.error = Some(format!("Failed to write {}: {}", dst_file, e));
+                return result;
+            }
+            // Without the source write the subtree would exist in both files: put the
+            // destination back, and say so when even that fails
+            if let Err(e) = common::atomic::write_atomic(&src_file, &new_src) {
+                result.error = Some(match common::atomic::write_atomic(&dst_file, &dst_raw) {
+                    Ok(()) => format!("Failed to write {}: {}; {} was restored", src_file, e, dst_file),
+                    Err(restore) => format!(
+                        "Failed to write {}: {}; restoring {} also failed ({}), so the subtree is now in both files",
+                        src_file, e, dst_file, restore,
+                    ),
+                });
+                return result;
+            }
+        }
+
+        result.success = true;
+        result.moved_block_count = transplant.file_id_map.len() as u32;
+        result.new_file_id = Some(transplant.new_file_id);
+        result.file_id_map = serde_json::Value::Object(
+            transplant.file_id_map.into_iter()
+                .map(|(old, new)| (old, serde_json::Value::String(new)))
+                .collect(),
+        );
+        result.broken_references = transplant.broken_references;
+        if dry_run {
+            result.diff = Some(common::diff::unified_diff(&dst_file, &dst_raw, &new_dst)
+                + &common::diff::unified_diff(&src_file, &src_raw, &new_src));
+        }
+        result
+    }
+
+    /// Generate a new `.unity` or `.prefab` file from a declarative spec: a hierarchy of named
+    /// objects with their components and property values (see `scaffold` for the format).
+    /// Script paths resolve through the project's GUID cache. An existing file is only
+    /// replaced with `overwrite`; with `dry_run` (default: true) nothing is written and the
+    /// YAML is returned, with a diff against any file it would replace.
+    #[napi]
+    pub fn scaffold_file(
+        &mut self,
+        output_path: String,
+        spec: serde_json::Value,
+        overwrite: Option<bool>,
+        dry_run: Option<bool>,
+    ) -> ScaffoldResult {
+        let _call = telemetry::call("scaffold_file");
+        let dry_run = dry_run.unwrap_or(true);
+        let mut result = ScaffoldResult {
+            success: false,
+            error: None,
+            file: output_path.clone(),
+            dry_run,
+            object_count: 0,
+            block_count: 0,
+            file_ids: serde_json::Value::Object(serde_json::Map::new()),
+            content: None,
+            diff: None,
+        };
+        let is_prefab = if output_path.ends_with(".prefab") {
+            true
+        } else if output_path.ends_with(".unity") {
+            false
+        } else {
+            result.error = Some("Output path must end in .unity or .prefab".to_string());
+            return result;
+        };
+        if !overwrite.unwrap_or(false) && Path::new(&output_path).exists() {
+            result.error = Some(format!("{} already exists; pass overwrite to replace it", output_path));
+            return result;
+        }
+        let mut spec: scaffold::ScaffoldSpec = match serde_json::from_value(spec) {
+            Ok(s) => s,
+            Err(e) => {
+                result.error = Some(format!("Invalid scaffold spec: {}", e));
+                return result;
+            }
+        };
+        self.ensure_guid_resolver(&output_path);
+        let path_to_guid: HashMap<String, String> = self.guid_cache.iter()
+            .map(|(guid, path)| (path.clone(), guid.clone()))
+            .collect();
+        let generated = match scaffold::resolve_scripts(&mut spec, &path_to_guid)
+            .and_then(|_| scaffold::generate(&spec, is_prefab))
+        {
+            Ok(g) => g,
+            Err(e) => {
+                result.error = Some(e);
+                return result;
+            }
+        };
+
+        if !dry_run {
+            let written = Path::new(&output_path).parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .map_or(Ok(()), crate::safety::create_dir_all)
+                .and_then(|_| common::atomic::write_atomic(&output_path, &generated.content));
+            if let Err(e) = written {
+                result.error = Some(format!("Failed to write: {}", e));
+                return result;
+            }
+        }
+
+        result.success = true;
+        result.object_count = generated.file_ids.len() as u32;
+        result.block_count = generated.block_count;
+        result.file_ids = serde_json::Value::Object(
+            generated.file_ids.into_iter()
+                .map(|(path, id)| (path, serde_json::Value::String(id)))
+                .collect(),
+        );
+        if dry_run {
+            let existing = common::read_raw_unity_file(&output_path).unwrap_or_default();
+            result.diff = Some(common::diff::unified_diff(&output_path, &existing, &generated.content));
+            result.content = Some(generated.content);
+        }
+        result
+    }
+
+    /// Set tag, layer, active state and/or name on every GameObject of a scene or prefab that
+    /// matches `selector`, in one pass that rewrites only the affected value text. The result
+    /// reports each matched object's changes; with `dry_run` (default: true) nothing is written
+    /// and `diff` previews the exact change.
+    #[napi]
+    pub fn bulk_update(
+        &mut self,
+        file: String,
+        selector: GameObjectSelector,
+        changes: GameObjectChanges,
+        dry_run: Option<bool>,
+    ) -> BulkUpdateResult {
+        let _call = telemetry::call("bulk_update");
+        let dry_run = dry_run.unwrap_or(true);
+        let mut result = BulkUpdateResult {
+            success: false,
+            error: None,
+            file: file.clone(),
+            dry_run,
+            matched_count: 0,
+            changed_count: 0,
+            objects: Vec::new(),
+            warnings: Vec::new(),
+            diff: None,
+        };
+        // Raw content, so CRLF files are written back with their line endings
+        let content = match common::read_raw_unity_file(&file) {
+            Ok(c) => c,
+            Err(e) => {
+                result.error = Some(e.to_string());
+                return result;
+            }
+        };
+        self.ensure_guid_resolver(&file);
+
+        let (new_content, objects) = match bulk::bulk_update(&content, &selector, &changes, &self.guid_cache, &self.config) {
+            Ok(r) => r,
+            Err(e) => {
+                result.error = Some(e);
+                return result;
+            }
+        };
+        if changes.tag.is_some() || changes.layer.is_some() {
+            let tag = changes.tag.as_deref().unwrap_or("Untagged");
+            result.warnings = self.tag_layer_warnings(tag, changes.layer.unwrap_or(0)).unwrap_or_default();
+        }
+        result.matched_count = objects.len() as u32;
+        result.changed_count = objects.iter().filter(|o| !o.changes.is_empty()).count() as u32;
+        result.objects = objects;
+
+        if !dry_run && new_content != content {
+            if let Err(e) = common::atomic::write_atomic(&file, &new_content) {
+                result.error = Some(format!("Failed to write: {}", e));
+                return result;
+            }
+        }
+        if dry_run {
+            result.diff = Some(common::diff::unified_diff(&file, &content, &new_content));
+        }
+        result.success = true;
+        result
+    }
+
+    /// Catalog every prefab in the project: root object, GameObject count, component type
+    /// histogram, script types, nested prefab references and serialized size. Prefabs are
+    /// read in parallel; entries are sorted by path.
+    #[napi]
+    pub fn catalog_prefabs(&mut self, project_root: String) -> PrefabCatalog {
+        let _call = telemetry::call("catalog_prefabs");
+        if self.project_root.as_deref() != Some(project_root.as_str()) {
+            self.set_project_root(project_root.clone(), None);
+        }
+        catalog::catalog(&project_root, &self.guid_cache, &self.config)
+    }
+
+    /// Cross-check scripts' component expectations against scenes and prefabs. Expectations come
+    /// from [RequireComponent] (errors) and GetComponent/TryGetComponent calls on the script's own
+    /// GameObject (warnings); types the script adds itself via AddComponent are not expected.
+    /// An optional `budget` caps the scripts, scenes and prefabs read.
+    #[napi]
+    pub fn analyze_component_usage(&mut self, project_root: String, budget: Option<ResourceBudget>) -> ComponentUsageReport {
+        let _call = crate::telemetry::call("analyze_component_usage");
+        let meter = Meter::new(budget.as_ref());
+        if self.project_root.as_deref() != Some(project_root.as_str()) {
+            self.set_project_root(project_root.clone(), None);
+        }
+        let guid_by_path: HashMap<&str, &str> = self.guid_cache.iter().map(|(g, p)| (p.as_str(), g.as_str())).collect();
+
+        let mut script_types: std::collections::HashSet<String> = std::collections::HashSet::new();
+        let mut scripts: Vec<ScriptComponentUsage> = Vec::new();
+        for cs in crate::walker::walk_project_files(project_root.clone(), vec![".cs".to_string()], None, None) {
+            if !meter.admit_file(&cs) {
+                break;
+            }
+            let Ok(content) = common::read_unity_file(&cs) else { continue };
+            let relative = self.relative_to_project(&cs);
+            let summary = crate::csharp::summary::summarize_source(&relative, &content);
+            script_types.extend(summary.types.into_iter().map(|t| t.name));
+
+            let (expects, adds) = crate::csharp::summary::component_expectations(&content);
+            if expects.is_empty() && adds.is_empty() {
+                continue;
+            }
+            scripts.push(ScriptComponentUsage {
+                script_guid: guid_by_path.get(relative.as_str()).map(|g| g.to_string()),
+                script_path: relative,
+                expects: expects.into_iter().map(|(type_name, source)| ComponentExpectation {
+                    required: source == "RequireComponent",
+                    type_name,
+                    source,
+                }).collect(),
+                adds,
+            });
+        }
+        let by_guid: HashMap<String, ScriptComponentUsage> = scripts.iter()
+            .filter(|s| !s.expects.is_empty())
+            .filter_map(|s| Some((s.script_guid.clone()?, s.clone())))
+            .collect();
+
+        // Gather objects first so every component type seen in the project counts as checkable
+        let mut files: Vec<(String, Vec<usage::ObjectComponents>)> = Vec::new();
+        let mut seen_types: std::collections::HashSet<String> = std::collections::HashSet::new();
+        if !by_guid.is_empty() {
+            for file in crate::walker::walk_project_files(project_root.clone(), vec![".unity".to_string(), ".prefab".to_string()], None, None) {
+                if !meter.admit_file(&file) {
+                    break;
+                }
+                let Ok(content) = common::read_unity_file(&file) else { continue };
+                let index = BlockIndex::new(&content);
+                let objects: Vec<usage::ObjectComponents> = UnityYamlParser::extract_gameobjects_with_config(&content, &self.config)
+                    .into_iter()
+                    .map(|go| {
+                        let components = component::extract_components_indexed(&index, &go.file_id, &self.guid_cache, &self.config);
+                        (go.file_id, go.name, components)
+                    })
+                    .collect();
+                for (_, _, components) in &objects {
+                    seen_types.extend(components.iter().map(|c| c.type_name.clone()));
+                }
+                files.push((self.relative_to_project(&file), objects));
+            }
+        }
+
+        let known = |ty: &str| script_types.contains(ty) || seen_types.contains(ty);
+        let issues = files.iter()
+            .flat_map(|(file, objects)| usage::check_objects(file, objects, &by_guid, &known))
+            .collect();
+
+        ComponentUsageReport {
+            project_root,
+            scripts,
+            files_checked: files.len() as u32,
+            issues,
+            budget_exhausted: meter.exhausted(),
+        }
+    }
+
+    /// Every MonoBehaviour script of the project's type registry with the number of scenes and
+    /// prefabs instantiating it; never-used scripts are flagged `unused` and listed first.
+    /// Abstract classes and classes not named after their file are left out, as Unity cannot
+    /// attach them. An optional `budget` caps the scenes and prefabs read.
+    #[napi]
+    pub fn script_coverage(&self, project_root: String, budget: Option<ResourceBudget>) -> ScriptCoverageReport {
+        let _call = telemetry::call("script_coverage");
+        let meter = Meter::new(budget.as_ref());
+        coverage::coverage(&project_root, &self.config, &meter)
+    }
+
+    /// Infer the serialized schema of a component type (built-in type name such as "Rigidbody",
+    /// or a MonoBehaviour script name) from every occurrence in the project's scenes, prefabs
+    /// and assets: property paths, observed value types, numeric ranges and nullability.
+    /// An optional `budget` caps the files read.
+    #[napi]
+    pub fn infer_component_schema(&mut self, project_root: String, type_name: String, budget: Option<ResourceBudget>) -> ComponentSchema {
+        let _call = crate::telemetry::call("infer_component_schema");
+        let mut builder = schema::SchemaBuilder::default();
+        let meter = Meter::new(budget.as_ref());
+        let (script_path, files) = self.for_each_component_block(&project_root, &type_name, &meter, |block| builder.add_sample(block));
+        ComponentSchema {
+            type_name,
+            script_path,
+            samples: builder.samples,
+            files,
+            properties: builder.finish(),
+            budget_exhausted: meter.exhausted(),
+        }
+    }
+
+    /// Distribution of one property of a component type (built-in type name such as "Light",
+    /// or a MonoBehaviour script name) across the project's scenes, prefabs and assets, e.g. all
+    /// Light intensities. `property_path` is dotted (`m_Color.r`, `settings.speed`), matched
+    /// case-insensitively with or without `m_`. Numbers are binned into `buckets` ranges
+    /// (default 10) once there are more distinct values than that; other values are counted.
+    /// Built-in fields renamed across Unity versions (m_Drag, now m_LinearDamping) are found under
+    /// either name, listed in `aliases_applied`. An optional `budget` caps the files read.
+    #[napi]
+    pub fn property_histogram(&mut self, project_root: String, component_type: String, property_path: String, buckets: Option<u32>, budget: Option<ResourceBudget>) -> PropertyHistogram {
+        let _call = telemetry::call("property_histogram");
+        let mut builder = histogram::HistogramBuilder::default();
+        let meter = Meter::new(budget.as_ref());
+        let (_, files) = self.for_each_component_block(&project_root, &component_type, &meter, |block| builder.add_sample(block, &property_path));
+        let (samples, missing) = (builder.samples, builder.missing);
+        let aliases_applied = std::mem::take(&mut builder.aliases).into_iter().collect();
+        let summary = builder.finish(buckets.unwrap_or(histogram::DEFAULT_BUCKETS));
+        PropertyHistogram {
+            component_type,
+            property_path,
+            samples,
+            missing,
+            numeric: summary.numeric,
+            min: summary.min,
+            max: summary.max,
+            mean: summary.mean,
+            bins: summary.bins,
+            files,
+            aliases_applied,
+            budget_exhausted: meter.exhausted(),
+        }
+    }
+
+    /// Custom script execution order of a project, from script .meta files
+    /// (MonoImporter executionOrder) and ProjectSettings/MonoManager.asset.
+    /// Scripts with the default order (0) are omitted; results are sorted by order.
+    #[napi]
+    pub fn get_execution_order(&mut self, project_root: String) -> Vec<ExecutionOrderEntry> {
+        let _call = crate::telemetry::call("get_execution_order");
+        if self.project_root.as_deref() != Some(project_root.as_str()) {
+            self.set_project_root(project_root.clone(), None);
+        }
+        let root = Path::new(&project_root);
+        let mut orders: HashMap<String, (i32, &'static str)> = HashMap::new();
+
+        let mono_manager = root.join("ProjectSettings").join("MonoManager.asset");
+        if let Ok(content) = common::read_unity_file(mono_manager) {
+            for (guid, order) in settings::parse_mono_manager_order(&content) {
+                orders.insert(guid, (order, "mono_manager"));
+            }
+        }
+
+        // .meta values are authoritative in current Unity versions
+        for dir in [root.join("Assets"), root.join("Packages")] {
+            for entry in crate::safety::walk_dir(dir).into_iter().filter_map(|e| e.ok()) {
+                if !entry.file_name().to_string_lossy().ends_with(".cs.meta") {
+                    continue;
+                }
+                let Ok(meta) = common::read_unity_file(entry.path()) else {
+                    continue;
+                };
+                if let (Some(guid), Some(order)) = (extract_guid_from_meta(&meta), settings::parse_meta_execution_order(&meta)) {
+                    orders.insert(guid, (order, "meta"));
+                }
+            }
+        }
+
+        let mut entries: Vec<ExecutionOrderEntry> = orders
+            .into_iter()
+            .filter(|(_, (order, _))| *order != 0)
+            .map(|(guid, (order, source))| {
+                let script_path = self.guid_cache.get(&guid).cloned();
+                let script_name = script_path.as_ref()
+                    .and_then(|p| Path::new(p).file_stem())
+                    .map(|s| s.to_string_lossy().to_string());
+                ExecutionOrderEntry { guid, script_path, script_name, order, source: source.to_string() }
+            })
+            .collect();
+        entries.sort_by(|a, b| a.order.cmp(&b.order).then_with(|| a.script_path.cmp(&b.script_path)));
+        entries
+    }
+
+    /// Read a .asset file and return its root objects with properties.
+    /// When `decode_mesh` is true (default), Mesh assets (class 43) get their
+    /// hex vertex/index data decoded into structured arrays.
+    /// `max_property_bytes` summarizes oversized arrays/strings (e.g. m_Tiles, keyframes).
+    #[napi(ts_return_type = "Array<AssetObjectOutput>")]
+    pub fn read_asset(&mut self, file: String, decode_mesh: Option<bool>, max_property_bytes: Option<u32>) -> serde_json::Value {
+        let _call = telemetry::call("read_asset");
+        let path = Path::new(&file);
+        if !path.exists() {
+            return serde_json::json!([]);
+        }
+
+        let content = match common::read_unity_file(path) {
+            Ok(c) => c,
+            Err(_) => return serde_json::json!([]),
+        };
+
+        self.read_asset_from_content(file, content, decode_mesh, max_property_bytes)
+    }
+
+    /// Read in-memory .asset content and return its root objects with properties
+    #[napi(ts_return_type = "Array<AssetObjectOutput>")]
+    pub fn read_asset_from_content(&mut self, file: String, content: String, decode_mesh: Option<bool>, max_property_bytes: Option<u32>) -> serde_json::Value {
+        let _call = telemetry::call("read_asset_from_content");
+        let content = common::normalize_line_endings(content);
+        self.ensure_guid_resolver(&file);
+
+        let blocks = UnityYamlParser::extract_asset_objects(&content);
+        let mut objects = Vec::new();
+
+        for (class_id, file_id, block_content) in &blocks {
+            // Extract m_Name from block
+            let name = regex::Regex::new(r"m_Name:[ \t]*([^\n]*)")
+                .ok()
+                .and_then(|re| re.captures(block_content))
+                .and_then(|caps| caps.get(1))
+                .map(|m| m.as_str().trim().to_string())
+                .unwrap_or_default();
+
+            // Determine type name from block (first line after header like "MonoBehaviour:")
+            let type_name = regex::Regex::new(r"^([A-Za-z][A-Za-z0-9_]*):")
+                .ok()
+                .and_then(|re| re.captures(block_content))
+                .and_then(|caps| caps.get(1))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_else(|| format!("ClassID_{}", class_id));
+
+            // Extract script GUID for MonoBehaviour (class_id 114)
+            let mut script_guid: Option<String> = None;
+            let mut script_path: Option<String> = None;
+
+            if *class_id == 114 {
+                let guid_re = regex::Regex::new(r"m_Script:\s*\{[^}]*guid:\s*([a-f0-9]{32})").ok();
+                if let Some(re) = guid_re {
+                    if let Some(caps) = re.captures(block_content) {
+                        if let Some(guid_match) = caps.get(1) {
+                            let guid = guid_match.as_str().to_string();
+                            script_guid = Some(guid.clone());
+                            script_path = self.guid_cache.get(&guid).cloned();
+                        }
+                    }
+                }
+            }
+
+            // Extract properties using existing infrastructure
+            // We need the full content with header for extract_properties
+            let full_block = format!("--- !u!{} &{}\n{}", class_id, file_id, block_content);
+            let mut properties = component::extract_properties(&full_block, file_id, *class_id, &self.guid_cache);
+
+            // Auto-decode Mesh binary data (class 43) unless opted out
+            if decode_mesh.unwrap_or(true) && *class_id == 43 {
+                mesh::decode_mesh_data(&mut properties);
+            }
+            let aliases_applied = self.config.filter_properties(&[class_id.to_string().as_str(), type_name.as_str()], &mut properties);
+            if let Some(max_bytes) = max_property_bytes {
+                component::summarize_large_values(&mut properties, max_bytes as usize);
+            }
+
+            objects.push(AssetObjectOutput {
+                class_id: *class_id,
+                file_id: file_id.clone(),
+                type_name,
+                name,
+                properties,
+                script_guid,
+                script_path,
+                aliases_applied: (!aliases_applied.is_empty()).then_some(aliases_applied),
+            });
+        }
+
+        serde_json::json!(objects)
+    }
+
+    fn nested_expansion(&self, max_nesting_depth: Option<u32>) -> prefab::NestedExpansion<'_> {
+        prefab::NestedExpansion {
+            project_root: self.project_root.as_deref(),
+            guid_cache: &self.guid_cache,
+            config: &self.config,
+            max_depth: max_nesting_depth.unwrap_or(3).min(10),
+            hierarchy_order: false,
+        }
+    }
+
+    /// The scene's sidecar index if it is current. A stale sidecar is rebuilt; a missing one only
+    /// when `build_missing` is set.
+    fn fresh_scene_index(&mut self, file: &str, build_missing: bool) -> Option<scene_index::SceneIndex> {
+        self.ensure_guid_resolver(file);
+        let root = self.project_root.clone()?;
+        let relative = self.relative_to_project(file);
+        let path = scene_index::index_path(Path::new(&root), &relative);
+        let stamp = scene_index::source_stamp(Path::new(file))?;
+        if let Some(index) = scene_index::SceneIndex::load_fresh(&path, &relative, stamp) {
+            return Some(index);
+        }
+        if !build_missing && !path.exists() {
+            return None;
+        }
+        let content = common::read_unity_file(file).ok()?;
+        let index = scene_index::SceneIndex::build(&relative, &content, stamp, &self.guid_cache, &self.config);
+        let _ = index.save(&path);
+        Some(index)
+    }
+
+    /// Stored annotations of a file, re-anchored to its current content (persisted when changed).
+    fn resolved_annotations(&mut self, file: &str, content: &str, file_id: Option<&str>, label: Option<&str>) -> Vec<ObjectAnnotation> {
+        self.ensure_guid_resolver(file);
+        let Some(root) = self.project_root.clone() else {
+            return Vec::new();
+        };
+        let Ok(mut store) = annotations::AnnotationStore::load(Path::new(&root)) else {
+            return Vec::new();
+        };
+        let relative = self.relative_to_project(file);
+        if store.query(&relative, None, None).is_empty() {
+            return Vec::new();
+        }
+        if store.resolve(&relative, &correlate::fingerprints(content, &self.config)) {
+            // Best effort: a read-only project still gets the resolved view
+            let _ = store.save(Path::new(&root));
+        }
+        store.query(&relative, file_id, label)
+    }
+
+    /// Append image dimensions to texture/sprite references in component properties.
+    fn annotate_image_references(&self, components: &mut [Component]) {
+        let Some(root) = self.project_root.as_deref() else { return };
+        let mut cache: HashMap<String, Option<TextureInfo>> = HashMap::new();
+        let mut lookup = |path: &str| cache.entry(path.to_string())
+            .or_insert_with(|| image::texture_info(&Path::new(root).join(path), path))
+            .clone();
+        for props in components.iter_mut().filter_map(|c| c.properties.as_mut()) {
+            image::annotate_references(props, &mut lookup);
+        }
+    }
+
+    /// Express a file path relative to the project root (forward slashes), when possible.
+    /// Layer names from the project's TagManager.asset (empty when unknown)
+    /// Memoized "does this script define the method" check against project sources.
+    fn script_method_lookup(&self) -> impl FnMut(&str, &str) -> Option<bool> + use<> {
+        let root = self.project_root.clone();
+        let mut sources: HashMap<String, Option<String>> = HashMap::new();
+        move |script: &str, method: &str| {
+            let source = sources.entry(script.to_string()).or_insert_with(|| {
+                common::read_unity_file(Path::new(root.as_deref()?).join(script)).ok()
+            });
+            crate::csharp::summary::defines_method(source.as_deref()?, method)
+        }
+    }
+
+    /// Lookup answering whether a project scene has a block with a fileID, reading each scene once.
+    fn scene_object_lookup(&self) -> impl FnMut(&str, &str) -> Option<bool> + use<> {
+        let root = self.project_root.clone();
+        let mut scenes: HashMap<String, Option<HashSet<String>>> = HashMap::new();
+        move |scene: &str, file_id: &str| {
+            let ids = scenes.entry(scene.to_string()).or_insert_with(|| {
+                let content = common::read_unity_file(Path::new(root.as_deref()?).join(scene)).ok()?;
+                Some(UnityYamlParser::parse_all_blocks(&content).into_iter().map(|(_, id, _)| id).collect())
+            });
+            Some(ids.as_ref()?.contains(file_id))
+        }
+    }
+
+    fn project_layer_names(&self) -> Vec<Option<String>> {
+        self.project_root.as_ref()
+            .and_then(|root| common::read_unity_file(Path::new(root).join("ProjectSettings").join("TagManager.asset")).ok())
+            .map(|content| settings::parse_layer_names(&content))
+            .unwrap_or_default()
+    }
+
+    fn relative_to_project(&self, file: &str) -> String {
+        self.project_root
+            .as_deref()
+            .and_then(|root| Path::new(file).strip_prefix(root).ok())
+            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|| file.to_string())
+    }
+
+    fn ensure_guid_resolver(&mut self, file: &str) {
+        if self.project_root.is_none() {
+            if let Some(root) = find_project_root(file) {
+                self.project_root = Some(root.clone());
+                // An injected cache (set_guid_cache) already covers the project
+                if self.guid_cache.is_empty() {
+                    self.build_guid_cache(&root);
+                }
+                self.load_project_config(&root);
+                self.load_tag_manager();
+            }
+        }
+    }
+
+    fn load_tag_manager(&mut self) {
+        self.tag_manager = self.project_root.as_ref()
+            .filter(|_| self.validate_tags)
+            .and_then(|root| common::read_unity_file(Path::new(root).join("ProjectSettings").join("TagManager.asset")).ok())
+            .map(|content| settings::TagManagerInfo::parse(&content));
+    }
+
+    /// Merge `.unity-agentic/config.json` when the project has one. Invalid files are ignored
+    /// here; `load_config` reports their errors.
+    fn load_project_config(&mut self, project_root: &str) {
+        let path = Path::new(project_root).join(config::PROJECT_CONFIG_PATH);
+        if path.is_file() {
+            let _ = self.config.load_file(&path);
+        }
+    }
+
+    /// Call `visit` with every non-stripped block of a component type (built-in type name, or a
+    /// MonoBehaviour script name) in the project's scenes, prefabs and assets. Returns the
+    /// backing script path and the files that contained at least one block.
+    fn for_each_component_block(&mut self, project_root: &str, type_name: &str, meter: &Meter, mut visit: impl FnMut(&str)) -> (Option<String>, Vec<String>) {
+        if self.project_root.as_deref() != Some(project_root) {
+            self.set_project_root(project_root.to_string(), None);
+        }
+        let script_guids: Vec<&String> = self.guid_cache.iter()
+            .filter(|(_, path)| path.ends_with(".cs") && Path::new(path.as_str()).file_stem().is_some_and(|s| s == type_name))
+            .map(|(guid, _)| guid)
+            .collect();
+        let script_path = script_guids.first().and_then(|guid| self.guid_cache.get(*guid)).cloned();
+        let type_line = format!("\n{}:", type_name);
+
+        let mut files = Vec::new();
+        let extensions = vec![".unity".to_string(), ".prefab".to_string(), ".asset".to_string()];
+        for file in crate::walker::walk_project_files(project_root.to_string(), extensions, None, None) {
+            if !meter.admit_file(&file) {
+                break;
+            }
+            let Ok(content) = common::read_unity_file(&file) else { continue };
+            if !content.contains(&type_line) && !script_guids.iter().any(|g| content.contains(g.as_str())) {
+                continue;
+            }
+            let mut found = false;
+            let (_, blocks) = transplant::split_raw_blocks(&content);
+            for block in blocks.iter().filter(|b| !b.stripped) {
+                let block_type = block.text.lines().nth(1).unwrap_or("").trim_end_matches(':');
+                let matches = block_type == type_name
+                    || (self.config.is_script_container(block.class_id)
+                        && rendering::field(&block.text, "m_Script")
+                            .is_some_and(|s| script_guids.iter().any(|g| s.contains(g.as_str()))));
+                if matches {
+                    visit(&block.text);
+                    found = true;
+                }
+            }
+            if found {
+                files.push(self.relative_to_project(&file));
+            }
+        }
+        (script_path, files)
     }
 
     fn build_guid_cache(&mut self, project_root: &str) {
         let assets_dir = Path::new(project_root).join("Assets");
         if assets_dir.exists() {
-            self.scan_meta_files(&assets_dir, project_root);
+            let filter = crate::walker::DirFilter::for_project(Path::new(project_root), &[]);
+            self.scan_meta_files(&assets_dir, project_root, &filter);
         }
     }
 
-    fn scan_meta_files(&mut self, dir: &Path, project_root: &str) {
-        if let Ok(entries) = fs::read_dir(dir) {
+    fn scan_meta_files(&mut self, dir: &Path, project_root: &str, filter: &crate::walker::DirFilter) {
+        if let Ok(entries) = crate::safety::read_dir(dir) {
             for entry in entries.filter_map(|e| e.ok()) {
                 let path = entry.path();
                 if path.is_dir() {
-                    self.scan_meta_files(&path, project_root);
+                    if filter.allows(&path) {
+                        self.scan_meta_files(&path, project_root, filter);
+                    }
                 } else if path.extension().map_or(false, |e| e == "meta") {
                     if let Ok(content) = common::read_unity_file(&path) {
                         if let Some(guid) = extract_guid_from_meta(&content) {
@@ -762,18 +3025,20 @@ impl Scanner {
         component::extract_components(content, file_id, &self.guid_cache)
     }
 
-    fn build_gameobject_output(&self, obj: &GameObject, components: &[Component], verbose: bool, include_properties: bool) -> serde_json::Value {
-        let mut output = serde_json::json!({
-            "name": obj.name,
-            "active": obj.active,
-        });
-
-        if verbose {
-            output["file_id"] = serde_json::json!(obj.file_id);
-            output["component_count"] = serde_json::json!(components.len());
+    fn build_gameobject_output(&self, obj: &GameObject, components: &[Component], verbose: bool, include_properties: bool, tag: String, layer: u32) -> SceneObjectOutput {
+        SceneObjectOutput {
+            name: obj.name.clone(),
+            active: obj.active,
+            file_id: verbose.then(|| obj.file_id.clone()),
+            component_count: verbose.then_some(components.len() as u32),
+            components: self.component_outputs(components, verbose, include_properties),
+            tag,
+            layer,
         }
+    }
 
-        let comp_output: Vec<serde_json::Value> = components
+    fn component_outputs(&self, components: &[Component], verbose: bool, include_properties: bool) -> Vec<ComponentOutput> {
+        components
             .iter()
             .map(|c| {
                 if verbose {
@@ -782,10 +3047,7 @@ impl Scanner {
                     self.clean_component(c, include_properties)
                 }
             })
-            .collect();
-
-        output["components"] = serde_json::json!(comp_output);
-        output
+            .collect()
     }
 
     #[allow(dead_code)]
@@ -796,140 +3058,214 @@ impl Scanner {
             name: obj.name.clone(),
             file_id: obj.file_id.clone(),
             active: obj.active,
-            tag,
+            tag: tag.clone(),
             layer,
             depth: None,
             components: components.to_vec(),
             children: if children.is_empty() { None } else { Some(children) },
+            ordered_children: None,
+            warnings: self.tag_layer_warnings(&tag, layer),
             parent_transform_id: parent_id,
+            provenance: None,
         }
     }
 
-    fn extract_gameobject_details_indexed(&self, index: &BlockIndex, obj: &GameObject, components: &[Component]) -> GameObjectDetail {
+    fn extract_gameobject_details_indexed(&self, file: &str, index: &BlockIndex, obj: &GameObject, components: &[Component]) -> GameObjectDetail {
         let (tag, layer, parent_id, children) = gameobject::extract_metadata_indexed(index, &obj.file_id, &self.config);
+        let ordered_children = gameobject::resolve_children_indexed(index, &children, &self.config);
 
         GameObjectDetail {
             name: obj.name.clone(),
             file_id: obj.file_id.clone(),
             active: obj.active,
-            tag,
+            tag: tag.clone(),
             layer,
             depth: None,
             components: components.to_vec(),
             children: if children.is_empty() { None } else { Some(children) },
+            ordered_children: if ordered_children.is_empty() { None } else { Some(ordered_children) },
+            warnings: self.tag_layer_warnings(&tag, layer),
             parent_transform_id: parent_id,
+            provenance: Some(provenance::resolve(
+                index, &obj.file_id, &self.relative_to_project(file), &self.guid_cache, self.project_root.as_deref(), &self.config,
+            )),
         }
     }
 
-    fn build_detail_output(&self, detail: &GameObjectDetail, verbose: bool, include_properties: bool) -> serde_json::Value {
-        let mut output = serde_json::json!({
-            "name": detail.name,
-            "file_id": detail.file_id,
-            "active": detail.active,
-            "tag": detail.tag,
-            "layer": detail.layer,
-        });
-
-        let comp_output: Vec<serde_json::Value> = detail.components
-            .iter()
-            .map(|c| {
-                if verbose {
-                    self.verbose_component(c, include_properties)
-                } else {
-                    self.clean_component(c, include_properties)
-                }
-            })
-            .collect();
+    fn build_detail_output(&self, detail: &GameObjectDetail, verbose: bool, include_properties: bool) -> GameObjectInspection {
+        GameObjectInspection {
+            name: detail.name.clone(),
+            file_id: detail.file_id.clone(),
+            active: detail.active,
+            tag: detail.tag.clone(),
+            layer: detail.layer,
+            components: self.component_outputs(&detail.components, verbose, include_properties),
+            children: detail.children.clone().filter(|_| verbose),
+            ordered_children: detail.ordered_children.clone(),
+            parent_transform_id: detail.parent_transform_id.clone().filter(|_| verbose),
+            annotations: None,
+            warnings: detail.warnings.clone(),
+            // Plain local objects only carry it in verbose mode, keeping compact output small
+            provenance: detail.provenance.clone().filter(|p| verbose || p.kind != "scene" || p.origin != "project"),
+        }
+    }
 
-        output["components"] = serde_json::json!(comp_output);
+    /// Tag/layer warnings for a GameObject; None unless validation is on and a TagManager was found.
+    fn tag_layer_warnings(&self, tag: &str, layer: u32) -> Option<Vec<String>> {
+        let warnings = self.tag_manager.as_ref()?.warnings(tag, layer);
+        if warnings.is_empty() { None } else { Some(warnings) }
+    }
 
-        if verbose {
-            if let Some(ref children) = detail.children {
-                output["children"] = serde_json::json!(children);
-            }
-            if let Some(ref parent) = detail.parent_transform_id {
-                output["parent_transform_id"] = serde_json::json!(parent);
+    /// Attach added components (properties only with `include_properties`) and resolve removed
+    /// and extended source objects of extracted PrefabInstances.
+    fn complete_prefab_instances(&self, instances: &mut [PrefabInstanceInfo], content: &str, include_properties: bool) {
+        prefab::attach_added_components(instances, content, &self.guid_cache, &self.config);
+        prefab::resolve_removed_objects(instances, self.project_root.as_deref(), &self.guid_cache);
+        if !include_properties {
+            for added in instances.iter_mut().flat_map(|pi| pi.added_components.iter_mut().flatten()) {
+                added.component.properties = None;
             }
         }
-
-        output
     }
 
-    fn build_prefab_instance_output(&self, pi: &PrefabInstanceInfo, content: Option<&str>, include_properties: bool) -> serde_json::Value {
-        let mut output = serde_json::json!({
-            "type": "PrefabInstance",
-            "name": pi.name,
-            "file_id": pi.file_id,
-            "source_guid": pi.source_guid,
-            "modifications_count": pi.modifications_count,
-        });
-        if let Some(ref src) = pi.source_prefab {
-            output["source_prefab"] = serde_json::json!(src);
-        }
+    fn build_prefab_instance_output(&self, pi: &PrefabInstanceInfo, content: Option<&str>, include_properties: bool) -> PrefabInstanceOutput {
+        let mut output = prefab_instance_output(pi);
         if include_properties {
-            if let Some(content) = content {
-                if let Some(block) = prefab::extract_prefab_block(content, &pi.file_id) {
-                    let mods = prefab::extract_modifications(&block);
-                    // Group by target_file_id
-                    let mut grouped: std::collections::HashMap<String, Vec<serde_json::Value>> = std::collections::HashMap::new();
-                    for m in &mods {
-                        let entry = grouped.entry(m.target_file_id.clone()).or_default();
-                        entry.push(serde_json::json!({
-                            "propertyPath": m.property_path,
-                            "value": m.value,
-                        }));
-                    }
-                    output["modifications"] = serde_json::json!(grouped);
+            if let Some(block) = content.and_then(|content| prefab::extract_prefab_block(content, &pi.file_id)) {
+                // Group by target_file_id
+                let mut grouped: HashMap<String, Vec<PrefabModificationOutput>> = HashMap::new();
+                for m in prefab::extract_modifications(&block) {
+                    grouped.entry(m.target_file_id).or_default().push(PrefabModificationOutput {
+                        property_path: m.property_path,
+                        value: m.value,
+                    });
                 }
+                output.modifications = Some(grouped);
             }
         }
         output
     }
 
-    fn clean_component(&self, comp: &Component, include_properties: bool) -> serde_json::Value {
-        let mut cleaned = serde_json::json!({
-            "type": comp.type_name,
-        });
-
-        if let Some(ref path) = comp.script_path {
-            cleaned["script"] = serde_json::json!(path);
+    fn clean_component(&self, comp: &Component, include_properties: bool) -> ComponentOutput {
+        ComponentOutput {
+            type_name: comp.type_name.clone(),
+            script: comp.script_path.clone(),
+            class_id: None,
+            file_id: None,
+            script_path: None,
+            script_guid: None,
+            script_name: None,
+            editor_class_identifier: None,
+            enabled: comp.enabled,
+            properties: comp.properties.clone().filter(|_| include_properties),
+            aliases_applied: comp.aliases_applied.clone().filter(|_| include_properties),
         }
+    }
 
-        if include_properties {
-            if let Some(ref props) = comp.properties {
-                cleaned["properties"] = props.clone();
-            }
+    fn verbose_component(&self, comp: &Component, include_properties: bool) -> ComponentOutput {
+        ComponentOutput {
+            type_name: comp.type_name.clone(),
+            script: None,
+            class_id: Some(comp.class_id),
+            file_id: Some(comp.file_id.clone()),
+            script_path: comp.script_path.clone(),
+            script_guid: comp.script_guid.clone(),
+            script_name: comp.script_name.clone(),
+            editor_class_identifier: comp.editor_class_identifier.clone(),
+            enabled: comp.enabled,
+            properties: comp.properties.clone().filter(|_| include_properties),
+            aliases_applied: comp.aliases_applied.clone().filter(|_| include_properties),
         }
+    }
+}
 
-        cleaned
+/// Base output of a PrefabInstance (no modifications or nested expansion).
+/// Apply an already parsed field selection (None keeps everything)
+fn project_with(value: serde_json::Value, selection: Option<&FieldSelection>) -> serde_json::Value {
+    match selection {
+        Some(selection) => selection.apply(value),
+        None => value,
     }
+}
 
-    fn verbose_component(&self, comp: &Component, include_properties: bool) -> serde_json::Value {
-        let mut verbose = serde_json::json!({
-            "type": comp.type_name,
-            "class_id": comp.class_id,
-            "file_id": comp.file_id,
-        });
+fn prefab_instance_output(pi: &PrefabInstanceInfo) -> PrefabInstanceOutput {
+    PrefabInstanceOutput {
+        object_type: "PrefabInstance".to_string(),
+        name: pi.name.clone(),
+        file_id: Some(pi.file_id.clone()),
+        source_guid: pi.source_guid.clone(),
+        source_prefab: pi.source_prefab.clone(),
+        modifications_count: pi.modifications_count,
+        removed_components: pi.removed_components.clone(),
+        removed_game_objects: pi.removed_game_objects.clone(),
+        added_components: pi.added_components.clone(),
+        parent_transform_id: pi.parent_transform_id.clone(),
+        parent_game_object_id: pi.parent_game_object_id.clone(),
+        parent_prefab_instance_id: pi.parent_prefab_instance_id.clone(),
+        modifications: None,
+        expanded: None,
+        unexpanded_reason: None,
+    }
+}
 
-        if let Some(ref path) = comp.script_path {
-            verbose["script_path"] = serde_json::json!(path);
+/// Apply the per-call property size budget to extracted components
+fn summarize_component_properties(components: &mut [Component], max_property_bytes: Option<u32>) {
+    let Some(max_bytes) = max_property_bytes else {
+        return;
+    };
+    for comp in components {
+        if let Some(props) = comp.properties.as_mut() {
+            component::summarize_large_values(props, max_bytes as usize);
         }
+    }
+}
 
-        if let Some(ref guid) = comp.script_guid {
-            verbose["script_guid"] = serde_json::json!(guid);
-        }
+/// Layer names from the TagManager.asset next to a ProjectSettings file (all None when missing)
+fn sibling_layer_names(file: &str) -> Vec<Option<String>> {
+    let tag_manager = Path::new(file).with_file_name("TagManager.asset");
+    common::read_unity_file(tag_manager)
+        .map(|content| settings::parse_layer_names(&content))
+        .unwrap_or_default()
+}
 
-        if let Some(ref name) = comp.script_name {
-            verbose["script_name"] = serde_json::json!(name);
-        }
+/// Empty paginated result carrying an error message
+fn paginated_error(options: PaginationOptions, error: String) -> PaginatedInspection {
+    PaginatedInspection {
+        file: options.file,
+        total: 0,
+        total_in_scene: 0,
+        cursor: options.cursor.unwrap_or(0).max(0),
+        next_cursor: None,
+        truncated: false,
+        page_size: options.page_size.unwrap_or(200).min(1000),
+        gameobjects: Vec::new(),
+        prefab_instances: None,
+        error: Some(error),
+    }
+}
 
-        if include_properties {
-            if let Some(ref props) = comp.properties {
-                verbose["properties"] = props.clone();
-            }
+/// A JSON object given inline, or read from the file a string names (relative to `root`).
+fn json_object_or_file(root: Option<&str>, value: serde_json::Value) -> Option<serde_json::Map<String, serde_json::Value>> {
+    let value = match value {
+        serde_json::Value::String(file) => {
+            let path = root.map_or_else(|| Path::new(&file).to_path_buf(), |root| Path::new(root).join(&file));
+            serde_json::from_str(&crate::safety::read_to_string(path).ok()?).ok()?
         }
+        other => other,
+    };
+    match value {
+        serde_json::Value::Object(map) => Some(map),
+        _ => None,
+    }
+}
 
-        verbose
+/// Parse a sibling-order option: whether it asks for the editor's hierarchy order rather than
+/// document order.
+fn hierarchy_sort(sort: Option<&str>) -> Result<bool, String> {
+    match sort {
+        None | Some("document") => Ok(false),
+        Some("hierarchy") => Ok(true),
+        Some(other) => Err(format!("Unknown sort '{}' (expected 'document' or 'hierarchy')", other)),
     }
 }
 
@@ -1216,4 +3552,568 @@ GameObject:
         assert_eq!(gos[0].name, "RealObject");
         assert_eq!(gos[0].file_id, "101");
     }
+
+    #[test]
+    fn test_from_content_matches_file_based() {
+        let content = "%YAML 1.1\n--- !u!1 &100\nGameObject:\n  m_Component:\n  - component: {fileID: 101}\n  m_Layer: 0\n  m_Name: Player\n  m_TagString: Player\n  m_IsActive: 1\n--- !u!4 &101\nTransform:\n  m_GameObject: {fileID: 100}\n  m_Father: {fileID: 0}\n";
+        let mut tmp = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut tmp, content.as_bytes()).unwrap();
+        let file = tmp.path().to_string_lossy().to_string();
+
+        let mut scanner = Scanner::new();
+        let from_file = scanner.inspect_all(file.clone(), false, false);
+        // CRLF buffers (e.g. git blobs on Windows) are normalized before parsing
+        let from_content = scanner.inspect_all_from_content(file.clone(), content.replace('\n', "\r\n"), false, false);
+        assert_eq!(from_file.count, 1);
+        assert_eq!(from_content.count, from_file.count);
+        assert_eq!(from_content.gameobjects[0].name, from_file.gameobjects[0].name);
+
+        let found = scanner.find_by_name_from_content(file, content.to_string(), "Player".to_string(), false);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].file_id, "100");
+    }
+
+    #[test]
+    fn test_from_content_does_not_require_file_on_disk() {
+        let content = "--- !u!1 &100\nGameObject:\n  m_Name: Unsaved\n  m_IsActive: 1\n";
+        let scanner = Scanner::new();
+        let gos = scanner.scan_scene_minimal_from_content(content.to_string());
+        assert_eq!(gos.len(), 1);
+        assert_eq!(gos[0].name, "Unsaved");
+    }
+
+    #[test]
+    fn test_typed_outputs_keep_snake_case_json_shape() {
+        let content = "%YAML 1.1\n--- !u!1 &100\nGameObject:\n  m_Component:\n  - component: {fileID: 101}\n  m_Layer: 5\n  m_Name: Player\n  m_TagString: Player\n  m_IsActive: 1\n--- !u!4 &101\nTransform:\n  m_GameObject: {fileID: 100}\n  m_Children: []\n  m_Father: {fileID: 0}\n";
+        let mut scanner = Scanner::new();
+        let keys = |value: &serde_json::Value| {
+            let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+
+        let scanned = scanner.scan_scene_with_components_from_content("Test.unity".to_string(), content.to_string(), None);
+        assert_eq!(keys(&scanned[0]), vec!["active", "components", "layer", "name", "tag"]);
+        assert_eq!(keys(&scanned[0]["components"][0]), vec!["type"]);
+        let verbose = scanner.scan_scene_with_components_from_content("Test.unity".to_string(), content.to_string(), Some(ScanOptions { verbose: Some(true), fields: None }));
+        assert_eq!(verbose[0]["component_count"], 1);
+        assert_eq!(keys(&verbose[0]["components"][0]), vec!["class_id", "file_id", "type"]);
+
+        let options = InspectOptions {
+            file: "Test.unity".to_string(),
+            identifier: Some("100".to_string()),
+            include_properties: None,
+            verbose: None,
+            expand_nested: None,
+            max_nesting_depth: None,
+            max_property_bytes: None,
+            resolve_images: None,
+            fields: None,
+        };
+        let inspected = scanner.inspect_from_content(options, content.to_string()).unwrap();
+        assert_eq!(keys(&inspected), vec!["active", "components", "file_id", "layer", "name", "tag"]);
+
+        let fields = Some(vec!["name".to_string(), "components.type".to_string()]);
+        let projected = scanner.scan_scene_with_components_from_content("Test.unity".to_string(), content.to_string(), Some(ScanOptions { verbose: Some(true), fields: fields.clone() }));
+        assert_eq!(keys(&projected[0]), vec!["components", "name"]);
+        assert_eq!(keys(&projected[0]["components"][0]), vec!["type"]);
+        let options = InspectOptions {
+            file: "Test.unity".to_string(),
+            identifier: Some("Player".to_string()),
+            include_properties: Some(true),
+            verbose: None,
+            expand_nested: None,
+            max_nesting_depth: None,
+            max_property_bytes: None,
+            resolve_images: None,
+            fields,
+        };
+        let projected = scanner.inspect_from_content(options, content.to_string()).unwrap();
+        assert_eq!(projected, serde_json::json!({"name": "Player", "components": [{"type": "Transform"}]}));
+
+        let asset = scanner.read_asset_from_content("Test.asset".to_string(), "--- !u!114 &1\nMonoBehaviour:\n  m_Name: Settings\n  speed: 2\n".to_string(), None, None);
+        assert_eq!(keys(&asset[0]), vec!["class_id", "file_id", "name", "properties", "type_name"]);
+    }
+
+    #[test]
+    fn test_inspect_all_fields_skips_unselected_components() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("Test.unity");
+        fs::write(&file, "%YAML 1.1\n--- !u!1 &100\nGameObject:\n  m_Component:\n  - component: {fileID: 101}\n  m_Layer: 0\n  m_Name: Player\n  m_TagString: Untagged\n  m_IsActive: 1\n--- !u!4 &101\nTransform:\n  m_GameObject: {fileID: 100}\n  m_LocalPosition: {x: 1, y: 2, z: 3}\n  m_Children: []\n  m_Father: {fileID: 0}\n").unwrap();
+        let file = file.to_string_lossy().to_string();
+        let mut scanner = Scanner::new();
+        let fields = |paths: &[&str]| paths.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        let names = scanner.inspect_all_fields(file.clone(), fields(&["name"]), Some(true), None);
+        assert_eq!(names["gameobjects"], serde_json::json!([{"name": "Player"}]));
+        let types = scanner.inspect_all_fields(file.clone(), fields(&["components.type_name"]), Some(true), None);
+        assert_eq!(types["gameobjects"], serde_json::json!([{"components": [{"type_name": "Transform"}]}]));
+        let properties = scanner.inspect_all_fields(file, fields(&["components.properties"]), Some(true), None);
+        assert_eq!(properties["gameobjects"][0]["components"][0]["properties"]["LocalPosition"], "{x: 1, y: 2, z: 3}");
+    }
+
+    #[test]
+    fn test_get_execution_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let scripts = tmp.path().join("Assets").join("Scripts");
+        fs::create_dir_all(&scripts).unwrap();
+        fs::create_dir_all(tmp.path().join("ProjectSettings")).unwrap();
+        fs::write(scripts.join("Boot.cs"), "class Boot {}").unwrap();
+        fs::write(scripts.join("Boot.cs.meta"), "fileFormatVersion: 2\nguid: 11111111111111111111111111111111\nMonoImporter:\n  executionOrder: -100\n").unwrap();
+        fs::write(scripts.join("Late.cs"), "class Late {}").unwrap();
+        fs::write(scripts.join("Late.cs.meta"), "fileFormatVersion: 2\nguid: 22222222222222222222222222222222\nMonoImporter:\n  executionOrder: 0\n").unwrap();
+        fs::write(
+            tmp.path().join("ProjectSettings").join("MonoManager.asset"),
+            "MonoManager:\n  m_ExecutionOrder:\n  - first: {fileID: 11500000, guid: 22222222222222222222222222222222, type: 3}\n    second: 500\n",
+        ).unwrap();
+
+        let mut scanner = Scanner::new();
+        let entries = scanner.get_execution_order(tmp.path().to_string_lossy().to_string());
+        assert_eq!(entries.len(), 1, "meta executionOrder overrides MonoManager; 0 is omitted");
+        assert_eq!(entries[0].script_name.as_deref(), Some("Boot"));
+        assert_eq!(entries[0].script_path.as_deref(), Some("Assets/Scripts/Boot.cs"));
+        assert_eq!(entries[0].order, -100);
+        assert_eq!(entries[0].source, "meta");
+    }
+
+    #[test]
+    fn test_build_context_pack() {
+        let tmp = tempfile::tempdir().unwrap();
+        let scene = tmp.path().join("Pack.unity");
+        fs::write(&scene, "%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Name: World\n  m_IsActive: 1\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Children:\n  - {fileID: 4}\n  m_Father: {fileID: 0}\n\
+--- !u!1 &3\nGameObject:\n  m_Component:\n  - component: {fileID: 4}\n  - component: {fileID: 5}\n  m_Name: Player\n  m_IsActive: 1\n\
+--- !u!4 &4\nTransform:\n  m_GameObject: {fileID: 3}\n  m_Children: []\n  m_Father: {fileID: 2}\n\
+--- !u!20 &5\nCamera:\n  m_GameObject: {fileID: 3}\n  m_TargetTexture: {fileID: 0}\n  follow: {fileID: 1}\n").unwrap();
+
+        let mut scanner = Scanner::new();
+        let pack = scanner.build_context_pack(scene.to_string_lossy().to_string(), "Player".to_string(), None);
+        assert_eq!(pack["focus"]["file_id"], "3");
+        let kinds: Vec<&str> = pack["sections"].as_array().unwrap().iter().map(|s| s["kind"].as_str().unwrap()).collect();
+        assert_eq!(kinds, vec!["target", "parent", "reference"]);
+        assert_eq!(pack["sections"][1]["name"], "World");
+        assert_eq!(pack["sections"][2]["property"], "follow");
+
+        let tiny = scanner.build_context_pack(scene.to_string_lossy().to_string(), "3".to_string(), Some(1));
+        assert_eq!(tiny["sections"].as_array().unwrap().len(), 0);
+        assert_eq!(tiny["omitted"].as_array().unwrap().len(), 3);
+
+        let missing = scanner.build_context_pack(scene.to_string_lossy().to_string(), "Nope".to_string(), None);
+        assert_eq!(missing["is_error"], true);
+    }
+
+    #[test]
+    fn test_guid_cache_injection() {
+        let tmp = tempfile::tempdir().unwrap();
+        let assets = tmp.path().join("Assets");
+        fs::create_dir_all(&assets).unwrap();
+        fs::write(assets.join("Scanned.cs.meta"), "fileFormatVersion: 2\nguid: 11111111111111111111111111111111\n").unwrap();
+        fs::write(assets.join("Main.unity"), "%YAML 1.1\n").unwrap();
+
+        let mut scanner = Scanner::new();
+        let loaded = scanner.set_guid_cache(serde_json::json!({
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA": "Assets\\Player.cs",
+            "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb": 42,
+        }));
+        assert_eq!(loaded, 1);
+        assert_eq!(scanner.cache_size(), 1);
+        assert_eq!(scanner.get_guid_path("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()).as_deref(), Some("Assets/Player.cs"));
+        assert_eq!(scanner.get_path_guid("Assets/Player.cs".to_string()).as_deref(), Some("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"));
+
+        // Detecting the project does not rescan over the injected cache
+        scanner.scene_stats(assets.join("Main.unity").to_string_lossy().to_string());
+        assert_eq!(scanner.cache_size(), 1);
+        let absolute = tmp.path().join("Assets").join("Player.cs");
+        assert!(scanner.get_path_guid(absolute.to_string_lossy().to_string()).is_some());
+        assert_eq!(scanner.set_guid_cache(serde_json::json!([])), 0);
+    }
+
+    #[test]
+    fn test_set_project_root_warm_start_and_packages() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_string_lossy().to_string();
+        fs::create_dir_all(tmp.path().join("Assets")).unwrap();
+        fs::write(tmp.path().join("Assets").join("Scanned.cs.meta"), "guid: 11111111111111111111111111111111\n").unwrap();
+        let package = tmp.path().join("Library").join("PackageCache").join("com.example@1.0");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("Tool.cs.meta"), "guid: 22222222222222222222222222222222\n").unwrap();
+        fs::write(tmp.path().join("cache.json"), r#"{"33333333333333333333333333333333": "Assets/Cached.cs"}"#).unwrap();
+
+        let mut scanner = Scanner::new();
+        scanner.set_project_root(root.clone(), Some(ProjectRootOptions { guid_cache: Some(serde_json::json!("cache.json")), include_packages: None, guid_aliases: None, guid_history: None }));
+        assert_eq!(scanner.cache_size(), 1, "persisted cache replaces the Assets/ scan");
+
+        fs::write(tmp.path().join("history.json"), r#"{"55555555555555555555555555555555": "Assets/Old/Scanned.cs"}"#).unwrap();
+        let mut scanner = Scanner::new();
+        scanner.set_project_root(root, Some(ProjectRootOptions {
+            guid_cache: None,
+            include_packages: Some(true),
+            guid_aliases: Some(serde_json::json!({ "44444444444444444444444444444444": "1".repeat(32) })),
+            guid_history: Some(serde_json::json!("history.json")),
+        }));
+        assert_eq!(scanner.get_guid_path("1".repeat(32)).as_deref(), Some("Assets/Scanned.cs"));
+        assert_eq!(scanner.get_guid_path("2".repeat(32)).as_deref(), Some("Library/PackageCache/com.example@1.0/Tool.cs"));
+        let aliased = scanner.resolve_guid("4".repeat(32)).unwrap();
+        assert_eq!((aliased.status.as_str(), aliased.message.as_str()), ("aliased", "moved to Assets/Scanned.cs"));
+        let moved = scanner.resolve_guid("5".repeat(32)).unwrap();
+        assert_eq!((moved.status.as_str(), moved.former_path.as_deref()), ("moved", Some("Assets/Old/Scanned.cs")));
+        assert!(scanner.resolve_guid("6".repeat(32)).is_none());
+    }
+
+    #[test]
+    fn test_scene_index_sidecar() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("Assets")).unwrap();
+        let scene = tmp.path().join("Assets").join("Main.unity");
+        let go = |id: u32, name: &str| format!("--- !u!1 &{}\nGameObject:\n  m_Component:\n  - component: {{fileID: {}}}\n  m_Name: {}\n  m_TagString: Untagged\n  m_IsActive: 1\n--- !u!4 &{}\nTransform:\n  m_GameObject: {{fileID: {}}}\n  m_Children: []\n  m_Father: {{fileID: 0}}\n", id, id + 1, name, id + 1, id);
+        fs::write(&scene, format!("%YAML 1.1\n{}", go(1, "Player"))).unwrap();
+        let file = scene.to_string_lossy().to_string();
+
+        let mut scanner = Scanner::new();
+        scanner.set_project_root(tmp.path().to_string_lossy().to_string(), None);
+        let built = scanner.build_scene_index(file.clone());
+        assert_eq!(built["objects"], 1);
+        assert!(Path::new(built["index_path"].as_str().unwrap()).is_file());
+        assert_eq!(scanner.find_by_name(file.clone(), "Player".to_string(), false).len(), 1);
+
+        // A changed source (size/mtime) invalidates the sidecar on the next lookup
+        fs::write(&scene, format!("%YAML 1.1\n{}{}", go(1, "Player"), go(10, "Enemy"))).unwrap();
+        assert_eq!(scanner.find_by_name(file.clone(), "Enemy".to_string(), false)[0].file_id, "10");
+        assert_eq!(scanner.query_scene_index(file, None, Some("Untagged".to_string()), Some("Transform".to_string())).len(), 2);
+    }
+
+    #[test]
+    fn test_annotations_survive_file_id_remap() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("Assets")).unwrap();
+        let scene = tmp.path().join("Assets").join("Main.unity");
+        let go = |id: u32, name: &str| format!("--- !u!1 &{}\nGameObject:\n  m_Component:\n  - component: {{fileID: {}}}\n  m_Name: {}\n  m_TagString: Untagged\n  m_IsActive: 1\n--- !u!4 &{}\nTransform:\n  m_GameObject: {{fileID: {}}}\n  m_Children: []\n  m_Father: {{fileID: 0}}\n", id, id + 1, name, id + 1, id);
+        fs::write(&scene, format!("%YAML 1.1\n{}", go(1, "Spawner"))).unwrap();
+        let file = scene.to_string_lossy().to_string();
+
+        let mut scanner = Scanner::new();
+        scanner.set_project_root(tmp.path().to_string_lossy().to_string(), None);
+        let added = scanner.annotate_object(file.clone(), "1".to_string(), Some("Spawns wave 2".to_string()), Some(vec!["todo".to_string()]));
+        assert_eq!(added["file"], "Assets/Main.unity");
+        assert!(scanner.annotate_object(file.clone(), "99".to_string(), None, None)["is_error"].as_bool().unwrap());
+
+        fs::write(&scene, format!("%YAML 1.1\n{}", go(500, "Spawner"))).unwrap();
+        let found = scanner.get_annotations(file.clone(), None, Some("todo".to_string()));
+        assert_eq!((found[0].file_id.as_str(), found[0].remapped_from.as_deref()), ("500", Some("1")));
+
+        let options = InspectOptions {
+            file: file.clone(),
+            identifier: Some("500".to_string()),
+            include_properties: None,
+            verbose: None,
+            expand_nested: None,
+            max_nesting_depth: None,
+            max_property_bytes: None,
+            resolve_images: None,
+            fields: None,
+        };
+        let inspected = scanner.inspect(options).unwrap();
+        assert_eq!(inspected["annotations"][0]["note"], "Spawns wave 2");
+        assert!(scanner.remove_annotation(file.clone(), found[0].id));
+        assert!(scanner.get_annotations(file, None, None).is_empty());
+    }
+    #[test]
+    fn test_analyze_rename_impact() {
+        let tmp = tempfile::tempdir().unwrap();
+        let assets = tmp.path().join("Assets");
+        fs::create_dir_all(&assets).unwrap();
+        let controller_guid = "cccccccccccccccccccccccccccccccc";
+        let clip_guid = "dddddddddddddddddddddddddddddddd";
+        fs::write(assets.join("Robot.controller"), format!("--- !u!1102 &1\nAnimatorState:\n  m_Motion: {{fileID: 7400000, guid: {}, type: 2}}\n", clip_guid)).unwrap();
+        fs::write(assets.join("Robot.controller.meta"), format!("fileFormatVersion: 2\nguid: {}\n", controller_guid)).unwrap();
+        fs::write(assets.join("Wave.anim"), "--- !u!74 &7400000\nAnimationClip:\n  m_PositionCurves:\n  - curve:\n      m_Curve: []\n    path: Arm/Hand\n  m_FloatCurves: []\n").unwrap();
+        fs::write(assets.join("Wave.anim.meta"), format!("fileFormatVersion: 2\nguid: {}\n", clip_guid)).unwrap();
+        fs::write(assets.join("Grab.cs"), "class Grab {\n  void Start() { var arm = GameObject.Find(\"Robot/Arm\"); }\n}\n").unwrap();
+        fs::write(assets.join("Main.unity"), format!("%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {{fileID: 2}}\n  - component: {{fileID: 3}}\n  m_Name: Robot\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {{fileID: 1}}\n  m_Children:\n  - {{fileID: 11}}\n  - {{fileID: 31}}\n  m_Father: {{fileID: 0}}\n\
+--- !u!95 &3\nAnimator:\n  m_GameObject: {{fileID: 1}}\n  m_Controller: {{fileID: 9100000, guid: {}, type: 2}}\n\
+--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {{fileID: 11}}\n  m_Name: Arm\n\
+--- !u!4 &11\nTransform:\n  m_GameObject: {{fileID: 10}}\n  m_Children: []\n  m_Father: {{fileID: 2}}\n\
+--- !u!1 &30\nGameObject:\n  m_Component:\n  - component: {{fileID: 31}}\n  - component: {{fileID: 32}}\n  m_Name: Limb\n\
+--- !u!4 &31\nTransform:\n  m_GameObject: {{fileID: 30}}\n  m_Children: []\n  m_Father: {{fileID: 2}}\n\
+--- !u!114 &32\nMonoBehaviour:\n  m_GameObject: {{fileID: 30}}\n  m_OnUse:\n    m_PersistentCalls:\n      m_Calls:\n      - m_Target: {{fileID: 32}}\n        m_MethodName: Toggle\n        m_Mode: 5\n        m_Arguments:\n          m_StringArgument: Arm\n        m_CallState: 2\n", controller_guid)).unwrap();
+
+        let mut scanner = Scanner::new();
+        let root = tmp.path().to_string_lossy().to_string();
+        let report = scanner.analyze_rename_impact(root.clone(), "Assets/Main.unity".to_string(), "10".to_string(), "Limb".to_string());
+        assert_eq!((report["old_name"].as_str(), report["new_path"].as_str()), (Some("Arm"), Some("Robot/Limb")));
+        let kinds: Vec<&str> = report["risks"].as_array().unwrap().iter().map(|r| r["kind"].as_str().unwrap()).collect();
+        assert_eq!(kinds, vec!["animation_path", "find_call", "name_conflict", "unity_event_argument"]);
+        assert_eq!(report["risks"][0]["file"], "Assets/Wave.anim");
+        assert_eq!(report["risks"][1]["line"], 2);
+
+        let missing = scanner.analyze_rename_impact(root, "Assets/Main.unity".to_string(), "99".to_string(), "X".to_string());
+        assert!(missing["is_error"].as_bool().unwrap());
+    }
+    #[test]
+    fn test_tag_validation_warns_on_undefined_tags_and_layers() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("Assets")).unwrap();
+        fs::create_dir_all(tmp.path().join("ProjectSettings")).unwrap();
+        fs::write(
+            tmp.path().join("ProjectSettings").join("TagManager.asset"),
+            "%YAML 1.1\n--- !u!78 &1\nTagManager:\n  tags:\n  - Enemy\n  layers:\n  - Default\n  - TransparentFX\n",
+        ).unwrap();
+        let scene = tmp.path().join("Assets").join("Main.unity");
+        let go = |id: u32, name: &str, tag: &str, layer: u32| format!("--- !u!1 &{}\nGameObject:\n  m_Component:\n  - component: {{fileID: {}}}\n  m_Layer: {}\n  m_Name: {}\n  m_TagString: {}\n  m_IsActive: 1\n--- !u!4 &{}\nTransform:\n  m_GameObject: {{fileID: {}}}\n  m_Children: []\n  m_Father: {{fileID: 0}}\n", id, id + 1, layer, name, tag, id + 1, id);
+        fs::write(&scene, format!("%YAML 1.1\n{}{}", go(1, "Grunt", "Enemy", 0), go(3, "Imported", "Boss", 9))).unwrap();
+        let file = scene.to_string_lossy().to_string();
+
+        let mut scanner = Scanner::new();
+        scanner.set_project_root(tmp.path().to_string_lossy().to_string(), None);
+        let inspection = scanner.inspect_all(file.clone(), false, false);
+        assert!(inspection.gameobjects.iter().all(|g| g.warnings.is_none()), "validation is off by default");
+
+        assert!(scanner.set_tag_validation(true));
+        let inspection = scanner.inspect_all(file.clone(), false, false);
+        let warnings = |name: &str| inspection.gameobjects.iter().find(|g| g.name == name).unwrap().warnings.clone();
+        assert_eq!(warnings("Grunt"), None);
+        assert_eq!(warnings("Imported").unwrap().len(), 2);
+
+        let options = InspectOptions {
+            file,
+            identifier: Some("Imported".to_string()),
+            include_properties: None,
+            verbose: None,
+            expand_nested: None,
+            max_nesting_depth: None,
+            max_property_bytes: None,
+            resolve_images: None,
+            fields: None,
+        };
+        let inspected = scanner.inspect(options).unwrap();
+        assert_eq!(inspected["warnings"][0], "Tag \"Boss\" is not defined in TagManager");
+    }
+
+    #[test]
+    fn test_property_histogram_across_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("Assets")).unwrap();
+        let light = |id: u32, intensity: &str| format!("--- !u!108 &{}\nLight:\n  m_GameObject: {{fileID: 1}}\n  m_Intensity: {}\n", id, intensity);
+        fs::write(tmp.path().join("Assets/Main.unity"), format!("%YAML 1.1\n{}{}", light(10, "1"), light(11, "2.5"))).unwrap();
+        fs::write(tmp.path().join("Assets/Lamp.prefab"), format!("%YAML 1.1\n{}", light(12, "1"))).unwrap();
+        fs::write(tmp.path().join("Assets/Other.prefab"), "%YAML 1.1\n--- !u!4 &1\nTransform:\n  m_Father: {fileID: 0}\n").unwrap();
+
+        let mut scanner = Scanner::new();
+        let histogram = scanner.property_histogram(tmp.path().to_string_lossy().to_string(), "Light".to_string(), "intensity".to_string(), None, None);
+        assert_eq!((histogram.samples, histogram.missing, histogram.numeric), (3, 0, true));
+        let bins: Vec<(&str, u32)> = histogram.bins.iter().map(|b| (b.label.as_str(), b.count)).collect();
+        assert_eq!(bins, vec![("1", 2), ("2.5", 1)]);
+        let mut files = histogram.files.clone();
+        files.sort();
+        assert_eq!(files, vec!["Assets/Lamp.prefab", "Assets/Main.unity"]);
+        assert!(!histogram.budget_exhausted);
+
+        let budget = ResourceBudget { max_files: Some(1), ..Default::default() };
+        let partial = scanner.property_histogram(tmp.path().to_string_lossy().to_string(), "Light".to_string(), "intensity".to_string(), None, Some(budget));
+        assert!(partial.budget_exhausted);
+        assert!(partial.files.len() <= 1);
+    }
+
+    #[test]
+    fn test_health_report() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("Assets/Scripts")).unwrap();
+        fs::write(tmp.path().join("Assets/Scripts/Mover.cs"), "class Mover {}").unwrap();
+        fs::write(tmp.path().join("Assets/Scripts/Mover.cs.meta"), "guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n").unwrap();
+        let scene = tmp.path().join("Assets/Main.unity");
+        fs::write(&scene, "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  - component: {fileID: 3}\n  - component: {fileID: 4}\n  m_Name: cube\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Father: {fileID: 0}\n\
+--- !u!114 &3\nMonoBehaviour:\n  m_GameObject: {fileID: 1}\n  m_Script: {fileID: 11500000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}\n\
+--- !u!114 &4\nMonoBehaviour:\n  m_GameObject: {fileID: 1}\n  m_Script: {fileID: 11500000, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 3}\n").unwrap();
+        let rules = tmp.path().join("rules.json");
+        fs::write(&rules, r#"{"naming": [{"pattern": "^[A-Z]"}]}"#).unwrap();
+
+        let mut scanner = Scanner::new();
+        let report = scanner.health_report(scene.to_string_lossy().to_string(), Some(rules.to_string_lossy().to_string()));
+        assert_eq!(report["file"], "Assets/Main.unity");
+        let category = |name: &str| report["categories"].as_array().unwrap().iter().find(|c| c["name"] == name).unwrap().clone();
+        let missing = category("missing_references");
+        assert_eq!((missing["error_count"].as_u64(), missing["score"].as_u64()), (Some(1), Some(80)));
+        assert!(missing["issues"][0]["message"].as_str().unwrap().contains("Missing script"));
+        assert_eq!(category("lint")["error_count"], 1);
+        assert!(report["skipped"].as_array().unwrap().is_empty());
+        assert!(report["score"].as_u64().unwrap() < 100);
+
+        let unscored = scanner.health_report(scene.to_string_lossy().to_string(), None);
+        assert_eq!(unscored["skipped"][0], "lint: no rules_path given");
+        assert_eq!(scanner.health_report(scene.to_string_lossy().to_string(), Some("missing.json".to_string()))["is_error"], true);
+    }
+
+    #[test]
+    fn test_subscribe_and_poll_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let scene = tmp.path().join("Main.unity");
+        let content = "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Name: A\n--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_LocalPosition: {x: 0, y: 0, z: 0}\n  m_Father: {fileID: 0}\n";
+        fs::write(&scene, content).unwrap();
+        let file = scene.to_string_lossy().to_string();
+        let mut scanner = Scanner::new();
+
+        let not_subscribed = scanner.poll_changes(file.clone());
+        assert!(!not_subscribed.success && not_subscribed.error.is_some());
+        assert_eq!(scanner.subscribe(file.clone())["object_count"], 1);
+        let unchanged = scanner.poll_changes(file.clone());
+        assert!(unchanged.success && !unchanged.changed);
+
+        fs::write(&scene, content.replace("{x: 0,", "{x: 2,")).unwrap();
+        let moved = scanner.poll_changes(file.clone());
+        assert!(moved.changed);
+        assert_eq!(moved.modified[0].changed_components, vec!["Transform"]);
+        assert!(!scanner.poll_changes(file.clone()).changed, "a poll consumes the changes it reports");
+
+        assert!(scanner.unsubscribe(file.clone()));
+        assert!(!scanner.unsubscribe(file));
+    }
+
+    #[test]
+    fn test_paginated_inspection_extreme_cursors() {
+        let content = "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Name: A\n  m_IsActive: 1\n--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Father: {fileID: 0}\n--- !u!1 &3\nGameObject:\n  m_Component:\n  - component: {fileID: 4}\n  m_Name: B\n  m_IsActive: 1\n--- !u!4 &4\nTransform:\n  m_GameObject: {fileID: 3}\n  m_Father: {fileID: 0}\n";
+        let page = |cursor: Option<i64>, page_size: Option<u32>| Scanner::new().inspect_all_paginated_from_content(PaginationOptions {
+            file: "Test.unity".to_string(),
+            include_properties: None,
+            verbose: None,
+            page_size,
+            cursor,
+            max_depth: None,
+            filter_component: None,
+            max_property_bytes: None,
+            sort: None,
+        }, content.to_string());
+
+        let first = page(None, Some(1));
+        assert_eq!((first.total, first.gameobjects.len(), first.next_cursor), (2, 1, Some(1)));
+        let past_end = page(Some(i64::MAX), Some(1000));
+        assert!(past_end.gameobjects.is_empty() && !past_end.truncated && past_end.next_cursor.is_none());
+        assert_eq!(past_end.cursor, i64::MAX);
+        let negative = page(Some(-5), None);
+        assert_eq!((negative.cursor, negative.gameobjects.len()), (0, 2));
+        let empty_page = page(Some(0), Some(0));
+        assert!(empty_page.next_cursor.is_none(), "a zero page size must not loop on the same cursor");
+    }
+
+    #[test]
+    fn test_hierarchy_sort_for_paginated_and_expanded() {
+        // Document order is Child, A, B; the editor shows B { Child }, A
+        let content = "%YAML 1.1\n--- !u!1 &5\nGameObject:\n  m_Component:\n  - component: {fileID: 6}\n  m_Name: Child\n  m_IsActive: 1\n--- !u!4 &6\nTransform:\n  m_GameObject: {fileID: 5}\n  m_Children: []\n  m_Father: {fileID: 4}\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Name: A\n  m_IsActive: 1\n--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Children: []\n  m_Father: {fileID: 0}\n\
+--- !u!1 &3\nGameObject:\n  m_Component:\n  - component: {fileID: 4}\n  m_Name: B\n  m_IsActive: 1\n--- !u!4 &4\nTransform:\n  m_GameObject: {fileID: 3}\n  m_Children:\n  - {fileID: 6}\n  m_Father: {fileID: 0}\n\
+--- !u!1660057539 &9223372036854775807\nSceneRoots:\n  m_Roots:\n  - {fileID: 4}\n  - {fileID: 2}\n";
+        let names = |sort: Option<&str>| {
+            let page = Scanner::new().inspect_all_paginated_from_content(PaginationOptions {
+                file: "Test.unity".to_string(),
+                include_properties: None,
+                verbose: None,
+                page_size: None,
+                cursor: None,
+                max_depth: None,
+                filter_component: None,
+                max_property_bytes: None,
+                sort: sort.map(str::to_string),
+            }, content.to_string());
+            (page.gameobjects.into_iter().map(|go| go.name).collect::<Vec<_>>(), page.error)
+        };
+        assert_eq!(names(None).0, vec!["Child", "A", "B"]);
+        assert_eq!(names(Some("hierarchy")).0, vec!["B", "Child", "A"]);
+        let (gameobjects, error) = names(Some("alphabetical"));
+        assert!(gameobjects.is_empty() && error.is_some_and(|e| e.contains("alphabetical")));
+
+        let tree = Scanner::new().inspect_expanded_from_content("Test.unity".to_string(), content.to_string(), None, Some("hierarchy".to_string()));
+        let roots: Vec<&str> = tree.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(roots, vec!["B", "A"]);
+        assert_eq!(tree[0].children[0].name, "Child");
+        let unsorted = Scanner::new().inspect_expanded_from_content("Test.unity".to_string(), content.to_string(), None, None);
+        assert_eq!(unsorted.iter().map(|n| n.name.as_str()).collect::<Vec<_>>(), vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_bulk_update_dry_run_diff_matches_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let scene = dir.path().join("Level.unity");
+        let content = "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Layer: 0\n  m_Name: Enemy\n  m_TagString: Untagged\n  m_IsActive: 1\n--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Children: []\n  m_Father: {fileID: 0}\n";
+        fs::write(&scene, content).unwrap();
+        let file = scene.to_string_lossy().to_string();
+        let selector = GameObjectSelector { name: Some("Enemy".to_string()), tag: None, layer: None, component_type: None, active: None, under: None };
+        let changes = GameObjectChanges { name: None, tag: None, layer: None, active: Some(false) };
+
+        let mut scanner = Scanner::new();
+        let preview = scanner.bulk_update(file.clone(), selector.clone(), changes.clone(), Some(true));
+        let diff = preview.diff.expect("dry run carries a diff");
+        assert!(diff.contains("\n-  m_IsActive: 1\n+  m_IsActive: 0\n"), "{}", diff);
+        assert_eq!(fs::read_to_string(&scene).unwrap(), content, "a dry run writes nothing");
+
+        let written = scanner.bulk_update(file, selector, changes, Some(false));
+        assert!(written.success && written.diff.is_none());
+        assert_eq!(common::diff::unified_diff(&scene.to_string_lossy(), content, &fs::read_to_string(&scene).unwrap()), diff);
+    }
+
+    #[test]
+    fn test_move_gameobject_between_files_keeps_each_files_line_endings() {
+        let dir = tempfile::tempdir().unwrap();
+        let (src, dst) = (dir.path().join("Level.unity"), dir.path().join("Other.unity"));
+        let src_content = "%YAML 1.1\r\n--- !u!1 &10\r\nGameObject:\r\n  m_Component:\r\n  - component: {fileID: 11}\r\n  m_Name: Mover\r\n--- !u!4 &11\r\nTransform:\r\n  m_GameObject: {fileID: 10}\r\n  m_Children: []\r\n  m_Father: {fileID: 0}\r\n--- !u!1 &20\r\nGameObject:\r\n  m_Component:\r\n  - component: {fileID: 21}\r\n  m_Name: Stays\r\n--- !u!4 &21\r\nTransform:\r\n  m_GameObject: {fileID: 20}\r\n  m_Children: []\r\n  m_Father: {fileID: 0}\r\n";
+        let dst_content = "%YAML 1.1\n--- !u!1 &100\nGameObject:\n  m_Component:\n  - component: {fileID: 101}\n  m_Name: Holder\n--- !u!4 &101\nTransform:\n  m_GameObject: {fileID: 100}\n  m_Children: []\n  m_Father: {fileID: 0}\n";
+        fs::write(&src, src_content).unwrap();
+        fs::write(&dst, dst_content).unwrap();
+        let (src_file, dst_file) = (src.to_string_lossy().to_string(), dst.to_string_lossy().to_string());
+
+        let preview = Scanner::new().move_gameobject_between_files(src_file.clone(), "10".into(), dst_file.clone(), None, None);
+        assert!(preview.success && preview.dry_run, "dry run by default");
+        assert_eq!(fs::read_to_string(&src).unwrap(), src_content);
+
+        let moved = Scanner::new().move_gameobject_between_files(src_file, "10".into(), dst_file, Some("100".into()), Some(false));
+        assert!(moved.success, "{:?}", moved.error);
+        let (src_after, dst_after) = (fs::read_to_string(&src).unwrap(), fs::read_to_string(&dst).unwrap());
+        assert!(!src_after.contains("Mover") && src_after.contains("  m_Name: Stays\r\n"));
+        assert_eq!(src_after.matches('\n').count(), src_after.matches("\r\n").count(), "source stays CRLF");
+        assert!(dst_after.contains("  m_Name: Mover\n") && !dst_after.contains('\r'), "destination stays LF");
+    }
+
+    #[test]
+    fn test_scaffold_file_is_a_dry_run_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("New.unity").to_string_lossy().to_string();
+        let spec = serde_json::json!({ "objects": [{ "name": "Root" }] });
+
+        let preview = Scanner::new().scaffold_file(file.clone(), spec.clone(), None, None);
+        assert!(preview.success && preview.dry_run, "{:?}", preview.error);
+        assert!(preview.content.is_some() && !Path::new(&file).exists());
+
+        let written = Scanner::new().scaffold_file(file.clone(), spec, None, Some(false));
+        assert!(written.success && Path::new(&file).exists());
+    }
+
+    #[test]
+    fn test_bulk_update_keeps_crlf() {
+        let dir = tempfile::tempdir().unwrap();
+        let scene = dir.path().join("Level.unity");
+        let content = "%YAML 1.1\r\n--- !u!1 &1\r\nGameObject:\r\n  m_Component:\r\n  - component: {fileID: 2}\r\n  m_Layer: 0\r\n  m_Name: Enemy\r\n  m_TagString: Untagged\r\n  m_IsActive: 1\r\n--- !u!4 &2\r\nTransform:\r\n  m_GameObject: {fileID: 1}\r\n  m_Father: {fileID: 0}\r\n";
+        fs::write(&scene, content).unwrap();
+        let selector = GameObjectSelector { name: Some("Enemy".to_string()), tag: None, layer: None, component_type: None, active: None, under: None };
+        let changes = GameObjectChanges { name: None, tag: None, layer: Some(8), active: None };
+
+        let written = Scanner::new().bulk_update(scene.to_string_lossy().to_string(), selector, changes, Some(false));
+        assert_eq!(written.changed_count, 1);
+        assert_eq!(fs::read_to_string(&scene).unwrap(), content.replace("m_Layer: 0", "m_Layer: 8"));
+    }
+
+    #[test]
+    fn test_count_objects_reads_crlf_files_as_is() {
+        let dir = tempfile::tempdir().unwrap();
+        let scene = dir.path().join("Level.unity");
+        let content = "%YAML 1.1\r\n--- !u!1 &1\r\nGameObject:\r\n  m_Name: Enemy\r\n--- !u!4 &2\r\nTransform: {}\r\n--- !u!1 &3 stripped\r\nGameObject:\r\n";
+        fs::write(&scene, content).unwrap();
+
+        let counts = Scanner::new().count_objects(scene.to_string_lossy().to_string()).unwrap();
+        assert_eq!(counts.total_blocks, 3);
+        assert_eq!(counts.gameobject_count, 2);
+        assert_eq!(counts.stripped_count, 1);
+        assert_eq!(counts.size_bytes, content.len() as i64, "size is the file's own, CRLF included");
+    }
 }
@@ -0,0 +1,228 @@
+use crate::common::{NavMeshAgentInfo, NavMeshObstacleInfo, NavMeshReport, OffMeshLinkInfo};
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::{field, flag, nested_field, number, owner_game_object, ref_file_id};
+
+const OFF_MESH_LINK_CLASS_ID: u32 = 191;
+const NAV_MESH_AGENT_CLASS_ID: u32 = 195;
+const NAV_MESH_SETTINGS_CLASS_ID: u32 = 196;
+const NAV_MESH_OBSTACLE_CLASS_ID: u32 = 208;
+
+/// Collect NavMeshAgents, NavMeshObstacles and OffMeshLinks of a scene and
+/// cross-check them against the scene's NavMeshSettings bake parameters.
+pub fn analyze_navigation(file: &str, content: &str) -> NavMeshReport {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+
+    let settings = blocks.iter().find(|(cid, _, _)| *cid == NAV_MESH_SETTINGS_CLASS_ID).map(|(_, _, body)| body.as_str());
+    let baked = |key: &str| {
+        settings.and_then(|s| nested_field(s, "m_BuildSettings", key)).and_then(|v| v.parse::<f64>().ok())
+    };
+    let has_baked_navmesh = settings
+        .and_then(|s| field(s, "m_NavMeshData"))
+        .and_then(ref_file_id)
+        .is_some_and(|id| id != "0");
+    let baked_agent_radius = baked("agentRadius");
+    let baked_agent_height = baked("agentHeight");
+
+    let owned = |class_id: u32| {
+        blocks.iter().filter(move |(cid, _, body)| *cid == class_id && field(body, "m_GameObject").is_some())
+    };
+
+    let agents: Vec<NavMeshAgentInfo> = owned(NAV_MESH_AGENT_CLASS_ID)
+        .map(|(_, file_id, body)| {
+            let (game_object_id, game_object_name) = owner_game_object(&index, body);
+            let agent_type_id = field(body, "m_AgentTypeID").and_then(|v| v.parse().ok()).unwrap_or(0);
+            let radius = number(body, "m_Radius");
+            let height = number(body, "m_Height");
+            let mut issues = Vec::new();
+            // Bake settings describe the default (humanoid, id 0) agent type only
+            if agent_type_id == 0 {
+                if baked_agent_radius.is_some_and(|r| radius > r) {
+                    issues.push("radius_exceeds_baked_agent".to_string());
+                }
+                if baked_agent_height.is_some_and(|h| height > h) {
+                    issues.push("height_exceeds_baked_agent".to_string());
+                }
+            }
+            if number(body, "m_Speed") <= 0.0 {
+                issues.push("zero_speed".to_string());
+            }
+            NavMeshAgentInfo {
+                file_id: file_id.clone(),
+                game_object_id,
+                game_object_name,
+                enabled: flag(body, "m_Enabled"),
+                agent_type_id,
+                radius,
+                height,
+                speed: number(body, "m_Speed"),
+                angular_speed: number(body, "m_AngularSpeed"),
+                acceleration: number(body, "m_Acceleration"),
+                stopping_distance: number(body, "m_StoppingDistance"),
+                base_offset: number(body, "m_BaseOffset"),
+                avoidance_priority: number(body, "avoidancePriority") as i32,
+                issues,
+            }
+        })
+        .collect();
+
+    let obstacles: Vec<NavMeshObstacleInfo> = owned(NAV_MESH_OBSTACLE_CLASS_ID)
+        .map(|(_, file_id, body)| {
+            let (game_object_id, game_object_name) = owner_game_object(&index, body);
+            NavMeshObstacleInfo {
+                file_id: file_id.clone(),
+                game_object_id,
+                game_object_name,
+                enabled: flag(body, "m_Enabled"),
+                shape: if field(body, "m_Shape") == Some("1") { "box" } else { "capsule" }.to_string(),
+                carve: flag(body, "m_Carve"),
+                extents: field(body, "m_Extents").unwrap_or("").to_string(),
+            }
+        })
+        .collect();
+
+    // OffMeshLink endpoints reference Transforms; report the owning GameObject's name
+    let endpoint_name = |value: Option<&str>| -> Option<String> {
+        let transform_id = value.and_then(ref_file_id).filter(|id| *id != "0")?;
+        let (_, transform) = index.get(transform_id)?;
+        let (_, name) = owner_game_object(&index, transform);
+        Some(name)
+    };
+    let links: Vec<OffMeshLinkInfo> = owned(OFF_MESH_LINK_CLASS_ID)
+        .map(|(_, file_id, body)| {
+            let (game_object_id, game_object_name) = owner_game_object(&index, body);
+            let start = endpoint_name(field(body, "m_Start"));
+            let end = endpoint_name(field(body, "m_End"));
+            let mut issues = Vec::new();
+            if start.is_none() || end.is_none() {
+                issues.push("missing_endpoint".to_string());
+            }
+            OffMeshLinkInfo {
+                file_id: file_id.clone(),
+                game_object_id,
+                game_object_name,
+                enabled: flag(body, "m_Enabled"),
+                start,
+                end,
+                bi_directional: flag(body, "m_BiDirectional"),
+                activated: flag(body, "m_Activated"),
+                cost_override: number(body, "m_CostOverride"),
+                issues,
+            }
+        })
+        .collect();
+
+    let mut issues = Vec::new();
+    if !has_baked_navmesh && (!agents.is_empty() || !links.is_empty()) {
+        issues.push("agents_without_baked_navmesh".to_string());
+    }
+
+    NavMeshReport {
+        file: file.to_string(),
+        has_baked_navmesh,
+        baked_agent_radius,
+        baked_agent_height,
+        baked_agent_slope: baked("agentSlope"),
+        baked_agent_climb: baked("agentClimb"),
+        agents,
+        obstacles,
+        links,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1
+--- !u!196 &4
+NavMeshSettings:
+  serializedVersion: 2
+  m_BuildSettings:
+    serializedVersion: 2
+    agentTypeID: 0
+    agentRadius: 0.5
+    agentHeight: 2
+    agentSlope: 45
+    agentClimb: 0.4
+  m_NavMeshData: {fileID: 0}
+--- !u!1 &100
+GameObject:
+  m_Component:
+  - component: {fileID: 101}
+  - component: {fileID: 102}
+  m_Name: Guard
+  m_IsActive: 1
+--- !u!4 &101
+Transform:
+  m_GameObject: {fileID: 100}
+  m_Father: {fileID: 0}
+--- !u!195 &102
+NavMeshAgent:
+  m_GameObject: {fileID: 100}
+  m_Enabled: 1
+  m_AgentTypeID: 0
+  m_Radius: 0.8
+  m_Speed: 3.5
+  m_Acceleration: 8
+  avoidancePriority: 50
+  m_AngularSpeed: 120
+  m_StoppingDistance: 0
+  m_Height: 2
+  m_BaseOffset: 1
+--- !u!1 &200
+GameObject:
+  m_Component:
+  - component: {fileID: 201}
+  - component: {fileID: 202}
+  m_Name: Ledge
+  m_IsActive: 1
+--- !u!4 &201
+Transform:
+  m_GameObject: {fileID: 200}
+  m_Father: {fileID: 0}
+--- !u!191 &202
+OffMeshLink:
+  m_GameObject: {fileID: 200}
+  m_Enabled: 1
+  m_AreaIndex: 2
+  m_Start: {fileID: 101}
+  m_End: {fileID: 0}
+  m_CostOverride: -1
+  m_BiDirectional: 1
+  m_Activated: 1
+--- !u!208 &203
+NavMeshObstacle:
+  m_GameObject: {fileID: 200}
+  m_Enabled: 1
+  m_Shape: 1
+  m_Extents: {x: 0.5, y: 0.5, z: 0.5}
+  m_Carve: 1
+";
+
+    #[test]
+    fn test_analyze_navigation() {
+        let report = analyze_navigation("Level.unity", SCENE);
+        assert!(!report.has_baked_navmesh);
+        assert_eq!(report.baked_agent_radius, Some(0.5));
+        assert_eq!(report.baked_agent_slope, Some(45.0));
+        assert_eq!(report.issues, vec!["agents_without_baked_navmesh".to_string()]);
+
+        assert_eq!(report.agents.len(), 1);
+        let guard = &report.agents[0];
+        assert_eq!(guard.game_object_name, "Guard");
+        assert_eq!(guard.speed, 3.5);
+        assert_eq!(guard.avoidance_priority, 50);
+        assert_eq!(guard.issues, vec!["radius_exceeds_baked_agent".to_string()]);
+
+        assert_eq!(report.links.len(), 1);
+        assert_eq!(report.links[0].start.as_deref(), Some("Guard"));
+        assert_eq!(report.links[0].end, None);
+        assert_eq!(report.links[0].issues, vec!["missing_endpoint".to_string()]);
+
+        assert_eq!(report.obstacles.len(), 1);
+        assert_eq!(report.obstacles[0].shape, "box");
+        assert!(report.obstacles[0].carve);
+    }
+}
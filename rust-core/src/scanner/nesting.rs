@@ -0,0 +1,162 @@
+//! Prefab nesting graph: which prefabs each prefab instantiates (nested prefabs and variant
+//! bases), with cycles and over-deep nesting chains.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+use crate::common::{self, PrefabNestingChain, PrefabNestingReport, META_GUID_RE, SOURCE_PREFAB_RE};
+use crate::csharp::deps::cycles;
+use crate::walker::{walk_project_files, NapiWalkOptions};
+
+/// Nesting depth reported when no threshold is given.
+pub const DEFAULT_MAX_NESTING_DEPTH: u32 = 8;
+
+/// Prefab → prefabs it instantiates, by project-relative path. Sources that are not project
+/// prefabs (missing, or in an unwalked package) are left out.
+fn nesting_graph(project_root: &Path, include_packages: bool) -> BTreeMap<String, BTreeSet<String>> {
+    let options = NapiWalkOptions { include_packages: Some(include_packages), follow_symlinks: None };
+    let files = walk_project_files(project_root.to_string_lossy().to_string(), vec![".prefab".to_string()], None, Some(options));
+    let prefabs: Vec<(String, String)> = files.iter()
+        .map(|file| {
+            let relative = Path::new(file).strip_prefix(project_root).unwrap_or(Path::new(file)).to_string_lossy().replace('\\', "/");
+            (file.clone(), relative)
+        })
+        .collect();
+    let path_by_guid: HashMap<String, String> = prefabs.iter()
+        .filter_map(|(file, relative)| {
+            let meta = common::read_unity_file(format!("{}.meta", file)).ok()?;
+            Some((META_GUID_RE.captures(&meta)?[1].to_string(), relative.clone()))
+        })
+        .collect();
+
+    prefabs.iter()
+        .map(|(file, relative)| {
+            let content = common::read_unity_file(file).unwrap_or_default();
+            let sources = SOURCE_PREFAB_RE.captures_iter(&content)
+                .filter_map(|c| path_by_guid.get(&c[1]).cloned())
+                .collect();
+            (relative.clone(), sources)
+        })
+        .collect()
+}
+
+/// Deepest nesting chain below each prefab (the prefab first), ignoring edges inside cycles.
+fn deepest_chains(edges: &BTreeMap<String, BTreeSet<String>>, cyclic: &BTreeSet<(String, String)>) -> BTreeMap<String, Vec<String>> {
+    fn visit(
+        node: &str,
+        edges: &BTreeMap<String, BTreeSet<String>>,
+        cyclic: &BTreeSet<(String, String)>,
+        memo: &mut BTreeMap<String, Vec<String>>,
+    ) -> Vec<String> {
+        if let Some(chain) = memo.get(node) {
+            return chain.clone();
+        }
+        let mut deepest: Vec<String> = Vec::new();
+        for next in edges.get(node).into_iter().flatten() {
+            if cyclic.contains(&(node.to_string(), next.clone())) {
+                continue;
+            }
+            let chain = visit(next, edges, cyclic, memo);
+            if chain.len() > deepest.len() {
+                deepest = chain;
+            }
+        }
+        deepest.insert(0, node.to_string());
+        memo.insert(node.to_string(), deepest.clone());
+        deepest
+    }
+
+    let mut memo = BTreeMap::new();
+    for node in edges.keys() {
+        visit(node, edges, cyclic, &mut memo);
+    }
+    memo
+}
+
+/// Follow nested-prefab and variant-base references between the project's prefabs and report
+/// reference cycles and nesting chains deeper than `max_depth` (each reported from its
+/// outermost prefab only).
+pub fn detect(project_root: &str, max_depth: Option<u32>, include_packages: bool) -> PrefabNestingReport {
+    let max_depth = max_depth.unwrap_or(DEFAULT_MAX_NESTING_DEPTH);
+    let edges = nesting_graph(Path::new(project_root), include_packages);
+
+    let mut groups = cycles(&edges);
+    // A prefab that instantiates itself is a cycle of one
+    groups.extend(edges.iter().filter(|(prefab, sources)| sources.contains(*prefab)).map(|(prefab, _)| vec![prefab.clone()]));
+    groups.sort();
+    let cyclic: BTreeSet<(String, String)> = groups.iter()
+        .flat_map(|group| {
+            edges.iter()
+                .filter(|(from, _)| group.contains(from))
+                .flat_map(|(from, sources)| sources.iter().filter(|s| group.contains(s)).map(|s| (from.clone(), s.clone())))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let chains = deepest_chains(&edges, &cyclic);
+    let too_deep = |prefab: &str| chains.get(prefab).is_some_and(|c| c.len() as u32 - 1 > max_depth);
+    let mut deep_chains: Vec<PrefabNestingChain> = chains.iter()
+        .filter(|(prefab, _)| too_deep(prefab))
+        // Skip prefabs that are themselves nested in a reported chain
+        .filter(|(prefab, _)| !edges.iter().any(|(parent, sources)| sources.contains(*prefab) && too_deep(parent) && !cyclic.contains(&(parent.clone(), prefab.to_string()))))
+        .map(|(prefab, chain)| PrefabNestingChain { prefab: prefab.clone(), depth: chain.len() as u32 - 1, chain: chain.clone() })
+        .collect();
+    deep_chains.sort_by(|a, b| b.depth.cmp(&a.depth).then_with(|| a.prefab.cmp(&b.prefab)));
+
+    PrefabNestingReport {
+        project_root: project_root.to_string(),
+        prefab_count: edges.len() as u32,
+        max_depth,
+        cycles: groups,
+        deep_chains,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_prefab(root: &Path, name: &str, guid: &str, sources: &[&str]) {
+        let instances: String = sources.iter().enumerate()
+            .map(|(i, source)| format!("--- !u!1001 &{}\nPrefabInstance:\n  m_Modification:\n    m_Modifications: []\n  m_SourcePrefab: {{fileID: 100100000, guid: {}, type: 3}}\n", 500 + i, source))
+            .collect();
+        let path = root.join("Assets").join(format!("{}.prefab", name));
+        fs::write(&path, format!("%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Name: {}\n{}", name, instances)).unwrap();
+        fs::write(format!("{}.meta", path.display()), format!("fileFormatVersion: 2\nguid: {}\n", guid)).unwrap();
+    }
+
+    fn guid(n: u32) -> String {
+        format!("{:032x}", n)
+    }
+
+    #[test]
+    fn test_detects_cycles_and_deep_chains() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("Assets")).unwrap();
+        // Chain: A → B → C → D; cycle: X ↔ Y; self-reference: S; Missing source ignored
+        write_prefab(root, "A", &guid(1), &[&guid(2)]);
+        write_prefab(root, "B", &guid(2), &[&guid(3), &guid(99)]);
+        write_prefab(root, "C", &guid(3), &[&guid(4)]);
+        write_prefab(root, "D", &guid(4), &[]);
+        write_prefab(root, "X", &guid(5), &[&guid(6)]);
+        write_prefab(root, "Y", &guid(6), &[&guid(5), &guid(4)]);
+        write_prefab(root, "S", &guid(7), &[&guid(7)]);
+
+        let report = detect(&root.to_string_lossy(), Some(2), false);
+        assert_eq!(report.prefab_count, 7);
+        assert_eq!(report.cycles, vec![
+            vec!["Assets/S.prefab".to_string()],
+            vec!["Assets/X.prefab".to_string(), "Assets/Y.prefab".to_string()],
+        ]);
+        assert_eq!(report.deep_chains.len(), 1);
+        assert_eq!(report.deep_chains[0].prefab, "Assets/A.prefab");
+        assert_eq!(report.deep_chains[0].depth, 3);
+        assert_eq!(report.deep_chains[0].chain, vec!["Assets/A.prefab", "Assets/B.prefab", "Assets/C.prefab", "Assets/D.prefab"]);
+
+        let relaxed = detect(&root.to_string_lossy(), None, false);
+        assert!(relaxed.deep_chains.is_empty());
+        assert_eq!(relaxed.max_depth, DEFAULT_MAX_NESTING_DEPTH);
+    }
+}
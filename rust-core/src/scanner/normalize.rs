@@ -0,0 +1,44 @@
+use super::transplant::split_raw_blocks;
+
+/// Canonical form of Unity YAML for semantic diffing: blocks sorted by fileID (then class ID),
+/// trailing whitespace and blank lines stripped, LF line endings.
+/// Property and list order inside blocks is kept (component and child order are meaningful).
+/// The output is for comparison only and must never be written back over a scene.
+pub fn normalize(content: &str) -> (String, usize) {
+    let content = content.replace("\r\n", "\n");
+    let (preamble, mut blocks) = split_raw_blocks(&content);
+    blocks.sort_by_key(|block| (block.file_id.parse::<i128>().unwrap_or(i128::MAX), block.class_id));
+
+    let mut out = String::with_capacity(content.len());
+    for line in preamble.lines().chain(blocks.iter().flat_map(|b| b.text.lines())) {
+        let line = line.trim_end();
+        if !line.is_empty() {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    (out, blocks.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_sorts_blocks_and_whitespace() {
+        let a = "%YAML 1.1\r\n%TAG !u! tag:unity3d.com,2011:\r\n--- !u!4 &20\r\nTransform:\r\n  m_GameObject: {fileID: 3}   \r\n--- !u!1 &3\r\nGameObject:\r\n  m_Name: \r\n\r\n";
+        let b = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!1 &3\nGameObject:\n  m_Name:\n--- !u!4 &20\nTransform:\n  m_GameObject: {fileID: 3}\n";
+        let (normalized, count) = normalize(a);
+        assert_eq!(count, 2);
+        assert_eq!(normalized, b);
+        assert_eq!(normalize(b).0, normalized);
+    }
+
+    #[test]
+    fn test_normalize_orders_negative_and_large_ids_numerically() {
+        let content = "--- !u!1 &9000000000000000000\nGameObject:\n--- !u!1 &10\nGameObject:\n--- !u!1 &-5\nGameObject:\n--- !u!1 &9\nGameObject:\n";
+        let (normalized, _) = normalize(content);
+        let ids: Vec<&str> = normalized.lines().filter_map(|l| l.split('&').nth(1)).collect();
+        assert_eq!(ids, vec!["-5", "9", "10", "9000000000000000000"]);
+    }
+}
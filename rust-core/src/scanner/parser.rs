@@ -1,6 +1,7 @@
 use regex::Regex;
 use std::collections::HashMap;
 use crate::common::GameObject;
+use crate::telemetry::{self, Phase};
 use super::config::ComponentConfig;
 
 /// Pre-indexed block lookup for O(1) access by file_id.
@@ -34,6 +35,28 @@ impl BlockIndex {
     }
 }
 
+/// Find the block whose header is exactly `--- !u!{class_id} &{file_id}`, header included.
+///
+/// The header must start a line and the id must be followed by whitespace or the end of
+/// input, so `&12` never matches `&123`. The block runs to the next header line or EOF.
+pub fn find_block<'a>(content: &'a str, class_id: u32, file_id: &str) -> Option<&'a str> {
+    let header = format!("--- !u!{} &{}", class_id, file_id);
+    let mut from = 0;
+    while let Some(pos) = content[from..].find(&header) {
+        let start = from + pos;
+        let end = start + header.len();
+        let at_line_start = start == 0 || content.as_bytes()[start - 1] == b'\n';
+        let terminated = content[end..].chars().next().is_none_or(char::is_whitespace);
+        if at_line_start && terminated {
+            let block_end = content[end..].find("\n--- !u!")
+                .map_or(content.len(), |p| end + p + 1);
+            return Some(&content[start..block_end]);
+        }
+        from = end;
+    }
+    None
+}
+
 /// Unity YAML parser for extracting blocks and data
 pub struct UnityYamlParser;
 
@@ -45,6 +68,7 @@ impl UnityYamlParser {
 
     /// Extract all GameObjects from Unity YAML content with custom config
     pub fn extract_gameobjects_with_config(content: &str, config: &ComponentConfig) -> Vec<GameObject> {
+        let _parse = telemetry::phase(Phase::Parse);
         // Use (?s) for DOTALL mode to match across newlines
         // Use \n (not \s*\n) after fileID to reject stripped blocks like "--- !u!1 &123 stripped"
         // which lack m_Name/m_IsActive and cause the lazy .*? to bleed into the next block
@@ -69,16 +93,7 @@ impl UnityYamlParser {
 
     /// Extract a specific block by class type and file ID
     pub fn extract_block(content: &str, class_id: u32, file_id: &str) -> Option<String> {
-        let header = format!("--- !u!{} &{}", class_id, file_id);
-        let start_pos = content.find(&header)?;
-        let after_header = &content[start_pos..];
-
-        // Find the end of this block (start of next block or end of content)
-        let end_offset = after_header[header.len()..].find("--- !u!")
-            .map(|pos| header.len() + pos)
-            .unwrap_or(after_header.len());
-
-        Some(after_header[..end_offset].to_string())
+        find_block(content, class_id, file_id).map(str::to_string)
     }
 
     /// Extract GameObject block by file ID
@@ -108,7 +123,8 @@ impl UnityYamlParser {
 
     /// Get all blocks from content, indexed by file ID
     pub fn parse_all_blocks(content: &str) -> Vec<(u32, String, String)> {
-        let pattern = Regex::new(r"--- !u!(\d+) &(-?\d+)(?: stripped)?\s*\n")
+        let _parse = telemetry::phase(Phase::Parse);
+        let pattern = Regex::new(r"(?m)^--- !u!(\d+) &(-?\d+)(?: stripped)?(?:\s*\n|[ \t]*\z)")
             .expect("Invalid regex");
 
         let mut blocks = Vec::new();
@@ -292,4 +308,65 @@ GameObject:
         assert!(index.get("30").is_some());
         assert!(index.get("40").is_some());
     }
+
+    // Conformance fixtures trimmed from files saved by different Unity versions.
+    const UNITY_2019_SCENE: &str = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!29 &1\nOcclusionCullingSettings:\n  m_ObjectHideFlags: 0\n  serializedVersion: 2\n--- !u!104 &2\nRenderSettings:\n  m_Fog: 0\n--- !u!157 &3\nLightmapSettings:\n  serializedVersion: 11\n--- !u!196 &4\nNavMeshSettings:\n  serializedVersion: 2\n--- !u!1 &12\nGameObject:\n  m_Component:\n  - component: {fileID: 123}\n  m_Name: Short\n  m_IsActive: 1\n--- !u!1 &123\nGameObject:\n  m_Component:\n  - component: {fileID: 1234}\n  m_Name: Long\n  m_IsActive: 1\n--- !u!4 &123\nTransform:\n  m_GameObject: {fileID: 12}\n  m_Children: []\n  m_Father: {fileID: 0}\n  m_RootOrder: 0\n--- !u!4 &1234\nTransform:\n  m_GameObject: {fileID: 123}\n  m_Children: []\n  m_Father: {fileID: 0}\n  m_RootOrder: 1\n";
+
+    const UNITY_2022_SCENE: &str = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!29 &1\nOcclusionCullingSettings:\n  serializedVersion: 2\n--- !u!1001 &5000\nPrefabInstance:\n  m_ObjectHideFlags: 0\n  serializedVersion: 2\n  m_Modification:\n    serializedVersion: 3\n    m_TransformParent: {fileID: 0}\n    m_Modifications: []\n  m_SourcePrefab: {fileID: 100100000, guid: 0123456789abcdef0123456789abcdef, type: 3}\n--- !u!4 &500 stripped\nTransform:\n  m_CorrespondingSourceObject: {fileID: 400000, guid: 0123456789abcdef0123456789abcdef, type: 3}\n  m_PrefabInstance: {fileID: 5000}\n--- !u!1 &-8211\nGameObject:\n  m_Name: Negative\n  m_IsActive: 1\n--- !u!1660057539 &9223372036854775807\nSceneRoots:\n  m_ObjectHideFlags: 0\n  m_Roots:\n  - {fileID: 5000}";
+
+    #[test]
+    fn test_find_block_does_not_match_id_prefix() {
+        let short = find_block(UNITY_2019_SCENE, 1, "12").expect("&12 block");
+        assert!(short.starts_with("--- !u!1 &12\n"));
+        assert!(short.contains("m_Name: Short") && !short.contains("m_Name: Long"));
+
+        let long = find_block(UNITY_2019_SCENE, 4, "123").expect("&123 transform");
+        assert!(long.contains("m_RootOrder: 0") && !long.contains("m_RootOrder: 1"));
+        assert!(find_block(UNITY_2019_SCENE, 4, "12").is_none());
+        assert!(find_block(UNITY_2019_SCENE, 1, "1").is_none());
+        assert_eq!(UnityYamlParser::extract_gameobject_block(UNITY_2019_SCENE, "123").unwrap().lines().nth(4), Some("  m_Name: Long"));
+    }
+
+    #[test]
+    fn test_find_block_stripped_negative_and_unterminated() {
+        let stripped = find_block(UNITY_2022_SCENE, 4, "500").expect("stripped transform");
+        assert!(stripped.starts_with("--- !u!4 &500 stripped\n"));
+        assert!(stripped.ends_with("m_PrefabInstance: {fileID: 5000}\n"));
+        assert!(find_block(UNITY_2022_SCENE, 1001, "50").is_none());
+        assert!(find_block(UNITY_2022_SCENE, 1, "-8211").unwrap().contains("m_Name: Negative"));
+        assert!(find_block(UNITY_2022_SCENE, 1, "8211").is_none());
+
+        let roots = find_block(UNITY_2022_SCENE, 1660057539, "9223372036854775807").expect("last block");
+        assert!(roots.ends_with("  - {fileID: 5000}"));
+    }
+
+    #[test]
+    fn test_parse_all_blocks_conformance() {
+        let ids = |content: &str| UnityYamlParser::parse_all_blocks(content).into_iter()
+            .map(|(class_id, file_id, _)| (class_id, file_id))
+            .collect::<Vec<_>>();
+        let owned = |pairs: &[(u32, &str)]| pairs.iter().map(|(c, f)| (*c, f.to_string())).collect::<Vec<_>>();
+
+        assert_eq!(ids(UNITY_2019_SCENE), owned(&[(29, "1"), (104, "2"), (157, "3"), (196, "4"), (1, "12"), (1, "123"), (4, "123"), (4, "1234")]));
+        assert_eq!(ids(UNITY_2022_SCENE), owned(&[(29, "1"), (1001, "5000"), (4, "500"), (1, "-8211"), (1660057539, "9223372036854775807")]));
+
+        for content in [UNITY_2019_SCENE, UNITY_2022_SCENE] {
+            for (class_id, file_id, body) in UnityYamlParser::parse_all_blocks(content) {
+                assert!(!body.starts_with("---") && body.contains(":\n"), "body of &{} starts at its type line", file_id);
+                let block = find_block(content, class_id, &file_id).expect("every parsed block is findable");
+                assert!(block.ends_with(&body), "find_block and parse_all_blocks agree on &{}", file_id);
+            }
+        }
+
+        // A header on the last line with no trailing newline still yields an (empty) block
+        let truncated = "--- !u!1 &1\nGameObject:\n  m_Name: A\n--- !u!4 &2 stripped";
+        assert_eq!(ids(truncated), owned(&[(1, "1"), (4, "2")]));
+        assert_eq!(UnityYamlParser::parse_all_blocks(truncated)[1].2, "");
+        assert!(find_block(truncated, 4, "2").is_some());
+
+        // Header lookalikes inside values do not start blocks
+        let quoted = "--- !u!114 &7\nMonoBehaviour:\n  note: see --- !u!1 &8 for details\n";
+        assert_eq!(ids(quoted), owned(&[(114, "7")]));
+        assert!(find_block(quoted, 1, "8").is_none());
+    }
 }
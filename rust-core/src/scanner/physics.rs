@@ -0,0 +1,268 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::common::PhysicsObjectInfo;
+use super::config::ComponentConfig;
+use super::gameobject::{build_parent_map, extract_layer};
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::{field, flag, ref_file_id};
+
+const RIGIDBODY_CLASS_ID: u32 = 54;
+const RIGIDBODY_2D_CLASS_ID: u32 = 50;
+const MESH_COLLIDER_CLASS_ID: u32 = 64;
+/// Components that animate a GameObject's transform.
+const ANIMATION_CLASS_IDS: &[u32] = &[95, 111]; // Animator, Animation
+
+/// 3D collider class IDs.
+const COLLIDER_CLASS_IDS: &[u32] = &[
+    65,  // BoxCollider
+    64,  // MeshCollider
+    135, // SphereCollider
+    136, // CapsuleCollider
+    146, // WheelCollider
+    154, // TerrainCollider
+];
+
+/// 2D collider class IDs.
+const COLLIDER_2D_CLASS_IDS: &[u32] = &[
+    58, // CircleCollider2D
+    60, // PolygonCollider2D
+    61, // BoxCollider2D
+    66, // CompositeCollider2D
+    68, // EdgeCollider2D
+    70, // CapsuleCollider2D
+];
+
+/// Collision masks and layer names from ProjectSettings, when available.
+pub struct PhysicsSettings<'a> {
+    pub layer_names: &'a [Option<String>],
+    pub masks_3d: Option<&'a [u32]>,
+    pub masks_2d: Option<&'a [u32]>,
+}
+
+#[derive(Default)]
+struct PhysicsComponents {
+    rigidbody: Option<(u32, String)>,
+    colliders: Vec<(u32, String, String)>,
+    animated: bool,
+}
+
+/// Audit Rigidbody/Collider pairs of a scene or prefab.
+pub fn audit_physics(content: &str, config: &ComponentConfig, settings: &PhysicsSettings) -> Vec<PhysicsObjectInfo> {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+    let parents = build_parent_map(&blocks, config);
+
+    // Group physics-relevant components by owning GameObject (sorted for stable output)
+    let mut by_go: BTreeMap<String, PhysicsComponents> = BTreeMap::new();
+    for (class_id, _, body) in &blocks {
+        let is_rigidbody = *class_id == RIGIDBODY_CLASS_ID || *class_id == RIGIDBODY_2D_CLASS_ID;
+        let is_collider = COLLIDER_CLASS_IDS.contains(class_id) || COLLIDER_2D_CLASS_IDS.contains(class_id);
+        let is_animation = ANIMATION_CLASS_IDS.contains(class_id);
+        if !is_rigidbody && !is_collider && !is_animation {
+            continue;
+        }
+        let Some(go) = field(body, "m_GameObject").and_then(ref_file_id) else {
+            continue;
+        };
+        let entry = by_go.entry(go.to_string()).or_default();
+        let type_name = body.lines().next().unwrap_or("").trim_end_matches(':').to_string();
+        if is_rigidbody {
+            entry.rigidbody = Some((*class_id, body.clone()));
+        } else if is_collider {
+            entry.colliders.push((*class_id, type_name, body.clone()));
+        } else {
+            entry.animated = true;
+        }
+    }
+
+    let ancestors = |go: &str| -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = parents.get(go);
+        while let Some(parent) = current {
+            if !seen.insert(parent.clone()) {
+                break;
+            }
+            chain.push(parent.clone());
+            current = parents.get(parent);
+        }
+        chain
+    };
+    let has_rigidbody = |go: &str| by_go.get(go).is_some_and(|c| c.rigidbody.is_some());
+    let is_animated = |go: &str| by_go.get(go).is_some_and(|c| c.animated);
+
+    let mut results = Vec::new();
+    for (go_id, comps) in &by_go {
+        if comps.rigidbody.is_none() && comps.colliders.is_empty() {
+            continue;
+        }
+        let go_block = index.get_by_class_and_id(config.gameobject_class_id, go_id).unwrap_or("");
+        let layer = extract_layer(go_block);
+        let chain = ancestors(go_id);
+        let mut issues = Vec::new();
+
+        let is_kinematic = comps.rigidbody.as_ref().is_some_and(|(class_id, body)| {
+            if *class_id == RIGIDBODY_2D_CLASS_ID {
+                // Rigidbody2D: m_BodyType 1 = Kinematic, 2 = Static
+                matches!(field(body, "m_BodyType"), Some("1") | Some("2"))
+            } else {
+                flag(body, "m_IsKinematic")
+            }
+        });
+
+        let body_in_hierarchy = comps.rigidbody.is_some() || chain.iter().any(|a| has_rigidbody(a));
+        let moving = is_animated(go_id) || chain.iter().any(|a| is_animated(a));
+        if !comps.colliders.is_empty() && !body_in_hierarchy && moving {
+            issues.push("moving_collider_without_rigidbody".to_string());
+        }
+
+        if let Some((rb_class, _)) = &comps.rigidbody {
+            if !is_kinematic && comps.colliders.iter().any(|(class_id, _, body)| {
+                *class_id == MESH_COLLIDER_CLASS_ID && !flag(body, "m_Convex")
+            }) {
+                issues.push("non_convex_mesh_collider_on_rigidbody".to_string());
+            }
+
+            let uses_2d = *rb_class == RIGIDBODY_2D_CLASS_ID;
+            let mixed = comps.colliders.iter().any(|(class_id, _, _)| {
+                if uses_2d { COLLIDER_CLASS_IDS.contains(class_id) } else { COLLIDER_2D_CLASS_IDS.contains(class_id) }
+            });
+            if mixed {
+                issues.push("mixed_2d_3d_physics".to_string());
+            }
+
+            if comps.colliders.is_empty() && !has_descendant_collider(go_id, &by_go, &parents) {
+                issues.push("rigidbody_without_collider".to_string());
+            }
+
+            let masks = if uses_2d { settings.masks_2d } else { settings.masks_3d };
+            if masks.and_then(|m| m.get(layer as usize)).is_some_and(|mask| *mask == 0) {
+                issues.push("layer_collides_with_nothing".to_string());
+            }
+        }
+
+        results.push(PhysicsObjectInfo {
+            game_object_id: go_id.clone(),
+            game_object_name: field(go_block, "m_Name").unwrap_or("").to_string(),
+            layer,
+            layer_name: settings.layer_names.get(layer as usize).cloned().flatten(),
+            rigidbody: comps.rigidbody.as_ref().map(|(class_id, _)| {
+                if *class_id == RIGIDBODY_2D_CLASS_ID { "Rigidbody2D" } else { "Rigidbody" }.to_string()
+            }),
+            is_kinematic,
+            colliders: comps.colliders.iter().map(|(_, name, _)| name.clone()).collect(),
+            issues,
+        });
+    }
+
+    results
+}
+
+/// Compound colliders: a Rigidbody uses colliders on any descendant GameObject.
+fn has_descendant_collider(
+    go_id: &str,
+    by_go: &BTreeMap<String, PhysicsComponents>,
+    parents: &HashMap<String, String>,
+) -> bool {
+    by_go.iter()
+        .filter(|(_, comps)| !comps.colliders.is_empty())
+        .any(|(candidate, _)| {
+            let mut seen = HashSet::new();
+            let mut current = parents.get(candidate);
+            while let Some(parent) = current {
+                if parent == go_id {
+                    return true;
+                }
+                if !seen.insert(parent) {
+                    break;
+                }
+                current = parents.get(parent);
+            }
+            false
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn go(id: u32, name: &str, transform: u32, father: u32, layer: u32) -> String {
+        format!("--- !u!1 &{id}
+GameObject:
+  m_Component:
+  - component: {{fileID: {transform}}}
+  m_Layer: {layer}
+  m_Name: {name}
+  m_IsActive: 1
+--- !u!4 &{transform}
+Transform:
+  m_GameObject: {{fileID: {id}}}
+  m_Father: {{fileID: {father}}}
+")
+    }
+
+    fn component(class_id: u32, type_name: &str, id: u32, go: u32, extra: &str) -> String {
+        format!("--- !u!{class_id} &{id}\n{type_name}:\n  m_GameObject: {{fileID: {go}}}\n{extra}")
+    }
+
+    fn audit(content: &str) -> Vec<PhysicsObjectInfo> {
+        let settings = PhysicsSettings { layer_names: &[], masks_3d: None, masks_2d: None };
+        audit_physics(content, &ComponentConfig::default(), &settings)
+    }
+
+    #[test]
+    fn test_moving_collider_without_rigidbody() {
+        let content = [
+            go(1, "Door", 11, 0, 0),
+            component(95, "Animator", 12, 1, ""),
+            component(65, "BoxCollider", 13, 1, "  m_IsTrigger: 0\n"),
+            go(2, "Wall", 21, 0, 0),
+            component(65, "BoxCollider", 22, 2, "  m_IsTrigger: 0\n"),
+        ].concat();
+        let objects = audit(&content);
+        assert_eq!(objects.len(), 2);
+        let door = objects.iter().find(|o| o.game_object_name == "Door").unwrap();
+        assert_eq!(door.issues, vec!["moving_collider_without_rigidbody".to_string()]);
+        let wall = objects.iter().find(|o| o.game_object_name == "Wall").unwrap();
+        assert!(wall.issues.is_empty(), "static colliders don't need rigidbodies");
+    }
+
+    #[test]
+    fn test_non_convex_mesh_collider_and_compound_colliders() {
+        let content = [
+            go(1, "Crate", 11, 0, 0),
+            component(54, "Rigidbody", 12, 1, "  m_IsKinematic: 0\n"),
+            component(64, "MeshCollider", 13, 1, "  m_Convex: 0\n"),
+            go(2, "Ship", 21, 0, 0),
+            component(54, "Rigidbody", 22, 2, "  m_IsKinematic: 0\n"),
+            go(3, "Hull", 31, 21, 0),
+            component(65, "BoxCollider", 32, 3, ""),
+            go(4, "Ghost", 41, 0, 0),
+            component(54, "Rigidbody", 42, 4, "  m_IsKinematic: 1\n"),
+        ].concat();
+        let objects = audit(&content);
+        let by_name = |name: &str| objects.iter().find(|o| o.game_object_name == name).unwrap();
+
+        assert_eq!(by_name("Crate").issues, vec!["non_convex_mesh_collider_on_rigidbody".to_string()]);
+        assert!(by_name("Ship").issues.is_empty(), "child collider forms a compound collider");
+        assert!(by_name("Hull").issues.is_empty());
+        assert_eq!(by_name("Ghost").issues, vec!["rigidbody_without_collider".to_string()]);
+        assert!(by_name("Ghost").is_kinematic);
+    }
+
+    #[test]
+    fn test_layer_collides_with_nothing() {
+        let content = [
+            go(1, "Bullet", 11, 0, 8),
+            component(54, "Rigidbody", 12, 1, "  m_IsKinematic: 0\n"),
+            component(135, "SphereCollider", 13, 1, ""),
+        ].concat();
+        let mut masks = vec![u32::MAX; 32];
+        masks[8] = 0;
+        let names: Vec<Option<String>> = (0..32).map(|i| (i == 8).then(|| "Bullets".to_string())).collect();
+        let settings = PhysicsSettings { layer_names: &names, masks_3d: Some(&masks), masks_2d: None };
+        let objects = audit_physics(&content, &ComponentConfig::default(), &settings);
+        assert_eq!(objects[0].layer_name.as_deref(), Some("Bullets"));
+        assert_eq!(objects[0].issues, vec!["layer_collides_with_nothing".to_string()]);
+    }
+}
@@ -1,7 +1,35 @@
 use regex::Regex;
 use std::collections::HashMap;
-
-use crate::common::{PrefabInstanceInfo, PrefabModification};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::common::{self, AddedPrefabComponent, NestedPrefabNode, PrefabInstanceInfo, PrefabModification, RemovedPrefabObject};
+use super::component;
+use super::config::ComponentConfig;
+use super::gameobject;
+use super::parser::{BlockIndex, UnityYamlParser};
+
+static TRANSFORM_PARENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_TransformParent:[ \t]*\{fileID:[ \t]*(-?\d+)").unwrap()
+});
+static OBJECT_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{fileID:[ \t]*(-?\d+)(?:,[ \t]*guid:[ \t]*([a-f0-9]{32}))?").unwrap()
+});
+static TYPE_NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z][A-Za-z0-9_]*):").unwrap()
+});
+static NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_Name:[ \t]*([^\n]*)").unwrap()
+});
+static OWNER_GO_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_GameObject:[ \t]*\{fileID:[ \t]*(-?\d+)").unwrap()
+});
+static SOURCE_OBJECT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_CorrespondingSourceObject:[ \t]*\{fileID:[ \t]*(-?\d+)(?:,[ \t]*guid:[ \t]*([a-f0-9]{32}))?").unwrap()
+});
+static PREFAB_INSTANCE_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_PrefabInstance:[ \t]*\{fileID:[ \t]*(-?\d+)").unwrap()
+});
 
 /// Extract all PrefabInstance blocks (!u!1001) from Unity YAML content
 pub fn extract_prefab_instances(
@@ -9,6 +37,8 @@ pub fn extract_prefab_instances(
     guid_cache: &HashMap<String, String>,
 ) -> Vec<PrefabInstanceInfo> {
     let header_re = Regex::new(r"--- !u!1001 &(-?\d+)\s*\n").expect("Invalid regex");
+    // Only built when some instance is parented under another object
+    let mut index: Option<BlockIndex> = None;
 
     header_re
         .captures_iter(content)
@@ -21,6 +51,13 @@ pub fn extract_prefab_instances(
             let source_guid = extract_source_guid(&block).unwrap_or_default();
             let source_prefab = guid_cache.get(&source_guid).cloned();
             let modifications_count = count_modifications(&block);
+            let removed_components = extract_removed_objects(&block, "m_RemovedComponents");
+            let removed_game_objects = extract_removed_objects(&block, "m_RemovedGameObjects");
+            let parent_transform_id = extract_transform_parent(&block);
+            let (parent_game_object_id, parent_prefab_instance_id) = match &parent_transform_id {
+                Some(transform_id) => resolve_transform_owner(index.get_or_insert_with(|| BlockIndex::new(content)), transform_id),
+                None => (None, None),
+            };
 
             Some(PrefabInstanceInfo {
                 name,
@@ -28,23 +65,34 @@ pub fn extract_prefab_instances(
                 source_guid,
                 source_prefab,
                 modifications_count,
+                removed_components: if removed_components.is_empty() { None } else { Some(removed_components) },
+                removed_game_objects: if removed_game_objects.is_empty() { None } else { Some(removed_game_objects) },
+                added_components: None,
+                parent_transform_id,
+                parent_game_object_id,
+                parent_prefab_instance_id,
             })
         })
         .collect()
 }
 
+/// Owner of a Transform an instance is parented to: its GameObject, or, for a stripped
+/// Transform that belongs to another prefab instance, that PrefabInstance.
+fn resolve_transform_owner(index: &BlockIndex, transform_id: &str) -> (Option<String>, Option<String>) {
+    let Some((_, body)) = index.get(transform_id) else { return (None, None) };
+    let capture = |re: &Regex| re.captures(body)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
+        .filter(|id| id != "0");
+    match capture(&OWNER_GO_RE) {
+        Some(go_id) => (Some(go_id), None),
+        None => (None, capture(&PREFAB_INSTANCE_REF_RE)),
+    }
+}
+
 /// Extract the block content for a PrefabInstance by file ID
 pub fn extract_prefab_block(content: &str, file_id: &str) -> Option<String> {
-    let header = format!("--- !u!1001 &{}", file_id);
-    let start_pos = content.find(&header)?;
-    let after_header = &content[start_pos..];
-
-    let end_offset = after_header[header.len()..]
-        .find("--- !u!")
-        .map(|pos| header.len() + pos)
-        .unwrap_or(after_header.len());
-
-    Some(after_header[..end_offset].to_string())
+    super::parser::find_block(content, 1001, file_id).map(str::to_string)
 }
 
 /// Extract the display name from m_Modifications (looks for propertyPath: m_Name)
@@ -81,6 +129,159 @@ pub fn count_modifications(block: &str) -> u32 {
         .count() as u32
 }
 
+/// Extract the object references listed under a removal array
+/// (`m_RemovedComponents` or `m_RemovedGameObjects`) of a PrefabInstance block.
+pub fn extract_removed_objects(block: &str, key: &str) -> Vec<RemovedPrefabObject> {
+    let mut removed = Vec::new();
+    let mut lines = block.lines();
+    let header = format!("{}:", key);
+
+    for line in lines.by_ref() {
+        let trimmed = line.trim_start();
+        if let Some(rest) = trimmed.strip_prefix(&header) {
+            // Inline flow form: "m_RemovedComponents: []"
+            if rest.trim().starts_with('[') {
+                return removed;
+            }
+            break;
+        }
+    }
+
+    for line in lines {
+        let trimmed = line.trim_start();
+        let Some(entry) = trimmed.strip_prefix("- ") else {
+            break;
+        };
+        if let Some(caps) = OBJECT_REF_RE.captures(entry) {
+            removed.push(RemovedPrefabObject {
+                file_id: caps.get(1).map_or("", |m| m.as_str()).to_string(),
+                guid: caps.get(2).map(|m| m.as_str().to_string()),
+                class_id: None,
+                type_name: None,
+                name: None,
+            });
+        }
+    }
+
+    removed
+}
+
+/// Components added in the file to objects of prefab instances, with the PrefabInstance they
+/// belong to. Such a component's `m_GameObject` is a stripped GameObject (a placeholder with
+/// `m_PrefabInstance` and `m_CorrespondingSourceObject` but no component list).
+pub fn extract_added_components(
+    content: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> Vec<(String, AddedPrefabComponent)> {
+    let index = BlockIndex::new(content);
+    let capture = |re: &Regex, body: &str| re.captures(body)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
+        .filter(|id| id != "0");
+    let mut added = Vec::new();
+    for (class_id, file_id, body) in UnityYamlParser::parse_all_blocks(content) {
+        if class_id == config.gameobject_class_id {
+            continue;
+        }
+        let Some(go_id) = capture(&OWNER_GO_RE, &body) else { continue };
+        let Some(go) = index.get_by_class_and_id(config.gameobject_class_id, &go_id) else { continue };
+        let Some(instance_id) = capture(&PREFAB_INSTANCE_REF_RE, go) else { continue };
+        let Some(source) = SOURCE_OBJECT_RE.captures(go) else { continue };
+        let Some(component) = component::extract_single_component_indexed(&index, &file_id, guid_cache, config) else { continue };
+        added.push((instance_id, AddedPrefabComponent {
+            game_object_id: go_id,
+            source_object_id: source.get(1).map_or("", |m| m.as_str()).to_string(),
+            source_guid: source.get(2).map(|m| m.as_str().to_string()),
+            source_object_name: None,
+            component,
+        }));
+    }
+    added
+}
+
+/// Attach the components added to each instance's objects (see `extract_added_components`).
+pub fn attach_added_components(
+    instances: &mut [PrefabInstanceInfo],
+    content: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) {
+    if instances.is_empty() {
+        return;
+    }
+    for (instance_id, component) in extract_added_components(content, guid_cache, config) {
+        if let Some(pi) = instances.iter_mut().find(|pi| pi.file_id == instance_id) {
+            pi.added_components.get_or_insert_with(Vec::new).push(component);
+        }
+    }
+}
+
+/// Resolve removed component/GameObject targets against their source prefabs, filling in
+/// class_id, type_name and name, and name the source objects added components extend.
+/// Source files are loaded once per GUID.
+pub fn resolve_removed_objects(
+    instances: &mut [PrefabInstanceInfo],
+    project_root: Option<&str>,
+    guid_cache: &HashMap<String, String>,
+) {
+    let mut sources: HashMap<String, Option<BlockIndex>> = HashMap::new();
+
+    for pi in instances.iter_mut() {
+        let source_guid = pi.source_guid.clone();
+        for list in [&mut pi.removed_components, &mut pi.removed_game_objects].into_iter().flatten() {
+            for entry in list.iter_mut() {
+                let guid = entry.guid.clone().unwrap_or_else(|| source_guid.clone());
+                let index = sources.entry(guid.clone()).or_insert_with(|| load_source(&guid, project_root, guid_cache));
+                if let Some(index) = index {
+                    resolve_removed_entry(entry, index);
+                }
+            }
+        }
+        for added in pi.added_components.iter_mut().flatten() {
+            let guid = added.source_guid.clone().unwrap_or_else(|| source_guid.clone());
+            let index = sources.entry(guid.clone()).or_insert_with(|| load_source(&guid, project_root, guid_cache));
+            added.source_object_name = index.as_ref()
+                .and_then(|index| index.get(&added.source_object_id))
+                .and_then(|(_, block)| NAME_RE.captures(block))
+                .and_then(|c| c.get(1))
+                .map(|m| m.as_str().trim().to_string())
+                .filter(|s| !s.is_empty());
+        }
+    }
+}
+
+/// Index of the source prefab with this GUID, when it can be loaded.
+fn load_source(guid: &str, project_root: Option<&str>, guid_cache: &HashMap<String, String>) -> Option<BlockIndex> {
+    let rel_path = guid_cache.get(guid)?;
+    let full_path = match project_root {
+        Some(root) => Path::new(root).join(rel_path),
+        None => Path::new(rel_path).to_path_buf(),
+    };
+    common::read_unity_file(full_path).ok().map(|c| BlockIndex::new(&c))
+}
+
+fn resolve_removed_entry(entry: &mut RemovedPrefabObject, index: &BlockIndex) {
+    let Some((class_id, block)) = index.get(&entry.file_id) else {
+        return;
+    };
+    entry.class_id = Some(class_id);
+    entry.type_name = TYPE_NAME_RE.captures(block)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string());
+
+    // GameObjects carry their own name; components report their owner's name
+    let name_block = match OWNER_GO_RE.captures(block).and_then(|c| c.get(1)) {
+        Some(owner) => index.get(owner.as_str()).map(|(_, b)| b),
+        None => Some(block),
+    };
+    entry.name = name_block
+        .and_then(|b| NAME_RE.captures(b))
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|s| !s.is_empty());
+}
+
 /// Extract all modifications from a PrefabInstance block as structured data
 pub fn extract_modifications(block: &str) -> Vec<PrefabModification> {
     let mut modifications = Vec::new();
@@ -140,6 +341,205 @@ pub fn extract_modifications(block: &str) -> Vec<PrefabModification> {
     modifications
 }
 
+/// Extract the m_TransformParent fileID from a PrefabInstance block (None when it is a root instance)
+pub fn extract_transform_parent(block: &str) -> Option<String> {
+    TRANSFORM_PARENT_RE.captures(block)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
+        .filter(|s| s != "0")
+}
+
+/// Shared state for one nested-prefab expansion.
+pub struct NestedExpansion<'a> {
+    pub project_root: Option<&'a str>,
+    pub guid_cache: &'a HashMap<String, String>,
+    pub config: &'a ComponentConfig,
+    pub max_depth: u32,
+    /// Order siblings as the editor's Hierarchy window does instead of by document order
+    pub hierarchy_order: bool,
+}
+
+impl NestedExpansion<'_> {
+    /// Build the full hierarchy of a scene/prefab, recursively expanding every
+    /// PrefabInstance into the hierarchy of its source prefab.
+    pub fn expand_file(&self, content: &str, source_file: &str) -> Vec<NestedPrefabNode> {
+        let mut visiting = Vec::new();
+        self.expand_content(content, source_file, 0, &mut visiting)
+    }
+
+    /// Expand the source prefab of a single PrefabInstance.
+    /// Returns the source hierarchy, or the reason it could not be expanded.
+    pub fn expand_source(&self, pi: &PrefabInstanceInfo) -> Result<Vec<NestedPrefabNode>, String> {
+        let mut visiting = Vec::new();
+        let removed = pi.removed_game_objects.as_deref().unwrap_or_default();
+        self.expand_guid(&pi.source_guid, removed, 1, &mut visiting)
+    }
+
+    fn expand_guid(
+        &self,
+        source_guid: &str,
+        removed_game_objects: &[RemovedPrefabObject],
+        nesting_level: u32,
+        visiting: &mut Vec<String>,
+    ) -> Result<Vec<NestedPrefabNode>, String> {
+        if nesting_level > self.max_depth {
+            return Err("depth_limit".to_string());
+        }
+        if visiting.iter().any(|g| g == source_guid) {
+            return Err("cycle".to_string());
+        }
+        let rel_path = self.guid_cache.get(source_guid).ok_or_else(|| "missing_source".to_string())?;
+        let full_path = match self.project_root {
+            Some(root) => Path::new(root).join(rel_path),
+            None => Path::new(rel_path).to_path_buf(),
+        };
+        let content = common::read_unity_file(&full_path).map_err(|_| "missing_source".to_string())?;
+
+        visiting.push(source_guid.to_string());
+        let mut nodes = self.expand_content(&content, rel_path, nesting_level, visiting);
+        visiting.pop();
+
+        // Drop source objects the instance removes (m_RemovedGameObjects), with their subtrees
+        if !removed_game_objects.is_empty() {
+            prune_removed(&mut nodes, removed_game_objects, nesting_level);
+        }
+        Ok(nodes)
+    }
+
+    fn expand_content(
+        &self,
+        content: &str,
+        source_file: &str,
+        nesting_level: u32,
+        visiting: &mut Vec<String>,
+    ) -> Vec<NestedPrefabNode> {
+        let index = BlockIndex::new(content);
+        let gameobjects = UnityYamlParser::extract_gameobjects_with_config(content, self.config);
+        let instances = extract_prefab_instances(content, self.guid_cache);
+
+        // Flat node list plus the transform (or PrefabInstance) each node hangs from
+        let mut nodes: Vec<NestedPrefabNode> = Vec::with_capacity(gameobjects.len() + instances.len());
+        let mut parents: Vec<Option<String>> = Vec::with_capacity(nodes.capacity());
+        let mut transform_owner: HashMap<String, usize> = HashMap::new();
+        let mut instance_owner: HashMap<String, usize> = HashMap::new();
+
+        for go in &gameobjects {
+            let (_, _, parent_id, _) = gameobject::extract_metadata_indexed(&index, &go.file_id, self.config);
+            if let Some(tid) = index.get_by_class_and_id(self.config.gameobject_class_id, &go.file_id)
+                .and_then(|block| gameobject::find_transform_id_indexed(&index, block, self.config))
+            {
+                transform_owner.insert(tid.to_string(), nodes.len());
+            }
+            nodes.push(NestedPrefabNode {
+                name: go.name.clone(),
+                file_id: go.file_id.clone(),
+                node_type: "GameObject".to_string(),
+                source_file: source_file.to_string(),
+                nesting_level,
+                active: Some(go.active),
+                source_guid: None,
+                source_prefab: None,
+                unexpanded_reason: None,
+                children: Vec::new(),
+            });
+            parents.push(parent_id);
+        }
+
+        for pi in &instances {
+            let parent_id = extract_prefab_block(content, &pi.file_id)
+                .and_then(|block| extract_transform_parent(&block));
+            let removed = pi.removed_game_objects.as_deref().unwrap_or_default();
+            let (children, unexpanded_reason) = match self.expand_guid(&pi.source_guid, removed, nesting_level + 1, visiting) {
+                Ok(children) => (children, None),
+                Err(reason) => (Vec::new(), Some(reason)),
+            };
+            instance_owner.insert(pi.file_id.clone(), nodes.len());
+            nodes.push(NestedPrefabNode {
+                name: pi.name.clone(),
+                file_id: pi.file_id.clone(),
+                node_type: "PrefabInstance".to_string(),
+                source_file: source_file.to_string(),
+                nesting_level,
+                active: None,
+                source_guid: Some(pi.source_guid.clone()),
+                source_prefab: pi.source_prefab.clone(),
+                unexpanded_reason,
+                children,
+            });
+            parents.push(parent_id);
+        }
+
+        // Resolve each parent transform to a node: either a local GameObject's transform,
+        // or a stripped transform that stands in for an object inside a PrefabInstance.
+        let parent_idx: Vec<Option<usize>> = parents
+            .iter()
+            .map(|parent| {
+                let pid = parent.as_ref()?;
+                if let Some(&idx) = transform_owner.get(pid) {
+                    return Some(idx);
+                }
+                let (class_id, block) = index.get(pid)?;
+                if !self.config.is_hierarchy_provider(class_id) {
+                    return None;
+                }
+                let pi_id = PREFAB_INSTANCE_REF_RE.captures(block)?.get(1)?.as_str();
+                instance_owner.get(pi_id).copied()
+            })
+            .collect();
+
+        let mut child_lists: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        let mut roots = Vec::new();
+        for (idx, parent) in parent_idx.iter().enumerate() {
+            match parent {
+                Some(p) if *p != idx => child_lists[*p].push(idx),
+                _ => roots.push(idx),
+            }
+        }
+
+        if self.hierarchy_order {
+            let order = gameobject::hierarchy_order(&UnityYamlParser::parse_all_blocks(content), self.config);
+            let rank = |idx: &usize| order.get(&nodes[*idx].file_id).copied().unwrap_or(usize::MAX);
+            roots.sort_by_key(rank);
+            for list in &mut child_lists {
+                list.sort_by_key(rank);
+            }
+        }
+
+        let mut slots: Vec<Option<NestedPrefabNode>> = nodes.into_iter().map(Some).collect();
+        roots
+            .into_iter()
+            .filter_map(|idx| assemble_tree(idx, &mut slots, &child_lists))
+            .collect()
+    }
+}
+
+/// Remove GameObject nodes at `nesting_level` whose file_id is listed as removed.
+fn prune_removed(nodes: &mut Vec<NestedPrefabNode>, removed: &[RemovedPrefabObject], nesting_level: u32) {
+    nodes.retain(|n| {
+        n.nesting_level != nesting_level
+            || n.node_type != "GameObject"
+            || !removed.iter().any(|r| r.file_id == n.file_id)
+    });
+    for node in nodes.iter_mut() {
+        prune_removed(&mut node.children, removed, nesting_level);
+    }
+}
+
+/// Move nodes out of `slots` into a tree rooted at `idx` (each node is taken at most once).
+fn assemble_tree(
+    idx: usize,
+    slots: &mut [Option<NestedPrefabNode>],
+    child_lists: &[Vec<usize>],
+) -> Option<NestedPrefabNode> {
+    let mut node = slots[idx].take()?;
+    for &child in &child_lists[idx] {
+        if let Some(child_node) = assemble_tree(child, slots, child_lists) {
+            node.children.push(child_node);
+        }
+    }
+    Some(node)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,6 +658,43 @@ PrefabInstance:
         assert_eq!(instances[1].file_id, "800000");
     }
 
+    #[test]
+    fn test_prefab_instance_transform_parent_resolution() {
+        let content = "%YAML 1.1
+--- !u!1 &100
+GameObject:
+  m_Name: Spawner
+--- !u!4 &101
+Transform:
+  m_GameObject: {fileID: 100}
+  m_Father: {fileID: 0}
+--- !u!1001 &200
+PrefabInstance:
+  m_Modification:
+    m_TransformParent: {fileID: 101}
+  m_SourcePrefab: {fileID: 100100000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}
+--- !u!4 &201 stripped
+Transform:
+  m_CorrespondingSourceObject: {fileID: 400000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}
+  m_PrefabInstance: {fileID: 200}
+--- !u!1001 &300
+PrefabInstance:
+  m_Modification:
+    m_TransformParent: {fileID: 201}
+  m_SourcePrefab: {fileID: 100100000, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 3}
+";
+        let instances = extract_prefab_instances(content, &HashMap::new());
+        assert_eq!(instances[0].parent_transform_id.as_deref(), Some("101"));
+        assert_eq!(instances[0].parent_game_object_id.as_deref(), Some("100"));
+        assert!(instances[0].parent_prefab_instance_id.is_none());
+        assert_eq!(instances[1].parent_transform_id.as_deref(), Some("201"));
+        assert!(instances[1].parent_game_object_id.is_none());
+        assert_eq!(instances[1].parent_prefab_instance_id.as_deref(), Some("200"));
+
+        let roots = extract_prefab_instances(PREFAB_BLOCK, &HashMap::new());
+        assert!(roots[0].parent_transform_id.is_none() && roots[0].parent_game_object_id.is_none());
+    }
+
     #[test]
     fn test_extract_modifications() {
         let mods = extract_modifications(PREFAB_BLOCK);
@@ -298,4 +735,251 @@ PrefabInstance:
         assert_eq!(instances.len(), 1);
         assert_eq!(instances[0].name, "<unnamed>");
     }
+
+    const INNER_PREFAB: &str = "%YAML 1.1
+--- !u!1 &100
+GameObject:
+  m_Component:
+  - component: {fileID: 101}
+  m_Layer: 0
+  m_Name: Barrel
+  m_IsActive: 1
+--- !u!4 &101
+Transform:
+  m_Father: {fileID: 0}
+  m_Children: []
+";
+
+    fn outer_prefab(inner_guid: &str) -> String {
+        format!("%YAML 1.1
+--- !u!1 &200
+GameObject:
+  m_Component:
+  - component: {{fileID: 201}}
+  m_Layer: 0
+  m_Name: Turret
+  m_IsActive: 1
+--- !u!4 &201
+Transform:
+  m_Father: {{fileID: 0}}
+  m_Children: []
+--- !u!1001 &300
+PrefabInstance:
+  m_Modification:
+    m_TransformParent: {{fileID: 201}}
+    m_Modifications:
+    - target: {{fileID: 100, guid: {g}, type: 3}}
+      propertyPath: m_Name
+      value: Gun
+      objectReference: {{fileID: 0}}
+    m_RemovedComponents: []
+  m_SourcePrefab: {{fileID: 100100000, guid: {g}, type: 3}}
+", g = inner_guid)
+    }
+
+    const INNER_GUID: &str = "11111111111111111111111111111111";
+    const OUTER_GUID: &str = "22222222222222222222222222222222";
+
+    fn nested_project() -> (tempfile::TempDir, HashMap<String, String>) {
+        let tmp = tempfile::tempdir().unwrap();
+        let prefabs = tmp.path().join("Assets").join("Prefabs");
+        std::fs::create_dir_all(&prefabs).unwrap();
+        std::fs::write(prefabs.join("Inner.prefab"), INNER_PREFAB).unwrap();
+        std::fs::write(prefabs.join("Outer.prefab"), outer_prefab(INNER_GUID)).unwrap();
+        let mut cache = HashMap::new();
+        cache.insert(INNER_GUID.to_string(), "Assets/Prefabs/Inner.prefab".to_string());
+        cache.insert(OUTER_GUID.to_string(), "Assets/Prefabs/Outer.prefab".to_string());
+        (tmp, cache)
+    }
+
+    fn scene_with_outer_instance() -> String {
+        format!("%YAML 1.1
+--- !u!1001 &900
+PrefabInstance:
+  m_Modification:
+    m_TransformParent: {{fileID: 0}}
+    m_Modifications:
+    - target: {{fileID: 200, guid: {g}, type: 3}}
+      propertyPath: m_Name
+      value: MainTurret
+      objectReference: {{fileID: 0}}
+    m_RemovedComponents: []
+  m_SourcePrefab: {{fileID: 100100000, guid: {g}, type: 3}}
+", g = OUTER_GUID)
+    }
+
+    #[test]
+    fn test_expand_nested_prefab_instances() {
+        let (tmp, cache) = nested_project();
+        let root = tmp.path().to_string_lossy().to_string();
+        let config = ComponentConfig::default();
+        let expansion = NestedExpansion { project_root: Some(&root), guid_cache: &cache, config: &config, max_depth: 3, hierarchy_order: false };
+
+        let nodes = expansion.expand_file(&scene_with_outer_instance(), "Assets/Main.unity");
+        assert_eq!(nodes.len(), 1);
+        let instance = &nodes[0];
+        assert_eq!(instance.name, "MainTurret");
+        assert_eq!(instance.node_type, "PrefabInstance");
+        assert_eq!(instance.source_file, "Assets/Main.unity");
+        assert!(instance.unexpanded_reason.is_none());
+
+        // Outer prefab root, with the nested Gun instance parented under its transform
+        assert_eq!(instance.children.len(), 1);
+        let turret = &instance.children[0];
+        assert_eq!(turret.name, "Turret");
+        assert_eq!(turret.source_file, "Assets/Prefabs/Outer.prefab");
+        assert_eq!(turret.nesting_level, 1);
+        assert_eq!(turret.children.len(), 1);
+
+        let gun = &turret.children[0];
+        assert_eq!(gun.name, "Gun");
+        assert_eq!(gun.node_type, "PrefabInstance");
+        assert_eq!(gun.children.len(), 1);
+        assert_eq!(gun.children[0].name, "Barrel");
+        assert_eq!(gun.children[0].source_file, "Assets/Prefabs/Inner.prefab");
+        assert_eq!(gun.children[0].nesting_level, 2);
+    }
+
+    #[test]
+    fn test_expand_nested_depth_limit() {
+        let (tmp, cache) = nested_project();
+        let root = tmp.path().to_string_lossy().to_string();
+        let config = ComponentConfig::default();
+        let expansion = NestedExpansion { project_root: Some(&root), guid_cache: &cache, config: &config, max_depth: 1, hierarchy_order: false };
+
+        let nodes = expansion.expand_file(&scene_with_outer_instance(), "Assets/Main.unity");
+        let gun = &nodes[0].children[0].children[0];
+        assert_eq!(gun.name, "Gun");
+        assert!(gun.children.is_empty());
+        assert_eq!(gun.unexpanded_reason.as_deref(), Some("depth_limit"));
+    }
+
+    #[test]
+    fn test_expand_nested_cycle_and_missing_source() {
+        let (tmp, mut cache) = nested_project();
+        // Inner now points back at Outer: Outer -> Inner -> Outer
+        std::fs::write(
+            tmp.path().join("Assets/Prefabs/Inner.prefab"),
+            outer_prefab(OUTER_GUID),
+        ).unwrap();
+        let root = tmp.path().to_string_lossy().to_string();
+        let config = ComponentConfig::default();
+        {
+            let expansion = NestedExpansion { project_root: Some(&root), guid_cache: &cache, config: &config, max_depth: 10, hierarchy_order: false };
+            let pi = &extract_prefab_instances(&scene_with_outer_instance(), &cache)[0];
+            let nodes = expansion.expand_source(pi).unwrap();
+            let inner_instance = &nodes[0].children[0];
+            let outer_again = &inner_instance.children[0].children[0];
+            assert_eq!(outer_again.unexpanded_reason.as_deref(), Some("cycle"));
+        }
+
+        cache.remove(INNER_GUID);
+        let expansion = NestedExpansion { project_root: Some(&root), guid_cache: &cache, config: &config, max_depth: 10, hierarchy_order: false };
+        let pi = &extract_prefab_instances(&scene_with_outer_instance(), &cache)[0];
+        let nodes = expansion.expand_source(pi).unwrap();
+        assert_eq!(nodes[0].children[0].unexpanded_reason.as_deref(), Some("missing_source"));
+    }
+
+    const REMOVALS_BLOCK: &str = "--- !u!1001 &950
+PrefabInstance:
+  m_Modification:
+    m_TransformParent: {fileID: 0}
+    m_Modifications: []
+    m_RemovedComponents:
+    - {fileID: 202, guid: 22222222222222222222222222222222, type: 3}
+    m_RemovedGameObjects:
+    - {fileID: 210, guid: 22222222222222222222222222222222, type: 3}
+    m_AddedGameObjects: []
+  m_SourcePrefab: {fileID: 100100000, guid: 22222222222222222222222222222222, type: 3}
+";
+
+    #[test]
+    fn test_extract_removed_objects() {
+        let components = extract_removed_objects(REMOVALS_BLOCK, "m_RemovedComponents");
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].file_id, "202");
+        assert_eq!(components[0].guid.as_deref(), Some(OUTER_GUID));
+
+        let game_objects = extract_removed_objects(REMOVALS_BLOCK, "m_RemovedGameObjects");
+        assert_eq!(game_objects.len(), 1);
+        assert_eq!(game_objects[0].file_id, "210");
+
+        // Inline empty arrays and missing keys yield nothing
+        assert!(extract_removed_objects(PREFAB_BLOCK, "m_RemovedComponents").is_empty());
+        assert!(extract_removed_objects(PREFAB_BLOCK, "m_RemovedGameObjects").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_removed_objects_against_source() {
+        let (tmp, cache) = nested_project();
+        let outer = format!("{}--- !u!1 &210
+GameObject:
+  m_Component:
+  - component: {{fileID: 211}}
+  m_Name: Muzzle
+  m_IsActive: 1
+--- !u!4 &211
+Transform:
+  m_Father: {{fileID: 201}}
+--- !u!65 &202
+BoxCollider:
+  m_GameObject: {{fileID: 200}}
+  m_Enabled: 1
+", outer_prefab(INNER_GUID));
+        std::fs::write(tmp.path().join("Assets/Prefabs/Outer.prefab"), outer).unwrap();
+        let root = tmp.path().to_string_lossy().to_string();
+
+        let mut instances = extract_prefab_instances(REMOVALS_BLOCK, &cache);
+        resolve_removed_objects(&mut instances, Some(&root), &cache);
+        let pi = &instances[0];
+
+        let comp = &pi.removed_components.as_ref().unwrap()[0];
+        assert_eq!(comp.class_id, Some(65));
+        assert_eq!(comp.type_name.as_deref(), Some("BoxCollider"));
+        assert_eq!(comp.name.as_deref(), Some("Turret"));
+
+        let go = &pi.removed_game_objects.as_ref().unwrap()[0];
+        assert_eq!(go.type_name.as_deref(), Some("GameObject"));
+        assert_eq!(go.name.as_deref(), Some("Muzzle"));
+
+        // The expanded view no longer contains the removed GameObject
+        let config = ComponentConfig::default();
+        let expansion = NestedExpansion { project_root: Some(&root), guid_cache: &cache, config: &config, max_depth: 3, hierarchy_order: false };
+        let nodes = expansion.expand_source(pi).unwrap();
+        let turret = &nodes[0];
+        assert_eq!(turret.name, "Turret");
+        assert!(turret.children.iter().all(|c| c.name != "Muzzle"));
+        assert!(turret.children.iter().any(|c| c.name == "Gun"));
+    }
+
+    #[test]
+    fn test_added_components_on_stripped_objects() {
+        let (tmp, cache) = nested_project();
+        let root = tmp.path().to_string_lossy().to_string();
+        let scene = format!("{}--- !u!1 &960 stripped
+GameObject:
+  m_CorrespondingSourceObject: {{fileID: 200, guid: {OUTER_GUID}, type: 3}}
+  m_PrefabInstance: {{fileID: 950}}
+  m_PrefabAsset: {{fileID: 0}}
+--- !u!65 &970
+BoxCollider:
+  m_GameObject: {{fileID: 960}}
+  m_IsTrigger: 1
+--- !u!4 &980
+Transform:
+  m_GameObject: {{fileID: 0}}
+", REMOVALS_BLOCK);
+
+        let config = ComponentConfig::default();
+        let mut instances = extract_prefab_instances(&scene, &cache);
+        attach_added_components(&mut instances, &scene, &cache, &config);
+        resolve_removed_objects(&mut instances, Some(&root), &cache);
+
+        let added = instances[0].added_components.as_ref().unwrap();
+        assert_eq!(added.len(), 1);
+        assert_eq!((added[0].game_object_id.as_str(), added[0].source_object_id.as_str()), ("960", "200"));
+        assert_eq!(added[0].source_object_name.as_deref(), Some("Turret"));
+        assert_eq!((added[0].component.type_name.as_str(), added[0].component.file_id.as_str()), ("BoxCollider", "970"));
+        assert_eq!(added[0].component.properties.as_ref().unwrap()["IsTrigger"], "1");
+    }
 }
@@ -0,0 +1,230 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::common::{self, ObjectProvenance};
+use super::config::ComponentConfig;
+use super::gameobject::find_transform_id_indexed;
+use super::parser::BlockIndex;
+use super::prefab;
+use super::rendering::{field, ref_file_id};
+
+/// Longest variant chain followed before giving up (guards against corrupt or cyclic prefabs).
+const MAX_VARIANT_DEPTH: usize = 16;
+
+/// Package an asset path belongs to, and whether it is an imported package sample:
+/// `Packages/<name>/...`, `Library/PackageCache/<name>@<version>/...` or
+/// `Assets/Samples/<package display name>/<version>/...`.
+pub fn package_origin(path: &str) -> Option<(String, bool)> {
+    let path = path.replace('\\', "/");
+    let mut parts = path.split('/');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some("Packages"), Some(name), Some(_)) => Some((name.to_string(), false)),
+        (Some("Library"), Some("PackageCache"), Some(name)) => {
+            Some((name.split('@').next().unwrap_or(name).to_string(), false))
+        }
+        (Some("Assets"), Some("Samples"), Some(name)) if parts.next().is_some() => Some((name.to_string(), true)),
+        _ => None,
+    }
+}
+
+/// `origin` and `package` of a path: "package_sample", "package" or "project".
+fn origin_of(path: &str) -> (String, Option<String>) {
+    match package_origin(path) {
+        Some((name, true)) => ("package_sample".to_string(), Some(name)),
+        Some((name, false)) => ("package".to_string(), Some(name)),
+        None => ("project".to_string(), None),
+    }
+}
+
+/// Prefabs `source_guid` is a variant of, nearest base first. A variant's root is a
+/// PrefabInstance without a parent Transform; its source is the next base.
+pub fn variant_chain(source_guid: &str, guid_cache: &HashMap<String, String>, project_root: Option<&str>) -> Vec<String> {
+    let mut chain = Vec::new();
+    let Some(root) = project_root else { return chain };
+    let mut seen: HashSet<String> = HashSet::from([source_guid.to_string()]);
+    let mut guid = source_guid.to_string();
+    while chain.len() < MAX_VARIANT_DEPTH {
+        let Some(path) = guid_cache.get(&guid) else { break };
+        let Ok(content) = common::read_unity_file(Path::new(root).join(path)) else { break };
+        let base = prefab::extract_prefab_instances(&content, guid_cache).into_iter()
+            .find(|pi| pi.parent_transform_id.is_none() && !pi.source_guid.is_empty());
+        let Some(base) = base.filter(|pi| seen.insert(pi.source_guid.clone())) else { break };
+        chain.push(base.source_prefab.clone().unwrap_or_else(|| base.source_guid.clone()));
+        guid = base.source_guid;
+    }
+    chain
+}
+
+/// The PrefabInstance a block belongs to (`m_PrefabInstance`), if any.
+fn prefab_instance_of(block: &str) -> Option<&str> {
+    field(block, "m_PrefabInstance").and_then(ref_file_id).filter(|id| *id != "0")
+}
+
+/// Where a GameObject of `file` comes from: defined directly in the file, part of a prefab
+/// instance (its GameObject links to one), or added in this file under a prefab instance (an
+/// ancestor Transform is a stripped object of one). Instances report their source prefab and
+/// variant chain; `origin` tells whether that prefab, or for local objects the file itself,
+/// lives in a package or an imported package sample.
+pub fn resolve(
+    index: &BlockIndex,
+    go_id: &str,
+    file: &str,
+    guid_cache: &HashMap<String, String>,
+    project_root: Option<&str>,
+    config: &ComponentConfig,
+) -> ObjectProvenance {
+    let mut provenance = ObjectProvenance {
+        kind: "scene".to_string(),
+        prefab_instance_id: None,
+        source_guid: None,
+        source_prefab: None,
+        variant_chain: Vec::new(),
+        origin: String::new(),
+        package: None,
+    };
+    let go_block = index.get_by_class_and_id(config.gameobject_class_id, go_id);
+    let instance = match go_block.and_then(prefab_instance_of) {
+        Some(pi) => {
+            provenance.kind = "prefab_instance".to_string();
+            Some(pi.to_string())
+        }
+        None => go_block.and_then(|block| added_under_instance(index, block, config)).inspect(|_| {
+            provenance.kind = "added_to_prefab_instance".to_string();
+        }),
+    };
+
+    let Some(instance) = instance else {
+        (provenance.origin, provenance.package) = origin_of(file);
+        return provenance;
+    };
+    let source_guid = index.get(&instance)
+        .and_then(|(_, block)| prefab::extract_source_guid(block));
+    provenance.prefab_instance_id = Some(instance);
+    if let Some(guid) = source_guid {
+        provenance.source_prefab = guid_cache.get(&guid).cloned();
+        provenance.variant_chain = variant_chain(&guid, guid_cache, project_root);
+        provenance.source_guid = Some(guid);
+    }
+
+    (provenance.origin, provenance.package) = if provenance.kind == "added_to_prefab_instance" {
+        origin_of(file)
+    } else {
+        // The nearest packaged prefab in the chain decides; a project variant of a package
+        // prefab still originates from that package
+        provenance.source_prefab.iter().chain(&provenance.variant_chain)
+            .map(|path| origin_of(path))
+            .find(|(origin, _)| origin != "project")
+            .unwrap_or_else(|| {
+                let origin = if provenance.source_prefab.is_some() { "project" } else { "unknown" };
+                (origin.to_string(), None)
+            })
+    };
+    provenance
+}
+
+/// PrefabInstance owning the nearest stripped ancestor Transform of a local GameObject.
+fn added_under_instance(index: &BlockIndex, go_block: &str, config: &ComponentConfig) -> Option<String> {
+    let mut seen = HashSet::new();
+    let mut transform_id = find_transform_id_indexed(index, go_block, config)?.to_string();
+    while seen.insert(transform_id.clone()) {
+        let (_, transform) = index.get(&transform_id)?;
+        let father_id = field(transform, "m_Father").and_then(ref_file_id).filter(|id| *id != "0")?;
+        let (_, father) = index.get(father_id)?;
+        if let Some(pi) = prefab_instance_of(father) {
+            return Some(pi.to_string());
+        }
+        transform_id = father_id.to_string();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const BASE_GUID: &str = "11111111111111111111111111111111";
+    const VARIANT_GUID: &str = "22222222222222222222222222222222";
+
+    fn scene(source_guid: &str) -> String {
+        format!("%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {{fileID: 2}}\n  m_Name: Level\n  m_IsActive: 1\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {{fileID: 1}}\n  m_Children: []\n  m_Father: {{fileID: 0}}\n\
+--- !u!1001 &100\nPrefabInstance:\n  m_ObjectHideFlags: 0\n  serializedVersion: 2\n  m_Modification:\n    m_TransformParent: {{fileID: 0}}\n    m_Modifications: []\n  m_SourcePrefab: {{fileID: 100100000, guid: {guid}, type: 3}}\n\
+--- !u!4 &101 stripped\nTransform:\n  m_CorrespondingSourceObject: {{fileID: 400000, guid: {guid}, type: 3}}\n  m_PrefabInstance: {{fileID: 100}}\n  m_PrefabAsset: {{fileID: 0}}\n\
+--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {{fileID: 11}}\n  m_Name: Added\n  m_IsActive: 1\n\
+--- !u!4 &11\nTransform:\n  m_GameObject: {{fileID: 10}}\n  m_Children:\n  - {{fileID: 21}}\n  m_Father: {{fileID: 101}}\n\
+--- !u!1 &20\nGameObject:\n  m_Component:\n  - component: {{fileID: 21}}\n  m_Name: AddedChild\n  m_IsActive: 1\n\
+--- !u!4 &21\nTransform:\n  m_GameObject: {{fileID: 20}}\n  m_Children: []\n  m_Father: {{fileID: 11}}\n\
+--- !u!1 &30\nGameObject:\n  m_Component:\n  - component: {{fileID: 31}}\n  m_Name: Legacy\n  m_PrefabInstance: {{fileID: 100}}\n  m_IsActive: 1\n\
+--- !u!4 &31\nTransform:\n  m_GameObject: {{fileID: 30}}\n  m_Children: []\n  m_Father: {{fileID: 0}}\n", guid = source_guid)
+    }
+
+    #[test]
+    fn test_package_origin() {
+        assert_eq!(package_origin("Packages/com.unity.cinemachine/Runtime/Rig.prefab"), Some(("com.unity.cinemachine".to_string(), false)));
+        assert_eq!(package_origin("Library/PackageCache/com.unity.xr@2.1.0/Prefabs/Rig.prefab"), Some(("com.unity.xr".to_string(), false)));
+        assert_eq!(package_origin("Assets/Samples/XR Interaction Toolkit/2.5.2/Starter Assets/Rig.prefab"), Some(("XR Interaction Toolkit".to_string(), true)));
+        assert_eq!(package_origin("Assets/Samples/Rig.prefab"), None);
+        assert_eq!(package_origin("Assets/Prefabs/Rig.prefab"), None);
+    }
+
+    #[test]
+    fn test_resolve_kinds() {
+        let content = scene(BASE_GUID);
+        let index = BlockIndex::new(&content);
+        let mut cache = HashMap::new();
+        cache.insert(BASE_GUID.to_string(), "Packages/com.acme.props/Crate.prefab".to_string());
+        let config = ComponentConfig::default();
+        let resolve_id = |id: &str| resolve(&index, id, "Assets/Scenes/Main.unity", &cache, None, &config);
+
+        let level = resolve_id("1");
+        assert_eq!((level.kind.as_str(), level.origin.as_str()), ("scene", "project"));
+        assert!(level.prefab_instance_id.is_none());
+
+        for id in ["10", "20"] {
+            let added = resolve_id(id);
+            assert_eq!(added.kind, "added_to_prefab_instance");
+            assert_eq!(added.prefab_instance_id.as_deref(), Some("100"));
+            assert_eq!(added.source_prefab.as_deref(), Some("Packages/com.acme.props/Crate.prefab"));
+            assert_eq!(added.origin, "project", "added objects belong to the scene");
+        }
+
+        let legacy = resolve_id("30");
+        assert_eq!(legacy.kind, "prefab_instance");
+        assert_eq!((legacy.origin.as_str(), legacy.package.as_deref()), ("package", Some("com.acme.props")));
+
+        let sample = resolve(&index, "1", "Assets/Samples/Acme Props/1.0.0/Demo/Demo.unity", &cache, None, &config);
+        assert_eq!((sample.origin.as_str(), sample.package.as_deref()), ("package_sample", Some("Acme Props")));
+
+        let unresolved = resolve(&index, "30", "Assets/Main.unity", &HashMap::new(), None, &config);
+        assert_eq!(unresolved.source_guid.as_deref(), Some(BASE_GUID));
+        assert_eq!(unresolved.origin, "unknown");
+    }
+
+    #[test]
+    fn test_resolve_variant_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("Assets/Samples/Acme Props/1.0.0")).unwrap();
+        fs::create_dir_all(root.join("Assets/Prefabs")).unwrap();
+        // Project variant -> sample base
+        fs::write(root.join("Assets/Samples/Acme Props/1.0.0/Crate.prefab"), "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Name: Crate\n").unwrap();
+        let variant = scene(BASE_GUID).split("--- !u!4 &101").next().unwrap().to_string();
+        fs::write(root.join("Assets/Prefabs/Crate Variant.prefab"), variant).unwrap();
+
+        let mut cache = HashMap::new();
+        cache.insert(BASE_GUID.to_string(), "Assets/Samples/Acme Props/1.0.0/Crate.prefab".to_string());
+        cache.insert(VARIANT_GUID.to_string(), "Assets/Prefabs/Crate Variant.prefab".to_string());
+        let root = root.to_string_lossy().to_string();
+        assert_eq!(variant_chain(VARIANT_GUID, &cache, Some(&root)), vec!["Assets/Samples/Acme Props/1.0.0/Crate.prefab"]);
+        assert!(variant_chain(BASE_GUID, &cache, Some(&root)).is_empty());
+
+        let content = scene(VARIANT_GUID);
+        let index = BlockIndex::new(&content);
+        let legacy = resolve(&index, "30", "Assets/Main.unity", &cache, Some(&root), &ComponentConfig::default());
+        assert_eq!(legacy.source_prefab.as_deref(), Some("Assets/Prefabs/Crate Variant.prefab"));
+        assert_eq!(legacy.variant_chain.len(), 1);
+        assert_eq!((legacy.origin.as_str(), legacy.package.as_deref()), ("package_sample", Some("Acme Props")));
+    }
+}
@@ -0,0 +1,617 @@
+//! Computed columns for GameObject queries: `alias = expression` projections such as
+//! `pos = transform.localPosition` or `dist = length(transform.localPosition)`, and boolean
+//! filter expressions, evaluated natively so derived values need not be computed in JS.
+//!
+//! Paths name GameObject fields (`name`, `tag`, `layer`, `active`, `file_id`) or a component
+//! (type or script name, case-insensitive) followed by its properties, with or without the
+//! `m_` prefix (`transform.localPosition.y`, `Light.m_Intensity`). Flow mappings of numbers
+//! (`{x: 1, y: 2, z: 3}`) are vectors; arithmetic works on numbers and per component on
+//! vectors. A path that does not resolve yields null, which propagates through operators.
+//! Built-in fields renamed across Unity versions resolve under either name (see `aliases`).
+
+use serde_json::{Map, Value as Json};
+use std::cell::{OnceCell, RefCell};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::common::{Component, GameObjectSelector};
+use super::aliases;
+use super::bulk::select_gameobjects;
+use super::component::extract_components_indexed;
+use super::config::ComponentConfig;
+use super::gameobject::{extract_layer, extract_tag};
+use super::parser::BlockIndex;
+use super::rendering::field;
+
+/// Value of an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Number(f64),
+    /// Named components in serialized order (`x, y, z`, `r, g, b, a`, ...)
+    Vector(Vec<(String, f64)>),
+    Text(String),
+}
+
+impl Value {
+    /// A serialized scalar: a number, a flow mapping of numbers, or text.
+    pub fn from_scalar(text: &str) -> Value {
+        let text = text.trim();
+        if let Ok(n) = text.parse::<f64>() {
+            return Value::Number(n);
+        }
+        if let Some(inner) = text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+            let parts: Option<Vec<(String, f64)>> = inner.split(',')
+                .map(|part| {
+                    let (key, value) = part.split_once(':')?;
+                    Some((key.trim().to_string(), value.trim().parse().ok()?))
+                })
+                .collect();
+            if let Some(parts) = parts.filter(|p| !p.is_empty()) {
+                return Value::Vector(parts);
+            }
+        }
+        Value::Text(text.to_string())
+    }
+
+    fn from_json(value: &Json) -> Value {
+        match value {
+            Json::String(s) => Value::from_scalar(s),
+            Json::Number(n) => n.as_f64().map_or(Value::Null, Value::Number),
+            Json::Bool(b) => Value::Number(if *b { 1.0 } else { 0.0 }),
+            _ => Value::Null,
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        match self {
+            Value::Null => Json::Null,
+            Value::Number(n) => number_json(*n),
+            Value::Vector(parts) => Json::Object(parts.iter().map(|(k, v)| (k.clone(), number_json(*v))).collect::<Map<_, _>>()),
+            Value::Text(s) => Json::String(s.clone()),
+        }
+    }
+
+    /// Component of a vector by name (`.x`)
+    pub(super) fn member(&self, name: &str) -> Value {
+        match self {
+            Value::Vector(parts) => parts.iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map_or(Value::Null, |(_, v)| Value::Number(*v)),
+            _ => Value::Null,
+        }
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Number(n) => *n != 0.0,
+            Value::Vector(parts) => parts.iter().any(|(_, v)| *v != 0.0),
+            Value::Text(s) => !s.is_empty(),
+        }
+    }
+}
+
+fn number_json(n: f64) -> Json {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        Json::from(n as i64)
+    } else {
+        serde_json::Number::from_f64(n).map_or(Json::Null, Json::Number)
+    }
+}
+
+/// Binary operator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Add, Sub, Mul, Div,
+    Lt, Le, Gt, Ge, Eq, Ne,
+    And, Or,
+}
+
+/// Parsed expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Text(String),
+    Path(Vec<String>),
+    Call(String, Vec<Expr>),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+/// A named computed projection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub alias: String,
+    pub expr: Expr,
+}
+
+/// Parse `alias = expression`; without an alias the expression text names the column.
+pub fn parse_column(spec: &str) -> Result<Column, String> {
+    let (alias, source) = match spec.split_once('=') {
+        Some((alias, rest)) if !rest.starts_with('=') && is_identifier(alias.trim()) => (alias.trim(), rest),
+        _ => (spec.trim(), spec),
+    };
+    Ok(Column { alias: alias.to_string(), expr: parse_expr(source)? })
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(Token::Number(text.parse().map_err(|_| format!("Invalid number '{}'", text))?));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c == '"' || c == '\'' {
+            let end = chars[i + 1..].iter().position(|&q| q == c).ok_or("Unterminated string")? + i + 1;
+            tokens.push(Token::Text(chars[i + 1..end].iter().collect()));
+            i = end + 1;
+        } else {
+            let two: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let op = ["<=", ">=", "==", "!=", "&&", "||"].into_iter().find(|op| two == *op);
+            if let Some(op) = op {
+                tokens.push(Token::Op(op));
+                i += 2;
+                continue;
+            }
+            tokens.push(match c {
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                ',' => Token::Comma,
+                '+' => Token::Op("+"),
+                '-' => Token::Op("-"),
+                '*' => Token::Op("*"),
+                '/' => Token::Op("/"),
+                '<' => Token::Op("<"),
+                '>' => Token::Op(">"),
+                '!' => Token::Op("!"),
+                _ => return Err(format!("Unexpected character '{}'", c)),
+            });
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parse an expression: numbers, 'strings', paths, function calls, unary `-`/`!`,
+/// `* /`, `+ -`, comparisons, `&&` and `||` (lowest).
+pub fn parse_expr(source: &str) -> Result<Expr, String> {
+    let tokens = tokenize(source)?;
+    let mut pos = 0;
+    let expr = parse_binary(&tokens, &mut pos, 0)?;
+    if pos < tokens.len() {
+        return Err(format!("Unexpected {:?} in '{}'", tokens[pos], source.trim()));
+    }
+    Ok(expr)
+}
+
+fn binary_op(token: Option<&Token>) -> Option<(Op, u8)> {
+    let Some(Token::Op(op)) = token else { return None };
+    Some(match *op {
+        "||" => (Op::Or, 1),
+        "&&" => (Op::And, 2),
+        "==" => (Op::Eq, 3),
+        "!=" => (Op::Ne, 3),
+        "<" => (Op::Lt, 4),
+        "<=" => (Op::Le, 4),
+        ">" => (Op::Gt, 4),
+        ">=" => (Op::Ge, 4),
+        "+" => (Op::Add, 5),
+        "-" => (Op::Sub, 5),
+        "*" => (Op::Mul, 6),
+        "/" => (Op::Div, 6),
+        _ => return None,
+    })
+}
+
+fn parse_binary(tokens: &[Token], pos: &mut usize, min_precedence: u8) -> Result<Expr, String> {
+    let mut left = parse_unary(tokens, pos)?;
+    while let Some((op, precedence)) = binary_op(tokens.get(*pos)).filter(|(_, p)| *p > min_precedence) {
+        *pos += 1;
+        let right = parse_binary(tokens, pos, precedence)?;
+        left = Expr::Binary(op, Box::new(left), Box::new(right));
+    }
+    Ok(left)
+}
+
+fn parse_unary(tokens: &[Token], pos: &mut usize) -> Result<Expr, String> {
+    let token = tokens.get(*pos).cloned().ok_or("Unexpected end of expression")?;
+    *pos += 1;
+    match token {
+        Token::Op("-") => Ok(Expr::Neg(Box::new(parse_unary(tokens, pos)?))),
+        Token::Op("!") => Ok(Expr::Not(Box::new(parse_unary(tokens, pos)?))),
+        Token::Number(n) => Ok(Expr::Number(n)),
+        Token::Text(s) => Ok(Expr::Text(s)),
+        Token::LParen => {
+            let inner = parse_binary(tokens, pos, 0)?;
+            expect(tokens, pos, Token::RParen)?;
+            Ok(inner)
+        }
+        Token::Ident(name) if tokens.get(*pos) == Some(&Token::LParen) => {
+            *pos += 1;
+            let mut args = Vec::new();
+            if tokens.get(*pos) == Some(&Token::RParen) {
+                *pos += 1;
+            } else {
+                loop {
+                    args.push(parse_binary(tokens, pos, 0)?);
+                    match tokens.get(*pos) {
+                        Some(Token::Comma) => *pos += 1,
+                        _ => break,
+                    }
+                }
+                expect(tokens, pos, Token::RParen)?;
+            }
+            check_call(&name, args.len())?;
+            Ok(Expr::Call(name.to_ascii_lowercase(), args))
+        }
+        Token::Ident(path) => {
+            let segments: Vec<String> = path.split('.').map(str::to_string).collect();
+            if segments.iter().any(String::is_empty) {
+                return Err(format!("Invalid path '{}'", path));
+            }
+            Ok(Expr::Path(segments))
+        }
+        other => Err(format!("Unexpected {:?}", other)),
+    }
+}
+
+fn expect(tokens: &[Token], pos: &mut usize, token: Token) -> Result<(), String> {
+    if tokens.get(*pos) == Some(&token) {
+        *pos += 1;
+        Ok(())
+    } else {
+        Err(format!("Expected {:?}", token))
+    }
+}
+
+/// Supported functions and their argument counts.
+const FUNCTIONS: [(&str, usize, usize); 10] = [
+    ("length", 1, 1), ("distance", 2, 2), ("dot", 2, 2), ("abs", 1, 1), ("sqrt", 1, 1),
+    ("floor", 1, 1), ("ceil", 1, 1), ("round", 1, 2), ("min", 2, usize::MAX), ("max", 2, usize::MAX),
+];
+
+fn check_call(name: &str, args: usize) -> Result<(), String> {
+    let lower = name.to_ascii_lowercase();
+    match FUNCTIONS.iter().find(|(f, _, _)| *f == lower) {
+        None => Err(format!("Unknown function '{}'", name)),
+        Some((_, min, max)) if args < *min || args > *max => Err(format!("Wrong number of arguments for {}()", lower)),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Evaluate `expr`, resolving paths through `resolve`.
+pub fn eval(expr: &Expr, resolve: &dyn Fn(&[String]) -> Value) -> Value {
+    match expr {
+        Expr::Number(n) => Value::Number(*n),
+        Expr::Text(s) => Value::Text(s.clone()),
+        Expr::Path(segments) => resolve(segments),
+        Expr::Neg(inner) => map_numbers(&eval(inner, resolve), |n| -n),
+        Expr::Not(inner) => boolean(!eval(inner, resolve).is_truthy()),
+        Expr::Binary(op, left, right) => {
+            let left = eval(left, resolve);
+            match op {
+                // Short-circuit
+                Op::And if !left.is_truthy() => boolean(false),
+                Op::Or if left.is_truthy() => boolean(true),
+                Op::And | Op::Or => boolean(eval(right, resolve).is_truthy()),
+                _ => binary(*op, &left, &eval(right, resolve)),
+            }
+        }
+        Expr::Call(name, args) => {
+            let args: Vec<Value> = args.iter().map(|a| eval(a, resolve)).collect();
+            call(name, &args)
+        }
+    }
+}
+
+fn boolean(b: bool) -> Value {
+    Value::Number(if b { 1.0 } else { 0.0 })
+}
+
+fn map_numbers(value: &Value, f: impl Fn(f64) -> f64) -> Value {
+    match value {
+        Value::Number(n) => Value::Number(f(*n)),
+        Value::Vector(parts) => Value::Vector(parts.iter().map(|(k, v)| (k.clone(), f(*v))).collect()),
+        _ => Value::Null,
+    }
+}
+
+fn binary(op: Op, left: &Value, right: &Value) -> Value {
+    let arith = |f: fn(f64, f64) -> f64| match (left, right) {
+        (Value::Number(a), Value::Number(b)) => Value::Number(f(*a, *b)),
+        (Value::Vector(a), Value::Vector(b)) if a.len() == b.len() => {
+            Value::Vector(a.iter().zip(b).map(|((k, x), (_, y))| (k.clone(), f(*x, *y))).collect())
+        }
+        (Value::Vector(a), Value::Number(b)) => Value::Vector(a.iter().map(|(k, x)| (k.clone(), f(*x, *b))).collect()),
+        (Value::Number(a), Value::Vector(b)) => Value::Vector(b.iter().map(|(k, y)| (k.clone(), f(*a, *y))).collect()),
+        _ => Value::Null,
+    };
+    match op {
+        Op::Add => arith(|a, b| a + b),
+        Op::Sub => arith(|a, b| a - b),
+        Op::Mul => arith(|a, b| a * b),
+        Op::Div => arith(|a, b| a / b),
+        Op::Eq | Op::Ne => {
+            let equal = match (left, right) {
+                (Value::Null, _) | (_, Value::Null) => return Value::Null,
+                (Value::Text(a), Value::Text(b)) => a == b,
+                _ => left == right,
+            };
+            boolean(equal == (op == Op::Eq))
+        }
+        Op::Lt | Op::Le | Op::Gt | Op::Ge => match (left, right) {
+            (Value::Number(a), Value::Number(b)) => boolean(match op {
+                Op::Lt => a < b,
+                Op::Le => a <= b,
+                Op::Gt => a > b,
+                _ => a >= b,
+            }),
+            _ => Value::Null,
+        },
+        Op::And | Op::Or => unreachable!("handled by eval"),
+    }
+}
+
+fn components(value: &Value) -> Option<Vec<f64>> {
+    match value {
+        Value::Vector(parts) => Some(parts.iter().map(|(_, v)| *v).collect()),
+        Value::Number(n) => Some(vec![*n]),
+        _ => None,
+    }
+}
+
+fn call(name: &str, args: &[Value]) -> Value {
+    let number = |i: usize| match args.get(i) {
+        Some(Value::Number(n)) => Some(*n),
+        _ => None,
+    };
+    let result = match name {
+        "length" => components(&args[0]).map(|c| c.iter().map(|v| v * v).sum::<f64>().sqrt()),
+        "distance" => match binary(Op::Sub, &args[0], &args[1]) {
+            Value::Null => None,
+            diff => components(&diff).map(|c| c.iter().map(|v| v * v).sum::<f64>().sqrt()),
+        },
+        "dot" => match (components(&args[0]), components(&args[1])) {
+            (Some(a), Some(b)) if a.len() == b.len() => Some(a.iter().zip(&b).map(|(x, y)| x * y).sum()),
+            _ => None,
+        },
+        "abs" => return map_numbers(&args[0], f64::abs),
+        "sqrt" => return map_numbers(&args[0], f64::sqrt),
+        "floor" => return map_numbers(&args[0], f64::floor),
+        "ceil" => return map_numbers(&args[0], f64::ceil),
+        "round" => {
+            let scale = 10f64.powi(number(1).unwrap_or(0.0) as i32);
+            return map_numbers(&args[0], |v| (v * scale).round() / scale);
+        }
+        "min" | "max" => {
+            let numbers: Option<Vec<f64>> = (0..args.len()).map(number).collect();
+            numbers.map(|n| {
+                let fold: fn(f64, f64) -> f64 = if name == "min" { f64::min } else { f64::max };
+                n.into_iter().reduce(fold).unwrap_or(f64::NAN)
+            })
+        }
+        _ => None,
+    };
+    result.map_or(Value::Null, Value::Number)
+}
+
+/// Resolve `segments` below a component's parsed properties. Keys match case-insensitively,
+/// with or without `m_`; segments past a vector select its components.
+pub fn resolve_property(properties: &Json, segments: &[String]) -> Value {
+    let mut current = properties;
+    for (i, segment) in segments.iter().enumerate() {
+        let key = segment.strip_prefix("m_").unwrap_or(segment);
+        let next = current.as_object().and_then(|map| {
+            map.iter().find(|(k, _)| k.eq_ignore_ascii_case(key) || k.eq_ignore_ascii_case(segment)).map(|(_, v)| v)
+        });
+        match next {
+            Some(value) => current = value,
+            None => {
+                let value = Value::from_json(current);
+                return match &segments[i..] {
+                    [member] if matches!(value, Value::Vector(_)) => value.member(member),
+                    _ => Value::Null,
+                };
+            }
+        }
+    }
+    Value::from_json(current)
+}
+
+/// Value of a path for one GameObject: its own fields first, then `component.property...`.
+/// A property found under another Unity version's field name records the rename in `aliases_applied`.
+fn resolve_object_path(
+    segments: &[String],
+    file_id: &str,
+    go_block: &str,
+    components: &[Component],
+    aliases_applied: &RefCell<BTreeSet<String>>,
+) -> Value {
+    if let [key] = segments {
+        match key.as_str() {
+            "name" => return field(go_block, "m_Name").map_or(Value::Null, |n| Value::Text(n.to_string())),
+            "tag" => return Value::Text(extract_tag(go_block)),
+            "layer" => return Value::Number(f64::from(extract_layer(go_block))),
+            "active" => return Value::from_scalar(field(go_block, "m_IsActive").unwrap_or("0")),
+            "file_id" => return Value::Text(file_id.to_string()),
+            _ => {}
+        }
+    }
+    let Some((first, rest)) = segments.split_first() else { return Value::Null };
+    let Some(component) = components.iter()
+        .find(|c| c.type_name.eq_ignore_ascii_case(first) || c.script_name.as_deref().is_some_and(|s| s.eq_ignore_ascii_case(first)))
+    else { return Value::Null };
+    let Some(props) = component.properties.as_ref().filter(|_| !rest.is_empty()) else { return Value::Null };
+    let value = resolve_property(props, rest);
+    if value != Value::Null {
+        return value;
+    }
+    for (name, alias) in aliases::alternatives(&component.type_name, &rest[0]) {
+        let renamed: Vec<String> = std::iter::once(name.to_string()).chain(rest[1..].iter().cloned()).collect();
+        let value = resolve_property(props, &renamed);
+        if value != Value::Null {
+            aliases_applied.borrow_mut().insert(alias.describe(&component.type_name));
+            return value;
+        }
+    }
+    Value::Null
+}
+
+/// Rows for the GameObjects matching `selector` and `filter`: `file_id`, `name` and one value
+/// per computed column, plus `aliases_applied` when a path resolved through a field rename.
+/// Expressions are parsed up front, so a bad one fails the whole query.
+pub fn query_rows(
+    content: &str,
+    selector: &GameObjectSelector,
+    columns: &[String],
+    filter: Option<&str>,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> Result<Vec<Json>, String> {
+    let columns: Vec<Column> = columns.iter().map(|c| parse_column(c)).collect::<Result<_, _>>()?;
+    let filter = filter.map(parse_expr).transpose()?;
+    let index = BlockIndex::new(content);
+
+    let mut rows = Vec::new();
+    for file_id in select_gameobjects(content, selector, guid_cache, config) {
+        let Some(go_block) = index.get_by_class_and_id(config.gameobject_class_id, &file_id) else { continue };
+        // Components are only parsed once a path needs them
+        let components: OnceCell<Vec<Component>> = OnceCell::new();
+        let aliases_applied: RefCell<BTreeSet<String>> = RefCell::new(BTreeSet::new());
+        let resolve = |segments: &[String]| {
+            let components = components.get_or_init(|| extract_components_indexed(&index, &file_id, guid_cache, config));
+            resolve_object_path(segments, &file_id, go_block, components, &aliases_applied)
+        };
+        if filter.as_ref().is_some_and(|f| !eval(f, &resolve).is_truthy()) {
+            continue;
+        }
+        let mut row = Map::new();
+        row.insert("file_id".to_string(), Json::String(file_id.clone()));
+        row.insert("name".to_string(), Json::String(field(go_block, "m_Name").unwrap_or("").to_string()));
+        for column in &columns {
+            row.insert(column.alias.clone(), eval(&column.expr, &resolve).to_json());
+        }
+        let aliases_applied = aliases_applied.into_inner();
+        if !aliases_applied.is_empty() {
+            row.insert("aliases_applied".to_string(), Json::from(aliases_applied.into_iter().collect::<Vec<_>>()));
+        }
+        rows.push(Json::Object(row));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval_str(source: &str) -> Value {
+        let props = serde_json::json!({"LocalPosition": "{x: 3, y: 4, z: 0}", "Intensity": "2.5", "Name": "Lamp"});
+        let expr = parse_expr(source).unwrap();
+        eval(&expr, &|segments: &[String]| resolve_property(&props, segments))
+    }
+
+    #[test]
+    fn test_parse_column() {
+        let column = parse_column("dist = length(transform.localPosition)").unwrap();
+        assert_eq!(column.alias, "dist");
+        assert_eq!(column.expr, Expr::Call("length".to_string(), vec![Expr::Path(vec!["transform".to_string(), "localPosition".to_string()])]));
+        assert_eq!(parse_column("light.intensity").unwrap().alias, "light.intensity");
+        assert_eq!(parse_column("layer == 8").unwrap().alias, "layer == 8");
+        assert!(parse_column("x = length(").is_err());
+        assert!(parse_column("x = lenght(pos)").is_err());
+        assert!(parse_column("x = distance(pos)").is_err());
+        assert!(parse_column("x = 1 2").is_err());
+    }
+
+    #[test]
+    fn test_eval() {
+        assert_eq!(eval_str("localPosition"), Value::Vector(vec![("x".into(), 3.0), ("y".into(), 4.0), ("z".into(), 0.0)]));
+        assert_eq!(eval_str("length(m_LocalPosition)"), Value::Number(5.0));
+        assert_eq!(eval_str("localPosition.y * 2 + 1"), Value::Number(9.0));
+        assert_eq!(eval_str("-(1 + 2) * 3"), Value::Number(-9.0));
+        assert_eq!(eval_str("localPosition * 2"), Value::Vector(vec![("x".into(), 6.0), ("y".into(), 8.0), ("z".into(), 0.0)]));
+        assert_eq!(eval_str("distance(localPosition, localPosition * 0)"), Value::Number(5.0));
+        assert_eq!(eval_str("dot(localPosition, localPosition)"), Value::Number(25.0));
+        assert_eq!(eval_str("round(intensity / 3, 2)"), Value::Number(0.83));
+        assert_eq!(eval_str("max(intensity, 1, 4)"), Value::Number(4.0));
+        assert_eq!(eval_str("intensity > 2 && name == 'Lamp'"), Value::Number(1.0));
+        assert_eq!(eval_str("!(intensity >= 3) || missing"), Value::Number(1.0));
+        assert_eq!(eval_str("missing + 1"), Value::Null);
+        assert_eq!(eval_str("localPosition.w"), Value::Null);
+    }
+
+    #[test]
+    fn test_value_json() {
+        assert_eq!(Value::from_scalar("{r: 1, g: 0.5, b: 0, a: 1}").to_json(), serde_json::json!({"r": 1, "g": 0.5, "b": 0, "a": 1}));
+        assert_eq!(Value::from_scalar("{fileID: 0}").to_json(), serde_json::json!({"fileID": 0}));
+        assert_eq!(Value::from_scalar("{fileID: 11500000, guid: abc, type: 3}"), Value::Text("{fileID: 11500000, guid: abc, type: 3}".to_string()));
+        assert_eq!(Value::Number(f64::NAN).to_json(), Json::Null);
+    }
+
+    #[test]
+    fn test_query_rows() {
+        let content = "%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  - component: {fileID: 3}\n  m_Layer: 0\n  m_Name: Lamp\n  m_TagString: Untagged\n  m_IsActive: 1\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_LocalPosition: {x: 3, y: 4, z: 0}\n  m_Children: []\n  m_Father: {fileID: 0}\n\
+--- !u!108 &3\nLight:\n  m_GameObject: {fileID: 1}\n  m_Intensity: 2\n\
+--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {fileID: 11}\n  m_Layer: 8\n  m_Name: Far\n  m_TagString: Untagged\n  m_IsActive: 0\n\
+--- !u!4 &11\nTransform:\n  m_GameObject: {fileID: 10}\n  m_LocalPosition: {x: 0, y: 0, z: 30}\n  m_Children: []\n  m_Father: {fileID: 0}\n";
+        let selector = GameObjectSelector { name: None, tag: None, layer: None, component_type: None, active: None, under: None };
+        let columns = vec!["pos = transform.localPosition".to_string(), "dist = length(transform.localPosition)".to_string(), "light.intensity".to_string(), "layer".to_string()];
+        let config = ComponentConfig::default();
+        let rows = query_rows(content, &selector, &columns, None, &HashMap::new(), &config).unwrap();
+        assert_eq!(rows, vec![
+            serde_json::json!({"file_id": "1", "name": "Lamp", "pos": {"x": 3, "y": 4, "z": 0}, "dist": 5, "light.intensity": 2, "layer": 0}),
+            serde_json::json!({"file_id": "10", "name": "Far", "pos": {"x": 0, "y": 0, "z": 30}, "dist": 30, "light.intensity": null, "layer": 8}),
+        ]);
+
+        let near = query_rows(content, &selector, &[], Some("length(transform.localPosition) < 10 && active"), &HashMap::new(), &config).unwrap();
+        assert_eq!(near, vec![serde_json::json!({"file_id": "1", "name": "Lamp"})]);
+        assert!(query_rows(content, &selector, &["bad = (".to_string()], None, &HashMap::new(), &config).is_err());
+    }
+
+    #[test]
+    fn test_query_rows_legacy_field_names() {
+        let content = "%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  m_Layer: 0\n  m_Name: Crate\n  m_TagString: Untagged\n  m_IsActive: 1\n\
+--- !u!54 &2\nRigidbody:\n  m_GameObject: {fileID: 1}\n  m_Mass: 1\n  m_Drag: 0.5\n";
+        let selector = GameObjectSelector { name: None, tag: None, layer: None, component_type: None, active: None, under: None };
+        let columns = vec!["damping = rigidbody.linearDamping".to_string(), "rigidbody.mass".to_string()];
+        let rows = query_rows(content, &selector, &columns, Some("rigidbody.m_LinearDamping > 0"), &HashMap::new(), &ComponentConfig::default()).unwrap();
+        assert_eq!(rows, vec![serde_json::json!({
+            "file_id": "1", "name": "Crate", "damping": 0.5, "rigidbody.mass": 1,
+            "aliases_applied": ["Rigidbody.m_LinearDamping (named m_Drag before Unity 6000.0)"],
+        })]);
+    }
+}
@@ -0,0 +1,221 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::common::RenameRisk;
+use super::animation::CurveBinding;
+use super::config::ComponentConfig;
+use super::events::parse_persistent_calls;
+use super::gameobject::build_parent_map;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::{field, owner_game_object, ref_file_id};
+
+static GUID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"guid:[ \t]*([0-9a-fA-F]{32})").unwrap()
+});
+static STRING_LITERAL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"@?"((?:[^"\\\n]|\\.)*)""#).unwrap()
+});
+/// Calls that look objects up by name or hierarchy path, just before a string argument.
+static FIND_CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(Find|FindChild|FindDeepChild|FindInChildren)\s*\(\s*$").unwrap()
+});
+
+const ANIMATOR_CLASS_ID: u32 = 95;
+const ANIMATION_CLASS_ID: u32 = 111;
+
+/// An Animator or legacy Animation above the renamed object, whose clip curve paths
+/// include the object's name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatedAncestor {
+    pub game_object_id: String,
+    pub game_object_name: String,
+    /// Path from the animated ancestor down to the renamed object
+    pub relative_path: String,
+    /// Controller GUID (Animator) or clip GUIDs (Animation)
+    pub controller_guid: Option<String>,
+    pub clip_guids: Vec<String>,
+}
+
+/// Whether a name or hierarchy path string ("Root/Enemies/Boss") names `name`.
+fn names_object(text: &str, name: &str) -> bool {
+    text.split('/').any(|segment| segment == name)
+}
+
+/// Animators and Animations on strict ancestors of `go_id`, nearest first.
+pub fn animated_ancestors(content: &str, go_id: &str, config: &ComponentConfig) -> Vec<AnimatedAncestor> {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+    let parents = build_parent_map(&blocks, config);
+    let name_of = |id: &str| index.get_by_class_and_id(config.gameobject_class_id, id)
+        .and_then(|go| field(go, "m_Name"))
+        .unwrap_or("")
+        .to_string();
+
+    let mut animated: HashMap<String, (Option<String>, Vec<String>)> = HashMap::new();
+    for (class_id, _, body) in &blocks {
+        let (owner, _) = owner_game_object(&index, body);
+        match *class_id {
+            ANIMATOR_CLASS_ID => {
+                let controller = field(body, "m_Controller").and_then(|c| GUID_RE.captures(c)).map(|c| c[1].to_lowercase());
+                animated.entry(owner).or_default().0 = controller;
+            }
+            ANIMATION_CLASS_ID => {
+                let clips = animated.entry(owner).or_default();
+                for cap in GUID_RE.captures_iter(body) {
+                    let guid = cap[1].to_lowercase();
+                    if !clips.1.contains(&guid) {
+                        clips.1.push(guid);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut segments = vec![name_of(go_id)];
+    let mut current = go_id.to_string();
+    let mut seen = vec![current.clone()];
+    while let Some(parent) = parents.get(&current).filter(|p| !seen.contains(p)).cloned() {
+        if let Some((controller_guid, clip_guids)) = animated.get(&parent) {
+            let relative_path = segments.iter().rev().cloned().collect::<Vec<_>>().join("/");
+            result.push(AnimatedAncestor {
+                game_object_id: parent.clone(),
+                game_object_name: name_of(&parent),
+                relative_path,
+                controller_guid: controller_guid.clone(),
+                clip_guids: clip_guids.clone(),
+            });
+        }
+        segments.push(name_of(&parent));
+        seen.push(parent.clone());
+        current = parent;
+    }
+    result
+}
+
+/// Curve bindings of a clip whose path runs through the renamed object.
+pub fn binding_risks(clip: &str, bindings: &[CurveBinding], ancestor: &AnimatedAncestor) -> Vec<RenameRisk> {
+    let prefix: Vec<&str> = ancestor.relative_path.split('/').collect();
+    bindings.iter()
+        .filter(|b| {
+            let segments: Vec<&str> = b.path.split('/').collect();
+            segments.len() >= prefix.len() && segments[..prefix.len()] == prefix[..]
+        })
+        .map(|b| RenameRisk {
+            kind: "animation_path".to_string(),
+            severity: "high".to_string(),
+            file: clip.to_string(),
+            line: None,
+            reference: format!("{}:{}", b.path, b.attribute),
+            message: format!(
+                "Curve \"{}\" animated from \"{}\" addresses the object by name; it stops animating after the rename",
+                b.path, ancestor.game_object_name
+            ),
+        })
+        .collect()
+}
+
+/// String literals in C# source naming the object: lookups like `Find("...")` are high risk,
+/// other literals equal to the name (or a path through it) medium.
+pub fn code_risks(file: &str, source: &str, name: &str) -> Vec<RenameRisk> {
+    let mut risks = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        if line.trim_start().starts_with("//") || !line.contains(name) {
+            continue;
+        }
+        for cap in STRING_LITERAL_RE.captures_iter(line) {
+            let literal = &cap[1];
+            if !names_object(literal, name) {
+                continue;
+            }
+            let call = FIND_CALL_RE.captures(&line[..cap.get(0).unwrap().start()]).map(|c| c[1].to_string());
+            let (kind, severity, message) = match call {
+                Some(call) => ("find_call", "high", format!("{}(\"{}\") looks the object up by name", call, literal)),
+                None => ("string_literal", "medium", format!("String \"{}\" matches the object's name", literal)),
+            };
+            risks.push(RenameRisk {
+                kind: kind.to_string(),
+                severity: severity.to_string(),
+                file: file.to_string(),
+                line: Some(i as u32 + 1),
+                reference: line.trim().to_string(),
+                message,
+            });
+        }
+    }
+    risks
+}
+
+/// UnityEvent listeners of a scene/prefab whose string argument names the object
+/// (e.g. a `GameObject.Find` or `SendMessage`-style handler).
+pub fn event_risks(file: &str, content: &str, name: &str, config: &ComponentConfig) -> Vec<RenameRisk> {
+    let index = BlockIndex::new(content);
+    let mut risks = Vec::new();
+    for (class_id, _, block) in UnityYamlParser::parse_all_blocks(content) {
+        if class_id == config.gameobject_class_id || !block.contains("m_PersistentCalls:") || !block.contains(name) {
+            continue;
+        }
+        let (_, owner) = owner_game_object(&index, &block);
+        for event in parse_persistent_calls(&block) {
+            for call in &event.calls {
+                let Some(argument) = call.get("m_StringArgument").filter(|a| names_object(a, name)) else { continue };
+                let method = call.get("m_MethodName").map(String::as_str).unwrap_or("");
+                let target = call.get("m_Target").and_then(|t| ref_file_id(t)).unwrap_or("0");
+                risks.push(RenameRisk {
+                    kind: "unity_event_argument".to_string(),
+                    severity: "medium".to_string(),
+                    file: file.to_string(),
+                    line: None,
+                    reference: format!("{}.{} → {}", owner, event.path, method),
+                    message: format!(
+                        "UnityEvent on \"{}\" calls {} (target fileID {}) with the string \"{}\"",
+                        owner, method, target, argument
+                    ),
+                });
+            }
+        }
+    }
+    risks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  - component: {fileID: 3}\n  m_Name: Robot\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Children:\n  - {fileID: 11}\n  m_Father: {fileID: 0}\n\
+--- !u!95 &3\nAnimator:\n  m_GameObject: {fileID: 1}\n  m_Controller: {fileID: 9100000, guid: CCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCC, type: 2}\n\
+--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {fileID: 11}\n  m_Name: Arm\n\
+--- !u!4 &11\nTransform:\n  m_GameObject: {fileID: 10}\n  m_Children:\n  - {fileID: 21}\n  m_Father: {fileID: 2}\n\
+--- !u!1 &20\nGameObject:\n  m_Component:\n  - component: {fileID: 21}\n  m_Name: Hand\n\
+--- !u!4 &21\nTransform:\n  m_GameObject: {fileID: 20}\n  m_Children: []\n  m_Father: {fileID: 11}\n";
+
+    fn binding(path: &str) -> CurveBinding {
+        CurveBinding { path: path.to_string(), attribute: "m_LocalPosition".to_string(), class_id: 4, script_guid: None }
+    }
+
+    #[test]
+    fn test_animation_paths_through_renamed_object() {
+        let ancestors = animated_ancestors(SCENE, "10", &ComponentConfig::default());
+        assert_eq!(ancestors.len(), 1);
+        assert_eq!(ancestors[0].relative_path, "Arm");
+        assert_eq!(ancestors[0].controller_guid.as_deref(), Some("cccccccccccccccccccccccccccccccc"));
+
+        let bindings = vec![binding("Arm"), binding("Arm/Hand"), binding("Armor"), binding("")];
+        let risks = binding_risks("Assets/Robot.anim", &bindings, &ancestors[0]);
+        let refs: Vec<&str> = risks.iter().map(|r| r.reference.as_str()).collect();
+        assert_eq!(refs, vec!["Arm:m_LocalPosition", "Arm/Hand:m_LocalPosition"]);
+        assert!(animated_ancestors(SCENE, "1", &ComponentConfig::default()).is_empty(), "own Animator paths exclude the root name");
+    }
+
+    #[test]
+    fn test_code_risks_separate_find_calls_from_literals() {
+        let source = "class A {\n  void Start() {\n    var arm = transform.Find(\"Arm/Hand\");\n    // GameObject.Find(\"Arm\")\n    if (name == \"Arm\") { }\n    Debug.Log(\"Armory\");\n  }\n}\n";
+        let risks = code_risks("Assets/A.cs", source, "Arm");
+        let found: Vec<(&str, Option<u32>)> = risks.iter().map(|r| (r.kind.as_str(), r.line)).collect();
+        assert_eq!(found, vec![("find_call", Some(3)), ("string_literal", Some(5))]);
+    }
+}
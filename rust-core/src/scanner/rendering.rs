@@ -0,0 +1,240 @@
+use crate::common::{CameraInfo, LightInfo};
+use super::parser::{BlockIndex, UnityYamlParser};
+
+const CAMERA_CLASS_ID: u32 = 20;
+const LIGHT_CLASS_ID: u32 = 108;
+
+/// Value of a top-level component field (`  key: value`).
+pub fn field<'a>(block: &'a str, key: &str) -> Option<&'a str> {
+    block.lines()
+        .find_map(|line| line.strip_prefix("  ")?.strip_prefix(key)?.strip_prefix(':'))
+        .map(str::trim)
+}
+
+/// Value of a field nested one level under a top-level map key (`  parent:\n    key: value`).
+pub fn nested_field<'a>(block: &'a str, parent: &str, key: &str) -> Option<&'a str> {
+    let parent_line = format!("  {}:", parent);
+    let mut lines = block.lines().skip_while(|line| line.trim_end() != parent_line);
+    lines.next()?;
+    lines
+        .take_while(|line| line.starts_with("    "))
+        .find_map(|line| line.strip_prefix("    ")?.strip_prefix(key)?.strip_prefix(':'))
+        .map(str::trim)
+}
+
+/// File ID referenced by a `{fileID: N}` value.
+pub fn ref_file_id(value: &str) -> Option<&str> {
+    let rest = value.split("fileID:").nth(1)?.trim_start();
+    let end = rest.find(|c: char| c != '-' && !c.is_ascii_digit()).unwrap_or(rest.len());
+    Some(&rest[..end]).filter(|id| !id.is_empty())
+}
+
+pub fn number(block: &str, key: &str) -> f64 {
+    field(block, key).and_then(|v| v.parse().ok()).unwrap_or(0.0)
+}
+
+pub fn flag(block: &str, key: &str) -> bool {
+    field(block, key) == Some("1")
+}
+
+/// Name and file ID of the GameObject owning a component block.
+pub fn owner_game_object(index: &BlockIndex, block: &str) -> (String, String) {
+    let go_id = field(block, "m_GameObject").and_then(ref_file_id).unwrap_or("0").to_string();
+    let name = index.get_by_class_and_id(1, &go_id)
+        .and_then(|go| field(go, "m_Name"))
+        .unwrap_or("")
+        .to_string();
+    (go_id, name)
+}
+
+/// Decode a layer bitmask into layer names ("Layer N" for unnamed layers).
+pub fn decode_layer_mask(mask: u32, layer_names: &[Option<String>]) -> Vec<String> {
+    if mask == u32::MAX {
+        return vec!["Everything".to_string()];
+    }
+    (0..32)
+        .filter(|bit| mask & (1 << bit) != 0)
+        .map(|bit| {
+            layer_names.get(bit as usize)
+                .cloned()
+                .flatten()
+                .unwrap_or_else(|| format!("Layer {}", bit))
+        })
+        .collect()
+}
+
+fn culling_mask(block: &str) -> u32 {
+    nested_field(block, "m_CullingMask", "m_Bits")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(u32::MAX)
+}
+
+/// Component blocks of a class that belong to a GameObject (skips stripped placeholders).
+fn component_blocks(content: &str, class_id: u32) -> Vec<(String, String)> {
+    UnityYamlParser::parse_all_blocks(content)
+        .into_iter()
+        .filter(|(cid, _, body)| *cid == class_id && field(body, "m_GameObject").is_some())
+        .map(|(_, file_id, body)| (file_id, body))
+        .collect()
+}
+
+/// Summaries of every Camera in a scene or prefab.
+pub fn list_cameras(content: &str, layer_names: &[Option<String>]) -> Vec<CameraInfo> {
+    let index = BlockIndex::new(content);
+    component_blocks(content, CAMERA_CLASS_ID)
+        .into_iter()
+        .map(|(file_id, block)| {
+            let (game_object_id, game_object_name) = owner_game_object(&index, &block);
+            let mask = culling_mask(&block);
+            CameraInfo {
+                file_id,
+                game_object_id,
+                game_object_name,
+                enabled: flag(&block, "m_Enabled"),
+                projection: if flag(&block, "orthographic") { "orthographic" } else { "perspective" }.to_string(),
+                field_of_view: number(&block, "field of view"),
+                orthographic_size: number(&block, "orthographic size"),
+                near_clip: number(&block, "near clip plane"),
+                far_clip: number(&block, "far clip plane"),
+                depth: number(&block, "m_Depth"),
+                clear_flags: match field(&block, "m_ClearFlags") {
+                    Some("2") => "solid_color",
+                    Some("3") => "depth_only",
+                    Some("4") => "nothing",
+                    _ => "skybox",
+                }
+                .to_string(),
+                culling_mask: mask,
+                culling_layers: decode_layer_mask(mask, layer_names),
+                hdr: flag(&block, "m_HDR"),
+                target_display: number(&block, "m_TargetDisplay") as u32,
+            }
+        })
+        .collect()
+}
+
+/// Summaries of every Light in a scene or prefab.
+pub fn list_lights(content: &str, layer_names: &[Option<String>]) -> Vec<LightInfo> {
+    let index = BlockIndex::new(content);
+    component_blocks(content, LIGHT_CLASS_ID)
+        .into_iter()
+        .map(|(file_id, block)| {
+            let (game_object_id, game_object_name) = owner_game_object(&index, &block);
+            let mask = culling_mask(&block);
+            LightInfo {
+                file_id,
+                game_object_id,
+                game_object_name,
+                enabled: flag(&block, "m_Enabled"),
+                light_type: match field(&block, "m_Type") {
+                    Some("0") => "spot",
+                    Some("1") => "directional",
+                    Some("3") => "area",
+                    Some("4") => "disc",
+                    _ => "point",
+                }
+                .to_string(),
+                intensity: number(&block, "m_Intensity"),
+                range: number(&block, "m_Range"),
+                spot_angle: number(&block, "m_SpotAngle"),
+                shadows: match nested_field(&block, "m_Shadows", "m_Type") {
+                    Some("1") => "hard",
+                    Some("2") => "soft",
+                    _ => "none",
+                }
+                .to_string(),
+                // LightmapBakeType: Realtime = 4, Baked = 2, Mixed = 1
+                bake_mode: match field(&block, "m_Lightmapping") {
+                    Some("1") => "mixed",
+                    Some("2") => "baked",
+                    _ => "realtime",
+                }
+                .to_string(),
+                culling_mask: mask,
+                culling_layers: decode_layer_mask(mask, layer_names),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1
+--- !u!1 &100
+GameObject:
+  m_Component:
+  - component: {fileID: 101}
+  m_Name: Main Camera
+  m_IsActive: 1
+--- !u!20 &101
+Camera:
+  m_GameObject: {fileID: 100}
+  m_Enabled: 1
+  serializedVersion: 2
+  m_ClearFlags: 2
+  near clip plane: 0.3
+  far clip plane: 1000
+  field of view: 60
+  orthographic: 0
+  orthographic size: 5
+  m_Depth: -1
+  m_CullingMask:
+    serializedVersion: 2
+    m_Bits: 4294967295
+  m_TargetDisplay: 0
+  m_HDR: 1
+--- !u!1 &200
+GameObject:
+  m_Component:
+  - component: {fileID: 201}
+  m_Name: Sun
+  m_IsActive: 1
+--- !u!108 &201
+Light:
+  m_GameObject: {fileID: 200}
+  m_Enabled: 1
+  serializedVersion: 10
+  m_Type: 1
+  m_Intensity: 1.5
+  m_Range: 10
+  m_SpotAngle: 30
+  m_CullingMask:
+    serializedVersion: 2
+    m_Bits: 3
+  m_Shadows:
+    m_Type: 2
+    m_Resolution: -1
+  m_Lightmapping: 1
+";
+
+    #[test]
+    fn test_list_cameras() {
+        let cameras = list_cameras(SCENE, &[]);
+        assert_eq!(cameras.len(), 1);
+        let cam = &cameras[0];
+        assert_eq!(cam.game_object_name, "Main Camera");
+        assert_eq!(cam.projection, "perspective");
+        assert_eq!(cam.field_of_view, 60.0);
+        assert_eq!(cam.near_clip, 0.3);
+        assert_eq!(cam.depth, -1.0);
+        assert_eq!(cam.clear_flags, "solid_color");
+        assert_eq!(cam.culling_layers, vec!["Everything".to_string()]);
+        assert!(cam.hdr);
+    }
+
+    #[test]
+    fn test_list_lights() {
+        let names = vec![Some("Default".to_string()), None];
+        let lights = list_lights(SCENE, &names);
+        assert_eq!(lights.len(), 1);
+        let light = &lights[0];
+        assert_eq!(light.game_object_name, "Sun");
+        assert_eq!(light.light_type, "directional");
+        assert_eq!(light.intensity, 1.5);
+        assert_eq!(light.shadows, "soft", "nested m_Shadows.m_Type, not the light's m_Type");
+        assert_eq!(light.bake_mode, "mixed");
+        assert_eq!(light.culling_layers, vec!["Default".to_string(), "Layer 1".to_string()]);
+    }
+}
@@ -0,0 +1,236 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+static HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^--- !u!\d+ &(-?\d+)").unwrap()
+});
+static GUID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"guid:[ \t]*([0-9a-fA-F]{32})").unwrap()
+});
+
+/// Which properties a replacement applies to: `[ClassName.]property[.sub]`.
+///
+/// A leading segment that starts with an uppercase letter (or `*`) filters by the block's
+/// root type name (`MeshRenderer`, `MonoBehaviour`, ...). The remaining dotted path must match
+/// the end of the property's key path, so `m_Materials`, `MeshRenderer.m_Materials` and
+/// `m_Modification.m_Modifications.value` all work.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertySelector {
+    pub class_name: Option<String>,
+    pub path: Vec<String>,
+}
+
+impl PropertySelector {
+    pub fn parse(selector: &str) -> Option<Self> {
+        let mut segments: Vec<String> = selector.split('.')
+            .map(|s| s.trim().to_string())
+            .collect();
+        if segments.iter().any(|s| s.is_empty()) {
+            return None;
+        }
+        let class_name = if segments.len() > 1
+            && segments[0].chars().next().is_some_and(|c| c.is_ascii_uppercase() || c == '*')
+        {
+            Some(segments.remove(0)).filter(|c| c != "*")
+        } else {
+            None
+        };
+        Some(PropertySelector { class_name, path: segments })
+    }
+
+    fn matches(&self, class_name: &str, key_path: &[String]) -> bool {
+        if self.class_name.as_deref().is_some_and(|c| c != class_name) {
+            return false;
+        }
+        key_path.len() >= self.path.len() && key_path.ends_with(&self.path)
+    }
+}
+
+/// One rewritten property value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueEdit {
+    /// 1-based line number
+    pub line: u32,
+    pub file_id: String,
+    pub class_name: String,
+    pub property: String,
+    pub old_line: String,
+    pub new_line: String,
+}
+
+/// Replace values of the selected properties, editing only the matched value text so
+/// indentation, key order and everything else in the file stay byte-identical.
+///
+/// A value matches when it equals `old_value` exactly, or when it is an object reference
+/// whose `guid` equals `old_value` and `new_value` is a GUID (then only the GUID is swapped,
+/// e.g. retargeting a material). `new_value` is inserted as given, so it must be YAML-encoded.
+/// `content` may be the raw file: CRLF line endings are kept as they are.
+pub fn replace_values(
+    content: &str,
+    selector: &PropertySelector,
+    old_value: &str,
+    new_value: &str,
+) -> (String, Vec<ValueEdit>) {
+    let mut out = String::with_capacity(content.len());
+    let mut edits = Vec::new();
+    let mut file_id = String::new();
+    let mut class_name = String::new();
+    let mut expect_class = false;
+    // (indent, key) of the enclosing mappings
+    let mut stack: Vec<(usize, String)> = Vec::new();
+
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        let body = line.trim_end_matches('\n').trim_end_matches('\r');
+        if let Some(cap) = HEADER_RE.captures(body) {
+            file_id = cap[1].to_string();
+            class_name.clear();
+            expect_class = true;
+            stack.clear();
+            out.push_str(line);
+            continue;
+        }
+        if expect_class {
+            expect_class = false;
+            if let Some(name) = body.strip_suffix(':').filter(|n| !n.starts_with(' ')) {
+                class_name = name.to_string();
+                out.push_str(line);
+                continue;
+            }
+        }
+        if class_name.is_empty() {
+            out.push_str(line);
+            continue;
+        }
+
+        let indent = body.len() - body.trim_start().len();
+        let trimmed = &body[indent..];
+        // "- key: value" opens a mapping two columns in; "- value" is a plain list item
+        let (item_indent, rest, is_item) = match trimmed.strip_prefix("- ") {
+            Some(rest) => (indent + 2, rest, true),
+            None => (indent, trimmed, false),
+        };
+
+        let (key, value_start) = match split_key(rest) {
+            Some((key, offset)) => (Some(key), body.len() - rest.len() + offset),
+            None if is_item => (None, body.len() - rest.len()),
+            None => {
+                out.push_str(line);
+                continue;
+            }
+        };
+
+        let path: Vec<String> = match key {
+            Some(key) => {
+                stack.retain(|(ind, _)| *ind < item_indent);
+                stack.push((item_indent, key.to_string()));
+                stack.iter().map(|(_, k)| k.clone()).collect()
+            }
+            None => {
+                stack.retain(|(ind, _)| *ind <= indent);
+                stack.iter().map(|(_, k)| k.clone()).collect()
+            }
+        };
+
+        let value = body[value_start..].trim();
+        let replaced = if value.is_empty() || !selector.matches(&class_name, &path) {
+            None
+        } else if value == old_value {
+            let lead = body[value_start..].len() - body[value_start..].trim_start().len();
+            Some(format!("{}{}", &body[..value_start + lead], new_value))
+        } else if is_guid(new_value) {
+            GUID_RE.captures(value)
+                .filter(|c| c[1].eq_ignore_ascii_case(old_value))
+                .map(|c| {
+                    let m = c.get(1).unwrap();
+                    let at = value_start + (body[value_start..].len() - body[value_start..].trim_start().len());
+                    format!("{}{}{}", &body[..at + m.start()], new_value, &body[at + m.end()..])
+                })
+        } else {
+            None
+        };
+
+        match replaced {
+            Some(new_body) if new_body != body => {
+                edits.push(ValueEdit {
+                    line: (i + 1) as u32,
+                    file_id: file_id.clone(),
+                    class_name: class_name.clone(),
+                    property: path.join("."),
+                    old_line: body.to_string(),
+                    new_line: new_body.clone(),
+                });
+                out.push_str(&new_body);
+                out.push_str(&line[body.len()..]);
+            }
+            _ => out.push_str(line),
+        }
+    }
+
+    (out, edits)
+}
+
+fn is_guid(text: &str) -> bool {
+    text.len() == 32 && text.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Split "key: value" / "key:" and return the key plus the byte offset where the value starts.
+fn split_key(s: &str) -> Option<(&str, usize)> {
+    if s.starts_with('{') || s.starts_with('[') {
+        return None;
+    }
+    let colon = s.find(": ").or_else(|| s.strip_suffix(':').map(|k| k.len()))?;
+    let key = s[..colon].trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some((key, (colon + 1).min(s.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1\n\
+--- !u!1 &100\nGameObject:\n  m_Layer: 3\n  m_Name: Cube\n\
+--- !u!23 &200\nMeshRenderer:\n  m_GameObject: {fileID: 100}\n  m_Materials:\n  - {fileID: 2100000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 2}\n  - {fileID: 2100000, guid: cccccccccccccccccccccccccccccccc, type: 2}\n\
+--- !u!1001 &300\nPrefabInstance:\n  m_Modification:\n    m_Modifications:\n    - target: {fileID: 1, guid: dddddddddddddddddddddddddddddddd, type: 3}\n      propertyPath: m_Layer\n      value: 3\n      objectReference: {fileID: 0}\n";
+
+    #[test]
+    fn test_parse_selector() {
+        assert_eq!(PropertySelector::parse("m_Layer").unwrap(), PropertySelector { class_name: None, path: vec!["m_Layer".into()] });
+        let s = PropertySelector::parse("MeshRenderer.m_Materials").unwrap();
+        assert_eq!(s.class_name.as_deref(), Some("MeshRenderer"));
+        assert!(PropertySelector::parse("*.m_Layer").unwrap().class_name.is_none());
+        assert!(PropertySelector::parse("a..b").is_none());
+    }
+
+    #[test]
+    fn test_replace_scalar_preserves_formatting() {
+        let sel = PropertySelector::parse("GameObject.m_Layer").unwrap();
+        let (out, edits) = replace_values(SCENE, &sel, "3", "8");
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].line, 4);
+        assert_eq!(edits[0].file_id, "100");
+        assert_eq!(out, SCENE.replacen("  m_Layer: 3\n", "  m_Layer: 8\n", 1));
+    }
+
+    #[test]
+    fn test_replace_guid_in_list_items() {
+        let sel = PropertySelector::parse("m_Materials").unwrap();
+        let (out, edits) = replace_values(SCENE, &sel, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].property, "m_Materials");
+        assert!(out.contains("  - {fileID: 2100000, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 2}\n"));
+        assert!(out.contains("guid: cccccccccccccccccccccccccccccccc"));
+    }
+
+    #[test]
+    fn test_replace_nested_modification_value() {
+        let sel = PropertySelector::parse("m_Modifications.value").unwrap();
+        let (out, edits) = replace_values(SCENE, &sel, "3", "8");
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].property, "m_Modification.m_Modifications.value");
+        assert!(out.contains("      value: 8\n"));
+        assert!(out.contains("  m_Layer: 3\n"));
+    }
+}
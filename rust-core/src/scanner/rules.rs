@@ -0,0 +1,280 @@
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::common::RuleViolation;
+use super::config::ComponentConfig;
+use super::gameobject::{build_parent_map, extract_tag};
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::rendering::field;
+
+/// Scene conventions, loaded from a JSON or TOML (`.toml` extension) rule file.
+/// Component names are type names ("Rigidbody") or MonoBehaviour script names ("PlayerController").
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleSet {
+    /// Deepest allowed hierarchy level (root objects are depth 0)
+    pub max_depth: Option<u32>,
+    pub naming: Vec<NamingRule>,
+    pub required_components: Vec<RequiredComponentsRule>,
+    pub forbidden_components: Vec<ForbiddenComponentsRule>,
+}
+
+/// Object names must match `pattern` (a regex), optionally only for a tag and/or component.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NamingRule {
+    pub id: Option<String>,
+    pub tag: Option<String>,
+    pub component: Option<String>,
+    pub pattern: String,
+    pub severity: Option<String>,
+}
+
+/// Objects with `tag` (every object when unset) must carry all `components`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequiredComponentsRule {
+    pub id: Option<String>,
+    pub tag: Option<String>,
+    pub components: Vec<String>,
+    pub severity: Option<String>,
+}
+
+/// Objects below an ancestor carrying `under` (e.g. "Canvas") must not carry any of `components`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ForbiddenComponentsRule {
+    pub id: Option<String>,
+    pub under: String,
+    pub components: Vec<String>,
+    pub severity: Option<String>,
+}
+
+/// A GameObject with the features rules are checked against.
+#[derive(Debug, Clone)]
+pub struct RuleSubject {
+    pub file_id: String,
+    pub name: String,
+    pub tag: String,
+    /// Names from the root down to the object, joined with '/'
+    pub path: String,
+    pub depth: u32,
+    pub components: HashSet<String>,
+    /// Components of every ancestor
+    pub ancestor_components: HashSet<String>,
+}
+
+impl RuleSet {
+    /// Parse a rule file; the format follows the extension (`.toml`, anything else is JSON).
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = crate::safety::read_to_string(path)
+            .map_err(|e| format!("Cannot read rules {}: {}", path.display(), e))?;
+        let is_toml = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let rules: RuleSet = if is_toml {
+            toml::from_str(&text).map_err(|e| format!("Invalid rules {}: {}", path.display(), e))?
+        } else {
+            serde_json::from_str(&text).map_err(|e| format!("Invalid rules {}: {}", path.display(), e))?
+        };
+        rules.compile_patterns()?;
+        Ok(rules)
+    }
+
+    fn compile_patterns(&self) -> Result<Vec<regex::Regex>, String> {
+        self.naming.iter()
+            .map(|rule| regex::Regex::new(&rule.pattern).map_err(|e| format!("Invalid naming pattern {:?}: {}", rule.pattern, e)))
+            .collect()
+    }
+
+    /// Number of individual rules, for reporting.
+    pub fn rule_count(&self) -> u32 {
+        (self.max_depth.is_some() as usize + self.naming.len() + self.required_components.len() + self.forbidden_components.len()) as u32
+    }
+
+    /// Every violation of every rule, in object order.
+    pub fn check(&self, subjects: &[RuleSubject]) -> Result<Vec<RuleViolation>, String> {
+        let patterns = self.compile_patterns()?;
+        let mut violations = Vec::new();
+        for subject in subjects {
+            let mut violate = |rule: String, severity: &Option<String>, message: String| {
+                violations.push(RuleViolation {
+                    rule,
+                    severity: severity.clone().unwrap_or_else(|| "error".to_string()),
+                    game_object_id: subject.file_id.clone(),
+                    game_object_name: subject.name.clone(),
+                    path: subject.path.clone(),
+                    message,
+                });
+            };
+
+            if let Some(max) = self.max_depth.filter(|max| subject.depth > *max) {
+                violate("max_depth".to_string(), &None, format!("Depth {} exceeds the maximum of {}", subject.depth, max));
+            }
+            for (i, (rule, pattern)) in self.naming.iter().zip(&patterns).enumerate() {
+                let applies = rule.tag.as_ref().is_none_or(|t| *t == subject.tag)
+                    && rule.component.as_ref().is_none_or(|c| subject.components.contains(c));
+                if applies && !pattern.is_match(&subject.name) {
+                    let id = rule.id.clone().unwrap_or_else(|| format!("naming[{}]", i));
+                    violate(id, &rule.severity, format!("Name \"{}\" does not match {}", subject.name, rule.pattern));
+                }
+            }
+            for (i, rule) in self.required_components.iter().enumerate() {
+                if rule.tag.as_ref().is_some_and(|t| *t != subject.tag) {
+                    continue;
+                }
+                let missing: Vec<&str> = rule.components.iter()
+                    .filter(|c| !subject.components.contains(*c))
+                    .map(String::as_str)
+                    .collect();
+                if !missing.is_empty() {
+                    let id = rule.id.clone().unwrap_or_else(|| format!("required_components[{}]", i));
+                    violate(id, &rule.severity, format!("Missing required component(s): {}", missing.join(", ")));
+                }
+            }
+            for (i, rule) in self.forbidden_components.iter().enumerate() {
+                if !subject.ancestor_components.contains(&rule.under) {
+                    continue;
+                }
+                let present: Vec<&str> = rule.components.iter()
+                    .filter(|c| subject.components.contains(*c))
+                    .map(String::as_str)
+                    .collect();
+                if !present.is_empty() {
+                    let id = rule.id.clone().unwrap_or_else(|| format!("forbidden_components[{}]", i));
+                    violate(id, &rule.severity, format!("Component(s) not allowed under {}: {}", rule.under, present.join(", ")));
+                }
+            }
+        }
+        Ok(violations)
+    }
+}
+
+/// Rule subjects for every non-stripped GameObject of Unity YAML content, in file order.
+/// MonoBehaviours contribute their script name (from the GUID cache) as well as their type.
+pub fn subjects(content: &str, guid_cache: &HashMap<String, String>, config: &ComponentConfig) -> Vec<RuleSubject> {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+    let parents = build_parent_map(&blocks, config);
+    let script_re = regex::Regex::new(&config.script_ref_pattern()).expect("Invalid regex");
+
+    let gameobjects: HashMap<&str, &str> = blocks.iter()
+        .filter(|(class_id, _, body)| *class_id == config.gameobject_class_id && body.contains("m_Component"))
+        .map(|(_, id, body)| (id.as_str(), body.as_str()))
+        .collect();
+    let components_of = |go_body: &str| -> HashSet<String> {
+        let mut names = HashSet::new();
+        for comp_id in UnityYamlParser::parse_component_refs(go_body) {
+            let Some((class_id, body)) = index.get(&comp_id) else { continue };
+            names.insert(body.lines().next().unwrap_or("").trim_end_matches(':').to_string());
+            if config.is_script_container(class_id) {
+                let script = script_re.captures(body)
+                    .and_then(|c| guid_cache.get(c.get(1)?.as_str()))
+                    .and_then(|path| Path::new(path).file_stem())
+                    .map(|stem| stem.to_string_lossy().to_string());
+                names.extend(script);
+            }
+        }
+        names
+    };
+    let components: HashMap<&str, HashSet<String>> = gameobjects.iter().map(|(id, body)| (*id, components_of(body))).collect();
+    let name_of = |id: &str| gameobjects.get(id).and_then(|go| field(go, "m_Name")).unwrap_or("").to_string();
+
+    blocks.iter()
+        .filter(|(_, id, _)| gameobjects.contains_key(id.as_str()))
+        .map(|(_, go_id, _)| {
+            let mut names = vec![name_of(go_id)];
+            let mut ancestor_components = HashSet::new();
+            let mut seen: HashSet<&str> = HashSet::from([go_id.as_str()]);
+            let mut current = go_id.as_str();
+            while let Some(parent) = parents.get(current).filter(|p| seen.insert(p.as_str())) {
+                names.push(name_of(parent));
+                if let Some(comps) = components.get(parent.as_str()) {
+                    ancestor_components.extend(comps.iter().cloned());
+                }
+                current = parent;
+            }
+            let depth = (names.len() - 1) as u32;
+            names.reverse();
+            RuleSubject {
+                file_id: go_id.clone(),
+                name: name_of(go_id),
+                tag: extract_tag(gameobjects[go_id.as_str()]),
+                path: names.join("/"),
+                depth,
+                components: components.get(go_id.as_str()).cloned().unwrap_or_default(),
+                ancestor_components,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  - component: {fileID: 3}\n  m_Name: HUD\n  m_TagString: Untagged\n\
+--- !u!224 &2\nRectTransform:\n  m_GameObject: {fileID: 1}\n  m_Children:\n  - {fileID: 11}\n  m_Father: {fileID: 0}\n\
+--- !u!223 &3\nCanvas:\n  m_GameObject: {fileID: 1}\n\
+--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {fileID: 11}\n  - component: {fileID: 12}\n  m_Name: health bar\n  m_TagString: Untagged\n\
+--- !u!224 &11\nRectTransform:\n  m_GameObject: {fileID: 10}\n  m_Children: []\n  m_Father: {fileID: 2}\n\
+--- !u!54 &12\nRigidbody:\n  m_GameObject: {fileID: 10}\n\
+--- !u!1 &20\nGameObject:\n  m_Component:\n  - component: {fileID: 21}\n  - component: {fileID: 22}\n  m_Name: Player\n  m_TagString: Player\n\
+--- !u!4 &21\nTransform:\n  m_GameObject: {fileID: 20}\n  m_Children: []\n  m_Father: {fileID: 0}\n\
+--- !u!114 &22\nMonoBehaviour:\n  m_GameObject: {fileID: 20}\n  m_Script: {fileID: 11500000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}\n";
+
+    fn scene_subjects() -> Vec<RuleSubject> {
+        let mut cache = HashMap::new();
+        cache.insert("a".repeat(32), "Assets/Scripts/PlayerController.cs".to_string());
+        subjects(SCENE, &cache, &ComponentConfig::default())
+    }
+
+    #[test]
+    fn test_subjects() {
+        let subjects = scene_subjects();
+        assert_eq!(subjects.len(), 3);
+        let bar = subjects.iter().find(|s| s.file_id == "10").unwrap();
+        assert_eq!((bar.path.as_str(), bar.depth), ("HUD/health bar", 1));
+        assert!(bar.ancestor_components.contains("Canvas"));
+        let player = subjects.iter().find(|s| s.file_id == "20").unwrap();
+        assert!(player.components.contains("PlayerController") && player.components.contains("MonoBehaviour"));
+    }
+
+    #[test]
+    fn test_check_rules() {
+        let rules: RuleSet = toml::from_str(r#"
+max_depth = 0
+
+[[naming]]
+pattern = "^[A-Z]"
+severity = "warning"
+
+[[required_components]]
+id = "player-needs-rigidbody"
+tag = "Player"
+components = ["PlayerController", "Rigidbody"]
+
+[[forbidden_components]]
+under = "Canvas"
+components = ["Rigidbody", "Collider"]
+"#).unwrap();
+        assert_eq!(rules.rule_count(), 4);
+        let violations = rules.check(&scene_subjects()).unwrap();
+        let rule_ids: Vec<&str> = violations.iter().map(|v| v.rule.as_str()).collect();
+        assert_eq!(rule_ids, vec!["max_depth", "naming[0]", "forbidden_components[0]", "player-needs-rigidbody"]);
+        assert_eq!(violations[1].severity, "warning");
+        assert_eq!(violations[3].message, "Missing required component(s): Rigidbody");
+    }
+
+    #[test]
+    fn test_load_rejects_bad_pattern_and_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("rules.json");
+        std::fs::write(&json, r#"{"naming": [{"pattern": "("}]}"#).unwrap();
+        assert!(RuleSet::load(&json).unwrap_err().contains("Invalid naming pattern"));
+        std::fs::write(&json, r#"{"max_depht": 3}"#).unwrap();
+        assert!(RuleSet::load(&json).is_err());
+        std::fs::write(&json, r#"{"max_depth": 3}"#).unwrap();
+        assert_eq!(RuleSet::load(&json).unwrap().max_depth, Some(3));
+    }
+}
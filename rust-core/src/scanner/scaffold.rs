@@ -0,0 +1,555 @@
+//! New scenes and prefabs generated from a declarative spec: a tree of named GameObjects with
+//! their components and property values. Built-in components start from the values Unity
+//! writes for a fresh component (see `defaults`), so a spec only lists what differs.
+//!
+//! ```json
+//! { "objects": [
+//!   { "name": "Player", "tag": "Player", "position": [0, 1, 0], "rotation": [0, 90, 0],
+//!     "components": [
+//!       { "type": "Rigidbody", "properties": { "m_Mass": 2 } },
+//!       { "type": "MonoBehaviour", "script": "Assets/Scripts/PlayerController.cs",
+//!         "properties": { "speed": 5, "cam": { "ref": "Player/Camera", "component": "Camera" } } } ],
+//!     "children": [ { "name": "Camera", "components": [ { "type": "Camera" } ] } ] } ] }
+//! ```
+//!
+//! Property values: numbers and strings encoded as Unity writes them (see `common::yaml`),
+//! booleans as 0/1, `null` as an empty object reference, `{"ref": "<path or unique name>",
+//! "component": "<type or script>"}` for objects of the file, `{"guid": ..., "fileID": ...,
+//! "type": ...}` for assets, objects of numbers as flow mappings (`{x: 0, y: 1, z: 0}`), other
+//! objects as nested mappings, arrays as sequences.
+//! Dotted keys (`m_Shadows.m_Type`) address nested fields.
+
+use serde::Deserialize;
+use serde_json::{Map, Value as Json};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::common::yaml::{encode_string, encode_value, format_float};
+use crate::common::Quaternion;
+use super::defaults;
+use super::transform::multiply;
+use super::transplant::fresh_file_id;
+
+const GAMEOBJECT_CLASS_ID: u32 = 1;
+const TRANSFORM_CLASS_ID: u32 = 4;
+const RECT_TRANSFORM_CLASS_ID: u32 = 224;
+const MONOBEHAVIOUR_CLASS_ID: u32 = 114;
+const SCENE_ROOTS_CLASS_ID: u32 = 1660057539;
+const SCENE_ROOTS_FILE_ID: &str = "9223372036854775807";
+
+/// Built-in component types a spec may name without a `class_id`.
+const CLASS_IDS: &[(&str, u32)] = &[
+    ("Transform", 4), ("Camera", 20), ("MeshRenderer", 23), ("MeshFilter", 33), ("Rigidbody", 54),
+    ("MeshCollider", 64), ("BoxCollider", 65), ("AudioListener", 81), ("AudioSource", 82), ("Animator", 95),
+    ("Light", 108), ("Animation", 111), ("MonoBehaviour", 114), ("LineRenderer", 120), ("SphereCollider", 135),
+    ("CapsuleCollider", 136), ("SkinnedMeshRenderer", 137), ("CharacterController", 143), ("Rigidbody2D", 50),
+    ("BoxCollider2D", 61), ("CircleCollider2D", 58), ("SpriteRenderer", 212), ("CanvasRenderer", 222),
+    ("Canvas", 223), ("RectTransform", 224), ("CanvasGroup", 225),
+];
+
+/// Components Unity serializes without `m_Enabled`.
+const NOT_BEHAVIOURS: &[u32] = &[4, 33, 50, 54, 222, 224];
+
+/// Scene-level settings blocks (fileIDs 1-4) every scene starts with; missing fields are
+/// filled in with Unity's defaults on load.
+const SCENE_SETTINGS: &[(u32, &str, &str)] = &[
+    (29, "OcclusionCullingSettings", "  m_ObjectHideFlags: 0\n  serializedVersion: 2\n  m_OcclusionBakeSettings:\n    smallestOccluder: 5\n    smallestHole: 0.25\n    backfaceThreshold: 100\n  m_SceneGUID: 00000000000000000000000000000000\n  m_OcclusionCullingData: {fileID: 0}\n"),
+    (104, "RenderSettings", "  m_ObjectHideFlags: 0\n  serializedVersion: 9\n  m_Fog: 0\n  m_AmbientMode: 0\n  m_SkyboxMaterial: {fileID: 10304, guid: 0000000000000000f000000000000000, type: 0}\n  m_Sun: {fileID: 0}\n"),
+    (157, "LightmapSettings", "  m_ObjectHideFlags: 0\n  serializedVersion: 12\n  m_GIWorkflowMode: 1\n  m_LightingDataAsset: {fileID: 0}\n  m_LightingSettings: {fileID: 0}\n"),
+    (196, "NavMeshSettings", "  serializedVersion: 2\n  m_ObjectHideFlags: 0\n  m_NavMeshData: {fileID: 0}\n"),
+];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScaffoldSpec {
+    #[serde(default)]
+    pub objects: Vec<ObjectSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectSpec {
+    pub name: String,
+    pub tag: Option<String>,
+    pub layer: Option<u32>,
+    pub active: Option<bool>,
+    pub position: Option<[f64; 3]>,
+    /// Euler angles in degrees
+    pub rotation: Option<[f64; 3]>,
+    pub scale: Option<[f64; 3]>,
+    #[serde(default)]
+    pub components: Vec<ComponentSpec>,
+    #[serde(default)]
+    pub children: Vec<ObjectSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentSpec {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Required for types missing from the built-in table
+    pub class_id: Option<u32>,
+    /// Script GUID for MonoBehaviours (project paths are resolved by the caller)
+    pub script: Option<String>,
+    #[serde(default)]
+    pub properties: Map<String, Json>,
+}
+
+/// A generated file.
+#[derive(Debug)]
+pub struct Scaffold {
+    pub content: String,
+    /// Object path ("Root/Child") → GameObject fileID, in spec order
+    pub file_ids: Vec<(String, String)>,
+    pub block_count: u32,
+}
+
+/// Serialized value tree of one block.
+#[derive(Debug, Clone)]
+enum Node {
+    Scalar(String),
+    Map(Vec<(String, Node)>),
+    Seq(Vec<Node>),
+}
+
+fn set(map: &mut Vec<(String, Node)>, path: &[&str], node: Node) {
+    let Some((first, rest)) = path.split_first() else { return };
+    let position = map.iter().position(|(key, _)| key == first);
+    if rest.is_empty() {
+        match (position, node) {
+            (Some(i), Node::Map(children)) if matches!(map[i].1, Node::Map(_)) => {
+                for (key, child) in children {
+                    if let Node::Map(existing) = &mut map[i].1 {
+                        set(existing, &[key.as_str()], child);
+                    }
+                }
+            }
+            (Some(i), node) => map[i].1 = node,
+            (None, node) => map.push((first.to_string(), node)),
+        }
+        return;
+    }
+    let i = position.unwrap_or_else(|| {
+        map.push((first.to_string(), Node::Map(Vec::new())));
+        map.len() - 1
+    });
+    if !matches!(map[i].1, Node::Map(_)) {
+        map[i].1 = Node::Map(Vec::new());
+    }
+    if let Node::Map(children) = &mut map[i].1 {
+        set(children, rest, node);
+    }
+}
+
+fn render(out: &mut String, map: &[(String, Node)], indent: usize) {
+    for (key, node) in map {
+        let pad = " ".repeat(indent);
+        match node {
+            Node::Scalar(value) if value.is_empty() => { let _ = writeln!(out, "{pad}{key}: "); }
+            Node::Scalar(value) => { let _ = writeln!(out, "{pad}{key}: {value}"); }
+            Node::Map(children) if children.is_empty() => { let _ = writeln!(out, "{pad}{key}: {{}}"); }
+            Node::Map(children) => {
+                let _ = writeln!(out, "{pad}{key}:");
+                render(out, children, indent + 2);
+            }
+            Node::Seq(items) if items.is_empty() => { let _ = writeln!(out, "{pad}{key}: []"); }
+            Node::Seq(items) => {
+                let _ = writeln!(out, "{pad}{key}:");
+                for item in items {
+                    match item {
+                        Node::Map(children) if !children.is_empty() => {
+                            // First key on the dash line, the rest under it
+                            let mut nested = String::new();
+                            render(&mut nested, children, indent + 2);
+                            let _ = write!(out, "{pad}- {}", &nested[indent + 2..]);
+                        }
+                        Node::Scalar(value) => { let _ = writeln!(out, "{pad}- {value}"); }
+                        _ => { let _ = writeln!(out, "{pad}- {{}}"); }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A default-table value (`[]` is an empty sequence) as a node.
+fn default_node(value: &str) -> Node {
+    if value == "[]" { Node::Seq(Vec::new()) } else { Node::Scalar(value.to_string()) }
+}
+
+fn vector(v: [f64; 3]) -> String {
+    format!("{{x: {}, y: {}, z: {}}}", format_float(v[0]), format_float(v[1]), format_float(v[2]))
+}
+
+/// Unity's rotation order: Z, then X, then Y.
+fn euler_to_quaternion(degrees: [f64; 3]) -> Quaternion {
+    let axis = |angle: f64, i: usize| {
+        let (sin, cos) = (angle.to_radians() / 2.0).sin_cos();
+        let mut xyz = [0.0; 3];
+        xyz[i] = sin;
+        Quaternion { x: xyz[0], y: xyz[1], z: xyz[2], w: cos }
+    };
+    let q = multiply(multiply(axis(degrees[1], 1), axis(degrees[0], 0)), axis(degrees[2], 2));
+    // Drop f64 noise (sin(180°) is 1.2e-16, not 0) that single precision would print
+    let snap = |n: f64| if n.abs() < 1e-12 { 0.0 } else { n };
+    Quaternion { x: snap(q.x), y: snap(q.y), z: snap(q.z), w: snap(q.w) }
+}
+
+/// One GameObject with its allocated fileIDs.
+struct Planned<'a> {
+    spec: &'a ObjectSpec,
+    path: String,
+    id: String,
+    transform_id: String,
+    is_rect: bool,
+    /// Non-Transform components with their fileIDs
+    components: Vec<(&'a ComponentSpec, String)>,
+    parent_transform: Option<String>,
+    child_transforms: Vec<String>,
+}
+
+struct Planner<'a> {
+    taken: HashSet<String>,
+    objects: Vec<Planned<'a>>,
+}
+
+impl<'a> Planner<'a> {
+    fn plan(&mut self, spec: &'a ObjectSpec, parent: Option<(&str, usize)>) -> Result<String, String> {
+        if spec.name.is_empty() {
+            return Err("Every object needs a name".to_string());
+        }
+        let path = match parent {
+            Some((parent_path, _)) => format!("{}/{}", parent_path, spec.name),
+            None => spec.name.clone(),
+        };
+        if self.objects.iter().any(|o| o.path == path) {
+            return Err(format!("Duplicate object path \"{}\"", path));
+        }
+        let id = fresh_file_id(&path, 0, &mut self.taken);
+        let transform_id = fresh_file_id(&path, 1, &mut self.taken);
+        let mut components = Vec::new();
+        for (i, component) in spec.components.iter().enumerate() {
+            if matches!(component.type_name.as_str(), "Transform" | "RectTransform") {
+                continue;
+            }
+            components.push((component, fresh_file_id(&path, i + 2, &mut self.taken)));
+        }
+        let position = self.objects.len();
+        self.objects.push(Planned {
+            spec,
+            path: path.clone(),
+            id,
+            is_rect: spec.components.iter().any(|c| c.type_name == "RectTransform"),
+            transform_id: transform_id.clone(),
+            components,
+            parent_transform: parent.map(|(_, i)| self.objects[i].transform_id.clone()),
+            child_transforms: Vec::new(),
+        });
+        for child in &spec.children {
+            let child_transform = self.plan(child, Some((&path, position)))?;
+            self.objects[position].child_transforms.push(child_transform);
+        }
+        Ok(transform_id)
+    }
+
+    /// fileID of the object (or one of its components) a `{"ref": ...}` names.
+    fn resolve_ref(&self, target: &str, component: Option<&str>) -> Result<String, String> {
+        let by_path = self.objects.iter().find(|o| o.path == target);
+        let object = match by_path {
+            Some(object) => object,
+            None => {
+                let mut named = self.objects.iter().filter(|o| o.spec.name == target);
+                match (named.next(), named.next()) {
+                    (Some(object), None) => object,
+                    (None, _) => return Err(format!("Unknown object reference \"{}\"", target)),
+                    _ => return Err(format!("Ambiguous object reference \"{}\"; use its path", target)),
+                }
+            }
+        };
+        match component {
+            None | Some("GameObject") => Ok(object.id.clone()),
+            Some("Transform" | "RectTransform") => Ok(object.transform_id.clone()),
+            Some(wanted) => object.components.iter()
+                .find(|(spec, _)| spec.type_name == wanted || spec.script.as_deref().is_some_and(|s| script_name(s) == wanted))
+                .map(|(_, id)| id.clone())
+                .ok_or_else(|| format!("\"{}\" has no {} component", object.path, wanted)),
+        }
+    }
+
+    fn node(&self, value: &Json) -> Result<Node, String> {
+        Ok(match value {
+            Json::Null => Node::Scalar("{fileID: 0}".to_string()),
+            Json::Bool(_) | Json::Number(_) | Json::String(_) => Node::Scalar(encode_value(value)?),
+            Json::Array(items) => Node::Seq(items.iter().map(|item| self.node(item)).collect::<Result<_, _>>()?),
+            Json::Object(map) => {
+                if let Some(target) = map.get("ref") {
+                    let target = target.as_str().ok_or("\"ref\" must be a string")?;
+                    let id = self.resolve_ref(target, map.get("component").and_then(Json::as_str))?;
+                    return Ok(Node::Scalar(format!("{{fileID: {}}}", id)));
+                }
+                if let Some(guid) = map.get("guid").and_then(Json::as_str) {
+                    let file_id = map.get("fileID").and_then(Json::as_i64).ok_or("Asset references need a \"fileID\"")?;
+                    let kind = map.get("type").and_then(Json::as_i64).unwrap_or(2);
+                    return Ok(Node::Scalar(format!("{{fileID: {}, guid: {}, type: {}}}", file_id, guid, kind)));
+                }
+                if !map.is_empty() && map.values().all(Json::is_number) {
+                    return Ok(Node::Scalar(encode_value(value)?));
+                }
+                let mut children = Vec::new();
+                for (key, v) in map {
+                    let path: Vec<&str> = key.split('.').collect();
+                    set(&mut children, &path, self.node(v)?);
+                }
+                Node::Map(children)
+            }
+        })
+    }
+
+    fn apply_properties(&self, fields: &mut Vec<(String, Node)>, properties: &Map<String, Json>) -> Result<(), String> {
+        for (key, value) in properties {
+            let path: Vec<&str> = key.split('.').collect();
+            set(fields, &path, self.node(value)?);
+        }
+        Ok(())
+    }
+}
+
+fn script_name(script: &str) -> &str {
+    let file = script.rsplit(['/', '\\']).next().unwrap_or(script);
+    file.strip_suffix(".cs").unwrap_or(file)
+}
+
+fn class_id_of(component: &ComponentSpec) -> Result<u32, String> {
+    component.class_id
+        .or_else(|| CLASS_IDS.iter().find(|(name, _)| *name == component.type_name).map(|(_, id)| *id))
+        .ok_or_else(|| format!("Unknown component type \"{}\"; give its class_id", component.type_name))
+}
+
+fn header(out: &mut String, class_id: u32, file_id: &str, type_name: &str) {
+    let _ = writeln!(out, "--- !u!{} &{}\n{}:", class_id, file_id, type_name);
+}
+
+fn ownership(fields: &mut Vec<(String, Node)>, go_id: &str) {
+    for key in ["m_ObjectHideFlags", "m_CorrespondingSourceObject", "m_PrefabInstance", "m_PrefabAsset"] {
+        let value = if key == "m_ObjectHideFlags" { "0" } else { "{fileID: 0}" };
+        fields.push((key.to_string(), Node::Scalar(value.to_string())));
+    }
+    fields.push(("m_GameObject".to_string(), Node::Scalar(format!("{{fileID: {}}}", go_id))));
+}
+
+/// Generate a scene (`is_prefab` false) or prefab from `spec`. Prefabs must have exactly one
+/// root object. Script GUIDs must already be resolved in `ComponentSpec::script`.
+pub fn generate(spec: &ScaffoldSpec, is_prefab: bool) -> Result<Scaffold, String> {
+    if spec.objects.is_empty() {
+        return Err("The spec has no objects".to_string());
+    }
+    if is_prefab && spec.objects.len() != 1 {
+        return Err(format!("A prefab needs exactly one root object, the spec has {}", spec.objects.len()));
+    }
+    let mut planner = Planner { taken: HashSet::new(), objects: Vec::new() };
+    if !is_prefab {
+        planner.taken.extend(["1", "2", "3", "4", SCENE_ROOTS_FILE_ID].map(str::to_string));
+    }
+    let mut roots = Vec::new();
+    for object in &spec.objects {
+        roots.push(planner.plan(object, None)?);
+    }
+
+    let mut out = String::from("%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n");
+    let mut block_count = 0u32;
+    if !is_prefab {
+        for (i, (class_id, type_name, body)) in SCENE_SETTINGS.iter().enumerate() {
+            header(&mut out, *class_id, &(i + 1).to_string(), type_name);
+            out.push_str(body);
+            block_count += 1;
+        }
+    }
+
+    for object in &planner.objects {
+        let spec = object.spec;
+        let mut go = Vec::new();
+        for (key, value) in [("m_ObjectHideFlags", "0"), ("m_CorrespondingSourceObject", "{fileID: 0}"),
+                             ("m_PrefabInstance", "{fileID: 0}"), ("m_PrefabAsset", "{fileID: 0}"), ("serializedVersion", "6")] {
+            go.push((key.to_string(), Node::Scalar(value.to_string())));
+        }
+        let component_refs = std::iter::once(&object.transform_id).chain(object.components.iter().map(|(_, id)| id))
+            .map(|id| Node::Map(vec![("component".to_string(), Node::Scalar(format!("{{fileID: {}}}", id)))]))
+            .collect();
+        go.push(("m_Component".to_string(), Node::Seq(component_refs)));
+        go.push(("m_Layer".to_string(), Node::Scalar(spec.layer.unwrap_or(0).to_string())));
+        go.push(("m_Name".to_string(), Node::Scalar(encode_string(&spec.name))));
+        go.push(("m_TagString".to_string(), Node::Scalar(encode_string(spec.tag.as_deref().unwrap_or("Untagged")))));
+        go.push(("m_Icon".to_string(), Node::Scalar("{fileID: 0}".to_string())));
+        go.push(("m_NavMeshLayer".to_string(), Node::Scalar("0".to_string())));
+        go.push(("m_StaticEditorFlags".to_string(), Node::Scalar("0".to_string())));
+        go.push(("m_IsActive".to_string(), Node::Scalar(if spec.active.unwrap_or(true) { "1" } else { "0" }.to_string())));
+        header(&mut out, GAMEOBJECT_CLASS_ID, &object.id, "GameObject");
+        render(&mut out, &go, 2);
+
+        // Transform (or RectTransform) with the spec's TRS and the hierarchy links
+        let mut transform = Vec::new();
+        ownership(&mut transform, &object.id);
+        let rotation = spec.rotation.map(euler_to_quaternion).unwrap_or(Quaternion { x: 0.0, y: 0.0, z: 0.0, w: 1.0 });
+        let trs = [
+            ("m_LocalRotation", format!("{{x: {}, y: {}, z: {}, w: {}}}", format_float(rotation.x), format_float(rotation.y), format_float(rotation.z), format_float(rotation.w))),
+            ("m_LocalPosition", vector(spec.position.unwrap_or([0.0; 3]))),
+            ("m_LocalScale", vector(spec.scale.unwrap_or([1.0; 3]))),
+            ("m_ConstrainProportionsScale", "0".to_string()),
+        ];
+        for (key, value) in trs {
+            transform.push((key.to_string(), Node::Scalar(value)));
+        }
+        let children = object.child_transforms.iter().map(|id| Node::Scalar(format!("{{fileID: {}}}", id))).collect();
+        transform.push(("m_Children".to_string(), Node::Seq(children)));
+        transform.push(("m_Father".to_string(), Node::Scalar(format!("{{fileID: {}}}", object.parent_transform.as_deref().unwrap_or("0")))));
+        transform.push(("m_LocalEulerAnglesHint".to_string(), Node::Scalar(vector(spec.rotation.unwrap_or([0.0; 3])))));
+        if object.is_rect {
+            for (key, value) in [("m_AnchorMin", "{x: 0.5, y: 0.5}"), ("m_AnchorMax", "{x: 0.5, y: 0.5}"),
+                                 ("m_AnchoredPosition", "{x: 0, y: 0}"), ("m_SizeDelta", "{x: 100, y: 100}"), ("m_Pivot", "{x: 0.5, y: 0.5}")] {
+                transform.push((key.to_string(), Node::Scalar(value.to_string())));
+            }
+        }
+        let transform_type = if object.is_rect { "RectTransform" } else { "Transform" };
+        if let Some(component) = spec.components.iter().find(|c| c.type_name == transform_type) {
+            planner.apply_properties(&mut transform, &component.properties)?;
+        }
+        let class_id = if object.is_rect { RECT_TRANSFORM_CLASS_ID } else { TRANSFORM_CLASS_ID };
+        header(&mut out, class_id, &object.transform_id, transform_type);
+        render(&mut out, &transform, 2);
+        block_count += 2;
+
+        for (component, id) in &object.components {
+            let class_id = class_id_of(component)?;
+            let mut fields = Vec::new();
+            ownership(&mut fields, &object.id);
+            if !NOT_BEHAVIOURS.contains(&class_id) {
+                fields.push(("m_Enabled".to_string(), Node::Scalar("1".to_string())));
+            }
+            if class_id == MONOBEHAVIOUR_CLASS_ID {
+                let guid = component.script.as_deref()
+                    .ok_or_else(|| format!("MonoBehaviour on \"{}\" needs a script", object.path))?;
+                for (key, value) in [("m_EditorHideFlags", "0".to_string()),
+                                     ("m_Script", format!("{{fileID: 11500000, guid: {}, type: 3}}", guid)),
+                                     ("m_Name", String::new()), ("m_EditorClassIdentifier", String::new())] {
+                    fields.push((key.to_string(), Node::Scalar(value)));
+                }
+            }
+            for (path, value) in defaults::builtin_defaults(class_id).unwrap_or_default() {
+                if path == "m_Enabled" || path == "m_ObjectHideFlags" {
+                    continue;
+                }
+                let segments: Vec<&str> = path.split('.').collect();
+                set(&mut fields, &segments, default_node(value));
+            }
+            planner.apply_properties(&mut fields, &component.properties)?;
+            header(&mut out, class_id, id, &component.type_name);
+            render(&mut out, &fields, 2);
+            block_count += 1;
+        }
+    }
+
+    if !is_prefab {
+        header(&mut out, SCENE_ROOTS_CLASS_ID, SCENE_ROOTS_FILE_ID, "SceneRoots");
+        let roots = roots.iter().map(|id| Node::Scalar(format!("{{fileID: {}}}", id))).collect();
+        render(&mut out, &[("m_ObjectHideFlags".to_string(), Node::Scalar("0".to_string())), ("m_Roots".to_string(), Node::Seq(roots))], 2);
+        block_count += 1;
+    }
+
+    Ok(Scaffold {
+        content: out,
+        file_ids: planner.objects.iter().map(|o| (o.path.clone(), o.id.clone())).collect(),
+        block_count,
+    })
+}
+
+/// Script GUIDs for `script` entries given as project paths, resolved through `path_to_guid`.
+pub fn resolve_scripts(spec: &mut ScaffoldSpec, path_to_guid: &HashMap<String, String>) -> Result<(), String> {
+    fn visit(object: &mut ObjectSpec, path_to_guid: &HashMap<String, String>) -> Result<(), String> {
+        for component in &mut object.components {
+            let Some(script) = component.script.as_mut() else { continue };
+            if script.len() == 32 && script.bytes().all(|b| b.is_ascii_hexdigit()) {
+                continue;
+            }
+            let normalized = script.replace('\\', "/");
+            *script = path_to_guid.get(&normalized).cloned().ok_or_else(|| format!("No script at \"{}\" in the project", normalized))?;
+        }
+        object.children.iter_mut().try_for_each(|child| visit(child, path_to_guid))
+    }
+    spec.objects.iter_mut().try_for_each(|object| visit(object, path_to_guid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::parser::{BlockIndex, UnityYamlParser};
+    use crate::scanner::rendering::field;
+
+    fn spec(json: Json) -> ScaffoldSpec {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_generate_scene() {
+        let guid = "a".repeat(32);
+        let spec = spec(serde_json::json!({ "objects": [
+            { "name": "Player", "tag": "Player", "position": [0, 1, 0], "rotation": [0, 90, 0],
+              "components": [
+                { "type": "Rigidbody", "properties": { "m_Mass": 2.5, "m_UseGravity": false } },
+                { "type": "MonoBehaviour", "script": guid, "properties": {
+                    "speed": 5, "label": "Hero: one", "note": "say \"hi\"\n", "ratio": 0.1, "cam": { "ref": "Camera", "component": "Camera" },
+                    "waypoints": [{ "x": 1, "y": 0, "z": 0 }], "tint": { "r": 1, "g": 0.5, "b": 0, "a": 1 } } } ],
+              "children": [ { "name": "Camera", "components": [ { "type": "Camera", "properties": { "field of view": 40 } } ] } ] },
+            { "name": "Sun", "active": false, "components": [ { "type": "Light", "properties": { "m_Shadows.m_Type": 2 } } ] }
+        ]}));
+        let scaffold = generate(&spec, false).unwrap();
+        let content = &scaffold.content;
+        assert!(content.starts_with("%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!29 &1\n"));
+        assert_eq!(scaffold.block_count as usize, UnityYamlParser::parse_all_blocks(content).len());
+
+        let ids: HashMap<&str, &str> = scaffold.file_ids.iter().map(|(p, id)| (p.as_str(), id.as_str())).collect();
+        let gameobjects = UnityYamlParser::extract_gameobjects(content);
+        let names: Vec<&str> = gameobjects.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["Player", "Camera", "Sun"]);
+        assert!(!gameobjects[2].active);
+
+        let index = BlockIndex::new(content);
+        let player = index.get(ids["Player"]).unwrap().1;
+        assert_eq!(field(player, "m_TagString"), Some("Player"));
+        let blocks = UnityYamlParser::parse_all_blocks(content);
+        let body = |type_name: &str| blocks.iter().find(|(_, _, b)| b.starts_with(&format!("{}:\n", type_name))).unwrap().2.as_str();
+        assert_eq!(field(body("Transform"), "m_LocalRotation"), Some("{x: 0, y: 0.70710677, z: 0, w: 0.70710677}"));
+        assert_eq!(field(body("Rigidbody"), "m_Mass"), Some("2.5"));
+        assert_eq!(field(body("Rigidbody"), "m_UseGravity"), Some("0"));
+        assert_eq!(field(body("Rigidbody"), "m_Enabled"), None);
+        assert_eq!(field(body("Camera"), "field of view"), Some("40"));
+        assert_eq!(field(body("Camera"), "near clip plane"), Some("0.3"));
+        assert!(body("Light").contains("  m_Shadows:\n    m_Type: 2\n    m_Resolution: -1\n"));
+
+        let script = body("MonoBehaviour");
+        let camera_id = blocks.iter().find(|(_, _, b)| b.starts_with("Camera:\n")).unwrap().1.clone();
+        assert_eq!(field(script, "cam"), Some(format!("{{fileID: {}}}", camera_id).as_str()));
+        assert_eq!(field(script, "label"), Some("'Hero: one'"));
+        assert_eq!(field(script, "note"), Some(r#""say \"hi\"\n""#));
+        assert_eq!(field(script, "ratio"), Some("0.1"));
+        assert!(script.contains("  waypoints:\n  - {x: 1, y: 0, z: 0}\n"));
+        assert_eq!(field(script, "tint"), Some("{r: 1, g: 0.5, b: 0, a: 1}"));
+
+        let roots = body("SceneRoots");
+        let player_transform = &blocks.iter().find(|(_, _, b)| b.starts_with("Transform:\n")).unwrap().1;
+        assert!(roots.contains(&format!("- {{fileID: {}}}", player_transform)));
+        assert_eq!(roots.matches("- {fileID:").count(), 2);
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        let two_roots = spec(serde_json::json!({ "objects": [{ "name": "A" }, { "name": "B" }] }));
+        assert!(generate(&two_roots, true).unwrap_err().contains("exactly one root"));
+        let bad_ref = spec(serde_json::json!({ "objects": [{ "name": "A", "components": [
+            { "type": "MonoBehaviour", "script": "b".repeat(32), "properties": { "t": { "ref": "Missing" } } }] }] }));
+        assert!(generate(&bad_ref, false).unwrap_err().contains("Unknown object reference \"Missing\""));
+        let unknown = spec(serde_json::json!({ "objects": [{ "name": "A", "components": [{ "type": "Volume" }] }] }));
+        assert!(generate(&unknown, false).unwrap_err().contains("give its class_id"));
+        assert!(serde_json::from_value::<ScaffoldSpec>(serde_json::json!({ "objects": [{ "name": "A", "colour": 1 }] })).is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::common::FindResult;
+use super::config::ComponentConfig;
+use super::gameobject::{extract_tag, hierarchy_breadcrumbs};
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::prefab;
+
+/// Sidecar index directory, relative to the project root.
+pub const SCENE_INDEX_DIR: &str = ".unity-agentic/scene-index";
+
+/// Bumped whenever the sidecar layout changes; older files are rebuilt.
+const INDEX_VERSION: u32 = 2;
+
+/// Persisted name/tag/component-type → fileID lookup for one scene or prefab.
+/// Valid only while the source file's mtime and size match the recorded stamp.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneIndex {
+    pub version: u32,
+    /// Source path relative to the project root
+    pub file: String,
+    pub mtime_ms: u64,
+    pub size_bytes: u64,
+    /// GameObjects then PrefabInstances, in file order
+    pub objects: Vec<FindResult>,
+    /// Object name → positions in `objects`
+    pub by_name: HashMap<String, Vec<usize>>,
+    /// Tag → GameObject fileIDs
+    pub by_tag: BTreeMap<String, Vec<String>>,
+    /// Component type (and script name for MonoBehaviours) → GameObject fileIDs
+    pub by_component: BTreeMap<String, Vec<String>>,
+}
+
+/// Modification time (ms since the epoch) and size of a file.
+pub fn source_stamp(path: &Path) -> Option<(u64, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64;
+    Some((mtime, meta.len()))
+}
+
+/// Sidecar location for a scene: `<root>/.unity-agentic/scene-index/<hash of relative path>.json`.
+pub fn index_path(project_root: &Path, relative_file: &str) -> PathBuf {
+    let hash = crate::walker::fnv1a64(crate::walker::FNV_OFFSET, relative_file.as_bytes());
+    project_root.join(SCENE_INDEX_DIR).join(format!("{:016x}.json", hash))
+}
+
+fn push_unique(map: &mut BTreeMap<String, Vec<String>>, key: &str, file_id: &str) {
+    let ids = map.entry(key.to_string()).or_default();
+    if !ids.iter().any(|id| id == file_id) {
+        ids.push(file_id.to_string());
+    }
+}
+
+impl SceneIndex {
+    /// Index every GameObject and PrefabInstance of Unity YAML content.
+    pub fn build(
+        file: &str,
+        content: &str,
+        stamp: (u64, u64),
+        guid_cache: &HashMap<String, String>,
+        config: &ComponentConfig,
+    ) -> Self {
+        let index = BlockIndex::new(content);
+        let gameobjects = UnityYamlParser::extract_gameobjects_with_config(content, config);
+        let script_re = regex::Regex::new(&config.script_ref_pattern()).expect("Invalid regex");
+
+        let mut objects: Vec<FindResult> = gameobjects.iter().map(|go| FindResult::from_game_object(go, None)).collect();
+        let mut by_tag = BTreeMap::new();
+        let mut by_component = BTreeMap::new();
+        for go in &gameobjects {
+            let Some(go_block) = index.get_by_class_and_id(config.gameobject_class_id, &go.file_id) else { continue };
+            push_unique(&mut by_tag, &extract_tag(go_block), &go.file_id);
+            for comp_id in UnityYamlParser::parse_component_refs(go_block) {
+                let Some((class_id, body)) = index.get(&comp_id) else { continue };
+                let type_name = body.lines().next().unwrap_or("").trim_end_matches(':');
+                push_unique(&mut by_component, type_name, &go.file_id);
+                if config.is_script_container(class_id) {
+                    let script = script_re.captures(body)
+                        .and_then(|c| guid_cache.get(c.get(1)?.as_str()))
+                        .and_then(|path| Path::new(path).file_stem())
+                        .map(|stem| stem.to_string_lossy().to_string());
+                    if let Some(script) = script {
+                        push_unique(&mut by_component, &script, &go.file_id);
+                    }
+                }
+            }
+        }
+        objects.extend(prefab::extract_prefab_instances(content, guid_cache).iter().map(|pi| FindResult::from_prefab_instance(pi, None)));
+        let names: HashMap<String, String> = objects.iter().map(|o| (o.file_id.clone(), o.name.clone())).collect();
+        let breadcrumbs = hierarchy_breadcrumbs(&UnityYamlParser::parse_all_blocks(content), &names, config);
+        for object in &mut objects {
+            object.set_breadcrumb(&breadcrumbs);
+        }
+
+        let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, object) in objects.iter().enumerate() {
+            by_name.entry(object.name.clone()).or_default().push(i);
+        }
+
+        SceneIndex {
+            version: INDEX_VERSION,
+            file: file.to_string(),
+            mtime_ms: stamp.0,
+            size_bytes: stamp.1,
+            objects,
+            by_name,
+            by_tag,
+            by_component,
+        }
+    }
+
+    /// Load a sidecar if it exists and still matches the source file's stamp.
+    pub fn load_fresh(path: &Path, file: &str, stamp: (u64, u64)) -> Option<Self> {
+        let text = crate::safety::read_to_string(path).ok()?;
+        let index: SceneIndex = serde_json::from_str(&text).ok()?;
+        (index.version == INDEX_VERSION && index.file == file && (index.mtime_ms, index.size_bytes) == stamp)
+            .then_some(index)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            crate::safety::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let text = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        crate::common::atomic::write_atomic(path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Objects with exactly this name (hash lookup) or matching a glob regex (scan of the index).
+    pub fn find_by_name(&self, pattern: &str, glob: Option<&regex::Regex>) -> Vec<FindResult> {
+        match glob {
+            Some(re) => self.objects.iter().filter(|o| re.is_match(&o.name)).cloned().collect(),
+            None => self.by_name.get(pattern)
+                .map(|positions| positions.iter().map(|&i| self.objects[i].clone()).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Objects matching every given criterion (name, tag, component type or script name).
+    pub fn query(&self, name: Option<&str>, tag: Option<&str>, component_type: Option<&str>) -> Vec<FindResult> {
+        let ids_for = |map: &BTreeMap<String, Vec<String>>, key: Option<&str>| {
+            key.map(|k| map.get(k).cloned().unwrap_or_default())
+        };
+        let tag_ids = ids_for(&self.by_tag, tag);
+        let component_ids = ids_for(&self.by_component, component_type);
+        let candidates: Vec<&FindResult> = match name {
+            Some(name) => self.by_name.get(name).map(|p| p.iter().map(|&i| &self.objects[i]).collect()).unwrap_or_default(),
+            None => self.objects.iter().collect(),
+        };
+        candidates.into_iter()
+            .filter(|o| tag_ids.as_ref().is_none_or(|ids| ids.contains(&o.file_id)))
+            .filter(|o| component_ids.as_ref().is_none_or(|ids| ids.contains(&o.file_id)))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1\n\
+--- !u!1 &1\nGameObject:\n  m_Component:\n  - component: {fileID: 2}\n  - component: {fileID: 3}\n  m_Name: Player\n  m_TagString: Player\n  m_IsActive: 1\n\
+--- !u!4 &2\nTransform:\n  m_GameObject: {fileID: 1}\n  m_Children: []\n  m_Father: {fileID: 0}\n\
+--- !u!114 &3\nMonoBehaviour:\n  m_GameObject: {fileID: 1}\n  m_Script: {fileID: 11500000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}\n\
+--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {fileID: 11}\n  m_Name: Enemy\n  m_TagString: Untagged\n  m_IsActive: 1\n\
+--- !u!4 &11\nTransform:\n  m_GameObject: {fileID: 10}\n  m_Children: []\n  m_Father: {fileID: 0}\n\
+--- !u!1 &20\nGameObject:\n  m_Component:\n  - component: {fileID: 21}\n  m_Name: Enemy\n  m_TagString: Untagged\n  m_IsActive: 0\n\
+--- !u!4 &21\nTransform:\n  m_GameObject: {fileID: 20}\n  m_Children: []\n  m_Father: {fileID: 0}\n";
+
+    fn build() -> SceneIndex {
+        let mut cache = HashMap::new();
+        cache.insert("a".repeat(32), "Assets/Scripts/PlayerController.cs".to_string());
+        SceneIndex::build("Assets/Main.unity", SCENE, (1, 2), &cache, &ComponentConfig::default())
+    }
+
+    #[test]
+    fn test_build_and_query() {
+        let index = build();
+        let enemies: Vec<String> = index.find_by_name("Enemy", None).into_iter().map(|o| o.file_id).collect();
+        assert_eq!(enemies, vec!["10", "20"]);
+        assert_eq!(index.objects[1].path.as_deref(), Some("Enemy"));
+        assert_eq!(index.objects[1].parent_name, None);
+        let glob = regex::Regex::new("^P").unwrap();
+        assert_eq!(index.find_by_name("P*", Some(&glob)).len(), 1);
+        assert_eq!(index.query(None, Some("Player"), None)[0].file_id, "1");
+        assert_eq!(index.query(None, None, Some("PlayerController"))[0].name, "Player");
+        assert_eq!(index.query(Some("Enemy"), None, Some("MonoBehaviour")).len(), 0);
+        assert_eq!(index.by_component["Transform"].len(), 3);
+    }
+
+    #[test]
+    fn test_save_and_load_fresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = index_path(dir.path(), "Assets/Main.unity");
+        // Stable across Rust releases, so sidecars survive toolchain upgrades
+        assert_eq!(path, dir.path().join(SCENE_INDEX_DIR).join("8c04b5e56264f187.json"));
+        build().save(&path).unwrap();
+        assert!(SceneIndex::load_fresh(&path, "Assets/Main.unity", (1, 2)).is_some());
+        assert!(SceneIndex::load_fresh(&path, "Assets/Main.unity", (5, 2)).is_none(), "mtime change invalidates");
+        assert!(SceneIndex::load_fresh(&path, "Assets/Other.unity", (1, 2)).is_none());
+    }
+}
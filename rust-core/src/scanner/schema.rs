@@ -0,0 +1,302 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::common::PropertySchema;
+
+/// Serialization bookkeeping present on every component; not part of its schema.
+const BOOKKEEPING_KEYS: &[&str] = &[
+    "m_ObjectHideFlags", "m_CorrespondingSourceObject", "m_PrefabInstance", "m_PrefabAsset",
+    "m_PrefabInternal", "m_PrefabParentObject", "m_GameObject", "m_Script", "m_EditorHideFlags",
+    "m_EditorClassIdentifier",
+];
+
+const MAX_EXAMPLES: usize = 3;
+const MAX_EXAMPLE_CHARS: usize = 80;
+
+/// One serialized field of a component block.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Scalar(String),
+    /// Block sequence with its item count (items are reported under `path[]`)
+    Sequence(usize),
+    /// Block mapping (children are reported under `path.child`)
+    Map,
+}
+
+fn unbalanced(value: &str) -> bool {
+    value.matches('{').count() > value.matches('}').count()
+        || value.matches('[').count() > value.matches(']').count()
+}
+
+/// Flatten a component block (header and type line included or not) into dotted property paths.
+/// Sequence items use `name[]`; flow values (`{x: 0, y: 1}`, references) stay scalar.
+pub fn flatten_block(text: &str) -> Vec<(String, FieldValue)> {
+    let lines: Vec<&str> = text.lines()
+        .filter(|l| !l.starts_with("--- !u!") && !l.trim().is_empty())
+        .filter(|l| l.starts_with(' '))
+        .collect();
+    let mut fields: Vec<(String, FieldValue)> = Vec::new();
+    // (indent, path, position in `fields` of a container still waiting for children)
+    let mut stack: Vec<(usize, String, Option<usize>)> = Vec::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+        let trimmed = line.trim_start();
+        let mut indent = line.len() - trimmed.len();
+        let mut content = trimmed;
+
+        if let Some(item) = trimmed.strip_prefix("- ").or_else(|| (trimmed == "-").then_some("")) {
+            // Sequence items sit at the indent of their key
+            while stack.last().is_some_and(|(ind, _, _)| *ind > indent) {
+                stack.pop();
+            }
+            let Some((_, key_path, pending)) = stack.last_mut().filter(|(ind, _, _)| *ind == indent) else { continue };
+            if let Some(pos) = *pending {
+                fields[pos].1 = FieldValue::Sequence(0);
+                *pending = None;
+            }
+            let item_path = format!("{}[]", key_path);
+            let parent_pos = fields.iter().rposition(|(p, _)| p == key_path.as_str());
+            if let Some(FieldValue::Sequence(count)) = parent_pos.map(|p| &mut fields[p].1) {
+                *count += 1;
+            }
+            let is_map_item = item.split_once(": ").is_some_and(|(k, _)| !k.starts_with('{') && !k.contains(' '))
+                || (item.ends_with(':') && !item.starts_with('{'));
+            if !is_map_item {
+                let mut value = item.to_string();
+                while unbalanced(&value) && i < lines.len() {
+                    value.push(' ');
+                    value.push_str(lines[i].trim());
+                    i += 1;
+                }
+                fields.push((item_path, FieldValue::Scalar(value)));
+                continue;
+            }
+            // A map item: its keys are children of `name[]` at indent + 2
+            stack.push((indent + 1, item_path, None));
+            indent += 2;
+            content = item;
+        }
+
+        let Some((key, value)) = content.split_once(':') else { continue };
+        while stack.last().is_some_and(|(ind, _, _)| *ind >= indent) {
+            if let Some((_, _, Some(pos))) = stack.pop() {
+                fields[pos].1 = FieldValue::Scalar(String::new());
+            }
+        }
+        if let Some((_, _, pending)) = stack.last_mut() {
+            if let Some(pos) = pending.take() {
+                fields[pos].1 = FieldValue::Map;
+            }
+        }
+        let path = match stack.last() {
+            Some((_, parent, _)) => format!("{}.{}", parent, key.trim()),
+            None => key.trim().to_string(),
+        };
+        let mut value = value.trim().to_string();
+        if value.is_empty() {
+            // Container (or empty scalar); decided by the next line
+            fields.push((path.clone(), FieldValue::Map));
+            stack.push((indent, path, Some(fields.len() - 1)));
+            continue;
+        }
+        while unbalanced(&value) && i < lines.len() {
+            value.push(' ');
+            value.push_str(lines[i].trim());
+            i += 1;
+        }
+        fields.push((path, FieldValue::Scalar(value)));
+    }
+    for (_, _, pending) in stack {
+        if let Some(pos) = pending {
+            fields[pos].1 = FieldValue::Scalar(String::new());
+        }
+    }
+    fields
+}
+
+fn flow_keys(value: &str) -> Vec<&str> {
+    value.trim_start_matches('{').trim_end_matches('}')
+        .split(',')
+        .filter_map(|part| part.split_once(':').map(|(k, _)| k.trim()))
+        .collect()
+}
+
+/// Value type of a serialized scalar: "int", "float", "string", "empty", "reference", "vector2",
+/// "vector3", "vector4", "color", "map" or "array".
+pub fn classify(value: &str) -> &'static str {
+    let v = value.trim();
+    if v.is_empty() {
+        return "empty";
+    }
+    if v.starts_with("{fileID:") {
+        return "reference";
+    }
+    if v.starts_with('{') {
+        return match flow_keys(v).as_slice() {
+            ["x", "y"] => "vector2",
+            ["x", "y", "z"] => "vector3",
+            ["x", "y", "z", "w"] => "vector4",
+            ["r", "g", "b", "a"] => "color",
+            _ => "map",
+        };
+    }
+    if v.starts_with('[') {
+        return "array";
+    }
+    if v.parse::<i64>().is_ok() {
+        return "int";
+    }
+    let numeric_chars = v.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'));
+    if numeric_chars && v.parse::<f64>().is_ok() {
+        return "float";
+    }
+    "string"
+}
+
+fn is_null(value: &str) -> bool {
+    let v = value.trim();
+    v.is_empty() || v.starts_with("{fileID: 0}") || v == "{fileID: 0, guid: 00000000000000000000000000000000, type: 0}"
+}
+
+#[derive(Default)]
+struct PropertyStats {
+    occurrences: u32,
+    types: BTreeMap<&'static str, u32>,
+    null_count: u32,
+    min: Option<f64>,
+    max: Option<f64>,
+    examples: Vec<String>,
+}
+
+impl PropertyStats {
+    fn observe_number(&mut self, n: f64) {
+        self.min = Some(self.min.map_or(n, |m| m.min(n)));
+        self.max = Some(self.max.map_or(n, |m| m.max(n)));
+    }
+}
+
+/// Accumulates component samples into a per-property schema.
+#[derive(Default)]
+pub struct SchemaBuilder {
+    pub samples: u32,
+    order: Vec<String>,
+    stats: HashMap<String, PropertyStats>,
+}
+
+impl SchemaBuilder {
+    pub fn add_sample(&mut self, block_text: &str) {
+        self.samples += 1;
+        let mut seen: HashSet<String> = HashSet::new();
+        for (path, value) in flatten_block(block_text) {
+            let root = path.split(['.', '[']).next().unwrap_or(&path);
+            if BOOKKEEPING_KEYS.contains(&root) {
+                continue;
+            }
+            if !self.stats.contains_key(&path) {
+                self.order.push(path.clone());
+            }
+            let stats = self.stats.entry(path.clone()).or_default();
+            if seen.insert(path) {
+                stats.occurrences += 1;
+            }
+            match value {
+                FieldValue::Scalar(v) => {
+                    let kind = classify(&v);
+                    *stats.types.entry(kind).or_insert(0) += 1;
+                    if is_null(&v) {
+                        stats.null_count += 1;
+                    }
+                    if let ("int" | "float", Ok(n)) = (kind, v.trim().parse::<f64>()) {
+                        stats.observe_number(n);
+                    }
+                    let example: String = v.chars().take(MAX_EXAMPLE_CHARS).collect();
+                    if stats.examples.len() < MAX_EXAMPLES && !stats.examples.contains(&example) {
+                        stats.examples.push(example);
+                    }
+                }
+                FieldValue::Sequence(len) => {
+                    *stats.types.entry("array").or_insert(0) += 1;
+                    stats.observe_number(len as f64);
+                }
+                FieldValue::Map => *stats.types.entry("map").or_insert(0) += 1,
+            }
+        }
+    }
+
+    /// Properties in first-seen order. Array lengths are reported as the min/max range.
+    pub fn finish(self) -> Vec<PropertySchema> {
+        let samples = self.samples;
+        let mut stats = self.stats;
+        self.order.into_iter().filter_map(|path| {
+            let s = stats.remove(&path)?;
+            let mut types: Vec<(&str, u32)> = s.types.into_iter().collect();
+            types.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+            // Items of a sequence are optional relative to the component, not to the array
+            let optional = s.occurrences < samples && !path.contains("[]");
+            Some(PropertySchema {
+                path,
+                types: types.into_iter().map(|(t, _)| t.to_string()).collect(),
+                occurrences: s.occurrences,
+                optional,
+                nullable: s.null_count > 0,
+                min: s.min,
+                max: s.max,
+                examples: s.examples,
+            })
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK: &str = "--- !u!114 &5\nMonoBehaviour:\n  m_ObjectHideFlags: 0\n  m_GameObject: {fileID: 1}\n  m_Enabled: 1\n  speed: 3.5\n  target: {fileID: 0}\n  tint: {r: 1, g: 0.5, b: 0, a: 1}\n  settings:\n    mode: 2\n    label: Fast\n  waypoints:\n  - {x: 0, y: 1, z: 2}\n  - {x: 3, y: 4, z: 5}\n  entries:\n  - first: 1\n    second: {fileID: 42}\n  empty: \n  curve:\n    m_Curve: []\n";
+
+    #[test]
+    fn test_flatten_block() {
+        let fields = flatten_block(BLOCK);
+        let get = |p: &str| fields.iter().find(|(path, _)| path == p).map(|(_, v)| v.clone());
+        assert_eq!(get("speed"), Some(FieldValue::Scalar("3.5".into())));
+        assert_eq!(get("settings"), Some(FieldValue::Map));
+        assert_eq!(get("settings.label"), Some(FieldValue::Scalar("Fast".into())));
+        assert_eq!(get("waypoints"), Some(FieldValue::Sequence(2)));
+        assert_eq!(fields.iter().filter(|(p, _)| p == "waypoints[]").count(), 2);
+        assert_eq!(get("entries[].second"), Some(FieldValue::Scalar("{fileID: 42}".into())));
+        assert_eq!(get("empty"), Some(FieldValue::Scalar(String::new())));
+        assert_eq!(get("curve.m_Curve"), Some(FieldValue::Scalar("[]".into())));
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("3"), "int");
+        assert_eq!(classify("-0.25"), "float");
+        assert_eq!(classify("1e-05"), "float");
+        assert_eq!(classify("Infinity"), "string");
+        assert_eq!(classify("{fileID: 0}"), "reference");
+        assert_eq!(classify("{x: 0, y: 1, z: 2}"), "vector3");
+        assert_eq!(classify("{r: 1, g: 1, b: 1, a: 1}"), "color");
+        assert_eq!(classify(""), "empty");
+    }
+
+    #[test]
+    fn test_schema_builder() {
+        let mut builder = SchemaBuilder::default();
+        builder.add_sample(BLOCK);
+        builder.add_sample("--- !u!114 &6\nMonoBehaviour:\n  m_Enabled: 1\n  speed: 10\n  target: {fileID: 7}\n  waypoints: []\n");
+        assert_eq!(builder.samples, 2);
+        let schema = builder.finish();
+        assert!(schema.iter().all(|p| p.path != "m_GameObject"));
+        let speed = schema.iter().find(|p| p.path == "speed").unwrap();
+        assert_eq!((speed.min, speed.max, speed.optional), (Some(3.5), Some(10.0), false));
+        assert_eq!(speed.types, vec!["float", "int"]);
+        let target = schema.iter().find(|p| p.path == "target").unwrap();
+        assert!(target.nullable);
+        let tint = schema.iter().find(|p| p.path == "tint").unwrap();
+        assert!(tint.optional);
+        let waypoints = schema.iter().find(|p| p.path == "waypoints").unwrap();
+        assert_eq!(waypoints.types, vec!["array"]);
+    }
+}
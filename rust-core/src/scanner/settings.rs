@@ -0,0 +1,423 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::common::{CollisionMatrix, InputAxis, LayerCollisionRow, LayerPair};
+
+/// Number of physics layers Unity supports.
+const LAYER_COUNT: usize = 32;
+
+static COLLISION_MATRIX_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"m_LayerCollisionMatrix:[ \t]*([0-9a-fA-F]*)").unwrap()
+});
+static MONO_MANAGER_ORDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"first:[ \t]*\{[^}\n]*guid:[ \t]*([a-f0-9]{32})[^}\n]*\}[ \t]*\n[ \t]*second:[ \t]*(-?\d+)").unwrap()
+});
+static META_EXECUTION_ORDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]+executionOrder:[ \t]*(-?\d+)").unwrap()
+});
+static ITEM_FIELD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[ \t]*(?:- )?([A-Za-z_][A-Za-z0-9_]*):[ \t]*([^\n]*)$").unwrap()
+});
+
+/// Parse TagManager.asset `layers:` into 32 slots (None for unnamed layers).
+pub fn parse_layer_names(tag_manager: &str) -> Vec<Option<String>> {
+    let mut names: Vec<Option<String>> = Vec::with_capacity(LAYER_COUNT);
+    let mut in_layers = false;
+
+    for line in tag_manager.lines() {
+        if !in_layers {
+            in_layers = line.trim_end() == "  layers:";
+            continue;
+        }
+        let trimmed = line.trim_start();
+        match trimmed.strip_prefix('-') {
+            Some(value) => {
+                let value = value.trim();
+                names.push(if value.is_empty() { None } else { Some(value.to_string()) });
+            }
+            None => break,
+        }
+    }
+
+    names.resize(LAYER_COUNT, None);
+    names
+}
+
+/// Tags every project has, whether or not TagManager.asset lists them.
+const BUILTIN_TAGS: &[&str] = &["Untagged", "Respawn", "Finish", "EditorOnly", "MainCamera", "Player", "GameController"];
+
+/// Parse TagManager.asset `tags:` (the project's custom tags).
+pub fn parse_tags(tag_manager: &str) -> Vec<String> {
+    tag_manager.lines()
+        .skip_while(|line| line.trim_end() != "  tags:")
+        .skip(1)
+        .map_while(|line| line.strip_prefix("  - ").or_else(|| (line.trim_end() == "  -").then_some("")))
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Tags and layer names of a project, for validating GameObject metadata.
+#[derive(Debug, Clone, Default)]
+pub struct TagManagerInfo {
+    pub tags: Vec<String>,
+    pub layer_names: Vec<Option<String>>,
+}
+
+impl TagManagerInfo {
+    pub fn parse(tag_manager: &str) -> Self {
+        TagManagerInfo { tags: parse_tags(tag_manager), layer_names: parse_layer_names(tag_manager) }
+    }
+
+    /// Problems with a GameObject's tag and layer: tags TagManager does not define, layers
+    /// outside 0-31 or without a name (typical after importing assets from another project).
+    pub fn warnings(&self, tag: &str, layer: u32) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !BUILTIN_TAGS.contains(&tag) && !self.tags.iter().any(|t| t == tag) {
+            warnings.push(format!("Tag \"{}\" is not defined in TagManager", tag));
+        }
+        match self.layer_names.get(layer as usize) {
+            None => warnings.push(format!("Layer {} is out of range (0-{})", layer, LAYER_COUNT - 1)),
+            Some(None) => warnings.push(format!("Layer {} has no name in TagManager", layer)),
+            Some(Some(_)) => {}
+        }
+        warnings
+    }
+}
+
+/// Decode `m_LayerCollisionMatrix` into one 32-bit mask per layer.
+/// Unity serializes the array as raw little-endian bytes in hex.
+pub fn parse_collision_masks(content: &str) -> Option<Vec<u32>> {
+    let hex = COLLISION_MATRIX_RE.captures(content)?.get(1)?.as_str();
+    if hex.len() < LAYER_COUNT * 8 {
+        return None;
+    }
+
+    (0..LAYER_COUNT)
+        .map(|layer| {
+            let chunk = &hex[layer * 8..layer * 8 + 8];
+            let mut bytes = [0u8; 4];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = u8::from_str_radix(&chunk[i * 2..i * 2 + 2], 16).ok()?;
+            }
+            Some(u32::from_le_bytes(bytes))
+        })
+        .collect()
+}
+
+/// Build the structured collision matrix for a physics settings file.
+pub fn build_collision_matrix(file: &str, content: &str, layer_names: &[Option<String>]) -> Option<CollisionMatrix> {
+    let masks = parse_collision_masks(content)?;
+    let name_of = |i: usize| layer_names.get(i).cloned().flatten();
+    let has_names = layer_names.iter().any(|n| n.is_some());
+    let visible = |i: usize| !has_names || name_of(i).is_some();
+
+    let layers = (0..LAYER_COUNT)
+        .filter(|&i| visible(i))
+        .map(|i| LayerCollisionRow {
+            index: i as u32,
+            name: name_of(i),
+            collides_with: (0..LAYER_COUNT)
+                .filter(|&j| visible(j) && layers_collide(&masks, i, j))
+                .map(|j| j as u32)
+                .collect(),
+        })
+        .collect();
+
+    let mut disabled_pairs = Vec::new();
+    for a in (0..LAYER_COUNT).filter(|&i| visible(i)) {
+        for b in (a..LAYER_COUNT).filter(|&j| visible(j)) {
+            if !layers_collide(&masks, a, b) {
+                disabled_pairs.push(LayerPair {
+                    layer_a: a as u32,
+                    layer_b: b as u32,
+                    name_a: name_of(a),
+                    name_b: name_of(b),
+                });
+            }
+        }
+    }
+
+    Some(CollisionMatrix {
+        file: file.to_string(),
+        layers,
+        disabled_pairs,
+    })
+}
+
+/// Whether two layers collide. Unity keeps the matrix symmetric; both bits must be set.
+pub fn layers_collide(masks: &[u32], a: usize, b: usize) -> bool {
+    let bit = |row: usize, col: usize| masks.get(row).is_some_and(|m| m & (1 << col) != 0);
+    bit(a, b) && bit(b, a)
+}
+
+/// Resolve a layer given by name or numeric index.
+pub fn resolve_layer(layer: &str, layer_names: &[Option<String>]) -> Option<usize> {
+    if let Ok(index) = layer.parse::<usize>() {
+        return (index < LAYER_COUNT).then_some(index);
+    }
+    layer_names.iter().position(|n| n.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(layer)))
+}
+
+/// Parse the (guid, order) pairs of MonoManager.asset's execution order map.
+pub fn parse_mono_manager_order(content: &str) -> Vec<(String, i32)> {
+    MONO_MANAGER_ORDER_RE
+        .captures_iter(content)
+        .filter_map(|caps| {
+            let guid = caps.get(1)?.as_str().to_string();
+            let order = caps.get(2)?.as_str().parse().ok()?;
+            Some((guid, order))
+        })
+        .collect()
+}
+
+/// Read `MonoImporter.executionOrder` from a script's .meta file.
+pub fn parse_meta_execution_order(meta: &str) -> Option<i32> {
+    META_EXECUTION_ORDER_RE.captures(meta)?.get(1)?.as_str().parse().ok()
+}
+
+/// Parse InputManager.asset `m_Axes` into typed axis entries.
+pub fn parse_input_axes(content: &str) -> Vec<InputAxis> {
+    let mut axes = Vec::new();
+    let mut current: Option<InputAxis> = None;
+    let mut in_axes = false;
+
+    for line in content.lines() {
+        if !in_axes {
+            in_axes = line.trim_end() == "  m_Axes:";
+            continue;
+        }
+        // A new top-level key ends the axes list
+        if !line.starts_with("  -") && !line.starts_with("    ") {
+            break;
+        }
+        if line.starts_with("  -") {
+            if let Some(axis) = current.take() {
+                axes.push(axis);
+            }
+            current = Some(empty_axis());
+        }
+
+        let (Some(axis), Some(caps)) = (current.as_mut(), ITEM_FIELD_RE.captures(line)) else {
+            continue;
+        };
+        let key = caps.get(1).map_or("", |m| m.as_str());
+        let value = caps.get(2).map_or("", |m| m.as_str()).trim();
+        let number = || value.parse::<f64>().unwrap_or(0.0);
+        match key {
+            "m_Name" => axis.name = value.to_string(),
+            "descriptiveName" => axis.descriptive_name = value.to_string(),
+            "descriptiveNegativeName" => axis.descriptive_negative_name = value.to_string(),
+            "negativeButton" => axis.negative_button = value.to_string(),
+            "positiveButton" => axis.positive_button = value.to_string(),
+            "altNegativeButton" => axis.alt_negative_button = value.to_string(),
+            "altPositiveButton" => axis.alt_positive_button = value.to_string(),
+            "gravity" => axis.gravity = number(),
+            "dead" => axis.dead = number(),
+            "sensitivity" => axis.sensitivity = number(),
+            "snap" => axis.snap = value == "1",
+            "invert" => axis.invert = value == "1",
+            "type" => {
+                axis.axis_type = match value {
+                    "1" => "mouse_movement",
+                    "2" => "joystick_axis",
+                    _ => "key_or_mouse_button",
+                }
+                .to_string()
+            }
+            "axis" => axis.axis = value.parse().unwrap_or(0),
+            "joyNum" => axis.joy_num = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    if let Some(axis) = current {
+        axes.push(axis);
+    }
+    axes
+}
+
+fn empty_axis() -> InputAxis {
+    InputAxis {
+        name: String::new(),
+        descriptive_name: String::new(),
+        descriptive_negative_name: String::new(),
+        negative_button: String::new(),
+        positive_button: String::new(),
+        alt_negative_button: String::new(),
+        alt_positive_button: String::new(),
+        gravity: 0.0,
+        dead: 0.0,
+        sensitivity: 0.0,
+        snap: false,
+        invert: false,
+        axis_type: "key_or_mouse_button".to_string(),
+        axis: 0,
+        joy_num: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAG_MANAGER: &str = "%YAML 1.1
+--- !u!78 &1
+TagManager:
+  serializedVersion: 2
+  tags:
+  - killzone
+  layers:
+  - Default
+  - TransparentFX
+  - Ignore Raycast
+  - 
+  - Water
+  - UI
+  - 
+  - 
+  - Enemy
+  - Projectile
+  m_SortingLayers:
+  - name: Default
+";
+
+    /// All layers collide except Enemy (8) <-> Projectile (9) and Projectile <-> Projectile.
+    fn dynamics_manager() -> String {
+        let mut masks = [u32::MAX; LAYER_COUNT];
+        masks[8] &= !(1 << 9);
+        masks[9] &= !((1 << 8) | (1 << 9));
+        let hex: String = masks.iter()
+            .flat_map(|m| m.to_le_bytes())
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!("%YAML 1.1\n--- !u!55 &1\nPhysicsManager:\n  m_Gravity: {{x: 0, y: -9.81, z: 0}}\n  m_LayerCollisionMatrix: {}\n  m_AutoSimulation: 1\n", hex)
+    }
+
+    #[test]
+    fn test_parse_layer_names() {
+        let names = parse_layer_names(TAG_MANAGER);
+        assert_eq!(names.len(), LAYER_COUNT);
+        assert_eq!(names[0].as_deref(), Some("Default"));
+        assert_eq!(names[3], None);
+        assert_eq!(names[8].as_deref(), Some("Enemy"));
+        assert_eq!(names[31], None);
+    }
+
+    #[test]
+    fn test_tag_and_layer_warnings() {
+        assert_eq!(parse_tags(TAG_MANAGER), vec!["killzone"]);
+        let info = TagManagerInfo::parse(TAG_MANAGER);
+        assert!(info.warnings("killzone", 8).is_empty());
+        assert!(info.warnings("MainCamera", 0).is_empty());
+        assert_eq!(info.warnings("Boss", 3), vec![
+            "Tag \"Boss\" is not defined in TagManager".to_string(),
+            "Layer 3 has no name in TagManager".to_string(),
+        ]);
+        assert_eq!(info.warnings("Untagged", 40), vec!["Layer 40 is out of range (0-31)".to_string()]);
+    }
+
+    #[test]
+    fn test_collision_matrix() {
+        let names = parse_layer_names(TAG_MANAGER);
+        let matrix = build_collision_matrix("DynamicsManager.asset", &dynamics_manager(), &names).unwrap();
+
+        assert_eq!(matrix.layers.len(), 7, "only named layers are listed");
+        let projectile = matrix.layers.iter().find(|l| l.name.as_deref() == Some("Projectile")).unwrap();
+        assert!(!projectile.collides_with.contains(&8));
+        assert!(!projectile.collides_with.contains(&9));
+        assert!(projectile.collides_with.contains(&0));
+
+        assert_eq!(matrix.disabled_pairs.len(), 2);
+        assert_eq!(matrix.disabled_pairs[0].name_a.as_deref(), Some("Enemy"));
+        assert_eq!(matrix.disabled_pairs[0].name_b.as_deref(), Some("Projectile"));
+    }
+
+    #[test]
+    fn test_layers_collide_by_name() {
+        let names = parse_layer_names(TAG_MANAGER);
+        let masks = parse_collision_masks(&dynamics_manager()).unwrap();
+        let enemy = resolve_layer("enemy", &names).unwrap();
+        let projectile = resolve_layer("Projectile", &names).unwrap();
+        assert!(!layers_collide(&masks, enemy, projectile));
+        assert!(layers_collide(&masks, enemy, resolve_layer("0", &names).unwrap()));
+        assert_eq!(resolve_layer("Missing", &names), None);
+    }
+
+    #[test]
+    fn test_parse_execution_order() {
+        let mono_manager = "%YAML 1.1
+--- !u!116 &1
+MonoManager:
+  m_ExecutionOrder:
+  - first: {fileID: 11500000, guid: 0123456789abcdef0123456789abcdef, type: 3}
+    second: -100
+  - first: {fileID: 11500000, guid: fedcba9876543210fedcba9876543210, type: 3}
+    second: 50
+";
+        let entries = parse_mono_manager_order(mono_manager);
+        assert_eq!(entries, vec![
+            ("0123456789abcdef0123456789abcdef".to_string(), -100),
+            ("fedcba9876543210fedcba9876543210".to_string(), 50),
+        ]);
+
+        let meta = "fileFormatVersion: 2\nguid: 0123456789abcdef0123456789abcdef\nMonoImporter:\n  serializedVersion: 2\n  executionOrder: -200\n  icon: {instanceID: 0}\n";
+        assert_eq!(parse_meta_execution_order(meta), Some(-200));
+        assert_eq!(parse_meta_execution_order("fileFormatVersion: 2\n"), None);
+    }
+
+    #[test]
+    fn test_parse_input_axes() {
+        let content = "%YAML 1.1
+--- !u!13 &1
+InputManager:
+  m_ObjectHideFlags: 0
+  serializedVersion: 2
+  m_Axes:
+  - serializedVersion: 3
+    m_Name: Horizontal
+    descriptiveName: 
+    descriptiveNegativeName: 
+    negativeButton: left
+    positiveButton: right
+    altNegativeButton: a
+    altPositiveButton: d
+    gravity: 3
+    dead: 0.001
+    sensitivity: 3
+    snap: 1
+    invert: 0
+    type: 0
+    axis: 0
+    joyNum: 0
+  - serializedVersion: 3
+    m_Name: Mouse X
+    descriptiveName: 
+    descriptiveNegativeName: 
+    negativeButton: 
+    positiveButton: 
+    altNegativeButton: 
+    altPositiveButton: 
+    gravity: 0
+    dead: 0
+    sensitivity: 0.1
+    snap: 0
+    invert: 0
+    type: 1
+    axis: 0
+    joyNum: 0
+  m_UsePhysicalKeys: 1
+";
+        let axes = parse_input_axes(content);
+        assert_eq!(axes.len(), 2);
+        assert_eq!(axes[0].name, "Horizontal");
+        assert_eq!(axes[0].positive_button, "right");
+        assert_eq!(axes[0].alt_negative_button, "a");
+        assert!(axes[0].snap);
+        assert_eq!(axes[0].dead, 0.001);
+        assert_eq!(axes[1].name, "Mouse X");
+        assert_eq!(axes[1].axis_type, "mouse_movement");
+        assert_eq!(axes[1].sensitivity, 0.1);
+        assert_eq!(axes[1].negative_button, "");
+    }
+}
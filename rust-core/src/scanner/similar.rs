@@ -0,0 +1,162 @@
+//! Objects of a scene or prefab configured like a given GameObject.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::common::SimilarObject;
+use super::config::ComponentConfig;
+use super::duplicates::{shapes, Shape};
+
+/// Similarity at or above which objects are reported when no threshold is given.
+pub const DEFAULT_THRESHOLD: f64 = 0.5;
+
+fn counts(components: &[String]) -> BTreeMap<&str, usize> {
+    let mut counts = BTreeMap::new();
+    for component in components {
+        *counts.entry(component.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Weighted Jaccard of two component multisets (two colliders vs one counts as a partial match).
+fn component_similarity(a: &BTreeMap<&str, usize>, b: &BTreeMap<&str, usize>) -> f64 {
+    let types: BTreeSet<&str> = a.keys().chain(b.keys()).copied().collect();
+    let (mut shared, mut total) = (0, 0);
+    for t in types {
+        let (x, y) = (a.get(t).copied().unwrap_or(0), b.get(t).copied().unwrap_or(0));
+        shared += x.min(y);
+        total += x.max(y);
+    }
+    if total == 0 { 1.0 } else { shared as f64 / total as f64 }
+}
+
+/// Fraction of the properties either object has that both have with the same value.
+fn property_similarity(a: &Shape, b: &Shape) -> f64 {
+    let keys: BTreeSet<&String> = a.properties.keys().chain(b.properties.keys()).collect();
+    if keys.is_empty() {
+        return 1.0;
+    }
+    let equal = keys.iter().filter(|key| a.properties.get(**key) == b.properties.get(**key)).count();
+    equal as f64 / keys.len() as f64
+}
+
+/// Components of `of` beyond those `other` has, one entry per surplus copy.
+fn surplus(of: &BTreeMap<&str, usize>, other: &BTreeMap<&str, usize>) -> Vec<String> {
+    of.iter()
+        .flat_map(|(t, n)| std::iter::repeat_n(t.to_string(), n.saturating_sub(other.get(t).copied().unwrap_or(0))))
+        .collect()
+}
+
+/// Rank the other GameObjects of Unity YAML content by how closely they match `go_id`.
+///
+/// The fingerprint of an object is its component types (scripts by name when the GUID
+/// resolves) and its component and GameObject property values, ignoring placement (transform,
+/// parent, sibling order) and local references. Similarity averages the component overlap and
+/// the share of properties with equal values. Returns the target and the number of objects
+/// compared, with the objects scoring at least `threshold`, most similar first; None when
+/// `go_id` is not a GameObject of the content.
+pub fn find_similar(
+    content: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+    go_id: &str,
+    threshold: f64,
+) -> Option<(SimilarObject, u32, Vec<SimilarObject>)> {
+    let shapes = shapes(content, guid_cache, config);
+    let target = shapes.iter().find(|s| s.file_id == go_id)?;
+    let target_counts = counts(&target.components);
+
+    let mut matches: Vec<SimilarObject> = shapes.iter()
+        .filter(|s| s.file_id != target.file_id)
+        .filter_map(|other| {
+            let other_counts = counts(&other.components);
+            let similarity = (component_similarity(&target_counts, &other_counts) + property_similarity(target, other)) / 2.0;
+            if similarity < threshold {
+                return None;
+            }
+            let differing_properties = target.properties.iter()
+                .filter(|(key, value)| other.properties.get(*key).is_some_and(|v| v != *value))
+                .map(|(key, _)| key.clone())
+                .collect();
+            Some(SimilarObject {
+                file_id: other.file_id.clone(),
+                name: other.name.clone(),
+                path: other.path.clone(),
+                similarity: (similarity * 1000.0).round() / 1000.0,
+                components: other.components.clone(),
+                missing_components: surplus(&target_counts, &other_counts),
+                extra_components: surplus(&other_counts, &target_counts),
+                differing_properties,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal));
+
+    let target = SimilarObject {
+        file_id: target.file_id.clone(),
+        name: target.name.clone(),
+        path: target.path.clone(),
+        similarity: 1.0,
+        components: target.components.clone(),
+        missing_components: Vec::new(),
+        extra_components: Vec::new(),
+        differing_properties: Vec::new(),
+    };
+    Some((target, shapes.len() as u32 - 1, matches))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A GameObject with a Transform at `x` and the given (class id, type, body) components.
+    fn object(id: u32, name: &str, x: u32, components: &[(u32, &str, &str)]) -> String {
+        let mut refs = format!("  - component: {{fileID: {}}}\n", id + 1);
+        let mut blocks = format!("--- !u!4 &{}\nTransform:\n  m_GameObject: {{fileID: {id}}}\n  m_LocalPosition: {{x: {x}, y: 0, z: 0}}\n  m_Children: []\n  m_Father: {{fileID: 0}}\n", id + 1);
+        for (i, (class_id, type_name, body)) in components.iter().enumerate() {
+            let comp_id = id + 2 + i as u32;
+            refs.push_str(&format!("  - component: {{fileID: {comp_id}}}\n"));
+            blocks.push_str(&format!("--- !u!{class_id} &{comp_id}\n{type_name}:\n  m_GameObject: {{fileID: {id}}}\n{body}"));
+        }
+        format!("--- !u!1 &{id}\nGameObject:\n  m_Component:\n{refs}  m_Layer: 0\n  m_Name: {name}\n{blocks}")
+    }
+
+    #[test]
+    fn test_find_similar_ranks_by_components_and_values() {
+        let light = |intensity: &str| (108, "Light", format!("  m_Intensity: {}\n  m_Range: 10\n", intensity));
+        let (lamp, dim, spot) = (light("1"), light("0.5"), light("1"));
+        let content = format!("%YAML 1.1\n{}{}{}{}{}",
+            object(10, "Lamp", 0, &[(lamp.0, lamp.1, &lamp.2)]),
+            object(20, "Lamp (1)", 5, &[(lamp.0, lamp.1, &lamp.2)]),
+            object(30, "DimLamp", 9, &[(dim.0, dim.1, &dim.2)]),
+            object(40, "SpotLamp", 2, &[(spot.0, spot.1, &spot.2), (65, "BoxCollider", "  m_Size: {x: 1, y: 1, z: 1}\n")]),
+            object(50, "Floor", 0, &[(65, "BoxCollider", "  m_Size: {x: 9, y: 1, z: 9}\n")]));
+        let config = ComponentConfig::default();
+
+        let (target, checked, matches) = find_similar(&content, &HashMap::new(), &config, "10", 0.5).unwrap();
+        assert_eq!((target.name.as_str(), checked), ("Lamp", 4));
+        assert_eq!(target.components, vec!["Light", "Transform"]);
+        let ranked: Vec<(&str, f64)> = matches.iter().map(|m| (m.name.as_str(), m.similarity)).collect();
+        assert_eq!(ranked[0], ("Lamp (1)", 1.0), "placement is ignored");
+        assert_eq!(ranked[1].0, "DimLamp");
+        assert_eq!(matches[1].differing_properties, vec!["Light.m_Intensity"]);
+        assert_eq!(ranked[2].0, "SpotLamp");
+        assert_eq!((matches[2].missing_components.clone(), matches[2].extra_components.clone()), (vec![], vec!["BoxCollider".to_string()]));
+        assert!(ranked.iter().all(|(name, _)| *name != "Floor"), "{:?}", ranked);
+
+        let (_, _, all) = find_similar(&content, &HashMap::new(), &config, "10", 0.0).unwrap();
+        let floor = all.iter().find(|m| m.name == "Floor").unwrap();
+        assert_eq!(floor.missing_components, vec!["Light"]);
+        assert!(floor.similarity < 0.5);
+
+        assert!(find_similar(&content, &HashMap::new(), &config, "11", 0.5).is_none(), "a Transform is not a GameObject");
+    }
+
+    #[test]
+    fn test_component_similarity_counts_repeats() {
+        let two = vec!["BoxCollider".to_string(), "BoxCollider".to_string(), "Transform".to_string()];
+        let one = vec!["BoxCollider".to_string(), "Transform".to_string()];
+        assert!((component_similarity(&counts(&two), &counts(&one)) - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(surplus(&counts(&two), &counts(&one)), vec!["BoxCollider"]);
+        assert_eq!(component_similarity(&counts(&[]), &counts(&[])), 1.0);
+    }
+}
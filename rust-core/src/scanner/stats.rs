@@ -0,0 +1,405 @@
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+use crate::common::{SceneStats, SceneStatsDiff, SubtreeMetrics};
+use super::config::ComponentConfig;
+use super::gameobject::build_parent_map;
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::prefab::extract_transform_parent;
+use super::rendering::{field, ref_file_id};
+
+static BLOCK_HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"--- !u!(\d+) &(-?\d+)( stripped)?[ \t]*\n([A-Za-z][A-Za-z0-9_]*):").unwrap()
+});
+
+/// Class IDs that are never counted as components.
+const NON_COMPONENT_CLASSES: &[u32] = &[
+    1,    // GameObject
+    1001, // PrefabInstance
+    1660057539, // SceneRoots
+];
+
+/// Block counts per class ID, in ascending class ID order, and the number of stripped blocks.
+/// Reads only the `--- !u!<class> &<id>` header lines; block bodies are never matched.
+pub fn count_blocks(content: &str) -> (Vec<(u32, u32)>, u32) {
+    let mut counts: BTreeMap<u32, u32> = BTreeMap::new();
+    let mut stripped = 0u32;
+    for (start, _) in content.match_indices("--- !u!") {
+        if start > 0 && content.as_bytes()[start - 1] != b'\n' {
+            continue;
+        }
+        let header = &content[start + 7..];
+        let header = &header[..header.find('\n').unwrap_or(header.len())];
+        let digits = header.find(|c: char| !c.is_ascii_digit()).unwrap_or(header.len());
+        let Ok(class_id) = header[..digits].parse::<u32>() else { continue };
+        *counts.entry(class_id).or_default() += 1;
+        if header.trim_end().ends_with(" stripped") {
+            stripped += 1;
+        }
+    }
+    (counts.into_iter().collect(), stripped)
+}
+
+/// Compute aggregate statistics for Unity YAML content.
+pub fn compute_stats(
+    file: &str,
+    content: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> SceneStats {
+    let gameobject_count = UnityYamlParser::extract_gameobjects_with_config(content, config).len() as u32;
+    let script_re = Regex::new(&config.script_ref_pattern()).expect("Invalid regex");
+
+    let mut component_types: BTreeMap<String, u32> = BTreeMap::new();
+    let mut script_types: BTreeSet<String> = BTreeSet::new();
+    let mut component_count = 0u32;
+    let mut prefab_instance_count = 0u32;
+    let mut script_count = 0u32;
+
+    let headers: Vec<_> = BLOCK_HEADER_RE.captures_iter(content).collect();
+    for (i, caps) in headers.iter().enumerate() {
+        let class_id: u32 = caps.get(1).and_then(|m| m.as_str().parse().ok()).unwrap_or(0);
+        if class_id == 1001 {
+            prefab_instance_count += 1;
+            continue;
+        }
+        // Stripped blocks are placeholders for objects owned by a PrefabInstance
+        if caps.get(3).is_some() || NON_COMPONENT_CLASSES.contains(&class_id) {
+            continue;
+        }
+
+        let body_start = caps.get(0).map_or(0, |m| m.end());
+        let body_end = headers.get(i + 1)
+            .and_then(|next| next.get(0))
+            .map_or(content.len(), |m| m.start());
+        let body = &content[body_start..body_end];
+        // Components belong to a GameObject; scene settings (RenderSettings, LightmapSettings,
+        // ...) and ScriptableObjects do not
+        if field(body, "m_GameObject").and_then(ref_file_id).is_none_or(|id| id == "0") {
+            continue;
+        }
+
+        let type_name = caps.get(4).map_or("", |m| m.as_str()).to_string();
+        component_count += 1;
+        *component_types.entry(type_name).or_insert(0) += 1;
+
+        if config.is_script_container(class_id) {
+            script_count += 1;
+            if let Some(guid) = script_re.captures(body).and_then(|c| c.get(1)) {
+                script_types.insert(script_type_label(guid.as_str(), guid_cache));
+            }
+        }
+    }
+
+    SceneStats {
+        file: file.to_string(),
+        size_bytes: content.len() as i64,
+        gameobject_count,
+        component_count,
+        prefab_instance_count,
+        script_count,
+        component_types: serde_json::json!(component_types),
+        script_types: script_types.into_iter().collect(),
+    }
+}
+
+/// Size and shape of the hierarchy under `go_id` (the GameObject itself included): descendant
+/// count, depth, widest level, components per type and nested PrefabInstances.
+/// Returns None when `go_id` is not a GameObject of the content.
+pub fn subtree_metrics(
+    file: &str,
+    content: &str,
+    go_id: &str,
+    guid_cache: &HashMap<String, String>,
+    config: &ComponentConfig,
+) -> Option<SubtreeMetrics> {
+    let blocks = UnityYamlParser::parse_all_blocks(content);
+    let index = BlockIndex::new(content);
+    let root = index.get_by_class_and_id(config.gameobject_class_id, go_id)?;
+    let script_re = Regex::new(&config.script_ref_pattern()).expect("Invalid regex");
+
+    let mut children: HashMap<String, Vec<String>> = HashMap::new();
+    for (child, parent) in build_parent_map(&blocks, config) {
+        children.entry(parent).or_default().push(child);
+    }
+
+    let mut level_counts: Vec<u32> = Vec::new();
+    let mut component_types: BTreeMap<String, u32> = BTreeMap::new();
+    let mut script_types: BTreeMap<String, u32> = BTreeMap::new();
+    let mut transform_ids: BTreeSet<String> = BTreeSet::new();
+    let mut seen: BTreeSet<String> = BTreeSet::new();
+    let (mut component_count, mut inactive_count, mut max_children) = (0u32, 0u32, 0u32);
+
+    let mut level = vec![go_id.to_string()];
+    while !level.is_empty() {
+        level_counts.push(level.len() as u32);
+        let mut next = Vec::new();
+        for id in level {
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            let Some(go) = index.get_by_class_and_id(config.gameobject_class_id, &id) else { continue };
+            if field(go, "m_IsActive") == Some("0") {
+                inactive_count += 1;
+            }
+            for comp_id in UnityYamlParser::parse_component_refs(go) {
+                let Some((class_id, body)) = index.get(&comp_id) else { continue };
+                if config.hierarchy_providers.contains(&class_id) {
+                    transform_ids.insert(comp_id.clone());
+                }
+                let type_name = config.class_names.get(&class_id)
+                    .cloned()
+                    .unwrap_or_else(|| super::class_id_to_name(class_id).to_string());
+                component_count += 1;
+                *component_types.entry(type_name).or_insert(0) += 1;
+                if config.is_script_container(class_id) {
+                    if let Some(guid) = script_re.captures(body).and_then(|c| c.get(1)) {
+                        *script_types.entry(script_type_label(guid.as_str(), guid_cache)).or_insert(0) += 1;
+                    }
+                }
+            }
+            let kids = children.get(&id).map_or(&[][..], Vec::as_slice);
+            max_children = max_children.max(kids.len() as u32);
+            next.extend(kids.iter().filter(|k| !seen.contains(*k)).cloned());
+        }
+        level = next;
+    }
+
+    let prefab_instance_count = blocks.iter()
+        .filter(|(class_id, _, _)| *class_id == 1001)
+        .filter(|(_, _, body)| extract_transform_parent(body).is_some_and(|p| transform_ids.contains(&p)))
+        .count() as u32;
+
+    Some(SubtreeMetrics {
+        file: file.to_string(),
+        game_object_id: go_id.to_string(),
+        name: field(root, "m_Name").unwrap_or("").to_string(),
+        descendant_count: seen.len() as u32 - 1,
+        max_depth: level_counts.len() as u32 - 1,
+        max_breadth: level_counts.iter().copied().max().unwrap_or(0),
+        max_children,
+        level_counts,
+        inactive_count,
+        component_count,
+        component_types: serde_json::json!(component_types),
+        script_types: serde_json::json!(script_types),
+        prefab_instance_count,
+    })
+}
+
+/// Compare statistics of two versions of the same file.
+pub fn diff_stats(file: &str, old: SceneStats, new: SceneStats) -> SceneStatsDiff {
+    let old_scripts: BTreeSet<&String> = old.script_types.iter().collect();
+    let new_scripts: BTreeSet<&String> = new.script_types.iter().collect();
+    let added_script_types = new_scripts.difference(&old_scripts).map(|s| s.to_string()).collect();
+    let removed_script_types = old_scripts.difference(&new_scripts).map(|s| s.to_string()).collect();
+
+    let mut type_deltas: BTreeMap<String, i64> = BTreeMap::new();
+    for (sign, types) in [(-1i64, &old.component_types), (1i64, &new.component_types)] {
+        if let Some(map) = types.as_object() {
+            for (name, count) in map {
+                *type_deltas.entry(name.clone()).or_insert(0) += sign * count.as_i64().unwrap_or(0);
+            }
+        }
+    }
+    type_deltas.retain(|_, delta| *delta != 0);
+
+    SceneStatsDiff {
+        file: file.to_string(),
+        gameobjects_delta: new.gameobject_count as i64 - old.gameobject_count as i64,
+        components_delta: new.component_count as i64 - old.component_count as i64,
+        prefab_instances_delta: new.prefab_instance_count as i64 - old.prefab_instance_count as i64,
+        scripts_delta: new.script_count as i64 - old.script_count as i64,
+        size_delta_bytes: new.size_bytes - old.size_bytes,
+        added_script_types,
+        removed_script_types,
+        component_type_deltas: serde_json::json!(type_deltas),
+        old,
+        new,
+    }
+}
+
+/// Human-readable identity for a script GUID: the script file stem when known.
+fn script_type_label(guid: &str, guid_cache: &HashMap<String, String>) -> String {
+    guid_cache.get(guid)
+        .and_then(|path| std::path::Path::new(path).file_stem())
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| format!("guid:{}", guid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_SCENE: &str = "%YAML 1.1
+--- !u!1 &100
+GameObject:
+  m_Component:
+  - component: {fileID: 101}
+  m_Name: Player
+  m_IsActive: 1
+--- !u!4 &101
+Transform:
+  m_GameObject: {fileID: 100}
+  m_Father: {fileID: 0}
+";
+
+    fn new_scene() -> String {
+        format!("{}--- !u!1 &200
+GameObject:
+  m_Component:
+  - component: {{fileID: 201}}
+  - component: {{fileID: 202}}
+  m_Name: Enemy
+  m_IsActive: 1
+--- !u!4 &201
+Transform:
+  m_GameObject: {{fileID: 200}}
+  m_Father: {{fileID: 0}}
+--- !u!114 &202
+MonoBehaviour:
+  m_GameObject: {{fileID: 200}}
+  m_Script: {{fileID: 11500000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}}
+--- !u!4 &300 stripped
+Transform:
+  m_PrefabInstance: {{fileID: 400}}
+--- !u!1001 &400
+PrefabInstance:
+  m_SourcePrefab: {{fileID: 100100000, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 3}}
+", OLD_SCENE)
+    }
+
+    #[test]
+    fn test_count_blocks_reads_headers_only() {
+        let content = "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Name: --- !u!1 &9\n--- !u!4 &2\nTransform: {}\n--- !u!1 &3 stripped\nGameObject:\n--- !u!1001 &4\nPrefabInstance:\n";
+        let (counts, stripped) = count_blocks(content);
+        assert_eq!(counts, vec![(1, 2), (4, 1), (1001, 1)]);
+        assert_eq!(stripped, 1);
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let mut cache = HashMap::new();
+        cache.insert("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(), "Assets/Scripts/EnemyAI.cs".to_string());
+        let stats = compute_stats("Main.unity", &new_scene(), &cache, &ComponentConfig::default());
+        assert_eq!(stats.gameobject_count, 2);
+        assert_eq!(stats.component_count, 3, "stripped blocks are not counted");
+        assert_eq!(stats.prefab_instance_count, 1);
+        assert_eq!(stats.script_count, 1);
+        assert_eq!(stats.script_types, vec!["EnemyAI".to_string()]);
+        assert_eq!(stats.component_types["Transform"], 2);
+        assert_eq!(stats.size_bytes, new_scene().len() as i64);
+
+        let settings = "--- !u!29 &1\nOcclusionCullingSettings:\n  m_ObjectHideFlags: 0\n--- !u!104 &2\nRenderSettings:\n  m_Fog: 0\n\
+--- !u!157 &3\nLightmapSettings:\n  m_GIWorkflowMode: 1\n--- !u!196 &4\nNavMeshSettings:\n  m_BuildSettings: {}\n";
+        let with_settings = compute_stats("Main.unity", &format!("{}{}", settings, OLD_SCENE.trim_start_matches("%YAML 1.1\n")), &cache, &ComponentConfig::default());
+        assert_eq!(with_settings.component_count, 1, "scene settings are not components");
+        assert_eq!(with_settings.component_types, serde_json::json!({ "Transform": 1 }));
+    }
+
+    #[test]
+    fn test_subtree_metrics() {
+        let content = "%YAML 1.1
+--- !u!1 &1
+GameObject:
+  m_Component:
+  - component: {fileID: 2}
+  m_Name: Panel
+  m_IsActive: 1
+--- !u!224 &2
+RectTransform:
+  m_GameObject: {fileID: 1}
+  m_Father: {fileID: 0}
+--- !u!1 &10
+GameObject:
+  m_Component:
+  - component: {fileID: 11}
+  - component: {fileID: 12}
+  m_Name: Button
+  m_IsActive: 0
+--- !u!224 &11
+RectTransform:
+  m_GameObject: {fileID: 10}
+  m_Father: {fileID: 2}
+--- !u!114 &12
+MonoBehaviour:
+  m_GameObject: {fileID: 10}
+  m_Script: {fileID: 11500000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 3}
+--- !u!1 &20
+GameObject:
+  m_Component:
+  - component: {fileID: 21}
+  m_Name: Label
+  m_IsActive: 1
+--- !u!224 &21
+RectTransform:
+  m_GameObject: {fileID: 20}
+  m_Father: {fileID: 2}
+--- !u!1 &30
+GameObject:
+  m_Component:
+  - component: {fileID: 31}
+  m_Name: Icon
+  m_IsActive: 1
+--- !u!224 &31
+RectTransform:
+  m_GameObject: {fileID: 30}
+  m_Father: {fileID: 11}
+--- !u!1001 &40
+PrefabInstance:
+  m_Modification:
+    m_TransformParent: {fileID: 31}
+";
+        let mut cache = HashMap::new();
+        cache.insert("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(), "Assets/UI/ButtonFx.cs".to_string());
+        let config = ComponentConfig::default();
+        let metrics = subtree_metrics("UI.prefab", content, "1", &cache, &config).unwrap();
+        assert_eq!(metrics.name, "Panel");
+        assert_eq!(metrics.descendant_count, 3);
+        assert_eq!(metrics.max_depth, 2);
+        assert_eq!(metrics.level_counts, vec![1, 2, 1]);
+        assert_eq!(metrics.max_breadth, 2);
+        assert_eq!(metrics.max_children, 2);
+        assert_eq!(metrics.inactive_count, 1);
+        assert_eq!(metrics.component_count, 5);
+        assert_eq!(metrics.component_types["RectTransform"], 4);
+        assert_eq!(metrics.script_types["ButtonFx"], 1);
+        assert_eq!(metrics.prefab_instance_count, 1);
+
+        let leaf = subtree_metrics("UI.prefab", content, "20", &cache, &config).unwrap();
+        assert_eq!((leaf.descendant_count, leaf.max_depth, leaf.component_count, leaf.prefab_instance_count), (0, 0, 1, 0));
+        assert!(subtree_metrics("UI.prefab", content, "2", &cache, &config).is_none());
+    }
+
+    #[test]
+    fn test_diff_stats() {
+        let cache = HashMap::new();
+        let config = ComponentConfig::default();
+        let old = compute_stats("Main.unity", OLD_SCENE, &cache, &config);
+        let new = compute_stats("Main.unity", &new_scene(), &cache, &config);
+        let diff = diff_stats("Main.unity", old, new);
+
+        assert_eq!(diff.gameobjects_delta, 1);
+        assert_eq!(diff.components_delta, 2);
+        assert_eq!(diff.prefab_instances_delta, 1);
+        assert_eq!(diff.scripts_delta, 1);
+        assert!(diff.size_delta_bytes > 0);
+        assert_eq!(diff.added_script_types, vec!["guid:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()]);
+        assert!(diff.removed_script_types.is_empty());
+        assert_eq!(diff.component_type_deltas["Transform"], 1);
+        assert_eq!(diff.component_type_deltas["MonoBehaviour"], 1);
+    }
+
+    #[test]
+    fn test_diff_stats_identical_is_empty() {
+        let cache = HashMap::new();
+        let config = ComponentConfig::default();
+        let a = compute_stats("Main.unity", OLD_SCENE, &cache, &config);
+        let b = compute_stats("Main.unity", OLD_SCENE, &cache, &config);
+        let diff = diff_stats("Main.unity", a, b);
+        assert_eq!(diff.gameobjects_delta, 0);
+        assert_eq!(diff.size_delta_bytes, 0);
+        assert!(diff.component_type_deltas.as_object().unwrap().is_empty());
+    }
+}
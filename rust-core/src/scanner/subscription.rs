@@ -0,0 +1,192 @@
+//! Change polling for scenes and prefabs.
+//!
+//! A snapshot keeps a hash of the whole file plus, per GameObject, a hash of its own block and
+//! of each of its components. Polling compares a fresh snapshot against the stored one, so an
+//! unchanged file costs one hash and a changed one reports only the objects that differ.
+
+use std::collections::{BTreeMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use crate::common::SceneObjectChange;
+use super::config::ComponentConfig;
+use super::correlate::fingerprints;
+use super::parser::BlockIndex;
+
+/// State of one GameObject at snapshot time.
+#[derive(Debug, Clone, PartialEq)]
+struct ObjectState {
+    name: String,
+    path: String,
+    /// Hash of the GameObject block itself (name, tag, layer, active, component list)
+    own_hash: u64,
+    /// (component file ID, type, hash of its block)
+    components: Vec<(String, String, u64)>,
+}
+
+/// Fingerprints of a file's GameObjects at one point in time.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    content_hash: u64,
+    objects: BTreeMap<String, ObjectState>,
+}
+
+impl Snapshot {
+    pub fn object_count(&self) -> usize {
+        self.objects.len()
+    }
+}
+
+/// Objects added, removed and modified between two snapshots.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Changes {
+    pub added: Vec<SceneObjectChange>,
+    pub removed: Vec<SceneObjectChange>,
+    pub modified: Vec<SceneObjectChange>,
+}
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Snapshot Unity YAML content.
+pub fn snapshot(content: &str, config: &ComponentConfig) -> Snapshot {
+    let index = BlockIndex::new(content);
+    let block_hash = |id: &str| index.get(id).map_or(0, |(_, body)| hash_of(body));
+    let objects = fingerprints(content, config).into_iter()
+        .map(|fp| {
+            let components = fp.components.into_iter()
+                .map(|(id, type_name)| {
+                    let hash = block_hash(&id);
+                    (id, type_name, hash)
+                })
+                .collect();
+            let state = ObjectState { own_hash: block_hash(&fp.file_id), name: fp.name, path: fp.path, components };
+            (fp.file_id, state)
+        })
+        .collect();
+    Snapshot { content_hash: hash_of(content), objects }
+}
+
+/// Refresh `previous` against new content: the same snapshot when the content hash matches,
+/// a fresh one otherwise.
+pub fn resnapshot(previous: &Snapshot, content: &str, config: &ComponentConfig) -> Snapshot {
+    if previous.content_hash == hash_of(content) {
+        return previous.clone();
+    }
+    snapshot(content, config)
+}
+
+fn change(file_id: &str, state: &ObjectState) -> SceneObjectChange {
+    SceneObjectChange {
+        file_id: file_id.to_string(),
+        name: state.name.clone(),
+        path: state.path.clone(),
+        gameobject_changed: false,
+        changed_components: Vec::new(),
+    }
+}
+
+/// Compare two snapshots of the same file.
+pub fn diff(old: &Snapshot, new: &Snapshot) -> Changes {
+    let mut changes = Changes::default();
+    if old.content_hash == new.content_hash {
+        return changes;
+    }
+    for (id, state) in &new.objects {
+        let Some(before) = old.objects.get(id) else {
+            changes.added.push(change(id, state));
+            continue;
+        };
+        if before == state {
+            continue;
+        }
+        let old_components: HashSet<&(String, String, u64)> = before.components.iter().collect();
+        let new_components: HashSet<&(String, String, u64)> = state.components.iter().collect();
+        let mut changed_components: Vec<String> = before.components.iter()
+            .filter(|c| !new_components.contains(c))
+            .chain(state.components.iter().filter(|c| !old_components.contains(c)))
+            .map(|(_, type_name, _)| type_name.clone())
+            .collect();
+        changed_components.dedup();
+        let mut modified = change(id, state);
+        // Renames and re-parenting show up as a changed name or path, not a changed block
+        modified.gameobject_changed = before.own_hash != state.own_hash || before.path != state.path;
+        modified.changed_components = changed_components;
+        changes.modified.push(modified);
+    }
+    for (id, state) in &old.objects {
+        if !new.objects.contains_key(id) {
+            changes.removed.push(change(id, state));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = "%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1 &100
+GameObject:
+  m_Component:
+  - component: {fileID: 101}
+  m_Name: Player
+--- !u!4 &101
+Transform:
+  m_GameObject: {fileID: 100}
+  m_LocalPosition: {x: 0, y: 0, z: 0}
+  m_Children: []
+  m_Father: {fileID: 0}
+--- !u!1 &200
+GameObject:
+  m_Component:
+  - component: {fileID: 201}
+  m_Name: Enemy
+--- !u!4 &201
+Transform:
+  m_GameObject: {fileID: 200}
+  m_LocalPosition: {x: 5, y: 0, z: 0}
+  m_Children: []
+  m_Father: {fileID: 0}
+";
+
+    #[test]
+    fn test_unchanged_content_has_no_changes() {
+        let config = ComponentConfig::default();
+        let before = snapshot(SCENE, &config);
+        assert_eq!(before.object_count(), 2);
+        let after = resnapshot(&before, SCENE, &config);
+        assert_eq!(diff(&before, &after), Changes::default());
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_modified() {
+        let config = ComponentConfig::default();
+        let before = snapshot(SCENE, &config);
+        let edited = SCENE
+            .replace("m_LocalPosition: {x: 5, y: 0, z: 0}", "m_LocalPosition: {x: 6, y: 0, z: 0}")
+            .replace("m_Name: Player", "m_Name: Hero")
+            .replace("--- !u!1 &200", "--- !u!1 &300")
+            .replace("m_GameObject: {fileID: 200}", "m_GameObject: {fileID: 300}");
+        let changes = diff(&before, &snapshot(&edited, &config));
+
+        let ids = |list: &[SceneObjectChange]| list.iter().map(|c| c.file_id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&changes.added), vec!["300"]);
+        assert_eq!(ids(&changes.removed), vec!["200"]);
+        assert_eq!(changes.removed[0].name, "Enemy");
+        assert_eq!(changes.modified.len(), 1);
+        assert_eq!((changes.modified[0].name.as_str(), changes.modified[0].gameobject_changed), ("Hero", true));
+        assert!(changes.modified[0].changed_components.is_empty());
+
+        let moved = SCENE.replace("m_LocalPosition: {x: 0, y: 0, z: 0}", "m_LocalPosition: {x: 1, y: 0, z: 0}");
+        let changes = diff(&before, &snapshot(&moved, &config));
+        assert_eq!(changes.modified.len(), 1);
+        assert_eq!(changes.modified[0].file_id, "100");
+        assert!(!changes.modified[0].gameobject_changed);
+        assert_eq!(changes.modified[0].changed_components, vec!["Transform"]);
+    }
+}
@@ -0,0 +1,233 @@
+//! Local and world position, rotation and scale of one GameObject.
+
+use std::collections::HashSet;
+
+use crate::common::{Quaternion, TransformSummary, TransformTrs, Vector3};
+use super::config::ComponentConfig;
+use super::gameobject::{extract_ref_list, find_transform_id_indexed};
+use super::parser::{BlockIndex, UnityYamlParser};
+use super::prefab;
+use super::query::Value;
+use super::rendering::{field, owner_game_object, ref_file_id};
+
+const RECT_TRANSFORM_CLASS_ID: u32 = 224;
+const PREFAB_INSTANCE_CLASS_ID: u32 = 1001;
+const SCENE_ROOTS_CLASS_ID: u32 = 1660057539;
+
+pub(super) const ZERO: Vector3 = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+pub(super) const ONE: Vector3 = Vector3 { x: 1.0, y: 1.0, z: 1.0 };
+pub(super) const IDENTITY: Quaternion = Quaternion { x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+
+/// Drop float noise (0.99999994) from composed values.
+pub(super) fn round(n: f64) -> f64 {
+    let rounded = (n * 1e6).round() / 1e6;
+    if rounded == 0.0 { 0.0 } else { rounded }
+}
+
+fn round_vector(v: Vector3) -> Vector3 {
+    Vector3 { x: round(v.x), y: round(v.y), z: round(v.z) }
+}
+
+/// Numeric members of a serialized flow mapping field, `default` for missing ones.
+fn members<const N: usize>(block: &str, key: &str, names: [&str; N], default: [f64; N]) -> Option<[f64; N]> {
+    let value = Value::from_scalar(field(block, key)?);
+    let mut out = default;
+    for (slot, name) in out.iter_mut().zip(names) {
+        if let Value::Number(n) = value.member(name) {
+            *slot = n;
+        }
+    }
+    Some(out)
+}
+
+fn vector(block: &str, key: &str, default: Vector3) -> Option<Vector3> {
+    members(block, key, ["x", "y", "z"], [default.x, default.y, default.z]).map(|[x, y, z]| Vector3 { x, y, z })
+}
+
+fn quaternion(block: &str, key: &str) -> Option<Quaternion> {
+    let [x, y, z, w] = members(block, key, ["x", "y", "z", "w"], [0.0, 0.0, 0.0, 1.0])?;
+    let length = (x * x + y * y + z * z + w * w).sqrt();
+    Some(if length > 0.0 { Quaternion { x: x / length, y: y / length, z: z / length, w: w / length } } else { IDENTITY })
+}
+
+pub(super) fn multiply(a: Quaternion, b: Quaternion) -> Quaternion {
+    Quaternion {
+        x: a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+        y: a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+        z: a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+        w: a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+    }
+}
+
+pub(super) fn rotate(q: Quaternion, v: Vector3) -> Vector3 {
+    // v + 2w(q × v) + 2q × (q × v)
+    let (tx, ty, tz) = (2.0 * (q.y * v.z - q.z * v.y), 2.0 * (q.z * v.x - q.x * v.z), 2.0 * (q.x * v.y - q.y * v.x));
+    Vector3 {
+        x: v.x + q.w * tx + (q.y * tz - q.z * ty),
+        y: v.y + q.w * ty + (q.z * tx - q.x * tz),
+        z: v.z + q.w * tz + (q.x * ty - q.y * tx),
+    }
+}
+
+pub(super) fn vector_add(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3 { x: a.x + b.x, y: a.y + b.y, z: a.z + b.z }
+}
+
+pub(super) fn scale_by(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3 { x: a.x * b.x, y: a.y * b.y, z: a.z * b.z }
+}
+
+/// Euler angles in degrees, each in [0, 360), for Unity's rotation order (Z, then X, then Y).
+fn euler_angles(q: Quaternion) -> Vector3 {
+    let x = (2.0 * (q.w * q.x - q.y * q.z)).clamp(-1.0, 1.0).asin();
+    let y = (2.0 * (q.w * q.y + q.x * q.z)).atan2(1.0 - 2.0 * (q.x * q.x + q.y * q.y));
+    let z = (2.0 * (q.w * q.z + q.x * q.y)).atan2(1.0 - 2.0 * (q.x * q.x + q.z * q.z));
+    let degrees = |radians: f64| {
+        let d = round(radians.to_degrees().rem_euclid(360.0));
+        if d >= 360.0 { 0.0 } else { d }
+    };
+    Vector3 { x: degrees(x), y: degrees(y), z: degrees(z) }
+}
+
+/// Local TRS of a Transform block; None for a stripped (nested prefab instance) Transform.
+pub(super) fn local_trs(block: &str) -> Option<TransformTrs> {
+    let position = vector(block, "m_LocalPosition", ZERO)?;
+    let rotation = quaternion(block, "m_LocalRotation").unwrap_or(IDENTITY);
+    Some(TransformTrs {
+        position,
+        rotation,
+        euler_angles: vector(block, "m_LocalEulerAnglesHint", ZERO).unwrap_or_else(|| euler_angles(rotation)),
+        scale: vector(block, "m_LocalScale", ONE).unwrap_or(ONE),
+    })
+}
+
+/// Name of the GameObject owning a Transform, or of the PrefabInstance a stripped one belongs to.
+fn transform_owner_name(index: &BlockIndex, block: &str) -> String {
+    match field(block, "m_PrefabInstance").and_then(ref_file_id).filter(|id| *id != "0") {
+        Some(instance) if field(block, "m_GameObject").is_none() => index
+            .get_by_class_and_id(PREFAB_INSTANCE_CLASS_ID, instance)
+            .and_then(prefab::extract_name_from_modifications)
+            .unwrap_or_default(),
+        _ => owner_game_object(index, block).1,
+    }
+}
+
+/// Local and world TRS, ancestors and sibling position of GameObject `go_id`. World values
+/// compose the parents' TRS up to the root or to the first ancestor that is a nested prefab
+/// instance. None when `go_id` is not a GameObject with a Transform.
+pub fn summarize(file: &str, content: &str, go_id: &str, config: &ComponentConfig) -> Option<TransformSummary> {
+    let index = BlockIndex::new(content);
+    let go = index.get_by_class_and_id(config.gameobject_class_id, go_id)?;
+    let transform_id = find_transform_id_indexed(&index, go, config)?;
+    let (class_id, transform) = index.get(transform_id)?;
+    let local = local_trs(transform)?;
+
+    let (mut position, mut rotation, mut scale) = (local.position, local.rotation, local.scale);
+    let mut parent_chain = Vec::new();
+    let mut world_exact = true;
+    let mut sibling_index = None;
+    let mut seen = HashSet::from([transform_id]);
+    let mut current = transform;
+    while let Some(father) = field(current, "m_Father").and_then(ref_file_id).filter(|id| *id != "0") {
+        let Some((_, parent)) = index.get(father).filter(|_| seen.insert(father)) else {
+            world_exact = false;
+            break;
+        };
+        if parent_chain.is_empty() {
+            sibling_index = extract_ref_list(parent, "m_Children").iter().position(|c| c == transform_id).map(|i| i as u32);
+        }
+        parent_chain.push(transform_owner_name(&index, parent));
+        let Some(parent_trs) = local_trs(parent) else {
+            world_exact = false;
+            break;
+        };
+        position = vector_add(parent_trs.position, rotate(parent_trs.rotation, scale_by(parent_trs.scale, position)));
+        rotation = multiply(parent_trs.rotation, rotation);
+        scale = scale_by(parent_trs.scale, scale);
+        current = parent;
+    }
+    parent_chain.reverse();
+
+    if field(transform, "m_Father").and_then(ref_file_id).is_none_or(|id| id == "0") {
+        sibling_index = field(transform, "m_RootOrder").and_then(|v| v.parse().ok()).or_else(|| {
+            UnityYamlParser::parse_all_blocks(content).iter()
+                .find(|(class_id, _, _)| *class_id == SCENE_ROOTS_CLASS_ID)
+                .and_then(|(_, _, body)| extract_ref_list(body, "m_Roots").iter().position(|r| r == transform_id))
+                .map(|i| i as u32)
+        });
+    }
+
+    Some(TransformSummary {
+        file: file.to_string(),
+        game_object_id: go_id.to_string(),
+        name: field(go, "m_Name").unwrap_or_default().to_string(),
+        transform_id: transform_id.to_string(),
+        is_rect_transform: class_id == RECT_TRANSFORM_CLASS_ID,
+        world: TransformTrs {
+            position: round_vector(position),
+            rotation: Quaternion { x: round(rotation.x), y: round(rotation.y), z: round(rotation.z), w: round(rotation.w) },
+            euler_angles: euler_angles(rotation),
+            scale: round_vector(scale),
+        },
+        local,
+        world_exact,
+        parent_chain,
+        sibling_index,
+        child_count: extract_ref_list(transform, "m_Children").len() as u32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_object(id: u32, name: &str, transform: &str) -> String {
+        format!("--- !u!1 &{id}\nGameObject:\n  m_Component:\n  - component: {{fileID: {}}}\n  m_Name: {name}\n{transform}", id + 1)
+    }
+
+    fn transform(id: u32, position: &str, rotation: &str, scale: &str, father: u32, children: &[u32]) -> String {
+        let children: String = children.iter().map(|c| format!("\n  - {{fileID: {}}}", c)).collect();
+        let children = if children.is_empty() { " []".to_string() } else { children };
+        format!("--- !u!4 &{}\nTransform:\n  m_GameObject: {{fileID: {id}}}\n  m_LocalRotation: {rotation}\n  m_LocalPosition: {position}\n  m_LocalScale: {scale}\n  m_Children:{children}\n  m_Father: {{fileID: {father}}}\n", id + 1)
+    }
+
+    #[test]
+    fn test_summarize_composes_parents() {
+        let turned = "{x: 0, y: 0.7071068, z: 0, w: 0.7071068}";
+        let identity = "{x: 0, y: 0, z: 0, w: 1}";
+        let content = format!("%YAML 1.1\n{}{}{}--- !u!1660057539 &9\nSceneRoots:\n  m_Roots:\n  - {{fileID: 31}}\n  - {{fileID: 11}}\n",
+            game_object(10, "Base", &transform(10, "{x: 1, y: 0, z: 0}", turned, "{x: 2, y: 2, z: 2}", 0, &[31, 21])),
+            game_object(20, "Turret", &transform(20, "{x: 0, y: 0, z: 1}", identity, "{x: 1, y: 1, z: 1}", 11, &[])),
+            game_object(30, "Ground", &transform(30, "{x: 0, y: 0, z: 0}", identity, "{x: 1, y: 1, z: 1}", 0, &[])));
+        let config = ComponentConfig::default();
+
+        let turret = summarize("Main.unity", &content, "20", &config).unwrap();
+        assert_eq!((turret.name.as_str(), turret.transform_id.as_str()), ("Turret", "21"));
+        assert_eq!(turret.parent_chain, vec!["Base"]);
+        assert_eq!((turret.sibling_index, turret.child_count, turret.world_exact), (Some(1), 0, true));
+        assert_eq!(turret.local.position, Vector3 { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(turret.world.position, Vector3 { x: 3.0, y: 0.0, z: 0.0 });
+        assert_eq!(turret.world.euler_angles, Vector3 { x: 0.0, y: 90.0, z: 0.0 });
+        assert_eq!(turret.world.scale, Vector3 { x: 2.0, y: 2.0, z: 2.0 });
+
+        let base = summarize("Main.unity", &content, "10", &config).unwrap();
+        assert!(base.parent_chain.is_empty());
+        assert_eq!((base.sibling_index, base.child_count), (Some(1), 2), "root order from SceneRoots");
+        assert_eq!(base.local.euler_angles, Vector3 { x: 0.0, y: 90.0, z: 0.0 });
+        assert_eq!(base.world.position, base.local.position);
+
+        assert!(summarize("Main.unity", &content, "11", &config).is_none(), "a Transform is not a GameObject");
+    }
+
+    #[test]
+    fn test_summarize_stops_at_nested_prefab_instance() {
+        let content = format!("%YAML 1.1\n{}--- !u!4 &50 stripped\nTransform:\n  m_CorrespondingSourceObject: {{fileID: 400, guid: abc, type: 3}}\n  m_PrefabInstance: {{fileID: 60}}\n\
+--- !u!1001 &60\nPrefabInstance:\n  m_Modification:\n    m_Modifications:\n    - target: {{fileID: 100, guid: abc, type: 3}}\n      propertyPath: m_Name\n      value: Truck\n      objectReference: {{fileID: 0}}\n",
+            game_object(10, "Wheel", &transform(10, "{x: 1, y: 2, z: 3}", "{x: 0, y: 0, z: 0, w: 1}", "{x: 1, y: 1, z: 1}", 50, &[])));
+        let wheel = summarize("Main.unity", &content, "10", &ComponentConfig::default()).unwrap();
+        assert!(!wheel.world_exact);
+        assert_eq!(wheel.parent_chain, vec!["Truck"]);
+        assert_eq!(wheel.world.position, Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+        assert_eq!(wheel.sibling_index, None);
+    }
+}
@@ -0,0 +1,474 @@
+use regex::{Captures, Regex};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::LazyLock;
+
+use crate::common::BrokenReference;
+use super::config::ComponentConfig;
+use super::rendering::{field, ref_file_id};
+
+const SCENE_ROOTS_CLASS_ID: u32 = 1660057539;
+
+static HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^--- !u!(\d+) &(-?\d+)([^\n]*)").unwrap()
+});
+// Local references only: guid-qualified references continue with ", guid:" and do not match
+static LOCAL_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{fileID:[ \t]*(-?\d+)\}").unwrap()
+});
+static COMP_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"component:[ \t]*\{fileID:[ \t]*(-?\d+)\}").unwrap()
+});
+static FATHER_LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^([ \t]*m_Father:[ \t]*)\{fileID:[ \t]*-?\d+\}").unwrap()
+});
+
+/// One `--- !u!` document of a Unity YAML file, header line included.
+#[derive(Debug, Clone)]
+pub struct RawBlock {
+    pub class_id: u32,
+    pub file_id: String,
+    pub stripped: bool,
+    pub text: String,
+}
+
+/// Split Unity YAML into the preamble (`%YAML`/`%TAG` lines) and its raw blocks,
+/// so the file can be reassembled byte-for-byte.
+pub fn split_raw_blocks(content: &str) -> (String, Vec<RawBlock>) {
+    let headers: Vec<Captures> = HEADER_RE.captures_iter(content).collect();
+    let preamble_end = headers.first().map_or(content.len(), |c| c.get(0).unwrap().start());
+    let preamble = content[..preamble_end].to_string();
+
+    let blocks = headers.iter().enumerate().map(|(i, cap)| {
+        let start = cap.get(0).unwrap().start();
+        let end = headers.get(i + 1).map_or(content.len(), |next| next.get(0).unwrap().start());
+        RawBlock {
+            class_id: cap[1].parse().unwrap_or(0),
+            file_id: cap[2].to_string(),
+            stripped: cap[3].contains("stripped"),
+            text: content[start..end].to_string(),
+        }
+    }).collect();
+
+    (preamble, blocks)
+}
+
+/// Outcome of a planned GameObject move: rewritten contents for both files plus the bookkeeping.
+#[derive(Debug, Clone)]
+pub struct Transplant {
+    pub src_content: String,
+    pub dst_content: String,
+    pub new_file_id: String,
+    /// Source fileID -> destination fileID, in source file order
+    pub file_id_map: Vec<(String, String)>,
+    pub broken_references: Vec<BrokenReference>,
+}
+
+/// Move the subtree rooted at GameObject `go_id` out of `src_content` and append it to
+/// `dst_content`, under the GameObject `parent_id` (or at the scene root).
+///
+/// Every moved block gets a fresh fileID that does not collide with the destination.
+/// Components, child Transforms and nested prefab instances (PrefabInstance plus its stripped
+/// blocks) travel with the subtree. Local references that would cross the file boundary are
+/// reset to `{fileID: 0}` and reported as broken.
+pub fn move_gameobject(
+    src_content: &str,
+    go_id: &str,
+    dst_content: &str,
+    parent_id: Option<&str>,
+    dst_is_prefab: bool,
+    config: &ComponentConfig,
+) -> Result<Transplant, String> {
+    let (src_preamble, src_blocks) = split_raw_blocks(src_content);
+    let (dst_preamble, mut dst_blocks) = split_raw_blocks(dst_content);
+    let src_by_id: HashMap<&str, &RawBlock> = src_blocks.iter().map(|b| (b.file_id.as_str(), b)).collect();
+
+    match src_by_id.get(go_id) {
+        Some(b) if b.class_id == config.gameobject_class_id && !b.stripped => {}
+        _ => return Err(format!("GameObject {} not found in source file", go_id)),
+    }
+    let transform_of = |blocks: &HashMap<&str, &RawBlock>, go: &str| -> Option<String> {
+        let go_block = blocks.get(go)?;
+        COMP_REF_RE.captures_iter(&go_block.text)
+            .map(|c| c[1].to_string())
+            .find(|id| blocks.get(id.as_str()).is_some_and(|b| config.hierarchy_providers.contains(&b.class_id)))
+    };
+    let root_transform = transform_of(&src_by_id, go_id)
+        .ok_or_else(|| format!("GameObject {} has no Transform", go_id))?;
+
+    // Destination parent transform (parent_id may name the GameObject or its Transform)
+    let dst_by_id: HashMap<&str, &RawBlock> = dst_blocks.iter().map(|b| (b.file_id.as_str(), b)).collect();
+    let dst_parent_transform = match parent_id {
+        Some(pid) => match dst_by_id.get(pid) {
+            Some(b) if config.hierarchy_providers.contains(&b.class_id) => Some(pid.to_string()),
+            Some(b) if b.class_id == config.gameobject_class_id => Some(
+                transform_of(&dst_by_id, pid).ok_or_else(|| format!("Parent {} has no Transform", pid))?,
+            ),
+            _ => return Err(format!("Parent {} not found in destination file", pid)),
+        },
+        None if dst_is_prefab => return Err("A parent_id is required when moving into a prefab".to_string()),
+        None => None,
+    };
+
+    let moved = collect_subtree(&src_blocks, &src_by_id, go_id, config);
+
+    // Fresh, collision-free fileIDs in the destination
+    let mut taken: HashSet<String> = dst_blocks.iter().map(|b| b.file_id.clone()).collect();
+    let mut id_map: HashMap<String, String> = HashMap::new();
+    let mut file_id_map = Vec::new();
+    for block in src_blocks.iter().filter(|b| moved.contains(&b.file_id)) {
+        let new_id = fresh_file_id(&block.file_id, dst_content.len(), &mut taken);
+        id_map.insert(block.file_id.clone(), new_id.clone());
+        file_id_map.push((block.file_id.clone(), new_id));
+    }
+
+    let mut broken_references = Vec::new();
+    let new_root_transform = id_map[&root_transform].clone();
+    let father_value = dst_parent_transform.clone().unwrap_or_else(|| "0".to_string());
+
+    // Rewrite moved blocks for the destination
+    let mut appended = String::new();
+    for block in src_blocks.iter().filter(|b| moved.contains(&b.file_id)) {
+        let mut text = block.text.clone();
+        if block.file_id == root_transform {
+            text = FATHER_LINE_RE.replace(&text, "${1}{fileID: 0}").into_owned();
+        }
+        let mut text = rewrite_refs(&text, &block.file_id, "outgoing", &mut broken_references, |id| {
+            id_map.get(id).cloned()
+        });
+        text = text.replacen(&format!("&{}", block.file_id), &format!("&{}", id_map[&block.file_id]), 1);
+        if block.file_id == root_transform {
+            text = FATHER_LINE_RE.replace(&text, format!("${{1}}{{fileID: {}}}", father_value)).into_owned();
+        }
+        if !text.ends_with('\n') {
+            text.push('\n');
+        }
+        appended.push_str(&text);
+    }
+
+    // Hook the new root into the destination hierarchy
+    for block in dst_blocks.iter_mut() {
+        match &dst_parent_transform {
+            Some(pt) if &block.file_id == pt => {
+                block.text = add_list_entry(&block.text, "m_Children", &new_root_transform);
+            }
+            None if block.class_id == SCENE_ROOTS_CLASS_ID => {
+                block.text = add_list_entry(&block.text, "m_Roots", &new_root_transform);
+            }
+            _ => {}
+        }
+    }
+
+    // Detach the subtree from the source hierarchy, then null out what still points at it
+    let old_father = src_by_id.get(root_transform.as_str())
+        .and_then(|b| field(&b.text, "m_Father"))
+        .and_then(ref_file_id)
+        .filter(|id| *id != "0")
+        .map(str::to_string);
+    let mut src_out = src_preamble;
+    for block in src_blocks.iter().filter(|b| !moved.contains(&b.file_id)) {
+        let mut text = block.text.clone();
+        if old_father.as_deref() == Some(block.file_id.as_str()) {
+            text = remove_list_entry(&text, "m_Children", &root_transform);
+        } else if old_father.is_none() && block.class_id == SCENE_ROOTS_CLASS_ID {
+            text = remove_list_entry(&text, "m_Roots", &root_transform);
+        }
+        let text = rewrite_refs(&text, &block.file_id, "incoming", &mut broken_references, |id| {
+            if moved.contains(id) { None } else { Some(id.to_string()) }
+        });
+        src_out.push_str(&text);
+    }
+
+    let mut dst_out = dst_preamble;
+    for block in &dst_blocks {
+        dst_out.push_str(&block.text);
+    }
+    if !dst_out.is_empty() && !dst_out.ends_with('\n') {
+        dst_out.push('\n');
+    }
+    dst_out.push_str(&appended);
+
+    Ok(Transplant {
+        src_content: src_out,
+        dst_content: dst_out,
+        new_file_id: id_map[go_id].clone(),
+        file_id_map,
+        broken_references,
+    })
+}
+
+/// GameObjects, components, Transforms and nested prefab instances under `go_id`.
+fn collect_subtree(
+    blocks: &[RawBlock],
+    by_id: &HashMap<&str, &RawBlock>,
+    go_id: &str,
+    config: &ComponentConfig,
+) -> HashSet<String> {
+    let mut moved: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<String> = VecDeque::from([go_id.to_string()]);
+
+    while let Some(id) = queue.pop_front() {
+        if !moved.insert(id.clone()) {
+            continue;
+        }
+        let Some(block) = by_id.get(id.as_str()) else { continue };
+
+        if block.class_id == config.gameobject_class_id && !block.stripped {
+            for cap in COMP_REF_RE.captures_iter(&block.text) {
+                queue.push_back(cap[1].to_string());
+            }
+        } else if config.hierarchy_providers.contains(&block.class_id) && !block.stripped {
+            for child in children_of(&block.text) {
+                queue.push_back(child);
+            }
+        } else if block.stripped {
+            // Stripped child Transform of a nested prefab: carry the whole instance
+            if let Some(pi) = field(&block.text, "m_PrefabInstance").and_then(ref_file_id) {
+                queue.push_back(pi.to_string());
+            }
+        }
+
+        if config.hierarchy_providers.contains(&block.class_id) && !block.stripped {
+            if let Some(owner) = field(&block.text, "m_GameObject").and_then(ref_file_id) {
+                queue.push_back(owner.to_string());
+            }
+        }
+        if block.class_id == 1001 {
+            for other in blocks.iter().filter(|b| b.stripped) {
+                if field(&other.text, "m_PrefabInstance").and_then(ref_file_id) == Some(id.as_str()) {
+                    queue.push_back(other.file_id.clone());
+                }
+            }
+        }
+    }
+
+    // Components added on top of nested prefab instances live outside the m_Component lists
+    loop {
+        let extra: Vec<String> = blocks.iter()
+            .filter(|b| !b.stripped && !moved.contains(&b.file_id))
+            .filter(|b| {
+                field(&b.text, "m_GameObject")
+                    .and_then(ref_file_id)
+                    .is_some_and(|owner| moved.contains(owner))
+            })
+            .map(|b| b.file_id.clone())
+            .collect();
+        if extra.is_empty() {
+            break;
+        }
+        moved.extend(extra);
+    }
+
+    moved.remove("0");
+    moved
+}
+
+/// Child Transform file IDs listed in a Transform's `m_Children`.
+pub fn children_of(transform: &str) -> Vec<String> {
+    let mut children = Vec::new();
+    let mut in_children = false;
+    for line in transform.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("m_Children:") {
+            in_children = true;
+            continue;
+        }
+        if in_children {
+            if !trimmed.starts_with("- ") {
+                break;
+            }
+            if let Some(id) = LOCAL_REF_RE.captures(trimmed).map(|c| c[1].to_string()) {
+                children.push(id);
+            }
+        }
+    }
+    children
+}
+
+/// Rewrite every local `{fileID: N}` in a block. `map` returns the replacement id, or
+/// None when the reference cannot survive the move (it is zeroed and reported).
+fn rewrite_refs(
+    text: &str,
+    from_file_id: &str,
+    direction: &str,
+    broken: &mut Vec<BrokenReference>,
+    map: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_key = String::new();
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        if i == 0 {
+            // Header line
+            out.push_str(line);
+            continue;
+        }
+        let trimmed = line.trim_start();
+        let item = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+        let key = if !item.starts_with('{') {
+            item.split(':').next().map(str::trim).filter(|k| !k.is_empty())
+        } else {
+            None
+        };
+        if let Some(k) = key {
+            last_key = k.to_string();
+        }
+        let rewritten = LOCAL_REF_RE.replace_all(line, |cap: &Captures| {
+            let id = &cap[1];
+            if id == "0" {
+                return cap[0].to_string();
+            }
+            match map(id) {
+                Some(new_id) => format!("{{fileID: {}}}", new_id),
+                None => {
+                    broken.push(BrokenReference {
+                        direction: direction.to_string(),
+                        from_file_id: from_file_id.to_string(),
+                        property: last_key.clone(),
+                        target_file_id: id.to_string(),
+                    });
+                    "{fileID: 0}".to_string()
+                }
+            }
+        });
+        out.push_str(&rewritten);
+    }
+    out
+}
+
+fn add_list_entry(text: &str, key: &str, id: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 32);
+    let mut lines = text.split_inclusive('\n').peekable();
+    let prefix = format!("{}:", key);
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with(&prefix) {
+            out.push_str(line);
+            continue;
+        }
+        let indent = &line[..line.len() - trimmed.len()];
+        out.push_str(&format!("{}{}:\n", indent, key));
+        let item_prefix = format!("{}- ", indent);
+        while let Some(item) = lines.next_if(|l| l.starts_with(&item_prefix)) {
+            out.push_str(item);
+        }
+        out.push_str(&format!("{}- {{fileID: {}}}\n", indent, id));
+    }
+    out
+}
+
+fn remove_list_entry(text: &str, key: &str, id: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut lines = text.split_inclusive('\n').peekable();
+    let prefix = format!("{}:", key);
+    let target = format!("{{fileID: {}}}", id);
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with(&prefix) {
+            out.push_str(line);
+            continue;
+        }
+        let indent = &line[..line.len() - trimmed.len()];
+        let item_prefix = format!("{}- ", indent);
+        let mut kept = Vec::new();
+        while let Some(item) = lines.next_if(|l| l.starts_with(&item_prefix)) {
+            if item.trim_end() != format!("{}{}", item_prefix, target) {
+                kept.push(item);
+            }
+        }
+        if kept.is_empty() && !trimmed.trim_end().ends_with("[]") {
+            out.push_str(&format!("{}{}: []\n", indent, key));
+        } else {
+            out.push_str(line);
+            kept.iter().for_each(|item| out.push_str(item));
+        }
+    }
+    out
+}
+
+/// Deterministic positive 64-bit fileID that is not yet taken.
+pub(super) fn fresh_file_id(old_id: &str, salt: usize, taken: &mut HashSet<String>) -> String {
+    let mut attempt = 0u32;
+    loop {
+        let mut hasher = DefaultHasher::new();
+        (old_id, salt, attempt).hash(&mut hasher);
+        let candidate = (hasher.finish() >> 1).max(1).to_string();
+        if taken.insert(candidate.clone()) {
+            return candidate;
+        }
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &str = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n\
+--- !u!1 &10\nGameObject:\n  m_Component:\n  - component: {fileID: 11}\n  m_Name: Root\n\
+--- !u!4 &11\nTransform:\n  m_GameObject: {fileID: 10}\n  m_Children:\n  - {fileID: 21}\n  m_Father: {fileID: 0}\n\
+--- !u!1 &20\nGameObject:\n  m_Component:\n  - component: {fileID: 21}\n  - component: {fileID: 22}\n  m_Name: Mover\n\
+--- !u!4 &21\nTransform:\n  m_GameObject: {fileID: 20}\n  m_Children: []\n  m_Father: {fileID: 11}\n\
+--- !u!114 &22\nMonoBehaviour:\n  m_GameObject: {fileID: 20}\n  target: {fileID: 10}\n  self: {fileID: 20}\n  m_Script: {fileID: 11500000, guid: abc, type: 3}\n\
+--- !u!114 &12\nMonoBehaviour:\n  m_GameObject: {fileID: 10}\n  follow: {fileID: 20}\n";
+
+    const DST: &str = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n\
+--- !u!1 &100\nGameObject:\n  m_Component:\n  - component: {fileID: 101}\n  m_Name: Holder\n\
+--- !u!4 &101\nTransform:\n  m_GameObject: {fileID: 100}\n  m_Children: []\n  m_Father: {fileID: 0}\n";
+
+    #[test]
+    fn test_split_raw_blocks_roundtrip() {
+        let (preamble, blocks) = split_raw_blocks(SRC);
+        assert!(preamble.starts_with("%YAML"));
+        assert_eq!(blocks.len(), 6);
+        let joined: String = std::iter::once(preamble).chain(blocks.into_iter().map(|b| b.text)).collect();
+        assert_eq!(joined, SRC);
+    }
+
+    #[test]
+    fn test_move_gameobject_under_parent() {
+        let config = ComponentConfig::default();
+        let t = move_gameobject(SRC, "20", DST, Some("100"), false, &config).unwrap();
+
+        let moved: Vec<&str> = t.file_id_map.iter().map(|(old, _)| old.as_str()).collect();
+        assert_eq!(moved, vec!["20", "21", "22"]);
+        let new_of = |old: &str| t.file_id_map.iter().find(|(o, _)| o == old).unwrap().1.clone();
+
+        // Destination: parent lists the new transform, moved blocks use new ids
+        assert!(t.dst_content.contains(&format!("  m_Children:\n  - {{fileID: {}}}\n", new_of("21"))));
+        assert!(t.dst_content.contains(&format!("--- !u!114 &{}\n", new_of("22"))));
+        assert!(t.dst_content.contains(&format!("self: {{fileID: {}}}", new_of("20"))));
+        assert!(t.dst_content.contains("m_Father: {fileID: 101}"));
+        assert!(t.dst_content.contains("m_Script: {fileID: 11500000, guid: abc, type: 3}"));
+        assert!(t.dst_content.contains("target: {fileID: 0}"));
+
+        // Source: subtree removed and detached
+        assert!(!t.src_content.contains("m_Name: Mover"));
+        assert!(t.src_content.contains("m_Children: []"));
+        assert!(t.src_content.contains("follow: {fileID: 0}"));
+
+        let outgoing: Vec<_> = t.broken_references.iter().filter(|b| b.direction == "outgoing").collect();
+        assert_eq!(outgoing.len(), 1);
+        assert_eq!(outgoing[0].property, "target");
+        assert_eq!(outgoing[0].target_file_id, "10");
+        let incoming: Vec<_> = t.broken_references.iter().filter(|b| b.direction == "incoming").collect();
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0].from_file_id, "12");
+    }
+
+    #[test]
+    fn test_move_gameobject_errors() {
+        let config = ComponentConfig::default();
+        assert!(move_gameobject(SRC, "999", DST, None, false, &config).is_err());
+        assert!(move_gameobject(SRC, "20", DST, Some("555"), false, &config).is_err());
+        assert!(move_gameobject(SRC, "20", DST, None, true, &config).is_err());
+    }
+
+    #[test]
+    fn test_fresh_file_id_avoids_taken() {
+        let mut taken = HashSet::new();
+        let a = fresh_file_id("5", 0, &mut taken);
+        let b = fresh_file_id("5", 0, &mut taken);
+        assert_ne!(a, b);
+    }
+}
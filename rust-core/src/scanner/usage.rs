@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use crate::common::{Component, MissingComponentIssue, ScriptComponentUsage};
+
+/// Abstract component types satisfied by any concrete type with the given suffix.
+const BASE_TYPE_SUFFIXES: &[(&str, &str)] = &[
+    ("Collider", "Collider"),
+    ("Collider2D", "Collider2D"),
+    ("Renderer", "Renderer"),
+    ("Joint", "Joint"),
+    ("Joint2D", "Joint2D"),
+    ("Effector2D", "Effector2D"),
+];
+
+/// Types every GameObject (or every lookup) trivially satisfies.
+const ALWAYS_PRESENT: &[&str] = &["Transform", "Component", "Behaviour", "MonoBehaviour", "Object", "GameObject"];
+
+/// Built-in component types that can be checked even if no file in the project uses them yet.
+const BUILTIN_COMPONENTS: &[&str] = &[
+    "Rigidbody", "Rigidbody2D", "CharacterController", "BoxCollider", "SphereCollider", "CapsuleCollider",
+    "MeshCollider", "BoxCollider2D", "CircleCollider2D", "PolygonCollider2D", "CapsuleCollider2D",
+    "MeshFilter", "MeshRenderer", "SkinnedMeshRenderer", "SpriteRenderer", "LineRenderer", "TrailRenderer",
+    "Animator", "Animation", "AudioSource", "AudioListener", "Camera", "Light", "ParticleSystem",
+    "NavMeshAgent", "NavMeshObstacle", "Canvas", "CanvasGroup", "CanvasRenderer", "RectTransform",
+    "Image", "RawImage", "Text", "Button", "Toggle", "Slider", "ScrollRect", "LayoutElement",
+    "LODGroup", "Terrain", "TerrainCollider", "VideoPlayer", "PlayableDirector",
+];
+
+/// Whether a GameObject's components satisfy an expected type. Expectations that cannot be
+/// checked statically (interfaces, generic parameters, types unknown to the project) count as met.
+pub fn satisfies(expected: &str, present: &[String], known_types: &dyn Fn(&str) -> bool) -> bool {
+    let short = expected.rsplit('.').next().unwrap_or(expected);
+    if ALWAYS_PRESENT.contains(&short) || present.iter().any(|p| p == short) {
+        return true;
+    }
+    if let Some((_, suffix)) = BASE_TYPE_SUFFIXES.iter().find(|(base, _)| *base == short) {
+        // Collider must not be satisfied by Collider2D and vice versa
+        return present.iter().any(|p| p.ends_with(suffix) && (short.ends_with("2D") || !p.ends_with("2D")));
+    }
+    let looks_generic = short.len() == 1 || (short.starts_with('I') && short.chars().nth(1).is_some_and(|c| c.is_ascii_uppercase()));
+    looks_generic || !(BUILTIN_COMPONENTS.contains(&short) || known_types(short))
+}
+
+/// (GameObject id, name, components) of one non-stripped GameObject.
+pub type ObjectComponents = (String, String, Vec<Component>);
+
+/// Check every GameObject in a file against the expectations of the scripts it carries.
+pub fn check_objects(
+    file: &str,
+    objects: &[ObjectComponents],
+    scripts: &HashMap<String, ScriptComponentUsage>,
+    known_types: &dyn Fn(&str) -> bool,
+) -> Vec<MissingComponentIssue> {
+    let mut issues = Vec::new();
+    for (go_id, go_name, components) in objects {
+        let present: Vec<String> = components.iter()
+            .flat_map(|c| [Some(c.type_name.clone()), c.script_name.clone()])
+            .flatten()
+            .collect();
+        for comp in components {
+            let Some(usage) = comp.script_guid.as_ref().and_then(|g| scripts.get(g)) else { continue };
+            for expectation in &usage.expects {
+                if satisfies(&expectation.type_name, &present, known_types) {
+                    continue;
+                }
+                issues.push(MissingComponentIssue {
+                    file: file.to_string(),
+                    game_object_id: go_id.clone(),
+                    game_object_name: go_name.clone(),
+                    script_path: usage.script_path.clone(),
+                    missing_type: expectation.type_name.clone(),
+                    source: expectation.source.clone(),
+                    severity: if expectation.required { "error" } else { "warning" }.to_string(),
+                });
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ComponentExpectation;
+
+    fn comp(type_name: &str, script: Option<(&str, &str)>) -> Component {
+        Component {
+            type_name: type_name.to_string(),
+            class_id: 0,
+            file_id: "1".to_string(),
+            script_path: None,
+            script_guid: script.map(|(g, _)| g.to_string()),
+            script_name: script.map(|(_, n)| n.to_string()),
+            editor_class_identifier: None,
+            enabled: None,
+            properties: None,
+            aliases_applied: None,
+        }
+    }
+
+    #[test]
+    fn test_satisfies_base_types() {
+        let known = |_: &str| true;
+        let present = vec!["Transform".to_string(), "BoxCollider".to_string()];
+        assert!(satisfies("Collider", &present, &known));
+        assert!(!satisfies("Collider2D", &present, &known));
+        assert!(!satisfies("Rigidbody", &present, &known));
+        assert!(satisfies("UnityEngine.Transform", &present, &known));
+        assert!(satisfies("IDamageable", &present, &known));
+        assert!(!satisfies("Rigidbody", &present, &|_: &str| false), "built-in types are always checkable");
+        assert!(satisfies("SomeThirdPartyThing", &present, &|_: &str| false));
+    }
+
+    #[test]
+    fn test_check_objects_reports_missing() {
+        let mut scripts = HashMap::new();
+        scripts.insert("g1".to_string(), ScriptComponentUsage {
+            script_path: "Assets/Mover.cs".to_string(),
+            script_guid: Some("g1".to_string()),
+            expects: vec![
+                ComponentExpectation { type_name: "Rigidbody".into(), source: "RequireComponent".into(), required: true },
+                ComponentExpectation { type_name: "Animator".into(), source: "GetComponent".into(), required: false },
+            ],
+            adds: vec![],
+        });
+        let objects = vec![
+            ("10".to_string(), "Ok".to_string(), vec![comp("Transform", None), comp("Rigidbody", None), comp("Animator", None), comp("MonoBehaviour", Some(("g1", "Mover")))]),
+            ("20".to_string(), "Bad".to_string(), vec![comp("Transform", None), comp("MonoBehaviour", Some(("g1", "Mover")))]),
+        ];
+        let issues = check_objects("Assets/S.unity", &objects, &scripts, &|_: &str| true);
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|i| i.game_object_id == "20"));
+        assert_eq!(issues[0].severity, "error");
+        assert_eq!(issues[1].severity, "warning");
+    }
+}
@@ -0,0 +1,443 @@
+//! Project-wide full text index over the serialized properties of scenes, prefabs and YAML
+//! assets, so analytic queries (`m_Intensity:[2 TO *] AND type:Light`) don't regex-scan every
+//! file. One document per serialized object; properties live in a JSON field keyed by their
+//! dotted path. The index is a sidecar under `.unity-agentic/search-index/` and is refreshed
+//! incrementally: only files whose mtime or size changed since the last build are re-indexed.
+//! Built-in fields renamed across Unity versions (m_Drag, now m_LinearDamping) are indexed under
+//! both spellings, so a query by either name finds files written by any editor version.
+
+use napi_derive::napi;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as Json};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tantivy::collector::{Count, TopDocs};
+use tantivy::query::QueryParser;
+use tantivy::schema::{
+    Field, IndexRecordOption, JsonObjectOptions, OwnedValue, Schema, TextFieldIndexing, Value, FAST, INDEXED, STORED,
+    STRING, TEXT,
+};
+use tantivy::{Index, IndexWriter, TantivyDocument, Term};
+
+use crate::common;
+use crate::scanner::aliases;
+use crate::scanner::parser::{BlockIndex, UnityYamlParser};
+use crate::scanner::rendering::{field, owner_game_object};
+use crate::scanner::scene_index::source_stamp;
+use crate::scanner::schema::{flatten_block, FieldValue};
+
+/// Sidecar index directory, relative to the project root.
+pub const SEARCH_INDEX_DIR: &str = ".unity-agentic/search-index";
+
+/// Bumped whenever the schema or document layout changes; each version has its own folder.
+const INDEX_VERSION: u32 = 2;
+
+const MANIFEST_FILE: &str = "files.json";
+const WRITER_MEMORY_BYTES: usize = 50_000_000;
+const DEFAULT_LIMIT: usize = 100;
+
+/// Fields with their own schema entry; any other field name in a query is a property path.
+const SCHEMA_FIELDS: &[&str] = &["path", "file_id", "class_id", "type", "game_object_id", "name", "props"];
+
+/// `field:` at the start of a query term
+static QUERY_FIELD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(^|[\s(+\-!])([A-Za-z_][\w.\[\]]*):"#).unwrap()
+});
+
+/// A build of the project index.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SearchIndexBuild {
+    pub success: bool,
+    pub error: Option<String>,
+    pub index_path: String,
+    /// Scenes, prefabs and YAML assets in the project
+    pub files_total: u32,
+    /// Files (re)indexed by this build
+    pub files_updated: u32,
+    /// Files dropped from the index because they no longer exist
+    pub files_removed: u32,
+    /// Serialized objects in the index
+    pub documents: i64,
+}
+
+/// One serialized object matching a query.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PropertySearchHit {
+    /// Project-relative path, forward slashes
+    pub path: String,
+    pub file_id: String,
+    pub class_id: u32,
+    /// Unity type name (e.g. "Light", "MonoBehaviour")
+    #[napi(js_name = "type")]
+    pub type_name: String,
+    /// Owning GameObject (the object itself for GameObjects); unset for assets
+    pub game_object_id: Option<String>,
+    pub game_object_name: Option<String>,
+    pub score: f64,
+    /// Field renames across Unity versions the object was also indexed under, e.g. its m_Drag
+    /// searchable as m_LinearDamping
+    pub aliases_applied: Vec<String>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PropertySearchResult {
+    pub success: bool,
+    pub error: Option<String>,
+    pub query: String,
+    /// All matches; `hits` holds the best `limit` of them
+    pub total_hits: u32,
+    pub hits: Vec<PropertySearchHit>,
+}
+
+/// Indexed files with the (mtime ms, size) they had when indexed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    files: BTreeMap<String, (u64, u64)>,
+}
+
+struct Fields {
+    path: Field,
+    file_id: Field,
+    class_id: Field,
+    type_name: Field,
+    game_object_id: Field,
+    name: Field,
+    props: Field,
+    aliases: Field,
+}
+
+fn schema() -> (Schema, Fields) {
+    let mut builder = Schema::builder();
+    // Raw tokens so values match exactly (GUIDs, "0.5", names with spaces when quoted)
+    let props = JsonObjectOptions::default()
+        .set_indexing_options(TextFieldIndexing::default().set_tokenizer("raw").set_index_option(IndexRecordOption::Basic))
+        .set_fast(Some("raw"))
+        .set_expand_dots_enabled();
+    let fields = Fields {
+        path: builder.add_text_field("path", STRING | STORED),
+        file_id: builder.add_text_field("file_id", STRING | STORED),
+        class_id: builder.add_u64_field("class_id", INDEXED | STORED | FAST),
+        type_name: builder.add_text_field("type", STRING | STORED),
+        game_object_id: builder.add_text_field("game_object_id", STRING | STORED),
+        name: builder.add_text_field("name", TEXT | STORED),
+        props: builder.add_json_field("props", props),
+        aliases: builder.add_text_field("aliases", STORED),
+    };
+    (builder.build(), fields)
+}
+
+fn index_dir(project_root: &Path) -> PathBuf {
+    project_root.join(SEARCH_INDEX_DIR).join(format!("v{}", INDEX_VERSION))
+}
+
+/// A serialized scalar as JSON: numbers stay numeric (for range queries), flow mappings
+/// (`{x: 0, y: 1}`, `{fileID: 0, guid: ..., type: 2}`) become objects.
+fn json_value(text: &str) -> Json {
+    let text = text.trim();
+    if let Ok(n) = text.parse::<f64>() {
+        if let Some(number) = serde_json::Number::from_f64(n) {
+            return Json::Number(number);
+        }
+    }
+    if let Some(inner) = text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+        let members: Option<Map<String, Json>> = inner.split(',')
+            .map(|member| member.split_once(':').map(|(k, v)| (k.trim().to_string(), json_value(v))))
+            .collect();
+        if let Some(members) = members.filter(|m| !m.is_empty()) {
+            return Json::Object(members);
+        }
+    }
+    Json::String(text.to_string())
+}
+
+/// Properties of a block keyed by dotted path; sequence items (`name[]`) collect into arrays
+/// under `name`, so `m_Materials.guid:...` matches any element.
+fn properties(body: &str) -> BTreeMap<String, OwnedValue> {
+    let mut values: BTreeMap<String, Vec<Json>> = BTreeMap::new();
+    for (path, value) in flatten_block(body) {
+        let FieldValue::Scalar(text) = value else { continue };
+        if text.is_empty() {
+            continue;
+        }
+        values.entry(path.replace("[]", "")).or_default().push(json_value(&text));
+    }
+    values.into_iter()
+        .map(|(path, mut items)| {
+            let value = if items.len() == 1 { items.remove(0) } else { Json::Array(items) };
+            (path, OwnedValue::from(value))
+        })
+        .collect()
+}
+
+/// Also index renamed built-in fields under their other spelling (unless the object has both),
+/// returning the renames applied.
+fn add_alias_properties(type_name: &str, properties: &mut BTreeMap<String, OwnedValue>) -> Vec<String> {
+    let roots: HashSet<&str> = properties.keys().map(|path| path.split('.').next().unwrap_or_default()).collect();
+    let mut added = Vec::new();
+    let mut applied = Vec::new();
+    for (path, value) in properties.iter() {
+        let (root, rest) = path.split_once('.').map_or((path.as_str(), None), |(root, rest)| (root, Some(rest)));
+        for (other, alias) in aliases::alternatives(type_name, root) {
+            if roots.contains(other) {
+                continue;
+            }
+            let renamed = rest.map_or(other.to_string(), |rest| format!("{}.{}", other, rest));
+            added.push((renamed, value.clone()));
+            let rename = alias.describe(type_name);
+            if !applied.contains(&rename) {
+                applied.push(rename);
+            }
+        }
+    }
+    properties.extend(added);
+    applied
+}
+
+/// One document per serialized object of a file.
+fn documents(relative: &str, content: &str, fields: &Fields) -> Vec<TantivyDocument> {
+    let index = BlockIndex::new(content);
+    UnityYamlParser::parse_all_blocks(content).iter()
+        .map(|(class_id, file_id, body)| {
+            let mut doc = TantivyDocument::default();
+            doc.add_text(fields.path, relative);
+            doc.add_text(fields.file_id, file_id);
+            doc.add_u64(fields.class_id, *class_id as u64);
+            let type_name = body.lines().next().unwrap_or_default().trim_end_matches(':').trim();
+            doc.add_text(fields.type_name, type_name);
+            let owner = if *class_id == 1 {
+                Some((file_id.clone(), field(body, "m_Name").unwrap_or_default().to_string()))
+            } else {
+                Some(owner_game_object(&index, body)).filter(|(id, _)| id != "0")
+            };
+            if let Some((go_id, name)) = owner {
+                doc.add_text(fields.game_object_id, go_id);
+                doc.add_text(fields.name, name);
+            }
+            let mut props = properties(body);
+            for rename in add_alias_properties(type_name, &mut props) {
+                doc.add_text(fields.aliases, rename);
+            }
+            doc.add_object(fields.props, props);
+            doc
+        })
+        .collect()
+}
+
+fn load_manifest(dir: &Path) -> Manifest {
+    crate::safety::read_to_string(dir.join(MANIFEST_FILE)).ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Scenes, prefabs and YAML assets of the project, as (absolute, project-relative) paths.
+fn project_files(project_root: &Path) -> Vec<(String, String)> {
+    let extensions: Vec<String> = crate::walker::extension_map("yaml").iter()
+        .filter(|ext| !matches!(**ext, ".yaml" | ".yml"))
+        .map(|s| s.to_string())
+        .collect();
+    crate::walker::walk_project_files(project_root.to_string_lossy().to_string(), extensions, None, None)
+        .into_iter()
+        .map(|file| {
+            let relative = Path::new(&file).strip_prefix(project_root)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_else(|_| file.clone());
+            (file, relative)
+        })
+        .collect()
+}
+
+fn open_or_create(dir: &Path) -> Result<Index, String> {
+    crate::safety::check_write(dir).map_err(|e| e.to_string())?;
+    crate::safety::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let directory = tantivy::directory::MmapDirectory::open(dir).map_err(|e| e.to_string())?;
+    Index::open_or_create(directory, schema().0).map_err(|e| format!("Failed to open search index {}: {}", dir.display(), e))
+}
+
+fn build(project_root: &Path) -> Result<SearchIndexBuild, String> {
+    let dir = index_dir(project_root);
+    let index = open_or_create(&dir)?;
+    let (_, fields) = schema();
+    let mut writer: IndexWriter<TantivyDocument> = index.writer(WRITER_MEMORY_BYTES).map_err(|e| e.to_string())?;
+    let mut manifest = load_manifest(&dir);
+
+    let files = project_files(project_root);
+    let mut present = HashSet::new();
+    let mut files_updated = 0;
+    for (file, relative) in &files {
+        present.insert(relative.as_str());
+        let Some(stamp) = source_stamp(Path::new(file)) else { continue };
+        if manifest.files.get(relative) == Some(&stamp) {
+            continue;
+        }
+        let Ok(content) = common::read_unity_file(file) else { continue };
+        writer.delete_term(Term::from_field_text(fields.path, relative));
+        for doc in documents(relative, &content, &fields) {
+            writer.add_document(doc).map_err(|e| e.to_string())?;
+        }
+        manifest.files.insert(relative.clone(), stamp);
+        files_updated += 1;
+    }
+    let removed: Vec<String> = manifest.files.keys().filter(|f| !present.contains(&f[..])).cloned().collect();
+    for relative in &removed {
+        writer.delete_term(Term::from_field_text(fields.path, relative));
+        manifest.files.remove(relative);
+    }
+    writer.commit().map_err(|e| format!("Failed to commit search index: {}", e))?;
+
+    let text = serde_json::to_string(&manifest).map_err(|e| e.to_string())?;
+    common::atomic::write_atomic(dir.join(MANIFEST_FILE), text).map_err(|e| format!("Failed to write search index manifest: {}", e))?;
+    let documents = index.reader().map_err(|e| e.to_string())?.searcher().num_docs();
+    Ok(SearchIndexBuild {
+        success: true,
+        error: None,
+        index_path: dir.to_string_lossy().to_string(),
+        files_total: files.len() as u32,
+        files_updated,
+        files_removed: removed.len() as u32,
+        documents: documents as i64,
+    })
+}
+
+/// Point bare property names at the JSON field: `m_Intensity:[2 TO *]` → `props.m_Intensity:[2 TO *]`.
+fn rewrite_query(query: &str) -> String {
+    QUERY_FIELD_RE.replace_all(query, |caps: &regex::Captures| {
+        let name = &caps[2];
+        let root = name.split('.').next().unwrap_or_default();
+        if SCHEMA_FIELDS.contains(&root) {
+            caps[0].to_string()
+        } else {
+            format!("{}props.{}:", &caps[1], name.replace("[]", ""))
+        }
+    }).into_owned()
+}
+
+fn search(project_root: &Path, query: &str, limit: usize) -> Result<(u32, Vec<PropertySearchHit>), String> {
+    let dir = index_dir(project_root);
+    if !dir.join(MANIFEST_FILE).exists() {
+        build(project_root)?;
+    }
+    let index = open_or_create(&dir)?;
+    let (_, fields) = schema();
+    let mut parser = QueryParser::for_index(&index, vec![fields.name, fields.type_name, fields.props]);
+    parser.set_conjunction_by_default();
+    let parsed = parser.parse_query(&rewrite_query(query)).map_err(|e| format!("Invalid query: {}", e))?;
+
+    let searcher = index.reader().map_err(|e| e.to_string())?.searcher();
+    let (total, top) = searcher.search(&parsed, &(Count, TopDocs::with_limit(limit.max(1))))
+        .map_err(|e| format!("Search failed: {}", e))?;
+    let text = |doc: &TantivyDocument, field: Field| doc.get_first(field).and_then(|v| v.as_str()).map(str::to_string);
+    let mut hits = Vec::new();
+    for (score, address) in top {
+        let doc: TantivyDocument = searcher.doc(address).map_err(|e| e.to_string())?;
+        hits.push(PropertySearchHit {
+            path: text(&doc, fields.path).unwrap_or_default(),
+            file_id: text(&doc, fields.file_id).unwrap_or_default(),
+            class_id: doc.get_first(fields.class_id).and_then(|v| v.as_u64()).unwrap_or_default() as u32,
+            type_name: text(&doc, fields.type_name).unwrap_or_default(),
+            game_object_id: text(&doc, fields.game_object_id),
+            game_object_name: text(&doc, fields.name),
+            score: score as f64,
+            aliases_applied: doc.get_all(fields.aliases).filter_map(|v| v.as_str()).map(str::to_string).collect(),
+        });
+    }
+    Ok((total as u32, hits))
+}
+
+/// Build or refresh the project's property search index under `.unity-agentic/search-index/`.
+/// Only scenes, prefabs and YAML assets changed since the last build are re-indexed, and
+/// deleted ones are dropped.
+#[napi]
+pub fn build_search_index(project_root: String) -> SearchIndexBuild {
+    let _call = crate::telemetry::call("build_search_index");
+    build(Path::new(&project_root)).unwrap_or_else(|error| SearchIndexBuild {
+        success: false,
+        error: Some(error),
+        index_path: index_dir(Path::new(&project_root)).to_string_lossy().to_string(),
+        files_total: 0,
+        files_updated: 0,
+        files_removed: 0,
+        documents: 0,
+    })
+}
+
+/// Serialized objects matching `query` (best `limit` first, default 100). Terms are ANDed
+/// unless joined with `OR`; fields are `type`, `class_id`, `name` (owning GameObject),
+/// `path`, `file_id`, `game_object_id`, and any property path, e.g.
+/// `m_Intensity:[2 TO *] AND type:Light`, `m_Script.guid:<guid>`, `m_LocalPosition.y:[* TO 0}`.
+/// Builds the index on first use; call `build_search_index` to pick up later edits.
+#[napi]
+pub fn search_project_index(project_root: String, query: String, limit: Option<u32>) -> PropertySearchResult {
+    let _call = crate::telemetry::call("search_project_index");
+    let limit = limit.map_or(DEFAULT_LIMIT, |l| l as usize);
+    match search(Path::new(&project_root), &query, limit) {
+        Ok((total_hits, hits)) => PropertySearchResult { success: true, error: None, query, total_hits, hits },
+        Err(error) => PropertySearchResult { success: false, error: Some(error), query, total_hits: 0, hits: Vec::new() },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn light(id: &str, name: &str, intensity: &str) -> String {
+        format!(
+            "--- !u!1 &{id}0\nGameObject:\n  m_Component:\n  - component: {{fileID: {id}1}}\n  m_Name: {name}\n\
+--- !u!108 &{id}1\nLight:\n  m_GameObject: {{fileID: {id}0}}\n  m_Intensity: {intensity}\n  m_Color: {{r: 1, g: 0.5, b: 0, a: 1}}\n"
+        )
+    }
+
+    #[test]
+    fn test_rewrite_query() {
+        assert_eq!(rewrite_query("m_Intensity:[2 TO *] AND type:Light"), "props.m_Intensity:[2 TO *] AND type:Light");
+        assert_eq!(rewrite_query("(m_Color.g:0.5 OR name:Lamp) -m_Materials[].guid:abc"),
+            "(props.m_Color.g:0.5 OR name:Lamp) -props.m_Materials.guid:abc");
+    }
+
+    #[test]
+    fn test_build_and_search() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("Assets/Scenes")).unwrap();
+        let scene = root.join("Assets/Scenes/Main.unity");
+        fs::write(&scene, format!("%YAML 1.1\n{}{}", light("1", "Sun", "3.5"), light("2", "Lamp", "1"))).unwrap();
+        fs::write(root.join("Assets/Bright.prefab"), format!("%YAML 1.1\n{}", light("3", "Spot", "2"))).unwrap();
+
+        let first = build_search_index(root.to_string_lossy().to_string());
+        assert!(first.success, "{:?}", first.error);
+        assert_eq!((first.files_total, first.files_updated, first.documents), (2, 2, 6));
+
+        let result = search_project_index(root.to_string_lossy().to_string(), "m_Intensity:[2 TO *] AND type:Light".to_string(), None);
+        assert!(result.success, "{:?}", result.error);
+        let mut names: Vec<&str> = result.hits.iter().filter_map(|h| h.game_object_name.as_deref()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Spot", "Sun"]);
+        assert_eq!(result.hits[0].class_id, 108);
+
+        let color = search_project_index(root.to_string_lossy().to_string(), "m_Color.g:0.5 name:Lamp".to_string(), None);
+        assert_eq!((color.total_hits, color.hits[0].path.as_str()), (1, "Assets/Scenes/Main.unity"));
+
+        // Only the edited file is re-indexed; deleted files drop out
+        fs::write(&scene, format!("%YAML 1.1\n{}", light("1", "Sun", "0.5"))).unwrap();
+        fs::remove_file(root.join("Assets/Bright.prefab")).unwrap();
+        let second = build_search_index(root.to_string_lossy().to_string());
+        assert_eq!((second.files_updated, second.files_removed, second.documents), (1, 1, 2));
+        let none = search_project_index(root.to_string_lossy().to_string(), "m_Intensity:[2 TO *]".to_string(), None);
+        assert_eq!(none.total_hits, 0);
+
+        // A legacy scene's m_Drag answers a query by the modern name, noting the rename
+        fs::write(root.join("Assets/Old.prefab"), "%YAML 1.1\n--- !u!54 &5\nRigidbody:\n  m_Drag: 0.5\n").unwrap();
+        build_search_index(root.to_string_lossy().to_string());
+        let damping = search_project_index(root.to_string_lossy().to_string(), "m_LinearDamping:0.5".to_string(), None);
+        assert_eq!((damping.total_hits, damping.hits[0].path.as_str()), (1, "Assets/Old.prefab"));
+        assert_eq!(damping.hits[0].aliases_applied, vec!["Rigidbody.m_LinearDamping (named m_Drag before Unity 6000.0)"]);
+
+        let invalid = search_project_index(root.to_string_lossy().to_string(), "m_Intensity:[2 TO".to_string(), None);
+        assert!(!invalid.success && invalid.error.unwrap().starts_with("Invalid query"));
+    }
+}
@@ -0,0 +1,285 @@
+//! `#include` graph of a project's shader sources (.shader, .compute, .cginc, .hlsl, ...):
+//! which files include which, include targets that do not resolve, and include cycles.
+
+use napi_derive::napi;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::common;
+use crate::csharp::deps::cycles;
+use crate::csharp::strip_block_comments;
+use crate::walker::{self, NapiWalkOptions};
+
+/// `#include "path"`, `#include <path>` and Unity's `#include_with_pragmas "path"`
+static INCLUDE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*#\s*include(?:_with_pragmas)?\s*[<"]([^">]+)[">]"#).unwrap()
+});
+
+const SHADER_EXTENSIONS: [&str; 7] = [".shader", ".compute", ".cginc", ".hlsl", ".hlslinc", ".glslinc", ".raytrace"];
+
+/// Include files that ship with the Unity editor (CGIncludes) rather than the project.
+const BUILTIN_INCLUDES: [&str; 12] = [
+    "HLSLSupport.cginc", "Lighting.cginc", "AutoLight.cginc", "TerrainEngine.cginc",
+    "TerrainSplatmapCommon.cginc", "Tessellation.cginc", "GLSLSupport.glslinc", "UnityCG.glslinc",
+    "SpeedTreeCommon.cginc", "SpeedTreeVertex.cginc", "SpeedTreeBillboardCommon.cginc", "SpeedTreeWind.cginc",
+];
+
+/// One `#include` directive.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderInclude {
+    /// Target as written
+    pub target: String,
+    pub line: u32,
+    /// Project-relative path of the included file
+    pub resolved: Option<String>,
+    /// "resolved", "builtin" (Unity's own CGIncludes), "unverified" (a package that is not
+    /// present locally) or "missing"
+    pub status: String,
+}
+
+/// A shader source file and its include edges.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderSourceFile {
+    /// Path relative to the project root
+    pub path: String,
+    pub includes: Vec<ShaderInclude>,
+    /// Files that include this one
+    pub included_by: Vec<String>,
+}
+
+/// An include target that resolves to no file.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingShaderInclude {
+    pub file: String,
+    pub line: u32,
+    pub target: String,
+}
+
+/// Include graph of a project's shader sources.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderIncludeReport {
+    pub project_root: String,
+    pub files: Vec<ShaderSourceFile>,
+    pub missing: Vec<MissingShaderInclude>,
+    /// Groups of files that include each other (directly or transitively), each sorted by path
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// Build the `#include` graph across the project's shader sources (Assets/ and embedded or local
+/// packages), flagging include targets that resolve to no file and include cycles.
+///
+/// Targets starting with `Assets/` or `Packages/` resolve from the project root (packages also
+/// through Library/PackageCache), anything else relative to the including file.
+#[napi]
+pub fn analyze_shader_includes(project_root: String) -> ShaderIncludeReport {
+    let _call = crate::telemetry::call("analyze_shader_includes");
+    let root = Path::new(&project_root);
+    let options = NapiWalkOptions { include_packages: Some(true), follow_symlinks: None };
+    let extensions = SHADER_EXTENSIONS.iter().map(|e| e.to_string()).collect();
+    let sources: Vec<(String, String)> = walker::walk_project_files(project_root.clone(), extensions, None, Some(options))
+        .into_iter()
+        .filter_map(|file| {
+            let content = common::read_unity_file(&file).ok()?;
+            let relative = Path::new(&file).strip_prefix(root).map(Path::to_path_buf).unwrap_or_else(|_| file.into());
+            Some((relative.to_string_lossy().replace('\\', "/"), content))
+        })
+        .collect();
+    build_report(project_root.clone(), &sources, |path| resolve_on_disk(root, path))
+}
+
+/// Include directives of one source: (line, target). Commented-out directives are skipped.
+pub fn parse_includes(content: &str) -> Vec<(u32, String)> {
+    strip_block_comments(content)
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let target = INCLUDE_RE.captures(line)?.get(1)?.as_str().trim().replace('\\', "/");
+            Some((i as u32 + 1, target))
+        })
+        .collect()
+}
+
+/// Project-relative path of a target included from `from`, with `.` and `..` folded;
+/// None when it climbs above the project root.
+fn target_path(from: &str, target: &str) -> Option<String> {
+    let joined = if target.starts_with("Assets/") || target.starts_with("Packages/") {
+        target.to_string()
+    } else {
+        match from.rsplit_once('/') {
+            Some((dir, _)) => format!("{}/{}", dir, target),
+            None => target.to_string(),
+        }
+    };
+    let mut parts: Vec<&str> = Vec::new();
+    for part in joined.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// How a target that is not among the walked sources resolves on disk.
+enum DiskLookup {
+    /// Found (project-relative path, for packages possibly under Library/PackageCache)
+    Found(String),
+    /// Under a package that is neither embedded nor in the package cache
+    UnknownPackage,
+    NotFound,
+}
+
+fn resolve_on_disk(root: &Path, path: &str) -> DiskLookup {
+    if root.join(path).is_file() {
+        return DiskLookup::Found(path.to_string());
+    }
+    let Some(rest) = path.strip_prefix("Packages/") else { return DiskLookup::NotFound };
+    let (package, inner) = rest.split_once('/').unwrap_or((rest, ""));
+    if root.join("Packages").join(package).is_dir() {
+        return DiskLookup::NotFound;
+    }
+    let cache = root.join("Library").join("PackageCache");
+    let cached = crate::safety::read_dir(&cache).ok().into_iter().flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .find(|name| name == package || name.split('@').next() == Some(package));
+    match cached {
+        Some(dir) if cache.join(&dir).join(inner).is_file() => {
+            DiskLookup::Found(format!("Library/PackageCache/{}/{}", dir, inner))
+        }
+        Some(_) => DiskLookup::NotFound,
+        None => DiskLookup::UnknownPackage,
+    }
+}
+
+fn is_builtin(target: &str) -> bool {
+    !target.contains('/')
+        && (BUILTIN_INCLUDES.contains(&target) || (target.starts_with("Unity") && target.ends_with(".cginc")))
+}
+
+fn build_report(
+    project_root: String,
+    sources: &[(String, String)],
+    on_disk: impl Fn(&str) -> DiskLookup,
+) -> ShaderIncludeReport {
+    let known: BTreeSet<&str> = sources.iter().map(|(path, _)| path.as_str()).collect();
+    let mut files: BTreeMap<String, ShaderSourceFile> = BTreeMap::new();
+    let mut edges: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut missing = Vec::new();
+
+    for (path, content) in sources {
+        let mut includes = Vec::new();
+        for (line, target) in parse_includes(content) {
+            let (resolved, status) = match target_path(path, &target) {
+                Some(candidate) if known.contains(candidate.as_str()) => (Some(candidate), "resolved"),
+                Some(candidate) => match on_disk(&candidate) {
+                    DiskLookup::Found(found) => (Some(found), "resolved"),
+                    DiskLookup::UnknownPackage => (None, "unverified"),
+                    DiskLookup::NotFound if is_builtin(&target) => (None, "builtin"),
+                    DiskLookup::NotFound => (None, "missing"),
+                },
+                None => (None, "missing"),
+            };
+            if status == "missing" {
+                missing.push(MissingShaderInclude { file: path.clone(), line, target: target.clone() });
+            }
+            if let Some(resolved) = &resolved {
+                edges.entry(path.clone()).or_default().insert(resolved.clone());
+            }
+            includes.push(ShaderInclude { target, line, resolved, status: status.to_string() });
+        }
+        files.insert(path.clone(), ShaderSourceFile { path: path.clone(), includes, included_by: Vec::new() });
+    }
+
+    for (from, targets) in &edges {
+        for target in targets {
+            if let Some(file) = files.get_mut(target) {
+                file.included_by.push(from.clone());
+            }
+        }
+    }
+    // A file including itself is a cycle of one
+    let mut cycles_found: Vec<Vec<String>> = edges.iter()
+        .filter(|(from, targets)| targets.contains(*from))
+        .map(|(from, _)| vec![from.clone()])
+        .collect();
+    cycles_found.extend(cycles(&edges));
+
+    ShaderIncludeReport {
+        project_root,
+        files: files.into_values().collect(),
+        missing,
+        cycles: cycles_found,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parse_includes() {
+        let content = "#include \"Common.hlsl\"\n  # include <Lib\\Noise.cginc>\n// #include \"Old.cginc\"\n/* #include \"Gone.cginc\"\n*/\n#include_with_pragmas \"Packages/com.unity.render-pipelines.core/ShaderLibrary/Common.hlsl\"\n#pragma vertex vert\n";
+        assert_eq!(parse_includes(content), vec![
+            (1, "Common.hlsl".to_string()),
+            (2, "Lib/Noise.cginc".to_string()),
+            (6, "Packages/com.unity.render-pipelines.core/ShaderLibrary/Common.hlsl".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_target_path() {
+        assert_eq!(target_path("Assets/Shaders/Water.shader", "Lib/Waves.hlsl").as_deref(), Some("Assets/Shaders/Lib/Waves.hlsl"));
+        assert_eq!(target_path("Assets/Shaders/Lib/Waves.hlsl", "../Common.hlsl").as_deref(), Some("Assets/Shaders/Common.hlsl"));
+        assert_eq!(target_path("Assets/Shaders/Water.shader", "./Common.hlsl").as_deref(), Some("Assets/Shaders/Common.hlsl"));
+        assert_eq!(target_path("Assets/Shaders/Water.shader", "Assets/Lib/A.hlsl").as_deref(), Some("Assets/Lib/A.hlsl"));
+        assert_eq!(target_path("Assets/A.hlsl", "../../B.hlsl"), None);
+    }
+
+    #[test]
+    fn test_analyze_shader_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |rel: &str, content: &str| {
+            let path = root.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("Assets/Shaders/Water.shader", "Shader \"Water\" {\nCGINCLUDE\n#include \"UnityCG.cginc\"\n#include \"Lib/Waves.hlsl\"\n#include \"Lib/Foam.hlsl\"\nENDCG\n}\n");
+        write("Assets/Shaders/Lib/Waves.hlsl", "#include \"../Common.hlsl\"\n#include \"Packages/com.acme.noise/Noise.hlsl\"\n#include \"Packages/com.unity.render-pipelines.core/ShaderLibrary/Common.hlsl\"\n");
+        write("Assets/Shaders/Common.hlsl", "#include \"Lib/Waves.hlsl\"\n#include \"Common.hlsl\"\n");
+        write("Assets/Shaders/Lib/Data.inc", "");
+        write("Assets/Shaders/Lib/Uses.hlsl", "#include \"Data.inc\"\n");
+        write("Library/PackageCache/com.acme.noise@1.2.0/Noise.hlsl", "");
+
+        let report = analyze_shader_includes(root.to_string_lossy().to_string());
+        let paths: Vec<&str> = report.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["Assets/Shaders/Common.hlsl", "Assets/Shaders/Lib/Uses.hlsl", "Assets/Shaders/Lib/Waves.hlsl", "Assets/Shaders/Water.shader"]);
+
+        let water = &report.files[3];
+        let statuses: Vec<&str> = water.includes.iter().map(|i| i.status.as_str()).collect();
+        assert_eq!(statuses, vec!["builtin", "resolved", "missing"]);
+        assert_eq!(report.missing, vec![MissingShaderInclude { file: "Assets/Shaders/Water.shader".to_string(), line: 5, target: "Lib/Foam.hlsl".to_string() }]);
+
+        let waves = &report.files[2];
+        assert_eq!(waves.includes[1].resolved.as_deref(), Some("Library/PackageCache/com.acme.noise@1.2.0/Noise.hlsl"));
+        assert_eq!(waves.includes[2].status, "unverified");
+        assert_eq!(waves.included_by, vec!["Assets/Shaders/Common.hlsl", "Assets/Shaders/Water.shader"]);
+        assert_eq!(report.files[1].includes[0].resolved.as_deref(), Some("Assets/Shaders/Lib/Data.inc"));
+
+        assert_eq!(report.cycles, vec![
+            vec!["Assets/Shaders/Common.hlsl".to_string()],
+            vec!["Assets/Shaders/Common.hlsl".to_string(), "Assets/Shaders/Lib/Waves.hlsl".to_string()],
+        ]);
+    }
+}
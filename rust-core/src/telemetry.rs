@@ -0,0 +1,189 @@
+//! Diagnostics for slow-call reports: tracing spans around napi entry points and parsing
+//! phases, logged to stderr at a napi-settable level, plus optional per-call phase timings
+//! (io / parse / regex) emitted as one JSON line per call.
+
+use napi_derive::napi;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, Registry};
+
+use crate::common::CallTiming;
+
+/// Timed phase of a call.
+#[derive(Clone, Copy, Debug)]
+pub enum Phase {
+    /// Reading files from disk
+    Io,
+    /// Splitting Unity YAML into blocks and GameObjects
+    Parse,
+    /// Regex-driven component and property extraction
+    Regex,
+}
+
+impl Phase {
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Io => "io",
+            Phase::Parse => "parse",
+            Phase::Regex => "regex",
+        }
+    }
+}
+
+static TIMING_ENABLED: AtomicBool = AtomicBool::new(false);
+static PHASE_NANOS: [AtomicU64; 3] = [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+static LOG_FILTER: OnceLock<Option<reload::Handle<LevelFilter, Registry>>> = OnceLock::new();
+
+// napi calls run on the JS thread, so call nesting and the last timing are tracked per thread;
+// phase time from rayon workers is pooled in PHASE_NANOS.
+thread_local! {
+    static CALL_DEPTH: Cell<u32> = const { Cell::new(0) };
+    static IN_PHASE: Cell<bool> = const { Cell::new(false) };
+    static LAST_TIMING: RefCell<Option<CallTiming>> = const { RefCell::new(None) };
+}
+
+/// Span (and, when timing is enabled, phase accounting) for one napi call. Every napi entry
+/// point opens one. Nested calls (e.g. `inspect` → `inspect_from_content`, or an entry point
+/// reused on a rayon worker) are folded into the outermost one, which alone starts the
+/// call's read budget and phase totals.
+pub struct CallGuard {
+    name: &'static str,
+    start: Instant,
+    outermost: bool,
+    _span: tracing::span::EnteredSpan,
+}
+
+pub fn call(name: &'static str) -> CallGuard {
+    let span = tracing::info_span!("call", name).entered();
+    // Rayon workers only ever run inside a call opened on the JS thread
+    let outermost = CALL_DEPTH.with(|d| d.replace(d.get() + 1)) == 0 && rayon::current_thread_index().is_none();
+    if outermost {
+        crate::safety::begin_call();
+        for nanos in &PHASE_NANOS {
+            nanos.store(0, Ordering::Relaxed);
+        }
+    }
+    CallGuard { name, start: Instant::now(), outermost, _span: span }
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        CALL_DEPTH.with(|d| d.set(d.get() - 1));
+        if !self.outermost || !TIMING_ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let ms = |phase: Phase| PHASE_NANOS[phase as usize].load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let timing = CallTiming {
+            call: self.name.to_string(),
+            total_ms: self.start.elapsed().as_secs_f64() * 1000.0,
+            io_ms: ms(Phase::Io),
+            parse_ms: ms(Phase::Parse),
+            regex_ms: ms(Phase::Regex),
+        };
+        if let Ok(line) = serde_json::to_string(&serde_json::json!({ "timing": timing })) {
+            eprintln!("{}", line);
+        }
+        LAST_TIMING.with(|last| *last.borrow_mut() = Some(timing));
+    }
+}
+
+/// Times one phase of the current call until dropped.
+pub struct PhaseGuard {
+    timed: Option<(Phase, Instant, tracing::span::EnteredSpan)>,
+}
+
+/// Start a timed phase. Time is summed across worker threads; a phase nested in another
+/// phase on the same thread counts only toward the outer one.
+pub fn phase(phase: Phase) -> PhaseGuard {
+    if IN_PHASE.with(|p| p.replace(true)) {
+        return PhaseGuard { timed: None };
+    }
+    let span = tracing::debug_span!("phase", name = phase.name()).entered();
+    PhaseGuard { timed: Some((phase, Instant::now(), span)) }
+}
+
+impl Drop for PhaseGuard {
+    fn drop(&mut self) {
+        if let Some((phase, start, _)) = self.timed.take() {
+            PHASE_NANOS[phase as usize].fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+            IN_PHASE.with(|p| p.set(false));
+        }
+    }
+}
+
+/// Install the stderr subscriber on first use. None when another global subscriber exists.
+fn log_filter() -> Option<&'static reload::Handle<LevelFilter, Registry>> {
+    LOG_FILTER
+        .get_or_init(|| {
+            let (filter, handle) = reload::Layer::new(LevelFilter::OFF);
+            let subscriber = tracing_subscriber::registry()
+                .with(filter)
+                .with(fmt::layer().with_writer(std::io::stderr).with_span_events(fmt::format::FmtSpan::CLOSE));
+            tracing::subscriber::set_global_default(subscriber).ok().map(|_| handle)
+        })
+        .as_ref()
+}
+
+/// Set the stderr log level: "off", "error", "warn", "info" (one line per call with its
+/// duration), "debug" (plus parsing phases) or "trace". Returns false for an unknown level or
+/// when the host process already installed its own tracing subscriber.
+#[napi]
+pub fn set_log_level(level: String) -> bool {
+    let Ok(level) = level.parse::<LevelFilter>() else {
+        return false;
+    };
+    log_filter().is_some_and(|handle| handle.reload(level).is_ok())
+}
+
+/// Enable or disable per-call phase timings. While enabled, every napi call that parses Unity
+/// files writes `{"timing": {...}}` as one JSON line to stderr.
+#[napi]
+pub fn set_call_timing(enabled: bool) {
+    TIMING_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Phase timings of the most recent call (on this thread) made while timing was enabled.
+#[napi]
+pub fn get_last_call_timing() -> Option<CallTiming> {
+    LAST_TIMING.with(|last| last.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_timing_folds_nested_calls_and_phases() {
+        set_call_timing(true);
+        {
+            let _outer = call("outer");
+            let _inner = call("inner");
+            let _parse = phase(Phase::Parse);
+            let _regex = phase(Phase::Regex);
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        set_call_timing(false);
+        let timing = get_last_call_timing().unwrap();
+        assert_eq!(timing.call, "outer");
+        assert!(timing.parse_ms >= 2.0);
+        assert!(timing.total_ms >= timing.parse_ms);
+    }
+
+    #[test]
+    fn test_only_the_entry_call_is_outermost() {
+        let outer = call("outer");
+        assert!(outer.outermost);
+        assert!(!call("walk_project_files").outermost, "nested entry points keep the caller's budget");
+        let on_worker = rayon::scope(|_| call("walk_project_files").outermost);
+        assert!(!on_worker, "workers keep the caller's budget");
+    }
+
+    #[test]
+    fn test_set_log_level_rejects_unknown_level() {
+        assert!(!set_log_level("loud".to_string()));
+    }
+}
@@ -0,0 +1,232 @@
+//! Contents of `.unitypackage` archives without extracting them. A package is a gzipped tar
+//! with one folder per asset GUID holding `asset` (absent for folders), `asset.meta`,
+//! `pathname` (the project path to import to) and optionally `preview.png`.
+
+use flate2::read::GzDecoder;
+use napi_derive::napi;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// One asset or folder in a package.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct UnityPackageEntry {
+    pub guid: String,
+    /// Project path the asset imports to (e.g. "Assets/Plugins/Tool/Tool.dll")
+    pub pathname: String,
+    /// Uncompressed size of the asset; unset for folders
+    pub size: Option<i64>,
+    pub is_folder: bool,
+    pub has_meta: bool,
+    pub has_preview: bool,
+    /// Against the project: "new", "update" (same GUID at the same path), "guid_conflict"
+    /// (the GUID belongs to another project asset) or "path_conflict" (another asset exists at
+    /// the path). Unset when no project root is given.
+    pub status: Option<String>,
+    /// Project path currently holding this GUID, when it differs from `pathname`
+    pub existing_path: Option<String>,
+}
+
+#[napi(object)]
+pub struct UnityPackageReport {
+    pub success: bool,
+    pub error: Option<String>,
+    pub path: String,
+    /// Sorted by pathname
+    pub entries: Vec<UnityPackageEntry>,
+    pub asset_count: u32,
+    pub folder_count: u32,
+    /// Uncompressed size of all assets
+    pub total_bytes: i64,
+    /// Entries with a GUID or path conflict
+    pub collision_count: u32,
+}
+
+impl UnityPackageReport {
+    fn failed(path: &str, error: String) -> Self {
+        UnityPackageReport {
+            success: false,
+            error: Some(error),
+            path: path.to_string(),
+            entries: Vec::new(),
+            asset_count: 0,
+            folder_count: 0,
+            total_bytes: 0,
+            collision_count: 0,
+        }
+    }
+}
+
+fn is_guid(name: &str) -> bool {
+    name.len() == 32 && name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Entries of a package archive, keyed by GUID. Only the small `pathname` files are read.
+fn read_entries(path: &Path) -> Result<BTreeMap<String, UnityPackageEntry>, String> {
+    crate::safety::check_read(path).map_err(|e| e.to_string())?;
+    let file = File::open(path).map_err(|e| format!("Cannot open {}: {}", path.display(), e))?;
+    crate::safety::charge(file.metadata().map_or(0, |m| m.len())).map_err(|e| e.to_string())?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let invalid = |e: std::io::Error| format!("Not a valid .unitypackage ({}): {}", path.display(), e);
+
+    let mut entries: BTreeMap<String, UnityPackageEntry> = BTreeMap::new();
+    for entry in archive.entries().map_err(invalid)? {
+        let mut entry = entry.map_err(invalid)?;
+        let entry_path = entry.path().map_err(invalid)?.to_string_lossy().to_string();
+        let mut parts = entry_path.trim_start_matches("./").splitn(2, '/');
+        let (Some(guid), Some(name)) = (parts.next(), parts.next()) else { continue };
+        if !is_guid(guid) {
+            continue;
+        }
+        let record = entries.entry(guid.to_string()).or_insert_with(|| UnityPackageEntry {
+            guid: guid.to_string(),
+            ..Default::default()
+        });
+        match name {
+            "asset" => record.size = Some(entry.header().size().map_err(invalid)? as i64),
+            "asset.meta" => record.has_meta = true,
+            "preview.png" => record.has_preview = true,
+            "pathname" => {
+                let mut text = String::new();
+                entry.read_to_string(&mut text).map_err(invalid)?;
+                // Older exporters append a second line ("00"); the path is the first
+                record.pathname = text.lines().next().unwrap_or_default().trim().to_string();
+            }
+            _ => {}
+        }
+    }
+    if entries.is_empty() {
+        return Err(format!("Not a valid .unitypackage ({}): no asset folders", path.display()));
+    }
+    entries.retain(|_, e| !e.pathname.is_empty());
+    for record in entries.values_mut() {
+        record.is_folder = record.size.is_none();
+    }
+    Ok(entries)
+}
+
+/// Status of a package entry against the project's `{guid: path}` cache.
+fn classify(entry: &UnityPackageEntry, project_guids: &HashMap<String, String>, project_root: &Path) -> (String, Option<String>) {
+    match project_guids.get(&entry.guid) {
+        Some(existing) if *existing == entry.pathname => ("update".to_string(), None),
+        Some(existing) => ("guid_conflict".to_string(), Some(existing.clone())),
+        None if !entry.is_folder && project_root.join(&entry.pathname).exists() => ("path_conflict".to_string(), None),
+        None => ("new".to_string(), None),
+    }
+}
+
+/// List the assets of a `.unitypackage` (paths, GUIDs, sizes) without extracting it. With
+/// `project_root`, each entry is checked against the project: assets whose GUID already
+/// belongs to a different project asset, or whose path is taken by another asset, are
+/// reported as collisions.
+#[napi]
+pub fn read_unitypackage(path: String, project_root: Option<String>) -> UnityPackageReport {
+    let _call = crate::telemetry::call("read_unitypackage");
+    let entries = match read_entries(Path::new(&path)) {
+        Ok(entries) => entries,
+        Err(e) => return UnityPackageReport::failed(&path, e),
+    };
+
+    let project_guids: Option<HashMap<String, String>> = project_root.as_ref().map(|root| {
+        let cache = crate::walker::build_guid_cache(root.clone());
+        cache.as_object()
+            .map(|map| map.iter().filter_map(|(guid, p)| Some((guid.clone(), p.as_str()?.replace('\\', "/")))).collect())
+            .unwrap_or_default()
+    });
+
+    let mut entries: Vec<UnityPackageEntry> = entries.into_values().collect();
+    if let (Some(root), Some(guids)) = (&project_root, &project_guids) {
+        for entry in &mut entries {
+            let (status, existing_path) = classify(entry, guids, Path::new(root));
+            entry.status = Some(status);
+            entry.existing_path = existing_path;
+        }
+    }
+    entries.sort_by(|a, b| a.pathname.cmp(&b.pathname));
+
+    UnityPackageReport {
+        success: true,
+        error: None,
+        path,
+        asset_count: entries.iter().filter(|e| !e.is_folder).count() as u32,
+        folder_count: entries.iter().filter(|e| e.is_folder).count() as u32,
+        total_bytes: entries.iter().filter_map(|e| e.size).sum(),
+        collision_count: entries.iter()
+            .filter(|e| matches!(e.status.as_deref(), Some("guid_conflict" | "path_conflict")))
+            .count() as u32,
+        entries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use std::fs;
+
+    /// A package with (guid, pathname, asset bytes or None for a folder) entries.
+    fn write_package(path: &Path, assets: &[(&str, &str, Option<&[u8]>)]) {
+        let mut builder = tar::Builder::new(GzEncoder::new(File::create(path).unwrap(), flate2::Compression::default()));
+        let mut add = |name: String, data: &[u8]| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, data).unwrap();
+        };
+        for (guid, pathname, asset) in assets {
+            add(format!("{}/pathname", guid), format!("{}\n00", pathname).as_bytes());
+            add(format!("{}/asset.meta", guid), format!("fileFormatVersion: 2\nguid: {}\n", guid).as_bytes());
+            if let Some(data) = asset {
+                add(format!("{}/asset", guid), data);
+            }
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_read_unitypackage() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tool, folder, shared, taken) = ("a".repeat(32), "b".repeat(32), "c".repeat(32), "d".repeat(32));
+        let package = dir.path().join("Tool.unitypackage");
+        write_package(&package, &[
+            (&tool, "Assets/Tool/Tool.cs", Some(b"class Tool {}")),
+            (&folder, "Assets/Tool", None),
+            (&shared, "Assets/Tool/Shared.mat", Some(b"material")),
+            (&taken, "Assets/Readme.txt", Some(b"read me")),
+        ]);
+
+        let project = dir.path().join("Project");
+        fs::create_dir_all(project.join("Assets/Common")).unwrap();
+        fs::write(project.join("Assets/Common/Shared.mat"), "x").unwrap();
+        fs::write(project.join("Assets/Common/Shared.mat.meta"), format!("guid: {}\n", shared)).unwrap();
+        fs::write(project.join("Assets/Readme.txt"), "mine").unwrap();
+        fs::write(project.join("Assets/Readme.txt.meta"), format!("guid: {}\n", "e".repeat(32))).unwrap();
+
+        let report = read_unitypackage(package.to_string_lossy().to_string(), None);
+        assert!(report.success, "{:?}", report.error);
+        assert_eq!((report.asset_count, report.folder_count, report.total_bytes), (3, 1, 28));
+        let paths: Vec<&str> = report.entries.iter().map(|e| e.pathname.as_str()).collect();
+        assert_eq!(paths, vec!["Assets/Readme.txt", "Assets/Tool", "Assets/Tool/Shared.mat", "Assets/Tool/Tool.cs"]);
+        assert!(report.entries.iter().all(|e| e.has_meta && e.status.is_none()));
+
+        let report = read_unitypackage(package.to_string_lossy().to_string(), Some(project.to_string_lossy().to_string()));
+        let status: Vec<(&str, Option<&str>, Option<&str>)> = report.entries.iter()
+            .map(|e| (e.pathname.as_str(), e.status.as_deref(), e.existing_path.as_deref()))
+            .collect();
+        assert_eq!(status, vec![
+            ("Assets/Readme.txt", Some("path_conflict"), None),
+            ("Assets/Tool", Some("new"), None),
+            ("Assets/Tool/Shared.mat", Some("guid_conflict"), Some("Assets/Common/Shared.mat")),
+            ("Assets/Tool/Tool.cs", Some("new"), None),
+        ]);
+        assert_eq!(report.collision_count, 2);
+
+        fs::write(dir.path().join("Broken.unitypackage"), "not gzip").unwrap();
+        let broken = read_unitypackage(dir.path().join("Broken.unitypackage").to_string_lossy().to_string(), None);
+        assert!(!broken.success);
+        assert!(broken.error.unwrap().contains("Not a valid .unitypackage"));
+    }
+}
@@ -0,0 +1,276 @@
+//! Unity-aware review of a version control change set: what kind of file each changed path is,
+//! which need Unity's YAML merge handling, and change combinations that break a project
+//! (assets and their `.meta` files out of step, prefabs edited together with their instances).
+
+use napi_derive::napi;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Read;
+use std::path::Path;
+
+use crate::common::{self, META_GUID_RE, SOURCE_PREFAB_RE};
+
+const CODE_EXTENSIONS: &[&str] = &[".cs", ".asmdef", ".asmref", ".shader", ".compute", ".cginc", ".hlsl", ".rsp"];
+
+/// A changed path with its category.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct VcsChangedFile {
+    /// Project-relative path, forward slashes
+    pub path: String,
+    /// "scene", "prefab", "meta", "settings", "code", "asset" or "other"
+    pub category: String,
+    /// Present on disk (false for deletions)
+    pub exists: bool,
+    /// Serialized as Unity YAML: merge with UnityYAMLMerge (Smart Merge), not line-based merging
+    pub unity_yaml: bool,
+}
+
+/// A risky combination of changes.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct VcsWarning {
+    /// "asset_without_meta", "meta_deleted_asset_present", "asset_deleted_meta_kept",
+    /// "orphan_meta" or "prefab_and_instance_changed"
+    pub kind: String,
+    /// "error" (the project breaks) or "warning" (review needed)
+    pub severity: String,
+    pub path: String,
+    /// Other paths involved (the .meta, the changed instances of a prefab)
+    pub related: Vec<String>,
+    pub message: String,
+}
+
+#[napi(object)]
+pub struct VcsChangeReport {
+    pub project_root: String,
+    pub files: Vec<VcsChangedFile>,
+    /// Changed files per category
+    #[napi(ts_type = "Record<string, number>")]
+    pub category_counts: serde_json::Value,
+    /// Changed files that need Unity's YAML merge handling
+    pub unity_yaml_count: u32,
+    pub warnings: Vec<VcsWarning>,
+}
+
+/// Project-relative form of a changed path (absolute paths under the root are accepted).
+fn relative(project_root: &Path, path: &str) -> String {
+    let normalized = path.replace('\\', "/");
+    let stripped = Path::new(&normalized).strip_prefix(project_root).map(|p| p.to_string_lossy().replace('\\', "/"));
+    stripped.unwrap_or(normalized).trim_start_matches("./").to_string()
+}
+
+fn extension(path: &str) -> String {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rfind('.').map_or(String::new(), |i| name[i..].to_lowercase())
+}
+
+fn category(path: &str) -> &'static str {
+    let ext = extension(path);
+    match ext.as_str() {
+        ".unity" => "scene",
+        ".prefab" => "prefab",
+        ".meta" => "meta",
+        _ if path.starts_with("ProjectSettings/") || path == "Packages/manifest.json" || path == "Packages/packages-lock.json" => "settings",
+        _ if CODE_EXTENSIONS.contains(&ext.as_str()) => "code",
+        _ if path.starts_with("Assets/") || path.starts_with("Packages/") => "asset",
+        _ => "other",
+    }
+}
+
+/// Whether a file is Unity YAML: by its header when it exists (binary-serialized assets share
+/// the extensions), else by extension.
+fn is_unity_yaml(project_root: &Path, path: &str, exists: bool) -> bool {
+    let ext = extension(path);
+    let yaml_extension = crate::walker::extension_map("yaml").iter().any(|e| e.eq_ignore_ascii_case(&ext))
+        || (path.starts_with("ProjectSettings/") && ext == ".asset");
+    if !yaml_extension || !exists {
+        return yaml_extension;
+    }
+    let full = project_root.join(path);
+    let mut header = [0u8; 5];
+    crate::safety::check_read(&full).is_ok()
+        && std::fs::File::open(&full).and_then(|mut f| f.read_exact(&mut header)).is_ok()
+        && &header == b"%YAML"
+}
+
+/// Tracked by a `.meta` file: anything under Assets/ or an embedded package.
+fn has_meta(path: &str) -> bool {
+    (path.starts_with("Assets/") || path.starts_with("Packages/"))
+        && !path.ends_with(".meta")
+        && path != "Packages/manifest.json"
+        && path != "Packages/packages-lock.json"
+}
+
+/// Classify `changed_paths` (project-relative or absolute, e.g. from `git diff --name-only`)
+/// and flag combinations that break the project or need a careful merge: an asset changed
+/// while its `.meta` is missing, a `.meta` deleted while its asset stays (its GUID is
+/// regenerated and every reference breaks), an asset deleted while its `.meta` stays, and a
+/// prefab changed together with scenes or prefabs that instantiate or derive from it.
+#[napi]
+pub fn vcs_classify_changes(project_root: String, changed_paths: Vec<String>) -> VcsChangeReport {
+    let _call = crate::telemetry::call("vcs_classify_changes");
+    let root = Path::new(&project_root);
+    let paths: BTreeSet<String> = changed_paths.iter().map(|p| relative(root, p)).filter(|p| !p.is_empty()).collect();
+    let exists = |path: &str| root.join(path).exists();
+
+    let files: Vec<VcsChangedFile> = paths.iter()
+        .map(|path| {
+            let present = exists(path);
+            VcsChangedFile {
+                path: path.clone(),
+                category: category(path).to_string(),
+                exists: present,
+                unity_yaml: is_unity_yaml(root, path, present),
+            }
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    for file in &files {
+        if file.category == "meta" {
+            let asset = file.path.trim_end_matches(".meta");
+            match (file.exists, exists(asset), paths.contains(asset)) {
+                (false, true, _) => warnings.push(VcsWarning {
+                    kind: "meta_deleted_asset_present".to_string(),
+                    severity: "error".to_string(),
+                    path: file.path.clone(),
+                    related: vec![asset.to_string()],
+                    message: format!("{} is deleted but {} remains; Unity will give it a new GUID and break references to it", file.path, asset),
+                }),
+                (true, false, false) => warnings.push(VcsWarning {
+                    kind: "orphan_meta".to_string(),
+                    severity: "warning".to_string(),
+                    path: file.path.clone(),
+                    related: vec![asset.to_string()],
+                    message: format!("{} changed but {} does not exist; Unity deletes orphaned .meta files", file.path, asset),
+                }),
+                _ => {}
+            }
+        } else if has_meta(&file.path) {
+            let meta = format!("{}.meta", file.path);
+            match (file.exists, exists(&meta)) {
+                (true, false) => warnings.push(VcsWarning {
+                    kind: "asset_without_meta".to_string(),
+                    severity: "error".to_string(),
+                    path: file.path.clone(),
+                    related: vec![meta.clone()],
+                    message: format!("{} has no {}; each checkout will generate a different GUID", file.path, meta),
+                }),
+                (false, true) if !paths.contains(&meta) => warnings.push(VcsWarning {
+                    kind: "asset_deleted_meta_kept".to_string(),
+                    severity: "warning".to_string(),
+                    path: file.path.clone(),
+                    related: vec![meta.clone()],
+                    message: format!("{} is deleted but {} is not; delete both together", file.path, meta),
+                }),
+                _ => {}
+            }
+        }
+    }
+    warnings.extend(prefab_instance_warnings(root, &files));
+
+    let mut counts: BTreeMap<&str, u32> = BTreeMap::new();
+    for file in &files {
+        *counts.entry(file.category.as_str()).or_insert(0) += 1;
+    }
+    VcsChangeReport {
+        category_counts: serde_json::to_value(&counts).unwrap_or_default(),
+        unity_yaml_count: files.iter().filter(|f| f.unity_yaml).count() as u32,
+        project_root,
+        files,
+        warnings,
+    }
+}
+
+/// Changed prefabs whose instances or variants (in changed scenes and prefabs) changed too:
+/// the instance's overrides may no longer match the edited prefab.
+fn prefab_instance_warnings(root: &Path, files: &[VcsChangedFile]) -> Vec<VcsWarning> {
+    let prefab_by_guid: HashMap<String, &str> = files.iter()
+        .filter(|f| f.category == "prefab" && f.exists)
+        .filter_map(|f| {
+            let meta = common::read_unity_file(root.join(format!("{}.meta", f.path))).ok()?;
+            Some((META_GUID_RE.captures(&meta)?[1].to_string(), f.path.as_str()))
+        })
+        .collect();
+    if prefab_by_guid.is_empty() {
+        return Vec::new();
+    }
+
+    let mut instances: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for file in files.iter().filter(|f| (f.category == "scene" || f.category == "prefab") && f.exists) {
+        let Ok(content) = common::read_unity_file(root.join(&file.path)) else { continue };
+        for caps in SOURCE_PREFAB_RE.captures_iter(&content) {
+            if let Some(prefab) = prefab_by_guid.get(&caps[1]).filter(|p| **p != file.path) {
+                instances.entry(prefab).or_default().insert(file.path.clone());
+            }
+        }
+    }
+    instances.into_iter()
+        .map(|(prefab, users)| VcsWarning {
+            kind: "prefab_and_instance_changed".to_string(),
+            severity: "warning".to_string(),
+            path: prefab.to_string(),
+            message: format!("{} changed together with {} that instantiate or derive from it; check their overrides still apply", prefab, users.iter().cloned().collect::<Vec<_>>().join(", ")),
+            related: users.into_iter().collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_category() {
+        assert_eq!(category("Assets/Scenes/Main.unity"), "scene");
+        assert_eq!(category("Assets/Player.prefab.meta"), "meta");
+        assert_eq!(category("ProjectSettings/TagManager.asset"), "settings");
+        assert_eq!(category("Packages/manifest.json"), "settings");
+        assert_eq!(category("Assets/Scripts/Player.cs"), "code");
+        assert_eq!(category("Assets/Textures/Grass.png"), "asset");
+        assert_eq!(category("README.md"), "other");
+        assert_eq!(relative(Path::new("/p"), "/p/Assets/A.cs"), "Assets/A.cs");
+        assert_eq!(relative(Path::new("/p"), ".\\Assets\\A.cs"), "Assets/A.cs");
+    }
+
+    #[test]
+    fn test_vcs_classify_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let full = root.join(path);
+            fs::create_dir_all(full.parent().unwrap()).unwrap();
+            fs::write(full, content).unwrap();
+        };
+        let crate_guid = "a".repeat(32);
+        write("Assets/Crate.prefab", "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Name: Crate\n");
+        write("Assets/Crate.prefab.meta", &format!("fileFormatVersion: 2\nguid: {}\n", crate_guid));
+        write("Assets/Main.unity", &format!("%YAML 1.1\n--- !u!1001 &5\nPrefabInstance:\n  m_SourcePrefab: {{fileID: 100100000, guid: {}, type: 3}}\n", crate_guid));
+        write("Assets/Main.unity.meta", "guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n");
+        write("Assets/Binary.asset", "\0\0binary");
+        write("Assets/Binary.asset.meta", "guid: cccccccccccccccccccccccccccccccc\n");
+        write("Assets/New.cs", "class New {}");
+        write("Assets/Kept.png", "png");
+        write("Assets/Gone.png.meta", "guid: dddddddddddddddddddddddddddddddd\n");
+        write("Assets/Orphan.txt.meta", "guid: eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee\n");
+
+        let changed = ["Assets/Crate.prefab", "Assets/Main.unity", "Assets/Binary.asset", "Assets/New.cs", "Assets/Kept.png.meta", "Assets/Gone.png", "Assets/Orphan.txt.meta"];
+        let report = vcs_classify_changes(root.to_string_lossy().to_string(), changed.iter().map(|p| p.to_string()).collect());
+
+        let yaml: Vec<&str> = report.files.iter().filter(|f| f.unity_yaml).map(|f| f.path.as_str()).collect();
+        assert_eq!(yaml, vec!["Assets/Crate.prefab", "Assets/Main.unity"], "binary .asset files are not YAML");
+        assert_eq!(report.category_counts["meta"], 2);
+        assert_eq!(report.category_counts["code"], 1);
+
+        let warnings: Vec<(&str, &str)> = report.warnings.iter().map(|w| (w.kind.as_str(), w.path.as_str())).collect();
+        assert_eq!(warnings, vec![
+            ("asset_deleted_meta_kept", "Assets/Gone.png"),
+            ("meta_deleted_asset_present", "Assets/Kept.png.meta"),
+            ("asset_without_meta", "Assets/New.cs"),
+            ("orphan_meta", "Assets/Orphan.txt.meta"),
+            ("prefab_and_instance_changed", "Assets/Crate.prefab"),
+        ]);
+        assert_eq!(report.warnings[4].related, vec!["Assets/Main.unity"]);
+    }
+}
@@ -1,11 +1,13 @@
 use napi_derive::napi;
 use rayon::prelude::*;
-use regex::RegexBuilder;
+use regex::{Regex, RegexBuilder};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use std::sync::LazyLock;
 
-use crate::common;
+use crate::common::{self, ResourceBudget};
+use crate::common::budget::Meter;
+use crate::scanner;
 
 /// Directories to always skip during project walks.
 const SKIP_DIRS: &[&str] = &[
@@ -24,6 +26,17 @@ const BINARY_EXTENSIONS: &[&str] = &[
     ".bank", ".bytes", ".db",
 ];
 
+/// Default upper bound on the size of a file grep will read (10 MB).
+const DEFAULT_GREP_MAX_FILE_SIZE: u32 = 10 * 1024 * 1024;
+
+/// Number of leading bytes inspected for NUL bytes when sniffing binary content.
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+/// A `m_Scenes` entry of EditorBuildSettings.asset: `- enabled: 1` followed by its `path:` line.
+static BUILD_SCENE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"-[ \t]*enabled:[ \t]*(\d)[ \t]*\r?\n[ \t]*path:[ \t]*([^\r\n]*)").unwrap()
+});
+
 /// Truncate a string to at most `max_bytes` bytes at a valid UTF-8 char boundary.
 fn truncate_line(s: &str, max_bytes: usize) -> String {
     if s.len() <= max_bytes {
@@ -37,47 +50,106 @@ fn truncate_line(s: &str, max_bytes: usize) -> String {
     format!("{}...", &s[..end])
 }
 
+/// Built-in file-type groups for grep, most specific first so a file is labeled with the
+/// narrowest group containing its extension.
+const FILE_TYPE_GROUPS: &[(&str, &[&str])] = &[
+    ("unity", &[".unity"]),
+    ("prefab", &[".prefab"]),
+    ("asset", &[".asset"]),
+    ("mat", &[".mat"]),
+    ("anim", &[".anim"]),
+    ("controller", &[".controller"]),
+    ("cs", &[".cs"]),
+    ("yaml", &[
+        ".yaml", ".yml", ".unity", ".prefab", ".asset",
+        ".mat", ".anim", ".controller", ".overrideController",
+        ".mask", ".mixer", ".lighting", ".preset", ".signal",
+        ".playable", ".renderTexture", ".flare", ".guiskin",
+        ".terrainlayer", ".cubemap",
+    ]),
+];
+
+/// Extensions searched when no (known) file type is given.
+const DEFAULT_GREP_EXTENSIONS: &[&str] = &[
+    ".cs", ".unity", ".prefab", ".asset", ".mat", ".anim", ".controller",
+    ".yaml", ".yml", ".txt", ".json", ".xml", ".shader", ".cginc", ".hlsl",
+    ".compute", ".asmdef", ".asmref",
+];
+
 /// Extension groups for file_type filtering in grep.
-fn extension_map(file_type: &str) -> Vec<&'static str> {
-    match file_type {
-        "cs" => vec![".cs"],
-        "yaml" => vec![
-            ".yaml", ".yml", ".unity", ".prefab", ".asset",
-            ".mat", ".anim", ".controller", ".overrideController",
-            ".mask", ".mixer", ".lighting", ".preset", ".signal",
-            ".playable", ".renderTexture", ".flare", ".guiskin",
-            ".terrainlayer", ".cubemap",
-        ],
-        "unity" => vec![".unity"],
-        "prefab" => vec![".prefab"],
-        "asset" => vec![".asset"],
-        "mat" => vec![".mat"],
-        "anim" => vec![".anim"],
-        "controller" => vec![".controller"],
-        _ => vec![
-            ".cs", ".unity", ".prefab", ".asset", ".mat", ".anim", ".controller",
-            ".yaml", ".yml", ".txt", ".json", ".xml", ".shader", ".cginc", ".hlsl",
-            ".compute", ".asmdef", ".asmref",
-        ],
+pub(crate) fn extension_map(file_type: &str) -> Vec<&'static str> {
+    FILE_TYPE_GROUPS
+        .iter()
+        .find(|(name, _)| *name == file_type)
+        .map_or(DEFAULT_GREP_EXTENSIONS, |(_, extensions)| extensions)
+        .to_vec()
+}
+
+/// File-type groups of a project: `file_types` from `.unity-agentic/config.json` (sorted by
+/// name, overriding built-ins of the same name) followed by the built-in groups.
+/// Extensions are lowercase with a leading dot.
+fn file_type_groups(project: &Path) -> Vec<(String, Vec<String>, bool)> {
+    let custom = scanner::config::project_file_types(project);
+    let mut groups: Vec<(String, Vec<String>, bool)> = custom.into_iter().map(|(name, exts)| (name, exts, true)).collect();
+    groups.sort();
+    for (name, extensions) in FILE_TYPE_GROUPS {
+        if !groups.iter().any(|(n, _, _)| n == name) {
+            groups.push((name.to_string(), extensions.iter().map(|e| e.to_lowercase()).collect(), false));
+        }
     }
+    groups
+}
+
+#[napi(object)]
+pub struct NapiFileTypeGroup {
+    pub name: String,
+    pub extensions: Vec<String>,
+    /// Defined in the project's `.unity-agentic/config.json`
+    pub custom: bool,
+}
+
+/// File-type groups grep accepts as `file_type`: the project's custom `file_types` (from
+/// `.unity-agentic/config.json`) and the built-in groups, so callers need no mirror of them.
+#[napi]
+pub fn list_file_type_groups(project_path: Option<String>) -> Vec<NapiFileTypeGroup> {
+    let _call = crate::telemetry::call("list_file_type_groups");
+    let project = PathBuf::from(project_path.unwrap_or_default());
+    file_type_groups(&project)
+        .into_iter()
+        .map(|(name, extensions, custom)| NapiFileTypeGroup { name, extensions, custom })
+        .collect()
+}
+
+/// Options controlling which roots the project walk covers.
+#[napi(object)]
+pub struct NapiWalkOptions {
+    /// Also walk embedded packages in `Packages/` and local `file:` packages
+    /// referenced from `Packages/manifest.json` (default: false)
+    pub include_packages: Option<bool>,
+    /// Follow symlinked files and directories; symlink loops are detected and skipped (default: false)
+    pub follow_symlinks: Option<bool>,
 }
 
 /// Walk a Unity project and collect files matching the given extensions.
 ///
 /// Walks `Assets/` (and `ProjectSettings/` when `.asset` is among extensions).
-/// Skips standard Unity noise directories (Library, Temp, etc.).
+/// Skips standard Unity noise directories (Library, Temp, etc.) and the `skip_dirs` of the
+/// global and project configs (see `DirFilter`); `exclude_dirs` adds rules for this call.
+/// With `include_packages`, embedded and local `file:` packages are walked as
+/// additional asset roots.
 #[napi]
 pub fn walk_project_files(
     project_path: String,
     extensions: Vec<String>,
     exclude_dirs: Option<Vec<String>>,
+    options: Option<NapiWalkOptions>,
 ) -> Vec<String> {
+    let _call = crate::telemetry::call("walk_project_files");
     let project = Path::new(&project_path);
-    let extra_excludes = exclude_dirs.unwrap_or_default();
-    let mut skip: HashSet<String> = SKIP_DIRS.iter().map(|s| s.to_string()).collect();
-    for dir in &extra_excludes {
-        skip.insert(dir.clone());
-    }
+    let filter = DirFilter::for_project(project, &exclude_dirs.unwrap_or_default());
+
+    let include_packages = options.as_ref().and_then(|o| o.include_packages).unwrap_or(false);
+    let follow_symlinks = options.as_ref().and_then(|o| o.follow_symlinks).unwrap_or(false);
 
     let ext_set: HashSet<String> = extensions
         .iter()
@@ -87,41 +159,145 @@ pub fn walk_project_files(
         })
         .collect();
 
-    let mut result: Vec<String> = Vec::new();
-
-    let assets_dir = project.join("Assets");
-    if assets_dir.is_dir() {
-        walk_dir_filtered(&assets_dir, &skip, &ext_set, &mut result);
-    }
+    let mut roots: Vec<PathBuf> = vec![project.join("Assets")];
 
     // Also walk ProjectSettings/ when .asset is requested
     if ext_set.contains(".asset") {
-        let settings_dir = project.join("ProjectSettings");
-        if settings_dir.is_dir() {
-            walk_dir_filtered(&settings_dir, &skip, &ext_set, &mut result);
+        roots.push(project.join("ProjectSettings"));
+    }
+
+    if include_packages {
+        roots.extend(package_roots(project));
+    }
+
+    let mut result: Vec<String> = Vec::new();
+    // Canonical paths already visited, so symlinked or doubly-referenced packages are walked once
+    let mut seen_roots: HashSet<PathBuf> = HashSet::new();
+    let mut seen_files: HashSet<PathBuf> = HashSet::new();
+
+    for root in roots {
+        if !root.is_dir() {
+            continue;
         }
+        let canonical = root.canonicalize().unwrap_or_else(|_| root.clone());
+        if !seen_roots.insert(canonical) {
+            continue;
+        }
+        walk_dir_filtered(&root, &filter, &ext_set, follow_symlinks, &mut seen_files, &mut result);
     }
 
     result
 }
 
+/// Package folders that should be treated as asset roots:
+/// embedded packages (`Packages/<name>/package.json`) and local packages
+/// referenced as `"file:<path>"` in `Packages/manifest.json`.
+fn package_roots(project: &Path) -> Vec<PathBuf> {
+    let packages_dir = project.join("Packages");
+    let mut roots: Vec<PathBuf> = Vec::new();
+
+    if let Ok(entries) = crate::safety::read_dir(&packages_dir) {
+        let mut embedded: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir() && p.join("package.json").is_file())
+            .collect();
+        embedded.sort();
+        roots.extend(embedded);
+    }
+
+    let manifest = crate::safety::read_to_string(packages_dir.join("manifest.json"))
+        .ok()
+        .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok());
+    if let Some(deps) = manifest.as_ref().and_then(|m| m.get("dependencies")).and_then(|d| d.as_object()) {
+        for value in deps.values() {
+            if let Some(local) = value.as_str().and_then(|v| v.strip_prefix("file:")) {
+                // Relative file: paths resolve against the Packages/ folder
+                let local = local.trim_start_matches("//");
+                let path = Path::new(local);
+                let resolved = if path.is_absolute() { path.to_path_buf() } else { packages_dir.join(path) };
+                // Tarball references (file:foo.tgz) are not walkable folders
+                if resolved.is_dir() {
+                    roots.push(resolved);
+                }
+            }
+        }
+    }
+
+    roots
+}
+
+/// Which directories project walks descend into: the built-in `SKIP_DIRS`, the `skip_dirs`
+/// and `include_dirs` of the global and project configs, and per-call excludes.
+///
+/// Rules without `/` match a directory name (`ThirdParty`, `Temp*`); rules with `/` match the
+/// project-relative path (`Assets/StreamingAssets`, `Assets/**/Generated`). An include rule
+/// wins over skip rules for the directories it matches, but cannot reach below a skipped parent.
+pub(crate) struct DirFilter {
+    root: PathBuf,
+    skip: Vec<(regex::Regex, bool)>,
+    include: Vec<(regex::Regex, bool)>,
+}
+
+impl DirFilter {
+    /// Filter for a project, reading the global and project configs.
+    pub(crate) fn for_project(project_root: &Path, extra_skips: &[String]) -> Self {
+        let global = scanner::config::global_config_path();
+        Self::with_global_config(project_root, global.as_deref(), extra_skips)
+    }
+
+    fn with_global_config(project_root: &Path, global_config: Option<&Path>, extra_skips: &[String]) -> Self {
+        let (skip_dirs, include_dirs) = scanner::config::walk_dir_rules(project_root, global_config);
+        let compile = |rules: &mut dyn Iterator<Item = &str>| -> Vec<(regex::Regex, bool)> {
+            rules
+                .map(|r| r.trim().trim_matches('/'))
+                .filter(|r| !r.is_empty())
+                .filter_map(|r| path_glob_to_regex(r).map(|re| (re, r.contains('/'))))
+                .collect()
+        };
+        let mut skips = SKIP_DIRS.iter().copied()
+            .chain(skip_dirs.iter().map(String::as_str))
+            .chain(extra_skips.iter().map(String::as_str));
+        DirFilter {
+            root: project_root.to_path_buf(),
+            skip: compile(&mut skips),
+            include: compile(&mut include_dirs.iter().map(String::as_str)),
+        }
+    }
+
+    fn matches(&self, rules: &[(regex::Regex, bool)], name: &str, rel: Option<&str>) -> bool {
+        rules.iter().any(|(re, full)| if *full { rel.is_some_and(|r| re.is_match(r)) } else { re.is_match(name) })
+    }
+
+    /// Whether the walk should descend into `dir`.
+    pub(crate) fn allows(&self, dir: &Path) -> bool {
+        let Some(name) = dir.file_name().map(|n| n.to_string_lossy()) else { return true };
+        let rel = dir.strip_prefix(&self.root).ok().map(|r| r.to_string_lossy().replace('\\', "/"));
+        !self.matches(&self.skip, &name, rel.as_deref()) || self.matches(&self.include, &name, rel.as_deref())
+    }
+
+    /// walkdir `filter_entry` predicate: walk roots and files always pass.
+    pub(crate) fn keeps(&self, entry: &walkdir::DirEntry) -> bool {
+        entry.depth() == 0 || !entry.file_type().is_dir() || self.allows(entry.path())
+    }
+}
+
 /// Internal recursive walker using walkdir crate.
+///
+/// When `follow_symlinks` is set, walkdir reports symlink loops as errors, which are
+/// skipped; `seen_files` dedupes files reachable through more than one link.
 fn walk_dir_filtered(
     root: &Path,
-    skip: &HashSet<String>,
+    filter: &DirFilter,
     ext_set: &HashSet<String>,
+    follow_symlinks: bool,
+    seen_files: &mut HashSet<PathBuf>,
     result: &mut Vec<String>,
 ) {
-    for entry in WalkDir::new(root)
+    for entry in crate::safety::walk_dir(root)
+        .follow_links(follow_symlinks)
         .into_iter()
-        .filter_entry(|e| {
-            if e.file_type().is_dir() {
-                if let Some(name) = e.file_name().to_str() {
-                    return !skip.contains(name);
-                }
-            }
-            true
-        })
+        .filter_entry(|e| filter.keeps(e))
     {
         let entry = match entry {
             Ok(e) => e,
@@ -135,6 +311,12 @@ fn walk_dir_filtered(
         if let Some(ext) = entry.path().extension() {
             let ext_str = format!(".{}", ext.to_string_lossy().to_lowercase());
             if ext_set.contains(&ext_str) {
+                if follow_symlinks {
+                    let canonical = entry.path().canonicalize().unwrap_or_else(|_| entry.path().to_path_buf());
+                    if !seen_files.insert(canonical) {
+                        continue;
+                    }
+                }
                 result.push(entry.path().to_string_lossy().to_string());
             }
         }
@@ -150,6 +332,12 @@ pub struct NapiGrepOptions {
     pub file_type: Option<String>,
     pub max_results: Option<u32>,
     pub context_lines: Option<u32>,
+    /// Skip files larger than this many bytes (default: 10 MB)
+    pub max_file_size: Option<u32>,
+    /// Search exactly these extensions instead of a `file_type` group
+    pub extensions: Option<Vec<String>>,
+    /// Limits on files, bytes and time searched
+    pub budget: Option<ResourceBudget>,
 }
 
 #[napi(object)]
@@ -160,6 +348,9 @@ pub struct NapiGrepMatch {
     pub line: String,
     pub context_before: Option<Vec<String>>,
     pub context_after: Option<Vec<String>>,
+    /// File-type group of the file: the requested `file_type`, else the narrowest group
+    /// containing its extension
+    pub file_type: Option<String>,
 }
 
 #[napi(object)]
@@ -167,21 +358,59 @@ pub struct NapiGrepResult {
     pub success: bool,
     pub project_path: String,
     pub pattern: String,
-    pub total_files_scanned: u32,
-    pub total_matches: u32,
+    pub total_files_scanned: i64,
+    pub total_matches: i64,
     pub truncated: bool,
     pub matches: Vec<NapiGrepMatch>,
     pub error: Option<String>,
+    /// Files skipped because they exceed `max_file_size`
+    pub skipped_large_files: u32,
+    /// Files skipped because their content looks binary (NUL bytes)
+    pub skipped_binary_files: u32,
+    /// Files were left unsearched because the budget ran out
+    pub budget_exhausted: bool,
+}
+
+/// Why grep did not search a candidate file.
+enum GrepSkip {
+    TooLarge,
+    Binary,
+    Unreadable,
+    OverBudget,
+}
+
+/// Check size and sniff the leading bytes for NUL before reading a whole file into memory.
+/// Returns the file size.
+fn check_grep_candidate(path: &Path, max_file_size: u64) -> Result<u64, GrepSkip> {
+    use std::io::Read;
+
+    crate::safety::check_read(path).map_err(|_| GrepSkip::Unreadable)?;
+    let metadata = std::fs::metadata(path).map_err(|_| GrepSkip::Unreadable)?;
+    if metadata.len() > max_file_size {
+        return Err(GrepSkip::TooLarge);
+    }
+
+    let file = std::fs::File::open(path).map_err(|_| GrepSkip::Unreadable)?;
+    let mut head = Vec::with_capacity(BINARY_SNIFF_BYTES);
+    file.take(BINARY_SNIFF_BYTES as u64)
+        .read_to_end(&mut head)
+        .map_err(|_| GrepSkip::Unreadable)?;
+    if head.contains(&0) {
+        return Err(GrepSkip::Binary);
+    }
+    Ok(metadata.len())
 }
 
 /// Grep across Unity project files in parallel using Rayon.
 #[napi]
 pub fn grep_project(options: NapiGrepOptions) -> NapiGrepResult {
+    let _call = crate::telemetry::call("grep_project");
     let project_path = options.project_path.clone();
     let pattern_str = options.pattern.clone();
     let file_type = options.file_type.as_deref().unwrap_or("all");
     let max_results = options.max_results.unwrap_or(100) as usize;
     let context_lines = options.context_lines.unwrap_or(0) as usize;
+    let max_file_size = options.max_file_size.unwrap_or(DEFAULT_GREP_MAX_FILE_SIZE) as u64;
 
     // Validate project path
     if !Path::new(&project_path).exists() {
@@ -195,6 +424,9 @@ pub fn grep_project(options: NapiGrepOptions) -> NapiGrepResult {
             truncated: false,
             matches: vec![],
             error: Some(err_msg),
+            skipped_large_files: 0,
+            skipped_binary_files: 0,
+            budget_exhausted: false,
         };
     }
 
@@ -211,18 +443,32 @@ pub fn grep_project(options: NapiGrepOptions) -> NapiGrepResult {
                 truncated: false,
                 matches: vec![],
                 error: Some(format!("Invalid regex pattern: {e}")),
+                skipped_large_files: 0,
+                skipped_binary_files: 0,
+                budget_exhausted: false,
             };
         }
     };
 
-    let extensions: Vec<String> = extension_map(file_type)
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-    let files = walk_project_files(project_path.clone(), extensions, None);
+    let groups = file_type_groups(Path::new(&project_path));
+    let requested_group = groups.iter().find(|(name, _, _)| name == file_type).filter(|_| options.extensions.is_none());
+    let extensions: Vec<String> = match (&options.extensions, requested_group) {
+        (Some(extensions), _) => extensions.clone(),
+        (None, Some((_, extensions, _))) => extensions.clone(),
+        (None, None) => extension_map(file_type).iter().map(|s| s.to_string()).collect(),
+    };
+    let group_of = |path: &str| -> Option<String> {
+        if let Some((name, _, _)) = requested_group {
+            return Some(name.clone());
+        }
+        let ext = format!(".{}", Path::new(path).extension()?.to_string_lossy().to_lowercase());
+        groups.iter().find(|(_, exts, _)| exts.contains(&ext)).map(|(name, _, _)| name.clone())
+    };
+    let files = walk_project_files(project_path.clone(), extensions, None, None);
 
     let binary_set: HashSet<&str> = BINARY_EXTENSIONS.iter().copied().collect();
     let project = PathBuf::from(&project_path);
+    let meter = Meter::new(options.budget.as_ref());
 
     // Filter out binary files
     let text_files: Vec<&String> = files
@@ -238,15 +484,17 @@ pub fn grep_project(options: NapiGrepOptions) -> NapiGrepResult {
         })
         .collect();
 
-    let total_files_scanned = text_files.len() as u32;
-
     // Parallel grep with rayon
-    let all_matches: Vec<NapiGrepMatch> = text_files
+    let per_file: Vec<Result<Vec<NapiGrepMatch>, GrepSkip>> = text_files
         .par_iter()
-        .flat_map(|file_path| {
+        .map(|file_path| {
+            let size = check_grep_candidate(Path::new(file_path), max_file_size)?;
+            if !meter.admit(size) {
+                return Err(GrepSkip::OverBudget);
+            }
             let content = match common::read_unity_file(file_path) {
                 Ok(c) => c,
-                Err(_) => return vec![],
+                Err(_) => return Err(GrepSkip::Unreadable),
             };
 
             let lines: Vec<&str> = content.split('\n').collect();
@@ -256,7 +504,9 @@ pub fn grep_project(options: NapiGrepOptions) -> NapiGrepResult {
                 .unwrap_or_else(|_| file_path.to_string());
 
             let mut file_matches: Vec<NapiGrepMatch> = Vec::new();
+            let file_group = group_of(file_path);
 
+            let _regex = crate::telemetry::phase(crate::telemetry::Phase::Regex);
             for (i, line) in lines.iter().enumerate() {
                 if regex.is_match(line) {
                     let truncated_line = truncate_line(line, 200);
@@ -291,14 +541,31 @@ pub fn grep_project(options: NapiGrepOptions) -> NapiGrepResult {
                         line: truncated_line,
                         context_before,
                         context_after,
+                        file_type: file_group.clone(),
                     });
                 }
             }
 
-            file_matches
+            Ok(file_matches)
         })
         .collect();
 
+    let mut skipped_large_files = 0u32;
+    let mut skipped_binary_files = 0u32;
+    let mut total_files_scanned = 0i64;
+    let mut all_matches: Vec<NapiGrepMatch> = Vec::new();
+    for outcome in per_file {
+        match outcome {
+            Ok(file_matches) => {
+                total_files_scanned += 1;
+                all_matches.extend(file_matches);
+            }
+            Err(GrepSkip::TooLarge) => skipped_large_files += 1,
+            Err(GrepSkip::Binary) => skipped_binary_files += 1,
+            Err(GrepSkip::Unreadable | GrepSkip::OverBudget) => {}
+        }
+    }
+
     let truncated = all_matches.len() > max_results;
     let matches: Vec<NapiGrepMatch> = all_matches.into_iter().take(max_results).collect();
 
@@ -307,11 +574,171 @@ pub fn grep_project(options: NapiGrepOptions) -> NapiGrepResult {
         project_path,
         pattern: pattern_str,
         total_files_scanned,
-        total_matches: matches.len() as u32,
+        total_matches: common::to_count(matches.len()),
         truncated,
         matches,
         error: None,
+        skipped_large_files,
+        skipped_binary_files,
+        budget_exhausted: meter.exhausted(),
+    }
+}
+
+// ========== Replace ==========
+
+#[napi(object)]
+#[derive(Clone)]
+pub struct NapiValueChange {
+    pub line_number: u32,
+    pub file_id: String,
+    pub class_name: String,
+    /// Dotted key path of the edited property (e.g. "m_Modification.m_Modifications.value")
+    pub property: String,
+    pub old_line: String,
+    pub new_line: String,
+}
+
+#[napi(object)]
+pub struct NapiFileReplacement {
+    pub file: String,
+    pub changes: Vec<NapiValueChange>,
+    /// Unified diff of the file change
+    pub diff: String,
+}
+
+#[napi(object)]
+pub struct NapiReplaceResult {
+    pub success: bool,
+    pub project_path: String,
+    pub selector: String,
+    pub dry_run: bool,
+    pub total_files_scanned: u32,
+    pub files_changed: u32,
+    pub total_changes: u32,
+    pub files: Vec<NapiFileReplacement>,
+    pub error: Option<String>,
+}
+
+/// Replace property values across every scene, prefab and YAML asset of a project.
+///
+/// `selector` is `[ClassName.]property[.sub]` (e.g. `m_Layer`, `MeshRenderer.m_Materials`).
+/// A value matches when it equals `old_value`, or when it is an object reference whose guid is
+/// `old_value` (retargeting asset references). Only the matched value text is rewritten.
+/// `new_value` is written as a YAML value: numbers, JSON objects (`{"x": 1}` → `{x: 1}`) and
+/// JSON strings are encoded like `encode_yaml_value`, other text is quoted when YAML needs it.
+/// Binary files are skipped; a file that cannot be read fails the call before anything is
+/// written. With `dry_run` (default: true) nothing is written and the result carries the diff report.
+#[napi]
+pub fn replace_property_values(
+    project_path: String,
+    selector: String,
+    old_value: String,
+    new_value: String,
+    dry_run: Option<bool>,
+) -> NapiReplaceResult {
+    let _call = crate::telemetry::call("replace_property_values");
+    let dry_run = dry_run.unwrap_or(true);
+    let mut result = NapiReplaceResult {
+        success: false,
+        project_path: project_path.clone(),
+        selector: selector.clone(),
+        dry_run,
+        total_files_scanned: 0,
+        files_changed: 0,
+        total_changes: 0,
+        files: vec![],
+        error: None,
+    };
+
+    if !Path::new(&project_path).exists() {
+        result.error = Some(format!("Project path not found: {project_path}"));
+        return result;
+    }
+    let Some(parsed) = scanner::replace::PropertySelector::parse(&selector) else {
+        result.error = Some(format!("Invalid selector: {selector}"));
+        return result;
+    };
+    if old_value.trim().is_empty() {
+        result.error = Some("old_value must not be empty".to_string());
+        return result;
+    }
+    let new_value = match serde_json::from_str::<serde_json::Value>(new_value.trim()) {
+        Ok(value @ (serde_json::Value::Number(_) | serde_json::Value::Object(_) | serde_json::Value::String(_))) => {
+            match common::yaml::encode_value(&value) {
+                Ok(text) => text,
+                Err(e) => {
+                    result.error = Some(format!("Invalid new_value: {e}"));
+                    return result;
+                }
+            }
+        }
+        _ => common::yaml::encode_string(new_value.trim()),
+    };
+
+    let extensions: Vec<String> = extension_map("yaml")
+        .iter()
+        .filter(|ext| !matches!(**ext, ".yaml" | ".yml"))
+        .map(|s| s.to_string())
+        .collect();
+    let files = walk_project_files(project_path.clone(), extensions, None, None);
+    let project = PathBuf::from(&project_path);
+
+    let relative = |file_path: &str| Path::new(file_path)
+        .strip_prefix(&project)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| file_path.to_string());
+    // No size limit: skipping a large scene would leave the project partly edited
+    let per_file: Vec<Result<Option<_>, String>> = files
+        .par_iter()
+        .map(|file_path| {
+            match check_grep_candidate(Path::new(file_path), u64::MAX) {
+                Ok(_) => {}
+                Err(GrepSkip::Binary) => return Ok(None),
+                Err(_) => return Err(file_path.clone()),
+            }
+            // Raw content, so CRLF files are written back with their line endings
+            let content = common::read_raw_unity_file(file_path).map_err(|_| file_path.clone())?;
+            let (updated, edits) = scanner::replace::replace_values(&content, &parsed, old_value.trim(), &new_value);
+            let diff = common::diff::unified_diff(&relative(file_path), &content, &updated);
+            Ok(Some((file_path.clone(), updated, edits, diff)))
+        })
+        .collect();
+    let unreadable: Vec<String> = per_file.iter().filter_map(|r| r.as_ref().err().map(|f| relative(f))).collect();
+    if !unreadable.is_empty() {
+        result.error = Some(format!("Cannot read {}; nothing was replaced", unreadable.join(", ")));
+        return result;
     }
+
+    for (file_path, updated, edits, diff) in per_file.into_iter().flatten().flatten() {
+        result.total_files_scanned += 1;
+        if edits.is_empty() {
+            continue;
+        }
+        if !dry_run {
+            if let Err(e) = common::atomic::write_atomic(&file_path, &updated) {
+                result.error = Some(format!("Failed to write {file_path}: {e}"));
+                return result;
+            }
+        }
+        let rel_path = relative(&file_path);
+        result.files_changed += 1;
+        result.total_changes += edits.len() as u32;
+        result.files.push(NapiFileReplacement {
+            diff,
+            changes: edits.into_iter().map(|e| NapiValueChange {
+                line_number: e.line,
+                file_id: e.file_id,
+                class_name: e.class_name,
+                property: e.property,
+                old_line: e.old_line,
+                new_line: e.new_line,
+            }).collect(),
+            file: rel_path,
+        });
+    }
+
+    result.success = true;
+    result
 }
 
 // ========== GUID Cache ==========
@@ -321,6 +748,7 @@ pub fn grep_project(options: NapiGrepOptions) -> NapiGrepResult {
 /// Returns a JSON object mapping `{ guid: relative_asset_path }`.
 #[napi]
 pub fn build_guid_cache(project_root: String) -> serde_json::Value {
+    let _call = crate::telemetry::call("build_guid_cache");
     let root = PathBuf::from(&project_root);
     let assets_dir = root.join("Assets");
 
@@ -329,8 +757,10 @@ pub fn build_guid_cache(project_root: String) -> serde_json::Value {
     }
 
     // Collect all .meta file paths
-    let meta_files: Vec<PathBuf> = WalkDir::new(&assets_dir)
+    let filter = DirFilter::for_project(&root, &[]);
+    let meta_files: Vec<PathBuf> = crate::safety::walk_dir(&assets_dir)
         .into_iter()
+        .filter_entry(|e| filter.keeps(e))
         .filter_map(|e| e.ok())
         .filter(|e| {
             e.file_type().is_file()
@@ -382,6 +812,7 @@ pub fn build_guid_cache(project_root: String) -> serde_json::Value {
 /// Returns a separate cache so project assets and package assets stay distinct.
 #[napi]
 pub fn build_package_guid_cache(project_root: String) -> serde_json::Value {
+    let _call = crate::telemetry::call("build_package_guid_cache");
     let root = PathBuf::from(&project_root);
     let package_cache = root.join("Library").join("PackageCache");
 
@@ -389,7 +820,7 @@ pub fn build_package_guid_cache(project_root: String) -> serde_json::Value {
         return serde_json::Value::Object(serde_json::Map::new());
     }
 
-    let meta_files: Vec<PathBuf> = WalkDir::new(&package_cache)
+    let meta_files: Vec<PathBuf> = crate::safety::walk_dir(&package_cache)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| {
@@ -438,6 +869,7 @@ pub fn build_package_guid_cache(project_root: String) -> serde_json::Value {
 /// build_package_guid_cache (Library/PackageCache/).
 #[napi]
 pub fn build_local_package_guid_cache(project_root: String) -> serde_json::Value {
+    let _call = crate::telemetry::call("build_local_package_guid_cache");
     let root = PathBuf::from(&project_root);
     let packages_dir = root.join("Packages");
 
@@ -445,7 +877,7 @@ pub fn build_local_package_guid_cache(project_root: String) -> serde_json::Value
         return serde_json::Value::Object(serde_json::Map::new());
     }
 
-    let meta_files: Vec<PathBuf> = WalkDir::new(&packages_dir)
+    let meta_files: Vec<PathBuf> = crate::safety::walk_dir(&packages_dir)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| {
@@ -487,6 +919,308 @@ pub fn build_local_package_guid_cache(project_root: String) -> serde_json::Value
     serde_json::Value::Object(map)
 }
 
+// ========== Content Hashing ==========
+
+/// Serialized fields Unity rewrites without any content change; dropped before hashing.
+const VOLATILE_KEYS: &[&str] = &["timeCreated", "licenseType"];
+
+/// Roots covered by `hash_project`, relative to the project root.
+const HASH_ROOTS: &[&str] = &["Assets", "ProjectSettings", "Packages"];
+
+#[napi(object)]
+pub struct NapiFileHash {
+    /// Path relative to the project root, with forward slashes
+    pub path: String,
+    pub hash: String,
+}
+
+#[napi(object)]
+pub struct NapiProjectHash {
+    pub project_root: String,
+    /// Combined hash over the sorted (path, hash) pairs of all matched files
+    pub hash: String,
+    pub file_count: u32,
+    pub files: Vec<NapiFileHash>,
+}
+
+/// 64-bit FNV-1a; unlike `DefaultHasher`, its output is stable across Rust releases.
+pub(crate) fn fnv1a64(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |h, b| (h ^ *b as u64).wrapping_mul(0x0000_0100_0000_01b3))
+}
+
+pub(crate) const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Hash file contents. Text is hashed with normalized line endings, without a UTF-8 BOM and
+/// without volatile fields; binary files (NUL in the leading bytes) are hashed as-is.
+fn content_hash(bytes: &[u8]) -> String {
+    let head = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    let hash = match std::str::from_utf8(bytes) {
+        Ok(text) if !head.contains(&0) => {
+            let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+            text.split('\n')
+                .map(|line| line.strip_suffix('\r').unwrap_or(line))
+                .filter(|line| {
+                    let key = line.trim_start().split(':').next().unwrap_or("");
+                    !VOLATILE_KEYS.contains(&key)
+                })
+                .fold(FNV_OFFSET, |h, line| fnv1a64(fnv1a64(h, line.as_bytes()), b"\n"))
+        }
+        _ => fnv1a64(FNV_OFFSET, bytes),
+    };
+    format!("{:016x}", hash)
+}
+
+/// Convert a path glob to an anchored regex: `**` spans directories, `*` and `?` do not.
+/// A glob without `/` is matched against the file name only.
+fn path_glob_to_regex(glob: &str) -> Option<regex::Regex> {
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            _ => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    regex::Regex::new(&pattern).ok()
+}
+
+/// Stable content hash of one file (see `hash_project` for normalization).
+/// Returns None when the file cannot be read.
+#[napi]
+pub fn hash_asset(file: String) -> Option<String> {
+    let _call = crate::telemetry::call("hash_asset");
+    let bytes = {
+        let _io = crate::telemetry::phase(crate::telemetry::Phase::Io);
+        crate::safety::read(&file).ok()?
+    };
+    Some(content_hash(&bytes))
+}
+
+/// Stable content hashes for the files under `Assets/`, `ProjectSettings/` and `Packages/`,
+/// optionally limited to paths matching any of `globs` (e.g. `Assets/**/*.prefab`, `*.unity`).
+///
+/// Line endings are normalized and volatile fields (`timeCreated`, `licenseType`) are ignored,
+/// so the combined hash only changes when relevant content does. Library/Temp and other
+/// noise directories are skipped.
+#[napi]
+pub fn hash_project(project_root: String, globs: Option<Vec<String>>) -> NapiProjectHash {
+    let _call = crate::telemetry::call("hash_project");
+    let root = PathBuf::from(&project_root);
+    let matchers: Vec<(regex::Regex, bool)> = globs
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|g| path_glob_to_regex(g).map(|re| (re, g.contains('/'))))
+        .collect();
+    let filter = DirFilter::for_project(&root, &[]);
+
+    let mut paths: Vec<(String, PathBuf)> = HASH_ROOTS
+        .iter()
+        .map(|r| root.join(r))
+        .filter(|r| r.is_dir())
+        .flat_map(|r| {
+            crate::safety::walk_dir(r)
+                .into_iter()
+                .filter_entry(|e| filter.keeps(e))
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .map(|e| e.into_path())
+        })
+        .filter_map(|path| {
+            let rel = path.strip_prefix(&root).ok()?.to_string_lossy().replace('\\', "/");
+            let name = rel.rsplit('/').next().unwrap_or(&rel);
+            let matched = matchers.is_empty()
+                || matchers.iter().any(|(re, full)| re.is_match(if *full { &rel } else { name }));
+            matched.then_some((rel, path))
+        })
+        .collect();
+    paths.sort();
+
+    let files: Vec<NapiFileHash> = paths
+        .par_iter()
+        .filter_map(|(rel, path)| {
+            let bytes = {
+                let _io = crate::telemetry::phase(crate::telemetry::Phase::Io);
+                crate::safety::read(path).ok()?
+            };
+            Some(NapiFileHash { path: rel.clone(), hash: content_hash(&bytes) })
+        })
+        .collect();
+
+    let hash = files.iter().fold(FNV_OFFSET, |h, f| {
+        let h = fnv1a64(fnv1a64(h, f.path.as_bytes()), b"\0");
+        fnv1a64(fnv1a64(h, f.hash.as_bytes()), b"\n")
+    });
+
+    NapiProjectHash {
+        project_root,
+        hash: format!("{:016x}", hash),
+        file_count: files.len() as u32,
+        files,
+    }
+}
+
+// ========== Project Snapshot ==========
+
+/// Default size bound of `project_snapshot`, in bytes of serialized JSON.
+const DEFAULT_SNAPSHOT_BUDGET: u32 = 8000;
+
+#[napi(object)]
+#[derive(serde::Serialize)]
+pub struct NapiSnapshotCount {
+    pub name: String,
+    pub count: u32,
+}
+
+#[napi(object)]
+#[derive(serde::Serialize)]
+pub struct NapiSnapshotScene {
+    pub path: String,
+    pub enabled: bool,
+}
+
+#[napi(object)]
+#[derive(serde::Serialize)]
+pub struct NapiSnapshotPackage {
+    pub name: String,
+    /// Version, or the `file:`/git reference for non-registry packages
+    pub version: String,
+}
+
+#[napi(object)]
+#[derive(serde::Serialize)]
+pub struct NapiProjectSnapshot {
+    pub project_root: String,
+    pub unity_version: Option<String>,
+    /// Scenes in EditorBuildSettings, in build order
+    pub build_scenes: Vec<NapiSnapshotScene>,
+    /// Top-level folders under Assets/ with their file counts (.meta excluded), largest first
+    pub folders: Vec<NapiSnapshotCount>,
+    /// Asset files per extension, most common first
+    pub asset_counts: Vec<NapiSnapshotCount>,
+    /// Packages/manifest.json dependencies
+    pub packages: Vec<NapiSnapshotPackage>,
+    /// C# types declared under Assets/, per kind (class, struct, enum, interface)
+    pub type_counts: Vec<NapiSnapshotCount>,
+    /// Entries dropped to stay within the budget, per section (e.g. "folders: 14 more")
+    pub omitted: Vec<String>,
+    /// Size of this snapshot as JSON, in bytes
+    pub size_bytes: u32,
+}
+
+fn editor_build_scenes(root: &Path) -> Vec<NapiSnapshotScene> {
+    let Ok(content) = common::read_unity_file(root.join("ProjectSettings").join("EditorBuildSettings.asset")) else {
+        return Vec::new();
+    };
+    BUILD_SCENE_RE
+        .captures_iter(&content)
+        .map(|c| NapiSnapshotScene { path: c[2].trim().to_string(), enabled: &c[1] == "1" })
+        .collect()
+}
+
+fn sorted_counts(counts: std::collections::HashMap<String, u32>) -> Vec<NapiSnapshotCount> {
+    let mut counts: Vec<NapiSnapshotCount> = counts.into_iter().map(|(name, count)| NapiSnapshotCount { name, count }).collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    counts
+}
+
+/// One bounded overview of a Unity project for an agent's context at session start: Unity
+/// version, build scenes, top-level Assets/ layout, asset counts per extension, packages and
+/// C# type counts. `budget` caps the serialized size in bytes (default 8000); when exceeded,
+/// folders, then packages, then asset counts, then build scenes are cut from the end and the
+/// dropped counts are listed in `omitted`.
+#[napi]
+pub fn project_snapshot(project_root: String, budget: Option<u32>) -> NapiProjectSnapshot {
+    let _call = crate::telemetry::call("project_snapshot");
+    let root = PathBuf::from(&project_root);
+    let budget = budget.unwrap_or(DEFAULT_SNAPSHOT_BUDGET) as usize;
+
+    let unity_version = crate::safety::read_to_string(root.join("ProjectSettings").join("ProjectVersion.txt"))
+        .ok()
+        .and_then(|text| text.lines().find_map(|l| l.strip_prefix("m_EditorVersion:").map(|v| v.trim().to_string())));
+
+    let mut folders: std::collections::HashMap<String, u32> = std::collections::HashMap::new();
+    let mut extensions: std::collections::HashMap<String, u32> = std::collections::HashMap::new();
+    let assets = root.join("Assets");
+    let filter = DirFilter::for_project(&root, &[]);
+    for entry in crate::safety::walk_dir(&assets)
+        .into_iter()
+        .filter_entry(|e| filter.keeps(e))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        let ext = path.extension().map(|e| format!(".{}", e.to_string_lossy().to_lowercase())).unwrap_or_default();
+        if ext == ".meta" {
+            continue;
+        }
+        *extensions.entry(if ext.is_empty() { "(none)".to_string() } else { ext }).or_insert(0) += 1;
+        let mut components = path.strip_prefix(&assets).ok().into_iter().flat_map(|rel| rel.components());
+        if let (Some(top), Some(_)) = (components.next(), components.next()) {
+            *folders.entry(format!("Assets/{}", top.as_os_str().to_string_lossy())).or_insert(0) += 1;
+        }
+    }
+
+    let packages = crate::safety::read_to_string(root.join("Packages").join("manifest.json"))
+        .ok()
+        .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+        .and_then(|manifest| manifest.get("dependencies").and_then(|d| d.as_object()).cloned())
+        .map(|deps| deps.into_iter()
+            .map(|(name, version)| NapiSnapshotPackage { name, version: version.as_str().unwrap_or_default().to_string() })
+            .collect())
+        .unwrap_or_default();
+
+    let mut kinds: std::collections::HashMap<String, u32> = std::collections::HashMap::new();
+    for ty in crate::csharp::build_type_registry(project_root.clone(), Some(false), Some(false)) {
+        *kinds.entry(ty.kind).or_insert(0) += 1;
+    }
+
+    let mut snapshot = NapiProjectSnapshot {
+        project_root,
+        unity_version,
+        build_scenes: editor_build_scenes(&root),
+        folders: sorted_counts(folders),
+        asset_counts: sorted_counts(extensions),
+        packages,
+        type_counts: sorted_counts(kinds),
+        omitted: Vec::new(),
+        size_bytes: 0,
+    };
+
+    let size = |s: &NapiProjectSnapshot| serde_json::to_string(s).map_or(0, |json| json.len());
+    let sections = ["folders", "packages", "asset_counts", "build_scenes"];
+    let mut dropped = [0u32; 4];
+    // Measured with the widest size_bytes so the final value cannot push it over the budget
+    snapshot.size_bytes = u32::MAX;
+    while size(&snapshot) > budget {
+        let removed = if snapshot.folders.pop().is_some() { 0 }
+            else if snapshot.packages.pop().is_some() { 1 }
+            else if snapshot.asset_counts.pop().is_some() { 2 }
+            else if snapshot.build_scenes.pop().is_some() { 3 }
+            else { break };
+        dropped[removed] += 1;
+        snapshot.omitted = sections.iter()
+            .zip(dropped)
+            .filter(|(_, n)| *n > 0)
+            .map(|(section, n)| format!("{}: {} more", section, n))
+            .collect();
+    }
+    // Twice, since the first value changes the digit count of the field itself
+    snapshot.size_bytes = size(&snapshot) as u32;
+    snapshot.size_bytes = size(&snapshot) as u32;
+    snapshot
+}
+
 // ========== Tests ==========
 
 #[cfg(test)]
@@ -554,6 +1288,7 @@ mod tests {
             tmp.path().to_string_lossy().to_string(),
             vec![".cs".to_string()],
             None,
+            None,
         );
         assert!(files.len() >= 2, "Expected at least 2 .cs files, got {}", files.len());
         assert!(files.iter().any(|f| f.contains("Player.cs")));
@@ -567,6 +1302,7 @@ mod tests {
             tmp.path().to_string_lossy().to_string(),
             vec![".cs".to_string()],
             None,
+            None,
         );
         assert!(
             !files.iter().any(|f| f.contains("Library")),
@@ -581,6 +1317,7 @@ mod tests {
             tmp.path().to_string_lossy().to_string(),
             vec![".asset".to_string()],
             None,
+            None,
         );
         assert!(
             files.iter().any(|f| f.contains("TagManager.asset")),
@@ -594,10 +1331,69 @@ mod tests {
             "/nonexistent/path/12345".to_string(),
             vec![".cs".to_string()],
             None,
+            None,
         );
         assert!(files.is_empty());
     }
 
+    #[test]
+    fn test_walk_include_packages() {
+        let tmp = create_temp_project();
+        let embedded = tmp.path().join("Packages").join("com.test.embedded");
+        fs::create_dir_all(embedded.join("Runtime")).unwrap();
+        fs::write(embedded.join("package.json"), "{}").unwrap();
+        fs::write(embedded.join("Runtime").join("Embedded.cs"), "class Embedded {}").unwrap();
+
+        let local = tmp.path().join("LocalPackages").join("com.test.local");
+        fs::create_dir_all(&local).unwrap();
+        fs::write(local.join("Local.cs"), "class Local {}").unwrap();
+        fs::write(
+            tmp.path().join("Packages").join("manifest.json"),
+            r#"{"dependencies": {"com.test.local": "file:../LocalPackages/com.test.local", "com.unity.ugui": "1.0.0"}}"#,
+        )
+        .unwrap();
+
+        let project = tmp.path().to_string_lossy().to_string();
+        let default_files = walk_project_files(project.clone(), vec![".cs".to_string()], None, None);
+        assert!(!default_files.iter().any(|f| f.contains("Embedded.cs")));
+
+        let files = walk_project_files(
+            project,
+            vec![".cs".to_string()],
+            None,
+            Some(NapiWalkOptions { include_packages: Some(true), follow_symlinks: None }),
+        );
+        assert!(files.iter().any(|f| f.ends_with("Embedded.cs")));
+        assert!(files.iter().any(|f| f.ends_with("Local.cs")));
+        assert!(files.iter().any(|f| f.ends_with("Player.cs")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_follow_symlinks_handles_loops() {
+        let tmp = create_temp_project();
+        let scripts = tmp.path().join("Assets").join("Scripts");
+        // Link back to an ancestor: a loop that must not hang the walk
+        std::os::unix::fs::symlink(tmp.path().join("Assets"), scripts.join("Loop")).unwrap();
+        let shared = tmp.path().join("Shared");
+        fs::create_dir_all(&shared).unwrap();
+        fs::write(shared.join("Shared.cs"), "class Shared {}").unwrap();
+        std::os::unix::fs::symlink(&shared, tmp.path().join("Assets").join("SharedLink")).unwrap();
+
+        let project = tmp.path().to_string_lossy().to_string();
+        let unfollowed = walk_project_files(project.clone(), vec![".cs".to_string()], None, None);
+        assert!(!unfollowed.iter().any(|f| f.ends_with("Shared.cs")));
+
+        let files = walk_project_files(
+            project,
+            vec![".cs".to_string()],
+            None,
+            Some(NapiWalkOptions { include_packages: None, follow_symlinks: Some(true) }),
+        );
+        assert!(files.iter().any(|f| f.ends_with("Shared.cs")));
+        assert_eq!(files.iter().filter(|f| f.ends_with("Player.cs")).count(), 1);
+    }
+
     #[test]
     fn test_yaml_extension_map_includes_unity_formats() {
         let exts = extension_map("yaml");
@@ -646,12 +1442,71 @@ mod tests {
             file_type: Some("yaml".to_string()),
             max_results: None,
             context_lines: None,
+            max_file_size: None,
+            extensions: None,
+            budget: None,
         });
         assert!(result.success);
         assert!(result.total_files_scanned > 0, "yaml type should scan .unity files");
         assert!(result.total_matches >= 1, "Should find match in .unity file");
     }
 
+    #[test]
+    fn test_replace_property_values_dry_run_then_write() {
+        let tmp = create_temp_project();
+        let assets = tmp.path().join("Assets");
+        let old_guid = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let new_guid = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+        let prefab = format!(
+            "%YAML 1.1\n--- !u!23 &200\nMeshRenderer:\n  m_Materials:\n  - {{fileID: 2100000, guid: {old_guid}, type: 2}}\n"
+        );
+        fs::write(assets.join("Cube.prefab"), &prefab).unwrap();
+        let project = tmp.path().to_string_lossy().to_string();
+
+        let preview = replace_property_values(project.clone(), "m_Materials".into(), old_guid.into(), new_guid.into(), None);
+        assert!(preview.success && preview.dry_run);
+        assert_eq!(preview.files_changed, 1);
+        assert_eq!(preview.total_changes, 1);
+        assert!(preview.files[0].diff.contains(&format!("+  - {{fileID: 2100000, guid: {new_guid}, type: 2}}")));
+        assert_eq!(fs::read_to_string(assets.join("Cube.prefab")).unwrap(), prefab);
+
+        let applied = replace_property_values(project, "m_Materials".into(), old_guid.into(), new_guid.into(), Some(false));
+        assert!(applied.success);
+        assert_eq!(fs::read_to_string(assets.join("Cube.prefab")).unwrap(), prefab.replace(old_guid, new_guid));
+    }
+
+    #[test]
+    fn test_replace_property_values_keeps_crlf() {
+        let tmp = create_temp_project();
+        let path = tmp.path().join("Assets/Crate.prefab");
+        let prefab = "%YAML 1.1\r\n--- !u!1 &100\r\nGameObject:\r\n  m_Layer: 3\r\n  m_Name: Crate\r\n";
+        fs::write(&path, prefab).unwrap();
+
+        let applied = replace_property_values(tmp.path().to_string_lossy().to_string(), "m_Layer".into(), "3".into(), "8".into(), Some(false));
+        assert_eq!(applied.total_changes, 1);
+        assert_eq!(applied.files[0].changes[0].new_line, "  m_Layer: 8");
+        assert_eq!(fs::read_to_string(&path).unwrap(), prefab.replace("m_Layer: 3", "m_Layer: 8"));
+    }
+
+    #[test]
+    fn test_replace_property_values_encodes_values_and_edits_large_files() {
+        let tmp = create_temp_project();
+        let path = tmp.path().join("Assets/Big.prefab");
+        // Larger than grep's default file size limit
+        let filler = "  m_Name: Filler\n".repeat(700_000);
+        let prefab = format!("%YAML 1.1\n--- !u!1 &100\nGameObject:\n  m_Name: Crate\n--- !u!1 &101\nGameObject:\n{filler}");
+        fs::write(&path, &prefab).unwrap();
+        let project = tmp.path().to_string_lossy().to_string();
+
+        let applied = replace_property_values(project.clone(), "m_Name".into(), "Crate".into(), "Crate: Old #2".into(), Some(false));
+        assert!(applied.success, "{:?}", applied.error);
+        assert_eq!(applied.files[0].changes[0].new_line, "  m_Name: 'Crate: Old #2'");
+
+        let applied = replace_property_values(project, "m_Name".into(), "'Crate: Old #2'".into(), r#"{"x": 1, "y": 0.5}"#.into(), Some(false));
+        assert_eq!(applied.files[0].changes[0].new_line, "  m_Name: {x: 1, y: 0.5}");
+        assert!(fs::read_to_string(&path).unwrap().starts_with("%YAML 1.1\n--- !u!1 &100\nGameObject:\n  m_Name: {x: 1, y: 0.5}\n"));
+    }
+
     #[test]
     fn test_grep_finds_pattern() {
         let tmp = create_temp_project();
@@ -661,6 +1516,9 @@ mod tests {
             file_type: Some("cs".to_string()),
             max_results: None,
             context_lines: None,
+            max_file_size: None,
+            extensions: None,
+            budget: None,
         });
         assert!(result.success);
         assert!(result.total_matches >= 2, "Expected matches in Player.cs and Enemy.cs");
@@ -675,6 +1533,9 @@ mod tests {
             file_type: Some("cs".to_string()),
             max_results: None,
             context_lines: Some(1),
+            max_file_size: None,
+            extensions: None,
+            budget: None,
         });
         assert!(result.success);
         assert!(!result.matches.is_empty());
@@ -692,10 +1553,113 @@ mod tests {
             file_type: Some("all".to_string()),
             max_results: Some(2),
             context_lines: None,
+            max_file_size: None,
+            extensions: None,
+            budget: None,
         });
         assert!(result.success);
         assert!(result.matches.len() <= 2);
         assert!(result.truncated);
+        assert!(!result.budget_exhausted);
+    }
+
+    #[test]
+    fn test_grep_budget_returns_partial_results() {
+        let tmp = create_temp_project();
+        let scripts = tmp.path().join("Assets").join("Scripts");
+        for i in 0..5 {
+            fs::write(scripts.join(format!("Needle{}.cs", i)), "// needle\n").unwrap();
+        }
+        let grep = |budget: ResourceBudget| grep_project(NapiGrepOptions {
+            project_path: tmp.path().to_string_lossy().to_string(),
+            pattern: "needle".to_string(),
+            file_type: Some("cs".to_string()),
+            max_results: None,
+            context_lines: None,
+            max_file_size: None,
+            extensions: Some(vec![".cs".to_string()]),
+            budget: Some(budget),
+        });
+
+        let result = grep(ResourceBudget { max_files: Some(2), ..Default::default() });
+        assert!(result.success);
+        assert!(result.budget_exhausted);
+        assert_eq!(result.total_files_scanned, 2);
+        assert!(result.total_matches <= 2);
+
+        let result = grep(ResourceBudget { max_files: Some(100), max_bytes: Some(1 << 20), max_ms: Some(60_000) });
+        assert!(!result.budget_exhausted);
+        assert_eq!(result.total_matches, 5);
+    }
+
+    #[test]
+    fn test_grep_skips_large_and_binary_files() {
+        let tmp = create_temp_project();
+        let scripts = tmp.path().join("Assets").join("Scripts");
+        fs::write(scripts.join("Big.cs"), format!("// needle\n{}", "x".repeat(4096))).unwrap();
+        fs::write(scripts.join("Blob.cs"), b"needle\0\x01\x02").unwrap();
+        fs::write(scripts.join("Small.cs"), "// needle\n").unwrap();
+
+        let result = grep_project(NapiGrepOptions {
+            project_path: tmp.path().to_string_lossy().to_string(),
+            pattern: "needle".to_string(),
+            file_type: Some("cs".to_string()),
+            max_results: None,
+            context_lines: None,
+            max_file_size: Some(1024),
+            extensions: None,
+            budget: None,
+        });
+        assert!(result.success);
+        assert_eq!(result.total_matches, 1);
+        assert!(result.matches[0].file.ends_with("Small.cs"));
+        assert_eq!(result.skipped_large_files, 1);
+        assert_eq!(result.skipped_binary_files, 1);
+    }
+
+    #[test]
+    fn test_grep_custom_file_type_groups() {
+        let tmp = create_temp_project();
+        let shaders = tmp.path().join("Assets").join("Shaders");
+        fs::create_dir_all(&shaders).unwrap();
+        fs::write(shaders.join("Water.shader"), "Shader \"Water\" { float4 _Tint; }\n").unwrap();
+        fs::write(shaders.join("Noise.HLSL"), "float noise(float2 uv);\n").unwrap();
+        fs::create_dir_all(tmp.path().join(".unity-agentic")).unwrap();
+        fs::write(
+            tmp.path().join(".unity-agentic").join("config.json"),
+            r#"{"file_types": {"shaders": [".shader", "hlsl", ".shadergraph"]}}"#,
+        )
+        .unwrap();
+        let project_path = tmp.path().to_string_lossy().to_string();
+
+        let groups = list_file_type_groups(Some(project_path.clone()));
+        assert_eq!((groups[0].name.as_str(), groups[0].custom), ("shaders", true));
+        assert!(groups[0].extensions.contains(&".hlsl".to_string()));
+        assert!(groups.iter().any(|g| g.name == "yaml" && !g.custom));
+
+        let grep = |file_type: Option<&str>, extensions: Option<Vec<String>>| grep_project(NapiGrepOptions {
+            project_path: project_path.clone(),
+            pattern: "float|health".to_string(),
+            file_type: file_type.map(str::to_string),
+            max_results: None,
+            context_lines: None,
+            max_file_size: None,
+            extensions,
+            budget: None,
+        });
+        let result = grep(Some("shaders"), None);
+        assert_eq!(result.total_matches, 2);
+        assert!(result.matches.iter().all(|m| m.file_type.as_deref() == Some("shaders")));
+
+        let result = grep(None, Some(vec![".cs".to_string(), ".hlsl".to_string()]));
+        let mut labeled: Vec<(String, Option<String>)> = result.matches.iter()
+            .map(|m| (Path::new(&m.file).file_name().unwrap().to_string_lossy().to_string(), m.file_type.clone()))
+            .collect();
+        labeled.sort();
+        assert_eq!(labeled, vec![
+            ("Enemy.cs".to_string(), Some("cs".to_string())),
+            ("Noise.HLSL".to_string(), Some("shaders".to_string())),
+        ]);
     }
 
     #[test]
@@ -707,6 +1671,9 @@ mod tests {
             file_type: None,
             max_results: None,
             context_lines: None,
+            max_file_size: None,
+            extensions: None,
+            budget: None,
         });
         assert!(!result.success);
         assert!(result.error.as_ref().unwrap().contains("Invalid regex"));
@@ -741,6 +1708,129 @@ mod tests {
 
     // ===== Tests against external fixtures (if available) =====
 
+    #[test]
+    fn test_hash_asset_ignores_line_endings_and_volatile_fields() {
+        let tmp = tempfile::tempdir().unwrap();
+        let a = tmp.path().join("a.meta");
+        let b = tmp.path().join("b.meta");
+        fs::write(&a, "fileFormatVersion: 2\nguid: 0123\ntimeCreated: 1500000000\n").unwrap();
+        fs::write(&b, "\u{feff}fileFormatVersion: 2\r\nguid: 0123\r\ntimeCreated: 1700000000\r\n").unwrap();
+        let hash = hash_asset(a.to_string_lossy().to_string()).unwrap();
+        assert_eq!(hash.len(), 16);
+        assert_eq!(Some(hash.clone()), hash_asset(b.to_string_lossy().to_string()));
+
+        fs::write(&b, "fileFormatVersion: 2\nguid: 4567\n").unwrap();
+        assert_ne!(Some(hash), hash_asset(b.to_string_lossy().to_string()));
+        assert!(hash_asset(tmp.path().join("missing").to_string_lossy().to_string()).is_none());
+    }
+
+    #[test]
+    fn test_hash_project_globs_and_change_detection() {
+        let tmp = create_temp_project();
+        let root = tmp.path().to_string_lossy().to_string();
+        let all = hash_project(root.clone(), None);
+        assert!(all.files.iter().any(|f| f.path == "ProjectSettings/TagManager.asset"));
+        assert!(all.files.iter().all(|f| !f.path.starts_with("Library/")));
+
+        let scripts = hash_project(root.clone(), Some(vec!["Assets/**/*.cs".to_string()]));
+        let paths: Vec<&str> = scripts.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["Assets/Scripts/Enemy.cs", "Assets/Scripts/Player.cs"]);
+        assert_eq!(hash_project(root.clone(), Some(vec!["*.cs".to_string()])).hash, scripts.hash);
+
+        fs::write(tmp.path().join("ProjectSettings").join("TagManager.asset"), "changed").unwrap();
+        assert_eq!(hash_project(root.clone(), Some(vec!["Assets/**/*.cs".to_string()])).hash, scripts.hash);
+        assert_ne!(hash_project(root, None).hash, all.hash);
+    }
+
+    #[test]
+    fn test_build_scene_pattern_reads_adjacent_lines_only() {
+        let caps = BUILD_SCENE_RE.captures("  - enabled: 1\r\n    path: Assets/Main.unity\r\n").unwrap();
+        assert_eq!((&caps[1], &caps[2]), ("1", "Assets/Main.unity"));
+        assert!(!BUILD_SCENE_RE.is_match("  - enabled: 1\n\n    path: Assets/Main.unity\n"));
+        assert!(!BUILD_SCENE_RE.is_match("  - enabled:\n    1\n    path: Assets/Main.unity\n"));
+    }
+
+    #[test]
+    fn test_project_snapshot_within_budget() {
+        let tmp = create_temp_project();
+        let settings = tmp.path().join("ProjectSettings");
+        fs::write(settings.join("ProjectVersion.txt"), "m_EditorVersion: 2022.3.10f1\nm_EditorVersionWithRevision: 2022.3.10f1 (abc)\n").unwrap();
+        fs::write(
+            settings.join("EditorBuildSettings.asset"),
+            "%YAML 1.1\n--- !u!1045 &1\nEditorBuildSettings:\n  m_Scenes:\n  - enabled: 1\n    path: Assets/Scenes/Main.unity\n    guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n  - enabled: 0\n    path: Assets/Scenes/Test.unity\n    guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n",
+        ).unwrap();
+        fs::create_dir_all(tmp.path().join("Packages")).unwrap();
+        fs::write(
+            tmp.path().join("Packages").join("manifest.json"),
+            r#"{"dependencies": {"com.unity.ugui": "1.0.0", "com.unity.textmeshpro": "3.0.6"}}"#,
+        ).unwrap();
+        let root = tmp.path().to_string_lossy().to_string();
+
+        let snapshot = project_snapshot(root.clone(), None);
+        assert_eq!(snapshot.unity_version.as_deref(), Some("2022.3.10f1"));
+        let scenes: Vec<(&str, bool)> = snapshot.build_scenes.iter().map(|s| (s.path.as_str(), s.enabled)).collect();
+        assert_eq!(scenes, vec![("Assets/Scenes/Main.unity", true), ("Assets/Scenes/Test.unity", false)]);
+        assert_eq!((snapshot.folders[0].name.as_str(), snapshot.folders[0].count), ("Assets/Scripts", 2));
+        assert!(snapshot.asset_counts.iter().all(|c| c.name != ".meta"));
+        assert_eq!(snapshot.packages.len(), 2);
+        assert_eq!(snapshot.type_counts[0].name, "class");
+        assert!(snapshot.omitted.is_empty());
+
+        let tight = project_snapshot(root, Some(snapshot.size_bytes - 60));
+        assert!(tight.size_bytes <= snapshot.size_bytes - 60);
+        assert!(tight.omitted.iter().any(|o| o.starts_with("folders: ")));
+        assert_eq!(tight.build_scenes.len(), 2, "build scenes are cut last");
+    }
+
+    #[test]
+    fn test_config_skip_and_include_dirs_apply_to_all_walks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let assets = tmp.path().join("Assets");
+        for (i, (dir, file)) in [
+            ("Scripts", "Player.cs"),
+            ("ThirdParty/Vendor", "Vendor.cs"),
+            ("StreamingAssets", "Data.cs"),
+            ("Plugins/Native/obj", "Generated.cs"),
+            ("Tools/obj", "Stale.cs"),
+        ].into_iter().enumerate() {
+            let dir = assets.join(dir);
+            fs::create_dir_all(&dir).unwrap();
+            let name = file.trim_end_matches(".cs");
+            fs::write(dir.join(file), format!("public class {} : MonoBehaviour {{}}", name)).unwrap();
+            fs::write(dir.join(format!("{}.meta", file)), format!("guid: {:032x}\n", i + 1)).unwrap();
+        }
+        let config_dir = tmp.path().join(".unity-agentic");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(
+            config_dir.join("config.json"),
+            r#"{"skip_dirs": ["Assets/ThirdParty", "Streaming*"], "include_dirs": ["Assets/Plugins/**/obj"]}"#,
+        ).unwrap();
+        let root = tmp.path().to_string_lossy().to_string();
+
+        let names = |paths: Vec<String>| -> Vec<String> {
+            let mut names: Vec<String> = paths.iter()
+                .map(|p| Path::new(p).file_name().unwrap().to_string_lossy().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        let expected = vec!["Generated.cs".to_string(), "Player.cs".to_string()];
+        assert_eq!(names(walk_project_files(root.clone(), vec![".cs".to_string()], None, None)), expected);
+        let cache = build_guid_cache(root.clone());
+        assert_eq!(names(cache.as_object().unwrap().values().map(|v| v.as_str().unwrap().to_string()).collect()), expected);
+        let types = crate::csharp::build_type_registry(root.clone(), None, None);
+        assert_eq!(names(types.iter().map(|t| t.file_path.clone()).collect()), expected);
+
+        let scripts = walk_project_files(root, vec![".cs".to_string()], Some(vec!["Scripts".to_string()]), None);
+        assert_eq!(names(scripts), vec!["Generated.cs".to_string()]);
+
+        let global = tmp.path().join("global.json");
+        fs::write(&global, r#"{"skip_dirs": ["Plugins"]}"#).unwrap();
+        let filter = DirFilter::with_global_config(tmp.path(), Some(&global), &[]);
+        assert!(!filter.allows(&assets.join("Plugins")));
+        assert!(filter.allows(&assets.join("Scripts")));
+    }
+
     #[test]
     fn test_walk_external_fixtures_cs() {
         let fixtures = fixtures_path();
@@ -751,6 +1841,7 @@ mod tests {
             fixtures.to_string_lossy().to_string(),
             vec![".cs".to_string()],
             None,
+            None,
         );
         assert!(files.len() >= 5, "External fixtures should have 5+ .cs files");
         assert!(files.iter().any(|f| f.contains("GameManager.cs")));
@@ -768,6 +1859,9 @@ mod tests {
             file_type: Some("asset".to_string()),
             max_results: None,
             context_lines: None,
+            max_file_size: None,
+            extensions: None,
+            budget: None,
         });
         assert!(result.success);
         assert!(result.total_matches >= 1);